@@ -0,0 +1,153 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! End-to-end pipeline benchmark
+//!
+//! Measures every stage a config passes through between loading and a
+//! write, each on its own, for generated configs of three sizes:
+//!
+//! 1. **parse**: config text to bindings
+//! 2. **conflicts**: indexing the bindings and listing the conflicts
+//! 3. **validate**: injection checks and danger assessment
+//! 4. **rebuild**: writing one edited binding back into the config text
+//! 5. **diff**: comparing the bindings before and after the edit
+//!
+//! Run with `cargo bench --bench pipeline`. CI runs it on the base branch
+//! and on the change, and `scripts/bench-budget.sh` fails the build when a
+//! stage got slower than the budget allows.
+
+use std::{hint::black_box, path::Path, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hypr_keybind_manager::{
+    config::{validator::ConfigValidator, ConfigManager},
+    core::{compare::compare_bindings, parser::parse_config_file, ConflictDetector, Keybinding},
+};
+
+/// Fixture sizes, in bind lines
+const SIZES: [(&str, usize); 3] = [("small", 50), ("medium", 500), ("huge", 5000)];
+
+/// A config with `count` bind lines, shaped like real ones: variables,
+/// commented groups, a submap section, `bindd` descriptions, launchers and
+/// a few conflicting combos
+fn fixture(count: usize) -> String {
+    let keys = ["Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P", "A", "S"];
+    let programs = [
+        "kitty",
+        "firefox",
+        "thunar",
+        "wofi --show drun",
+        "grim -g area",
+    ];
+
+    let mut config = String::from(
+        "# Generated benchmark fixture\n\
+         $mainMod = SUPER\n\
+         $term = kitty\n\n\
+         general {\n    gaps_in = 5\n    border_size = 2\n}\n\n",
+    );
+    for i in 0..count {
+        if i % 50 == 0 {
+            config.push_str(&format!("\n# Group {}\n", i / 50));
+        }
+        let key = format!("{}{}", keys[i % keys.len()], i / keys.len());
+        let line = match i % 5 {
+            0 => format!(
+                "bind = $mainMod, {}, exec, {}\n",
+                key,
+                programs[i % programs.len()]
+            ),
+            1 => format!(
+                "bind = $mainMod SHIFT, {}, workspace, {}\n",
+                key,
+                i % 10 + 1
+            ),
+            2 => format!("binde = $mainMod ALT, {}, resizeactive, 10 0\n", key),
+            3 => format!("bindd = CTRL, {}, Focus left, movefocus, l\n", key),
+            _ => format!("bind = $mainMod, {}, togglefloating\n", key),
+        };
+        config.push_str(&line);
+
+        // Every hundredth binding clashes with the one before
+        if i % 100 == 99 {
+            config.push_str(&format!("bind = $mainMod, {}, killactive\n", key));
+        }
+    }
+    config.push_str(
+        "\nsubmap = resize\n\
+         binde = , right, resizeactive, 10 0\n\
+         binde = , left, resizeactive, -10 0\n\
+         bind = , escape, submap, reset\n\
+         submap = reset\n",
+    );
+    config
+}
+
+/// The bindings with one in the middle edited, as the GUI would
+fn edited(bindings: &[Keybinding]) -> Vec<Keybinding> {
+    let mut edited = bindings.to_vec();
+    let middle = edited.len() / 2;
+    edited[middle].args = Some("edited".to_string());
+    edited
+}
+
+// Without its fixture there is nothing to measure
+#[allow(clippy::expect_used)]
+fn pipeline(c: &mut Criterion) {
+    let validator = ConfigValidator::new();
+
+    for (name, count) in SIZES {
+        let config = fixture(count);
+        let path = Path::new("hyprland.conf");
+        let bindings = parse_config_file(&config, path).expect("fixture parses");
+        let after = edited(&bindings);
+
+        let mut group = c.benchmark_group(name);
+        group.throughput(Throughput::Elements(bindings.len() as u64));
+
+        group.bench_function(BenchmarkId::new("parse", count), |b| {
+            b.iter(|| parse_config_file(black_box(&config), path))
+        });
+        group.bench_function(BenchmarkId::new("conflicts", count), |b| {
+            b.iter(|| {
+                let mut detector = ConflictDetector::new();
+                for binding in black_box(&bindings) {
+                    detector.add_binding(binding.clone());
+                }
+                detector.find_conflicts()
+            })
+        });
+        group.bench_function(BenchmarkId::new("validate", count), |b| {
+            b.iter(|| validator.validate_bindings(black_box(&bindings)))
+        });
+        group.bench_function(BenchmarkId::new("rebuild", count), |b| {
+            b.iter(|| ConfigManager::rebuild_config(black_box(&config), black_box(&after)))
+        });
+        group.bench_function(BenchmarkId::new("diff", count), |b| {
+            b.iter(|| compare_bindings(black_box(&bindings), black_box(&after)))
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default()
+        .sample_size(30)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(3));
+    targets = pipeline
+}
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Build script: generates the feature topics of the help window
+//!
+//! The "Features in Detail" sections of README.md are split into one
+//! `(title, body)` pair per `###` heading and written to
+//! `$OUT_DIR/help_features.rs`, which `core::help` includes. The README
+//! stays the single place the features are described, and the help window
+//! can never show an older version of it.
+
+use std::{env, fs, io, path::Path};
+
+/// The README section holding the feature descriptions
+const FEATURES_HEADING: &str = "## Features in Detail";
+
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-changed=README.md");
+
+    let readme = fs::read_to_string("README.md")?;
+    let sections = feature_sections(&readme);
+
+    let mut generated = String::from("/// `(title, body)` of each README feature section\n");
+    generated.push_str("pub static FEATURE_SECTIONS: &[(&str, &str)] = &[\n");
+    for (title, body) in &sections {
+        // `Debug` quotes and escapes a string as a Rust literal
+        generated.push_str(&format!("    ({:?}, {:?}),\n", title, body));
+    }
+    generated.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").map_err(io::Error::other)?;
+    fs::write(Path::new(&out_dir).join("help_features.rs"), generated)
+}
+
+/// Splits the features section into `###` subsections of plain text
+fn feature_sections(readme: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, Vec<String>)> = Vec::new();
+    let mut in_features = false;
+    let mut in_code = false;
+
+    for line in readme.lines() {
+        if line.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if !in_code && line.starts_with("## ") {
+            in_features = line.trim() == FEATURES_HEADING;
+            continue;
+        }
+        if !in_features {
+            continue;
+        }
+        if let Some(title) = line.strip_prefix("### ").filter(|_| !in_code) {
+            sections.push((plain_text(title), Vec::new()));
+            continue;
+        }
+        // Table rules (`|---|---|`) carry no text
+        if !line.is_empty() && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')) {
+            continue;
+        }
+        if let Some((_, body)) = sections.last_mut() {
+            body.push(if in_code {
+                format!("    {}", line)
+            } else {
+                plain_text(line)
+            });
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(title, body)| (title, body.join("\n").trim().to_string()))
+        .collect()
+}
+
+/// Removes Markdown emphasis, code marks and link targets, which a label
+/// would show literally
+fn plain_text(line: &str) -> String {
+    let line = line.replace("**", "").replace('`', "");
+
+    // [text](url) -> text
+    let mut plain = String::with_capacity(line.len());
+    let mut rest = line.as_str();
+    while let Some(open) = rest.find('[') {
+        let link = rest[open..].find("](").and_then(|middle| {
+            let close = rest[open + middle..].find(')')?;
+            Some((open + middle, open + middle + close))
+        });
+        let Some((middle, close)) = link else {
+            break;
+        };
+        plain.push_str(&rest[..open]);
+        plain.push_str(&rest[open + 1..middle]);
+        rest = &rest[close + 1..];
+    }
+    plain.push_str(rest);
+    plain
+}
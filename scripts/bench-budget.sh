@@ -0,0 +1,51 @@
+#!/usr/bin/env bash
+# Fail when a pipeline benchmark stage got slower than the budget allows
+#
+# Reads criterion's comparison against a saved baseline, so run both first:
+#   cargo bench --bench pipeline -- --save-baseline base   (on the base branch)
+#   cargo bench --bench pipeline -- --baseline base        (on the change)
+#
+# Usage: scripts/bench-budget.sh [budget-percent]   (default: 15)
+#
+# A stage fails when even the lower bound of its confidence interval is
+# slower than the budget, so runner noise alone does not fail the build.
+
+set -e
+
+BUDGET="${1:-15}"
+CRITERION_DIR="target/criterion"
+
+if ! command -v jq >/dev/null; then
+    echo "❌ Error: jq is required"
+    exit 1
+fi
+
+CHANGES=$(find "$CRITERION_DIR" -path '*/change/estimates.json' 2>/dev/null | sort)
+if [ -z "$CHANGES" ]; then
+    echo "⚠️  No baseline comparison found (the base branch has no pipeline benchmark yet)"
+    exit 0
+fi
+
+echo "📊 Pipeline stages against the baseline (budget: +${BUDGET}%)"
+
+FAILED=0
+for estimates in $CHANGES; do
+    # target/criterion/<size>/<stage>/<bindings>/change/estimates.json
+    STAGE=$(dirname "$(dirname "${estimates#"$CRITERION_DIR"/}")")
+    CHANGE=$(jq '.median.point_estimate * 100' "$estimates")
+    LOWER=$(jq '.median.confidence_interval.lower_bound * 100' "$estimates")
+
+    if awk -v lower="$LOWER" -v budget="$BUDGET" 'BEGIN { exit !(lower > budget) }'; then
+        printf "  ❌ %-24s %+7.1f%%\n" "$STAGE" "$CHANGE"
+        FAILED=1
+    else
+        printf "  ✅ %-24s %+7.1f%%\n" "$STAGE" "$CHANGE"
+    fi
+done
+
+if [ "$FAILED" -ne 0 ]; then
+    echo "❌ Performance budget exceeded"
+    exit 1
+fi
+
+echo "✨ All stages within budget"
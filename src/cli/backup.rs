@@ -0,0 +1,128 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/backup.rs
+//!
+//! `backup`: maintenance of the config backups
+
+use clap::Subcommand;
+use colored::*;
+use hypr_keybind_manager::config::{backup::BYTES_PER_MB, ConfigManager};
+use std::path::PathBuf;
+
+use super::{load_settings, resolve_config};
+
+#[derive(Subcommand)]
+pub enum BackupAction {
+    /// Delete the backups the retention policy does not keep
+    ///
+    /// Limits default to the backup preferences; 0 turns a limit off. The
+    /// newest backup and the pristine backup are always kept.
+    Prune {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Most backups kept
+        #[arg(long, value_name = "COUNT")]
+        max_count: Option<usize>,
+
+        /// Oldest backup kept, in days
+        #[arg(long, value_name = "DAYS")]
+        max_age: Option<u32>,
+
+        /// Most space the backups may take together, in megabytes
+        #[arg(long, value_name = "MB")]
+        max_size: Option<u64>,
+
+        /// List the backups that would be deleted without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Runs a `backup` subcommand.
+///
+/// `prune` deletes the backups the retention policy does not keep, the
+/// preferences' policy with the limits given as flags replaced.
+///
+/// # Arguments
+///
+/// * `action` - Subcommand and its arguments
+///
+/// # Returns
+///
+/// * `Ok(())` - Backups pruned (or listed, with `--dry-run`)
+/// * `Err(_)` - Config not found, or a backup could not be listed or deleted
+pub fn manage_backups(action: BackupAction) -> anyhow::Result<()> {
+    match action {
+        BackupAction::Prune {
+            config,
+            max_count,
+            max_age,
+            max_size,
+            dry_run,
+        } => {
+            let config = resolve_config(config)?;
+            let path = PathBuf::from(
+                shellexpand::tilde(
+                    config
+                        .to_str()
+                        .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+                )
+                .as_ref(),
+            );
+            let manager = ConfigManager::new(path)?;
+
+            // Flags override the preferences; 0 turns a limit off
+            let mut policy = load_settings().backup_retention;
+            if let Some(count) = max_count {
+                policy.max_count = (count > 0).then_some(count);
+            }
+            if let Some(days) = max_age {
+                policy.max_age_days = (days > 0).then_some(days);
+            }
+            if let Some(megabytes) = max_size {
+                policy.max_total_bytes =
+                    (megabytes > 0).then_some(megabytes.saturating_mul(BYTES_PER_MB));
+            }
+
+            let backups = if dry_run {
+                manager.backups_to_prune(&policy)?
+            } else {
+                manager.prune_backups(&policy)?
+            };
+            if backups.is_empty() {
+                println!("{} No backups to prune", "✓".green());
+                return Ok(());
+            }
+
+            for backup in &backups {
+                println!("  {} {}", "−".red(), backup.display());
+            }
+            println!(
+                "{} {} {} backup{}",
+                "✓".green(),
+                if dry_run { "Would delete" } else { "Deleted" },
+                backups.len(),
+                if backups.len() == 1 { "" } else { "s" }
+            );
+            if dry_run {
+                println!("\n{}", "Dry run: nothing was deleted".dimmed());
+            }
+        }
+    }
+
+    Ok(())
+}
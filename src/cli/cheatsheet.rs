@@ -0,0 +1,68 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/cheatsheet.rs
+//!
+//! `cheatsheet`: printable overviews of the bindings
+
+use colored::*;
+use hypr_keybind_manager::core::{cheatsheet::CheatSheetFormat, parser::parse_config_with_sources};
+use std::{fs, path::Path};
+
+/// Renders a cheat sheet of the config's bindings, sourced files included.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `format` - Markdown or HTML; `None` picks it by the output file's
+///   extension (Markdown when printing)
+/// * `output` - File to write; `None` prints the cheat sheet
+///
+/// # Returns
+///
+/// * `Err(_)` - The config cannot be read or parsed, or the file not written
+pub fn write_cheat_sheet(
+    config_path: &Path,
+    format: Option<CheatSheetFormat>,
+    output: Option<&Path>,
+) -> anyhow::Result<()> {
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, path)?;
+
+    let format =
+        format.unwrap_or_else(|| output.map(CheatSheetFormat::from_path).unwrap_or_default());
+    let sheet = format.render(&bindings);
+    match output {
+        Some(output) => {
+            fs::write(output, sheet)
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output.display(), e))?;
+            eprintln!(
+                "{} Wrote a cheat sheet of {} bindings to {} ({})",
+                "✓".green(),
+                bindings.len(),
+                output.display(),
+                format
+            );
+        }
+        None => print!("{}", sheet),
+    }
+    Ok(())
+}
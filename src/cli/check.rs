@@ -0,0 +1,609 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/check.rs
+//!
+//! `check`: conflict checking and repair
+//!
+//! Lists the conflicts of a config with suggested free key combos, along
+//! with lint findings, reserved combos, dispatchers the target Hyprland
+//! lacks and missing programs. With `--fix`, duplicate bindings are then
+//! dropped or commented out.
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::{
+        autofix::{bindings_to_drop, skip_reason, FixMode, FixStrategy},
+        resolve::{find_missing_programs, CommandResolver},
+    },
+    core::{
+        compat::{version_warnings, HyprlandVersion},
+        conflict::{Conflict, ConflictDetector, ConflictSeverity},
+        coverage::assess_coverage,
+        drift::find_drift,
+        idioms::builtin_duplicates,
+        key_suggestions::KeySuggestionEngine,
+        layouts::{configured_layouts, layout_warnings},
+        lint::{lint_bindings, LintLevel, LintPolicy},
+        parser::parse_config_report,
+        submap::submap_warnings,
+        validator::workspace_warnings,
+    },
+    ipc::{ClientMode, HyprlandClient},
+};
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+use super::{
+    backup_location, ensure_writable, location_suffix, open_config, print_diff, severity_colour,
+};
+
+/// Free key combos `check` suggests for each conflict
+const CONFLICT_SUGGESTIONS: usize = 3;
+
+/// Checks configuration file for keybinding conflicts.
+///
+/// Parses the Hyprland config, detects duplicate key combinations,
+/// and displays conflicts with coloured output, suggesting free key combos
+/// where moving a binding resolves a conflict. When Hyprland is running,
+/// bindings using dispatchers its version lacks are flagged too. Actions
+/// reachable from several key combos are only listed on request and do not
+/// count as conflicts. Lint findings are listed at the levels of the lint
+/// policy. With a version in `hyprland`, dispatchers are checked against
+/// that release instead of the running one; with `hyprland.runtime`, the
+/// bindings are compared with the ones the running Hyprland has loaded. With `fix`, conflicts are then repaired (see
+/// [`fix_conflicts`]). Exits with code 1 if conflicts are found and not
+/// all of them were fixed, if the lint policy denies a finding, or if the
+/// config and Hyprland's live bindings differ.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `explain` - Print the embedded explanation for each conflict
+/// * `reserved` - Warn about bindings on reserved combos
+/// * `duplicate_actions` - List actions bound to more than one key combo
+/// * `resolve_commands` - Check that the programs of exec bindings are installed
+/// * `hyprland` - Release to check dispatchers against, and whether to
+///   compare the config with Hyprland's live bindings
+/// * `fix` - Repair the conflicts found, and how
+///
+/// # Returns
+///
+/// * `Ok(())` - No conflicts found, or all of them fixed
+/// * `Err(_)` - File read, parse or write error, or Hyprland cannot be
+///   queried for `hyprland.runtime`
+///
+/// # Exits
+///
+/// Exits with code 1 if conflicts remain, a lint is denied or the live
+/// bindings differ
+pub fn check_conflicts(
+    config_path: &Path,
+    explain: bool,
+    reserved: bool,
+    duplicate_actions: bool,
+    resolve_commands: bool,
+    hyprland: HyprlandTarget,
+    fix: Option<FixOptions>,
+) -> anyhow::Result<()> {
+    // Expand tilde in path
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+
+    // Read config file
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+
+    println!("{} Parsing config: {}", "→".cyan(), path.display());
+
+    // Parse bindings, keeping whatever parses
+    let report = parse_config_report(&content, path);
+    let bindings = report.bindings;
+
+    println!("{} Found {} keybindings\n", "✓".green(), bindings.len());
+
+    // Report lines the parser skipped or only partially understood
+    if !report.diagnostics.is_empty() {
+        for diagnostic in &report.diagnostics {
+            println!("{} {}", "⚠".yellow(), diagnostic);
+        }
+        println!();
+    }
+
+    // Point out lines the parser does not understand at all
+    let coverage = assess_coverage(&content);
+    if !coverage.unknown.is_empty() {
+        println!(
+            "{} Parser understood {:.1}% of the config ({} unknown line{}); run `coverage` for details\n",
+            "⚠".yellow(),
+            coverage.percentage(),
+            coverage.unknown.len(),
+            if coverage.unknown.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    // Warn about dispatchers the targeted or running Hyprland does not have
+    let version = hyprland
+        .version
+        .or_else(|| HyprlandClient::new(ClientMode::ReadOnly).get_version().ok());
+    if let Some(version) = version {
+        let warnings = version_warnings(&bindings, version);
+        if hyprland.version.is_some() {
+            println!(
+                "{} Checking dispatchers against Hyprland {}",
+                "→".cyan(),
+                version
+            );
+            if warnings.is_empty() {
+                println!(
+                    "{} Every dispatcher is available in Hyprland {}\n",
+                    "✓".green(),
+                    version
+                );
+            }
+        }
+        if !warnings.is_empty() {
+            for (binding, warning) in &warnings {
+                println!(
+                    "{} {}: {}{}",
+                    "⚠".yellow(),
+                    binding.key_combo,
+                    warning,
+                    location_suffix(binding)
+                );
+            }
+            println!();
+        }
+    }
+
+    // Warn about submaps that cannot be left and misplaced catchalls
+    let warnings = submap_warnings(&bindings);
+    if !warnings.is_empty() {
+        for warning in &warnings {
+            println!("{} {}", "⚠".yellow(), warning);
+        }
+        println!();
+    }
+
+    // Warn about keys that stop working after a keyboard layout switch
+    let warnings = layout_warnings(&bindings, &configured_layouts(&content));
+    if !warnings.is_empty() {
+        for warning in &warnings {
+            println!(
+                "{} {}{}",
+                "⚠".yellow(),
+                warning,
+                location_suffix(&warning.binding)
+            );
+        }
+        println!();
+    }
+
+    // Suggest dispatchers for bindings that shell out to `hyprctl dispatch`
+    let duplicates = builtin_duplicates(&bindings);
+    if !duplicates.is_empty() {
+        for duplicate in &duplicates {
+            println!(
+                "{} {}{}",
+                "⚠".yellow(),
+                duplicate,
+                location_suffix(&duplicate.binding)
+            );
+        }
+        println!();
+    }
+
+    // Warn about workspace targets that are valid but look like mistakes
+    let warnings: Vec<_> = bindings
+        .iter()
+        .flat_map(|binding| {
+            workspace_warnings(&binding.dispatcher, binding.args.as_deref())
+                .into_iter()
+                .map(move |warning| (binding, warning))
+        })
+        .collect();
+    if !warnings.is_empty() {
+        for (binding, warning) in &warnings {
+            println!(
+                "{} {}: {}{}",
+                "⚠".yellow(),
+                binding.key_combo,
+                warning,
+                location_suffix(binding)
+            );
+        }
+        println!();
+    }
+
+    // Style lints, at the levels of the lint policy
+    let lints = lint_bindings(&content, &bindings, &load_lint_policy());
+    let denied = lints
+        .iter()
+        .filter(|finding| finding.level == LintLevel::Deny)
+        .count();
+    if !lints.is_empty() {
+        for finding in &lints {
+            let mark = match finding.level {
+                LintLevel::Deny => "✗".red(),
+                _ => "⚠".yellow(),
+            };
+            println!("{} {}", mark, finding);
+        }
+        println!();
+    }
+
+    // Point out exec bindings whose program is not installed
+    if resolve_commands {
+        let missing = find_missing_programs(&bindings, &CommandResolver::from_env());
+        if missing.is_empty() {
+            println!(
+                "{} Every exec binding starts an installed program\n",
+                "✓".green()
+            );
+        } else {
+            for program in &missing {
+                println!(
+                    "{} {}{}",
+                    "⚠".yellow(),
+                    program,
+                    location_suffix(&program.binding)
+                );
+            }
+            println!();
+        }
+    }
+
+    // Compare with the bindings the running Hyprland has loaded
+    let mut drifted = false;
+    if hyprland.runtime {
+        let live = HyprlandClient::new(ClientMode::ReadOnly).binds()?;
+        let drift = find_drift(&bindings, &live);
+        if drift.is_empty() {
+            println!(
+                "{} Hyprland has loaded exactly the config's bindings\n",
+                "✓".green()
+            );
+        } else {
+            println!("{} {}:", "⚠".yellow(), drift);
+            for line in drift.describe() {
+                println!("  {}", line);
+            }
+            println!();
+            drifted = true;
+        }
+    }
+
+    // Build conflict detector
+    let suggestions = KeySuggestionEngine::new(&bindings);
+    let mut detector = ConflictDetector::new();
+    detector.set_check_reserved(reserved);
+    for binding in bindings {
+        detector.add_binding(binding);
+    }
+
+    // Warn about bindings on combos Hyprland or common tools rely on
+    let shadows = detector.find_reserved_shadows();
+    if !shadows.is_empty() {
+        for shadow in &shadows {
+            println!(
+                "{} {}{}",
+                "⚠".yellow(),
+                shadow,
+                location_suffix(&shadow.binding)
+            );
+        }
+        println!();
+    }
+
+    // List actions reachable from several key combos
+    if duplicate_actions {
+        let duplicate_actions = detector.find_duplicate_actions();
+        if duplicate_actions.is_empty() {
+            println!(
+                "{} No action is bound to more than one key combo\n",
+                "✓".green()
+            );
+        } else {
+            println!(
+                "{} {} action{} bound to more than one key combo:\n",
+                "ℹ".blue(),
+                duplicate_actions.len(),
+                if duplicate_actions.len() == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            );
+            for duplicate in &duplicate_actions {
+                println!("  {}", duplicate);
+                for binding in &duplicate.bindings {
+                    println!(
+                        "    {} {}{}",
+                        "•".dimmed(),
+                        format!("{}", binding.key_combo).cyan(),
+                        location_suffix(binding).dimmed()
+                    );
+                }
+            }
+            println!();
+        }
+    }
+
+    // Find conflicts, most severe first
+    let mut conflicts = detector.find_conflicts();
+    conflicts.sort_by_key(|conflict| std::cmp::Reverse(conflict.severity));
+
+    if conflicts.is_empty() {
+        println!("{} {}", "✓".green().bold(), "No conflicts detected!".bold());
+        println!("\nYour keybindings are clean! ✓");
+    } else {
+        let counts: Vec<String> = [
+            ConflictSeverity::High,
+            ConflictSeverity::Medium,
+            ConflictSeverity::Low,
+        ]
+        .into_iter()
+        .filter_map(|severity| {
+            let count = conflicts.iter().filter(|c| c.severity == severity).count();
+            (count > 0)
+                .then(|| severity_colour(severity, &format!("{} {}", count, severity.label())))
+        })
+        .map(|count| count.to_string())
+        .collect();
+        println!(
+            "{} Found {} conflict{} ({}):\n",
+            "✗".red().bold(),
+            conflicts.len(),
+            if conflicts.len() == 1 { "" } else { "s" },
+            counts.join(", ")
+        );
+
+        for (i, conflict) in conflicts.iter().enumerate() {
+            println!(
+                "{} {} {}{}",
+                format!("Conflict {}", i + 1).yellow().bold(),
+                severity_colour(
+                    conflict.severity,
+                    &format!("[{}]", conflict.severity.label())
+                ),
+                format!("{}", conflict.key_combo).cyan(),
+                conflict
+                    .submap
+                    .as_ref()
+                    .map(|submap| format!(" (submap {})", submap))
+                    .unwrap_or_default()
+            );
+
+            for (idx, binding) in conflict.conflicting_bindings.iter().enumerate() {
+                let args = binding.args.as_deref().unwrap_or("");
+
+                println!(
+                    "  {} {} → {} {}{}",
+                    format!("{}.", idx + 1).dimmed(),
+                    format!("{}", binding.bind_type).magenta(),
+                    binding.dispatcher,
+                    args,
+                    location_suffix(binding).dimmed(),
+                );
+            }
+
+            let explanation = conflict.kind.explanation();
+            println!(
+                "  {} {}",
+                format!("{}:", explanation.title).blue(),
+                explanation.summary
+            );
+
+            let free = suggestions.suggest_for_conflict(conflict, CONFLICT_SUGGESTIONS);
+            if !free.is_empty() {
+                let free: Vec<String> = free.iter().map(ToString::to_string).collect();
+                println!("  {} {}", "Free instead:".green(), free.join(", "));
+            }
+            if explain {
+                for paragraph in explanation.details.split("\n\n") {
+                    println!("\n  {}", paragraph);
+                }
+                println!("\n  {} {}", "Learn more:".dimmed(), explanation.docs_url);
+            }
+            println!();
+        }
+
+        println!(
+            "{}",
+            "⚠ These keybindings will conflict at runtime!".yellow()
+        );
+        if !explain {
+            println!(
+                "{}",
+                "Run with --explain for details on each conflict.".dimmed()
+            );
+        }
+        if let Some(fix) = &fix {
+            if fix_conflicts(path, &conflicts, fix)? == 0 && denied == 0 && !drifted {
+                return Ok(());
+            }
+        }
+        std::process::exit(1);
+    }
+
+    if denied > 0 {
+        println!(
+            "\n{} {} binding{} denied by the lint policy",
+            "✗".red(),
+            denied,
+            if denied == 1 { "" } else { "s" }
+        );
+        std::process::exit(1);
+    }
+
+    if drifted {
+        println!(
+            "\n{} The config and Hyprland's live bindings differ; apply the config or reload Hyprland",
+            "✗".red()
+        );
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Which Hyprland `check` compares the config with
+pub struct HyprlandTarget {
+    /// Release to check dispatchers against (`None`: the running one, if any)
+    pub version: Option<HyprlandVersion>,
+    /// Also compare with the bindings the running Hyprland has loaded
+    pub runtime: bool,
+}
+
+/// How `check --fix` repairs conflicts
+pub struct FixOptions {
+    /// Binding to keep without asking (`None`: ask for each conflict)
+    pub strategy: Option<FixStrategy>,
+    /// Whether dropped bindings are deleted or commented out
+    pub mode: FixMode,
+    /// Only show the change as a diff
+    pub dry_run: bool,
+    /// Refuse to fix while the config has parse diagnostics
+    pub strict: bool,
+}
+
+/// Repairs the conflicts `check` found.
+///
+/// Each fixable conflict (see [`skip_reason`]) keeps one binding: the one
+/// the strategy picks, or the one the user chooses when there is none.
+/// All other bindings are dropped in a single write, which backs the
+/// config up first like every write. A dry run shows the write as a diff
+/// instead. In strict mode nothing is fixed while the config has parse
+/// diagnostics (see [`ensure_writable`]).
+///
+/// # Arguments
+///
+/// * `path` - Config file the conflicts were read from
+/// * `conflicts` - Conflicts in the order they were listed
+/// * `fix` - Strategy and what happens to dropped lines
+///
+/// # Returns
+///
+/// * `Ok(count)` - Number of conflicts left unfixed
+/// * `Err(_)` - Terminal or write error, or refused by strict mode; the
+///   config is unchanged
+fn fix_conflicts(path: &Path, conflicts: &[Conflict], fix: &FixOptions) -> anyhow::Result<usize> {
+    ensure_writable(path, fix.strict)?;
+    println!();
+    let mut dropped = Vec::new();
+    let mut unfixed = 0;
+
+    for (i, conflict) in conflicts.iter().enumerate() {
+        let label = format!("Conflict {} ({})", i + 1, conflict.key_combo);
+        if let Some(reason) = skip_reason(conflict) {
+            println!("{} {}: skipped, {}", "–".dimmed(), label, reason);
+            unfixed += 1;
+            continue;
+        }
+
+        let keep = match fix.strategy {
+            Some(strategy) => strategy.keep_index(conflict),
+            None => match ask_binding_to_keep(&label, conflict)? {
+                Some(keep) => keep,
+                None => {
+                    unfixed += 1;
+                    continue;
+                }
+            },
+        };
+        dropped.extend(bindings_to_drop(conflict, keep));
+    }
+
+    if dropped.is_empty() {
+        println!("{} Nothing to fix", "ℹ".blue());
+        return Ok(unfixed);
+    }
+
+    let mut manager = open_config(path)?;
+    if fix.dry_run {
+        println!();
+        print_diff(&manager.preview_drop_bindings(&dropped, fix.mode)?, path);
+        println!("\n{}", "Dry run: nothing was written".dimmed());
+        return Ok(conflicts.len());
+    }
+    manager.drop_bindings(&dropped, fix.mode)?;
+
+    println!(
+        "\n{} {} {} binding{}; the previous config is backed up in {}",
+        "✓".green(),
+        match fix.mode {
+            FixMode::Remove => "Removed",
+            FixMode::CommentOut => "Commented out",
+        },
+        dropped.len(),
+        if dropped.len() == 1 { "" } else { "s" },
+        backup_location(path).display()
+    );
+    if unfixed > 0 {
+        println!(
+            "{} {} conflict{} left to fix by hand",
+            "⚠".yellow(),
+            unfixed,
+            if unfixed == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(unfixed)
+}
+
+/// Asks which binding of a conflict to keep
+///
+/// # Arguments
+///
+/// * `label` - How the conflict was listed, e.g. "Conflict 2 (SUPER+K)"
+/// * `conflict` - Conflict whose numbered bindings were listed
+///
+/// # Returns
+///
+/// * `Ok(Some(index))` - Index into `conflicting_bindings` of the binding to keep
+/// * `Ok(None)` - The user skipped the conflict (or stdin closed)
+fn ask_binding_to_keep(label: &str, conflict: &Conflict) -> anyhow::Result<Option<usize>> {
+    let count = conflict.conflicting_bindings.len();
+    loop {
+        print!("{}: keep which binding? [1-{}, s to skip] ", label, count);
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+        if io::stdin().lock().read_line(&mut answer)? == 0 {
+            println!();
+            return Ok(None);
+        }
+        match answer.trim() {
+            "s" | "S" => return Ok(None),
+            choice => match choice.parse::<usize>() {
+                Ok(n) if (1..=count).contains(&n) => return Ok(Some(n - 1)),
+                _ => println!("  Enter a number from 1 to {}, or s", count),
+            },
+        }
+    }
+}
+
+/// Reads the lint policy; a missing or unreadable file gives the defaults
+fn load_lint_policy() -> LintPolicy {
+    LintPolicy::default_path()
+        .map(|path| LintPolicy::load(&path))
+        .unwrap_or_default()
+}
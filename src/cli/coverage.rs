@@ -0,0 +1,105 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/coverage.rs
+//!
+//! `coverage`: config lines the parser does not understand
+
+use colored::*;
+use hypr_keybind_manager::core::{coverage::assess_coverage, parser::diagnose_config};
+use std::{fs, path::Path};
+
+/// Reports how much of the config the parser understands.
+///
+/// Counts bind lines, variables, submap markers, sources and other known
+/// lines, then lists partially understood bind lines and unknown
+/// constructs. Works even when the config has bind lines that do not parse.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+///
+/// # Returns
+///
+/// * `Ok(())` - Report printed
+/// * `Err(_)` - File read error
+pub fn report_coverage(config_path: &Path) -> anyhow::Result<()> {
+    // Expand tilde in path
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let report = assess_coverage(&content);
+
+    let percentage = format!("{:.1}%", report.percentage());
+    let percentage = if report.unknown.is_empty() && report.partial_binds == 0 {
+        percentage.green()
+    } else if report.unknown.is_empty() {
+        percentage.yellow()
+    } else {
+        percentage.red()
+    };
+    println!(
+        "{} {} ({} of {} lines)\n",
+        "Parse coverage:".bold(),
+        percentage.bold(),
+        report.understood(),
+        report.total()
+    );
+
+    for (count, label) in [
+        (report.binds, "bind lines"),
+        (report.partial_binds, "bind lines with diagnostics"),
+        (report.variables, "variables"),
+        (report.submaps, "submap markers"),
+        (report.sources, "source lines"),
+        (report.other_known, "other known lines"),
+        (report.unknown.len(), "unknown lines"),
+    ] {
+        println!("  {:>4}  {}", count, label);
+    }
+
+    let diagnostics = diagnose_config(&content);
+    if !diagnostics.is_empty() {
+        println!("\n{}", "Partially understood:".bold());
+        for diagnostic in &diagnostics {
+            println!("  {} {}", "⚠".yellow(), diagnostic);
+        }
+    }
+
+    if !report.unknown.is_empty() {
+        println!("\n{}", "Not understood:".bold());
+        for construct in &report.unknown {
+            println!(
+                "  {} line {}: {} {}",
+                "✗".red(),
+                construct.line,
+                construct.text,
+                format!("({})", construct.reason).dimmed()
+            );
+        }
+        println!(
+            "\n{}",
+            "Unknown lines are kept as they are; unknown bind lines may stop the config loading."
+                .dimmed()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/doctor.rs
+//!
+//! `doctor`: the config health score and what lowers it
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::{
+        audit::{AuditLog, HealthTrend},
+        danger::DangerDetector,
+        doctor::{diagnose, Severity},
+        health::{assess_health, executable_on_path},
+        resolve::CommandResolver,
+    },
+    core::{conflict::ConflictDetector, parser::parse_config_report},
+};
+use std::{fs, path::Path};
+
+/// Prints the config health score and a full report of what is wrong.
+///
+/// Each score category shows the points it deducts. The report that
+/// follows lists every finding of the parser, the security layers, the
+/// dispatcher checks, conflict detection and program lookup, by section,
+/// with its severity and a suggestion (see
+/// [`hypr_keybind_manager::config::doctor`]). The score is recorded in the
+/// audit log so the change since the previous score can be shown.
+///
+/// Exits with 1 when the worst finding is a warning, 2 for an error and 3
+/// for a critical (security) finding.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+///
+/// # Returns
+///
+/// * `Ok(())` - Report printed, nothing worse than information found
+/// * `Err(_)` - File read error
+pub fn run_doctor(config_path: &Path) -> anyhow::Result<()> {
+    // Expand tilde in path
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    // Lines that do not parse are findings, not a reason to stop
+    let bindings = parse_config_report(&content, path).bindings;
+
+    let mut detector = ConflictDetector::new();
+    for binding in bindings.iter().cloned() {
+        detector.add_binding(binding);
+    }
+
+    let danger_detector = DangerDetector::with_installed_rules();
+    let report = assess_health(
+        &content,
+        &bindings,
+        &detector.find_conflicts(),
+        &danger_detector,
+        executable_on_path,
+    );
+
+    let score = format!("{}/100", report.score);
+    let score = match report.score {
+        90.. => score.green(),
+        70..=89 => score.yellow(),
+        _ => score.red(),
+    };
+    println!("{} {}", "Config health:".bold(), score.bold());
+
+    // Compare with the last different score, then record this one
+    if let Some(log) = AuditLog::default_path().map(AuditLog::new) {
+        let history = log.health_history(path).unwrap_or_default();
+        if let Some(trend) = HealthTrend::from_history(&history, report.score) {
+            let change = format!("{:+}", trend.change);
+            println!(
+                "  {} since {}",
+                if trend.change > 0 {
+                    change.green()
+                } else {
+                    change.red()
+                },
+                trend.since.format("%Y-%m-%d %H:%M")
+            );
+        }
+        if let Err(e) = log.record_health(path, report.score, chrono::Local::now()) {
+            eprintln!("{} Failed to record health score: {}", "⚠".yellow(), e);
+        }
+    }
+    println!();
+
+    for component in report.components() {
+        let penalty = format!("-{:<2}", component.penalty);
+        println!(
+            "  {} {:<20} {}",
+            if component.penalty == 0 {
+                penalty.dimmed()
+            } else {
+                penalty.yellow()
+            },
+            component.label,
+            component.detail.dimmed()
+        );
+    }
+
+    let findings = diagnose(
+        &content,
+        path,
+        &danger_detector,
+        &CommandResolver::from_env(),
+    );
+    for section in &findings.sections {
+        println!("\n{}", section.title.bold());
+        if section.findings.is_empty() {
+            println!("  {} {}", "✓".green(), "Nothing found".dimmed());
+        }
+        for finding in &section.findings {
+            let severity = format!("{:<8}", finding.severity.label());
+            let severity = match finding.severity {
+                Severity::Critical => severity.red().bold(),
+                Severity::Error => severity.red(),
+                Severity::Warning => severity.yellow(),
+                Severity::Info => severity.dimmed(),
+            };
+            let location = finding
+                .location
+                .as_ref()
+                .map(|location| format!(" ({})", location))
+                .unwrap_or_default();
+            println!("  {} {}{}", severity, finding.message, location.dimmed());
+            if let Some(suggestion) = &finding.suggestion {
+                println!("           {} {}", "→".cyan(), suggestion);
+            }
+        }
+    }
+
+    let counts: Vec<String> = [
+        Severity::Critical,
+        Severity::Error,
+        Severity::Warning,
+        Severity::Info,
+    ]
+    .into_iter()
+    .map(|severity| (severity, findings.count(severity)))
+    .filter(|(_, count)| *count > 0)
+    .map(|(severity, count)| match severity {
+        Severity::Critical | Severity::Info => format!("{} {}", count, severity),
+        _ if count == 1 => format!("1 {}", severity),
+        _ => format!("{} {}s", count, severity),
+    })
+    .collect();
+    println!();
+    match findings.exit_code() {
+        0 if counts.is_empty() => println!("{} No problems found", "✓".green()),
+        0 => println!("{} {}", "✓".green(), counts.join(", ")),
+        code => {
+            println!("{} {}", "✗".red(), counts.join(", "));
+            std::process::exit(code);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,515 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/edit.rs
+//!
+//! `add`, `rm` and `edit`: changing single bindings from scripts
+//!
+//! Bindings are selected by key combo or by their position in the config,
+//! the number `list --numbered` shows. New and edited bindings go through
+//! the same validation as the GUI's edit dialog.
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::{
+        autofix::FixMode,
+        danger::DangerLevel,
+        validator::{ConfigValidator, ValidationLevel},
+    },
+    core::{
+        conflict::ConflictDetector,
+        parser::{modifier_from_str, parse_config_with_sources},
+        BindFlag, BindType, KeyCombo, Keybinding, Modifier,
+    },
+    ipc::{ClientMode, HyprlandClient},
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{backup_location, location_suffix, open_config, print_diff};
+
+/// Reads one modifier name, rejecting unknown ones
+fn parse_modifier(name: &str) -> anyhow::Result<Modifier> {
+    modifier_from_str(name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown modifier: {} (SUPER, CTRL, SHIFT or ALT)", name))
+}
+
+/// Reads a bind keyword (`bind`, `bindl`, `binde`, ...)
+fn parse_bind_type(keyword: &str) -> anyhow::Result<BindType> {
+    BindType::from_keyword(&keyword.trim().to_lowercase())
+        .ok_or_else(|| anyhow::anyhow!("Unknown bind type: {}", keyword))
+}
+
+/// Runs a new or edited binding through layers 1 and 2
+///
+/// Injection attempts and critical commands are always refused; a
+/// dangerous command only with `force`. Other warnings are printed,
+/// including a dispatcher the running Hyprland does not have.
+///
+/// # Returns
+///
+/// * `Err(_)` - The binding was refused, with the reasons
+fn check_new_binding(binding: &Keybinding, force: bool) -> anyhow::Result<()> {
+    let mut validator = ConfigValidator::new();
+    if let Ok(version) = HyprlandClient::new(ClientMode::ReadOnly).get_version() {
+        validator = validator.with_hyprland_version(version);
+    }
+    let report = validator.validate_bindings(std::slice::from_ref(binding));
+    if report.has_errors() || report.has_critical_dangers() {
+        let reasons: Vec<String> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.validation_level == ValidationLevel::Error)
+            .map(|issue| issue.message.clone())
+            .chain(
+                report
+                    .dangerous_commands
+                    .iter()
+                    .filter(|(_, assessment)| assessment.danger_level == DangerLevel::Critical)
+                    .map(|(_, assessment)| format!("Critical command: {}", assessment.reason)),
+            )
+            .collect();
+        anyhow::bail!("Refused {}: {}", binding, reasons.join("; "));
+    }
+    if report.highest_danger == DangerLevel::Dangerous && !force {
+        anyhow::bail!(
+            "Refused {}: the command is dangerous (add --force to save it anyway)",
+            binding
+        );
+    }
+    for issue in &report.issues {
+        println!("{} {}", "⚠".yellow(), issue.message);
+    }
+    Ok(())
+}
+
+/// A binding described by the options of `add`
+pub struct NewBinding {
+    pub mods: Vec<String>,
+    pub key: String,
+    pub dispatcher: String,
+    pub args: Option<String>,
+    pub bind_type: String,
+    pub submap: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+}
+
+impl NewBinding {
+    /// Builds the binding, rejecting unknown modifiers and bind keywords
+    pub fn into_keybinding(self) -> anyhow::Result<Keybinding> {
+        let modifiers = self
+            .mods
+            .iter()
+            .filter(|name| !name.trim().is_empty())
+            .map(|name| parse_modifier(name))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if self.key.trim().is_empty() {
+            anyhow::bail!("No key given");
+        }
+        let mut bind_type = parse_bind_type(&self.bind_type)?;
+        if self.description.is_some() {
+            bind_type = bind_type.with(BindFlag::Description);
+        }
+
+        Ok(Keybinding {
+            key_combo: KeyCombo::new(modifiers, &self.key),
+            bind_type,
+            dispatcher: self.dispatcher,
+            args: self.args.filter(|args| !args.is_empty()),
+            submap: self.submap,
+            source_file: None,
+            description: self.description,
+            category: self.category,
+            device: None,
+            location: None,
+        })
+    }
+}
+
+/// Adds one binding to the config.
+///
+/// The binding goes through the same checks as one added in the GUI:
+/// injection attempts and critical commands are refused, and dangerous
+/// commands or a key that is already bound in the same submap are refused
+/// unless forced. The write is transactional and backs the config up
+/// first. Every refusal is an error, so scripts see a non-zero exit code.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `binding` - The binding to add
+/// * `force` - Add despite a conflict or a dangerous command
+/// * `dry_run` - Only show the change as a diff
+///
+/// # Returns
+///
+/// * `Err(_)` - The binding was refused, or the config cannot be read or written
+pub fn add_binding(
+    config_path: &Path,
+    binding: Keybinding,
+    force: bool,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = PathBuf::from(expanded_path.as_ref());
+
+    check_new_binding(&binding, force)?;
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let mut bindings = parse_config_with_sources(&content, &path)?;
+
+    let mut detector = ConflictDetector::new();
+    for existing in &bindings {
+        detector.add_binding(existing.clone());
+    }
+    detector.add_binding(binding.clone());
+    let clashing: Vec<Keybinding> = detector
+        .find_conflicts()
+        .into_iter()
+        .filter(|conflict| {
+            conflict.key_combo == binding.key_combo && conflict.submap == binding.submap
+        })
+        .flat_map(|conflict| conflict.conflicting_bindings)
+        // The new binding is the only one not read from a file
+        .filter(|existing| existing.location.is_some())
+        .collect();
+    if !clashing.is_empty() {
+        let listed: Vec<String> = clashing
+            .iter()
+            .map(|existing| format!("{}{}", existing, location_suffix(existing)))
+            .collect();
+        if !force {
+            anyhow::bail!(
+                "{} is already bound: {} (add --force to add it anyway)",
+                binding.key_combo,
+                listed.join(", ")
+            );
+        }
+        for existing in &listed {
+            println!("{} Conflicts with {}", "⚠".yellow(), existing);
+        }
+    }
+
+    bindings.push(binding.clone());
+    let mut manager = open_config(&path)?;
+    if dry_run {
+        print_diff(&manager.preview_bindings(&bindings)?, &path);
+        println!("\n{}", "Dry run: nothing was written".dimmed());
+        return Ok(());
+    }
+
+    manager.write_bindings(&bindings)?;
+    println!(
+        "{} Added {}; a backup of the previous config is in {}",
+        "✓".green(),
+        binding,
+        backup_location(&path).display()
+    );
+    Ok(())
+}
+
+/// Finds the bindings `rm` or `edit` should act on
+///
+/// # Arguments
+///
+/// * `bindings` - The config's bindings, in `list` order
+/// * `target` - A number from `list --numbered`, or a key combo such as
+///   `SUPER+SHIFT+Q`
+/// * `submap` - Submap of the key combo (`None`: global bindings)
+///
+/// # Returns
+///
+/// * `Ok(indices)` - Indices into `bindings`; never empty
+/// * `Err(_)` - No such number, an unreadable combo or no binding on it
+fn select_bindings(
+    bindings: &[Keybinding],
+    target: &str,
+    submap: Option<&str>,
+) -> anyhow::Result<Vec<usize>> {
+    let target = target.trim();
+    if let Ok(number) = target.parse::<usize>() {
+        if number == 0 || number > bindings.len() {
+            anyhow::bail!(
+                "No binding number {} (the config has {}; see list --numbered)",
+                number,
+                bindings.len()
+            );
+        }
+        return Ok(vec![number - 1]);
+    }
+
+    let mut parts: Vec<&str> = target.split('+').map(str::trim).collect();
+    let key = parts.pop().unwrap_or_default();
+    if key.is_empty() || parts.iter().any(|part| part.is_empty()) {
+        anyhow::bail!(
+            "Cannot read {}: use MOD+KEY or a number from list --numbered",
+            target
+        );
+    }
+    let modifiers = parts
+        .into_iter()
+        .map(parse_modifier)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let combo = KeyCombo::new(modifiers, key);
+
+    let selected: Vec<usize> = bindings
+        .iter()
+        .enumerate()
+        .filter(|(_, binding)| binding.key_combo == combo && binding.submap.as_deref() == submap)
+        .map(|(index, _)| index)
+        .collect();
+    if selected.is_empty() {
+        match submap {
+            Some(submap) => anyhow::bail!("Nothing is bound to {} in submap {}", combo, submap),
+            None => anyhow::bail!("Nothing is bound to {}", combo),
+        }
+    }
+    Ok(selected)
+}
+
+/// Lists the bindings a key combo matched, when one was expected
+fn ambiguous(bindings: &[Keybinding], selected: &[usize], hint: &str) -> anyhow::Error {
+    let listed: Vec<String> = selected
+        .iter()
+        .map(|index| {
+            format!(
+                "  {}. {}{}",
+                index + 1,
+                bindings[*index],
+                location_suffix(&bindings[*index])
+            )
+        })
+        .collect();
+    anyhow::anyhow!(
+        "{} bindings match; {}:\n{}",
+        selected.len(),
+        hint,
+        listed.join("\n")
+    )
+}
+
+/// Removes bindings from the config.
+///
+/// Only the removed bindings' lines change, through a transaction that
+/// backs the config up first. Bindings read from `source`d files cannot
+/// be removed this way, except a single one from a file marked read-only,
+/// which is unbound in the main config (see `config::overrides`).
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `target` - A number from `list --numbered`, or a key combo
+/// * `submap` - Submap of the key combo (`None`: global bindings)
+/// * `all` - Remove every binding on the combo instead of refusing when
+///   there are several
+/// * `mode` - Whether the lines are deleted or commented out
+/// * `dry_run` - Only show the change as a diff
+///
+/// # Returns
+///
+/// * `Err(_)` - Nothing matched, the match was ambiguous, or the config
+///   cannot be read or written
+pub fn remove_bindings(
+    config_path: &Path,
+    target: &str,
+    submap: Option<&str>,
+    all: bool,
+    mode: FixMode,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = PathBuf::from(expanded_path.as_ref());
+    let content =
+        fs::read_to_string(&path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, &path)?;
+
+    let selected = select_bindings(&bindings, target, submap)?;
+    if selected.len() > 1 && !all {
+        return Err(ambiguous(
+            &bindings,
+            &selected,
+            "remove one by number, or all of them with --all",
+        ));
+    }
+    let removed: Vec<Keybinding> = selected
+        .iter()
+        .map(|index| bindings[*index].clone())
+        .collect();
+
+    let mut manager = open_config(&path)?;
+    // A binding of a read-only sourced file is unbound in the main config
+    let unbound = match &removed[..] {
+        [binding]
+            if binding
+                .source_file
+                .as_deref()
+                .is_some_and(|file| manager.is_read_only_source(file)) =>
+        {
+            Some(binding)
+        }
+        _ => None,
+    };
+    if dry_run {
+        let diff = match unbound {
+            Some(binding) => manager.preview_source_override(binding, None, &bindings)?,
+            None => manager.preview_drop_bindings(&removed, mode)?,
+        };
+        print_diff(&diff, &path);
+        println!("\n{}", "Dry run: nothing was written".dimmed());
+        return Ok(());
+    }
+
+    match unbound {
+        Some(binding) => manager.write_source_override(binding, None, &bindings)?,
+        None => manager.drop_bindings(&removed, mode)?,
+    }
+    for binding in &removed {
+        println!("{} Removed {}", "✓".green(), binding);
+    }
+    println!(
+        "  A backup of the previous config is in {}",
+        backup_location(&path).display()
+    );
+    Ok(())
+}
+
+/// The changes `edit` makes to a binding; `None` keeps a field
+pub struct BindingChange {
+    pub dispatcher: Option<String>,
+    pub args: Option<String>,
+    pub bind_type: Option<String>,
+    pub description: Option<String>,
+}
+
+impl BindingChange {
+    /// Returns the binding with the changes applied
+    fn apply(self, binding: &Keybinding) -> anyhow::Result<Keybinding> {
+        let mut edited = binding.clone();
+        if let Some(dispatcher) = self.dispatcher {
+            edited.dispatcher = dispatcher;
+        }
+        if let Some(args) = self.args {
+            edited.args = Some(args).filter(|args| !args.is_empty());
+        }
+        if let Some(bind_type) = self.bind_type {
+            edited.bind_type = parse_bind_type(&bind_type)?;
+        }
+        if let Some(description) = self.description {
+            edited.description = Some(description).filter(|description| !description.is_empty());
+        }
+        // A description is written on a bindd line, and only there
+        edited.bind_type = match edited.description {
+            Some(_) => edited.bind_type.with(BindFlag::Description),
+            None => edited.bind_type.without(BindFlag::Description),
+        };
+        Ok(edited)
+    }
+}
+
+/// Changes one binding of the config.
+///
+/// The edited binding is checked like a new one (see `add`). When it was
+/// read from the main config only its line is rewritten, and one from a
+/// file marked read-only is overridden in the main config (see
+/// `config::overrides`); the write goes through a transaction that backs
+/// the config up first.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `target` - A number from `list --numbered`, or a key combo
+/// * `submap` - Submap of the key combo (`None`: global bindings)
+/// * `change` - Fields to change
+/// * `force` - Save despite a dangerous command
+/// * `dry_run` - Only show the change as a diff
+///
+/// # Returns
+///
+/// * `Err(_)` - Nothing or several bindings matched, the edit was refused,
+///   or the config cannot be read or written
+pub fn edit_binding(
+    config_path: &Path,
+    target: &str,
+    submap: Option<&str>,
+    change: BindingChange,
+    force: bool,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = PathBuf::from(expanded_path.as_ref());
+    let content =
+        fs::read_to_string(&path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, &path)?;
+
+    let selected = select_bindings(&bindings, target, submap)?;
+    let [index] = selected[..] else {
+        return Err(ambiguous(&bindings, &selected, "pick one by number"));
+    };
+    let old = bindings[index].clone();
+    let new = change.apply(&old)?;
+    if new.to_string() == old.to_string() && new.description == old.description {
+        println!("{} {} is unchanged", "✓".green(), old);
+        return Ok(());
+    }
+    check_new_binding(&new, force)?;
+    let mut updated = bindings.clone();
+    updated[index] = new.clone();
+
+    let mut manager = open_config(&path)?;
+    // A binding of a read-only sourced file is overridden in the main config
+    let read_only = old
+        .source_file
+        .as_deref()
+        .is_some_and(|file| manager.is_read_only_source(file));
+    if dry_run {
+        let diff = if read_only {
+            manager.preview_source_override(&old, Some(&new), &bindings)?
+        } else {
+            manager.preview_binding_update(&old, &new, &updated)?
+        };
+        print_diff(&diff, &path);
+        println!("\n{}", "Dry run: nothing was written".dimmed());
+        return Ok(());
+    }
+
+    if read_only {
+        manager.write_source_override(&old, Some(&new), &bindings)?;
+    } else {
+        manager.write_binding_update(&old, &new, &updated)?;
+    }
+    println!(
+        "{} Changed {} to {}; a backup of the previous config is in {}",
+        "✓".green(),
+        old,
+        new,
+        backup_location(&path).display()
+    );
+    Ok(())
+}
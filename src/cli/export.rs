@@ -0,0 +1,74 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/export.rs
+//!
+//! `export`: writing the bindings to JSON, YAML or Markdown
+
+use colored::*;
+use hypr_keybind_manager::{config::export::ExportFormat, core::parser::parse_config_with_sources};
+use std::{fs, path::Path};
+
+/// Exports the config's bindings, sourced files included.
+///
+/// Bindings a sway or i3 export leaves out are listed on standard error.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `format` - File format; `None` picks it by the output file's extension
+///   (config lines when printing)
+/// * `output` - File to write; `None` prints the export
+///
+/// # Returns
+///
+/// * `Err(_)` - The config cannot be read or parsed, or the file not written
+pub fn export_bindings(
+    config_path: &Path,
+    format: Option<ExportFormat>,
+    output: Option<&Path>,
+) -> anyhow::Result<()> {
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, path)?;
+
+    let format = format.unwrap_or_else(|| output.map(ExportFormat::from_path).unwrap_or_default());
+    let exported = format.render(&bindings)?;
+    if let Some(foreign) = format.foreign() {
+        for skipped in foreign.export(&bindings).skipped {
+            eprintln!("{} Not exported: {}", "⚠".yellow(), skipped);
+        }
+    }
+    match output {
+        Some(output) => {
+            fs::write(output, exported)
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output.display(), e))?;
+            eprintln!(
+                "{} Exported {} bindings to {} ({})",
+                "✓".green(),
+                bindings.len(),
+                output.display(),
+                format
+            );
+        }
+        None => print!("{}", exported),
+    }
+    Ok(())
+}
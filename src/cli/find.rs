@@ -0,0 +1,91 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/find.rs
+//!
+//! `find`: fuzzy search of bindings by key combo, action or description
+
+use colored::*;
+use hypr_keybind_manager::core::{palette::search_bindings, parser::parse_config_with_sources};
+use std::{fs, path::Path};
+
+use super::location_suffix;
+
+/// Prints the bindings matching a fuzzy search, best match first.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `query` - What to look for
+/// * `limit` - Most results to print (`None`: all)
+pub fn find_keybindings(
+    config_path: &Path,
+    query: &str,
+    limit: Option<usize>,
+) -> anyhow::Result<()> {
+    // Expand tilde in path
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, path)?;
+
+    let matches = search_bindings(&bindings, query);
+    if matches.is_empty() {
+        anyhow::bail!("No binding matches '{}'", query);
+    }
+
+    let shown = limit.unwrap_or(matches.len()).min(matches.len());
+    for found in &matches[..shown] {
+        let binding = found.binding;
+        let action = match &binding.args {
+            Some(args) => format!("{} {}", binding.dispatcher, args),
+            None => binding.dispatcher.clone(),
+        };
+        let submap = binding
+            .submap
+            .as_deref()
+            .map(|submap| format!("[{}] ", submap))
+            .unwrap_or_default();
+        println!(
+            "{}{}  {}{}",
+            submap.cyan(),
+            binding.key_combo.to_string().bold(),
+            action,
+            location_suffix(binding).dimmed()
+        );
+        if let Some(description) = &binding.description {
+            println!("  {}", description.italic());
+        }
+    }
+
+    if shown < matches.len() {
+        println!(
+            "\n{} {} of {} matches (raise --limit for more)",
+            "✓".green(),
+            shown,
+            matches.len()
+        );
+    } else {
+        let plural = if matches.len() == 1 { "" } else { "es" };
+        println!("\n{} {} match{}", "✓".green(), matches.len(), plural);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/fixtures.rs
+//!
+//! `fixtures`: test fixtures from the conflicts of a config
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::fixtures::{fixtures_from_conflicts, write_fixtures},
+    core::{conflict::ConflictDetector, parser::parse_config_with_sources},
+};
+use std::{fs, path::Path};
+
+/// Writes the config's conflicts as regression test fixtures.
+///
+/// Developer tool: each detected conflict becomes a minimal config snippet
+/// in `out_dir` that the test suite checks against the conflict detector.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `out_dir` - Fixture directory (usually `tests/fixtures/conflicts`)
+///
+/// # Returns
+///
+/// * `Ok(())` - Fixtures written (or none needed)
+/// * `Err(_)` - File read, parse or write error
+pub fn generate_fixtures(config_path: &Path, out_dir: &Path) -> anyhow::Result<()> {
+    // Expand tilde in path
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, path)?;
+
+    let mut detector = ConflictDetector::new();
+    for binding in bindings {
+        detector.add_binding(binding);
+    }
+
+    let fixtures = fixtures_from_conflicts(&detector.find_conflicts());
+    if fixtures.is_empty() {
+        println!("{} No conflicts detected, nothing to write", "✓".green());
+        return Ok(());
+    }
+
+    let written = write_fixtures(out_dir, &fixtures)?;
+    for fixture in &written {
+        println!("{} {}", "+".green(), fixture.display());
+    }
+    println!(
+        "\n{} {} written, {} already present in {}",
+        "✓".green(),
+        written.len(),
+        fixtures.len() - written.len(),
+        out_dir.display()
+    );
+    println!("Review the arguments for personal paths before committing them.");
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/gui.rs
+//!
+//! `gui`: launching the graphical user interface
+
+use colored::*;
+use hypr_keybind_manager::ui::App;
+use std::path::Path;
+
+/// Launches the graphical user interface.
+///
+/// Creates and runs the GTK4 application window for visual keybinding
+/// management with real-time conflict detection and editing capabilities.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file, or `None` to discover it
+/// * `strict` - Block writes while the config has parse diagnostics
+///
+/// # Returns
+///
+/// * `Ok(())` - GUI closed successfully
+/// * `Err(_)` - Failed to create or run application
+///
+/// # Blocking
+///
+/// This function blocks until the GUI window is closed by the user.
+pub fn launch_gui(config_path: Option<&Path>, strict: bool) -> anyhow::Result<()> {
+    eprintln!("{} Launching GUI...", "→".cyan());
+
+    // Create and run app; it discovers the config when none is given
+    let app = App::new(config_path.map(Path::to_path_buf))
+        .map_err(|e| anyhow::anyhow!("Failed to create app: {}", e))?;
+    app.set_strict_mode(strict);
+
+    app.run();
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/import.rs
+//!
+//! `import`: merging exported or foreign bindings into a config
+//!
+//! Imported bindings are reviewed like a GUI import before any of them is
+//! written.
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::{
+        export::ExportFormat,
+        import_review::{ImportMode, ImportReview, ImportVerdict},
+        interop::ForeignConfig,
+        validator::ConfigValidator,
+    },
+    core::parser::parse_config_with_sources,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{backup_location, confirm, open_config, print_diff};
+
+/// Imports bindings into the config after reviewing them.
+///
+/// The file is read as an export or Hyprland config (by its extension), or
+/// translated from sway or i3 with `from`. Every binding is reviewed like a
+/// GUI import: blocked bindings and lines that could not be read are
+/// listed and left out, and dangerous commands are only imported when
+/// confirmed (never with `yes`).
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `file` - File to import (supports tilde expansion)
+/// * `from` - Window manager of a foreign config; `None` for an export
+/// * `replace` - Replace the config's own bindings instead of merging
+/// * `yes` - Skip the confirmation prompts
+/// * `dry_run` - Show the diff without writing
+///
+/// # Returns
+///
+/// * `Err(_)` - A file cannot be read or parsed, or the config not written
+pub fn import_bindings(
+    config_path: &Path,
+    file: &Path,
+    from: Option<ForeignConfig>,
+    replace: bool,
+    yes: bool,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let expand = |path: &Path| -> anyhow::Result<PathBuf> {
+        let path = path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?;
+        Ok(PathBuf::from(shellexpand::tilde(path).as_ref()))
+    };
+    let path = expand(config_path)?;
+    let file = expand(file)?;
+
+    let import_content = fs::read_to_string(&file)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file.display(), e))?;
+    let imported = match from {
+        Some(from) => from.translate(&import_content),
+        None => ExportFormat::from_path(&file).read(&import_content, &file)?,
+    };
+    let mut review = ImportReview::new(imported.bindings, &ConfigValidator::new());
+    review.unreadable = imported.errors;
+
+    println!(
+        "{} Read {} binding{} from {}",
+        "→".cyan(),
+        review.entries.len(),
+        if review.entries.len() == 1 { "" } else { "s" },
+        file.display()
+    );
+    for error in &review.unreadable {
+        println!("  {} Skipped {}", "✗".yellow(), error);
+    }
+    for entry in &review.entries {
+        match &entry.verdict {
+            ImportVerdict::Blocked(reason) => {
+                println!("  {} Left out {}: {}", "⛔".red(), entry.binding, reason)
+            }
+            ImportVerdict::Dangerous(assessment) => println!(
+                "  {} Dangerous {}: {}",
+                "⚠".yellow(),
+                entry.binding,
+                assessment.reason
+            ),
+            ImportVerdict::Accepted => {}
+        }
+    }
+
+    let dangerous = review.dangerous().count();
+    let acknowledged = dangerous > 0
+        && !yes
+        && !dry_run
+        && confirm(&format!(
+            "Also import the {} dangerous command{}?",
+            dangerous,
+            if dangerous == 1 { "" } else { "s" }
+        ))?;
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let current = parse_config_with_sources(&content, &path)?;
+    let mode = if replace {
+        ImportMode::Replace
+    } else {
+        ImportMode::Merge
+    };
+    let (bindings, added) = review.apply_to(current, mode, acknowledged);
+    if added == 0 && !replace {
+        println!("{} Nothing new to import", "✓".green());
+        return Ok(());
+    }
+
+    let mut manager = open_config(&path)?;
+    if dry_run {
+        println!();
+        print_diff(&manager.preview_bindings(&bindings)?, &path);
+        println!("\n{}", "Dry run: nothing was written".dimmed());
+        return Ok(());
+    }
+    if !yes
+        && !confirm(&format!(
+            "Import {} binding{} into {}?",
+            added,
+            if added == 1 { "" } else { "s" },
+            path.display()
+        ))?
+    {
+        return Ok(());
+    }
+
+    manager.write_bindings(&bindings)?;
+    println!(
+        "{} Imported {} binding{}; a backup of the previous config is in {}",
+        "✓".green(),
+        added,
+        if added == 1 { "" } else { "s" },
+        backup_location(&path).display()
+    );
+    Ok(())
+}
@@ -0,0 +1,72 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/list.rs
+//!
+//! `list`: the bindings of a config as tables
+//!
+//! Rendering is left to [`super::table`].
+
+use colored::*;
+use hypr_keybind_manager::core::parser::parse_config_with_sources;
+use std::{fs, path::Path};
+
+use super::table::{render_by_category, TableLayout};
+
+/// Lists all keybindings from the configuration file.
+///
+/// Parses the Hyprland config and displays all keybindings as an aligned,
+/// colourised table. Long arguments wrap to fit the terminal by default.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `layout` - How to handle rows wider than the terminal
+///
+/// # Returns
+///
+/// * `Ok(())` - Successfully listed bindings
+/// * `Err(_)` - File read or parse error
+pub fn list_keybindings(
+    config_path: &Path,
+    layout: TableLayout,
+    numbered: bool,
+) -> anyhow::Result<()> {
+    // Expand tilde in path
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+
+    // Read and parse
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+
+    let bindings = parse_config_with_sources(&content, path)?;
+
+    println!(
+        "{}",
+        format!("Keybindings from: {}\n", path.display()).bold()
+    );
+
+    // Width is detected from the terminal (None when piped); categorised
+    // bindings get a table per category
+    println!("{}", render_by_category(&bindings, layout, None, numbered));
+
+    println!("\n{} Total: {} bindings", "✓".green(), bindings.len());
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/mod.rs
+//!
+//! Subcommands of the command-line interface
+//!
+//! Everything here is only used by the `hypr-keybind-manager` binary. Each
+//! module handles one subcommand (or a few closely related ones), while
+//! `main.rs` only parses the arguments and dispatches. Helpers shared by
+//! several subcommands live in this module. Business logic stays in the
+//! library crate.
+
+pub mod backup;
+pub mod cheatsheet;
+pub mod check;
+pub mod coverage;
+pub mod doctor;
+pub mod edit;
+pub mod export;
+pub mod find;
+pub mod fixtures;
+pub mod gui;
+pub mod import;
+pub mod list;
+pub mod profile;
+pub mod query;
+pub mod rules;
+pub mod run;
+pub mod split;
+pub mod stats;
+pub mod table;
+pub mod undo;
+pub mod watch;
+
+#[cfg(test)]
+mod tests;
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::{
+        backup::BackupMode, diff::ConfigDiff, discovery::discover_config, format_bind_line,
+        paths::RuntimePaths, settings::Settings, state::AppState, ConfigManager,
+    },
+    core::{
+        compare::{ComparisonRow, RowStatus},
+        conflict::ConflictSeverity,
+        parser::parse_config_report,
+        Keybinding,
+    },
+};
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+
+/// Colours text by conflict severity: red for high, yellow for medium and
+/// dimmed for low
+pub fn severity_colour(severity: ConflictSeverity, text: &str) -> ColoredString {
+    match severity {
+        ConflictSeverity::High => text.red().bold(),
+        ConflictSeverity::Medium => text.yellow(),
+        ConflictSeverity::Low => text.dimmed(),
+    }
+}
+
+/// Formats where a binding was read from, for the end of a report line
+///
+/// # Returns
+///
+/// `" (file:line)"`, or an empty string for a binding without a location
+pub fn location_suffix(binding: &Keybinding) -> String {
+    binding
+        .location
+        .as_ref()
+        .map(|location| format!(" ({})", location))
+        .unwrap_or_default()
+}
+
+/// Reads the saved preferences; an unreadable file gives the defaults
+pub fn load_settings() -> Settings {
+    Settings::default_path()
+        .and_then(|settings| Settings::load(&settings).ok())
+        .unwrap_or_default()
+}
+
+/// Refuses to write to a config with parse diagnostics in strict mode
+///
+/// Strict mode is on with `--strict` or the `strict` preference. Writes
+/// rewrite every bind line, so a line the parser only partly understood
+/// would be written back in its misread form. The diagnostics are listed
+/// before the error.
+///
+/// # Arguments
+///
+/// * `path` - Config file about to be written
+/// * `strict` - Whether `--strict` was given
+///
+/// # Returns
+///
+/// * `Ok(())` - Not in strict mode, or the config has no diagnostics
+/// * `Err(_)` - The config has diagnostics, or cannot be read
+pub fn ensure_writable(path: &Path, strict: bool) -> anyhow::Result<()> {
+    if !strict && !load_settings().strict {
+        return Ok(());
+    }
+
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let diagnostics = parse_config_report(&content, path).diagnostics;
+    if diagnostics.is_empty() {
+        return Ok(());
+    }
+
+    for diagnostic in &diagnostics {
+        eprintln!("{} {}", "⚠".yellow(), diagnostic);
+    }
+    anyhow::bail!(
+        "Strict mode: resolve {} parse diagnostic{} before making changes",
+        diagnostics.len(),
+        if diagnostics.len() == 1 { "" } else { "s" }
+    )
+}
+
+/// Returns `--config`, or the config discovered from the environment
+///
+/// See [`hypr_keybind_manager::config::discovery`] for the locations tried.
+pub fn resolve_config(config: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    Ok(discover_config(config.as_deref())?)
+}
+
+/// Opens a config for writing, backed up as the preferences say
+pub fn open_config(path: &Path) -> anyhow::Result<ConfigManager> {
+    let settings = load_settings();
+    let mut manager = ConfigManager::new(path.to_path_buf())?
+        .with_backup_mode(settings.backup_mode)
+        .with_backup_compression(settings.backup_compression)
+        .with_retention(settings.backup_retention);
+    if let Some(state_path) = AppState::default_path() {
+        manager.set_state_path(state_path);
+    }
+    Ok(manager)
+}
+
+/// Returns where writes to a config are backed up
+///
+/// The backup directory, or with git backups the repository directory.
+pub fn backup_location(path: &Path) -> PathBuf {
+    match load_settings().backup_mode {
+        BackupMode::Timestamped => RuntimePaths::backup_dir(path).unwrap_or_default(),
+        BackupMode::Git => path.parent().map(Path::to_path_buf).unwrap_or_default(),
+    }
+}
+
+/// Asks a yes/no question on the terminal; anything but "y" means no
+pub fn confirm(question: &str) -> anyhow::Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Prints a config diff in unified format, coloured
+///
+/// # Arguments
+///
+/// * `diff` - Changes to the file
+/// * `path` - The file, named in the `---` / `+++` lines
+pub fn print_diff(diff: &ConfigDiff, path: &Path) {
+    let label = path.display().to_string();
+    for line in diff.to_unified(&label, &format!("{} (new)", label)).lines() {
+        match line.chars().next() {
+            Some('@') => println!("{}", line.cyan()),
+            Some('+') => println!("{}", line.green()),
+            Some('-') => println!("{}", line.red()),
+            Some('\\') => println!("{}", line.dimmed()),
+            _ => println!("{}", line),
+        }
+    }
+}
+
+/// Prints the rows of a comparison that differ
+///
+/// # Returns
+///
+/// Whether any row differs
+pub fn print_comparison(rows: &[ComparisonRow]) -> bool {
+    let mut any = false;
+    for row in rows
+        .iter()
+        .filter(|row| row.status() != RowStatus::Unchanged)
+    {
+        any = true;
+        let marker = row.status().marker();
+        let marker = match row.status() {
+            RowStatus::Added => marker.green(),
+            RowStatus::Removed => marker.red(),
+            _ => marker.yellow(),
+        };
+        match &row.submap {
+            Some(submap) => println!("{} {} (submap {})", marker, row.key_combo, submap),
+            None => println!("{} {}", marker, row.key_combo),
+        }
+        for binding in &row.left {
+            println!("    {} {}", "−".red(), format_bind_line(binding));
+        }
+        for binding in &row.right {
+            println!("    {} {}", "+".green(), format_bind_line(binding));
+        }
+    }
+    any
+}
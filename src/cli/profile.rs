@@ -0,0 +1,232 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/profile.rs
+//!
+//! `profile`: saving and switching between sets of bindings
+
+use clap::Subcommand;
+use colored::*;
+use hypr_keybind_manager::{
+    config::{paths::RuntimePaths, profiles::ProfileStore, validator::ConfigValidator},
+    core::{compare::compare_bindings, parser::parse_config_with_sources, Keybinding},
+};
+use std::{fs, path::PathBuf};
+
+use super::{confirm, ensure_writable, open_config, print_comparison, print_diff, resolve_config};
+
+#[derive(Subcommand)]
+pub enum ProfileAction {
+    /// Save the config's bindings as a profile (replacing one of that name)
+    Save {
+        /// Profile name (letters, digits, `-` and `_`)
+        name: String,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+
+    /// List the saved profiles
+    List,
+
+    /// Replace the config's bindings with a profile's
+    Apply {
+        /// Profile to switch to
+        name: String,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Switch without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Refuse to write while the config has parse diagnostics (also the `strict` preference)
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Show how two profiles differ (or a profile and the config)
+    Diff {
+        /// Profile on the left side
+        left: String,
+
+        /// Profile on the right side (default: the config's bindings)
+        right: Option<String>,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+}
+
+/// Saves, lists, applies or compares keybinding profiles.
+///
+/// Profiles live under [`ProfileStore::default_dir`]. Applying one rewrites
+/// the config's own bindings in one transaction (with a backup), after
+/// showing what changes; bindings from sourced files are kept.
+///
+/// # Arguments
+///
+/// * `action` - Profile subcommand and its options
+///
+/// # Returns
+///
+/// * `Ok(())` - Done, or the switch was declined
+/// * `Err(_)` - Unknown profile, invalid profile or config, or write error
+pub fn manage_profiles(action: ProfileAction) -> anyhow::Result<()> {
+    let store = ProfileStore::new(
+        ProfileStore::default_dir()
+            .ok_or_else(|| anyhow::anyhow!("Cannot locate the profiles directory"))?,
+    );
+    let read_config = |config: Option<PathBuf>| -> anyhow::Result<(PathBuf, Vec<Keybinding>)> {
+        let config_path = resolve_config(config)?;
+        let path = PathBuf::from(
+            shellexpand::tilde(
+                config_path
+                    .to_str()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+            )
+            .as_ref(),
+        );
+        let content =
+            fs::read_to_string(&path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+        let bindings = parse_config_with_sources(&content, &path)?;
+        Ok((path, bindings))
+    };
+
+    match action {
+        ProfileAction::Save { name, config } => {
+            let (path, bindings) = read_config(config)?;
+            store.save(&name, &bindings)?;
+            let saved = bindings
+                .iter()
+                .filter(|binding| binding.source_file.is_none())
+                .count();
+            println!(
+                "{} Saved {} binding{} from {} as profile {}",
+                "✓".green(),
+                saved,
+                if saved == 1 { "" } else { "s" },
+                path.display(),
+                name.bold()
+            );
+        }
+        ProfileAction::List => {
+            let names = store.list()?;
+            if names.is_empty() {
+                println!("{}", "No profiles saved yet".dimmed());
+            }
+            for name in names {
+                let count = store.load(&name).map(|bindings| bindings.len());
+                match count {
+                    Ok(count) => println!("  {} ({} bindings)", name.bold(), count),
+                    Err(e) => println!("  {} {}", name.bold(), e.to_string().red()),
+                }
+            }
+        }
+        ProfileAction::Apply {
+            name,
+            config,
+            yes,
+            dry_run,
+            strict,
+        } => {
+            let (path, current) = read_config(config)?;
+            ensure_writable(&path, strict)?;
+            let mut profile = store.load(&name)?;
+
+            let report = ConfigValidator::new().validate_bindings(&profile);
+            if report.has_errors() || report.has_critical_dangers() {
+                anyhow::bail!(
+                    "Profile {} does not pass validation; fix it before applying it",
+                    name
+                );
+            }
+
+            let own: Vec<Keybinding> = current
+                .iter()
+                .filter(|binding| binding.source_file.is_none())
+                .cloned()
+                .collect();
+            let rows = compare_bindings(&own, &profile);
+            if !print_comparison(&rows) {
+                println!(
+                    "{} {} already matches profile {}",
+                    "✓".green(),
+                    path.display(),
+                    name
+                );
+                return Ok(());
+            }
+
+            profile.extend(
+                current
+                    .into_iter()
+                    .filter(|binding| binding.source_file.is_some()),
+            );
+            let mut manager = open_config(&path)?;
+            if dry_run {
+                println!();
+                print_diff(&manager.preview_bindings(&profile)?, &path);
+                println!("\n{}", "Dry run: nothing was written".dimmed());
+                return Ok(());
+            }
+            if !yes && !confirm(&format!("Switch to profile {}?", name))? {
+                return Ok(());
+            }
+
+            manager.write_bindings(&profile)?;
+            println!(
+                "{} Switched to profile {}; a backup of the previous config is in {}",
+                "✓".green(),
+                name.bold(),
+                RuntimePaths::backup_dir(&path)
+                    .unwrap_or_default()
+                    .display()
+            );
+        }
+        ProfileAction::Diff {
+            left,
+            right,
+            config,
+        } => {
+            let right_bindings = match &right {
+                Some(right) => store.load(right)?,
+                None => read_config(config)?
+                    .1
+                    .into_iter()
+                    .filter(|binding| binding.source_file.is_none())
+                    .collect(),
+            };
+            let rows = compare_bindings(&store.load(&left)?, &right_bindings);
+            if !print_comparison(&rows) {
+                println!(
+                    "{} {} and {} bind the same",
+                    "✓".green(),
+                    left,
+                    right.as_deref().unwrap_or("the config")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
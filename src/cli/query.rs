@@ -0,0 +1,71 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/query.rs
+//!
+//! `query`: jq-style queries of the bindings for scripts
+
+use hypr_keybind_manager::core::{
+    conflict::ConflictDetector,
+    parser::parse_config_with_sources,
+    query::{query_model, Query},
+};
+use std::{fs, path::Path};
+
+/// Prints the results of a query over the parsed bindings.
+///
+/// Nothing but the results is printed, one per line, so the output can be
+/// piped straight into other tools: strings as raw text (unless `json`),
+/// everything else as compact JSON. See [`hypr_keybind_manager::core::query`]
+/// for the syntax.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `query` - The query text
+/// * `json` - Print strings as JSON strings too
+///
+/// # Returns
+///
+/// * `Ok(())` - Query ran (possibly without results)
+/// * `Err(_)` - File read, parse or query error
+pub fn run_query(config_path: &Path, query: &str, json: bool) -> anyhow::Result<()> {
+    // Parse the query first, so a typo fails before any file is read
+    let query: Query = query.parse()?;
+
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, path)?;
+
+    let mut detector = ConflictDetector::new();
+    for binding in &bindings {
+        detector.add_binding(binding.clone());
+    }
+    let model = query_model(&bindings, &detector.find_conflicts());
+
+    for result in query.run(&model)? {
+        match result {
+            serde_json::Value::String(text) if !json => println!("{}", text),
+            other => println!("{}", other),
+        }
+    }
+
+    Ok(())
+}
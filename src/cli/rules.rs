@@ -0,0 +1,58 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/rules.rs
+//!
+//! `update-rules`: installing updates of the danger detection rules
+
+use colored::*;
+use hypr_keybind_manager::config::danger::updates::{RulesChannel, CHANNEL_FILE, INSTALLED_FILE};
+
+/// Fetches, verifies and installs the danger rule update.
+///
+/// Only runs when a rules channel is configured; without one, danger
+/// detection stays on the built-in rules and nothing is fetched.
+///
+/// # Returns
+///
+/// * `Ok(())` - Ruleset installed, or no channel configured
+/// * `Err(_)` - Invalid channel, failed download, bad signature or rollback
+pub fn update_rules() -> anyhow::Result<()> {
+    let dir = RulesChannel::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Neither XDG_CONFIG_HOME nor HOME is set"))?;
+    let channel_path = dir.join(CHANNEL_FILE);
+
+    let Some(channel) = RulesChannel::load(&channel_path)? else {
+        println!(
+            "{} No rules channel configured ({}); using the built-in rules only",
+            "→".cyan(),
+            channel_path.display()
+        );
+        return Ok(());
+    };
+
+    println!("{} Fetching rules from {}", "→".cyan(), channel.url);
+    let signed = channel.fetch()?;
+    let rules = channel.install(&signed, &dir.join(INSTALLED_FILE))?;
+
+    println!(
+        "{} Installed danger rules version {} ({} patterns, {} dangerous and {} suspicious commands)",
+        "✓".green(),
+        rules.version,
+        rules.patterns.len(),
+        rules.dangerous_commands.len(),
+        rules.suspicious_commands.len()
+    );
+    Ok(())
+}
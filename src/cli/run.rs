@@ -0,0 +1,124 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/run.rs
+//!
+//! `run`: running the binding that best matches a name
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::danger::{DangerDetector, DangerLevel},
+    core::{palette::find_bindings, parser::parse_config_with_sources, Keybinding},
+    ipc::{ClientMode, HyprlandClient},
+};
+use std::{fs, path::Path};
+
+use super::{confirm, location_suffix};
+
+/// Runner-up matches `run` lists below the one it runs
+const RUN_ALTERNATIVES: usize = 3;
+
+/// Runs the binding that best matches a query.
+///
+/// Shows the match (and the runners-up, in case it picked the wrong one),
+/// then dispatches its action over IPC. An `exec` command the danger
+/// detector rates suspicious or worse needs confirmation first.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `query` - Fuzzy name of the binding
+/// * `yes` - Skip the confirmation for risky commands
+/// * `dry_run` - Show the match without dispatching
+///
+/// # Returns
+///
+/// * `Ok(())` - Binding dispatched, or the user declined
+/// * `Err(_)` - File read or parse error, no match, or IPC failure
+pub fn run_binding(
+    config_path: &Path,
+    query: &str,
+    yes: bool,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    // Expand tilde in path
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, path)?;
+
+    let matches = find_bindings(&bindings, query);
+    let Some(best) = matches.first() else {
+        anyhow::bail!("No binding matches '{}'", query);
+    };
+    let binding = best.binding;
+
+    let action = |binding: &Keybinding| match &binding.args {
+        Some(args) => format!("{} {}", binding.dispatcher, args),
+        None => binding.dispatcher.clone(),
+    };
+    println!(
+        "{} {}  {}{}",
+        "→".cyan(),
+        binding.key_combo.to_string().bold(),
+        action(binding),
+        location_suffix(binding).dimmed()
+    );
+    if let Some(description) = &binding.description {
+        println!("  {}", description.italic());
+    }
+    for other in matches.iter().skip(1).take(RUN_ALTERNATIVES) {
+        println!(
+            "  {} {}  {}",
+            "also:".dimmed(),
+            other.binding.key_combo,
+            action(other.binding).dimmed()
+        );
+    }
+
+    // Same danger assessment the validator applies to exec bindings
+    if binding.dispatcher == "exec" {
+        if let Some(args) = &binding.args {
+            let danger = DangerDetector::with_installed_rules().assess_command(args);
+            if danger.danger_level >= DangerLevel::Suspicious {
+                println!(
+                    "\n{} {:?}: {}",
+                    "⚠".yellow(),
+                    danger.danger_level,
+                    danger.reason
+                );
+                if !dry_run && !yes && !confirm("Run it anyway?")? {
+                    println!("{} Not run", "✗".red());
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    if dry_run {
+        println!("{} Dry run: nothing dispatched", "✓".green());
+        return Ok(());
+    }
+
+    HyprlandClient::new(ClientMode::Live).dispatch(binding)?;
+    println!("{} Dispatched", "✓".green());
+
+    Ok(())
+}
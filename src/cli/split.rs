@@ -0,0 +1,117 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/split.rs
+//!
+//! `split`: moving the bindings of a config into a sourced file
+
+use colored::*;
+use hypr_keybind_manager::config::{diff::ConfigDiff, split::plan_split};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{backup_location, confirm, open_config, print_diff};
+
+/// Moves the bindings of a config into a new file the config sources.
+///
+/// Shows what will move (bind lines, variables, the new `source` line) and
+/// asks before applying the split with [`ConfigManager::split_bindings`],
+/// which backs up the config and checks that every binding survives.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `into` - Bindings file to create (supports tilde expansion)
+/// * `yes` - Split without asking
+/// * `dry_run` - Only show the plan and the diffs of both files
+///
+/// # Returns
+///
+/// * `Ok(())` - Config split, or the split declined or only previewed
+/// * `Err(_)` - The split is not possible, or reading or writing failed
+pub fn split_config(
+    config_path: &Path,
+    into: &Path,
+    yes: bool,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let expand = |path: &Path| -> anyhow::Result<PathBuf> {
+        let path = path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?;
+        Ok(PathBuf::from(shellexpand::tilde(path).as_ref()))
+    };
+    let path = expand(config_path)?;
+    let binds_path = expand(into)?;
+    if binds_path.exists() {
+        anyhow::bail!("{} already exists", binds_path.display());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let plan = plan_split(&content, &path, &binds_path)?;
+
+    println!(
+        "{} Moving {} bind line{} from {} to {}",
+        "→".cyan(),
+        plan.moved_lines,
+        if plan.moved_lines == 1 { "" } else { "s" },
+        path.display(),
+        binds_path.display()
+    );
+    if !plan.moved_variables.is_empty() {
+        println!("  Variables moved: ${}", plan.moved_variables.join(", $"));
+    }
+    if !plan.copied_variables.is_empty() {
+        println!(
+            "  Variables copied (still used by the config): ${}",
+            plan.copied_variables.join(", $")
+        );
+    }
+    if let Some(source_line) = plan
+        .main_content
+        .lines()
+        .find(|line| line.starts_with("source = "))
+    {
+        println!("  The config will source it with: {}", source_line.bold());
+    }
+
+    if dry_run {
+        println!();
+        print_diff(&ConfigDiff::between(&content, &plan.main_content), &path);
+        print_diff(&ConfigDiff::between("", &plan.binds_content), &binds_path);
+        println!("\n{}", "Dry run: nothing was written".dimmed());
+        return Ok(());
+    }
+    if !yes && !confirm("Split the config?")? {
+        return Ok(());
+    }
+
+    open_config(&path)?.split_bindings(&binds_path)?;
+
+    println!(
+        "{} Config split; a backup of the original is in {}",
+        "✓".green(),
+        backup_location(&path).display()
+    );
+    println!(
+        "  Bindings in {} are read-only when managing {}; edit them with:",
+        binds_path.display(),
+        path.display()
+    );
+    println!("  hypr-keybind-manager gui -c {}", binds_path.display());
+    Ok(())
+}
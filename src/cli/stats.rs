@@ -0,0 +1,112 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/stats.rs
+//!
+//! `stats`: statistics about the bindings of a config
+
+use colored::*;
+use hypr_keybind_manager::core::{parser::parse_config_with_sources, stats::BindingStats};
+use std::{fs, path::Path};
+
+use super::location_suffix;
+
+/// Prints statistics about the config's bindings.
+///
+/// See [`hypr_keybind_manager::core::stats`] for what is counted.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `json` - Print the statistics as pretty JSON instead
+pub fn show_stats(config_path: &Path, json: bool) -> anyhow::Result<()> {
+    // Expand tilde in path
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = Path::new(expanded_path.as_ref());
+
+    let content =
+        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
+    let bindings = parse_config_with_sources(&content, path)?;
+    let stats = BindingStats::collect(&bindings);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} {} bindings, {} with a description\n",
+        "Statistics:".bold(),
+        stats.total,
+        stats.described
+    );
+
+    let percent = |count: usize| count as f64 * 100.0 / stats.total.max(1) as f64;
+    for (title, tallies) in [
+        ("By modifiers", &stats.by_modifiers),
+        ("By dispatcher", &stats.by_dispatcher),
+        ("By submap", &stats.by_submap),
+    ] {
+        println!("{}", title.bold());
+        for tally in tallies {
+            println!(
+                "  {:<24} {:>4}  {}",
+                tally.name,
+                tally.count,
+                format!("{:.0}%", percent(tally.count)).dimmed()
+            );
+        }
+        println!();
+    }
+
+    println!(
+        "{} {}",
+        "Free keys".bold(),
+        "(letters, digits and F1-F12, outside submaps)".dimmed()
+    );
+    for space in &stats.modifier_space {
+        let free = format!("{:>2} free", space.free);
+        println!(
+            "  {:<24} {}  {}",
+            space.modifiers,
+            if space.free == 0 {
+                free.red()
+            } else {
+                free.green()
+            },
+            format!("{} used", space.used).dimmed()
+        );
+    }
+
+    if !stats.longest_args.is_empty() {
+        println!("\n{}", "Longest arguments".bold());
+        for binding in &stats.longest_args {
+            let args = binding.args.as_deref().unwrap_or_default();
+            println!(
+                "  {:>4}  {}  {} {}{}",
+                args.chars().count(),
+                binding.key_combo.to_string().cyan(),
+                binding.dispatcher,
+                args,
+                location_suffix(binding).dimmed()
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,204 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/table.rs
+//!
+//! Keybinding table rendering for `list`
+//!
+//! Uses comfy-table for column alignment, terminal width detection and
+//! wrapping. Three layouts are supported:
+//! - **Wrap** (default): long cells wrap onto extra lines to fit the terminal
+//! - **Truncate**: every binding stays on one line, long cells end in `…`
+//! - **Wide**: no width limit at all (useful when piping to `less -S`)
+//!
+//! When stdout is not a terminal no width is detected and the table is
+//! rendered at its natural width, so piped output is never cut short.
+//!
+//! Bindings tagged with `# @category:` annotations are listed in one table
+//! per category ([`render_by_category`]). Configs with `device` sections get
+//! a device column. Numbered tables start with each binding's position in
+//! the config, the number `rm` and `edit` select it by.
+
+use colored::Colorize;
+use comfy_table::{
+    presets::UTF8_HORIZONTAL_ONLY, Attribute, Cell, Color, ContentArrangement, Row, Table,
+    TableComponent,
+};
+use hypr_keybind_manager::core::Keybinding;
+use std::collections::BTreeMap;
+
+/// Heading of the bindings without a category
+const UNCATEGORISED: &str = "Uncategorised";
+
+/// Device column text of bindings outside any `device` section
+const ALL_DEVICES: &str = "all devices";
+
+/// How the table should cope with content wider than the terminal
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TableLayout {
+    /// Wrap long cells onto additional lines
+    #[default]
+    Wrap,
+    /// Keep one line per binding, truncating long cells
+    Truncate,
+    /// Ignore the terminal width entirely
+    Wide,
+}
+
+/// Renders keybindings as an aligned table
+///
+/// # Arguments
+///
+/// * `bindings` - Keybindings to render, in display order
+/// * `layout` - Wrapping behaviour for content wider than the terminal
+/// * `width` - Explicit table width; `None` detects the terminal width
+///
+/// # Returns
+///
+/// The rendered table, without a trailing newline
+#[cfg(test)]
+pub fn render_bindings(bindings: &[Keybinding], layout: TableLayout, width: Option<u16>) -> String {
+    let rows: Vec<(usize, &Keybinding)> = bindings.iter().enumerate().collect();
+    render_rows(&rows, layout, width, false)
+}
+
+/// Renders bindings with their 1-based position in the config
+///
+/// # Arguments
+///
+/// * `rows` - Bindings to render with their 0-based index in the config
+/// * `layout` - Wrapping behaviour for content wider than the terminal
+/// * `width` - Explicit table width; `None` detects the terminal width
+/// * `numbered` - Whether the first column shows the positions
+fn render_rows(
+    rows: &[(usize, &Keybinding)],
+    layout: TableLayout,
+    width: Option<u16>,
+    numbered: bool,
+) -> String {
+    let mut table = Table::new();
+    table.load_preset(UTF8_HORIZONTAL_ONLY);
+    // Only the header separator and outer rules; no line between every row
+    table.remove_style(TableComponent::HorizontalLines);
+    table.remove_style(TableComponent::MiddleIntersections);
+    table.remove_style(TableComponent::LeftBorderIntersections);
+    table.remove_style(TableComponent::RightBorderIntersections);
+    table.set_truncation_indicator("…");
+
+    match layout {
+        TableLayout::Wrap | TableLayout::Truncate => {
+            table.set_content_arrangement(ContentArrangement::Dynamic);
+            if let Some(width) = width {
+                table.set_width(width);
+            }
+        }
+        TableLayout::Wide => {
+            table.set_content_arrangement(ContentArrangement::Disabled);
+        }
+    }
+
+    // Only configs with `device` sections get a device column
+    let show_device = rows.iter().any(|(_, binding)| binding.device.is_some());
+    let mut header = vec!["Keys", "Type", "Dispatcher", "Arguments"];
+    if numbered {
+        header.insert(0, "#");
+    }
+    if show_device {
+        header.push("Device");
+    }
+    table.set_header(
+        header
+            .into_iter()
+            .map(|title| Cell::new(title).add_attribute(Attribute::Bold)),
+    );
+
+    for (index, binding) in rows {
+        let mut cells = vec![
+            Cell::new(binding.key_combo.to_string())
+                .fg(Color::Cyan)
+                .add_attribute(Attribute::Bold),
+            Cell::new(binding.bind_type.to_string()).fg(Color::Magenta),
+            Cell::new(&binding.dispatcher).fg(Color::Green),
+            Cell::new(binding.args.as_deref().unwrap_or("")),
+        ];
+        if numbered {
+            cells.insert(0, Cell::new(index + 1).add_attribute(Attribute::Dim));
+        }
+        if show_device {
+            cells.push(match &binding.device {
+                Some(device) => Cell::new(device).fg(Color::Yellow),
+                None => Cell::new(ALL_DEVICES).add_attribute(Attribute::Dim),
+            });
+        }
+        let mut row = Row::from(cells);
+
+        if layout == TableLayout::Truncate {
+            row.max_height(1);
+        }
+
+        table.add_row(row);
+    }
+
+    table.to_string()
+}
+
+/// Renders keybindings as one table per category
+///
+/// Categories are sorted by name, each under a heading with its binding
+/// count; bindings without a category come last. Without any categories
+/// this is a single table without a heading.
+///
+/// # Arguments
+///
+/// * `bindings` - Keybindings to render, in display order
+/// * `layout` - Wrapping behaviour for content wider than the terminal
+/// * `width` - Explicit table width; `None` detects the terminal width
+/// * `numbered` - Start each row with the binding's 1-based position in
+///   `bindings`, which stays the same when categories regroup them
+pub fn render_by_category(
+    bindings: &[Keybinding],
+    layout: TableLayout,
+    width: Option<u16>,
+    numbered: bool,
+) -> String {
+    let mut categories: BTreeMap<&str, Vec<(usize, &Keybinding)>> = BTreeMap::new();
+    let mut uncategorised = Vec::new();
+    for row in bindings.iter().enumerate() {
+        match &row.1.category {
+            Some(category) => categories.entry(category).or_default().push(row),
+            None => uncategorised.push(row),
+        }
+    }
+    if categories.is_empty() {
+        return render_rows(&uncategorised, layout, width, numbered);
+    }
+
+    let mut sections: Vec<(&str, Vec<(usize, &Keybinding)>)> = categories.into_iter().collect();
+    if !uncategorised.is_empty() {
+        sections.push((UNCATEGORISED, uncategorised));
+    }
+
+    sections
+        .iter()
+        .map(|(category, rows)| {
+            let heading = format!("{} ({})", category, rows.len());
+            format!(
+                "{}\n{}",
+                heading.bold(),
+                render_rows(rows, layout, width, numbered)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
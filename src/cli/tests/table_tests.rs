@@ -0,0 +1,130 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Table rendering tests
+//!
+//! Tests for width-aware `list` output
+
+use hypr_keybind_manager::core::{KeyCombo, Keybinding, Modifier};
+
+use crate::cli::table::{render_bindings, render_by_category, TableLayout};
+
+const LONG_ARGS: &str =
+    "notify-send 'A very long notification body that will never fit on a narrow terminal'";
+
+fn sample_bindings() -> Vec<Keybinding> {
+    vec![
+        Keybinding::new(
+            KeyCombo::new(vec![Modifier::Super], "K"),
+            "exec",
+            Some(LONG_ARGS),
+        ),
+        Keybinding::new(
+            KeyCombo::new(vec![Modifier::Super, Modifier::Shift], "Q"),
+            "killactive",
+            None,
+        ),
+    ]
+}
+
+fn max_line_width(output: &str) -> usize {
+    output
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_wrap_fits_requested_width() {
+    let output = render_bindings(&sample_bindings(), TableLayout::Wrap, Some(50));
+
+    assert!(max_line_width(&output) <= 50, "Output:\n{}", output);
+    assert!(output.contains("killactive"));
+    // Wrapped, not truncated: every word of the argument is still present
+    assert!(output.contains("terminal"));
+}
+
+#[test]
+fn test_truncate_keeps_one_line_per_binding() {
+    let wrapped = render_bindings(&sample_bindings(), TableLayout::Wrap, Some(50));
+    let truncated = render_bindings(&sample_bindings(), TableLayout::Truncate, Some(50));
+
+    assert!(max_line_width(&truncated) <= 50, "Output:\n{}", truncated);
+    assert!(truncated.lines().count() < wrapped.lines().count());
+    assert!(truncated.contains('…'));
+    assert!(!truncated.contains("terminal"));
+}
+
+#[test]
+fn test_wide_ignores_width() {
+    let output = render_bindings(&sample_bindings(), TableLayout::Wide, Some(50));
+
+    assert!(output.contains(LONG_ARGS));
+    assert!(max_line_width(&output) > 50);
+}
+
+#[test]
+fn test_columns_are_aligned() {
+    let output = render_bindings(&sample_bindings(), TableLayout::Wide, None);
+
+    let positions: Vec<usize> = output
+        .lines()
+        .filter(|line| line.contains("exec") || line.contains("killactive"))
+        .map(|line| {
+            let byte = line.find("bind ").unwrap();
+            line[..byte].chars().count()
+        })
+        .collect();
+
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions[0], positions[1], "Output:\n{}", output);
+}
+
+#[test]
+fn test_categories_get_a_table_each_with_uncategorised_last() {
+    let mut bindings = sample_bindings();
+    let plain = render_by_category(&bindings, TableLayout::Wide, None, false);
+    assert_eq!(plain, render_bindings(&bindings, TableLayout::Wide, None));
+
+    bindings[1].category = Some("window management".to_string());
+    bindings.push(Keybinding {
+        category: Some("media".to_string()),
+        ..bindings[1].clone()
+    });
+    let output = render_by_category(&bindings, TableLayout::Wide, None, false);
+
+    let media = output.find("media (1)").unwrap();
+    let windows = output.find("window management (1)").unwrap();
+    let uncategorised = output.find("Uncategorised (1)").unwrap();
+    assert!(media < windows && windows < uncategorised);
+    assert_eq!(output.matches("Dispatcher").count(), 3);
+}
+
+#[test]
+fn test_numbers_are_config_positions_across_categories() {
+    let mut bindings = sample_bindings();
+    bindings[0].category = Some("media".to_string());
+    let output = render_by_category(&bindings, TableLayout::Wide, None, true);
+
+    assert!(output.contains(" # "), "Output:\n{}", output);
+    // Numbers count from 1 over the whole config, not per table
+    let uncategorised = output.find("Uncategorised").unwrap();
+    assert!(
+        output[uncategorised..].contains(" 2 "),
+        "Output:\n{}",
+        output
+    );
+    assert!(!output.contains(" 0 "));
+}
@@ -0,0 +1,121 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/undo.rs
+//!
+//! `--undo-last`: undoing the last change written to a config
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::{
+        backup::{read_backup, BackupMode},
+        diff::ConfigDiff,
+    },
+    core::{compare::compare_bindings, parser::parse_config_with_sources, Keybinding},
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{confirm, load_settings, open_config, print_comparison, print_diff};
+
+/// Undoes the last change written to the config.
+///
+/// Every write backs the config up first, so the newest backup is the
+/// config as it was before the last change. The binding changes are shown
+/// and, once confirmed, that backup is restored. Restoring backs up the
+/// current config too, so running this again redoes the change.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `yes` - Restore without asking for confirmation
+/// * `dry_run` - Show the changes without writing anything
+///
+/// # Returns
+///
+/// * `Ok(())` - Change undone, nothing to undo, or not confirmed
+/// * `Err(_)` - No backup to undo to, or a read, parse or write error
+pub fn undo_last_write(config_path: &Path, yes: bool, dry_run: bool) -> anyhow::Result<()> {
+    let path = PathBuf::from(
+        shellexpand::tilde(
+            config_path
+                .to_str()
+                .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+        )
+        .as_ref(),
+    );
+    if load_settings().backup_mode == BackupMode::Git {
+        anyhow::bail!(
+            "Backups of {} are git commits; undo the last one with: git -C {} revert HEAD",
+            path.display(),
+            path.parent().unwrap_or(Path::new(".")).display()
+        );
+    }
+
+    let manager = open_config(&path)?;
+    let Some(backup) = manager.list_backups()?.into_iter().next() else {
+        anyhow::bail!("{} has no backups yet: nothing to undo", path.display());
+    };
+
+    let read = |file: &Path| {
+        fs::read_to_string(file).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))
+    };
+    let current = read(&path)?;
+    let previous = read_backup(&backup)?;
+    if current == previous {
+        println!(
+            "{} {} already matches its latest backup; nothing to undo",
+            "✓".green(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    // Backups only cover the main config, so sourced bindings are left out
+    let own_bindings = |content: &str| -> anyhow::Result<Vec<Keybinding>> {
+        Ok(parse_config_with_sources(content, &path)?
+            .into_iter()
+            .filter(|binding| binding.source_file.is_none())
+            .collect())
+    };
+    println!(
+        "{} Undoing the last change to {} (backup {})",
+        "→".cyan(),
+        path.display(),
+        backup.display()
+    );
+    let rows = compare_bindings(&own_bindings(&current)?, &own_bindings(&previous)?);
+    if !print_comparison(&rows) {
+        println!("  No binding changes; only other lines of the config differ");
+    }
+
+    if dry_run {
+        println!();
+        print_diff(&ConfigDiff::between(&current, &previous), &path);
+        println!("\n{}", "Dry run: nothing was written".dimmed());
+        return Ok(());
+    }
+    if !yes && !confirm("Undo the last change?")? {
+        return Ok(());
+    }
+
+    manager.restore_backup(&backup)?;
+    println!(
+        "{} Last change undone; run --undo-last again to redo it",
+        "✓".green()
+    );
+    Ok(())
+}
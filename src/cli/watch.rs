@@ -0,0 +1,554 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! src/cli/watch.rs
+//!
+//! `watch` and `daemon`: reacting to changes of the config
+//!
+//! `watch` checks the config after every change made by another program,
+//! reports what the change added or fixed, and compares the config with
+//! Hyprland's live bindings. `daemon` backs the config up after every
+//! change made outside this tool.
+
+use colored::*;
+use hypr_keybind_manager::{
+    config::{
+        danger::DangerLevel,
+        notifications::{self, NotificationAction, NotificationEvent},
+        paths::RuntimePaths,
+        validator::{ConfigValidator, ValidationLevel},
+    },
+    core::{
+        conflict::{Conflict, ConflictChanges, ConflictDetector},
+        drift::{find_drift, Drift},
+        parser::{diagnose_config, parse_config_with_sources},
+    },
+    ipc::{
+        debounce::ReloadDebouncer,
+        events::{EventListener, HyprlandEvent},
+        ClientMode, HyprlandClient,
+    },
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use super::{load_settings, open_config, severity_colour};
+
+/// Changes to the config closer together than this are one change
+const WATCH_SETTLE: Duration = Duration::from_millis(500);
+
+/// A backup this recent means the change was this tool's own write
+const OWN_WRITE_WINDOW: Duration = Duration::from_secs(5);
+
+/// Time Hyprland gets to reload a changed config before drift is checked
+const DRIFT_AFTER_CHANGE: Duration = Duration::from_secs(3);
+
+/// Watches the config and shows desktop notifications about it.
+///
+/// Runs until interrupted. The config is checked for problems and
+/// conflicts on start and again after every change made by another
+/// program, printing what the change added, fixed or resolved. Such
+/// changes are also reported as notifications, as a validation failure
+/// when the changed config has problems; changes
+/// this tool wrote itself (recognised by the backup every write makes) are
+/// not. Every `drift_interval` seconds, and shortly after each change, the
+/// config is compared with Hyprland's live bindings. Each kind of
+/// notification can be switched off in the preferences, which are re-read
+/// for every event.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `drift_interval` - Seconds between drift checks; 0 disables them
+///
+/// # Returns
+///
+/// * `Err(_)` - The config directory cannot be watched
+pub fn watch_config(config_path: &Path, drift_interval: u64) -> anyhow::Result<()> {
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = PathBuf::from(expanded_path.as_ref());
+    let (_watcher, rx) = watch_directory_of(&path)?;
+
+    println!(
+        "{} Watching {} (Ctrl+C to stop)",
+        "→".cyan(),
+        path.display()
+    );
+    // Starting point for the changes printed after each edit
+    let mut last_check = match fs::read_to_string(&path) {
+        Ok(content) => {
+            let check = check_watched_config(&content, &path);
+            print_watch_changes(
+                &path,
+                &WatchCheck {
+                    conflicts: Some(Vec::new()),
+                    ..WatchCheck::default()
+                },
+                &check,
+            );
+            check
+        }
+        Err(_) => WatchCheck::default(),
+    };
+
+    let client = HyprlandClient::new(ClientMode::ReadOnly);
+    let drift_interval = (drift_interval > 0).then(|| Duration::from_secs(drift_interval));
+    let mut debouncer = ReloadDebouncer::new(WATCH_SETTLE);
+    let mut next_drift_check = drift_interval.map(|_| Instant::now());
+    let mut last_drift = Drift::default();
+
+    loop {
+        // Wait for the next event, or wake up for a pending deadline
+        let now = Instant::now();
+        let timeout = [
+            debouncer.time_until_due(now),
+            next_drift_check.map(|due| due.saturating_duration_since(now)),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(Duration::from_secs(3600));
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(event)) if event.paths.contains(&path) => {
+                debouncer.request(Instant::now());
+            }
+            Ok(_) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("Stopped receiving file events for {}", path.display())
+            }
+        }
+
+        let now = Instant::now();
+        if debouncer.poll(now) {
+            if let Some(mut check) = config_changed(&path) {
+                print_watch_changes(&path, &last_check, &check);
+                if check.problems.is_empty() || check.problems != last_check.problems {
+                    notify(
+                        &path,
+                        if check.problems.is_empty() {
+                            NotificationEvent::ExternalChange {
+                                config_path: path.clone(),
+                            }
+                        } else {
+                            NotificationEvent::ValidationFailed {
+                                problems: check.problems.clone(),
+                            }
+                        },
+                    );
+                }
+                // A config that stopped parsing is compared with the last
+                // conflicts known once it parses again
+                if check.conflicts.is_none() {
+                    check.conflicts = last_check.conflicts.take();
+                }
+                last_check = check;
+            }
+            if next_drift_check.is_some() {
+                next_drift_check = Some(now + DRIFT_AFTER_CHANGE);
+            }
+        }
+
+        if next_drift_check.is_some_and(|due| due <= now) {
+            next_drift_check = drift_interval.map(|interval| now + interval);
+            if let Some(drift) = check_drift(&path, &client) {
+                if !drift.is_empty() && drift != last_drift {
+                    println!("{} Drift: {}", "⚠".yellow(), drift);
+                    notify(
+                        &path,
+                        NotificationEvent::Drift {
+                            differences: drift.describe(),
+                        },
+                    );
+                }
+                last_drift = drift;
+            }
+        }
+    }
+}
+
+/// Backs up the config whenever another program changes it.
+///
+/// Runs until interrupted, like `watch` but without notifications. Each
+/// settled change made outside this tool is saved through the backup
+/// backend chosen in the preferences: a timestamped copy (skipped when the
+/// newest backup already matches) or a git commit. The tool's own writes
+/// are left alone, as they were backed up before being made. Preferences
+/// are re-read for every change. While Hyprland is running, its own config
+/// reloads are logged too.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
+/// * `prune` - Prune old backups by the retention preferences after each backup
+///
+/// # Returns
+///
+/// * `Err(_)` - The config directory cannot be watched
+pub fn run_daemon(config_path: &Path, prune: bool) -> anyhow::Result<()> {
+    let expanded_path = shellexpand::tilde(
+        config_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
+    );
+    let path = PathBuf::from(expanded_path.as_ref());
+    let mut manager = open_config(&path)?;
+    let (_watcher, rx) = watch_directory_of(&path)?;
+
+    println!(
+        "{} Backing up {} after every change (Ctrl+C to stop)",
+        "→".cyan(),
+        path.display()
+    );
+
+    // Log Hyprland's reloads next to the backups
+    match EventListener::connect() {
+        Ok(events) => {
+            thread::spawn(move || {
+                for event in events.iter() {
+                    if event == HyprlandEvent::ConfigReloaded {
+                        println!("{} Hyprland reloaded its config", "↻".cyan());
+                    }
+                }
+                println!("{} Hyprland closed its event socket", "ℹ".blue());
+            });
+        }
+        Err(e) => println!("{} Not logging Hyprland reloads: {}", "ℹ".blue(), e),
+    }
+
+    let mut debouncer = ReloadDebouncer::new(WATCH_SETTLE);
+    // When this daemon last backed up: its own backups are no sign of a write
+    // by the tool
+    let mut last_backup = None;
+
+    loop {
+        let timeout = debouncer
+            .time_until_due(Instant::now())
+            .unwrap_or(Duration::from_secs(3600));
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(event)) if event.paths.contains(&path) => {
+                debouncer.request(Instant::now());
+            }
+            Ok(_) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                anyhow::bail!("Stopped receiving file events for {}", path.display())
+            }
+        }
+
+        if !debouncer.poll(Instant::now()) || written_by_us(&path, last_backup) {
+            continue;
+        }
+        let settings = load_settings();
+        manager.set_backup_mode(settings.backup_mode);
+        manager.set_backup_compression(settings.backup_compression);
+
+        match manager.backup_now() {
+            Ok(backup) => {
+                last_backup = Some(SystemTime::now());
+                match backup {
+                    Some(backup) => {
+                        println!("{} Backed up to {}", "✓".green(), backup.display())
+                    }
+                    None => println!("{} Committed {} to git", "✓".green(), path.display()),
+                }
+            }
+            Err(e) => {
+                eprintln!("{} Backup of {} failed: {}", "✗".red(), path.display(), e);
+                continue;
+            }
+        }
+
+        if prune {
+            match manager.prune_backups(&settings.backup_retention) {
+                Ok(deleted) if !deleted.is_empty() => {
+                    println!("  Pruned {} old backup(s)", deleted.len())
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("{} Pruning failed: {}", "✗".red(), e),
+            }
+        }
+    }
+}
+
+/// Watches the directory holding a config
+///
+/// The directory is watched rather than the file, as editors and atomic
+/// writes replace the file.
+///
+/// # Returns
+///
+/// The watcher, which stops when dropped, and its events
+fn watch_directory_of(
+    path: &Path,
+) -> anyhow::Result<(
+    RecommendedWatcher,
+    mpsc::Receiver<notify::Result<notify::Event>>,
+)> {
+    let directory = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Config path has no parent directory"))?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = RecommendedWatcher::new(
+        move |event: notify::Result<notify::Event>| {
+            let _ = tx.send(event);
+        },
+        notify::Config::default(),
+    )?;
+    watcher.watch(directory, RecursiveMode::NonRecursive)?;
+    Ok((watcher, rx))
+}
+
+/// What `watch` found in one check of the config
+#[derive(Default)]
+struct WatchCheck {
+    /// What is wrong with the config (empty if nothing)
+    problems: Vec<String>,
+    /// Conflicting key combos (`None`: the config does not parse)
+    conflicts: Option<Vec<Conflict>>,
+}
+
+/// Handles a settled change of the watched config
+///
+/// # Returns
+///
+/// * `Some(check)` - Another program changed the config; what is wrong
+///   with it now
+/// * `None` - The change was this tool's own write, or the config cannot
+///   be read (e.g. mid-replacement)
+fn config_changed(path: &Path) -> Option<WatchCheck> {
+    if written_by_us(path, None) {
+        return None;
+    }
+    let content = fs::read_to_string(path).ok()?;
+    println!("{} {} changed", "→".cyan(), path.display());
+    Some(check_watched_config(&content, path))
+}
+
+/// Parses the watched config and checks it for problems and conflicts
+fn check_watched_config(content: &str, path: &Path) -> WatchCheck {
+    let mut problems: Vec<String> = diagnose_config(content)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let mut conflicts = None;
+    match parse_config_with_sources(content, path) {
+        Ok(bindings) => {
+            let report = ConfigValidator::new().validate_bindings(&bindings);
+            problems.extend(
+                report
+                    .issues
+                    .iter()
+                    .filter(|issue| issue.validation_level == ValidationLevel::Error)
+                    .map(|issue| {
+                        let binding = &bindings[issue.binding_index];
+                        format!("{}: {}", binding.key_combo, issue.message)
+                    }),
+            );
+            problems.extend(
+                report
+                    .dangerous_commands
+                    .iter()
+                    .filter(|(_, assessment)| assessment.danger_level == DangerLevel::Critical)
+                    .map(|(index, assessment)| {
+                        format!(
+                            "{}: critical command: {}",
+                            bindings[*index].key_combo, assessment.reason
+                        )
+                    }),
+            );
+
+            let mut detector = ConflictDetector::new();
+            for binding in bindings {
+                detector.add_binding(binding);
+            }
+            let mut found = detector.find_conflicts();
+            found.sort_by_key(|conflict| conflict.key_combo.to_string());
+            conflicts = Some(found);
+        }
+        Err(e) => problems.push(format!("Config does not parse: {}", e)),
+    }
+
+    WatchCheck {
+        problems,
+        conflicts,
+    }
+}
+
+/// Prints what changed since the previous check of the watched config
+///
+/// Only new and fixed problems, and added, changed and resolved
+/// conflicts, are listed; the rest is summed up in one line. Bindings in
+/// `path` are shown by line number, those in sourced files by file name
+/// and line.
+fn print_watch_changes(path: &Path, before: &WatchCheck, after: &WatchCheck) {
+    for problem in &after.problems {
+        if !before.problems.contains(problem) {
+            println!("  {} {}", "✗".red(), problem);
+        }
+    }
+    for problem in &before.problems {
+        if !after.problems.contains(problem) {
+            println!("  {} Fixed: {}", "✓".green(), problem);
+        }
+    }
+
+    // Without a parse there is nothing to compare; the parse error is
+    // among the problems
+    let (Some(earlier), Some(conflicts)) = (&before.conflicts, &after.conflicts) else {
+        return;
+    };
+    let changes = ConflictChanges::between(earlier, conflicts);
+    let describe = |conflict: &Conflict| {
+        let submap = conflict
+            .submap
+            .as_ref()
+            .map(|submap| format!(" (submap {})", submap))
+            .unwrap_or_default();
+        let lines: Vec<String> = conflict
+            .conflicting_bindings
+            .iter()
+            .filter_map(|binding| binding.location.as_ref())
+            .map(|location| match location.file.file_name() {
+                Some(name) if location.file != path => {
+                    format!("{}:{}", name.to_string_lossy(), location.line)
+                }
+                _ => location.line.to_string(),
+            })
+            .collect();
+        let lines = if lines.is_empty() {
+            String::new()
+        } else {
+            format!(" (lines {})", lines.join(", "))
+        };
+        format!(
+            "{}{} {}{}",
+            conflict.key_combo.to_string().cyan(),
+            submap,
+            severity_colour(
+                conflict.severity,
+                &format!("[{}]", conflict.severity.label())
+            ),
+            lines.dimmed()
+        )
+    };
+    for conflict in &changes.added {
+        println!("  {} New conflict: {}", "✗".red(), describe(conflict));
+    }
+    for conflict in &changes.changed {
+        println!(
+            "  {} Conflict changed: {}",
+            "⚠".yellow(),
+            describe(conflict)
+        );
+    }
+    for conflict in &changes.resolved {
+        println!("  {} Resolved: {}", "✓".green(), describe(conflict));
+    }
+
+    let summary = format!(
+        "{} problem{}, {} conflict{}",
+        after.problems.len(),
+        if after.problems.len() == 1 { "" } else { "s" },
+        conflicts.len(),
+        if conflicts.len() == 1 { "" } else { "s" }
+    );
+    if after.problems.is_empty() && conflicts.is_empty() {
+        println!("  {} {}", "✓".green(), summary);
+    } else {
+        println!("  {}", summary.dimmed());
+    }
+}
+
+/// Returns whether this tool wrote the config just now
+///
+/// Every write by this tool first backs up the config, so a backup made
+/// moments ago means the change was ours, not another program's. With git
+/// backups the backup is a commit, which updates the repository's index.
+///
+/// # Arguments
+///
+/// * `path` - Config that changed
+/// * `since` - Only backups made after this count (`daemon` passes the time
+///   of its own last backup, which was not a write)
+fn written_by_us(path: &Path, since: Option<SystemTime>) -> bool {
+    let backups = RuntimePaths::backup_dir(path)
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path());
+    let git_index = path.parent().map(|dir| dir.join(".git").join("index"));
+
+    backups
+        .chain(git_index)
+        .filter_map(|file| fs::metadata(file).ok()?.modified().ok())
+        .filter(|modified| since.is_none_or(|since| *modified > since))
+        .any(|modified| {
+            SystemTime::now()
+                .duration_since(modified)
+                .is_ok_and(|age| age < OWN_WRITE_WINDOW)
+        })
+}
+
+/// Compares the config with Hyprland's live bindings
+///
+/// # Returns
+///
+/// * `Some(drift)` - Both could be read
+/// * `None` - Hyprland is not running or the config cannot be parsed
+fn check_drift(path: &Path, client: &HyprlandClient) -> Option<Drift> {
+    let live = client.binds().ok()?;
+    let content = fs::read_to_string(path).ok()?;
+    let bindings = parse_config_with_sources(&content, path).ok()?;
+    Some(find_drift(&bindings, &live))
+}
+
+/// Shows a notification without blocking the watch loop
+///
+/// Notifications the preferences switch off are skipped. Choosing "Open
+/// GUI" starts the GUI on the config.
+fn notify(path: &Path, event: NotificationEvent) {
+    if !event.is_enabled(&load_settings()) {
+        return;
+    }
+
+    let path = path.to_path_buf();
+    thread::spawn(move || match notifications::send(&event) {
+        Ok(Some(NotificationAction::OpenGui)) => {
+            let started = env::current_exe().and_then(|exe| {
+                Command::new(exe)
+                    .arg("gui")
+                    .arg("--config")
+                    .arg(&path)
+                    .spawn()
+            });
+            if let Err(e) = started {
+                eprintln!("{} Failed to open the GUI: {}", "✗".red(), e);
+            }
+        }
+        Ok(Some(NotificationAction::Ignore) | None) => {}
+        Err(e) => eprintln!("{} {}", "✗".red(), e),
+    });
+}
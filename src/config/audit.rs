@@ -0,0 +1,173 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit log
+//!
+//! Append-only record of events worth looking back on. Currently this is
+//! the health score of each config, so the GUI and `doctor` can show
+//! whether a config is getting better or worse over time.
+//!
+//! The log lives next to the state file, at
+//! `$XDG_STATE_HOME/hypr-keybind-manager/audit.log`, with one event per
+//! line: RFC 3339 timestamp, event kind (`health`), score and canonical
+//! config path, separated by tabs.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local, SecondsFormat};
+
+use crate::config::{state::AppState, ConfigError};
+
+const HEALTH_EVENT: &str = "health";
+
+/// A health score recorded at a point in time
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthRecord {
+    pub timestamp: DateTime<Local>,
+    pub score: u8,
+}
+
+/// How the health score changed since it was last different
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthTrend {
+    /// Current score minus the previous one
+    pub change: i16,
+    /// When the previous score was recorded
+    pub since: DateTime<Local>,
+}
+
+impl HealthTrend {
+    /// Compares the current score with the history of a config
+    ///
+    /// The log only records changes, so the most recent record with a
+    /// different score is the one to compare against.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(HealthTrend)` - A different score was recorded before
+    /// * `None` - No history, or the score has never changed
+    pub fn from_history(history: &[HealthRecord], current: u8) -> Option<Self> {
+        let previous = history
+            .iter()
+            .rev()
+            .find(|record| record.score != current)?;
+
+        Some(Self {
+            change: current as i16 - previous.score as i16,
+            since: previous.timestamp,
+        })
+    }
+}
+
+/// Append-only audit log on disk
+#[derive(Clone, Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// Returns the default log location, next to the state file
+    pub fn default_path() -> Option<PathBuf> {
+        Some(AppState::default_path()?.with_file_name("audit.log"))
+    }
+
+    /// Opens the log at a path (the file is created on first write)
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Returns the recorded health scores of a config, oldest first
+    ///
+    /// Malformed lines are skipped. A missing log is an empty history.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::Io` if the log exists but cannot be read.
+    pub fn health_history(&self, config_path: &Path) -> Result<Vec<HealthRecord>, ConfigError> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(ConfigError::Io(e)),
+        };
+
+        let config = canonical(config_path);
+        let config = config.to_string_lossy();
+
+        Ok(content
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\t');
+                let timestamp = DateTime::parse_from_rfc3339(fields.next()?).ok()?;
+                if fields.next()? != HEALTH_EVENT {
+                    return None;
+                }
+                let score = fields.next()?.parse().ok()?;
+                (fields.next()? == config).then(|| HealthRecord {
+                    timestamp: timestamp.with_timezone(&Local),
+                    score,
+                })
+            })
+            .collect())
+    }
+
+    /// Records the health score of a config if it changed
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - The score differs from the last record and was appended
+    /// * `Ok(false)` - Same as the last record; nothing written
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::Io` if the log cannot be read or written.
+    pub fn record_health(
+        &self,
+        config_path: &Path,
+        score: u8,
+        timestamp: DateTime<Local>,
+    ) -> Result<bool, ConfigError> {
+        let history = self.health_history(config_path)?;
+        if history.last().is_some_and(|record| record.score == score) {
+            return Ok(false);
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            timestamp.to_rfc3339_opts(SecondsFormat::Secs, false),
+            HEALTH_EVENT,
+            score,
+            canonical(config_path).to_string_lossy()
+        )?;
+
+        Ok(true)
+    }
+}
+
+/// Resolves symlinks so the same config is recognised via any path
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,167 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Automatic repair of conflicting bindings for `check --fix`
+//!
+//! Only conflicts where one binding can simply win are repaired:
+//!
+//! - [`ConflictKind::RedundantDuplicate`]: the copies are dropped
+//! - [`ConflictKind::MultipleActions`]: every action but the kept one is
+//!   dropped
+//!
+//! Press/release pairs are usually intended and catchall shadowing is
+//! fixed with an `unbind`, not by dropping lines, so both are left for the
+//! user. So are conflicts involving a sourced file, which is never edited.
+//!
+//! Dropped bindings are removed from, or commented out on, the line they
+//! were read from (see [`writer::drop_bind_lines`](crate::config::writer::drop_bind_lines)),
+//! so the rest of the config does not move.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::autofix::{bindings_to_drop, is_fixable, FixStrategy};
+//! use hypr_keybind_manager::core::{conflict::ConflictDetector, parser::parse_config_file};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, K, exec, kitty\n\
+//!               bind = SUPER, K, exec, alacritty\n";
+//! let mut detector = ConflictDetector::new();
+//! for binding in parse_config_file(config, Path::new("hyprland.conf")).unwrap() {
+//!     detector.add_binding(binding);
+//! }
+//! let conflict = &detector.find_conflicts()[0];
+//!
+//! assert!(is_fixable(conflict));
+//! let keep = FixStrategy::KeepFirst.keep_index(conflict);
+//! let dropped = bindings_to_drop(conflict, keep);
+//! assert_eq!(dropped[0].args.as_deref(), Some("alacritty"));
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use crate::core::{
+    conflict::{Conflict, ConflictKind},
+    Keybinding,
+};
+
+/// Which binding of a conflict survives a non-interactive fix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixStrategy {
+    /// Keep the binding declared first in the config
+    KeepFirst,
+    /// Keep the binding declared last in the config
+    KeepLast,
+}
+
+impl FixStrategy {
+    /// Index into [`Conflict::conflicting_bindings`] of the binding to keep
+    ///
+    /// # Arguments
+    /// * `conflict` - Conflict being fixed
+    pub fn keep_index(self, conflict: &Conflict) -> usize {
+        match self {
+            FixStrategy::KeepFirst => 0,
+            FixStrategy::KeepLast => conflict.conflicting_bindings.len().saturating_sub(1),
+        }
+    }
+}
+
+impl FromStr for FixStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "keep-first" => Ok(FixStrategy::KeepFirst),
+            "keep-last" => Ok(FixStrategy::KeepLast),
+            other => Err(format!(
+                "unknown strategy '{other}' (expected keep-first or keep-last)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for FixStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FixStrategy::KeepFirst => "keep-first",
+            FixStrategy::KeepLast => "keep-last",
+        })
+    }
+}
+
+/// What happens to the line of a dropped binding
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FixMode {
+    /// Delete the line
+    #[default]
+    Remove,
+    /// Keep the line behind a `# `, so it is easy to restore
+    CommentOut,
+}
+
+/// Why `--fix` leaves a conflict alone
+///
+/// # Arguments
+/// * `conflict` - Conflict to check
+///
+/// # Returns
+/// * `None` - The conflict can be fixed by dropping bindings
+/// * `Some(reason)` - Why it is left for the user
+pub fn skip_reason(conflict: &Conflict) -> Option<&'static str> {
+    match conflict.kind {
+        ConflictKind::PressAndRelease => {
+            return Some("press and release actions are usually intended")
+        }
+        ConflictKind::CatchallShadowing => return Some("an unbind fixes catchall shadowing"),
+        ConflictKind::RedundantDuplicate | ConflictKind::MultipleActions => {}
+    }
+
+    let editable = conflict
+        .conflicting_bindings
+        .iter()
+        .all(|binding| binding.source_file.is_none() && binding.location.is_some());
+    (!editable).then_some("a binding is in a sourced file, which is never edited")
+}
+
+/// Whether `--fix` can repair a conflict by dropping bindings
+///
+/// # Arguments
+/// * `conflict` - Conflict to check
+///
+/// # Returns
+/// `true` for redundant duplicates and multiple actions whose bindings
+/// were all read from a known line of the main config (see
+/// [`skip_reason`])
+pub fn is_fixable(conflict: &Conflict) -> bool {
+    skip_reason(conflict).is_none()
+}
+
+/// The bindings of a conflict that a fix drops
+///
+/// # Arguments
+/// * `conflict` - Conflict being fixed
+/// * `keep` - Index into [`Conflict::conflicting_bindings`] of the binding
+///   that stays
+///
+/// # Returns
+/// Every other binding, with its location, in config order
+pub fn bindings_to_drop(conflict: &Conflict, keep: usize) -> Vec<Keybinding> {
+    conflict
+        .conflicting_bindings
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != keep)
+        .map(|(_, binding)| binding.clone())
+        .collect()
+}
@@ -0,0 +1,728 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Backup backends
+//!
+//! Every write first saves the config through a [`BackupBackend`], chosen
+//! with the `backup_mode` preference:
+//!
+//! - [`TimestampedBackups`] (default): a copy named
+//!   `{basename}.YYYY-MM-DD_HHMMSS` in `backups/` beside the config, listed
+//!   by the backup dialog and restored by `--undo-last`
+//! - [`GitBackups`]: commits to the git repository holding the config directory
+//!   (created with `git init` if there is none), one commit per write with a
+//!   message describing it, so the history is browsed with `git log`
+//!
+//! The git backend runs the `git` command; only the config file is staged
+//! and committed, so other changes in an existing repository are left alone.
+//!
+//! Timestamped backups are pruned after every write by a
+//! [`RetentionPolicy`]: a maximum count, age and total size, each optional.
+//!
+//! They can be compressed with gzip or zstd ([`BackupCompression`]), which
+//! adds `.gz` or `.zst` to the name. Each one has a SHA-256 checksum of the
+//! config content beside it (`{backup}.sha256`), checked by [`read_backup`]
+//! before a restore. A backup of content that matches the newest backup is
+//! not written again.
+
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    str::FromStr,
+    time::SystemTime,
+};
+
+use chrono::{Local, NaiveDateTime, TimeDelta};
+use flate2::{read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::ConfigError,
+    core::{
+        compare::{compare_bindings, RowStatus},
+        parser::parse_config_file,
+    },
+};
+
+/// Bytes in a megabyte, the unit of the backup size limit in settings
+pub const BYTES_PER_MB: u64 = 1_000_000;
+
+/// Extension of the checksum file beside each backup
+pub const CHECKSUM_EXTENSION: &str = "sha256";
+
+/// zstd level for compressed backups: the library default, fast and small
+/// enough for config files
+const ZSTD_LEVEL: i32 = 3;
+
+/// Author used for backup commits when git has no identity configured
+const FALLBACK_NAME: &str = "hypr-keybind-manager";
+const FALLBACK_EMAIL: &str = "hypr-keybind-manager@localhost";
+
+/// Where the config is saved before the tool writes it
+pub trait BackupBackend: fmt::Debug {
+    /// Saves the config as it is now, before a write changes it
+    ///
+    /// # Arguments
+    ///
+    /// * `config_path` - Config file about to be written
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(path))` - A backup file was written
+    /// * `Ok(None)` - The backup is kept elsewhere (for example in git)
+    /// * `Err(ConfigError)` - Nothing was saved; the write must not go ahead
+    fn backup(&self, config_path: &Path) -> Result<Option<PathBuf>, ConfigError>;
+
+    /// Records a write that has just happened
+    ///
+    /// The default does nothing: the backup taken before the write is all a
+    /// backend needs to undo it.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_path` - Config file that was written
+    /// * `message` - What the write changed (see [`describe_change`])
+    fn record(&self, config_path: &Path, message: &str) -> Result<(), ConfigError> {
+        let _ = (config_path, message);
+        Ok(())
+    }
+}
+
+/// Which backup backend to use
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupMode {
+    /// Timestamped copies in `backups/` ([`TimestampedBackups`])
+    #[default]
+    Timestamped,
+    /// Commits to a git repository ([`GitBackups`])
+    Git,
+}
+
+impl BackupMode {
+    /// Builds the backend for a config
+    ///
+    /// # Arguments
+    ///
+    /// * `config_path` - Config file the backups are of
+    /// * `backup_dir` - Directory for timestamped backups
+    /// * `compression` - How timestamped backups are compressed
+    pub fn backend(
+        self,
+        config_path: &Path,
+        backup_dir: &Path,
+        compression: BackupCompression,
+    ) -> Box<dyn BackupBackend> {
+        match self {
+            BackupMode::Timestamped => Box::new(
+                TimestampedBackups::new(backup_dir.to_path_buf()).with_compression(compression),
+            ),
+            BackupMode::Git => Box::new(GitBackups::new(
+                config_path
+                    .parent()
+                    .unwrap_or_else(|| Path::new("."))
+                    .to_path_buf(),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for BackupMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BackupMode::Timestamped => "timestamped",
+            BackupMode::Git => "git",
+        })
+    }
+}
+
+impl FromStr for BackupMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "timestamped" => Ok(BackupMode::Timestamped),
+            "git" => Ok(BackupMode::Git),
+            other => Err(format!("Unknown backup mode: {other}")),
+        }
+    }
+}
+
+/// How timestamped backups are compressed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupCompression {
+    /// Plain copies of the config
+    #[default]
+    None,
+    /// gzip, readable with `zcat`
+    Gzip,
+    /// zstd, readable with `zstdcat`
+    Zstd,
+}
+
+impl BackupCompression {
+    /// Extension added to the backup name (`None` for plain copies)
+    pub fn extension(self) -> Option<&'static str> {
+        match self {
+            BackupCompression::None => None,
+            BackupCompression::Gzip => Some("gz"),
+            BackupCompression::Zstd => Some("zst"),
+        }
+    }
+
+    /// Tells from a backup's name how it is compressed
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Backup file
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => BackupCompression::Gzip,
+            Some("zst") => BackupCompression::Zstd,
+            _ => BackupCompression::None,
+        }
+    }
+
+    fn compress(self, content: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            BackupCompression::None => Ok(content.to_vec()),
+            BackupCompression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(content)?;
+                encoder.finish()
+            }
+            BackupCompression::Zstd => zstd::encode_all(content, ZSTD_LEVEL),
+        }
+    }
+
+    fn decompress(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            BackupCompression::None => Ok(data.to_vec()),
+            BackupCompression::Gzip => {
+                let mut content = Vec::new();
+                GzDecoder::new(data).read_to_end(&mut content)?;
+                Ok(content)
+            }
+            BackupCompression::Zstd => zstd::decode_all(data),
+        }
+    }
+}
+
+impl fmt::Display for BackupCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BackupCompression::None => "none",
+            BackupCompression::Gzip => "gzip",
+            BackupCompression::Zstd => "zstd",
+        })
+    }
+}
+
+impl FromStr for BackupCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(BackupCompression::None),
+            "gzip" => Ok(BackupCompression::Gzip),
+            "zstd" => Ok(BackupCompression::Zstd),
+            other => Err(format!("Unknown backup compression: {other}")),
+        }
+    }
+}
+
+/// Which timestamped backups to keep
+///
+/// The newest backup is always kept, whatever the limits: it is the
+/// rollback point of the last write. Beyond it, backups are kept newest
+/// first until one breaks a limit; that one and every older one go.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Most backups kept (`None`: no limit)
+    pub max_count: Option<usize>,
+    /// Oldest backup kept, in days (`None`: no limit)
+    pub max_age_days: Option<u32>,
+    /// Most disk space the backups may take together, in bytes (`None`: no
+    /// limit)
+    pub max_total_bytes: Option<u64>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_count: Some(50),
+            max_age_days: None,
+            max_total_bytes: None,
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Keeps every backup
+    pub const KEEP_ALL: Self = Self {
+        max_count: None,
+        max_age_days: None,
+        max_total_bytes: None,
+    };
+
+    /// Picks the backups to delete
+    ///
+    /// # Arguments
+    ///
+    /// * `backups` - Timestamped backups, newest first
+    /// * `now` - Time the ages are measured from
+    ///
+    /// # Returns
+    ///
+    /// Paths of the backups the policy does not keep, newest first
+    pub fn select(&self, backups: &[BackupInfo], now: NaiveDateTime) -> Vec<PathBuf> {
+        let max_age = self
+            .max_age_days
+            .map(|days| TimeDelta::days(i64::from(days)));
+        let mut total_bytes = backups.first().map_or(0, |backup| backup.size);
+
+        // The newest backup is skipped: it stays whatever the limits
+        let first_dropped = backups
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find(|(index, backup)| {
+                total_bytes = total_bytes.saturating_add(backup.size);
+                self.max_count.is_some_and(|max| *index >= max)
+                    || max_age.is_some_and(|max| now - backup.created > max)
+                    || self.max_total_bytes.is_some_and(|max| total_bytes > max)
+            })
+            .map(|(index, _)| index);
+
+        first_dropped.map_or_else(Vec::new, |index| {
+            backups[index..]
+                .iter()
+                .map(|backup| backup.path.clone())
+                .collect()
+        })
+    }
+}
+
+/// A timestamped backup file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    /// Time in the file name
+    pub created: NaiveDateTime,
+    /// File size in bytes
+    pub size: u64,
+}
+
+/// Timestamped copies of the config in a backup directory
+#[derive(Clone, Debug)]
+pub struct TimestampedBackups {
+    backup_dir: PathBuf,
+    compression: BackupCompression,
+}
+
+impl TimestampedBackups {
+    /// Creates the backend, writing uncompressed copies
+    ///
+    /// # Arguments
+    ///
+    /// * `backup_dir` - Existing directory the copies are written to
+    pub fn new(backup_dir: PathBuf) -> Self {
+        Self {
+            backup_dir,
+            compression: BackupCompression::None,
+        }
+    }
+
+    /// Compresses new backups
+    ///
+    /// Backups already written keep their compression.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - How new backups are compressed
+    pub fn with_compression(mut self, compression: BackupCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Returns the newest backup of a config if it holds exactly `checksum`
+    fn unchanged_since_newest(&self, original_name: &str, checksum: &str) -> Option<PathBuf> {
+        let prefix = format!("{original_name}.");
+        let (newest, _) = dated_backups(&self.backup_dir)
+            .ok()?
+            .into_iter()
+            .find(|(path, _)| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&prefix))
+            })?;
+        let newest_checksum = match fs::read_to_string(checksum_path(&newest)) {
+            Ok(stored) => stored.trim().to_string(),
+            // Backups from before checksums were kept
+            Err(_) => sha256_hex(read_backup(&newest).ok()?.as_bytes()),
+        };
+        (newest_checksum == checksum).then_some(newest)
+    }
+}
+
+impl BackupBackend for TimestampedBackups {
+    fn backup(&self, config_path: &Path) -> Result<Option<PathBuf>, ConfigError> {
+        let content = fs::read(config_path)?;
+        let original_name = config_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "config".to_string());
+
+        if let Some(newest) = self.unchanged_since_newest(&original_name, &sha256_hex(&content)) {
+            // The watcher tells its own writes apart by fresh backups, so the
+            // backup that stands in for this one is touched
+            File::options()
+                .write(true)
+                .open(&newest)?
+                .set_modified(SystemTime::now())?;
+            return Ok(Some(newest));
+        }
+
+        // e.g. hyprland.conf.2025-01-31_142501, or with zstd
+        // hyprland.conf.2025-01-31_142501.zst
+        let timestamp = Local::now().format("%Y-%m-%d_%H%M%S");
+        let mut file_name = format!("{}.{}", original_name, timestamp);
+        if let Some(extension) = self.compression.extension() {
+            file_name = format!("{file_name}.{extension}");
+        }
+        let backup_path = self.backup_dir.join(file_name);
+
+        fs::write(&backup_path, self.compression.compress(&content)?)?;
+        write_checksum(&backup_path, &content)?;
+        Ok(Some(backup_path))
+    }
+}
+
+/// Lists the timestamped backups in a directory, newest first
+///
+/// Names must look like `{basename}.YYYY-MM-DD_HHMMSS`, optionally followed
+/// by a compression extension; other files are skipped.
+///
+/// # Arguments
+///
+/// * `backup_dir` - Directory holding the backups
+///
+/// # Returns
+///
+/// Backup paths with the time in their names
+pub fn dated_backups(backup_dir: &Path) -> Result<Vec<(PathBuf, NaiveDateTime)>, ConfigError> {
+    let mut backups: Vec<(PathBuf, NaiveDateTime)> = fs::read_dir(backup_dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let created = backup_timestamp(path.file_name()?.to_str()?)?;
+            Some((path, created))
+        })
+        .collect();
+
+    // Newest first
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.1));
+    Ok(backups)
+}
+
+/// Reads the time from a timestamped backup's name
+///
+/// # Arguments
+///
+/// * `file_name` - e.g. `hyprland.conf.2025-01-31_142501.gz`
+///
+/// # Returns
+///
+/// The time, or `None` if the name is not a timestamped backup's
+pub fn backup_timestamp(file_name: &str) -> Option<NaiveDateTime> {
+    let stem = ["gz", "zst"]
+        .into_iter()
+        .find_map(|extension| file_name.strip_suffix(&format!(".{extension}")))
+        .unwrap_or(file_name);
+
+    // Expect "basename.ext.YYYY-MM-DD_HHMMSS"
+    let parts: Vec<&str> = stem.split('.').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    NaiveDateTime::parse_from_str(parts[2], "%Y-%m-%d_%H%M%S").ok()
+}
+
+/// Reads a backup, decompressing it and checking its checksum
+///
+/// Backups without a checksum file (made before checksums were kept, or
+/// any other config file) are read without the check.
+///
+/// # Arguments
+///
+/// * `path` - Backup file
+///
+/// # Returns
+///
+/// * `Ok(content)` - The config as it was backed up
+/// * `Err(ConfigError::ChecksumMismatch)` - The backup has been damaged
+/// * `Err(ConfigError)` - The backup cannot be read or decompressed
+pub fn read_backup(path: &Path) -> Result<String, ConfigError> {
+    let content = BackupCompression::of(path).decompress(&fs::read(path)?)?;
+
+    match fs::read_to_string(checksum_path(path)) {
+        Ok(stored) if stored.trim() != sha256_hex(&content) => {
+            return Err(ConfigError::ChecksumMismatch(path.to_path_buf()));
+        }
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    String::from_utf8(content).map_err(|_| {
+        ConfigError::BackupFailed(format!("Backup is not UTF-8 text: {}", path.display()))
+    })
+}
+
+/// Deletes a backup and its checksum file
+///
+/// # Arguments
+///
+/// * `path` - Backup file
+pub fn remove_backup(path: &Path) -> io::Result<()> {
+    fs::remove_file(path)?;
+    match fs::remove_file(checksum_path(path)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the checksum file kept beside a backup
+///
+/// # Arguments
+///
+/// * `backup_path` - Backup file, e.g. `hyprland.conf.2025-01-31_142501.zst`
+pub fn checksum_path(backup_path: &Path) -> PathBuf {
+    let mut name = backup_path.as_os_str().to_os_string();
+    name.push(format!(".{CHECKSUM_EXTENSION}"));
+    PathBuf::from(name)
+}
+
+/// Records the checksum of a backup's content beside it
+///
+/// # Arguments
+///
+/// * `backup_path` - Backup file
+/// * `content` - Config content it holds, before compression
+pub(crate) fn write_checksum(backup_path: &Path, content: &[u8]) -> io::Result<()> {
+    fs::write(
+        checksum_path(backup_path),
+        format!("{}\n", sha256_hex(content)),
+    )
+}
+
+/// SHA-256 of some content, as lowercase hex
+fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// Commits of the config to a git repository
+///
+/// [`backup`](BackupBackend::backup) commits changes made outside the tool,
+/// so each write's commit only holds that write;
+/// [`record`](BackupBackend::record) commits the write itself.
+#[derive(Clone, Debug)]
+pub struct GitBackups {
+    repo_dir: PathBuf,
+}
+
+impl GitBackups {
+    /// Creates the backend
+    ///
+    /// The repository is only created on the first backup.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo_dir` - Directory holding the config
+    pub fn new(repo_dir: PathBuf) -> Self {
+        Self { repo_dir }
+    }
+
+    /// Runs `git` in the repository directory
+    fn git(&self, args: &[&str]) -> Result<Output, ConfigError> {
+        Command::new("git")
+            .arg("-C")
+            .arg(&self.repo_dir)
+            .args(args)
+            .output()
+            .map_err(|e| ConfigError::BackupFailed(format!("Failed to run git: {}", e)))
+    }
+
+    /// Runs `git`, turning a non-zero exit into an error
+    fn git_checked(&self, args: &[&str]) -> Result<Output, ConfigError> {
+        let output = self.git(args)?;
+        if !output.status.success() {
+            return Err(ConfigError::BackupFailed(format!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(output)
+    }
+
+    /// Creates the repository unless the directory is already in one
+    ///
+    /// A repository further up, such as a dotfiles checkout of the home
+    /// directory, is reused rather than nesting a second one inside it;
+    /// [`commit_file`](Self::commit_file) only ever commits the config.
+    fn ensure_repository(&self) -> Result<(), ConfigError> {
+        if self
+            .git(&["rev-parse", "--is-inside-work-tree"])?
+            .status
+            .success()
+        {
+            return Ok(());
+        }
+        self.git_checked(&["init", "--quiet"])?;
+        Ok(())
+    }
+
+    /// Stages and commits the config file, if it has changes
+    ///
+    /// Only the file itself is committed, whatever else is staged.
+    fn commit_file(&self, file_name: &str, message: &str) -> Result<(), ConfigError> {
+        let status = self.git_checked(&["status", "--porcelain", "--", file_name])?;
+        if status.stdout.is_empty() {
+            return Ok(());
+        }
+
+        self.git_checked(&["add", "--", file_name])?;
+
+        // Backup commits are made unattended: no signing prompt, and an
+        // identity of our own when the user has not configured one
+        let mut args = vec!["-c", "commit.gpgsign=false"];
+        let name = format!("user.name={FALLBACK_NAME}");
+        let email = format!("user.email={FALLBACK_EMAIL}");
+        if !self.git(&["config", "user.name"])?.status.success() {
+            args.extend(["-c", &name]);
+        }
+        if !self.git(&["config", "user.email"])?.status.success() {
+            args.extend(["-c", &email]);
+        }
+        args.extend(["commit", "--quiet", "--message", message, "--", file_name]);
+        self.git_checked(&args)?;
+        Ok(())
+    }
+}
+
+impl BackupBackend for GitBackups {
+    fn backup(&self, config_path: &Path) -> Result<Option<PathBuf>, ConfigError> {
+        self.ensure_repository()?;
+
+        let file_name = git_file_name(config_path)?;
+        let tracked = self
+            .git(&["ls-files", "--error-unmatch", "--", &file_name])?
+            .status
+            .success();
+        let message = if tracked {
+            format!("Save changes to {file_name} made outside hypr-keybind-manager")
+        } else {
+            format!("Track {file_name}")
+        };
+        self.commit_file(&file_name, &message)?;
+        Ok(None)
+    }
+
+    fn record(&self, config_path: &Path, message: &str) -> Result<(), ConfigError> {
+        self.commit_file(&git_file_name(config_path)?, message)
+    }
+}
+
+fn git_file_name(config_path: &Path) -> Result<String, ConfigError> {
+    config_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+        .ok_or_else(|| {
+            ConfigError::BackupFailed(format!(
+                "Config path has no UTF-8 file name: {}",
+                config_path.display()
+            ))
+        })
+}
+
+/// Describes a write for a backup commit message
+///
+/// A single changed binding gives a message like `Add SUPER+B`; several
+/// give a summary line followed by one line per key combo. A write that
+/// changes no binding is described by `fallback`.
+///
+/// # Arguments
+///
+/// * `config_path` - Config file being written, for the parser
+/// * `before` - Content before the write
+/// * `after` - Content after the write
+/// * `fallback` - Message when no binding changed
+pub fn describe_change(config_path: &Path, before: &str, after: &str, fallback: &str) -> String {
+    let bindings = |content: &str| parse_config_file(content, config_path).unwrap_or_default();
+    let changes: Vec<(RowStatus, String)> = compare_bindings(&bindings(before), &bindings(after))
+        .into_iter()
+        .map(|row| {
+            let keys = match &row.submap {
+                Some(submap) => format!("{} in submap {}", row.key_combo, submap),
+                None => row.key_combo.to_string(),
+            };
+            (row.status(), keys)
+        })
+        .filter(|(status, _)| *status != RowStatus::Unchanged)
+        .collect();
+
+    let verb = |status: RowStatus| match status {
+        RowStatus::Added => "Add",
+        RowStatus::Removed => "Remove",
+        _ => "Change",
+    };
+
+    match changes.as_slice() {
+        [] => fallback.to_string(),
+        [(status, keys)] => format!("{} {}", verb(*status), keys),
+        _ => {
+            let count = |wanted: RowStatus| {
+                changes
+                    .iter()
+                    .filter(|(status, _)| *status == wanted)
+                    .count()
+            };
+            let summary = [
+                (count(RowStatus::Added), "added"),
+                (count(RowStatus::Removed), "removed"),
+                (count(RowStatus::Changed), "changed"),
+            ]
+            .into_iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, label)| format!("{count} {label}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+            let details = changes
+                .iter()
+                .map(|(status, keys)| format!("{} {}", verb(*status), keys))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("Update keybindings ({summary})\n\n{details}")
+        }
+    }
+}
@@ -81,11 +81,20 @@
 //!
 //! 1. **Fast path**: Check safe whitelist first (O(1) HashSet lookup)
 //! 2. **Critical patterns**: Regex matching for system destruction (Round 1)
-//! 3. **Dangerous arguments**: Context-aware analysis (chmod 777, pipe to shell)
+//! 3. **Dangerous arguments**: Context-aware analysis (chmod 777, pipe to shell,
+//!    bindings that rewrite bindings, see [`SELF_REFERENCE`])
 //! 4. **Dangerous commands**: Word boundary matching (privilege escalation, disk ops)
 //! 5. **Entropy analysis**: Mathematical detection of encoded payloads (Round 3) ✅
 //! 6. **Suspicious commands**: Flag encoding tools, downloaders (after entropy check)
 //!
+//! Rules fetched through the opt-in update channel (see [`updates`]) are
+//! checked after these steps and can only raise the verdict.
+//!
+//! Leading `VAR=value` assignments (`exec, GDK_BACKEND=wayland app`) are skipped
+//! before step 1 so the real executable is assessed. Assignments that hook the
+//! dynamic loader or an interpreter (`LD_PRELOAD=`, `BASH_ENV=`) are flagged as
+//! Suspicious on their own.
+//!
 //! # References
 //!
 //! - **Comprehensive entropy documentation**: [`../docs/ENTROPY_DETECTION.md`](../docs/ENTROPY_DETECTION.md)
@@ -101,8 +110,28 @@ use std::collections::HashSet;
 pub mod entropy;
 pub mod patterns;
 pub mod types;
+pub mod updates;
 
+use patterns::SafeWrapper;
 pub use types::{DangerAssessment, DangerLevel};
+use updates::{CompiledRule, RuleSet, RulesChannel};
+
+/// `matched_pattern` of bindings that rewrite the bindings themselves
+///
+/// A bind whose command runs `hyprctl keyword bind`, `hyprctl reload` or a
+/// writing command of this manager changes the binding set on every press,
+/// which can loop or silently undo edits.
+pub const SELF_REFERENCE: &str = "self-referencing binding";
+
+/// A command with a safe wrapper stripped from its front
+struct UnwrappedCommand {
+    /// Wrapper name, e.g. `systemd-run --user`
+    wrapper: String,
+    /// The command line the wrapper runs
+    wrapped: String,
+    /// `VAR=value` assignments the wrapper makes for it
+    env: Vec<String>,
+}
 
 /// Pattern-based dangerous command detector
 pub struct DangerDetector {
@@ -110,6 +139,10 @@ pub struct DangerDetector {
     dangerous_commands: HashSet<String>,
     suspicious_commands: HashSet<String>,
     safe_commands: HashSet<String>,
+    suspicious_env_vars: HashSet<String>,
+    safe_wrappers: Vec<SafeWrapper>,
+    /// Pattern rules from an installed update
+    update_patterns: Vec<CompiledRule>,
 }
 
 impl Default for DangerDetector {
@@ -119,6 +152,9 @@ impl Default for DangerDetector {
             dangerous_commands: patterns::build_dangerous_commands(),
             suspicious_commands: patterns::build_suspicious_commands(),
             safe_commands: patterns::build_safe_commands(),
+            suspicious_env_vars: patterns::build_suspicious_env_vars(),
+            safe_wrappers: patterns::build_safe_wrappers(),
+            update_patterns: Vec::new(),
         }
     }
 }
@@ -129,9 +165,48 @@ impl DangerDetector {
         Self::default()
     }
 
+    /// Creates a detector with the built-in rules plus an update's rules
+    ///
+    /// Commands are only ever added to the dangerous and suspicious lists;
+    /// the safe list stays built-in.
+    pub fn with_rules(rules: &RuleSet) -> Self {
+        let mut detector = Self::default();
+        detector
+            .dangerous_commands
+            .extend(rules.dangerous_commands.iter().cloned());
+        detector
+            .suspicious_commands
+            .extend(rules.suspicious_commands.iter().cloned());
+        detector.update_patterns = rules.compiled_patterns();
+        detector
+    }
+
+    /// Creates a detector with the installed rule update, if any
+    ///
+    /// Without an update channel this is [`Self::new`]. An installed
+    /// ruleset that fails verification is reported and ignored.
+    pub fn with_installed_rules() -> Self {
+        let Some(dir) = RulesChannel::config_dir() else {
+            return Self::new();
+        };
+
+        match updates::load_installed_rules(&dir) {
+            Ok(Some(rules)) => Self::with_rules(&rules),
+            Ok(None) => Self::new(),
+            Err(e) => {
+                eprintln!("⚠️  Ignoring danger rule update: {}", e);
+                Self::new()
+            }
+        }
+    }
+
     /// Assesses the danger level of a command string
     ///
     /// # Detection Order (Optimised for Performance and Accuracy)
+    /// 0. **Environment assignments** - Leading `VAR=value` words are skipped so the
+    ///    real executable is assessed; loader hooks like `LD_PRELOAD=` are flagged.
+    ///    Safe wrappers (`flatpak run`, `systemd-run --user`, `uwsm app`) are looked
+    ///    through and the wrapped program is assessed in their place
     /// 1. **Safe whitelist** - Fast path for known-safe commands (O(1))
     /// 2. **Critical patterns** - System-destroying regex (Round 1)
     /// 3. **Dangerous arguments** - Secondary pattern analysis (chmod 777, etc.)
@@ -152,19 +227,81 @@ impl DangerDetector {
     /// // Dangerous command
     /// let assessment = detector.assess_command("chmod 777 ~/.ssh");
     /// assert_eq!(assessment.danger_level, DangerLevel::Dangerous);
+    ///
+    /// // Environment assignments are not the command
+    /// let assessment = detector.assess_command("GDK_BACKEND=wayland firefox");
+    /// assert_eq!(assessment.danger_level, DangerLevel::Safe);
+    ///
+    /// // Neither are launch wrappers
+    /// let assessment = detector.assess_command("uwsm app -- kitty");
+    /// assert_eq!(assessment.reason, "Known safe command (run via 'uwsm app')");
     /// ```
     pub fn assess_command(&self, command: &str) -> DangerAssessment {
+        let assessment = self.assess_with_builtin_rules(command);
+
+        // Update rules only ever raise the verdict
+        self.update_patterns
+            .iter()
+            .filter(|rule| rule.assessment.danger_level > assessment.danger_level)
+            .filter(|rule| rule.regex.is_match(command))
+            .max_by_key(|rule| rule.assessment.danger_level)
+            .map(|rule| rule.assessment.clone())
+            .unwrap_or(assessment)
+    }
+
+    /// Runs the built-in detection steps described on [`Self::assess_command`]
+    fn assess_with_builtin_rules(&self, command: &str) -> DangerAssessment {
+        let words: Vec<&str> = command.split_whitespace().collect();
+
+        // Step 0: Skip leading `VAR=value` assignments (`exec, FOO=1 app --flag`)
+        // The real executable follows them; the assignments themselves are only
+        // a concern when they hook into the dynamic loader or an interpreter.
+        // Anything later in the pipeline is at least Suspicious, so this only
+        // needs to win over a Safe verdict.
+        let assignment_count = words
+            .iter()
+            .take_while(|word| is_env_assignment(word))
+            .count();
+        let (assignments, program) = words.split_at(assignment_count);
+        let env_assessment = self.check_env_assignments(assignments);
+
+        // Look through safe wrappers: the wrapped program is what actually runs,
+        // so it is assessed on its own and the wrapper is only mentioned.
+        // Variables a wrapper sets for it (`systemd-run -E`, `flatpak run
+        // --env`) are checked like leading assignments.
+        if let Some(mut unwrapped) = self.unwrap_safe_wrapper(program) {
+            let mut wrappers = vec![format!("'{}'", unwrapped.wrapper)];
+            let mut env = std::mem::take(&mut unwrapped.env);
+            while let Some(inner) =
+                self.unwrap_safe_wrapper(&unwrapped.wrapped.split_whitespace().collect::<Vec<_>>())
+            {
+                wrappers.push(format!("'{}'", inner.wrapper));
+                env.extend(inner.env.iter().cloned());
+                unwrapped = inner;
+            }
+            let env: Vec<&str> = env.iter().map(String::as_str).collect();
+            let env_assessment = env_assessment.or_else(|| self.check_env_assignments(&env));
+
+            let mut assessment = self.assess_command(&unwrapped.wrapped);
+            if assessment.danger_level == DangerLevel::Safe {
+                if let Some(env_assessment) = env_assessment {
+                    return env_assessment;
+                }
+            }
+            assessment.reason = format!("{} (run via {})", assessment.reason, wrappers.join(", "));
+            return assessment;
+        }
+
         // Step 1: Fast path - Check safe whitelist first
         // This is O(1) and avoids unnecessary checks for common commands
-        let words: Vec<&str> = command.split_whitespace().collect();
-        if let Some(first_word) = words.first() {
+        if let Some(first_word) = program.first() {
             if self.safe_commands.contains(*first_word) {
-                return DangerAssessment {
+                return env_assessment.unwrap_or_else(|| DangerAssessment {
                     danger_level: DangerLevel::Safe,
                     reason: "Known safe command".to_string(),
                     recommendation: String::new(),
                     matched_pattern: None,
-                };
+                });
             }
         }
 
@@ -203,6 +340,9 @@ impl DangerDetector {
 
         // Step 3: Check for dangerous argument patterns (secondary analysis)
         // Some commands are only dangerous with specific arguments
+        if let Some(assessment) = check_self_reference(&words) {
+            return assessment;
+        }
         if let Some(assessment) = self.check_dangerous_arguments(command) {
             return assessment;
         }
@@ -231,7 +371,12 @@ impl DangerDetector {
 
         // Check individual tokens for encoded data
         // IMPORTANT: Check hex BEFORE base64 because hex alphabet is subset of base64
-        for word in &words {
+        // Assignments are checked by value: "NAME=" itself reads as base64
+        let tokens = assignments
+            .iter()
+            .filter_map(|assignment| assignment.split_once('=').map(|(_, value)| value))
+            .chain(program.iter().copied());
+        for word in tokens {
             // Skip very short tokens to reduce false positives
             if word.len() < 8 {
                 continue;
@@ -239,9 +384,9 @@ impl DangerDetector {
 
             // Skip command names themselves (they shouldn't be treated as encoded data)
             // This prevents false positives on commands like "uuencode", "base64", etc.
-            if self.suspicious_commands.contains(*word)
-                || self.dangerous_commands.contains(*word)
-                || self.safe_commands.contains(*word)
+            if self.suspicious_commands.contains(word)
+                || self.dangerous_commands.contains(word)
+                || self.safe_commands.contains(word)
             {
                 continue;
             }
@@ -340,13 +485,130 @@ impl DangerDetector {
             }
         }
 
-        // No patterns matched - safe
-        DangerAssessment {
+        // No patterns matched - safe unless an assignment was flagged
+        env_assessment.unwrap_or_else(|| DangerAssessment {
             danger_level: DangerLevel::Safe,
             reason: "No dangerous patterns detected".to_string(),
             recommendation: String::new(),
             matched_pattern: None,
+        })
+    }
+
+    /// Checks leading environment assignments for loader/interpreter hooks
+    ///
+    /// Variables like `LD_PRELOAD` make the launched program load arbitrary
+    /// code, which is a common persistence technique (MITRE ATT&CK T1574.006)
+    /// but also has legitimate uses (e.g. gamemode), so they are Suspicious.
+    fn check_env_assignments(&self, assignments: &[&str]) -> Option<DangerAssessment> {
+        assignments.iter().find_map(|assignment| {
+            let (name, value) = assignment.split_once('=')?;
+            if !self.suspicious_env_vars.contains(name) {
+                return None;
+            }
+
+            Some(DangerAssessment {
+                danger_level: DangerLevel::Suspicious,
+                reason: format!(
+                    "Environment variable '{}' makes the command load extra code ('{}')",
+                    name, value
+                ),
+                recommendation: format!(
+                    "Make sure you trust '{}'. {} is a common way to hijack programs.",
+                    value, name
+                ),
+                matched_pattern: Some(format!("{}=", name)),
+            })
+        })
+    }
+
+    /// Strips a safe wrapper from the front of a command
+    ///
+    /// Skips the wrapper's own options (and their values) up to the wrapped
+    /// program, keeping the values of options that set environment
+    /// variables. For `flatpak run`, the application ID stands in for the
+    /// program by its last component (`org.mozilla.firefox` is `firefox`),
+    /// unless `--command` names the executable explicitly.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(UnwrappedCommand)` - Wrapper name, wrapped command line and
+    ///   the variables set for it
+    /// * `None` - Not a safe wrapper, a required option is missing, or nothing is wrapped
+    fn unwrap_safe_wrapper(&self, program: &[&str]) -> Option<UnwrappedCommand> {
+        let wrapper = self.safe_wrappers.iter().find(|wrapper| {
+            program.len() > wrapper.prefix.len() && program.starts_with(wrapper.prefix)
+        })?;
+
+        let mut rest = &program[wrapper.prefix.len()..];
+        let mut has_required_option = wrapper.required_option.is_none();
+        let mut command_override = None;
+        let mut env = Vec::new();
+
+        while let Some(word) = rest.first() {
+            if *word == "--" {
+                rest = &rest[1..];
+                break;
+            }
+            if !word.starts_with('-') {
+                break;
+            }
+
+            let (name, inline_value) = match word.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (*word, None),
+            };
+            let takes_next = inline_value.is_none() && wrapper.value_options.contains(&name);
+            let value = if takes_next {
+                rest.get(1).copied()
+            } else {
+                inline_value
+            };
+
+            if Some(name) == wrapper.required_option {
+                has_required_option = true;
+            }
+            if name == "--command" {
+                command_override = value;
+            }
+            if wrapper.env_options.contains(&name) {
+                env.extend(value.map(str::to_string));
+            }
+
+            rest = rest
+                .get(if takes_next { 2 } else { 1 }..)
+                .unwrap_or_default();
         }
+
+        if !has_required_option {
+            return None;
+        }
+
+        let wrapped = match (wrapper.app_id, rest.split_first()) {
+            (_, None) => return None,
+            (true, Some((app_id, args))) => {
+                let app_program =
+                    command_override.unwrap_or_else(|| app_id.rsplit('.').next().unwrap_or(app_id));
+                std::iter::once(app_program.to_lowercase())
+                    .chain(args.iter().map(|arg| arg.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+            (false, Some(_)) => rest.join(" "),
+        };
+
+        let name = wrapper
+            .prefix
+            .iter()
+            .chain(wrapper.required_option.iter())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Some(UnwrappedCommand {
+            wrapper: name,
+            wrapped,
+            env,
+        })
     }
 
     /// Checks for dangerous argument patterns (secondary analysis)
@@ -408,5 +670,76 @@ impl DangerDetector {
     }
 }
 
+/// Checks for commands that rewrite or re-trigger bindings
+///
+/// Flags, anywhere in the command (after `hyprctl dispatch exec` too):
+/// - `hyprctl keyword bind...` / `unbind`: adds or removes a binding on
+///   every press, so the binding set drifts from the config
+/// - `hyprctl reload`: reloads every binding from inside a binding
+/// - `hypr-keybind-manager run` (may run this very binding again) and
+///   `hypr-keybind-manager check --fix` (rewrites the config)
+fn check_self_reference(words: &[&str]) -> Option<DangerAssessment> {
+    let program = |word: &str| word.rsplit('/').next().unwrap_or_default().to_string();
+
+    for (i, word) in words.iter().enumerate() {
+        // Flags such as `-j` or `--batch` come before the command
+        let rest: Vec<&str> = words[i + 1..]
+            .iter()
+            .copied()
+            .skip_while(|word| word.starts_with('-'))
+            .collect();
+
+        let reason = match (program(word).as_str(), rest.as_slice()) {
+            ("hyprctl", ["keyword", keyword, ..])
+                if keyword.starts_with("bind") || *keyword == "unbind" =>
+            {
+                format!(
+                    "Runs 'hyprctl keyword {}', changing the bindings on every press",
+                    keyword
+                )
+            }
+            ("hyprctl", ["reload", ..]) => {
+                "Runs 'hyprctl reload', reloading every binding from inside a binding".to_string()
+            }
+            ("hypr-keybind-manager", ["run", ..]) => {
+                "Runs 'hypr-keybind-manager run', which can match and run this binding again"
+                    .to_string()
+            }
+            ("hypr-keybind-manager", ["check", ..]) if rest.contains(&"--fix") => {
+                "Runs 'hypr-keybind-manager check --fix', rewriting the config on every press"
+                    .to_string()
+            }
+            _ => continue,
+        };
+
+        return Some(DangerAssessment {
+            danger_level: DangerLevel::Dangerous,
+            reason,
+            recommendation: "Bindings that rewrite or re-run bindings can loop and undo edits. \
+                             Set bindings in the config instead, or run this by hand."
+                .to_string(),
+            matched_pattern: Some(SELF_REFERENCE.to_string()),
+        });
+    }
+
+    None
+}
+
+/// Returns whether a word is a shell-style `NAME=value` assignment
+///
+/// Names follow POSIX rules: a letter or underscore, then letters, digits
+/// or underscores. `--opt=value` and `=value` are not assignments.
+pub(crate) fn is_env_assignment(word: &str) -> bool {
+    let Some((name, _)) = word.split_once('=') else {
+        return false;
+    };
+
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 #[cfg(test)]
 mod tests;
@@ -21,27 +21,34 @@ use std::collections::HashSet;
 
 use regex::Regex;
 
-/// Builds regex patterns for critical system-destroying commands (Round 1)
+use crate::core::help::{HelpSection, HelpTopic};
+
+/// Regex sources for critical system-destroying commands (Round 1)
 ///
 /// These patterns detect immediate, irreversible system destruction:
 /// - Pattern 0-1: `rm -rf /` variants (filesystem destruction)
 /// - Pattern 2: `dd` to disk devices (partition table destruction)
 /// - Pattern 3: Fork bomb (resource exhaustion)
+pub const CRITICAL_PATTERNS: [&str; 4] = [
+    // Pattern 0a: rm -rf / (r before f)
+    r"rm\s+.*[rR].*[fF].*\s+/\s*$",
+    // Pattern 0b: rm -fr / (f before r)
+    r"rm\s+.*[fF].*[rR].*\s+/\s*$",
+    // Pattern 1: dd to disk devices
+    r"dd\s+.*of=/dev/(sd[a-z]|nvme\d+n\d+)",
+    // Pattern 2: Fork bomb
+    r":\(\)\s*\{\s*:\s*\|\s*:\s*&\s*}\s*;\s*:",
+];
+
+/// Builds the regexes of [`CRITICAL_PATTERNS`], in the same order
+///
+/// The sources are constants covered by the tests, so none is expected to
+/// be invalid; one that was would be left out rather than panic.
 pub fn build_critical_patterns() -> Vec<Regex> {
-    vec![
-        // Pattern 0a: rm -rf / (r before f)
-        Regex::new(r"rm\s+.*[rR].*[fF].*\s+/\s*$")
-            .expect("rm -rf / pattern (r before f) should be valid regex"),
-        // Pattern 0b: rm -fr / (f before r)
-        Regex::new(r"rm\s+.*[fF].*[rR].*\s+/\s*$")
-            .expect("rm -fr / pattern (f before r) should be valid regex"),
-        // Pattern 1: dd to disk devices
-        Regex::new(r"dd\s+.*of=/dev/(sd[a-z]|nvme\d+n\d+)")
-            .expect("dd to disk pattern should be valid regex"),
-        // Pattern 2: Fork bomb
-        Regex::new(r":\(\)\s*\{\s*:\s*\|\s*:\s*&\s*}\s*;\s*:")
-            .expect("fork bomb pattern should be valid regex"),
-    ]
+    CRITICAL_PATTERNS
+        .iter()
+        .filter_map(|pattern| Regex::new(pattern).ok())
+        .collect()
 }
 
 /// Builds HashSet of dangerous commands (Round 2)
@@ -121,6 +128,114 @@ pub fn build_suspicious_commands() -> HashSet<String> {
     .collect()
 }
 
+/// Builds HashSet of suspicious environment variables
+///
+/// Leading `VAR=value` assignments on an exec line are skipped when finding
+/// the real command, but these variables make the launched program load or
+/// run extra code and are flagged on their own.
+///
+/// # Categories
+/// - **Dynamic loader**: LD_PRELOAD, LD_AUDIT, LD_LIBRARY_PATH
+/// - **Interpreter startup hooks**: BASH_ENV, PERL5OPT, NODE_OPTIONS
+pub fn build_suspicious_env_vars() -> HashSet<String> {
+    vec![
+        // Dynamic loader hooks (library injection)
+        "LD_PRELOAD",
+        "LD_AUDIT",
+        "LD_LIBRARY_PATH",
+        // Interpreter startup hooks (code run before the script)
+        "BASH_ENV",
+        "PERL5OPT",
+        "NODE_OPTIONS",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+/// A launcher that runs another program without raising its privileges
+///
+/// Commands started through one of these are assessed by the program they
+/// wrap, so `flatpak run org.mozilla.firefox` is judged as Firefox rather
+/// than as an unknown `flatpak` invocation.
+#[derive(Clone, Copy, Debug)]
+pub struct SafeWrapper {
+    /// Words that introduce the wrapper (e.g. `["flatpak", "run"]`)
+    pub prefix: &'static [&'static str],
+    /// Option without which the wrapper is not safe (e.g. `--user`)
+    pub required_option: Option<&'static str>,
+    /// Options that take their value as the following word
+    pub value_options: &'static [&'static str],
+    /// Options whose value is a `VAR=value` assignment for the wrapped
+    /// program (e.g. `--setenv`)
+    pub env_options: &'static [&'static str],
+    /// Whether the first operand is a Flatpak application ID
+    pub app_id: bool,
+}
+
+/// Builds the list of wrappers that are looked through (Round 2)
+///
+/// # Wrappers
+/// - **flatpak run**: Sandboxed application, identified by its app ID
+/// - **systemd-run --user**: Transient unit in the user's own service manager.
+///   Without `--user` it runs as a system service, so it is not looked through.
+/// - **uwsm app** / **uwsm-app**: UWSM session launcher (systemd user units)
+pub fn build_safe_wrappers() -> Vec<SafeWrapper> {
+    const UWSM_VALUE_OPTIONS: &[&str] = &["-s", "-a", "-u", "-t", "-d", "-p", "-S"];
+
+    vec![
+        SafeWrapper {
+            prefix: &["flatpak", "run"],
+            required_option: None,
+            value_options: &[
+                "--arch",
+                "--branch",
+                "--command",
+                "--cwd",
+                "--runtime",
+                "--runtime-version",
+                "--commit",
+                "--runtime-commit",
+                "--env",
+            ],
+            env_options: &["--env"],
+            app_id: true,
+        },
+        SafeWrapper {
+            prefix: &["systemd-run"],
+            required_option: Some("--user"),
+            value_options: &[
+                "-u",
+                "--unit",
+                "-p",
+                "--property",
+                "-E",
+                "--setenv",
+                "--description",
+                "--slice",
+                "--working-directory",
+                "--nice",
+            ],
+            env_options: &["-E", "--setenv"],
+            app_id: false,
+        },
+        SafeWrapper {
+            prefix: &["uwsm", "app"],
+            required_option: None,
+            value_options: UWSM_VALUE_OPTIONS,
+            env_options: &[],
+            app_id: false,
+        },
+        SafeWrapper {
+            prefix: &["uwsm-app"],
+            required_option: None,
+            value_options: UWSM_VALUE_OPTIONS,
+            env_options: &[],
+            app_id: false,
+        },
+    ]
+}
+
 /// Builds HashSet of safe commands (Round 2)
 ///
 /// Whitelisted commands that are known to be safe. This provides:
@@ -181,3 +296,85 @@ pub fn build_safe_commands() -> HashSet<String> {
     .map(String::from)
     .collect()
 }
+
+/// Help topics describing the rules above, for the Help window
+///
+/// The command lists are read from the builders, so the topics list exactly
+/// what the detector checks. Rules added through the update channel are not
+/// included.
+pub fn danger_rule_topics() -> Vec<HelpTopic> {
+    let sorted = |commands: HashSet<String>| {
+        let mut commands: Vec<String> = commands.into_iter().collect();
+        commands.sort();
+        commands.join(", ")
+    };
+    let wrappers: Vec<String> = build_safe_wrappers()
+        .iter()
+        .map(|wrapper| {
+            let mut words = wrapper.prefix.join(" ");
+            if let Some(option) = wrapper.required_option {
+                words.push_str(&format!(" {}", option));
+            }
+            words
+        })
+        .collect();
+
+    vec![
+        HelpTopic::new(
+            HelpSection::DangerRules,
+            "Critical commands (blocked)",
+            "Commands that destroy the system at once are never written to the config:\n\
+             - rm -rf / (in either flag order)\n\
+             - dd writing to a disk device (of=/dev/sdX, of=/dev/nvmeXnY)\n\
+             - fork bombs (:(){ :|:& };:)",
+        ),
+        HelpTopic::new(
+            HelpSection::DangerRules,
+            "Dangerous commands (warned)",
+            format!(
+                "Commands that can do serious damage but have legitimate uses are saved \
+                 with a warning:\n{}\n\n\
+                 Their arguments are looked at too: chmod 777, piping a download into a \
+                 shell, and bindings that rewrite or re-run the bindings themselves \
+                 (hyprctl keyword bind, hyprctl reload) are flagged.",
+                sorted(build_dangerous_commands())
+            ),
+        ),
+        HelpTopic::new(
+            HelpSection::DangerRules,
+            "Suspicious commands",
+            format!(
+                "Tools often used to hide or fetch payloads are pointed out:\n{}\n\n\
+                 Arguments that look like base64 or hex encoded data (by their Shannon \
+                 entropy) are suspicious as well.",
+                sorted(build_suspicious_commands())
+            ),
+        ),
+        HelpTopic::new(
+            HelpSection::DangerRules,
+            "Suspicious environment variables",
+            format!(
+                "Leading VAR=value assignments are skipped to find the real command, \
+                 but these load or run extra code and are flagged:\n{}",
+                sorted(build_suspicious_env_vars())
+            ),
+        ),
+        HelpTopic::new(
+            HelpSection::DangerRules,
+            "Launch wrappers",
+            format!(
+                "Commands started through these launchers are judged by the program \
+                 they start:\n{}",
+                wrappers.join(", ")
+            ),
+        ),
+        HelpTopic::new(
+            HelpSection::DangerRules,
+            "Known safe commands",
+            format!(
+                "Common applications are recognised as safe right away:\n{}",
+                sorted(build_safe_commands())
+            ),
+        ),
+    ]
+}
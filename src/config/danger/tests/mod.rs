@@ -18,6 +18,7 @@
 //! - Pattern tests (critical patterns, dangerous commands, safe whitelist)
 //! - Entropy tests (Shannon entropy, base64/hex encoding detection)
 //! - Integration tests (end-to-end danger assessment)
+//! - Update tests (signed rulesets, merging with the built-in rules)
 
 #[cfg(test)]
 mod entropy_tests;
@@ -27,3 +28,6 @@ mod integration_tests;
 
 #[cfg(test)]
 mod patterns_tests;
+
+#[cfg(test)]
+mod updates_tests;
@@ -54,6 +54,14 @@ fn test_detect_rm_rf_root_critical() {
     }
 }
 
+#[test]
+fn test_every_critical_pattern_compiles() {
+    assert_eq!(
+        patterns::build_critical_patterns().len(),
+        patterns::CRITICAL_PATTERNS.len()
+    );
+}
+
 #[test]
 fn test_detect_dd_to_disk_critical() {
     let detector = DangerDetector::new();
@@ -439,3 +447,204 @@ fn test_unknown_commands_default_to_safe() {
         );
     }
 }
+
+// ========================================================================
+// Environment Assignments: `exec, VAR=value app`
+// ========================================================================
+
+#[test]
+fn test_env_assignments_skipped_before_command() {
+    let detector = DangerDetector::new();
+
+    // The whitelisted executable follows the assignments
+    let assessment = detector.assess_command("ENV=1 GDK_BACKEND=wayland firefox --new-window");
+    assert_eq!(assessment.danger_level, DangerLevel::Safe);
+    assert_eq!(assessment.reason, "Known safe command");
+
+    // Dangerous commands are still found behind assignments
+    let assessment = detector.assess_command("LANG=C sudo reboot");
+    assert_eq!(assessment.danger_level, DangerLevel::Dangerous);
+    assert_eq!(assessment.matched_pattern.as_deref(), Some("sudo"));
+
+    // Option-style words are not assignments
+    let assessment = detector.assess_command("--config=foo firefox");
+    assert_ne!(assessment.reason, "Known safe command");
+}
+
+#[test]
+fn test_suspicious_env_assignments_flagged() {
+    let detector = DangerDetector::new();
+
+    let test_cases = vec![
+        "LD_PRELOAD=/tmp/hook.so firefox",
+        "FOO=1 LD_AUDIT=/tmp/audit.so my-app",
+        "BASH_ENV=/tmp/payload bash -c true",
+    ];
+
+    for command in test_cases {
+        let assessment = detector.assess_command(command);
+        assert_eq!(
+            assessment.danger_level,
+            DangerLevel::Suspicious,
+            "Command '{}' should be flagged as Suspicious",
+            command
+        );
+        assert!(
+            assessment
+                .matched_pattern
+                .as_deref()
+                .is_some_and(|pattern| pattern.ends_with('=')),
+            "Should report the assignment: {:?}",
+            assessment.matched_pattern
+        );
+    }
+
+    // A more serious command still wins over the assignment
+    let assessment = detector.assess_command("LD_PRELOAD=/tmp/hook.so rm -rf /");
+    assert_eq!(assessment.danger_level, DangerLevel::Critical);
+}
+
+// ========================================================================
+// Safe Wrappers: `flatpak run`, `systemd-run --user`, `uwsm app`
+// ========================================================================
+
+#[test]
+fn test_safe_wrappers_assess_wrapped_program() {
+    let detector = DangerDetector::new();
+
+    let test_cases = vec![
+        ("flatpak run org.mozilla.firefox", "'flatpak run'"),
+        (
+            "flatpak run --branch stable org.mozilla.firefox",
+            "'flatpak run'",
+        ),
+        (
+            "systemd-run --user --scope -u browser kitty",
+            "'systemd-run --user'",
+        ),
+        ("uwsm app -s b -- foot", "'uwsm app'"),
+        ("uwsm-app -- nvim", "'uwsm-app'"),
+    ];
+
+    for (command, wrapper) in test_cases {
+        let assessment = detector.assess_command(command);
+        assert_eq!(assessment.danger_level, DangerLevel::Safe);
+        assert_eq!(
+            assessment.reason,
+            format!("Known safe command (run via {})", wrapper),
+            "Command '{}' should be whitelisted through its wrapper",
+            command
+        );
+    }
+
+    // Wrappers can be nested
+    let assessment = detector.assess_command("uwsm app -- flatpak run org.mozilla.firefox");
+    assert!(assessment
+        .reason
+        .ends_with("(run via 'uwsm app', 'flatpak run')"));
+}
+
+#[test]
+fn test_safe_wrappers_do_not_hide_danger() {
+    let detector = DangerDetector::new();
+
+    let assessment = detector.assess_command("uwsm app -- sudo reboot");
+    assert_eq!(assessment.danger_level, DangerLevel::Dangerous);
+    assert_eq!(assessment.matched_pattern.as_deref(), Some("sudo"));
+
+    let assessment = detector.assess_command("systemd-run --user -- base64 -d payload");
+    assert_eq!(assessment.danger_level, DangerLevel::Suspicious);
+    assert!(assessment.reason.contains("'systemd-run --user'"));
+
+    // Assignments before the wrapper are still checked
+    let assessment = detector.assess_command("LD_PRELOAD=/tmp/hook.so uwsm app -- kitty");
+    assert_eq!(assessment.matched_pattern.as_deref(), Some("LD_PRELOAD="));
+
+    // So are variables the wrapper sets for the wrapped program
+    for command in [
+        "systemd-run --user -E LD_PRELOAD=/tmp/hook.so firefox",
+        "systemd-run --user --setenv=LD_PRELOAD=/tmp/hook.so firefox",
+        "flatpak run --env=LD_PRELOAD=/tmp/hook.so org.mozilla.firefox",
+        "flatpak run --env LD_PRELOAD=/tmp/hook.so org.mozilla.firefox",
+        "uwsm app -- systemd-run --user -E LD_PRELOAD=/tmp/hook.so firefox",
+    ] {
+        let assessment = detector.assess_command(command);
+        assert_eq!(
+            assessment.danger_level,
+            DangerLevel::Suspicious,
+            "{}",
+            command
+        );
+        assert_eq!(
+            assessment.matched_pattern.as_deref(),
+            Some("LD_PRELOAD="),
+            "{}",
+            command
+        );
+    }
+    let assessment = detector.assess_command("systemd-run --user -E LANG=C firefox");
+    assert_eq!(assessment.danger_level, DangerLevel::Safe);
+
+    // Without --user, systemd-run starts a system service and is not looked through
+    let assessment = detector.assess_command("systemd-run --scope firefox");
+    assert!(!assessment.reason.contains("run via"));
+}
+
+#[test]
+fn test_self_referencing_bindings_are_dangerous() {
+    let detector = DangerDetector::new();
+
+    for command in [
+        "hyprctl keyword bind SUPER,K,exec,kitty",
+        "hyprctl -q keyword unbind SUPER,K",
+        "/usr/bin/hyprctl reload",
+        "uwsm app -- hyprctl keyword bindd SUPER,K,Terminal,exec,kitty",
+        "hypr-keybind-manager run browser",
+        "hypr-keybind-manager check -c hyprland.conf --fix --strategy keep-first",
+    ] {
+        let assessment = detector.assess_command(command);
+        assert_eq!(
+            assessment.danger_level,
+            DangerLevel::Dangerous,
+            "{} should be Dangerous",
+            command
+        );
+        assert_eq!(
+            assessment.matched_pattern.as_deref(),
+            Some(SELF_REFERENCE),
+            "{}",
+            command
+        );
+    }
+
+    // Reading state, or checking without fixing, changes no bindings
+    for command in [
+        "hyprctl binds",
+        "hyprctl keyword general:gaps_in 5",
+        "hypr-keybind-manager check",
+        "hypr-keybind-manager gui",
+    ] {
+        assert_ne!(
+            detector.assess_command(command).matched_pattern.as_deref(),
+            Some(SELF_REFERENCE),
+            "{}",
+            command
+        );
+    }
+}
+
+#[test]
+fn test_danger_rule_topics_list_the_checked_commands() {
+    let topics = patterns::danger_rule_topics();
+    let body = |title: &str| {
+        topics
+            .iter()
+            .find(|topic| topic.title == title)
+            .map(|topic| topic.body.clone())
+            .unwrap_or_default()
+    };
+
+    assert!(body("Dangerous commands (warned)").contains("pkexec, shred, srm"));
+    assert!(body("Suspicious environment variables").contains("LD_PRELOAD"));
+    assert!(body("Launch wrappers").contains("systemd-run --user"));
+}
@@ -0,0 +1,149 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ed25519_dalek::{Signer, SigningKey};
+use std::fs;
+use tempfile::TempDir;
+
+use crate::config::danger::{
+    updates::{load_installed_rules, RulesChannel, RulesError, CHANNEL_FILE, INSTALLED_FILE},
+    DangerDetector, DangerLevel,
+};
+
+const URL: &str = "https://example.org/danger-rules.json";
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn channel_file(key: &SigningKey) -> String {
+    format!(
+        "# Danger rule updates\nurl = {}\npublic_key = {}\n",
+        URL,
+        hex(key.verifying_key().as_bytes())
+    )
+}
+
+/// A signed envelope around a ruleset of the given version
+fn signed(key: &SigningKey, version: u64) -> String {
+    let payload = serde_json::json!({
+        "version": version,
+        "patterns": [{
+            "name": "install from source",
+            "regex": r"git\s+clone\s.*make\s+install",
+            "level": "dangerous",
+            "reason": "Builds and installs unreviewed code"
+        }],
+        "suspicious_commands": ["socat"]
+    })
+    .to_string();
+    let signature = key.sign(payload.as_bytes());
+
+    serde_json::json!({ "payload": payload, "signature": hex(&signature.to_bytes()) }).to_string()
+}
+
+#[test]
+fn test_only_rulesets_signed_with_the_pinned_key_verify() {
+    let key = signing_key(1);
+    let channel = RulesChannel::parse(&channel_file(&key)).unwrap();
+
+    let rules = channel.verify(&signed(&key, 2)).unwrap();
+    assert_eq!(rules.version, 2);
+    assert_eq!(rules.suspicious_commands, vec!["socat"]);
+
+    assert!(matches!(
+        channel.verify(&signed(&signing_key(2), 2)),
+        Err(RulesError::BadSignature)
+    ));
+    let tampered = signed(&key, 2).replace("socat", "firefox");
+    assert!(matches!(
+        channel.verify(&tampered),
+        Err(RulesError::BadSignature)
+    ));
+}
+
+#[test]
+fn test_channel_requires_https_and_a_key() {
+    let key = signing_key(1);
+    let plain_http = channel_file(&key).replace("https://", "http://");
+
+    assert!(RulesChannel::parse(&plain_http).is_err());
+    assert!(RulesChannel::parse(&format!("url = {}\n", URL)).is_err());
+    assert!(RulesChannel::parse(&channel_file(&key)).is_ok());
+}
+
+#[test]
+fn test_installed_rules_are_reverified_and_never_rolled_back() {
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path();
+    let key = signing_key(1);
+
+    // No channel: built-in rules only, nothing is read
+    assert!(load_installed_rules(dir).unwrap().is_none());
+
+    fs::write(dir.join(CHANNEL_FILE), channel_file(&key)).unwrap();
+    let channel = RulesChannel::load(&dir.join(CHANNEL_FILE))
+        .unwrap()
+        .unwrap();
+    let installed = dir.join(INSTALLED_FILE);
+    channel.install(&signed(&key, 3), &installed).unwrap();
+    assert_eq!(load_installed_rules(dir).unwrap().unwrap().version, 3);
+
+    assert!(matches!(
+        channel.install(&signed(&key, 2), &installed),
+        Err(RulesError::Rollback {
+            installed: 3,
+            offered: 2
+        })
+    ));
+
+    // Editing the installed file breaks its signature
+    let content = fs::read_to_string(&installed).unwrap();
+    fs::write(&installed, content.replace("socat", "ssh")).unwrap();
+    assert!(load_installed_rules(dir).is_err());
+}
+
+#[test]
+fn test_update_rules_only_raise_the_verdict() {
+    let key = signing_key(1);
+    let channel = RulesChannel::parse(&channel_file(&key)).unwrap();
+    let detector = DangerDetector::with_rules(&channel.verify(&signed(&key, 1)).unwrap());
+
+    let command = "git clone https://example.org/tool && make install";
+    assert!(DangerDetector::new().assess_command(command).danger_level < DangerLevel::Dangerous);
+    let assessment = detector.assess_command(command);
+    assert_eq!(assessment.danger_level, DangerLevel::Dangerous);
+    assert_eq!(
+        assessment.matched_pattern.as_deref(),
+        Some("install from source")
+    );
+    assert_eq!(
+        detector.assess_command("socat - TCP:host:1").danger_level,
+        DangerLevel::Suspicious
+    );
+
+    // Built-in verdicts above the rule's level are kept
+    assert_eq!(
+        detector.assess_command("rm -rf /").danger_level,
+        DangerLevel::Critical
+    );
+    assert_eq!(
+        detector.assess_command("firefox").danger_level,
+        DangerLevel::Safe
+    );
+}
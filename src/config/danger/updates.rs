@@ -0,0 +1,483 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Danger rule updates
+//!
+//! New attack patterns can be distributed as a signed ruleset instead of a
+//! crate release. The built-in rules stay the pinned default, and the
+//! update channel is opt-in and manual:
+//!
+//! - **Offline by default**: nothing is fetched unless a channel file
+//!   exists *and* `hypr-keybind-manager update-rules` is run
+//! - **Signed**: rulesets are signed with Ed25519. The public key is pinned
+//!   in the channel file, and the signature is checked when a ruleset is
+//!   fetched and again every time it is loaded
+//! - **Data only**: a ruleset holds regexes and command names; nothing in
+//!   it is ever executed
+//! - **Additive**: rules are merged with the built-in ones and can only
+//!   raise a verdict. The safe list cannot be changed remotely
+//! - **No rollback**: a ruleset older than the installed one is refused
+//!
+//! # Files
+//!
+//! Both live in `$XDG_CONFIG_HOME/hypr-keybind-manager/` (falling back to
+//! `~/.config/hypr-keybind-manager/`):
+//!
+//! - `rules-channel`: where to fetch from and the key to trust
+//!   ```text
+//!   url = https://example.org/hypr-keybind-manager/danger-rules.json
+//!   public_key = <64 hex digits>
+//!   ```
+//! - `danger-rules.json`: the installed ruleset, exactly as fetched
+//!
+//! # Format
+//!
+//! The fetched file is an envelope holding the ruleset as a string and the
+//! hex signature of that string's bytes:
+//!
+//! ```text
+//! {"payload": "{\"version\": 2, ...}", "signature": "<128 hex digits>"}
+//! ```
+//!
+//! The payload is a [`RuleSet`]:
+//!
+//! ```text
+//! {
+//!   "version": 2,
+//!   "patterns": [{
+//!     "name": "install from source",
+//!     "regex": "git\\s+clone\\s.*make\\s+install",
+//!     "level": "dangerous",
+//!     "reason": "Builds and installs unreviewed code",
+//!     "recommendation": "Use a packaged version instead"
+//!   }],
+//!   "dangerous_commands": ["mkswap"],
+//!   "suspicious_commands": ["socat"]
+//! }
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::config::{
+    danger::{DangerAssessment, DangerLevel},
+    paths::RuntimePaths,
+};
+
+/// Name of the channel file in the config directory
+pub const CHANNEL_FILE: &str = "rules-channel";
+
+/// Name of the installed ruleset in the config directory
+pub const INSTALLED_FILE: &str = "danger-rules.json";
+
+/// Largest ruleset envelope accepted, in bytes
+const MAX_RULESET_BYTES: usize = 1024 * 1024;
+
+/// Most rules of each kind accepted
+const MAX_RULES: usize = 1000;
+
+/// Compiled size limit per pattern, so a ruleset cannot exhaust memory
+const REGEX_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// Seconds before a fetch is abandoned
+const FETCH_TIMEOUT_SECS: &str = "30";
+
+/// Errors from fetching, verifying or loading a ruleset
+#[derive(Debug, Error)]
+pub enum RulesError {
+    #[error("Invalid rules channel file: {0}")]
+    InvalidChannel(String),
+
+    #[error("Failed to fetch rules: {0}")]
+    FetchFailed(String),
+
+    #[error("Ruleset signature does not match the pinned public key")]
+    BadSignature,
+
+    #[error("Invalid ruleset: {0}")]
+    InvalidRuleSet(String),
+
+    #[error("Ruleset version {offered} is older than the installed version {installed}")]
+    Rollback { installed: u64, offered: u64 },
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Danger level a remote rule can assign
+///
+/// There is no `Safe`: remote rules can only raise a verdict.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Suspicious,
+    Dangerous,
+    Critical,
+}
+
+impl From<RuleLevel> for DangerLevel {
+    fn from(level: RuleLevel) -> Self {
+        match level {
+            RuleLevel::Suspicious => DangerLevel::Suspicious,
+            RuleLevel::Dangerous => DangerLevel::Dangerous,
+            RuleLevel::Critical => DangerLevel::Critical,
+        }
+    }
+}
+
+/// A regex rule of a ruleset
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct PatternRule {
+    /// Short name, shown as the matched pattern
+    pub name: String,
+    /// Regex matched against the whole command
+    pub regex: String,
+    /// Level of a matching command
+    pub level: RuleLevel,
+    /// Why a matching command is dangerous
+    pub reason: String,
+    /// What to do instead
+    #[serde(default)]
+    pub recommendation: String,
+}
+
+/// Danger rules distributed through the update channel
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct RuleSet {
+    /// Increases with every release of the ruleset
+    pub version: u64,
+    /// Regex rules, checked after the built-in pipeline
+    #[serde(default)]
+    pub patterns: Vec<PatternRule>,
+    /// Added to the built-in dangerous commands
+    #[serde(default)]
+    pub dangerous_commands: Vec<String>,
+    /// Added to the built-in suspicious commands
+    #[serde(default)]
+    pub suspicious_commands: Vec<String>,
+}
+
+impl RuleSet {
+    /// Checks limits, command names and that every regex compiles
+    fn validate(&self) -> Result<(), RulesError> {
+        let invalid = |message: String| Err(RulesError::InvalidRuleSet(message));
+
+        if self.patterns.len() > MAX_RULES
+            || self.dangerous_commands.len() > MAX_RULES
+            || self.suspicious_commands.len() > MAX_RULES
+        {
+            return invalid(format!("more than {} rules of one kind", MAX_RULES));
+        }
+
+        for command in self
+            .dangerous_commands
+            .iter()
+            .chain(&self.suspicious_commands)
+        {
+            let valid = !command.is_empty()
+                && command
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "._+-".contains(c));
+            if !valid {
+                return invalid(format!("'{}' is not a command name", command));
+            }
+        }
+
+        for pattern in &self.patterns {
+            if let Err(e) = compile(&pattern.regex) {
+                return invalid(format!("pattern '{}': {}", pattern.name, e));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compiles the pattern rules for the detector
+    pub(crate) fn compiled_patterns(&self) -> Vec<CompiledRule> {
+        self.patterns
+            .iter()
+            .filter_map(|rule| {
+                Some(CompiledRule {
+                    regex: compile(&rule.regex).ok()?,
+                    assessment: DangerAssessment {
+                        danger_level: rule.level.into(),
+                        reason: rule.reason.clone(),
+                        recommendation: rule.recommendation.clone(),
+                        matched_pattern: Some(rule.name.clone()),
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+/// A pattern rule ready for matching
+#[derive(Clone, Debug)]
+pub(crate) struct CompiledRule {
+    pub(crate) regex: Regex,
+    /// Returned when the regex matches
+    pub(crate) assessment: DangerAssessment,
+}
+
+fn compile(regex: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(regex)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+}
+
+/// The fetched file: a ruleset and its signature
+#[derive(Debug, Deserialize)]
+struct SignedRuleSet {
+    /// The [`RuleSet`] as JSON text, exactly as signed
+    payload: String,
+    /// Hex Ed25519 signature of the payload bytes
+    signature: String,
+}
+
+/// Where rulesets come from and the key they must be signed with
+#[derive(Clone, Debug)]
+pub struct RulesChannel {
+    /// HTTPS address of the signed ruleset
+    pub url: String,
+    key: VerifyingKey,
+}
+
+impl RulesChannel {
+    /// Returns the directory holding the channel file and installed ruleset
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - [`RuntimePaths::config_dir`]
+    /// * `None` - Neither `XDG_CONFIG_HOME` nor `HOME` is set
+    pub fn config_dir() -> Option<PathBuf> {
+        RuntimePaths::from_env().config_dir()
+    }
+
+    /// Loads the channel file
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(channel))` - The channel is configured
+    /// * `Ok(None)` - No channel file: the built-in rules are used alone
+    ///
+    /// # Errors
+    ///
+    /// `RulesError::InvalidChannel` for a malformed file, `RulesError::Io`
+    /// if it exists but cannot be read.
+    pub fn load(path: &Path) -> Result<Option<Self>, RulesError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RulesError::Io(e)),
+        }
+    }
+
+    /// Parses the `url = ...` and `public_key = ...` lines of a channel file
+    ///
+    /// # Errors
+    ///
+    /// `RulesError::InvalidChannel` if either is missing, the URL is not
+    /// HTTPS or the key is not 32 hex-encoded bytes.
+    pub fn parse(content: &str) -> Result<Self, RulesError> {
+        let mut url = None;
+        let mut key = None;
+        for (name, value) in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+        {
+            match name.trim() {
+                "url" => url = Some(value.trim().to_string()),
+                "public_key" => key = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+
+        let invalid = |message: &str| RulesError::InvalidChannel(message.to_string());
+        let url = url.ok_or_else(|| invalid("missing 'url'"))?;
+        if !url.starts_with("https://") {
+            return Err(invalid("'url' must be an https:// address"));
+        }
+
+        let key = key.ok_or_else(|| invalid("missing 'public_key'"))?;
+        let key = decode_hex::<32>(&key)
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| invalid("'public_key' is not an Ed25519 key in hex"))?;
+
+        Ok(Self { url, key })
+    }
+
+    /// Checks a signed ruleset against the pinned key
+    ///
+    /// # Arguments
+    /// * `signed` - The fetched envelope
+    ///
+    /// # Errors
+    ///
+    /// * `RulesError::BadSignature` - Not signed with the pinned key
+    /// * `RulesError::InvalidRuleSet` - Malformed, over the limits, or a
+    ///   regex does not compile
+    pub fn verify(&self, signed: &str) -> Result<RuleSet, RulesError> {
+        if signed.len() > MAX_RULESET_BYTES {
+            return Err(RulesError::InvalidRuleSet(format!(
+                "larger than {} bytes",
+                MAX_RULESET_BYTES
+            )));
+        }
+
+        let envelope: SignedRuleSet =
+            serde_json::from_str(signed).map_err(|e| RulesError::InvalidRuleSet(e.to_string()))?;
+        let signature = decode_hex::<64>(&envelope.signature)
+            .map(|bytes| Signature::from_bytes(&bytes))
+            .ok_or(RulesError::BadSignature)?;
+        self.key
+            .verify_strict(envelope.payload.as_bytes(), &signature)
+            .map_err(|_| RulesError::BadSignature)?;
+
+        // Only signed data is parsed any further
+        let rules: RuleSet = serde_json::from_str(&envelope.payload)
+            .map_err(|e| RulesError::InvalidRuleSet(e.to_string()))?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    /// Downloads the signed ruleset with `curl`
+    ///
+    /// Only HTTPS is allowed, redirects included. The download is limited
+    /// in time and size. Nothing is verified yet; see [`Self::verify`].
+    ///
+    /// # Errors
+    ///
+    /// `RulesError::FetchFailed` if `curl` is missing or the download fails.
+    pub fn fetch(&self) -> Result<String, RulesError> {
+        let output = Command::new("curl")
+            .args([
+                "--fail",
+                "--silent",
+                "--show-error",
+                "--location",
+                "--proto",
+                "=https",
+                "--proto-redir",
+                "=https",
+                "--max-time",
+                FETCH_TIMEOUT_SECS,
+                "--max-filesize",
+                &MAX_RULESET_BYTES.to_string(),
+                "--",
+                &self.url,
+            ])
+            .output()
+            .map_err(|e| RulesError::FetchFailed(format!("cannot run curl: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(RulesError::FetchFailed(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        String::from_utf8(output.stdout)
+            .map_err(|_| RulesError::FetchFailed("response is not UTF-8".to_string()))
+    }
+
+    /// Verifies a signed ruleset and installs it
+    ///
+    /// # Arguments
+    /// * `signed` - The fetched envelope
+    /// * `installed_path` - Where the installed ruleset is kept
+    ///
+    /// # Errors
+    ///
+    /// Any error of [`Self::verify`], `RulesError::Rollback` if the
+    /// installed ruleset is newer, or `RulesError::Io` if it cannot be
+    /// written. The installed ruleset is unchanged on error.
+    pub fn install(&self, signed: &str, installed_path: &Path) -> Result<RuleSet, RulesError> {
+        let rules = self.verify(signed)?;
+
+        // An installed ruleset that no longer verifies is simply replaced
+        if let Ok(Some(installed)) = self.load_installed(installed_path) {
+            if rules.version < installed.version {
+                return Err(RulesError::Rollback {
+                    installed: installed.version,
+                    offered: rules.version,
+                });
+            }
+        }
+
+        if let Some(parent) = installed_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(installed_path, signed)?;
+        Ok(rules)
+    }
+
+    /// Loads and re-verifies the installed ruleset
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(rules))` - The installed ruleset
+    /// * `Ok(None)` - Nothing installed
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::verify`], e.g. if the file was changed after installing.
+    pub fn load_installed(&self, installed_path: &Path) -> Result<Option<RuleSet>, RulesError> {
+        match fs::read_to_string(installed_path) {
+            Ok(signed) => self.verify(&signed).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RulesError::Io(e)),
+        }
+    }
+}
+
+/// Loads the installed ruleset from a config directory
+///
+/// # Returns
+///
+/// * `Ok(Some(rules))` - A channel is configured and its ruleset installed
+/// * `Ok(None)` - No channel or nothing installed: built-in rules only
+///
+/// # Errors
+///
+/// An invalid channel file, or an installed ruleset that does not verify.
+pub fn load_installed_rules(config_dir: &Path) -> Result<Option<RuleSet>, RulesError> {
+    match RulesChannel::load(&config_dir.join(CHANNEL_FILE))? {
+        Some(channel) => channel.load_installed(&config_dir.join(INSTALLED_FILE)),
+        None => Ok(None),
+    }
+}
+
+/// Decodes exactly `N` bytes of hex
+fn decode_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    let hex = hex.trim().as_bytes();
+    if hex.len() != N * 2 {
+        return None;
+    }
+
+    let mut bytes = [0u8; N];
+    for (byte, pair) in bytes.iter_mut().zip(hex.chunks(2)) {
+        let pair = std::str::from_utf8(pair).ok()?;
+        *byte = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(bytes)
+}
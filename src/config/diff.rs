@@ -0,0 +1,386 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Line-level diffs of config content
+//!
+//! [`ConfigDiff`] compares the config as it is with the content a write is
+//! about to commit, so the change can be reviewed first: by `--dry-run` on
+//! the CLI commands that write, in the GUI's "Review changes" dialog and
+//! through [`ConfigTransaction::preview`](crate::config::ConfigTransaction::preview).
+//!
+//! Lines are matched with Myers' algorithm after skipping the common start
+//! and end, which is where most of a config is for a typical edit. Lines
+//! compare with their line endings, so a change from `\n` to `\r\n` or a
+//! dropped final newline shows up as a changed line.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::diff::ConfigDiff;
+//!
+//! let old = "$mainMod = SUPER\nbind = $mainMod, Q, killactive\n";
+//! let new = "$mainMod = SUPER\nbind = $mainMod, Q, exec, kitty\n";
+//!
+//! let diff = ConfigDiff::between(old, new);
+//! assert_eq!((diff.removed(), diff.added()), (1, 1));
+//! assert_eq!(
+//!     diff.to_unified("hyprland.conf", "hyprland.conf (new)"),
+//!     "--- hyprland.conf\n\
+//!      +++ hyprland.conf (new)\n\
+//!      @@ -1,2 +1,2 @@\n \
+//!      $mainMod = SUPER\n\
+//!      -bind = $mainMod, Q, killactive\n\
+//!      +bind = $mainMod, Q, exec, kitty\n"
+//! );
+//! ```
+
+use std::fmt::Write as _;
+
+/// Unchanged lines shown around each change
+pub const DEFAULT_CONTEXT: usize = 3;
+
+/// Edit distance (changed lines) up to which the smallest diff is searched
+/// for; past it, the rest of the changed region is shown as replaced
+const MAX_EDIT_DISTANCE: usize = 1000;
+
+/// What happened to a line
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineChange {
+    /// In both versions (shown for context)
+    Unchanged,
+    /// Only in the current config
+    Removed,
+    /// Only in the new content
+    Added,
+}
+
+/// One line of a hunk
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLine {
+    pub change: LineChange,
+    /// The line without its line ending
+    pub text: String,
+    /// The line is the last one and has no final newline
+    pub missing_newline: bool,
+}
+
+/// A run of changes with the unchanged lines around it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hunk {
+    /// First line of the hunk in the current config (1-based)
+    pub old_start: usize,
+    /// Lines of the current config the hunk covers
+    pub old_len: usize,
+    /// First line of the hunk in the new content (1-based)
+    pub new_start: usize,
+    /// Lines of the new content the hunk covers
+    pub new_len: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+impl Hunk {
+    /// The `@@ -1,3 +1,4 @@` header of the hunk
+    ///
+    /// As in `diff -u`, an empty side is numbered after the line it
+    /// follows.
+    pub fn header(&self) -> String {
+        let range = |start: usize, len: usize| match len {
+            0 => format!("{},0", start - 1),
+            1 => start.to_string(),
+            _ => format!("{},{}", start, len),
+        };
+        format!(
+            "@@ -{} +{} @@",
+            range(self.old_start, self.old_len),
+            range(self.new_start, self.new_len)
+        )
+    }
+}
+
+/// Line-level difference between two versions of a config
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    hunks: Vec<Hunk>,
+}
+
+/// Step of an edit script turning the old lines into the new ones
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edit {
+    Keep,
+    Delete,
+    Insert,
+}
+
+impl ConfigDiff {
+    /// Compares two versions, with [`DEFAULT_CONTEXT`] lines of context
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - Content as it is (usually the config on disk)
+    /// * `new` - Content about to be written
+    pub fn between(old: &str, new: &str) -> Self {
+        Self::with_context(old, new, DEFAULT_CONTEXT)
+    }
+
+    /// Compares two versions, showing `context` unchanged lines around
+    /// each change
+    ///
+    /// Changes closer together than twice the context share a hunk.
+    pub fn with_context(old: &str, new: &str, context: usize) -> Self {
+        let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+        let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+        let edits = edit_script(&old_lines, &new_lines);
+
+        let mut hunks = Vec::new();
+        // Lines of each side before edit `index`
+        let mut positions = Vec::with_capacity(edits.len() + 1);
+        let (mut old_pos, mut new_pos) = (0, 0);
+        for edit in &edits {
+            positions.push((old_pos, new_pos));
+            match edit {
+                Edit::Keep => {
+                    old_pos += 1;
+                    new_pos += 1;
+                }
+                Edit::Delete => old_pos += 1,
+                Edit::Insert => new_pos += 1,
+            }
+        }
+        positions.push((old_pos, new_pos));
+
+        let is_change = |edit: &Edit| *edit != Edit::Keep;
+        let mut index = 0;
+        while let Some(first_change) = edits[index..].iter().position(is_change) {
+            let first_change = index + first_change;
+            let start = first_change.saturating_sub(context).max(index);
+
+            let mut end = first_change;
+            loop {
+                while end < edits.len() && is_change(&edits[end]) {
+                    end += 1;
+                }
+                match edits[end..].iter().position(is_change) {
+                    Some(gap) if gap <= 2 * context => end += gap,
+                    _ => break,
+                }
+            }
+            let end = (end + context).min(edits.len());
+
+            let lines = edits[start..end]
+                .iter()
+                .zip(&positions[start..end])
+                .map(|(edit, &(old_pos, new_pos))| match edit {
+                    Edit::Keep => diff_line(LineChange::Unchanged, old_lines[old_pos]),
+                    Edit::Delete => diff_line(LineChange::Removed, old_lines[old_pos]),
+                    Edit::Insert => diff_line(LineChange::Added, new_lines[new_pos]),
+                })
+                .collect();
+            let (old_start, new_start) = positions[start];
+            let (old_end, new_end) = positions[end];
+            hunks.push(Hunk {
+                old_start: old_start + 1,
+                old_len: old_end - old_start,
+                new_start: new_start + 1,
+                new_len: new_end - new_start,
+                lines,
+            });
+            index = end;
+        }
+
+        Self { hunks }
+    }
+
+    /// Whether both versions are the same
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    pub fn hunks(&self) -> &[Hunk] {
+        &self.hunks
+    }
+
+    /// Number of lines only in the new content
+    pub fn added(&self) -> usize {
+        self.count(LineChange::Added)
+    }
+
+    /// Number of lines only in the current config
+    pub fn removed(&self) -> usize {
+        self.count(LineChange::Removed)
+    }
+
+    /// Renders the diff in unified format, as `diff -u` does
+    ///
+    /// # Arguments
+    ///
+    /// * `old_label` - Name of the current version, for the `---` line
+    /// * `new_label` - Name of the new version, for the `+++` line
+    ///
+    /// # Returns
+    ///
+    /// The diff, or an empty string when nothing changes
+    pub fn to_unified(&self, old_label: &str, new_label: &str) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut out = format!("--- {}\n+++ {}\n", old_label, new_label);
+        for hunk in &self.hunks {
+            out.push_str(&hunk.header());
+            out.push('\n');
+            for line in &hunk.lines {
+                let prefix = match line.change {
+                    LineChange::Unchanged => ' ',
+                    LineChange::Removed => '-',
+                    LineChange::Added => '+',
+                };
+                let _ = writeln!(out, "{}{}", prefix, line.text);
+                if line.missing_newline {
+                    out.push_str("\\ No newline at end of file\n");
+                }
+            }
+        }
+        out
+    }
+
+    fn count(&self, change: LineChange) -> usize {
+        self.hunks
+            .iter()
+            .flat_map(|hunk| &hunk.lines)
+            .filter(|line| line.change == change)
+            .count()
+    }
+}
+
+fn diff_line(change: LineChange, line: &str) -> DiffLine {
+    let text = line.strip_suffix('\n');
+    DiffLine {
+        change,
+        missing_newline: text.is_none(),
+        text: text
+            .map(|text| text.strip_suffix('\r').unwrap_or(text))
+            .unwrap_or(line)
+            .to_string(),
+    }
+}
+
+/// Shortest edit script from `old` to `new`
+///
+/// Lines both start or end with are kept without searching; the rest
+/// goes through Myers' O(ND) search (deletions before insertions, as in
+/// `diff`).
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let mut edits = vec![Edit::Keep; prefix];
+    edits.extend(myers(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    edits.extend(vec![Edit::Keep; suffix]);
+    edits
+}
+
+/// Myers' diff of two line slices
+///
+/// Round `d` finds the furthest x reachable with `d` edits on every
+/// diagonal `k` (`x - y`). Each round's diagonals are kept to walk back
+/// from the end, so memory grows with the square of the edit distance;
+/// past [`MAX_EDIT_DISTANCE`] the lines are shown as all removed, then all
+/// added.
+fn myers(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = n + m;
+    let offset = max + 1;
+    let mut furthest = vec![0isize; 2 * max as usize + 3];
+    // Diagonals -(d + 1)..=d + 1 as they were before round d
+    let mut rounds: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max {
+        if d as usize > MAX_EDIT_DISTANCE {
+            return replace_all(old.len(), new.len());
+        }
+        rounds.push(furthest[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let index = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && furthest[index - 1] < furthest[index + 1]) {
+                furthest[index + 1]
+            } else {
+                furthest[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            furthest[index] = x;
+            if x >= n && y >= m {
+                return backtrack(&rounds, n, m);
+            }
+        }
+    }
+    // The end is reached within n + m rounds; replacing every line is
+    // still a correct script should the loop ever finish without it
+    replace_all(old.len(), new.len())
+}
+
+/// Walks the Myers rounds back from the end into an edit script
+fn backtrack(rounds: &[Vec<isize>], n: isize, m: isize) -> Vec<Edit> {
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, before) in rounds.iter().enumerate().rev() {
+        let d = d as isize;
+        let furthest = |k: isize| before[(k + d + 1) as usize];
+        let k = x - y;
+        let previous_k = if k == -d || (k != d && furthest(k - 1) < furthest(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = furthest(previous_k);
+        let previous_y = previous_x - previous_k;
+
+        while x > previous_x && y > previous_y {
+            edits.push(Edit::Keep);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            edits.push(if x == previous_x {
+                Edit::Insert
+            } else {
+                Edit::Delete
+            });
+        }
+        x = previous_x;
+        y = previous_y;
+    }
+    edits.reverse();
+    edits
+}
+
+fn replace_all(removed: usize, added: usize) -> Vec<Edit> {
+    let mut edits = vec![Edit::Delete; removed];
+    edits.extend(vec![Edit::Insert; added]);
+    edits
+}
@@ -0,0 +1,274 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hyprland config discovery
+//!
+//! Finds the config to edit when `--config` is not given, trying in order:
+//!
+//! 1. `$HYPRLAND_CONFIG` ([`CONFIG_ENV_VAR`]), used as given
+//! 2. The config the running Hyprland instance loaded, read from the log
+//!    in its instance directory (`HYPRLAND_INSTANCE_SIGNATURE`)
+//! 3. `$XDG_CONFIG_HOME/hypr/hyprland.conf`
+//! 4. `~/.config/hypr/hyprland.conf`
+//! 5. `hypr/hyprland.conf` in each of `$XDG_CONFIG_DIRS` (`/etc/xdg`)
+//!
+//! The first that exists wins. Inside a flatpak `$XDG_CONFIG_HOME` points
+//! into the sandbox, so the home directory is tried as well. When nothing
+//! is found, [`ConfigError::ConfigNotDiscovered`] lists every path tried.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::discovery::{ConfigDiscovery, ConfigSource};
+//! use std::{ffi::OsString, path::Path};
+//!
+//! let discovery = ConfigDiscovery::from_vars(|name| match name {
+//!     "HYPRLAND_CONFIG" => Some(OsString::from("/home/user/dotfiles/hyprland.conf")),
+//!     _ => None,
+//! });
+//! let found = discovery.discover(None).unwrap();
+//! assert_eq!(found.path, Path::new("/home/user/dotfiles/hyprland.conf"));
+//! assert_eq!(found.source, ConfigSource::Environment);
+//! ```
+
+use std::{
+    env,
+    ffi::OsString,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::config::ConfigError;
+
+/// Environment variable naming the config to edit
+pub const CONFIG_ENV_VAR: &str = "HYPRLAND_CONFIG";
+
+/// Config path below a config base directory
+const CONFIG_RELATIVE_PATH: &str = "hypr/hyprland.conf";
+
+/// Base directories tried when `XDG_CONFIG_DIRS` is not set
+const DEFAULT_CONFIG_DIRS: &str = "/etc/xdg";
+
+/// Where a discovered config path came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// `--config` on the command line
+    CommandLine,
+    /// [`CONFIG_ENV_VAR`]
+    Environment,
+    /// The log of the running Hyprland instance
+    RunningInstance,
+    /// `$XDG_CONFIG_HOME`
+    XdgConfigHome,
+    /// `~/.config`
+    Home,
+    /// One of `$XDG_CONFIG_DIRS`
+    XdgConfigDirs,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ConfigSource::CommandLine => "--config",
+            ConfigSource::Environment => CONFIG_ENV_VAR,
+            ConfigSource::RunningInstance => "running Hyprland instance",
+            ConfigSource::XdgConfigHome => "XDG_CONFIG_HOME",
+            ConfigSource::Home => "home directory",
+            ConfigSource::XdgConfigDirs => "XDG_CONFIG_DIRS",
+        })
+    }
+}
+
+/// A config path and where it came from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscoveredConfig {
+    /// Path of the config, with a leading `~` expanded
+    pub path: PathBuf,
+    /// Where the path came from
+    pub source: ConfigSource,
+}
+
+/// Config locations resolved from the environment
+#[derive(Clone, Debug)]
+pub struct ConfigDiscovery {
+    environment: Option<PathBuf>,
+    instance_logs: Vec<PathBuf>,
+    config_home: Option<PathBuf>,
+    home: Option<PathBuf>,
+    config_dirs: Vec<PathBuf>,
+}
+
+impl ConfigDiscovery {
+    /// Resolves the locations from the process environment
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| env::var_os(name))
+    }
+
+    /// Resolves the locations from the given variable lookup
+    ///
+    /// As for [`RuntimePaths`](crate::config::paths::RuntimePaths),
+    /// relative or empty XDG variables are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `var` - Returns the value of an environment variable, if set
+    pub fn from_vars(var: impl Fn(&str) -> Option<OsString>) -> Self {
+        let absolute = |name: &str| var(name).map(PathBuf::from).filter(|dir| dir.is_absolute());
+
+        // Hyprland 0.40+ keeps its instance directories under
+        // $XDG_RUNTIME_DIR/hypr; older releases used /tmp/hypr
+        let instance_logs = var("HYPRLAND_INSTANCE_SIGNATURE")
+            .filter(|signature| !signature.is_empty())
+            .map(|signature| {
+                absolute("XDG_RUNTIME_DIR")
+                    .map(|dir| dir.join("hypr"))
+                    .into_iter()
+                    .chain([PathBuf::from("/tmp/hypr")])
+                    .map(|base| base.join(&signature).join("hyprland.log"))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let config_dirs = var("XDG_CONFIG_DIRS")
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| OsString::from(DEFAULT_CONFIG_DIRS));
+
+        Self {
+            environment: var(CONFIG_ENV_VAR)
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from),
+            instance_logs,
+            config_home: absolute("XDG_CONFIG_HOME"),
+            home: var("HOME")
+                .filter(|home| !home.is_empty())
+                .map(PathBuf::from),
+            config_dirs: env::split_paths(&config_dirs)
+                .filter(|dir| dir.is_absolute())
+                .collect(),
+        }
+    }
+
+    /// Paths tried when neither `--config` nor [`CONFIG_ENV_VAR`] is given
+    ///
+    /// Reads the running instance's log, if any, to find the config it
+    /// loaded. Duplicates are left out.
+    ///
+    /// # Returns
+    ///
+    /// Candidate paths in the order they are tried, existing or not
+    pub fn candidates(&self) -> Vec<DiscoveredConfig> {
+        let instance = self
+            .instance_logs
+            .iter()
+            .find_map(|log| fs::read_to_string(log).ok())
+            .and_then(|log| config_from_instance_log(&log))
+            .map(|path| (ConfigSource::RunningInstance, path));
+        let xdg = self
+            .config_home
+            .as_ref()
+            .map(|dir| (ConfigSource::XdgConfigHome, dir.join(CONFIG_RELATIVE_PATH)));
+        let home = self.home.as_ref().map(|home| {
+            (
+                ConfigSource::Home,
+                home.join(".config").join(CONFIG_RELATIVE_PATH),
+            )
+        });
+        let system = self
+            .config_dirs
+            .iter()
+            .map(|dir| (ConfigSource::XdgConfigDirs, dir.join(CONFIG_RELATIVE_PATH)));
+
+        let mut candidates: Vec<DiscoveredConfig> = Vec::new();
+        for (source, path) in instance.into_iter().chain(xdg).chain(home).chain(system) {
+            if !candidates.iter().any(|candidate| candidate.path == path) {
+                candidates.push(DiscoveredConfig { path, source });
+            }
+        }
+        candidates
+    }
+
+    /// Finds the config to edit
+    ///
+    /// # Arguments
+    ///
+    /// * `explicit` - The `--config` path, if given
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DiscoveredConfig)` - `explicit` or [`CONFIG_ENV_VAR`] as
+    ///   given (`~` expanded), or else the first of
+    ///   [`candidates`](Self::candidates) that exists
+    /// * `Err(ConfigError::ConfigNotDiscovered)` - No candidate exists
+    pub fn discover(&self, explicit: Option<&Path>) -> Result<DiscoveredConfig, ConfigError> {
+        if let Some(path) = explicit {
+            return Ok(DiscoveredConfig {
+                path: expand_tilde(path),
+                source: ConfigSource::CommandLine,
+            });
+        }
+        if let Some(path) = &self.environment {
+            return Ok(DiscoveredConfig {
+                path: expand_tilde(path),
+                source: ConfigSource::Environment,
+            });
+        }
+
+        let candidates = self.candidates();
+        match candidates.iter().find(|candidate| candidate.path.exists()) {
+            Some(found) => Ok(found.clone()),
+            None => Err(ConfigError::ConfigNotDiscovered(
+                candidates
+                    .into_iter()
+                    .map(|candidate| candidate.path)
+                    .collect(),
+            )),
+        }
+    }
+}
+
+/// Finds the config to edit from the process environment
+///
+/// Shorthand for [`ConfigDiscovery::from_env`] and
+/// [`discover`](ConfigDiscovery::discover), for callers that only need
+/// the path.
+pub fn discover_config(explicit: Option<&Path>) -> Result<PathBuf, ConfigError> {
+    ConfigDiscovery::from_env()
+        .discover(explicit)
+        .map(|found| found.path)
+}
+
+/// Expands a leading `~`; paths that are not UTF-8 are left as they are
+fn expand_tilde(path: &Path) -> PathBuf {
+    match path.to_str() {
+        Some(text) => PathBuf::from(shellexpand::tilde(text).as_ref()),
+        None => path.to_path_buf(),
+    }
+}
+
+/// The config path a Hyprland instance log says was loaded
+///
+/// Hyprland logs `Using config: <path>` on start; the last such line wins,
+/// since `--config` on a restarted instance may differ.
+fn config_from_instance_log(log: &str) -> Option<PathBuf> {
+    log.lines()
+        .rev()
+        .filter_map(|line| {
+            let start = line.to_ascii_lowercase().find("using config")?;
+            line[start..]
+                .split_whitespace()
+                .map(|word| word.trim_matches(|c: char| matches!(c, '"' | '\'' | ',')))
+                .find(|word| word.starts_with('/'))
+                .map(PathBuf::from)
+        })
+        .next()
+}
@@ -0,0 +1,402 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Full config health report for `doctor`
+//!
+//! Runs every check the tool has over one config and sorts what they find
+//! into sections:
+//!
+//! - **Parsing**: lines the parser skipped or only partly understood
+//! - **Security**: shell metacharacters and other values Layer 1 rejects,
+//!   and risky `exec` commands (Layer 2)
+//! - **Dispatchers**: unknown dispatchers, arguments that do not fit them
+//!   and workspace targets that look like mistakes
+//! - **Conflicts**: key combos bound more than once
+//! - **Programs**: `exec` bindings whose program is not installed
+//!
+//! Every finding has a [`Severity`] and, where there is an obvious fix, a
+//! suggestion. The worst severity decides the exit code of `doctor`
+//! ([`DoctorReport::exit_code`]), so scripts and CI can tell a config
+//! with warnings from one that is broken.
+//!
+//! # Example
+//! ```
+//! use std::path::Path;
+//! use hypr_keybind_manager::config::{
+//!     danger::DangerDetector,
+//!     doctor::{diagnose, Severity},
+//!     resolve::CommandResolver,
+//! };
+//!
+//! let config = "bind = SUPER, Q, killactive\nbind = SUPER, Q, exec, rm -rf /\n";
+//! let report = diagnose(
+//!     config,
+//!     Path::new("hyprland.conf"),
+//!     &DangerDetector::new(),
+//!     &CommandResolver::default(),
+//! );
+//!
+//! assert_eq!(report.worst(), Some(Severity::Critical));
+//! assert_eq!(report.exit_code(), 3);
+//! ```
+
+use std::{fmt, path::Path};
+
+use crate::{
+    config::{
+        danger::{DangerDetector, DangerLevel},
+        health::is_dead_binding,
+        resolve::{find_missing_programs, CommandResolver},
+    },
+    core::{
+        conflict::{ConflictDetector, ConflictSeverity},
+        key_suggestions::KeySuggestionEngine,
+        parser::{parse_config_report, DiagnosticKind},
+        validator::{validate_keybinding, workspace_warnings, ValidationError},
+        Keybinding,
+    },
+};
+
+/// How much attention a finding needs, mildest first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Worth knowing; nothing is wrong
+    Info,
+    /// Works, but probably not as intended
+    Warning,
+    /// Part of the config is ignored or does not work
+    Error,
+    /// A security problem
+    Critical,
+}
+
+impl Severity {
+    /// Short lowercase name, for reports
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Critical => "critical",
+        }
+    }
+
+    /// Exit code of `doctor` when this is the worst finding
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Severity::Info => 0,
+            Severity::Warning => 1,
+            Severity::Error => 2,
+            Severity::Critical => 3,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// One problem found in the config
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    /// How much attention it needs
+    pub severity: Severity,
+    /// What is wrong, naming the binding or line
+    pub message: String,
+    /// Where it is (`file:line`), when known
+    pub location: Option<String>,
+    /// How to fix it, when there is an obvious way
+    pub suggestion: Option<String>,
+}
+
+impl Finding {
+    fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Finding {
+            severity,
+            message: message.into(),
+            location: None,
+            suggestion: None,
+        }
+    }
+
+    fn at(mut self, binding: &Keybinding) -> Self {
+        self.location = binding.location.as_ref().map(ToString::to_string);
+        self
+    }
+
+    fn suggest(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+}
+
+/// The findings of one kind of check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DoctorSection {
+    /// Heading of the section
+    pub title: &'static str,
+    /// Findings in config order
+    pub findings: Vec<Finding>,
+}
+
+/// Everything `doctor` found, by section
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DoctorReport {
+    /// Parsing, Security, Dispatchers, Conflicts and Programs, in that order
+    pub sections: Vec<DoctorSection>,
+    /// Number of bindings that parsed
+    pub bindings: usize,
+}
+
+impl DoctorReport {
+    /// All findings, section by section
+    pub fn findings(&self) -> impl Iterator<Item = &Finding> {
+        self.sections.iter().flat_map(|section| &section.findings)
+    }
+
+    /// The most severe finding's severity (`None`: nothing found)
+    pub fn worst(&self) -> Option<Severity> {
+        self.findings().map(|finding| finding.severity).max()
+    }
+
+    /// Number of findings of a severity
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings()
+            .filter(|finding| finding.severity == severity)
+            .count()
+    }
+
+    /// Exit code for the report: 0 when there is nothing worse than
+    /// information, then 1 for warnings, 2 for errors and 3 for critical
+    /// findings
+    pub fn exit_code(&self) -> i32 {
+        self.worst().map_or(0, Severity::exit_code)
+    }
+}
+
+/// Runs every check over a config
+///
+/// Bind lines that do not parse are reported and the rest of the config
+/// is still checked.
+///
+/// # Arguments
+///
+/// * `content` - The config
+/// * `path` - Where it was read from (for locations and `source` lines)
+/// * `detector` - Danger detector for `exec` commands
+/// * `resolver` - Where programs are looked up
+///
+/// # Returns
+///
+/// The findings, in sections
+pub fn diagnose(
+    content: &str,
+    path: &Path,
+    detector: &DangerDetector,
+    resolver: &CommandResolver,
+) -> DoctorReport {
+    let parsed = parse_config_report(content, path);
+    let bindings = parsed.bindings;
+
+    let parsing = parsed
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let (severity, suggestion) = match diagnostic.kind {
+                DiagnosticKind::Unparseable => (
+                    Severity::Error,
+                    "Write it as `bind = MODS, key, dispatcher, args`",
+                ),
+                DiagnosticKind::UnknownFlag => (
+                    Severity::Error,
+                    "Use the flag letters Hyprland knows (e, l, r, m, d, ...)",
+                ),
+                DiagnosticKind::Source => (Severity::Error, "Fix the path or remove the line"),
+                DiagnosticKind::UnknownModifier => {
+                    (Severity::Warning, "Use SUPER, CTRL, SHIFT or ALT")
+                }
+                DiagnosticKind::UndefinedVariable => (
+                    Severity::Warning,
+                    "Define the variable before its first use",
+                ),
+                DiagnosticKind::MissingKey => (Severity::Warning, "Add the key the binding is for"),
+            };
+            Finding {
+                severity,
+                message: diagnostic.message.clone(),
+                location: Some(match &diagnostic.file {
+                    Some(file) => format!("{}:{}", file.display(), diagnostic.line),
+                    None => format!("line {}", diagnostic.line),
+                }),
+                suggestion: Some(suggestion.to_string()),
+            }
+        })
+        .collect();
+
+    let mut security = Vec::new();
+    let mut dispatchers = Vec::new();
+    for binding in &bindings {
+        match validate_keybinding(binding) {
+            // Often a pipe the user wrote on purpose, but this tool will
+            // not save the binding and the shell runs whatever it holds
+            Err(error @ ValidationError::ShellMetacharacters(_)) => security.push(
+                Finding::new(Severity::Error, format!("{}: {}", binding, error))
+                    .at(binding)
+                    .suggest("Move the command into a script and exec the script"),
+            ),
+            Err(
+                error @ (ValidationError::InvalidDispatcher(_)
+                | ValidationError::InvalidArguments(_)
+                | ValidationError::InvalidKey(_)
+                | ValidationError::MouseBindingKey(_)),
+            ) => dispatchers.push(
+                Finding::new(Severity::Error, format!("{}: {}", binding, error))
+                    .at(binding)
+                    .suggest("Check the dispatcher list in the help window"),
+            ),
+            Err(error) => security
+                .push(Finding::new(Severity::Error, format!("{}: {}", binding, error)).at(binding)),
+            Ok(()) => {}
+        }
+
+        for warning in workspace_warnings(&binding.dispatcher, binding.args.as_deref()) {
+            let mut finding =
+                Finding::new(Severity::Warning, format!("{}: {}", binding, warning)).at(binding);
+            finding.suggestion = warning.suggestion();
+            dispatchers.push(finding);
+        }
+        // Catchalls outside a submap are conflicts of a kind; the rest
+        // can never do anything
+        if is_dead_binding(binding) && !binding.key_combo.is_catchall() {
+            dispatchers.push(
+                Finding::new(
+                    Severity::Warning,
+                    format!("{}: never does anything", binding),
+                )
+                .at(binding)
+                .suggest("Give it a dispatcher (and a command for exec), or remove it"),
+            );
+        }
+
+        // Unlike validation, Layer 2 also assesses what Layer 1 rejected,
+        // as the report should say how bad a command is
+        if matches!(binding.dispatcher.trim(), "exec" | "execr") {
+            let command = binding.args.as_deref().unwrap_or_default();
+            let assessment = detector.assess_command(command);
+            let severity = match assessment.danger_level {
+                DangerLevel::Critical => Severity::Critical,
+                DangerLevel::Dangerous => Severity::Error,
+                DangerLevel::Suspicious => Severity::Warning,
+                DangerLevel::Safe => continue,
+            };
+            security.push(
+                Finding::new(severity, format!("{}: {}", binding, assessment.reason))
+                    .at(binding)
+                    .suggest(assessment.recommendation),
+            );
+        }
+    }
+
+    let mut detector = ConflictDetector::new();
+    for binding in &bindings {
+        detector.add_binding(binding.clone());
+    }
+    let suggestions = KeySuggestionEngine::new(&bindings);
+    let conflicts = detector
+        .find_conflicts()
+        .into_iter()
+        .map(|conflict| {
+            let severity = match conflict.severity {
+                ConflictSeverity::High => Severity::Error,
+                ConflictSeverity::Medium => Severity::Warning,
+                ConflictSeverity::Low => Severity::Info,
+            };
+            let bound: Vec<String> = conflict
+                .conflicting_bindings
+                .iter()
+                .map(|binding| {
+                    format!(
+                        "{} {}",
+                        binding.dispatcher,
+                        binding.args.as_deref().unwrap_or_default()
+                    )
+                    .trim()
+                    .to_string()
+                })
+                .collect();
+            let free: Vec<String> = suggestions
+                .suggest_for_conflict(&conflict, 2)
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            let suggestion = if free.is_empty() {
+                "Remove the extra bindings (`check --fix`)".to_string()
+            } else {
+                format!("Move one of them to {}", free.join(" or "))
+            };
+            Finding {
+                severity,
+                message: format!(
+                    "{} is bound {} times ({}): {}",
+                    conflict.key_combo,
+                    conflict.conflicting_bindings.len(),
+                    bound.join(", "),
+                    conflict.kind.explanation().summary
+                ),
+                location: conflict.conflicting_bindings[0]
+                    .location
+                    .as_ref()
+                    .map(ToString::to_string),
+                suggestion: Some(suggestion),
+            }
+        })
+        .collect();
+
+    let programs = find_missing_programs(&bindings, resolver)
+        .into_iter()
+        .map(|missing| {
+            Finding::new(Severity::Warning, missing.to_string())
+                .at(&missing.binding)
+                .suggest("Install it, or fix the command")
+        })
+        .collect();
+
+    DoctorReport {
+        sections: vec![
+            DoctorSection {
+                title: "Parsing",
+                findings: parsing,
+            },
+            DoctorSection {
+                title: "Security",
+                findings: security,
+            },
+            DoctorSection {
+                title: "Dispatchers",
+                findings: dispatchers,
+            },
+            DoctorSection {
+                title: "Conflicts",
+                findings: conflicts,
+            },
+            DoctorSection {
+                title: "Programs",
+                findings: programs,
+            },
+        ],
+        bindings: bindings.len(),
+    }
+}
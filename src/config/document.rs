@@ -0,0 +1,427 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lossless model of a config file
+//!
+//! A [`ConfigDocument`] holds every line of a config in order, each
+//! classified the way the parser reads it: blank lines, comments, variable
+//! definitions, `source` lines, submap markers, `unbind` lines, bind lines
+//! (with the parsed binding), bind lines that do not parse, `exec-once`,
+//! `env` and rule directives, and everything else (settings and sections).
+//! Rendering a document gives back the exact text it was parsed from, byte
+//! for byte.
+//!
+//! Keybinding edits are applied to the document rather than to raw text:
+//! [`ConfigDocument::set_bindings`] aligns the bind lines with a new binding
+//! list (see [`writer`](crate::config::writer)), and
+//! [`ConfigDocument::replace_bind_block`] swaps all bind lines for a
+//! pre-rendered block. Lines that are not bind lines are never changed, and
+//! neither are bind lines the parser cannot read.
+//!
+//! Directive lines that run together form protected blocks
+//! ([`ConfigDocument::protected_blocks`]). Bindings are never written into
+//! one: a new binding whose neighbour sits inside a block goes below it.
+//! Bind lines inside `device` sections belong to that input device
+//! ([`ConfigDocument::device_blocks`]).
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::document::ConfigDocument;
+//!
+//! let config = "$mainMod = SUPER\n\n# Apps\nbind = $mainMod, K, exec, kitty\n";
+//! let mut document = ConfigDocument::parse(config);
+//! assert_eq!(document.to_string(), config);
+//!
+//! let mut bindings = document.bindings();
+//! bindings[0].args = Some("foot".to_string());
+//! document.set_bindings(&bindings);
+//! assert_eq!(
+//!     document.to_string(),
+//!     "$mainMod = SUPER\n\n# Apps\nbind = $mainMod, K, exec, foot\n"
+//! );
+//! ```
+
+use std::{fmt, ops::Range};
+
+use crate::{
+    config::{writer, ConfigManager},
+    core::{
+        parser::{
+            collect_variables, device_sections, is_bind_line, parse_bind_line,
+            parse_category_annotation, parse_source_line, parse_submap_line, parse_unbind_line,
+            substitute_variables, VariableTable,
+        },
+        types::{Keybinding, Unbind},
+    },
+};
+
+/// What a line of a config is
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LineKind {
+    /// Empty or whitespace only
+    Blank,
+    /// `# ...`
+    Comment,
+    /// `$name = value`
+    Variable,
+    /// `source = path`
+    Source,
+    /// `submap = name` (`Some`) or `submap = reset` (`None`)
+    Submap(Option<String>),
+    /// `unbind = MODS, KEY`
+    Unbind(Unbind),
+    /// A bind line and its binding (with the submap it is in)
+    Bind(Box<Keybinding>),
+    /// A bind line the parser cannot read
+    BrokenBind,
+    /// `exec-once`, `env`, window rules and the like
+    Directive(Directive),
+    /// Anything else: settings, sections, `monitor`, ...
+    Other,
+}
+
+/// Keywords whose lines are protected from interleaved bindings
+///
+/// Startup commands, environment variables and rules are usually kept
+/// together in blocks of their own, and the order within a block can
+/// matter (`env` lines are read before `exec-once` runs).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Directive {
+    /// `exec-once`, `execr-once`: run once at startup
+    ExecOnce,
+    /// `exec`, `execr`: run on every reload
+    Exec,
+    /// `exec-shutdown`: run before Hyprland exits
+    ExecShutdown,
+    /// `env`, `envd`: environment variables
+    Env,
+    /// `windowrule`, `windowrulev2`
+    WindowRule,
+    /// `layerrule`
+    LayerRule,
+}
+
+impl Directive {
+    /// Classifies a line by its keyword
+    ///
+    /// # Arguments
+    /// * `line` - Trimmed config line
+    ///
+    /// # Returns
+    /// The directive, or `None` if the line has another keyword
+    pub fn from_line(line: &str) -> Option<Self> {
+        let (keyword, _) = line.split_once('=')?;
+        match keyword.trim() {
+            "exec-once" | "execr-once" => Some(Self::ExecOnce),
+            "exec" | "execr" => Some(Self::Exec),
+            "exec-shutdown" => Some(Self::ExecShutdown),
+            "env" | "envd" => Some(Self::Env),
+            "windowrule" | "windowrulev2" => Some(Self::WindowRule),
+            "layerrule" => Some(Self::LayerRule),
+            _ => None,
+        }
+    }
+}
+
+/// One line of a config: its exact text and what it is
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocumentLine {
+    /// The line as written, without the line break
+    pub text: String,
+    /// How the parser reads the line
+    pub kind: LineKind,
+}
+
+/// Every line of a config, classified, in order
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigDocument {
+    lines: Vec<DocumentLine>,
+    /// Whether the text ends with a line break
+    trailing_newline: bool,
+}
+
+impl ConfigDocument {
+    /// Parses config content into a document
+    ///
+    /// Never fails: a line the parser cannot read is kept as
+    /// [`LineKind::BrokenBind`] or [`LineKind::Other`].
+    pub fn parse(content: &str) -> Self {
+        let variables = collect_variables(content);
+        let devices = device_sections(content);
+        let mut current_submap: Option<String> = None;
+        let mut annotation: Option<String> = None;
+
+        // Split on '\n' only, so a '\r' before it stays part of the line
+        let lines = content
+            .split_inclusive('\n')
+            .map(|text| text.strip_suffix('\n').unwrap_or(text))
+            .enumerate()
+            .map(|(index, text)| {
+                let trimmed = text.trim();
+                let substituted = substitute_variables(trimmed, &variables);
+                let category = annotation.take();
+
+                let kind = if trimmed.is_empty() {
+                    LineKind::Blank
+                } else if trimmed.starts_with('#') {
+                    annotation = parse_category_annotation(trimmed);
+                    LineKind::Comment
+                } else if let Some(submap) = parse_submap_line(&substituted) {
+                    current_submap = submap.clone();
+                    LineKind::Submap(submap)
+                } else if parse_source_line(&substituted).is_some() {
+                    LineKind::Source
+                } else if let Some(unbind) = parse_unbind_line(&substituted) {
+                    LineKind::Unbind(unbind)
+                } else if is_bind_line(trimmed) {
+                    match parse_bind_line(&substituted) {
+                        Ok((_, binding)) => LineKind::Bind(Box::new(Keybinding {
+                            submap: current_submap.clone(),
+                            category,
+                            device: devices.get(index).cloned().flatten(),
+                            ..binding
+                        })),
+                        Err(_) => LineKind::BrokenBind,
+                    }
+                } else if trimmed.starts_with('$') {
+                    LineKind::Variable
+                } else if let Some(directive) = Directive::from_line(trimmed) {
+                    LineKind::Directive(directive)
+                } else {
+                    LineKind::Other
+                };
+
+                DocumentLine {
+                    text: text.to_string(),
+                    kind,
+                }
+            })
+            .collect();
+
+        Self {
+            lines,
+            trailing_newline: content.ends_with('\n'),
+        }
+    }
+
+    /// The lines of the document, in order
+    pub fn lines(&self) -> &[DocumentLine] {
+        &self.lines
+    }
+
+    /// Whether the last line ends with a line break
+    pub fn ends_with_newline(&self) -> bool {
+        self.trailing_newline
+    }
+
+    /// The bindings the document declares, as the parser returns them
+    ///
+    /// Bind lines cancelled by a later `unbind` line are left out. Bindings
+    /// carry no location, as the document does not know its file.
+    pub fn bindings(&self) -> Vec<Keybinding> {
+        let mut bindings: Vec<Keybinding> = Vec::new();
+        for line in &self.lines {
+            match &line.kind {
+                LineKind::Unbind(unbind) => bindings.retain(|binding| !unbind.cancels(binding)),
+                LineKind::Bind(binding) => bindings.push((**binding).clone()),
+                _ => {}
+            }
+        }
+        bindings
+    }
+
+    /// Classifies the document into protected blocks
+    ///
+    /// A block runs from a directive line to the last directive line after
+    /// it with no blank line in between. Whatever else sits inside, such as
+    /// comments or bind lines a user put there, belongs to the block. Every
+    /// other line is free to have bindings written next to it.
+    ///
+    /// # Returns
+    /// Line index ranges of the blocks, in order
+    pub fn protected_blocks(&self) -> Vec<Range<usize>> {
+        let mut blocks = Vec::new();
+        let mut current: Option<Range<usize>> = None;
+
+        for (index, line) in self.lines.iter().enumerate() {
+            match line.kind {
+                LineKind::Directive(_) => {
+                    let start = current.as_ref().map_or(index, |block| block.start);
+                    current = Some(start..index + 1);
+                }
+                LineKind::Blank => blocks.extend(current.take()),
+                _ => {}
+            }
+        }
+        blocks.extend(current);
+
+        blocks
+    }
+
+    /// Finds the input device sections that have a name
+    ///
+    /// Bind lines inside one belong to that device
+    /// ([`Keybinding::device`]); new bindings only go into the section of
+    /// their own device.
+    ///
+    /// # Returns
+    /// Line index ranges of the sections (from the `device {` line to its
+    /// closing brace) with the device name, in order
+    pub fn device_blocks(&self) -> Vec<(Range<usize>, String)> {
+        let mut blocks: Vec<(Range<usize>, String)> = Vec::new();
+        for (index, device) in device_sections(&self.to_string()).into_iter().enumerate() {
+            let Some(device) = device else { continue };
+            match blocks.last_mut() {
+                Some((range, name)) if range.end == index && *name == device => {
+                    range.end = index + 1
+                }
+                _ => blocks.push((index..index + 1, device)),
+            }
+        }
+        blocks
+    }
+
+    /// The variables the document defines
+    pub fn variables(&self) -> VariableTable {
+        VariableTable::from_config(&self.to_string())
+    }
+
+    /// Makes the bind lines hold exactly `bindings`, in order
+    ///
+    /// Unchanged bind lines stay as they are, changed ones are rewritten in
+    /// place and new ones are inserted next to their neighbours (see
+    /// [`writer::rewrite_bind_lines`]). A document without bind lines gets
+    /// the bindings appended under a `# Keybindings` header.
+    pub fn set_bindings(&mut self, bindings: &[Keybinding]) {
+        let content = writer::rewrite_document(self, bindings).unwrap_or_else(|| {
+            let block = ConfigManager::render_bindings(bindings, &self.variables());
+            self.append_block(&block)
+        });
+        *self = Self::parse(&content);
+    }
+
+    /// Replaces all bind lines and submap markers with a rendered block
+    ///
+    /// Category annotations of the bind lines go with them; the block
+    /// brings its own. Lines inside device sections stay where they are.
+    ///
+    /// The block goes where the first run of bind lines ends (below the
+    /// protected block that run is in, if any), or is appended under a
+    /// `# Keybindings` header if there are none. Bind lines the parser
+    /// cannot read are kept where they are.
+    pub fn replace_bind_block(&mut self, block: &str) {
+        let device_blocks = self.device_blocks();
+        let is_binding: Vec<bool> = (0..self.lines.len())
+            .map(|index| {
+                (matches!(
+                    self.lines[index].kind,
+                    LineKind::Bind(_) | LineKind::Submap(_)
+                ) || self.is_annotation(index))
+                    && !device_blocks
+                        .iter()
+                        .any(|(range, _)| range.contains(&index))
+            })
+            .collect();
+
+        let Some(first) = is_binding.iter().position(|&binding| binding) else {
+            let content = self.append_block(block);
+            *self = Self::parse(&content);
+            return;
+        };
+        let mut end = is_binding[first..]
+            .iter()
+            .position(|&binding| !binding)
+            .map_or(self.lines.len(), |offset| first + offset);
+        if let Some(block) = self
+            .protected_blocks()
+            .into_iter()
+            .find(|block| block.start < end && end < block.end)
+        {
+            end = block.end;
+        }
+
+        let mut content = String::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            if index == end {
+                content.push_str(block);
+            }
+            if !is_binding[index] {
+                content.push_str(&line.text);
+                content.push('\n');
+            }
+        }
+        if end == self.lines.len() {
+            content.push_str(block);
+        }
+        *self = Self::parse(&content);
+    }
+
+    /// Returns whether a line is a category annotation of a bind line
+    ///
+    /// Annotations stacked right above a bind line all belong to it (the
+    /// last one sets the category), see [`Self::annotation_lines`].
+    pub fn is_annotation(&self, index: usize) -> bool {
+        let below = self.lines[index..]
+            .iter()
+            .position(|line| !is_annotation_line(line))
+            .map(|offset| index + offset);
+        below.is_some_and(|below| {
+            below > index && matches!(self.lines[below].kind, LineKind::Bind(_))
+        })
+    }
+
+    /// The category annotation lines right above a line
+    ///
+    /// # Returns
+    /// The index range of the annotations (empty if there are none)
+    pub fn annotation_lines(&self, index: usize) -> Range<usize> {
+        let start = self.lines[..index]
+            .iter()
+            .rposition(|line| !is_annotation_line(line))
+            .map_or(0, |above| above + 1);
+        start..index
+    }
+
+    /// The document's text with `block` appended under a header
+    fn append_block(&self, block: &str) -> String {
+        let mut content = self.to_string();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str("\n# Keybindings\n");
+        content.push_str(block);
+        content
+    }
+}
+
+/// Returns whether a line is a `# @category:` annotation
+fn is_annotation_line(line: &DocumentLine) -> bool {
+    line.kind == LineKind::Comment && parse_category_annotation(&line.text).is_some()
+}
+
+impl fmt::Display for ConfigDocument {
+    /// Renders the document as the exact text it was parsed from
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, line) in self.lines.iter().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+            f.write_str(&line.text)?;
+        }
+        if self.trailing_newline && !self.lines.is_empty() {
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
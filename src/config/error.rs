@@ -23,6 +23,12 @@ pub enum ConfigError {
     /// Configuration file does not exist.
     #[error("Config file not found: {0}")]
     NotFound(PathBuf),
+    /// No config was given and none of the usual locations has one.
+    #[error(
+        "No Hyprland config found; pass --config or set HYPRLAND_CONFIG. Tried:{}",
+        list_paths(.0)
+    )]
+    ConfigNotDiscovered(Vec<PathBuf>),
     /// Backup directory cannot be created or written to.
     #[error("Backup directory not writable: {0}")]
     BackupDirNotWritable(PathBuf),
@@ -35,6 +41,15 @@ pub enum ConfigError {
     /// Failed to create backup file.
     #[error("Failed to create backup: {0}")]
     BackupFailed(String),
+    /// Bindings cannot be encoded in the export format.
+    #[error("Export failed: {0}")]
+    ExportFailed(String),
+    /// An import file is not in the expected format.
+    #[error("Import failed: {0}")]
+    ImportFailed(String),
+    /// A backup's content does not match the checksum recorded with it.
+    #[error("Backup is damaged (checksum mismatch): {0}")]
+    ChecksumMismatch(PathBuf),
     /// Atomic write operation failed.
     #[error("Atomic write failed: {0}")]
     WriteFailed(String),
@@ -59,4 +74,31 @@ pub enum ConfigError {
     /// Failed to write file to path
     #[error("Failed to write to path: {0}")]
     WriteError(PathBuf),
+    /// A pre- or post-write hook failed
+    #[error("Hook failed: {0}")]
+    HookFailed(String),
+    /// The bindings cannot be moved into a file of their own
+    #[error("Cannot split config: {0}")]
+    SplitFailed(String),
+    /// A profile name is empty or contains characters other than letters,
+    /// digits, `-` and `_`
+    #[error("Invalid profile name: {0:?}")]
+    InvalidProfileName(String),
+    /// No profile of that name has been saved
+    #[error("Profile not found: {0}")]
+    ProfileNotFound(String),
+    /// A desktop notification could not be shown
+    #[error("Notification failed: {0}")]
+    NotificationFailed(String),
+    /// The settings file is not valid TOML
+    #[error("Invalid settings file: {0}")]
+    InvalidSettings(String),
+}
+
+/// One indented line per path, for error messages
+fn list_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("\n  {}", path.display()))
+        .collect()
 }
@@ -0,0 +1,261 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export formats
+//!
+//! Bindings are exported as Hyprland config lines, to be sourced or
+//! imported again, as JSON, YAML or TOML for scripts and other tools, or
+//! as a best-effort sway or i3 config (see [`crate::config::interop`]).
+//! The structured formats hold one [`ExportDocument`]: a `bindings` list
+//! with every field of each [`Keybinding`], in the same shape as the input
+//! of `query`.
+//!
+//! Importing reads the same files back (see [`ExportFormat::read`]). An
+//! entry of a structured file that is not a valid binding is reported on
+//! its own, and the rest of the file is still imported.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::export::ExportFormat;
+//! use std::path::Path;
+//!
+//! assert_eq!(ExportFormat::from_path(Path::new("binds.yml")), ExportFormat::Yaml);
+//! assert_eq!(ExportFormat::from_path(Path::new("binds.conf")), ExportFormat::Hyprland);
+//! assert_eq!("toml".parse::<ExportFormat>(), Ok(ExportFormat::Toml));
+//! ```
+
+use std::{fmt, path::Path, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{interop::ForeignConfig, ConfigError, ConfigManager},
+    core::{
+        parser::{parse_config_file, VariableTable},
+        Keybinding,
+    },
+};
+
+/// File format of an export
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Hyprland config lines (`bind = ...`)
+    #[default]
+    Hyprland,
+    /// An [`ExportDocument`] as pretty-printed JSON
+    Json,
+    /// An [`ExportDocument`] as YAML
+    Yaml,
+    /// An [`ExportDocument`] as TOML
+    Toml,
+    /// `bindsym` lines for sway; bindings without an equivalent are
+    /// commented out
+    Sway,
+    /// `bindsym` lines for i3; bindings without an equivalent are
+    /// commented out
+    I3,
+}
+
+impl ExportFormat {
+    /// Every format, Hyprland first
+    pub const ALL: [ExportFormat; 6] = [
+        ExportFormat::Hyprland,
+        ExportFormat::Json,
+        ExportFormat::Yaml,
+        ExportFormat::Toml,
+        ExportFormat::Sway,
+        ExportFormat::I3,
+    ];
+
+    /// Usual file extension of the format
+    ///
+    /// sway and i3 read `config`, which has none; files they include are
+    /// usually `.conf`.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Hyprland | ExportFormat::Sway | ExportFormat::I3 => "conf",
+            ExportFormat::Json => "json",
+            ExportFormat::Yaml => "yaml",
+            ExportFormat::Toml => "toml",
+        }
+    }
+
+    /// The window manager of a sway or i3 export (`None` for the others)
+    pub fn foreign(self) -> Option<ForeignConfig> {
+        match self {
+            ExportFormat::Sway => Some(ForeignConfig::Sway),
+            ExportFormat::I3 => Some(ForeignConfig::I3),
+            _ => None,
+        }
+    }
+
+    /// Picks the format from a file's extension
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - File to export to
+    ///
+    /// # Returns
+    ///
+    /// The format with that extension (`.yml` is YAML too), or Hyprland
+    /// for any other file (sway and i3 are only used when asked for)
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("json") => ExportFormat::Json,
+            Some("yaml" | "yml") => ExportFormat::Yaml,
+            Some("toml") => ExportFormat::Toml,
+            _ => ExportFormat::Hyprland,
+        }
+    }
+
+    /// Renders bindings in the format
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - Bindings to export
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - File content
+    /// * `Err(ConfigError::ExportFailed)` - The bindings cannot be encoded
+    pub fn render(self, bindings: &[Keybinding]) -> Result<String, ConfigError> {
+        let document = ExportDocument {
+            bindings: bindings.to_vec(),
+        };
+        let failed = |e: &dyn fmt::Display| ConfigError::ExportFailed(format!("{self}: {e}"));
+
+        match self {
+            ExportFormat::Hyprland => {
+                let mut content = String::from("# Exported Hyprland Keybindings\n\n");
+                // Exported files define no variables, so values stay expanded
+                content.push_str(&ConfigManager::render_bindings(
+                    bindings,
+                    &VariableTable::default(),
+                ));
+                Ok(content)
+            }
+            ExportFormat::Json => serde_json::to_string_pretty(&document)
+                .map(|json| json + "\n")
+                .map_err(|e| failed(&e)),
+            ExportFormat::Yaml => serde_yaml::to_string(&document).map_err(|e| failed(&e)),
+            ExportFormat::Toml => toml::to_string_pretty(&document).map_err(|e| failed(&e)),
+            ExportFormat::Sway => Ok(ForeignConfig::Sway.export(bindings).content),
+            ExportFormat::I3 => Ok(ForeignConfig::I3.export(bindings).content),
+        }
+    }
+
+    /// Reads bindings from a file in the format
+    ///
+    /// Each entry of a JSON, YAML or TOML `bindings` list is read on its
+    /// own: one that is not a valid binding is reported in
+    /// [`ImportedBindings::errors`] instead of failing the whole file.
+    /// Imported bindings join the main config, so the file and line they
+    /// were exported from are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - File content
+    /// * `path` - File the content was read from (for error messages)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImportedBindings)` - The bindings read, and why the other
+    ///   entries were skipped
+    /// * `Err(ConfigError::ImportFailed)` - The file is not in the format,
+    ///   has no `bindings` list, or a bind line does not parse
+    pub fn read(self, content: &str, path: &Path) -> Result<ImportedBindings, ConfigError> {
+        let failed = |e: &dyn fmt::Display| ConfigError::ImportFailed(format!("{self}: {e}"));
+
+        let document: serde_json::Value = match self {
+            ExportFormat::Hyprland => {
+                let bindings = parse_config_file(content, path).map_err(|e| failed(&e))?;
+                return Ok(ImportedBindings {
+                    bindings,
+                    errors: Vec::new(),
+                });
+            }
+            ExportFormat::Json => serde_json::from_str(content).map_err(|e| failed(&e))?,
+            ExportFormat::Yaml => serde_yaml::from_str(content).map_err(|e| failed(&e))?,
+            ExportFormat::Toml => toml::from_str(content).map_err(|e| failed(&e))?,
+            ExportFormat::Sway => return Ok(ForeignConfig::Sway.translate(content)),
+            ExportFormat::I3 => return Ok(ForeignConfig::I3.translate(content)),
+        };
+
+        let entries = match document.get("bindings") {
+            Some(serde_json::Value::Array(entries)) => entries,
+            Some(_) => return Err(failed(&"`bindings` is not a list")),
+            None => return Err(failed(&"no `bindings` list")),
+        };
+
+        let mut imported = ImportedBindings::default();
+        for (index, entry) in entries.iter().enumerate() {
+            match serde_json::from_value::<Keybinding>(entry.clone()) {
+                Ok(mut binding) => {
+                    binding.source_file = None;
+                    binding.location = None;
+                    imported.bindings.push(binding);
+                }
+                Err(e) => imported
+                    .errors
+                    .push(format!("Binding {}: {}", index + 1, e)),
+            }
+        }
+        Ok(imported)
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExportFormat::Hyprland => "hyprland",
+            ExportFormat::Json => "json",
+            ExportFormat::Yaml => "yaml",
+            ExportFormat::Toml => "toml",
+            ExportFormat::Sway => "sway",
+            ExportFormat::I3 => "i3",
+        })
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ExportFormat::ALL
+            .into_iter()
+            .find(|format| format.to_string() == s)
+            .ok_or_else(|| format!("Unknown export format: {s}"))
+    }
+}
+
+/// Content of a JSON, YAML or TOML export
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExportDocument {
+    /// The exported bindings, in config order
+    pub bindings: Vec<Keybinding>,
+}
+
+/// Result of [`ExportFormat::read`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportedBindings {
+    /// Entries that are valid bindings, in file order
+    pub bindings: Vec<Keybinding>,
+    /// Why each other entry was skipped (`Binding 3: missing field ...`)
+    pub errors: Vec<String>,
+}
@@ -0,0 +1,239 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conflict regression fixtures
+//!
+//! Turns the conflicts detected in a config into minimal config snippets,
+//! one per conflict, so a user's bug report can become a permanent
+//! regression test for the conflict detector. Each fixture holds only the
+//! clashing bind lines (variables already expanded, inside their `submap`
+//! section if any) and a header stating what the detector must find:
+//!
+//! ```text
+//! # Conflict regression fixture
+//! # expect: multiple-actions SUPER+K 2
+//! bind = SUPER, K, exec, firefox
+//! bind = SUPER, K, exec, kitty
+//! ```
+//!
+//! The expectation reads: conflict kind, key combo, number of bindings.
+//! Fixtures live in `tests/fixtures/conflicts/` and are all checked by the
+//! test suite. Arguments are copied verbatim, so review generated fixtures
+//! for personal paths before committing them.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{format_bind_line, ConfigError},
+    core::{parser::parse_config_file, Conflict, ConflictDetector, SUBMAP_RESET},
+};
+
+const FIXTURE_HEADER: &str = "# Conflict regression fixture";
+const EXPECT_PREFIX: &str = "# expect:";
+
+/// Default fixture directory, relative to the repository root
+pub const FIXTURE_DIR: &str = "tests/fixtures/conflicts";
+
+/// A minimal config reproducing one conflict
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConflictFixture {
+    /// File name without extension, e.g. `multiple-actions-super-k`
+    pub name: String,
+    /// Config snippet including the expectation header
+    pub content: String,
+}
+
+/// What the detector must find in a fixture
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FixtureExpectation {
+    /// Conflict kind identifier (see `ConflictKind::explanation_id`)
+    pub kind: String,
+    /// Key combo as displayed, e.g. `SUPER+K`
+    pub key_combo: String,
+    /// Number of bindings in the conflict
+    pub bindings: usize,
+}
+
+impl ConflictFixture {
+    /// Builds the fixture for one conflict
+    pub fn from_conflict(conflict: &Conflict) -> Self {
+        let kind = conflict.kind.explanation_id();
+        let submap = conflict.submap.as_deref();
+
+        let mut content = format!(
+            "{}\n{} {} {} {}\n",
+            FIXTURE_HEADER,
+            EXPECT_PREFIX,
+            kind,
+            conflict.key_combo,
+            conflict.conflicting_bindings.len()
+        );
+        // Each binding goes in its own submap: a catchall shadowing a
+        // universal binding spans two
+        let mut current: Option<&str> = None;
+        for binding in &conflict.conflicting_bindings {
+            let wanted = binding.submap.as_deref();
+            if wanted != current {
+                content.push_str(&format!("submap = {}\n", wanted.unwrap_or(SUBMAP_RESET)));
+                current = wanted;
+            }
+            content.push_str(&format_bind_line(binding));
+            content.push('\n');
+        }
+        if current.is_some() {
+            content.push_str(&format!("submap = {}\n", SUBMAP_RESET));
+        }
+
+        let mut name = format!("{}-{}", kind, conflict.key_combo);
+        if let Some(submap) = submap {
+            name.push_str(&format!("-in-{}", submap));
+        }
+
+        Self {
+            name: slug(&name),
+            content,
+        }
+    }
+}
+
+/// Builds one fixture per conflict, sorted by name
+pub fn fixtures_from_conflicts(conflicts: &[Conflict]) -> Vec<ConflictFixture> {
+    let mut fixtures: Vec<ConflictFixture> = conflicts
+        .iter()
+        .map(ConflictFixture::from_conflict)
+        .collect();
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    fixtures
+}
+
+/// Writes fixtures into a directory as `<name>.conf`
+///
+/// A fixture identical to an existing file is skipped. When a different
+/// fixture already has the name, `-2`, `-3`, ... is appended.
+///
+/// # Returns
+///
+/// Paths of the files that were written
+///
+/// # Errors
+///
+/// Returns `ConfigError::Io` if the directory or a file cannot be written.
+pub fn write_fixtures(
+    dir: &Path,
+    fixtures: &[ConflictFixture],
+) -> Result<Vec<PathBuf>, ConfigError> {
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+
+    for fixture in fixtures {
+        let mut suffix = 1;
+        loop {
+            let file_name = match suffix {
+                1 => format!("{}.conf", fixture.name),
+                n => format!("{}-{}.conf", fixture.name, n),
+            };
+            let path = dir.join(file_name);
+
+            match fs::read_to_string(&path) {
+                Ok(existing) if existing == fixture.content => break,
+                Ok(_) => suffix += 1,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    fs::write(&path, &fixture.content)?;
+                    written.push(path);
+                    break;
+                }
+                Err(e) => return Err(ConfigError::Io(e)),
+            }
+        }
+    }
+
+    Ok(written)
+}
+
+/// Reads the expectation header of a fixture
+///
+/// # Returns
+///
+/// * `Some(FixtureExpectation)` - The fixture has a well-formed `# expect:` line
+/// * `None` - No expectation, or it is malformed
+pub fn parse_expectation(content: &str) -> Option<FixtureExpectation> {
+    let line = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(EXPECT_PREFIX))?;
+
+    let mut fields = line.split_whitespace();
+    let expectation = FixtureExpectation {
+        kind: fields.next()?.to_string(),
+        key_combo: fields.next()?.to_string(),
+        bindings: fields.next()?.parse().ok()?,
+    };
+
+    fields.next().is_none().then_some(expectation)
+}
+
+/// Checks that the detector finds exactly the expected conflict
+///
+/// # Errors
+///
+/// Returns a description of the mismatch.
+pub fn check_fixture(content: &str) -> Result<(), String> {
+    let expected = parse_expectation(content).ok_or("missing or malformed '# expect:' line")?;
+
+    let bindings = parse_config_file(content, Path::new("fixture.conf"))
+        .map_err(|e| format!("fixture does not parse: {}", e))?;
+    let mut detector = ConflictDetector::new();
+    for binding in bindings {
+        detector.add_binding(binding);
+    }
+
+    let conflicts = detector.find_conflicts();
+    let [conflict] = conflicts.as_slice() else {
+        return Err(format!("expected 1 conflict, found {}", conflicts.len()));
+    };
+
+    let found = FixtureExpectation {
+        kind: conflict.kind.explanation_id().to_string(),
+        key_combo: conflict.key_combo.to_string(),
+        bindings: conflict.conflicting_bindings.len(),
+    };
+    if found != expected {
+        return Err(format!(
+            "expected {} {} {}, found {} {} {}",
+            expected.kind,
+            expected.key_combo,
+            expected.bindings,
+            found.kind,
+            found.key_combo,
+            found.bindings
+        ));
+    }
+
+    Ok(())
+}
+
+/// Lower-cases a name and keeps only `[a-z0-9-]`
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
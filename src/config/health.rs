@@ -0,0 +1,292 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Config health score
+//!
+//! Condenses the state of a config into a single 0–100 score so users can
+//! see at a glance whether it needs attention, and watch it improve as they
+//! fix things. The score starts at 100 and each category deducts points,
+//! capped per category so one kind of problem cannot hide the others:
+//!
+//! | Category             | Per item                | Cap |
+//! |----------------------|-------------------------|-----|
+//! | Conflicts            | 5                       | 25  |
+//! | Dangerous commands   | 10 (3 if suspicious)    | 30  |
+//! | Missing executables  | 5                       | 20  |
+//! | Dead bindings        | 5                       | 15  |
+//! | Description coverage | up to 10, by coverage   | 10  |
+//!
+//! A binding counts as described when a comment sits on the line above it
+//! or at the end of its line, or when it is a `bindd` binding with a
+//! description of its own.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::{danger::DangerDetector, health::assess_health};
+//!
+//! let report = assess_health("", &[], &[], &DangerDetector::new(), |_| true);
+//! assert_eq!(report.score, 100);
+//! ```
+
+use std::{env, os::unix::fs::PermissionsExt, path::Path};
+
+use crate::config::danger::{DangerDetector, DangerLevel};
+use crate::core::{parser::is_bind_line, BindFlag, BindType, Conflict, Keybinding};
+
+const CONFLICT_PENALTY: u32 = 5;
+const CONFLICT_CAP: u32 = 25;
+const DANGEROUS_PENALTY: u32 = 10;
+const SUSPICIOUS_PENALTY: u32 = 3;
+const DANGER_CAP: u32 = 30;
+const MISSING_EXECUTABLE_PENALTY: u32 = 5;
+const MISSING_EXECUTABLE_CAP: u32 = 20;
+const DEAD_BINDING_PENALTY: u32 = 5;
+const DEAD_BINDING_CAP: u32 = 15;
+const DESCRIPTION_CAP: u32 = 10;
+
+/// Health of a config and what it is made of
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthReport {
+    /// Overall score, 0 (needs work) to 100 (nothing to fix)
+    pub score: u8,
+    /// Number of key combinations with more than one binding
+    pub conflicts: usize,
+    /// Bindings assessed as Dangerous or Critical
+    pub dangerous: usize,
+    /// Bindings assessed as Suspicious
+    pub suspicious: usize,
+    /// Programs run by `exec` bindings that could not be found
+    pub missing_executables: Vec<String>,
+    /// Bindings that can never do anything (see [`is_dead_binding`])
+    pub dead_bindings: usize,
+    /// Bind lines with a comment above or beside them
+    pub described: usize,
+    /// Bind lines in the config
+    pub bind_lines: usize,
+}
+
+/// One scored category of a [`HealthReport`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthComponent {
+    /// Category name
+    pub label: &'static str,
+    /// What was found, e.g. "2 conflicts"
+    pub detail: String,
+    /// Points deducted from the score
+    pub penalty: u8,
+    /// Most points this category can deduct
+    pub max_penalty: u8,
+}
+
+impl HealthReport {
+    /// Fraction of bind lines that are described (1.0 when there are none)
+    pub fn description_coverage(&self) -> f64 {
+        if self.bind_lines == 0 {
+            1.0
+        } else {
+            self.described as f64 / self.bind_lines as f64
+        }
+    }
+
+    /// Breaks the score down by category
+    pub fn components(&self) -> Vec<HealthComponent> {
+        let danger_points =
+            self.dangerous as u32 * DANGEROUS_PENALTY + self.suspicious as u32 * SUSPICIOUS_PENALTY;
+        let undescribed = 1.0 - self.description_coverage();
+
+        vec![
+            component(
+                "Conflicts",
+                plural(self.conflicts, "conflict"),
+                self.conflicts as u32 * CONFLICT_PENALTY,
+                CONFLICT_CAP,
+            ),
+            component(
+                "Dangers",
+                format!(
+                    "{} dangerous, {} suspicious",
+                    self.dangerous, self.suspicious
+                ),
+                danger_points,
+                DANGER_CAP,
+            ),
+            component(
+                "Missing executables",
+                plural(self.missing_executables.len(), "missing program"),
+                self.missing_executables.len() as u32 * MISSING_EXECUTABLE_PENALTY,
+                MISSING_EXECUTABLE_CAP,
+            ),
+            component(
+                "Dead bindings",
+                plural(self.dead_bindings, "dead binding"),
+                self.dead_bindings as u32 * DEAD_BINDING_PENALTY,
+                DEAD_BINDING_CAP,
+            ),
+            component(
+                "Descriptions",
+                format!("{:.0}% described", self.description_coverage() * 100.0),
+                (undescribed * DESCRIPTION_CAP as f64).round() as u32,
+                DESCRIPTION_CAP,
+            ),
+        ]
+    }
+}
+
+/// Computes the health of a config
+///
+/// # Arguments
+///
+/// * `content` - Raw config text (used for description coverage)
+/// * `bindings` - Parsed keybindings
+/// * `conflicts` - Conflicts found among `bindings`
+/// * `detector` - Danger detector for `exec` commands
+/// * `executable_exists` - Whether a program can be run; usually
+///   [`executable_on_path`], replaced in tests
+pub fn assess_health(
+    content: &str,
+    bindings: &[Keybinding],
+    conflicts: &[Conflict],
+    detector: &DangerDetector,
+    executable_exists: impl Fn(&str) -> bool,
+) -> HealthReport {
+    let mut report = HealthReport {
+        score: 0,
+        conflicts: conflicts.len(),
+        dangerous: 0,
+        suspicious: 0,
+        missing_executables: Vec::new(),
+        dead_bindings: 0,
+        described: 0,
+        bind_lines: 0,
+    };
+
+    for binding in bindings {
+        let is_exec = matches!(binding.dispatcher.trim(), "exec" | "execr");
+        let command = binding.args.as_deref().map(str::trim).unwrap_or_default();
+
+        if is_dead_binding(binding) {
+            report.dead_bindings += 1;
+            continue;
+        }
+        if !is_exec {
+            continue;
+        }
+
+        match detector.assess_command(command).danger_level {
+            DangerLevel::Dangerous | DangerLevel::Critical => report.dangerous += 1,
+            DangerLevel::Suspicious => report.suspicious += 1,
+            DangerLevel::Safe => {}
+        }
+
+        if let Some(program) = exec_program(command) {
+            if !executable_exists(program)
+                && !report.missing_executables.iter().any(|p| p == program)
+            {
+                report.missing_executables.push(program.to_string());
+            }
+        }
+    }
+
+    let mut previous_is_comment = false;
+    for line in content.lines().map(str::trim) {
+        if is_bind_line(line) {
+            report.bind_lines += 1;
+            if previous_is_comment || line.contains(" #") || has_description(line) {
+                report.described += 1;
+            }
+        }
+        previous_is_comment = line.starts_with('#');
+    }
+
+    let penalty: u32 = report.components().iter().map(|c| c.penalty as u32).sum();
+    report.score = 100u32.saturating_sub(penalty) as u8;
+    report
+}
+
+/// Returns whether a bind line carries a `d` flag description
+fn has_description(line: &str) -> bool {
+    line.split_once('=')
+        .and_then(|(keyword, _)| BindType::from_keyword(keyword.trim()))
+        .is_some_and(|bind_type| bind_type.contains(BindFlag::Description))
+}
+
+/// Returns whether a binding can never do anything
+///
+/// Covers bindings without a dispatcher, `exec` without a command,
+/// mouse binds (`bindm`) with a dispatcher other than `movewindow` or
+/// `resizewindow`, the only two Hyprland runs from a mouse bind, and
+/// `catchall` bindings outside a submap, where Hyprland ignores them.
+pub fn is_dead_binding(binding: &Keybinding) -> bool {
+    let dispatcher = binding.dispatcher.trim();
+    let command = binding.args.as_deref().map(str::trim).unwrap_or_default();
+
+    dispatcher.is_empty()
+        || (matches!(dispatcher, "exec" | "execr") && command.is_empty())
+        || (binding.bind_type.contains(BindFlag::Mouse)
+            && !matches!(dispatcher, "movewindow" | "resizewindow"))
+        || (binding.key_combo.is_catchall() && binding.submap.is_none())
+}
+
+/// Returns whether a program can be found and executed
+///
+/// Paths (anything containing `/`, after `~` expansion) are checked
+/// directly; bare names are looked up in `$PATH`.
+pub fn executable_on_path(program: &str) -> bool {
+    let is_executable = |path: &Path| {
+        path.metadata()
+            .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+    };
+
+    if program.contains('/') {
+        return is_executable(Path::new(shellexpand::tilde(program).as_ref()));
+    }
+
+    env::var_os("PATH")
+        .is_some_and(|path| env::split_paths(&path).any(|dir| is_executable(&dir.join(program))))
+}
+
+/// Extracts the program an exec command starts
+///
+/// Leading `VAR=value` assignments are skipped. Returns `None` when the
+/// program is computed at runtime (`$VAR`, `$(...)`, backticks).
+fn exec_program(command: &str) -> Option<&str> {
+    let program = command.split_whitespace().find(|word| {
+        !word
+            .split_once('=')
+            .is_some_and(|(name, _)| is_env_name(name))
+    })?;
+
+    (!program.contains(['$', '`', '('])).then_some(program)
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn component(label: &'static str, detail: String, points: u32, cap: u32) -> HealthComponent {
+    HealthComponent {
+        label,
+        detail,
+        penalty: points.min(cap) as u8,
+        max_penalty: cap as u8,
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+}
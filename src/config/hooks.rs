@@ -0,0 +1,323 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pre- and post-write hooks
+//!
+//! User scripts in the hooks directory run around every keybinding write,
+//! in the style of git hooks:
+//!
+//! - `pre-write` runs before the config is changed. A non-zero exit aborts
+//!   the write.
+//! - `post-write` runs after the config was written. Its exit status is
+//!   only reported.
+//!
+//! Hooks live in `$XDG_CONFIG_HOME/hypr-keybind-manager/hooks/` (falling back
+//! to `~/.config/...`) and must be executable. Each hook receives a JSON
+//! [`Snapshot`] of the bindings before and after the change, on stdin and
+//! as a file named by the `HYPR_KEYBIND_SNAPSHOT` environment variable, so
+//! hooks can act on exact change data without re-parsing the config.
+//!
+//! # Example hook
+//! ```bash
+//! #!/bin/sh
+//! # ~/.config/hypr-keybind-manager/hooks/post-write
+//! added=$(jq '.added | length' "$HYPR_KEYBIND_SNAPSHOT")
+//! notify-send "Keybindings saved" "$added added"
+//! ```
+
+use std::{
+    env, fs,
+    io::Write,
+    os::unix::fs::{OpenOptionsExt, PermissionsExt},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::Local;
+use serde::Serialize;
+
+use crate::{
+    config::{paths::RuntimePaths, ConfigError},
+    core::{
+        parser::{diagnose_config, parse_config_file, ParseDiagnostic},
+        Conflict, ConflictDetector, Keybinding,
+    },
+};
+
+/// Environment variable holding the path of the snapshot file
+pub const SNAPSHOT_ENV: &str = "HYPR_KEYBIND_SNAPSHOT";
+
+/// Snapshot format version, bumped on incompatible changes
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// How long a hook may run before it is killed
+pub const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// When a hook runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookEvent {
+    /// Before the config is written; failure aborts the write
+    PreWrite,
+    /// After the config was written
+    PostWrite,
+}
+
+impl HookEvent {
+    /// File name of the hook script for this event
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            HookEvent::PreWrite => "pre-write",
+            HookEvent::PostWrite => "post-write",
+        }
+    }
+}
+
+/// All bindings of a config with their conflicts and parse diagnostics
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BindingSet {
+    pub bindings: Vec<Keybinding>,
+    pub conflicts: Vec<Conflict>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl BindingSet {
+    /// Parses config content into a binding set
+    ///
+    /// Unparseable content yields an empty set; the diagnostics still
+    /// describe what went wrong.
+    pub fn from_content(content: &str, config_path: &Path) -> Self {
+        let bindings = parse_config_file(content, config_path).unwrap_or_default();
+
+        let mut detector = ConflictDetector::new();
+        for binding in bindings.iter().cloned() {
+            detector.add_binding(binding);
+        }
+
+        Self {
+            conflicts: detector.find_conflicts(),
+            diagnostics: diagnose_config(content),
+            bindings,
+        }
+    }
+}
+
+/// What hooks receive: the bindings before and after a write
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Snapshot {
+    /// Format version ([`SNAPSHOT_VERSION`])
+    pub version: u32,
+    /// Which hook is running
+    pub event: HookEvent,
+    /// Config file being written
+    pub config_path: PathBuf,
+    /// When the snapshot was taken (RFC 3339)
+    pub timestamp: String,
+    /// Bindings currently on disk
+    pub before: BindingSet,
+    /// Bindings after the write
+    pub after: BindingSet,
+    /// Bindings in `after` but not in `before`
+    pub added: Vec<Keybinding>,
+    /// Bindings in `before` but not in `after`
+    pub removed: Vec<Keybinding>,
+}
+
+impl Snapshot {
+    /// Builds a snapshot from the config content before and after a write
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Hook the snapshot is for
+    /// * `config_path` - Config file being written
+    /// * `before` - Current content
+    /// * `after` - Content about to be (or just) written
+    pub fn new(event: HookEvent, config_path: &Path, before: &str, after: &str) -> Self {
+        let before = BindingSet::from_content(before, config_path);
+        let after = BindingSet::from_content(after, config_path);
+
+        let added = after
+            .bindings
+            .iter()
+            .filter(|binding| !before.bindings.contains(binding))
+            .cloned()
+            .collect();
+        let removed = before
+            .bindings
+            .iter()
+            .filter(|binding| !after.bindings.contains(binding))
+            .cloned()
+            .collect();
+
+        Self {
+            version: SNAPSHOT_VERSION,
+            event,
+            config_path: config_path.to_path_buf(),
+            timestamp: Local::now().to_rfc3339(),
+            before,
+            after,
+            added,
+            removed,
+        }
+    }
+
+    /// Serialises the snapshot as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Fails when the config path is not valid UTF-8, as JSON strings must be.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Hook scripts in a directory
+#[derive(Clone, Debug)]
+pub struct Hooks {
+    dir: PathBuf,
+}
+
+impl Hooks {
+    /// Returns the default hooks directory
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - Path under [`RuntimePaths::config_dir`]
+    /// * `None` - Neither `XDG_CONFIG_HOME` nor `HOME` is set
+    pub fn default_dir() -> Option<PathBuf> {
+        Some(RuntimePaths::from_env().config_dir()?.join("hooks"))
+    }
+
+    /// Uses hooks from a directory (which need not exist)
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Returns the script for an event, if it exists and is executable
+    pub fn hook_path(&self, event: HookEvent) -> Option<PathBuf> {
+        let path = self.dir.join(event.file_name());
+        let metadata = path.metadata().ok()?;
+
+        (metadata.is_file() && metadata.permissions().mode() & 0o111 != 0).then_some(path)
+    }
+
+    /// Returns whether any hook is installed
+    pub fn any_installed(&self) -> bool {
+        [HookEvent::PreWrite, HookEvent::PostWrite]
+            .into_iter()
+            .any(|event| self.hook_path(event).is_some())
+    }
+
+    /// Runs the hook for the snapshot's event, if installed
+    ///
+    /// The snapshot is passed on stdin and as a temporary file (readable
+    /// only by the user) named by [`SNAPSHOT_ENV`]. The hook is killed
+    /// after [`HOOK_TIMEOUT`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - The hook ran and succeeded
+    /// * `Ok(false)` - No hook is installed for this event
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::HookFailed` if the snapshot could not be
+    /// serialised, or the hook could not be started, exited non-zero or
+    /// timed out.
+    pub fn run(&self, snapshot: &Snapshot) -> Result<bool, ConfigError> {
+        let Some(hook) = self.hook_path(snapshot.event) else {
+            return Ok(false);
+        };
+        let name = snapshot.event.file_name();
+        let json = snapshot.to_json().map_err(|e| {
+            ConfigError::HookFailed(format!("{} snapshot could not be written: {}", name, e))
+        })?;
+
+        let snapshot_file = SnapshotFile::create(&json)?;
+
+        let mut child = Command::new(&hook)
+            .arg(name)
+            .env(SNAPSHOT_ENV, &snapshot_file.0)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| ConfigError::HookFailed(format!("{} could not start: {}", name, e)))?;
+
+        // Hooks that ignore stdin close the pipe early; that is fine
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(json.as_bytes());
+        }
+
+        let started = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if started.elapsed() >= HOOK_TIMEOUT {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(ConfigError::HookFailed(format!(
+                    "{} timed out after {}s",
+                    name,
+                    HOOK_TIMEOUT.as_secs()
+                )));
+            }
+            thread::sleep(Duration::from_millis(20));
+        };
+
+        if !status.success() {
+            return Err(ConfigError::HookFailed(format!(
+                "{} exited with {}",
+                name, status
+            )));
+        }
+
+        Ok(true)
+    }
+}
+
+/// Temporary snapshot file, removed on drop
+struct SnapshotFile(PathBuf);
+
+impl SnapshotFile {
+    fn create(json: &str) -> Result<Self, ConfigError> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let path = env::temp_dir().join(format!(
+            "hypr-keybind-snapshot-{}-{}.json",
+            std::process::id(),
+            nanos
+        ));
+
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)?;
+        file.write_all(json.as_bytes())?;
+
+        Ok(Self(path))
+    }
+}
+
+impl Drop for SnapshotFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Security review of imported and pasted bindings
+//!
+//! Bindings from an import file or the clipboard go through the full
+//! validation pipeline (see [`crate::config::validator`]) before they join
+//! the config, instead of only when the config is written:
+//!
+//! - **Blocked**: Injection attempts (Layer 1) and Critical commands
+//!   (Layer 2). Writing them would be refused anyway, so they are never
+//!   added
+//! - **Dangerous**: Dangerous commands are only added once the user has
+//!   explicitly acknowledged them
+//! - **Accepted**: Everything else, Suspicious commands included (they are
+//!   badged in the list like any other)
+//!
+//! Entries of an import file that could not be read as bindings at all are
+//! listed in [`ImportReview::unreadable`], so one broken entry does not
+//! stop the rest of the file from being imported.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::{import_review::ImportReview, validator::ConfigValidator};
+//! use hypr_keybind_manager::core::parser::parse_config_file;
+//! use std::path::Path;
+//!
+//! let pasted = "bind = SUPER, K, exec, kitty\n\
+//!               bind = SUPER, X, exec, rm -rf /\n";
+//! let bindings = parse_config_file(pasted, Path::new("")).unwrap();
+//! let review = ImportReview::new(bindings, &ConfigValidator::new());
+//!
+//! assert_eq!(review.blocked().count(), 1);
+//! assert_eq!(review.pending(true).len(), 1);
+//! ```
+
+use crate::{
+    config::{
+        danger::{DangerAssessment, DangerLevel},
+        validator::{ConfigValidator, ValidationLevel},
+    },
+    core::Keybinding,
+};
+
+/// How reviewed bindings join the config
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportMode {
+    /// Replace all existing bindings with imported ones
+    Replace,
+    /// Merge imported bindings with existing (skip duplicates)
+    Merge,
+}
+
+/// What the review decided for one binding
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportVerdict {
+    /// Safe to add (possibly Suspicious)
+    Accepted,
+    /// Runs a Dangerous command; added only when acknowledged
+    Dangerous(DangerAssessment),
+    /// Never added: why writing it would be refused
+    Blocked(String),
+}
+
+/// A binding together with its verdict
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReviewedBinding {
+    pub binding: Keybinding,
+    pub verdict: ImportVerdict,
+}
+
+/// Verdicts for every binding of one import or paste
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportReview {
+    /// Bindings in their original order
+    pub entries: Vec<ReviewedBinding>,
+    /// Why entries of the file that are not bindings were skipped
+    pub unreadable: Vec<String>,
+}
+
+impl ImportReview {
+    /// Runs every validation layer over the bindings
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - Parsed bindings, not yet part of the config
+    /// * `validator` - Validator providing the injection and danger checks
+    pub fn new(bindings: Vec<Keybinding>, validator: &ConfigValidator) -> Self {
+        let report = validator.validate_bindings(&bindings);
+
+        let entries = bindings
+            .into_iter()
+            .enumerate()
+            .map(|(index, binding)| {
+                let error = report.issues.iter().find(|issue| {
+                    issue.binding_index == index && issue.validation_level == ValidationLevel::Error
+                });
+                let danger = report
+                    .dangerous_commands
+                    .iter()
+                    .find(|(binding_index, _)| *binding_index == index)
+                    .map(|(_, assessment)| assessment);
+
+                let verdict = match (error, danger) {
+                    (Some(issue), _) => ImportVerdict::Blocked(issue.message.clone()),
+                    (None, Some(assessment))
+                        if assessment.danger_level == DangerLevel::Critical =>
+                    {
+                        ImportVerdict::Blocked(format!("Critical command: {}", assessment.reason))
+                    }
+                    (None, Some(assessment)) => ImportVerdict::Dangerous(assessment.clone()),
+                    (None, None) => ImportVerdict::Accepted,
+                };
+                ReviewedBinding { binding, verdict }
+            })
+            .collect();
+
+        Self {
+            entries,
+            unreadable: Vec::new(),
+        }
+    }
+
+    /// Returns whether some binding may only be added once acknowledged
+    pub fn needs_acknowledgement(&self) -> bool {
+        self.dangerous().next().is_some()
+    }
+
+    /// Bindings running a Dangerous command
+    pub fn dangerous(&self) -> impl Iterator<Item = &ReviewedBinding> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.verdict, ImportVerdict::Dangerous(_)))
+    }
+
+    /// Bindings that are never added
+    pub fn blocked(&self) -> impl Iterator<Item = &ReviewedBinding> {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.verdict, ImportVerdict::Blocked(_)))
+    }
+
+    /// The bindings to add
+    ///
+    /// # Arguments
+    ///
+    /// * `acknowledged` - The user accepted the Dangerous commands
+    ///
+    /// # Returns
+    ///
+    /// Accepted bindings, plus Dangerous ones if acknowledged, in order
+    pub fn pending(&self, acknowledged: bool) -> Vec<Keybinding> {
+        self.entries
+            .iter()
+            .filter(|entry| match entry.verdict {
+                ImportVerdict::Accepted => true,
+                ImportVerdict::Dangerous(_) => acknowledged,
+                ImportVerdict::Blocked(_) => false,
+            })
+            .map(|entry| entry.binding.clone())
+            .collect()
+    }
+
+    /// Adds the pending bindings to a config's bindings
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - The config's current bindings
+    /// * `mode` - Replace the config's own bindings (sourced files are
+    ///   kept) or merge, skipping key combos that are already bound
+    /// * `acknowledged` - The user accepted the Dangerous commands
+    ///
+    /// # Returns
+    ///
+    /// The bindings to write, and how many of them were added
+    pub fn apply_to(
+        &self,
+        mut bindings: Vec<Keybinding>,
+        mode: ImportMode,
+        acknowledged: bool,
+    ) -> (Vec<Keybinding>, usize) {
+        let imported_bindings = self.pending(acknowledged);
+
+        let added = match mode {
+            ImportMode::Replace => {
+                // Replace: Clear all (except sourced files) and add imported
+                bindings.retain(|binding| binding.source_file.is_some());
+                let added = imported_bindings.len();
+                bindings.extend(imported_bindings);
+                added
+            }
+            ImportMode::Merge => {
+                // Merge: Add imported, skip duplicates (same key combo)
+                let before = bindings.len();
+                for binding in imported_bindings {
+                    let exists = bindings.iter().any(|b| b.key_combo == binding.key_combo);
+                    if !exists {
+                        bindings.push(binding);
+                    }
+                }
+                bindings.len() - before
+            }
+        };
+
+        (bindings, added)
+    }
+}
@@ -0,0 +1,704 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Importing keybindings from sway and i3, and exporting them back
+//!
+//! sway and i3 share their config syntax: `bindsym $mod+Shift+q kill`, with
+//! `set $mod Mod4` variables and `mode "resize" { ... }` blocks. Their bind
+//! lines are translated into Hyprland bindings:
+//!
+//! - **Modifiers**: `Mod4`/`Super` → SUPER, `Mod1`/`Alt` → ALT,
+//!   `Control`/`Ctrl` → CTRL, `Shift` → SHIFT
+//! - **Keys**: Keysyms carry over as they are; `bindcode 24` becomes
+//!   `code:24` and `button1`..`button3` the mouse buttons
+//! - **Flags**: `--release` → `bindr`, `--locked` → `bindl`; flags about
+//!   where a mouse binding applies are dropped
+//! - **Commands**: `exec`, `kill`, `focus`/`move <direction>`,
+//!   `[move container to] workspace`, `fullscreen`, `floating`, `sticky`,
+//!   the scratchpad, `resize grow|shrink`, `split toggle` and `mode`
+//!   (modes become submaps)
+//!
+//! A bind line that cannot be translated (an unknown command, chained
+//! commands, criteria such as `[class="x"]`, per-device bindings) is
+//! reported with its line number and the rest of the file is still
+//! imported. Translated bindings go through the same review as any other
+//! import (see [`crate::config::import_review`]).
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::interop::ForeignConfig;
+//!
+//! let sway = "set $mod Mod4\n\
+//!             bindsym $mod+Return exec foot\n\
+//!             bindsym $mod+Shift+2 move container to workspace number 2\n\
+//!             bindsym $mod+r mode \"resize\"\n";
+//! let imported = ForeignConfig::Sway.translate(sway);
+//!
+//! assert_eq!(imported.bindings.len(), 3);
+//! assert_eq!(imported.bindings[0].to_string(), "bind = SUPER+RETURN, exec, foot");
+//! assert_eq!(imported.bindings[1].dispatcher, "movetoworkspace");
+//! assert_eq!(imported.bindings[2].args.as_deref(), Some("resize"));
+//! ```
+//!
+//! # Exporting
+//!
+//! [`ForeignConfig::export`] goes the other way, for people running both
+//! compositors: the same dispatchers are written back as sway or i3
+//! commands, submaps as `mode` blocks and descriptions as comments.
+//! Everything else (`pseudo`, drag bindings, special workspaces by name,
+//! per-device bindings, ...) has no equivalent; those bindings are listed
+//! as comments at the end of the file and in [`ForeignExport::skipped`].
+
+use std::{fmt, path::PathBuf, str::FromStr};
+
+use crate::{
+    config::export::ImportedBindings,
+    core::{
+        keysyms::KEYSYM_ALIASES, BindFlag, BindType, Key, KeyCombo, Keybinding, Modifier,
+        ScrollDirection, SUBMAP_RESET,
+    },
+};
+
+/// Result of exporting bindings as a sway or i3 config
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ForeignExport {
+    /// The config, ready to be included from the window manager's config
+    pub content: String,
+    /// `binding: reason` for every binding that has no equivalent
+    pub skipped: Vec<String>,
+}
+
+/// Window manager whose config is imported
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForeignConfig {
+    /// sway (Wayland)
+    Sway,
+    /// i3 (X11)
+    I3,
+}
+
+impl ForeignConfig {
+    /// Both window managers, sway first
+    pub const ALL: [ForeignConfig; 2] = [ForeignConfig::Sway, ForeignConfig::I3];
+
+    /// Name shown in the import wizard
+    pub fn title(self) -> &'static str {
+        match self {
+            ForeignConfig::Sway => "sway",
+            ForeignConfig::I3 => "i3",
+        }
+    }
+
+    /// Where the window manager reads its config by default
+    pub fn default_path(self) -> PathBuf {
+        let path = match self {
+            ForeignConfig::Sway => "~/.config/sway/config",
+            ForeignConfig::I3 => "~/.config/i3/config",
+        };
+        PathBuf::from(shellexpand::tilde(path).as_ref())
+    }
+
+    /// Translates the bind lines of a config
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The sway or i3 config
+    ///
+    /// # Returns
+    ///
+    /// The translated bindings in config order, and a `line N: reason:
+    /// line` entry for every bind line that was left out
+    pub fn translate(self, content: &str) -> ImportedBindings {
+        let mut imported = ImportedBindings::default();
+        let mut variables: Vec<(String, String)> = Vec::new();
+        // Open blocks; `Some(name)` for a mode, `None` for anything else
+        // (`bar { ... }` binds belong to the bar, not the window manager)
+        let mut blocks: Vec<Option<String>> = Vec::new();
+
+        for (number, line) in logical_lines(content) {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some((name, value)) = line
+                .strip_prefix("set ")
+                .and_then(|rest| rest.trim().split_once(char::is_whitespace))
+            {
+                if name.starts_with('$') {
+                    variables.push((name.to_string(), value.trim().to_string()));
+                    // Longest first, so `$mod` does not eat into `$mod2`
+                    variables.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+                }
+                continue;
+            }
+            let line = variables
+                .iter()
+                .fold(line.to_string(), |line, (name, value)| {
+                    line.replace(name.as_str(), value)
+                });
+
+            if line == "}" {
+                blocks.pop();
+                continue;
+            }
+            if let Some(header) = line.strip_suffix('{') {
+                let mode = header
+                    .trim()
+                    .strip_prefix("mode ")
+                    .map(|name| unquote(name.trim()).to_string());
+                blocks.push(mode);
+                continue;
+            }
+
+            let (keyword, rest) = split_word(&line);
+            if !matches!(keyword, "bindsym" | "bindcode") {
+                continue;
+            }
+            let mode = match blocks.last() {
+                None => None,
+                Some(Some(mode)) => Some(mode.as_str()),
+                Some(None) => continue,
+            };
+            match translate_bind(keyword == "bindcode", rest, mode) {
+                Ok(binding) => imported.bindings.push(binding),
+                Err(reason) => imported
+                    .errors
+                    .push(format!("line {}: {}: {}", number, reason, line)),
+            }
+        }
+
+        imported
+    }
+
+    /// Writes bindings as a sway or i3 config
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - Bindings to export, in config order
+    ///
+    /// # Returns
+    ///
+    /// The config and the bindings that were left out of it. Left-out
+    /// bindings are also listed as comments at the end of the config.
+    pub fn export(self, bindings: &[Keybinding]) -> ForeignExport {
+        let mut export = ForeignExport::default();
+        let mut main = Vec::new();
+        let mut modes: Vec<(&str, Vec<String>)> = Vec::new();
+        let uses_super = bindings
+            .iter()
+            .any(|binding| binding.key_combo.modifiers.contains(&Modifier::Super));
+
+        for binding in bindings {
+            let lines = match self.export_bind(binding) {
+                Ok(line) => binding
+                    .description
+                    .iter()
+                    .map(|description| format!("# {}", description.replace('\n', " ")))
+                    .chain(std::iter::once(line))
+                    .collect::<Vec<_>>(),
+                Err(reason) => {
+                    export.skipped.push(format!("{}: {}", binding, reason));
+                    continue;
+                }
+            };
+            match binding.submap.as_deref() {
+                None => main.extend(lines),
+                Some(submap) => match modes.iter_mut().find(|(name, _)| *name == submap) {
+                    Some((_, mode_lines)) => mode_lines.extend(lines),
+                    None => modes.push((submap, lines)),
+                },
+            }
+        }
+
+        let content = &mut export.content;
+        content.push_str(&format!(
+            "# {} keybindings exported by hypr-keybind-manager\n\n",
+            self.title()
+        ));
+        if uses_super {
+            content.push_str("set $mod Mod4\n\n");
+        }
+        for line in &main {
+            content.push_str(line);
+            content.push('\n');
+        }
+        for (name, lines) in &modes {
+            content.push_str(&format!("\nmode \"{}\" {{\n", name));
+            for line in lines {
+                content.push_str(&format!("    {}\n", line));
+            }
+            content.push_str("}\n");
+        }
+        if !export.skipped.is_empty() {
+            content.push_str(&format!(
+                "\n# Not exported (no {} equivalent):\n",
+                self.title()
+            ));
+            for skipped in &export.skipped {
+                content.push_str(&format!("# {}\n", skipped.replace('\n', " ")));
+            }
+        }
+
+        export
+    }
+
+    /// Writes one binding as a `bindsym` or `bindcode` line
+    fn export_bind(self, binding: &Keybinding) -> Result<String, String> {
+        if binding.device.is_some() {
+            return Err("per-device bindings are not exported".to_string());
+        }
+
+        let mut flags = String::new();
+        for flag in binding.bind_type.flags() {
+            match flag {
+                BindFlag::Release => flags.push_str("--release "),
+                BindFlag::Locked if self == ForeignConfig::Sway => flags.push_str("--locked "),
+                BindFlag::Bypass if self == ForeignConfig::Sway => flags.push_str("--inhibited "),
+                // Keys repeat and bindings apply everywhere without a flag;
+                // i3 has no lock screen or inhibitor bindings to opt into
+                BindFlag::Repeat
+                | BindFlag::Locked
+                | BindFlag::Bypass
+                | BindFlag::Description
+                | BindFlag::NonConsuming
+                | BindFlag::Transparent
+                | BindFlag::IgnoreMods
+                | BindFlag::Universal => {}
+                BindFlag::Mouse
+                | BindFlag::Click
+                | BindFlag::Drag
+                | BindFlag::LongPress
+                | BindFlag::Separate => {
+                    return Err(format!(
+                        "no {} equivalent for {} bindings",
+                        self.title(),
+                        flag.label()
+                    ))
+                }
+            }
+        }
+
+        let (keyword, key) = match &binding.key_combo.key {
+            Key::Keycode(code) => ("bindcode", code.to_string()),
+            key => ("bindsym", self.export_key(key)?),
+        };
+        // `$mod+Shift+q`, the order sway's and i3's default configs use
+        let combo = [
+            (Modifier::Super, "$mod"),
+            (Modifier::Ctrl, "Control"),
+            (Modifier::Alt, "Mod1"),
+            (Modifier::Shift, "Shift"),
+        ]
+        .into_iter()
+        .filter(|(modifier, _)| binding.key_combo.modifiers.contains(modifier))
+        .map(|(_, name)| name)
+        .chain(std::iter::once(key.as_str()))
+        .collect::<Vec<_>>()
+        .join("+");
+        let command = self.export_command(binding)?;
+
+        Ok(format!("{} {}{} {}", keyword, flags, combo, command))
+    }
+
+    /// Spells a key the way sway and i3 expect it
+    fn export_key(self, key: &Key) -> Result<String, String> {
+        let key = match key {
+            Key::Named(name) => KEYSYM_ALIASES
+                .iter()
+                .map(|(keysym, _)| *keysym)
+                .find(|keysym| keysym.eq_ignore_ascii_case(name))
+                .map(str::to_string)
+                .unwrap_or_else(|| {
+                    if name.chars().count() == 1 {
+                        name.to_lowercase()
+                    } else {
+                        name.clone()
+                    }
+                }),
+            Key::MouseButton(272) => "button1".to_string(),
+            Key::MouseButton(274) => "button2".to_string(),
+            Key::MouseButton(273) => "button3".to_string(),
+            Key::Scroll(ScrollDirection::Up) => "button4".to_string(),
+            Key::Scroll(ScrollDirection::Down) => "button5".to_string(),
+            Key::Scroll(ScrollDirection::Left) => "button6".to_string(),
+            Key::Scroll(ScrollDirection::Right) => "button7".to_string(),
+            key => {
+                return Err(format!(
+                    "no {} equivalent for the key {}",
+                    self.title(),
+                    key
+                ))
+            }
+        };
+        Ok(key)
+    }
+
+    /// Translates a binding's dispatcher into a sway/i3 command
+    fn export_command(self, binding: &Keybinding) -> Result<String, String> {
+        let args = binding.args.as_deref().unwrap_or_default().trim();
+        let unsupported = || {
+            format!(
+                "no {} equivalent for '{}'",
+                self.title(),
+                [binding.dispatcher.as_str(), args].join(" ").trim()
+            )
+        };
+        let direction = |args: &str| match args.chars().next() {
+            Some('l') => Some("left"),
+            Some('r') => Some("right"),
+            Some('u' | 't') => Some("up"),
+            Some('d' | 'b') => Some("down"),
+            _ => None,
+        };
+        let workspace = |args: &str| -> Option<String> {
+            let target = match args {
+                "e+1" => "next".to_string(),
+                "e-1" => "prev".to_string(),
+                "m+1" => "next_on_output".to_string(),
+                "m-1" => "prev_on_output".to_string(),
+                "previous" => "back_and_forth".to_string(),
+                _ if args.parse::<u32>().is_ok() => format!("number {}", args),
+                _ => {
+                    let name = args.strip_prefix("name:")?;
+                    format!("\"{}\"", name)
+                }
+            };
+            Some(target)
+        };
+
+        let command = match (binding.dispatcher.as_str(), args) {
+            ("exec", command) if !command.is_empty() && !command.starts_with('[') => {
+                // Unquoted, `;` and `,` would end the command
+                let command = if command.contains([';', ',']) {
+                    format!("\"{}\"", command.replace('\\', "\\\\").replace('"', "\\\""))
+                } else {
+                    command.to_string()
+                };
+                match self {
+                    ForeignConfig::Sway => format!("exec {}", command),
+                    ForeignConfig::I3 => format!("exec --no-startup-id {}", command),
+                }
+            }
+            ("killactive", _) => "kill".to_string(),
+            ("exit", _) => "exit".to_string(),
+            ("movefocus", dir) => format!("focus {}", direction(dir).ok_or_else(unsupported)?),
+            ("movewindow", dir) if !dir.starts_with("mon:") => {
+                format!("move {}", direction(dir).ok_or_else(unsupported)?)
+            }
+            ("fullscreen", "" | "0") => "fullscreen toggle".to_string(),
+            ("togglefloating", "" | "active") => "floating toggle".to_string(),
+            ("setfloating", "" | "active") => "floating enable".to_string(),
+            ("settiled", "" | "active") => "floating disable".to_string(),
+            ("pin", "" | "active") => "sticky toggle".to_string(),
+            ("togglesplit", _) | ("layoutmsg", "togglesplit") => "split toggle".to_string(),
+            ("movetoworkspace" | "movetoworkspacesilent", "special") => {
+                "move scratchpad".to_string()
+            }
+            ("togglespecialworkspace", "" | "special") => "scratchpad show".to_string(),
+            ("workspace", target) => {
+                format!("workspace {}", workspace(target).ok_or_else(unsupported)?)
+            }
+            ("movetoworkspacesilent", target) => format!(
+                "move container to workspace {}",
+                workspace(target).ok_or_else(unsupported)?
+            ),
+            ("movetoworkspace", target) => {
+                // Hyprland follows the window; sway needs a second command
+                let target = workspace(target).ok_or_else(unsupported)?;
+                format!(
+                    "move container to workspace {}; workspace {}",
+                    target, target
+                )
+            }
+            ("resizeactive", delta) => {
+                let amounts: Vec<i32> = delta
+                    .split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| unsupported())?;
+                let [width, height] = amounts[..] else {
+                    return Err(unsupported());
+                };
+                let resize = |side: &str, amount: i32| {
+                    let change = if amount < 0 { "shrink" } else { "grow" };
+                    format!("resize {} {} {} px", change, side, amount.abs())
+                };
+                match (width, height) {
+                    (0, 0) => return Err(unsupported()),
+                    (width, 0) => resize("width", width),
+                    (0, height) => resize("height", height),
+                    (width, height) => {
+                        format!("{}; {}", resize("width", width), resize("height", height))
+                    }
+                }
+            }
+            ("submap", name) if !name.is_empty() => {
+                let name = if name == SUBMAP_RESET {
+                    "default"
+                } else {
+                    name
+                };
+                format!("mode \"{}\"", name)
+            }
+            _ => return Err(unsupported()),
+        };
+        Ok(command)
+    }
+}
+
+impl fmt::Display for ForeignConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.title())
+    }
+}
+
+impl FromStr for ForeignConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ForeignConfig::ALL
+            .into_iter()
+            .find(|config| config.title() == s)
+            .ok_or_else(|| format!("Unknown window manager: {s} (sway or i3)"))
+    }
+}
+
+/// Joins lines continued with a trailing `\`, numbering them by their
+/// first line (1-based)
+fn logical_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let (number, mut joined) = pending.take().unwrap_or((index + 1, String::new()));
+        match line.strip_suffix('\\') {
+            Some(start) => {
+                joined.push_str(start);
+                pending = Some((number, joined));
+            }
+            None => {
+                joined.push_str(line);
+                lines.push((number, joined));
+            }
+        }
+    }
+    lines.extend(pending);
+    lines
+}
+
+/// Splits off the first whitespace-separated word
+fn split_word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (text, ""),
+    }
+}
+
+/// Strips one pair of surrounding double quotes
+fn unquote(text: &str) -> &str {
+    text.strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text)
+}
+
+/// Translates the part of a bind line after `bindsym`/`bindcode`
+fn translate_bind(is_code: bool, mut rest: &str, mode: Option<&str>) -> Result<Keybinding, String> {
+    let mut bind_type = BindType::Bind;
+    loop {
+        let (word, after) = split_word(rest);
+        let Some(flag) = word.strip_prefix("--") else {
+            break;
+        };
+        match flag {
+            "release" => bind_type = bind_type.with(BindFlag::Release),
+            "locked" => bind_type = bind_type.with(BindFlag::Locked),
+            _ if flag.starts_with("input-device") => {
+                return Err("per-device bindings are not imported".to_string())
+            }
+            // Where a mouse binding applies, key repeat, layout handling
+            _ => {}
+        }
+        rest = after;
+    }
+
+    let (combo, command) = split_word(rest);
+    if command.is_empty() {
+        return Err("no command".to_string());
+    }
+    let key_combo = translate_combo(combo, is_code)?;
+    let (dispatcher, args) = translate_command(command)?;
+
+    Ok(Keybinding {
+        key_combo,
+        bind_type,
+        dispatcher: dispatcher.to_string(),
+        args,
+        submap: mode.map(str::to_string),
+        source_file: None,
+        description: None,
+        category: None,
+        device: None,
+        location: None,
+    })
+}
+
+/// Translates `Mod4+Shift+q` (or a key code for `bindcode`)
+fn translate_combo(combo: &str, is_code: bool) -> Result<KeyCombo, String> {
+    let mut parts: Vec<&str> = combo.split('+').collect();
+    let key = parts.pop().unwrap_or_default();
+
+    let modifiers = parts
+        .iter()
+        .map(|name| match name.to_lowercase().as_str() {
+            "mod4" | "super" => Ok(Modifier::Super),
+            "mod1" | "alt" => Ok(Modifier::Alt),
+            "control" | "ctrl" => Ok(Modifier::Ctrl),
+            "shift" => Ok(Modifier::Shift),
+            _ => Err(format!("modifier {} has no Hyprland equivalent", name)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let key = if is_code {
+        format!("code:{}", key)
+    } else {
+        match key {
+            "button1" => "mouse:272".to_string(),
+            "button2" => "mouse:274".to_string(),
+            "button3" => "mouse:273".to_string(),
+            "button4" => "mouse_up".to_string(),
+            "button5" => "mouse_down".to_string(),
+            _ => key.to_string(),
+        }
+    };
+    Ok(KeyCombo::new(modifiers, &key))
+}
+
+/// Translates a sway/i3 command into a dispatcher and its arguments
+fn translate_command(command: &str) -> Result<(&'static str, Option<String>), String> {
+    let command = command.trim();
+    let unsupported = || format!("no Hyprland equivalent for '{}'", command);
+
+    if command.starts_with('[') {
+        return Err("commands with criteria are not imported".to_string());
+    }
+    let (first, rest) = split_word(command);
+    if matches!(first, "exec" | "exec_always") {
+        let rest = rest.strip_prefix("--no-startup-id").unwrap_or(rest).trim();
+        if rest.is_empty() {
+            return Err("exec without a command".to_string());
+        }
+        return Ok(("exec", Some(unquote(rest).to_string())));
+    }
+    if command.contains([';', ',']) {
+        return Err("chained commands are not imported".to_string());
+    }
+
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let direction = |word: &str| match word {
+        "left" => Some("l"),
+        "right" => Some("r"),
+        "up" => Some("u"),
+        "down" => Some("d"),
+        _ => None,
+    };
+    let some = |args: &str| Some(args.to_string());
+
+    let translated = match words.as_slice() {
+        ["kill"] => ("killactive", None),
+        ["exit"] => ("exit", None),
+        ["focus", dir] => ("movefocus", some(direction(dir).ok_or_else(unsupported)?)),
+        ["move", dir] if direction(dir).is_some() => ("movewindow", direction(dir).and_then(some)),
+        ["fullscreen"] | ["fullscreen", "toggle"] => ("fullscreen", None),
+        ["floating", "toggle"] => ("togglefloating", None),
+        ["floating", "enable"] => ("setfloating", None),
+        ["floating", "disable"] => ("settiled", None),
+        ["sticky", "toggle" | "enable"] => ("pin", None),
+        ["split", "toggle"] | ["layout", "toggle", "split"] => ("layoutmsg", some("togglesplit")),
+        ["move", "scratchpad"] => ("movetoworkspace", some("special")),
+        ["scratchpad", "show"] => ("togglespecialworkspace", None),
+        ["workspace", target @ ..] => ("workspace", Some(workspace_target(target)?)),
+        ["move", "container" | "window", "to", "workspace", target @ ..]
+        | ["move", "to", "workspace", target @ ..]
+        | ["move", "container" | "window", "workspace", target @ ..] => {
+            ("movetoworkspace", Some(workspace_target(target)?))
+        }
+        ["resize", change @ ("grow" | "shrink"), side @ ("width" | "height"), amount, unit @ ..] => {
+            // `10 px`, `10px`, or i3's `10 px or 10 ppt` (pixels are taken);
+            // percentages alone have no equivalent
+            if unit.first() == Some(&"ppt") {
+                return Err(unsupported());
+            }
+            let amount: i32 = amount
+                .strip_suffix("px")
+                .unwrap_or(amount)
+                .parse()
+                .map_err(|_| unsupported())?;
+            let amount = if *change == "grow" { amount } else { -amount };
+            let delta = if *side == "width" {
+                format!("{} 0", amount)
+            } else {
+                format!("0 {}", amount)
+            };
+            ("resizeactive", Some(delta))
+        }
+        ["mode", ..] => {
+            let name = unquote(command["mode".len()..].trim());
+            let submap = if name == "default" {
+                SUBMAP_RESET
+            } else {
+                name
+            };
+            ("submap", some(submap))
+        }
+        _ => return Err(unsupported()),
+    };
+    Ok(translated)
+}
+
+/// Translates the target of `workspace` (`number 3`, `next`, `web`)
+fn workspace_target(words: &[&str]) -> Result<String, String> {
+    let words: Vec<&str> = words
+        .iter()
+        .copied()
+        .filter(|word| *word != "--no-auto-back-and-forth")
+        .collect();
+
+    let target = match words.as_slice() {
+        [] => return Err("workspace without a target".to_string()),
+        ["next"] => "e+1".to_string(),
+        ["prev"] => "e-1".to_string(),
+        ["next_on_output"] => "m+1".to_string(),
+        ["prev_on_output"] => "m-1".to_string(),
+        ["back_and_forth"] => "previous".to_string(),
+        ["number", name, ..] => {
+            // `number 1:web` is workspace 1
+            let digits: String = name.chars().take_while(char::is_ascii_digit).collect();
+            if digits.is_empty() {
+                return Err(format!("workspace number '{}' is not a number", name));
+            }
+            digits
+        }
+        _ => {
+            let name = unquote(&words.join(" ")).to_string();
+            if name.parse::<i32>().is_ok() {
+                name
+            } else {
+                format!("name:{}", name)
+            }
+        }
+    };
+    Ok(target)
+}
@@ -16,8 +16,15 @@
 //! This module provides safe, transactional operations for managing Hyprland
 //! configuration files. Key features:
 //! - **Atomic writes**: Uses temp-file-then-rename to prevent corruption
-//! - **Automatic backups**: Every write creates a timestamped backup
+//! - **Automatic backups**: Every write creates a timestamped backup, or a
+//!   git commit ([`backup`]); old backups are pruned by a retention policy
+//! - **Pristine backup**: The first-ever write keeps a copy of the original config
 //! - **Rollback safety**: Failed transactions leave original config untouched
+//! - **Discovery**: Finds the config from the environment ([`discovery`])
+//! - **Previews**: Line diffs of what a write would change ([`diff`])
+//! - **Doctor**: Every check over a config, by section and severity ([`doctor`])
+//! - **Exports**: Bindings as config lines, JSON, YAML or TOML ([`export`])
+//! - **Interop**: Bind lines of sway and i3 configs as Hyprland bindings ([`interop`])
 //! - **Symlink warnings**: Alerts user but allows symlinked configs
 //!
 //! # Example
@@ -34,24 +41,64 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+pub mod audit;
+pub mod autofix;
+pub mod backup;
 pub mod danger;
+pub mod diff;
+pub mod discovery;
+pub mod doctor;
+pub mod document;
 pub mod error;
+pub mod export;
+pub mod fixtures;
+pub mod health;
+pub mod hooks;
+pub mod import_review;
+pub mod interop;
+pub mod notifications;
+pub mod overrides;
+pub mod paths;
+pub mod profiles;
+pub mod resolve;
+pub mod settings;
+pub mod split;
+pub mod state;
 pub mod transaction;
 pub mod validator;
+pub mod writer;
 
 pub use {error::ConfigError, transaction::ConfigTransaction};
 
 use atomic_write_file::AtomicWriteFile;
-use chrono::Local;
+use chrono::{Local, NaiveDateTime};
+#[cfg(unix)]
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
 use std::{
     fs,
     io::Write,
     path::{Path, PathBuf},
 };
-#[cfg(unix)]
-use std::os::unix::fs::{MetadataExt, PermissionsExt};
 
-use crate::{core::types::Keybinding, Modifier::*};
+use crate::{
+    config::{
+        autofix::FixMode,
+        backup::{BackupBackend, BackupCompression, BackupInfo, BackupMode, RetentionPolicy},
+        diff::ConfigDiff,
+        document::ConfigDocument,
+        export::ExportFormat,
+        hooks::{HookEvent, Hooks, Snapshot},
+        paths::RuntimePaths,
+        state::AppState,
+    },
+    core::{
+        binds_options,
+        grouping::GroupSuggestion,
+        parser::{parse_modifiers, VariableTable, CATEGORY_ANNOTATION},
+        types::{submap_names, BindFlag, Keybinding, Modifier, SUBMAP_RESET},
+    },
+    Modifier::*,
+};
 
 /// Manages Hyprland configuration files with safe atomic operations.
 /// The ConfigManager provides read-only access and transactional writes
@@ -63,6 +110,18 @@ pub struct ConfigManager {
     /// Path to the Hyprland configuration file.
     config_path: PathBuf,
     backup_dir: PathBuf,
+    /// Application state file (tracks configs written before)
+    state_path: Option<PathBuf>,
+    /// Directory with pre-/post-write hook scripts
+    hooks_dir: Option<PathBuf>,
+    /// Saves the config before each write
+    backup: Box<dyn BackupBackend>,
+    /// Which backend `backup` is
+    backup_mode: BackupMode,
+    /// How new timestamped backups are compressed
+    compression: BackupCompression,
+    /// Which timestamped backups are kept after each write
+    retention: RetentionPolicy,
 }
 
 impl ConfigManager {
@@ -96,6 +155,10 @@ impl ConfigManager {
     pub fn new(config_path: PathBuf) -> Result<Self, ConfigError> {
         // Validate config file exists
         if !config_path.exists() {
+            // A flatpak sandbox may simply not be allowed to see it
+            if let Some(warning) = RuntimePaths::from_env().sandbox_access_warning(&config_path) {
+                eprintln!("⚠ Warning: {warning}");
+            }
             return Err(ConfigError::NotFound(config_path));
         }
 
@@ -114,14 +177,9 @@ impl ConfigManager {
 
         // Create backup directory next to config file
         // e.g., ~/.config/hypr/hyprland.conf → ~/.config/hypr/backups/
-        let backup_dir = config_path
-            .parent()
-            .ok_or_else(|| {
-                ConfigError::BackupDirNotWritable(PathBuf::from(
-                    "Config file has no parent directory",
-                ))
-            })?
-            .join("backups");
+        let backup_dir = RuntimePaths::backup_dir(&config_path).ok_or_else(|| {
+            ConfigError::BackupDirNotWritable(PathBuf::from("Config file has no parent directory"))
+        })?;
 
         // Create backup directory if it doesn't exist
         if !backup_dir.exists() {
@@ -135,11 +193,124 @@ impl ConfigManager {
         }
 
         Ok(Self {
+            backup: BackupMode::default().backend(
+                &config_path,
+                &backup_dir,
+                BackupCompression::default(),
+            ),
+            backup_mode: BackupMode::default(),
+            compression: BackupCompression::default(),
             config_path,
             backup_dir,
+            state_path: None,
+            hooks_dir: Hooks::default_dir(),
+            retention: RetentionPolicy::default(),
         })
     }
 
+    /// Records managed configs in an application state file.
+    ///
+    /// Without one, no state is read or written, and the pristine backup is
+    /// taken whenever none exists (see [`Self::ensure_pristine_backup`]). The
+    /// CLI and the GUI use [`AppState::default_path`]; library users and
+    /// tests opt in with a path of their own.
+    ///
+    /// # Arguments
+    ///
+    /// * `state_path` - Location of the state file
+    pub fn with_state_path(mut self, state_path: PathBuf) -> Self {
+        self.set_state_path(state_path);
+        self
+    }
+
+    /// Records managed configs in an application state file
+    ///
+    /// See [`Self::with_state_path`].
+    ///
+    /// # Arguments
+    ///
+    /// * `state_path` - Location of the state file
+    pub fn set_state_path(&mut self, state_path: PathBuf) {
+        self.state_path = Some(state_path);
+    }
+
+    /// Uses hook scripts from a specific directory instead of the XDG default.
+    ///
+    /// # Arguments
+    ///
+    /// * `hooks_dir` - Directory containing `pre-write` / `post-write`
+    pub fn with_hooks_dir(mut self, hooks_dir: PathBuf) -> Self {
+        self.hooks_dir = Some(hooks_dir);
+        self
+    }
+
+    /// Uses a different backup backend than timestamped copies.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Backend to save the config with before each write
+    pub fn with_backup_mode(mut self, mode: BackupMode) -> Self {
+        self.set_backup_mode(mode);
+        self
+    }
+
+    /// Switches the backup backend, e.g. when the preference changes.
+    ///
+    /// Backups already made are kept; the pristine backup is always a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Backend to save the config with before each write
+    pub fn set_backup_mode(&mut self, mode: BackupMode) {
+        self.backup_mode = mode;
+        self.backup = mode.backend(&self.config_path, &self.backup_dir, self.compression);
+    }
+
+    /// Compresses new timestamped backups.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - gzip, zstd or none (the default)
+    pub fn with_backup_compression(mut self, compression: BackupCompression) -> Self {
+        self.set_backup_compression(compression);
+        self
+    }
+
+    /// Changes how new timestamped backups are compressed, e.g. when the
+    /// preference changes.
+    ///
+    /// Backups already made keep their compression and can still be restored.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - gzip, zstd or none
+    pub fn set_backup_compression(&mut self, compression: BackupCompression) {
+        self.compression = compression;
+        self.backup = self
+            .backup_mode
+            .backend(&self.config_path, &self.backup_dir, compression);
+    }
+
+    /// Prunes backups after each write by a different policy than the
+    /// default (the 50 newest).
+    ///
+    /// # Arguments
+    ///
+    /// * `retention` - Which timestamped backups to keep
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Changes the retention policy, e.g. when the preferences change.
+    ///
+    /// # Arguments
+    ///
+    /// * `retention` - Which timestamped backups to keep
+    pub fn set_retention(&mut self, retention: RetentionPolicy) {
+        self.retention = retention;
+    }
+
     fn permission_warnings(config_path: &Path) -> Vec<String> {
         #[cfg(unix)]
         {
@@ -225,37 +396,113 @@ impl ConfigManager {
         &self.config_path
     }
 
-    #[allow(dead_code)]
-    fn create_timestamped_backup(&self) -> Result<PathBuf, ConfigError> {
-        // Read the current config content
-        let content = fs::read_to_string(&self.config_path)?;
+    /// Saves the config through the backup backend before a write.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(path))` - Timestamped backup file
+    /// * `Ok(None)` - Saved by a backend without files (git)
+    fn create_backup(&self) -> Result<Option<PathBuf>, ConfigError> {
+        self.backup.backup(&self.config_path)
+    }
 
-        // Generate timestamp in YYYY-MM-DD_HHMMSS format
-        let timestamp = Local::now().format("%Y-%m-%d_%H%M%S");
+    /// Backs up the config as it is now, outside a write.
+    ///
+    /// Used by `daemon` to keep changes made by hand. A timestamped backup
+    /// is only written if the config differs from the newest one; with git
+    /// backups, uncommitted changes are committed as made outside the tool.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(path))` - Timestamped backup holding the config
+    /// * `Ok(None)` - Saved by a backend without files (git)
+    /// * `Err(ConfigError)` - Config not readable or backup not written
+    pub fn backup_now(&self) -> Result<Option<PathBuf>, ConfigError> {
+        self.create_backup()
+    }
+
+    /// Tells the backup backend about a finished write.
+    ///
+    /// The write already happened, so a failure is only reported.
+    fn record_write(&self, message: &str) {
+        if let Err(e) = self.backup.record(&self.config_path, message) {
+            eprintln!("⚠ Warning: {e}");
+        }
+    }
 
-        // Build the backup filename
-        // Extract the original filename (e.g., "hyprland.conf")
+    /// Returns the path of the pristine backup for this config.
+    ///
+    /// The pristine backup is named `{basename}.pristine` (for example
+    /// `hyprland.conf.pristine`). It does not carry a timestamp, so
+    /// `list_backups()` never returns it and retention cleanup never
+    /// removes it.
+    pub fn pristine_backup_path(&self) -> PathBuf {
         let original_name = self
             .config_path
             .file_name()
-            .expect("Config path should have a file name")
-            .to_str()
-            .expect("Filename should be valid UTF-8");
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "config".to_string());
 
-        let backup_filename = format!("{}.{}", original_name, timestamp);
-        let backup_path = self.backup_dir.join(&backup_filename);
+        self.backup_dir.join(format!("{}.pristine", original_name))
+    }
+
+    /// Creates the pristine backup before the first-ever managed write.
+    ///
+    /// Whether a write is the first one is tracked in the application state
+    /// (see [`AppState`]), not by the presence of the backup file: if the
+    /// pristine backup is later deleted, it is not recreated from a config
+    /// the tool has already modified. Without a state file (see
+    /// [`Self::with_state_path`]) only the presence of the backup counts. An
+    /// existing pristine file is never overwritten.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(path))` - Pristine backup was created by this call
+    /// * `Ok(None)` - Config was already managed, or a pristine backup exists
+    /// * `Err(ConfigError)` - Config could not be read or backup not written
+    pub fn ensure_pristine_backup(&self) -> Result<Option<PathBuf>, ConfigError> {
+        let mut state = match &self.state_path {
+            Some(state_path) => Some(AppState::load(state_path.clone())?),
+            None => None,
+        };
+
+        if state
+            .as_ref()
+            .is_some_and(|state| state.is_managed(&self.config_path))
+        {
+            return Ok(None);
+        }
 
-        // Write the backup file
-        fs::write(&backup_path, &content)?;
+        let pristine_path = self.pristine_backup_path();
+        let created = if pristine_path.exists() {
+            None
+        } else {
+            let content = fs::read_to_string(&self.config_path)?;
+            fs::write(&pristine_path, &content)
+                .and_then(|()| backup::write_checksum(&pristine_path, content.as_bytes()))
+                .map_err(|e| {
+                    ConfigError::BackupFailed(format!("Failed to write pristine backup: {}", e))
+                })?;
+            Some(pristine_path)
+        };
 
-        // Return the path so caller can verify or log it
-        Ok(backup_path)
+        if let Some(state) = state.as_mut() {
+            state.mark_managed(&self.config_path);
+            // The pristine file itself guards against overwriting on the next
+            // run, so a state write failure is worth a warning but not an abort
+            if let Err(e) = state.save() {
+                eprintln!("⚠ Warning: Failed to save application state: {}", e);
+            }
+        }
+
+        Ok(created)
     }
 
     /// Lists all backups in the backup directory, sorted newest first.
     ///
     /// Parses timestamps from filenames matching the pattern:
-    /// `{basename}.YYYY-MM-DD_HHMMSS`
+    /// `{basename}.YYYY-MM-DD_HHMMSS`, with `.gz` or `.zst` after it for
+    /// compressed backups. Checksum files are not listed.
     ///
     /// Invalid files (wrong format, unparseable timestamps) are silently skipped.
     ///
@@ -273,52 +520,16 @@ impl ConfigManager {
     /// # Ok::<(), hypr_keybind_manager::config::ConfigError>(())
     /// ```
     pub fn list_backups(&self) -> Result<Vec<PathBuf>, ConfigError> {
-        // Read the backup directory
-        let entries = fs::read_dir(&self.backup_dir).map_err(ConfigError::Io)?;
-
-        // Collect valid backups with their timestamps
-        let mut backups: Vec<(PathBuf, chrono::NaiveDateTime)> = Vec::new();
-
-        for entry in entries {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue, // Skip unreadable entries
-            };
-
-            let path = entry.path();
-
-            // Only process files (not directories)
-            if !path.is_file() {
-                continue;
-            }
-
-            // Extract filename
-            let filename = match path.file_name().and_then(|n| n.to_str()) {
-                Some(name) => name,
-                None => continue, // Skip if filename is invalid UTF-8
-            };
-
-            // Parse the filename: expect "basename.ext.YYYY-MM-DD_HHMMSS"
-            let parts: Vec<&str> = filename.split('.').collect();
-            if parts.len() != 3 {
-                continue; // Not a valid backup filename
-            }
-
-            // Extract and parse the timestamp (3rd part)
-            let timestamp_str = parts[2];
-            match chrono::NaiveDateTime::parse_from_str(timestamp_str, "%Y-%m-%d_%H%M%S") {
-                Ok(timestamp) => {
-                    backups.push((path, timestamp));
-                }
-                Err(_) => continue, // Invalid timestamp format, skip this file
-            }
-        }
-
-        // Sort by timestamp, newest first (descending order)
-        backups.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(self
+            .dated_backups()?
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect())
+    }
 
-        // Extract just the paths (discard timestamps)
-        Ok(backups.into_iter().map(|(path, _)| path).collect())
+    /// Lists the timestamped backups with their timestamps, newest first
+    fn dated_backups(&self) -> Result<Vec<(PathBuf, NaiveDateTime)>, ConfigError> {
+        backup::dated_backups(&self.backup_dir)
     }
 
     /// Deletes old backups, keeping only the N most recent.
@@ -357,13 +568,79 @@ impl ConfigManager {
         // Delete the old backups
         let mut deleted_count = 0;
         for backup_path in to_delete {
-            fs::remove_file(backup_path).map_err(ConfigError::Io)?;
+            backup::remove_backup(backup_path).map_err(ConfigError::Io)?;
             deleted_count += 1;
         }
 
         Ok(deleted_count)
     }
 
+    /// Lists the backups a retention policy would delete.
+    ///
+    /// Nothing is deleted; see [`Self::prune_backups`]. The pristine backup
+    /// is never listed.
+    ///
+    /// # Arguments
+    /// - `policy` - Which backups to keep
+    ///
+    /// # Returns
+    /// - `Ok(Vec<PathBuf>)` - Backups to delete, newest first
+    /// - `Err(ConfigError)` - If the backup directory cannot be read
+    pub fn backups_to_prune(&self, policy: &RetentionPolicy) -> Result<Vec<PathBuf>, ConfigError> {
+        let backups: Vec<BackupInfo> = self
+            .dated_backups()?
+            .into_iter()
+            .map(|(path, created)| BackupInfo {
+                size: fs::metadata(&path).map_or(0, |metadata| metadata.len()),
+                path,
+                created,
+            })
+            .collect();
+
+        Ok(policy.select(&backups, Local::now().naive_local()))
+    }
+
+    /// Deletes the backups a retention policy does not keep.
+    ///
+    /// # Arguments
+    /// - `policy` - Which backups to keep
+    ///
+    /// # Returns
+    /// - `Ok(Vec<PathBuf>)` - Backups deleted, newest first
+    /// - `Err(ConfigError)` - If listing or deletion fails
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use hypr_keybind_manager::config::{backup::RetentionPolicy, ConfigManager};
+    /// # use std::path::PathBuf;
+    /// # let manager = ConfigManager::new(PathBuf::from("hyprland.conf"))?;
+    /// // Keep a month of backups, at most 20 MB
+    /// let policy = RetentionPolicy {
+    ///     max_count: None,
+    ///     max_age_days: Some(30),
+    ///     max_total_bytes: Some(20_000_000),
+    /// };
+    /// let deleted = manager.prune_backups(&policy)?;
+    /// println!("Deleted {} old backups", deleted.len());
+    /// # Ok::<(), hypr_keybind_manager::config::ConfigError>(())
+    /// ```
+    pub fn prune_backups(&self, policy: &RetentionPolicy) -> Result<Vec<PathBuf>, ConfigError> {
+        let to_delete = self.backups_to_prune(policy)?;
+        for backup_path in &to_delete {
+            backup::remove_backup(backup_path)?;
+        }
+        Ok(to_delete)
+    }
+
+    /// Prunes backups by the manager's retention policy after a write.
+    ///
+    /// The write already happened, so a failure is only reported.
+    fn apply_retention(&self) {
+        if let Err(e) = self.prune_backups(&self.retention) {
+            eprintln!("⚠ Warning: Failed to prune old backups: {e}");
+        }
+    }
+
     /// Restores the configuration from a specific backup file.
     ///
     /// This function performs a safe restore operation by:
@@ -424,13 +701,19 @@ impl ConfigManager {
         }
 
         // Step 2: Read content from the specified backup FIRST
-        // (Do this before creating safety backup to ensure backup is readable)
-        let backup_content = fs::read_to_string(backup_path)
-            .map_err(|e| ConfigError::BackupFailed(format!("Failed to read backup file: {}", e)))?;
+        // (Do this before creating safety backup to ensure backup is readable
+        // and matches its checksum)
+        let backup_content = backup::read_backup(backup_path).map_err(|e| match e {
+            ConfigError::Io(e) => {
+                ConfigError::BackupFailed(format!("Failed to read backup file: {}", e))
+            }
+            other => other,
+        })?;
 
         // Step 3: Create safety backup of CURRENT state
         // This allows undoing the restore if needed
-        let _safety_backup = self.create_timestamped_backup()?;
+        self.ensure_pristine_backup()?;
+        let _safety_backup = self.create_backup()?;
 
         // Step 4: Atomically write backup content to config file
         let mut file = AtomicWriteFile::options()
@@ -446,6 +729,12 @@ impl ConfigManager {
         file.commit()
             .map_err(|e| ConfigError::WriteFailed(format!("Failed to commit restore: {}", e)))?;
 
+        let backup_name = backup_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.record_write(&format!("Restore backup {backup_name}"));
+
         Ok(())
     }
 
@@ -479,46 +768,351 @@ impl ConfigManager {
         let original_content = self.read_config()?;
 
         // Rebuild config with updated keybindings
-        let new_content = self.rebuild_config(&original_content, bindings)?;
+        let new_content = Self::rebuild_config(&original_content, bindings);
 
         // Write atomically via transaction (creates backup automatically)
-        let transaction = ConfigTransaction::begin(self)?;
-        transaction.commit(&new_content)?;
+        self.commit_with_hooks(&original_content, &new_content, None)
+    }
+
+    /// Shows what [`Self::write_bindings`] would change, without writing
+    ///
+    /// # Returns
+    /// Line diff of the config (empty when nothing would change)
+    ///
+    /// # Errors
+    /// Returns `ConfigError::Io` if the config cannot be read
+    pub fn preview_bindings(&self, bindings: &[Keybinding]) -> Result<ConfigDiff, ConfigError> {
+        let original_content = self.read_config()?;
+        let new_content = Self::rebuild_config(&original_content, bindings);
+        Ok(ConfigDiff::between(&original_content, &new_content))
+    }
+
+    /// Writes an edit of a single keybinding
+    ///
+    /// When `old` was read from a known line of the main config and that
+    /// line still holds it, only that line is replaced (see
+    /// [`writer::replace_bind_line`]). Otherwise this falls back to
+    /// [`Self::write_bindings`] with the full list.
+    ///
+    /// # Arguments
+    /// * `old` - The binding as it was loaded, with its location
+    /// * `new` - The edited binding
+    /// * `bindings` - The complete list of keybindings after the edit
+    ///
+    /// # Errors
+    /// As [`Self::write_bindings`]
+    pub fn write_binding_update(
+        &mut self,
+        old: &Keybinding,
+        new: &Keybinding,
+        bindings: &[Keybinding],
+    ) -> Result<(), ConfigError> {
+        let original_content = self.read_config()?;
+        let new_content = self.updated_content(&original_content, old, new, bindings);
+
+        self.commit_with_hooks(&original_content, &new_content, None)
+    }
+
+    /// Shows what [`Self::write_binding_update`] would change, without writing
+    ///
+    /// # Errors
+    /// Returns `ConfigError::Io` if the config cannot be read
+    pub fn preview_binding_update(
+        &self,
+        old: &Keybinding,
+        new: &Keybinding,
+        bindings: &[Keybinding],
+    ) -> Result<ConfigDiff, ConfigError> {
+        let original_content = self.read_config()?;
+        let new_content = self.updated_content(&original_content, old, new, bindings);
+        Ok(ConfigDiff::between(&original_content, &new_content))
+    }
+
+    /// Content after editing one binding: only its line when it was read
+    /// from the main config, otherwise a rebuild
+    fn updated_content(
+        &self,
+        original_content: &str,
+        old: &Keybinding,
+        new: &Keybinding,
+        bindings: &[Keybinding],
+    ) -> String {
+        let in_main_config = old.source_file.is_none()
+            && old
+                .location
+                .as_ref()
+                .is_some_and(|location| location.file == self.config_path);
+        match in_main_config
+            .then(|| writer::replace_bind_line(original_content, old, new))
+            .flatten()
+        {
+            Some(content) => content,
+            None => Self::rebuild_config(original_content, bindings),
+        }
+    }
+
+    /// Overrides a binding of a read-only sourced file in the main config
+    ///
+    /// An `unbind` of `old` and its replacement are appended to the main
+    /// config (see [`overrides`]); the sourced file is not touched. The
+    /// write goes through a transaction with a backup like any other.
+    ///
+    /// # Arguments
+    /// * `old` - The binding as read from the read-only file
+    /// * `new` - Its replacement (`None`: only unbind it)
+    /// * `bindings` - Every loaded binding, sourced files included
+    ///
+    /// # Errors
+    /// As [`overrides::override_content`]; otherwise as [`Self::write_bindings`]
+    pub fn write_source_override(
+        &mut self,
+        old: &Keybinding,
+        new: Option<&Keybinding>,
+        bindings: &[Keybinding],
+    ) -> Result<(), ConfigError> {
+        let original_content = self.read_config()?;
+        let new_content =
+            overrides::override_content(&original_content, &self.config_path, old, new, bindings)?;
+        let action = if new.is_some() { "Override" } else { "Unbind" };
+        let file_name = old
+            .source_file
+            .as_deref()
+            .and_then(Path::file_name)
+            .unwrap_or_default()
+            .to_string_lossy();
+
+        self.commit_with_hooks(
+            &original_content,
+            &new_content,
+            Some(format!("{} {} from {}", action, old.key_combo, file_name)),
+        )
+    }
+
+    /// Shows what [`Self::write_source_override`] would change, without writing
+    ///
+    /// # Errors
+    /// As [`Self::write_source_override`]
+    pub fn preview_source_override(
+        &self,
+        old: &Keybinding,
+        new: Option<&Keybinding>,
+        bindings: &[Keybinding],
+    ) -> Result<ConfigDiff, ConfigError> {
+        let original_content = self.read_config()?;
+        let new_content =
+            overrides::override_content(&original_content, &self.config_path, old, new, bindings)?;
+        Ok(ConfigDiff::between(&original_content, &new_content))
+    }
+
+    /// Returns whether the main config marks a sourced file as read-only
+    ///
+    /// `false` when the main config cannot be read.
+    pub fn is_read_only_source(&self, file: &Path) -> bool {
+        self.read_config()
+            .is_ok_and(|content| overrides::is_read_only_source(&content, &self.config_path, file))
+    }
+
+    /// Drops bindings from the main config for `check --fix`
+    ///
+    /// Only the dropped bindings' lines change (see
+    /// [`writer::drop_bind_lines`]); the write goes through a transaction
+    /// with a backup like any other.
+    ///
+    /// # Arguments
+    /// * `dropped` - Bindings to drop, each read from a line of the main config
+    /// * `mode` - Whether their lines are deleted or commented out
+    ///
+    /// # Errors
+    /// `ConfigError::ValidationFailed` when a binding comes from a sourced
+    /// file or its line no longer holds it (the config changed since it
+    /// was read); otherwise as [`Self::write_bindings`]
+    pub fn drop_bindings(
+        &mut self,
+        dropped: &[Keybinding],
+        mode: FixMode,
+    ) -> Result<(), ConfigError> {
+        let original_content = self.read_config()?;
+        let new_content = self.dropped_content(&original_content, dropped, mode)?;
+
+        self.commit_with_hooks(&original_content, &new_content, None)
+    }
+
+    /// Shows what [`Self::drop_bindings`] would change, without writing
+    ///
+    /// # Errors
+    /// As [`Self::drop_bindings`]
+    pub fn preview_drop_bindings(
+        &self,
+        dropped: &[Keybinding],
+        mode: FixMode,
+    ) -> Result<ConfigDiff, ConfigError> {
+        let original_content = self.read_config()?;
+        let new_content = self.dropped_content(&original_content, dropped, mode)?;
+        Ok(ConfigDiff::between(&original_content, &new_content))
+    }
+
+    /// Content after dropping bindings read from lines of the main config
+    fn dropped_content(
+        &self,
+        original_content: &str,
+        dropped: &[Keybinding],
+        mode: FixMode,
+    ) -> Result<String, ConfigError> {
+        if let Some(binding) = dropped.iter().find(|binding| {
+            binding.source_file.is_some()
+                || binding
+                    .location
+                    .as_ref()
+                    .is_none_or(|location| location.file != self.config_path)
+        }) {
+            return Err(ConfigError::ValidationFailed(format!(
+                "{} is not in the main config",
+                binding.key_combo
+            )));
+        }
+
+        writer::drop_bind_lines(original_content, dropped, mode).ok_or_else(|| {
+            ConfigError::ValidationFailed(
+                "The config changed since it was checked; run check again".to_string(),
+            )
+        })
+    }
+
+    /// Writes keybindings back grouped under comment headers
+    ///
+    /// Used to accept [`suggest_groups`](crate::core::grouping::suggest_groups)
+    /// output: each group is written as `# Title` followed by its binds.
+    /// Non-keybinding content is preserved as in [`Self::write_bindings`].
+    ///
+    /// # Arguments
+    /// * `groups` - Groups to write, in order
+    pub fn write_grouped_bindings(
+        &mut self,
+        groups: &[GroupSuggestion],
+    ) -> Result<(), ConfigError> {
+        let original_content = self.read_config()?;
+        let mut document = ConfigDocument::parse(&original_content);
+        document.replace_bind_block(&Self::render_grouped_bindings(
+            groups,
+            &document.variables(),
+        ));
+        let new_content = document.to_string();
+
+        self.commit_with_hooks(
+            &original_content,
+            &new_content,
+            Some("Group keybindings under comment headers".to_string()),
+        )
+    }
+
+    /// Sets a `binds` option in the config
+    ///
+    /// Only the option's line changes (see
+    /// [`set_binds_option`](crate::core::binds_options::set_binds_option)).
+    ///
+    /// # Arguments
+    /// * `name` - A known option from
+    ///   [`BINDS_OPTIONS`](crate::core::binds_options::BINDS_OPTIONS)
+    /// * `value` - New value, checked against the option's kind
+    ///
+    /// # Errors
+    /// `ConfigError::ValidationFailed` for an unknown option or a value of
+    /// the wrong kind
+    pub fn write_binds_option(&mut self, name: &str, value: &str) -> Result<(), ConfigError> {
+        binds_options::validate_binds_option(name, value).map_err(ConfigError::ValidationFailed)?;
+
+        let original_content = self.read_config()?;
+        let new_content = binds_options::set_binds_option(&original_content, name, value);
+
+        self.commit_with_hooks(
+            &original_content,
+            &new_content,
+            Some(format!("Set binds:{name} = {value}")),
+        )
+    }
+
+    /// Commits new content in a transaction, running hooks around it
+    ///
+    /// A failing `pre-write` hook aborts the write. A failing `post-write`
+    /// hook is reported but does not undo the write, which already happened.
+    /// Snapshots are only built when a hook is installed.
+    ///
+    /// `message` describes the write to the backup backend; `None` lets the
+    /// transaction describe the binding changes itself.
+    fn commit_with_hooks(
+        &self,
+        original: &str,
+        new_content: &str,
+        message: Option<String>,
+    ) -> Result<(), ConfigError> {
+        let hooks = self
+            .hooks_dir
+            .clone()
+            .map(Hooks::new)
+            .filter(Hooks::any_installed);
+
+        let mut snapshot = hooks.as_ref().map(|_| {
+            Snapshot::new(
+                HookEvent::PreWrite,
+                &self.config_path,
+                original,
+                new_content,
+            )
+        });
+
+        if let (Some(hooks), Some(snapshot)) = (&hooks, &snapshot) {
+            hooks.run(snapshot)?;
+        }
+
+        let mut transaction = ConfigTransaction::begin(self)?;
+        if let Some(message) = message {
+            transaction = transaction.with_message(message);
+        }
+        transaction.commit(new_content)?;
+
+        if let (Some(hooks), Some(snapshot)) = (&hooks, &mut snapshot) {
+            snapshot.event = HookEvent::PostWrite;
+            if let Err(e) = hooks.run(snapshot) {
+                eprintln!("⚠ Warning: {e}");
+            }
+        }
 
         Ok(())
     }
 
     /// Exports keybindings to a specified file path
     ///
-    /// Creates a new config file containing only keybinding (no preservation of other content)
+    /// Creates a new file containing only keybindings (no preservation of
+    /// other content): config lines, or a JSON, YAML or TOML document.
+    ///
+    /// # Arguments
+    /// * `export_path` - File to create or overwrite
+    /// * `bindings` - Keybindings to export
+    /// * `format` - File format (see [`ExportFormat::from_path`] to pick it
+    ///   by extension)
     pub fn export_to(
         &self,
         export_path: &Path,
         bindings: &[Keybinding],
+        format: ExportFormat,
     ) -> Result<(), ConfigError> {
-        let mut content = String::from("# Exported Hyprland Keybindings\n\n");
-
-        for binding in bindings {
-            content.push_str(&self.format_binding(binding));
-            content.push('\n');
-        }
-
-        fs::write(export_path, content)?;
+        fs::write(export_path, format.render(bindings)?)?;
 
         Ok(())
     }
 
     /// Rebuilds config file, replacing keybinding lines whilst preserving everything else
     ///
-    /// This is the "smart" part - we identify the keybinding section and replace only that,
-    /// keeping comments, blank lines, and other settings intact.
+    /// This is the "smart" part - only bind lines that actually changed are
+    /// rewritten (see [`ConfigDocument::set_bindings`]). Untouched bind lines
+    /// stay where they are, byte-for-byte, along with comments, blank lines,
+    /// and other settings.
+    ///
+    /// A config without any bind lines gets the bindings appended under a
+    /// `# Keybindings` header instead.
     ///
-    /// # Strategy
-    /// 1. Scan through original line by line
-    /// 2. When we hit the first keybinding line, mark that position
-    /// 3. Skip all subsequent keybinding lines
-    /// 4. At the end of the keybinding section, insert our new bindings
-    /// 5. Continue with the rest of the file
+    /// Bindings from `source`d files are left out; their files are not
+    /// touched.
     ///
     /// # Arguments
     /// * `original` - Original config file content
@@ -526,124 +1120,276 @@ impl ConfigManager {
     ///
     /// # Returns
     /// The rebuilt config as a string
-    fn rebuild_config(
-        &self,
-        original: &str,
-        bindings: &[Keybinding],
-    ) -> Result<String, ConfigError> {
-        let mut result = String::new();
-        let mut in_keybinding_section = false;
-        let mut keybindings_written = false;
-
-        for line in original.lines() {
-            let trimmed = line.trim();
-
-            // Check if this is a keybinding line
-            let is_keybinding = trimmed.starts_with("bind")
-                && !trimmed.starts_with("#")
-                && (trimmed.starts_with("bind =")
-                    || trimmed.starts_with("binde =")
-                    || trimmed.starts_with("bindl =")
-                    || trimmed.starts_with("bindm =")
-                    || trimmed.starts_with("bindr =")
-                    || trimmed.starts_with("bindel ="));
-
-            if is_keybinding {
-                // Keybinding section has been reached
-                if !in_keybinding_section {
-                    in_keybinding_section = true;
-                }
-
-                // Skip this line - new bindings will be written at the end of the section
-                continue;
-            }
+    ///
+    /// Nothing is read or written, so this is also the stage measured by
+    /// the `pipeline` benchmark.
+    pub fn rebuild_config(original: &str, bindings: &[Keybinding]) -> String {
+        let mut document = ConfigDocument::parse(original);
+        document.set_bindings(&main_config_bindings(bindings));
+        document.to_string()
+    }
 
-            // If we're in keybinding section but hit a non-keybinding line, write our bindings now
-            if in_keybinding_section && !keybindings_written {
-                for binding in bindings {
-                    result.push_str(&self.format_binding(binding));
-                    result.push('\n');
-                }
-                keybindings_written = true;
-                in_keybinding_section = false;
-            }
+    /// Renders keybindings as config lines, one per binding
+    ///
+    /// Global bindings come first, followed by the submap sections (see
+    /// [`Self::render_submaps`]) and the device sections (see
+    /// [`Self::render_devices`]).
+    fn render_bindings(bindings: &[Keybinding], variables: &VariableTable) -> String {
+        let (own, devices): (Vec<Keybinding>, Vec<Keybinding>) = bindings
+            .iter()
+            .cloned()
+            .partition(|binding| binding.device.is_none());
+        let mut block = Self::render_global_bindings(&own, variables);
+        block.push_str(&Self::render_submaps(&own, variables));
+        block.push_str(&Self::render_devices(&devices, variables));
+        block
+    }
 
-            // Preserve all non-keybinding lines
-            result.push_str(line);
-            result.push('\n');
-        }
+    /// Renders the bindings that are not in a submap
+    fn render_global_bindings(bindings: &[Keybinding], variables: &VariableTable) -> String {
+        bindings
+            .iter()
+            .filter(|binding| binding.submap.is_none())
+            .map(|binding| format_annotated_bind_line(binding, variables) + "\n")
+            .collect()
+    }
 
-        // If we never found a keybinding section, or we're still in it at EOF, write bindings now
-        if !keybindings_written {
-            result.push_str("\n# Keybindings\n");
-            for binding in bindings {
-                result.push_str(&self.format_binding(binding));
-                result.push('\n');
+    /// Renders one `submap = name` ... `submap = reset` section per submap
+    ///
+    /// Sections follow the order in which submaps are first used, and each
+    /// is preceded by a blank line.
+    fn render_submaps(bindings: &[Keybinding], variables: &VariableTable) -> String {
+        submap_names(bindings)
+            .into_iter()
+            .map(|name| {
+                let lines: String = bindings
+                    .iter()
+                    .filter(|binding| binding.submap.as_deref() == Some(name))
+                    .map(|binding| format_annotated_bind_line(binding, variables) + "\n")
+                    .collect();
+                format!("\nsubmap = {}\n{}submap = {}\n", name, lines, SUBMAP_RESET)
+            })
+            .collect()
+    }
+
+    /// Renders one `device { name = ... }` section per input device
+    ///
+    /// Sections follow the order in which devices are first used, and each
+    /// is preceded by a blank line. Inside, bindings are rendered as by
+    /// [`Self::render_bindings`], indented.
+    fn render_devices(bindings: &[Keybinding], variables: &VariableTable) -> String {
+        let mut names: Vec<&str> = Vec::new();
+        for name in bindings
+            .iter()
+            .filter_map(|binding| binding.device.as_deref())
+        {
+            if !names.contains(&name) {
+                names.push(name);
             }
         }
 
-        Ok(result)
+        names
+            .into_iter()
+            .map(|name| {
+                let own: Vec<Keybinding> = bindings
+                    .iter()
+                    .filter(|binding| binding.device.as_deref() == Some(name))
+                    .map(|binding| Keybinding {
+                        device: None,
+                        ..binding.clone()
+                    })
+                    .collect();
+                let lines: String = Self::render_bindings(&own, variables)
+                    .lines()
+                    .map(|line| match line {
+                        "" => "\n".to_string(),
+                        line => format!("    {}\n", line),
+                    })
+                    .collect();
+                format!("\ndevice {{\n    name = {}\n{}}}\n", name, lines)
+            })
+            .collect()
     }
 
-    /// Formats a keybinding into a config file line
+    /// Renders grouped keybindings with a comment header above each group
     ///
-    /// Example output: `bind = SUPER, K, exec, firefox`
-    ///
-    /// # Arguments
-    /// * `binding` - The keybinding to format
-    ///
-    /// # Returns
-    /// A formatted config line (without trailing newline)
-    fn format_binding(&self, binding: &Keybinding) -> String {
-        // Build a modifier string
-        let modifiers_str = if binding.key_combo.modifiers.is_empty() {
-            String::new()
-        } else {
-            binding
-                .key_combo
-                .modifiers
-                .iter()
-                .map(|m| match m {
-                    Super => "SUPER",
-                    Ctrl => "CTRL",
-                    Shift => "SHIFT",
-                    Alt => "ALT",
-                })
-                .collect::<Vec<_>>()
-                .join("_")
+    /// Groups are separated by a blank line. Submap bindings keep their
+    /// submap sections, written after the groups. Bindings of a device
+    /// section are left out: they stay in their section.
+    fn render_grouped_bindings(groups: &[GroupSuggestion], variables: &VariableTable) -> String {
+        let without_devices = |bindings: &[Keybinding]| -> Vec<Keybinding> {
+            main_config_bindings(bindings)
+                .into_iter()
+                .filter(|binding| binding.device.is_none())
+                .collect()
         };
+        let mut block = groups
+            .iter()
+            .filter_map(|group| {
+                let lines =
+                    Self::render_global_bindings(&without_devices(&group.bindings), variables);
+                (!lines.is_empty()).then(|| format!("# {}\n{}", group.group.title(), lines))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let all_bindings: Vec<Keybinding> = groups
+            .iter()
+            .flat_map(|group| group.bindings.iter().cloned())
+            .collect();
+        block.push_str(&Self::render_submaps(
+            &without_devices(&all_bindings),
+            variables,
+        ));
+        block
+    }
+}
 
-        // Build the parts that will be comma-separated
-        let mut parts = Vec::new();
+/// Returns the bindings declared in the main config itself
+///
+/// Bindings read from `source`d files stay in those files and are never
+/// written into the main config.
+fn main_config_bindings(bindings: &[Keybinding]) -> Vec<Keybinding> {
+    bindings
+        .iter()
+        .filter(|binding| binding.source_file.is_none())
+        .cloned()
+        .collect()
+}
 
-        // Add modifiers and key
-        if !modifiers_str.is_empty() {
-            parts.push(modifiers_str);
-        } else {
-            // No modifiers - just key
-            parts.push(String::new());
+/// Formats a keybinding into a config file line
+///
+/// Example output: `bind = SUPER, K, exec, firefox`
+///
+/// # Arguments
+/// * `binding` - The keybinding to format
+///
+/// # Returns
+/// A formatted config line (without trailing newline)
+pub fn format_bind_line(binding: &Keybinding) -> String {
+    format_bind_line_with_variables(binding, &VariableTable::default())
+}
+
+/// Formats a keybinding into a config file line, keeping variables symbolic
+///
+/// Values the config defines as variables are written as those variables,
+/// so rewritten lines read like the ones around them: with
+/// `$mainMod = SUPER`, `SUPER_SHIFT` is written as `$mainMod SHIFT`, and
+/// with `$terminal = kitty`, an `exec, kitty` binding is written as
+/// `exec, $terminal`. Purely numeric arguments are always written as is,
+/// as a variable with the same value is most likely a coincidence.
+///
+/// # Arguments
+/// * `binding` - The keybinding to format
+/// * `variables` - Variables defined by the config the line is written to
+///
+/// # Returns
+/// A formatted config line (without trailing newline)
+pub fn format_bind_line_with_variables(binding: &Keybinding, variables: &VariableTable) -> String {
+    // Build the parts that will be comma-separated
+    let mut parts = vec![
+        modifiers_with_variables(&binding.key_combo.canonical_modifiers(), variables),
+        binding.key_combo.config_key(),
+    ];
+
+    // `bindd` lines carry a description between the key and the dispatcher
+    if binding.bind_type.contains(BindFlag::Description) {
+        parts.push(binding.description.clone().unwrap_or_default());
+    }
+    parts.push(binding.dispatcher.clone());
+
+    // Add args if present
+    if let Some(args) = &binding.args {
+        let numeric = args.chars().all(|c| c.is_ascii_digit());
+        match variables.name_for(args) {
+            Some(name) if !numeric => parts.push(format!("${}", name)),
+            _ => parts.push(args.clone()),
         }
+    }
 
-        // Add key
-        parts.push(binding.key_combo.key.clone());
+    // Format: bind_type = comma,separated,parts
+    // Example: bind = SUPER, K, exec, firefox
+    format!("{} = {}", binding.bind_type, parts.join(", "))
+}
+
+/// Formats a keybinding with its category annotation above it
+///
+/// Bindings without a category give just the bind line, as
+/// [`format_bind_line_with_variables`] writes it.
+///
+/// # Returns
+/// The `# @category: name` line (if any) and the bind line, separated by a
+/// line break (without trailing newline)
+pub fn format_annotated_bind_line(binding: &Keybinding, variables: &VariableTable) -> String {
+    let line = format_bind_line_with_variables(binding, variables);
+    match &binding.category {
+        Some(category) => format!("{}\n{}", format_category_annotation(category), line),
+        None => line,
+    }
+}
 
-        // Add dispatcher
-        parts.push(binding.dispatcher.clone());
+/// Formats the `# @category: name` annotation of a category
+pub fn format_category_annotation(category: &str) -> String {
+    format!("# {} {}", CATEGORY_ANNOTATION, category)
+}
 
-        // Add args if present
-        if let Some(args) = &binding.args {
-            parts.push(args.clone());
+/// Writes a modifier set, using the variable that covers most of it
+///
+/// Only variables holding nothing but modifiers of the set qualify; the
+/// first one defined wins a tie. Without one, the modifiers are joined with
+/// `_` (`SUPER_SHIFT`), and an empty set gives an empty string.
+fn modifiers_with_variables(modifiers: &[Modifier], variables: &VariableTable) -> String {
+    let modifier_name = |modifier: &Modifier| match modifier {
+        Super => "SUPER",
+        Ctrl => "CTRL",
+        Shift => "SHIFT",
+        Alt => "ALT",
+    };
+
+    let mut best: Option<(&str, Vec<Modifier>)> = None;
+    for (name, value) in variables.iter() {
+        let tokens = value.split(['_', ' ']).filter(|t| !t.is_empty()).count();
+        let Ok(covered) = parse_modifiers(value) else {
+            continue;
+        };
+        // `SUPER_SHIFT CTRL` would not parse back, so underscored values
+        // only qualify when they cover the whole set
+        let fits = !covered.is_empty()
+            && covered.len() == tokens
+            && covered.iter().all(|m| modifiers.contains(m))
+            && (!value.contains('_') || covered.len() == modifiers.len());
+        if fits
+            && best
+                .as_ref()
+                .is_none_or(|(_, most)| covered.len() > most.len())
+        {
+            best = Some((name, covered));
         }
+    }
 
-        // Format: bind_type = comma,separated,parts
-        // Example: bind = SUPER, K, exec, firefox
-        format!("{} = {}", binding.bind_type, parts.join(", "))
+    match best {
+        Some((name, covered)) => std::iter::once(format!("${}", name))
+            .chain(
+                modifiers
+                    .iter()
+                    .filter(|m| !covered.contains(m))
+                    .map(|m| modifier_name(m).to_string()),
+            )
+            .collect::<Vec<_>>()
+            .join(" "),
+        None => modifiers
+            .iter()
+            .map(modifier_name)
+            .collect::<Vec<_>>()
+            .join("_"),
     }
 }
 
 #[cfg(unix)]
 fn current_uid() -> Option<u32> {
-    fs::metadata("/proc/self").ok().map(|metadata| metadata.uid())
+    fs::metadata("/proc/self")
+        .ok()
+        .map(|metadata| metadata.uid())
 }
 
 #[cfg(test)]
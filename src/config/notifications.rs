@@ -0,0 +1,235 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Desktop notifications for headless operations
+//!
+//! `hypr-keybind-manager watch` runs without a window, so it reports what
+//! it notices as desktop notifications:
+//!
+//! - **External change**: the config was changed by something else, such
+//!   as an editor or a dotfile sync
+//! - **Validation failure**: after such a change the config no longer
+//!   parses cleanly or fails the security checks
+//! - **Drift**: Hyprland's live bindings differ from the config
+//!
+//! Notifications are sent with libnotify's `notify-send` and carry two
+//! action buttons, **Open GUI** and **Ignore**. Each kind can be switched
+//! off in the preferences (see [`Settings`]). Versions of `notify-send`
+//! without action support (before libnotify 0.7.10) still show the
+//! notification, just without buttons.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::notifications::{notify_send_args, NotificationEvent};
+//!
+//! let event = NotificationEvent::ValidationFailed {
+//!     problems: vec!["line 12: unknown bind flag 'z'".to_string()],
+//! };
+//! let args = notify_send_args(&event, true);
+//! assert!(args.contains(&"--action=open-gui=Open GUI".to_string()));
+//! assert_eq!(args.last().map(String::as_str), Some("line 12: unknown bind flag 'z'"));
+//! ```
+
+use std::{
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use crate::config::{settings::Settings, ConfigError};
+
+/// Command used to show notifications
+pub const NOTIFY_SEND: &str = "notify-send";
+
+/// Application name shown on notifications
+pub const APP_NAME: &str = "Hyprland Keybinding Manager";
+
+/// Problems listed in a notification body before the rest are counted
+const MAX_BODY_LINES: usize = 5;
+
+/// Something worth telling the user about while no window is open
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// The config was changed outside this tool
+    ExternalChange {
+        /// The changed config
+        config_path: PathBuf,
+    },
+    /// The config no longer validates
+    ValidationFailed {
+        /// One line per problem
+        problems: Vec<String>,
+    },
+    /// Hyprland's live bindings differ from the config
+    Drift {
+        /// What differs, one line per difference
+        differences: Vec<String>,
+    },
+}
+
+impl NotificationEvent {
+    /// Notification title
+    pub fn summary(&self) -> String {
+        match self {
+            NotificationEvent::ExternalChange { .. } => "Keybinding config changed".to_string(),
+            NotificationEvent::ValidationFailed { problems } => format!(
+                "Keybinding config has {} problem{}",
+                problems.len(),
+                if problems.len() == 1 { "" } else { "s" }
+            ),
+            NotificationEvent::Drift { .. } => {
+                "Hyprland's keybindings differ from the config".to_string()
+            }
+        }
+    }
+
+    /// Notification text, listing at most a few problems or differences
+    pub fn body(&self) -> String {
+        let lines = match self {
+            NotificationEvent::ExternalChange { config_path } => {
+                return format!("{} was changed by another program.", config_path.display());
+            }
+            NotificationEvent::ValidationFailed { problems } => problems,
+            NotificationEvent::Drift { differences } => differences,
+        };
+
+        let mut body: Vec<String> = lines.iter().take(MAX_BODY_LINES).cloned().collect();
+        if lines.len() > MAX_BODY_LINES {
+            body.push(format!("... and {} more", lines.len() - MAX_BODY_LINES));
+        }
+        body.join("\n")
+    }
+
+    /// libnotify urgency: validation failures are critical
+    pub fn urgency(&self) -> &'static str {
+        match self {
+            NotificationEvent::ValidationFailed { .. } => "critical",
+            _ => "normal",
+        }
+    }
+
+    /// Returns whether the preferences allow this kind of notification
+    pub fn is_enabled(&self, settings: &Settings) -> bool {
+        match self {
+            NotificationEvent::ExternalChange { .. } => settings.notify_external_changes,
+            NotificationEvent::ValidationFailed { .. } => settings.notify_validation_failures,
+            NotificationEvent::Drift { .. } => settings.notify_drift,
+        }
+    }
+}
+
+/// Button the user clicked on a notification
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationAction {
+    /// Open the GUI on the config
+    OpenGui,
+    /// Dismiss the notification
+    Ignore,
+}
+
+impl NotificationAction {
+    /// Every action, in button order
+    pub const ALL: [NotificationAction; 2] =
+        [NotificationAction::OpenGui, NotificationAction::Ignore];
+
+    /// Identifier `notify-send` prints when the button is clicked
+    pub fn id(&self) -> &'static str {
+        match self {
+            NotificationAction::OpenGui => "open-gui",
+            NotificationAction::Ignore => "ignore",
+        }
+    }
+
+    /// Button label
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationAction::OpenGui => "Open GUI",
+            NotificationAction::Ignore => "Ignore",
+        }
+    }
+
+    /// Reads the identifier `notify-send --wait` printed
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.id() == id.trim())
+    }
+}
+
+/// Arguments for `notify-send`
+///
+/// # Arguments
+///
+/// * `event` - What to notify about
+/// * `with_actions` - Add the action buttons and wait for a click
+pub fn notify_send_args(event: &NotificationEvent, with_actions: bool) -> Vec<String> {
+    let mut args = vec![
+        format!("--app-name={}", APP_NAME),
+        format!("--urgency={}", event.urgency()),
+        "--icon=input-keyboard".to_string(),
+    ];
+    if with_actions {
+        args.extend(
+            NotificationAction::ALL
+                .iter()
+                .map(|action| format!("--action={}={}", action.id(), action.label())),
+        );
+        args.push("--wait".to_string());
+    }
+    args.push(event.summary());
+    args.push(event.body());
+    args
+}
+
+/// Shows a notification and waits for the user's choice
+///
+/// Blocks until the notification is clicked or closed, so callers that
+/// keep working meanwhile run this on another thread.
+///
+/// # Returns
+///
+/// * `Ok(Some(action))` - The user clicked a button
+/// * `Ok(None)` - Closed without a click, or buttons are not supported
+///
+/// # Errors
+///
+/// Returns `ConfigError::NotificationFailed` if `notify-send` is missing
+/// or cannot show the notification at all.
+pub fn send(event: &NotificationEvent) -> Result<Option<NotificationAction>, ConfigError> {
+    let run = |with_actions: bool| {
+        Command::new(NOTIFY_SEND)
+            .args(notify_send_args(event, with_actions))
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| ConfigError::NotificationFailed(format!("{}: {}", NOTIFY_SEND, e)))
+    };
+
+    let output = run(true)?;
+    if output.status.success() {
+        return Ok(NotificationAction::from_id(&String::from_utf8_lossy(
+            &output.stdout,
+        )));
+    }
+
+    // Older notify-send rejects --action and --wait; show it without buttons
+    let output = run(false)?;
+    if output.status.success() {
+        Ok(None)
+    } else {
+        Err(ConfigError::NotificationFailed(format!(
+            "{} exited with {}",
+            NOTIFY_SEND, output.status
+        )))
+    }
+}
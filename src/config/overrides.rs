@@ -0,0 +1,202 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Overriding bindings of read-only sourced files
+//!
+//! A `# @read-only` comment right above a `source` line of the main config
+//! marks the sourced file as read-only, such as defaults shipped by a
+//! distribution:
+//!
+//! ```text
+//! # @read-only
+//! source = /usr/share/hypr/defaults.conf
+//! ```
+//!
+//! Sourced files are never written. Editing a binding from a read-only file
+//! appends an override to the main config instead: an `unbind` of the
+//! original key combo, then the edited binding. Deleting one appends only
+//! the `unbind`. Both land below the `source` line, so they win over the
+//! file, which stays untouched. Bindings from other sourced files still
+//! have to be edited in their own file.
+//!
+//! Hyprland's `unbind` removes every binding of the key combo declared
+//! above it, in any submap. An override is therefore refused while another
+//! binding uses the same combo. Bindings of `device` sections are refused
+//! too, as the appended lines would apply to every device.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::overrides::override_content;
+//! use hypr_keybind_manager::core::{parser::parse_config_with_sources, Keybinding};
+//! use std::path::Path;
+//!
+//! let dir = tempfile::tempdir()?;
+//! std::fs::write(dir.path().join("defaults.conf"), "bind = SUPER, Q, killactive\n")?;
+//! let config_path = dir.path().join("hyprland.conf");
+//! let config = "# @read-only\nsource = defaults.conf\n";
+//!
+//! let bindings = parse_config_with_sources(config, &config_path)?;
+//! let edited = Keybinding::new(bindings[0].key_combo.clone(), "exec", Some("kitty"));
+//! let content = override_content(config, &config_path, &bindings[0], Some(&edited), &bindings)?;
+//!
+//! assert_eq!(
+//!     content,
+//!     "# @read-only\nsource = defaults.conf\n\nunbind = SUPER, Q\nbind = SUPER, Q, exec, kitty\n"
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{format_annotated_bind_line, modifiers_with_variables, ConfigError},
+    core::{
+        parser::{
+            collect_variables, parse_source_line, resolve_source_path, substitute_variables,
+            VariableTable,
+        },
+        types::{KeyCombo, Keybinding, SUBMAP_RESET},
+    },
+};
+
+/// Comment that marks the file sourced on the next line as read-only
+pub const READ_ONLY_ANNOTATION: &str = "@read-only";
+
+/// Returns the files the main config marks as read-only
+///
+/// Paths are resolved as the parser resolves them, so they compare equal
+/// to [`Keybinding::source_file`] of the bindings read from them.
+///
+/// # Arguments
+/// * `content` - Content of the main config
+/// * `config_path` - Path of the main config (for resolving its sources)
+pub fn read_only_sources(content: &str, config_path: &Path) -> Vec<PathBuf> {
+    let variables = collect_variables(content);
+    let mut marked = false;
+    let mut sources = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if marked {
+            if let Some(source) = parse_source_line(&substitute_variables(trimmed, &variables)) {
+                sources.push(resolve_source_path(&source, config_path));
+            }
+        }
+        marked = trimmed
+            .strip_prefix('#')
+            .is_some_and(|comment| comment.trim() == READ_ONLY_ANNOTATION);
+    }
+
+    sources
+}
+
+/// Returns whether the main config marks `file` as read-only
+///
+/// # Arguments
+/// * `content` - Content of the main config
+/// * `config_path` - Path of the main config
+/// * `file` - A sourced file, as recorded in [`Keybinding::source_file`]
+pub fn is_read_only_source(content: &str, config_path: &Path, file: &Path) -> bool {
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    read_only_sources(content, config_path)
+        .iter()
+        .any(|source| source == file || canonical(source) == canonical(file))
+}
+
+/// Content of the main config after overriding a binding of a read-only file
+///
+/// The `unbind` line and the replacement (inside its submap section, if
+/// it has one) are appended; nothing else changes.
+///
+/// # Arguments
+/// * `original` - Content of the main config
+/// * `config_path` - Path of the main config
+/// * `old` - The binding as read from the read-only file
+/// * `new` - Its replacement (`None`: only unbind it)
+/// * `bindings` - Every loaded binding, sourced files included
+///
+/// # Errors
+/// `ConfigError::ValidationFailed` when `old` is not from a file marked
+/// read-only, either binding is in a `device` section, or the `unbind`
+/// would also remove another binding
+pub fn override_content(
+    original: &str,
+    config_path: &Path,
+    old: &Keybinding,
+    new: Option<&Keybinding>,
+    bindings: &[Keybinding],
+) -> Result<String, ConfigError> {
+    let refuse = |reason: String| Err(ConfigError::ValidationFailed(reason));
+
+    match &old.source_file {
+        Some(file) if is_read_only_source(original, config_path, file) => {}
+        Some(file) => {
+            return refuse(format!(
+                "{} is not marked '# {}' in the main config",
+                file.display(),
+                READ_ONLY_ANNOTATION
+            ))
+        }
+        None => return refuse(format!("{} is not from a sourced file", old.key_combo)),
+    }
+    if old.device.is_some() || new.is_some_and(|new| new.device.is_some()) {
+        return refuse("Bindings of a device section cannot be overridden".to_string());
+    }
+    if let Some(other) = bindings
+        .iter()
+        .find(|binding| *binding != old && binding.key_combo == old.key_combo)
+    {
+        return refuse(format!(
+            "Unbinding {} would also remove '{}'",
+            old.key_combo, other
+        ));
+    }
+
+    let variables = VariableTable::from_config(original);
+    let mut lines = vec![format_unbind_line(&old.key_combo, &variables)];
+    if let Some(new) = new {
+        let line = format_annotated_bind_line(new, &variables);
+        match &new.submap {
+            Some(submap) => lines.extend([
+                format!("submap = {}", submap),
+                line,
+                format!("submap = {}", SUBMAP_RESET),
+            ]),
+            None => lines.push(line),
+        }
+    }
+
+    let mut content = original.to_string();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !content.is_empty() && !content.ends_with("\n\n") {
+        content.push('\n');
+    }
+    content.push_str(&lines.join("\n"));
+    content.push('\n');
+    Ok(content)
+}
+
+/// Formats the `unbind = MODS, KEY` line of a key combo
+fn format_unbind_line(key_combo: &KeyCombo, variables: &VariableTable) -> String {
+    format!(
+        "unbind = {}, {}",
+        modifiers_with_variables(&key_combo.canonical_modifiers(), variables),
+        key_combo.config_key()
+    )
+}
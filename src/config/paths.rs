@@ -0,0 +1,246 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime path resolution
+//!
+//! Every file the tool keeps for itself is located here, so native,
+//! flatpak and AppImage builds agree on where things live:
+//!
+//! | Files                          | Base directory     | Native fallback  |
+//! |--------------------------------|--------------------|------------------|
+//! | Settings, hooks, rules         | `$XDG_CONFIG_HOME` | `~/.config`      |
+//! | First-write state, audit log   | `$XDG_STATE_HOME`  | `~/.local/state` |
+//! | Caches                         | `$XDG_CACHE_HOME`  | `~/.cache`       |
+//!
+//! Each gets a `hypr-keybind-manager` subdirectory. Relative or empty XDG
+//! variables are ignored, as the XDG base directory specification asks.
+//!
+//! Inside a flatpak sandbox (`FLATPAK_ID` set) the fallbacks are the
+//! per-app directories under `~/.var/app/<app-id>/` instead, which is where
+//! flatpak points the XDG variables anyway. The Hyprland config itself stays
+//! on the host, so [`RuntimePaths::sandbox_access_warning`] explains how to
+//! grant access when the sandbox cannot see it.
+//!
+//! An AppImage (`APPDIR` set) uses the native layout, plus the icons bundled
+//! in the image.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::paths::RuntimePaths;
+//! use std::{ffi::OsString, path::PathBuf};
+//!
+//! let paths = RuntimePaths::from_vars(|name| match name {
+//!     "HOME" => Some(OsString::from("/home/user")),
+//!     "FLATPAK_ID" => Some(OsString::from("io.github.tidynest.HyprKeybindManager")),
+//!     _ => None,
+//! });
+//! assert_eq!(
+//!     paths.config_dir(),
+//!     Some(PathBuf::from(
+//!         "/home/user/.var/app/io.github.tidynest.HyprKeybindManager/config/hypr-keybind-manager"
+//!     ))
+//! );
+//! ```
+
+use std::{
+    env,
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+/// Subdirectory of each base directory holding this tool's files
+pub const APP_DIR_NAME: &str = "hypr-keybind-manager";
+
+/// How the running binary was packaged
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Packaging {
+    /// Installed on the host, e.g. from a distribution package or cargo
+    Native,
+    /// Running in a flatpak sandbox
+    Flatpak {
+        /// Application ID, as in `~/.var/app/<app_id>`
+        app_id: String,
+    },
+    /// Running from a mounted AppImage
+    AppImage {
+        /// Mount point of the image
+        appdir: PathBuf,
+    },
+}
+
+/// Base directories resolved from the environment
+#[derive(Clone, Debug)]
+pub struct RuntimePaths {
+    home: Option<PathBuf>,
+    config_home: Option<PathBuf>,
+    state_home: Option<PathBuf>,
+    cache_home: Option<PathBuf>,
+    packaging: Packaging,
+}
+
+impl RuntimePaths {
+    /// Resolves the directories from the process environment
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| env::var_os(name))
+    }
+
+    /// Resolves the directories from the given variable lookup
+    ///
+    /// # Arguments
+    ///
+    /// * `var` - Returns the value of an environment variable, if set
+    pub fn from_vars(var: impl Fn(&str) -> Option<OsString>) -> Self {
+        let home = var("HOME")
+            .filter(|home| !home.is_empty())
+            .map(PathBuf::from);
+
+        let packaging = if let Some(app_id) = var("FLATPAK_ID").filter(|id| !id.is_empty()) {
+            Packaging::Flatpak {
+                app_id: app_id.to_string_lossy().into_owned(),
+            }
+        } else if let Some(appdir) = var("APPDIR").filter(|dir| !dir.is_empty()) {
+            Packaging::AppImage {
+                appdir: PathBuf::from(appdir),
+            }
+        } else {
+            Packaging::Native
+        };
+
+        // Flatpak keeps per-app data under ~/.var/app/<id>; elsewhere the
+        // XDG defaults are relative to the home directory
+        let fallback_root = home.as_ref().map(|home| match &packaging {
+            Packaging::Flatpak { app_id } => home.join(".var/app").join(app_id),
+            _ => home.clone(),
+        });
+        let fallback = |native: &str, flatpak: &str| {
+            fallback_root.as_ref().map(|root| match packaging {
+                Packaging::Flatpak { .. } => root.join(flatpak),
+                _ => root.join(native),
+            })
+        };
+        let base = |name: &str, native: &str, flatpak: &str| {
+            var(name)
+                .map(PathBuf::from)
+                .filter(|dir| dir.is_absolute())
+                .or_else(|| fallback(native, flatpak))
+        };
+
+        Self {
+            config_home: base("XDG_CONFIG_HOME", ".config", "config"),
+            state_home: base("XDG_STATE_HOME", ".local/state", ".local/state"),
+            cache_home: base("XDG_CACHE_HOME", ".cache", "cache"),
+            home,
+            packaging,
+        }
+    }
+
+    /// How the running binary was packaged
+    pub fn packaging(&self) -> &Packaging {
+        &self.packaging
+    }
+
+    /// Directory for settings, hooks and danger rule updates
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - `$XDG_CONFIG_HOME` or its fallback based path
+    /// * `None` - Neither `XDG_CONFIG_HOME` nor `HOME` is set
+    pub fn config_dir(&self) -> Option<PathBuf> {
+        Some(self.config_home.as_ref()?.join(APP_DIR_NAME))
+    }
+
+    /// Directory for the first-write state and the audit log
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - `$XDG_STATE_HOME` or its fallback based path
+    /// * `None` - Neither `XDG_STATE_HOME` nor `HOME` is set
+    pub fn state_dir(&self) -> Option<PathBuf> {
+        Some(self.state_home.as_ref()?.join(APP_DIR_NAME))
+    }
+
+    /// Directory for files that can be regenerated at any time
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - `$XDG_CACHE_HOME` or its fallback based path
+    /// * `None` - Neither `XDG_CACHE_HOME` nor `HOME` is set
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        Some(self.cache_home.as_ref()?.join(APP_DIR_NAME))
+    }
+
+    /// Icon theme directory bundled with the binary, if GTK won't find it
+    ///
+    /// Only an AppImage needs this: flatpak already lists `/app/share` in
+    /// `XDG_DATA_DIRS`, and native installs use the system icon theme.
+    pub fn icon_dir(&self) -> Option<PathBuf> {
+        match &self.packaging {
+            Packaging::AppImage { appdir } => Some(appdir.join("usr/share/icons")),
+            _ => None,
+        }
+    }
+
+    /// Directory for the backups of a config
+    ///
+    /// Backups stay next to the config rather than in a base directory:
+    /// that keeps them on the same filesystem, and inside a sandbox the
+    /// grant that exposes the config exposes its backups too.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - The `backups` directory beside the config
+    /// * `None` - The config path has no parent directory
+    pub fn backup_dir(config_path: &Path) -> Option<PathBuf> {
+        Some(config_path.parent()?.join("backups"))
+    }
+
+    /// Explains how to grant access to a config the sandbox cannot see
+    ///
+    /// A flatpak sandbox only sees host files it was given access to. When
+    /// the config lies outside the app's own directory and cannot be
+    /// found, it is most likely hidden rather than missing.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(warning)` - The config is outside the sandbox and not
+    ///   visible, with the `flatpak override` command that grants access
+    /// * `None` - Not sandboxed, or the config is reachable
+    pub fn sandbox_access_warning(&self, config_path: &Path) -> Option<String> {
+        let Packaging::Flatpak { app_id } = &self.packaging else {
+            return None;
+        };
+        let home = self.home.as_ref()?;
+
+        if config_path.starts_with(home.join(".var/app").join(app_id)) || config_path.exists() {
+            return None;
+        }
+
+        let dir = config_path.parent().unwrap_or(config_path);
+        let filesystem = if let Ok(relative) = dir.strip_prefix(home.join(".config")) {
+            format!("xdg-config/{}", relative.display())
+        } else if let Ok(relative) = dir.strip_prefix(home) {
+            format!("~/{}", relative.display())
+        } else {
+            dir.display().to_string()
+        };
+
+        Some(format!(
+            "{} is outside the flatpak sandbox and not visible to it; \
+             grant access with: flatpak override --user --filesystem={} {}",
+            config_path.display(),
+            filesystem,
+            app_id
+        ))
+    }
+}
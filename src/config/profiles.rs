@@ -0,0 +1,189 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Named keybinding profiles
+//!
+//! A profile is a saved set of bindings (say `work`, `gaming` or `laptop`)
+//! that can be swapped into the config in one go. Each is a plain config
+//! file of bind lines at
+//! `$XDG_CONFIG_HOME/hypr-keybind-manager/profiles/<name>.conf` (falling
+//! back to `~/.config/hypr-keybind-manager/profiles/`, or the flatpak
+//! equivalent; see [`crate::config::paths`]), so it can be read, edited or
+//! even `source`d by hand.
+//!
+//! Profiles hold the main config's bindings only: bindings from `source`d
+//! files are left alone when a profile is applied, so they are not saved
+//! either. Variables are expanded on saving, as a profile defines none.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::{config::profiles::ProfileStore, core::parser::parse_config_file};
+//! use std::path::Path;
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! let store = ProfileStore::new(dir.path().to_path_buf());
+//!
+//! let bindings = parse_config_file("bind = SUPER, G, exec, steam", Path::new("")).unwrap();
+//! store.save("gaming", &bindings).unwrap();
+//!
+//! assert_eq!(store.list().unwrap(), vec!["gaming".to_string()]);
+//! assert_eq!(store.load("gaming").unwrap(), bindings);
+//! ```
+
+use std::{fs, path::PathBuf};
+
+use crate::config::{paths::RuntimePaths, ConfigError, ConfigManager};
+use crate::core::{
+    compare::{compare_bindings, ComparisonRow},
+    parser::{parse_config_file, VariableTable},
+    types::Keybinding,
+};
+
+/// File extension of profile files
+pub const PROFILE_EXTENSION: &str = "conf";
+
+const PROFILE_HEADER: &str = "# hypr-keybind-manager profile\n\n";
+
+/// Directory of named profiles
+#[derive(Clone, Debug)]
+pub struct ProfileStore {
+    dir: PathBuf,
+}
+
+impl ProfileStore {
+    /// Returns the default profiles directory
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - Path under [`RuntimePaths::config_dir`]
+    /// * `None` - Neither `XDG_CONFIG_HOME` nor `HOME` is set
+    pub fn default_dir() -> Option<PathBuf> {
+        Some(RuntimePaths::from_env().config_dir()?.join("profiles"))
+    }
+
+    /// Uses profiles from a directory (which need not exist)
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Returns the file a profile is stored in
+    ///
+    /// Names are limited to letters, digits, `-` and `_`, so a name can
+    /// never point outside the profiles directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidProfileName` for any other name.
+    pub fn path(&self, name: &str) -> Result<PathBuf, ConfigError> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(ConfigError::InvalidProfileName(name.to_string()));
+        }
+        Ok(self.dir.join(format!("{name}.{PROFILE_EXTENSION}")))
+    }
+
+    /// Lists the saved profiles, sorted by name
+    ///
+    /// A missing directory simply has no profiles. Files whose names are
+    /// not valid profile names are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::Io` if the directory exists but cannot be read.
+    pub fn list(&self) -> Result<Vec<String>, ConfigError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(ConfigError::Io(e)),
+        };
+
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == PROFILE_EXTENSION))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .filter(|name| self.path(name).is_ok())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Saves bindings as a profile, replacing any profile of that name
+    ///
+    /// Bindings from `source`d files are left out (see the module docs).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Profile name
+    /// * `bindings` - Bindings to save
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidProfileName` for a bad name, or
+    /// `ConfigError::Io` if the profile cannot be written.
+    pub fn save(&self, name: &str, bindings: &[Keybinding]) -> Result<(), ConfigError> {
+        let path = self.path(name)?;
+        fs::create_dir_all(&self.dir)?;
+
+        let own: Vec<Keybinding> = bindings
+            .iter()
+            .filter(|binding| binding.source_file.is_none())
+            .cloned()
+            .collect();
+        let mut content = String::from(PROFILE_HEADER);
+        content.push_str(&ConfigManager::render_bindings(
+            &own,
+            &VariableTable::default(),
+        ));
+
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Loads the bindings of a profile
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::ProfileNotFound` if there is no such profile,
+    /// or `ConfigError::ValidationFailed` if its file does not parse.
+    pub fn load(&self, name: &str) -> Result<Vec<Keybinding>, ConfigError> {
+        let path = self.path(name)?;
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(ConfigError::ProfileNotFound(name.to_string()))
+            }
+            Err(e) => return Err(ConfigError::Io(e)),
+        };
+        parse_config_file(&content, &path)
+            .map_err(|e| ConfigError::ValidationFailed(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Compares two profiles
+    ///
+    /// # Returns
+    ///
+    /// Rows aligned by key combo, `left` as the left side; see
+    /// [`compare_bindings`]
+    ///
+    /// # Errors
+    ///
+    /// As [`load`](Self::load), for either profile.
+    pub fn diff(&self, left: &str, right: &str) -> Result<Vec<ComparisonRow>, ConfigError> {
+        Ok(compare_bindings(&self.load(left)?, &self.load(right)?))
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resolving what `exec` bindings start
+//!
+//! A binding whose program was uninstalled still looks fine in the config
+//! but does nothing when pressed. This pass splits the command into shell
+//! words, looks through environment assignments, launch wrappers
+//! (`flatpak run`, `uwsm app`, `systemd-run --user`, `gtk-launch`) and
+//! `sh -c`, and checks that what is left can be started:
+//! - a program: an executable on `$PATH`, or at the given path
+//! - a Flatpak application ID: an installed app (user or system)
+//! - a desktop entry (`uwsm app -- foot.desktop`): a `.desktop` file in an
+//!   `applications` directory of the XDG data directories
+//!
+//! Commands computed at runtime (`$TERMINAL`, `$(...)`) are not judged.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::resolve::{command_target, CommandTarget};
+//!
+//! assert_eq!(
+//!     command_target("GDK_BACKEND=wayland flatpak run --branch=stable org.gimp.GIMP"),
+//!     Some(CommandTarget::Flatpak("org.gimp.GIMP".to_string()))
+//! );
+//! assert_eq!(
+//!     command_target("sh -c 'kitty --class scratch'"),
+//!     Some(CommandTarget::Program("kitty".to_string()))
+//! );
+//! ```
+
+use std::{
+    env, fmt,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use crate::config::danger::{is_env_assignment, patterns::build_safe_wrappers};
+use crate::core::{equivalence::canonical_args, Keybinding};
+
+/// Shells whose `-c` argument is itself a command line
+const SHELLS: &[&str] = &["sh", "bash", "dash", "zsh", "fish"];
+
+/// Launchers taking a desktop entry name
+const DESKTOP_LAUNCHERS: &[&str] = &["gtk-launch", "dex"];
+
+/// What an `exec` command starts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CommandTarget {
+    /// A program by name (looked up in `$PATH`) or path
+    Program(String),
+    /// A Flatpak application ID (`org.mozilla.firefox`)
+    Flatpak(String),
+    /// A desktop entry file name (`foot.desktop`)
+    DesktopEntry(String),
+}
+
+impl fmt::Display for CommandTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandTarget::Program(program) if program.contains('/') => {
+                write!(f, "'{}' does not exist or is not executable", program)
+            }
+            CommandTarget::Program(program) => write!(f, "'{}' is not in PATH", program),
+            CommandTarget::Flatpak(app_id) => {
+                write!(f, "Flatpak app '{}' is not installed", app_id)
+            }
+            CommandTarget::DesktopEntry(entry) => {
+                write!(f, "no desktop entry '{}' is installed", entry)
+            }
+        }
+    }
+}
+
+/// An `exec` binding whose target cannot be found
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingProgram {
+    /// The binding as written
+    pub binding: Keybinding,
+    /// What it starts
+    pub target: CommandTarget,
+}
+
+impl fmt::Display for MissingProgram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} runs a missing program: {}",
+            self.binding.key_combo, self.target
+        )
+    }
+}
+
+/// Where programs, desktop entries and Flatpak apps are looked up
+#[derive(Clone, Debug, Default)]
+pub struct CommandResolver {
+    /// Directories searched for bare program names
+    pub path_dirs: Vec<PathBuf>,
+    /// Directories holding `.desktop` files
+    pub application_dirs: Vec<PathBuf>,
+    /// Flatpak installations (each with an `app/` directory)
+    pub flatpak_dirs: Vec<PathBuf>,
+}
+
+impl CommandResolver {
+    /// Builds the lookup directories from the environment
+    ///
+    /// `$PATH`, then `$XDG_DATA_HOME` and `$XDG_DATA_DIRS` (with their
+    /// defaults) for desktop entries, plus the exports of the user and
+    /// system Flatpak installations.
+    pub fn from_env() -> Self {
+        let home = env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+        let data_home = env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .unwrap_or_else(|| home.join(".local/share"));
+        let data_dirs = env::var_os("XDG_DATA_DIRS")
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".into());
+        let flatpak_dirs = vec![data_home.join("flatpak"), PathBuf::from("/var/lib/flatpak")];
+
+        let application_dirs = std::iter::once(data_home)
+            .chain(env::split_paths(&data_dirs))
+            .chain(flatpak_dirs.iter().map(|dir| dir.join("exports/share")))
+            .map(|dir| dir.join("applications"))
+            .collect();
+
+        Self {
+            path_dirs: env::var_os("PATH")
+                .map(|path| env::split_paths(&path).collect())
+                .unwrap_or_default(),
+            application_dirs,
+            flatpak_dirs,
+        }
+    }
+
+    /// Whether a target can be started
+    pub fn resolves(&self, target: &CommandTarget) -> bool {
+        match target {
+            CommandTarget::Program(program) if program.contains('/') => {
+                is_executable(Path::new(shellexpand::tilde(program).as_ref()))
+            }
+            CommandTarget::Program(program) => self
+                .path_dirs
+                .iter()
+                .any(|dir| is_executable(&dir.join(program))),
+            CommandTarget::Flatpak(app_id) => self
+                .flatpak_dirs
+                .iter()
+                .any(|dir| dir.join("app").join(app_id).is_dir()),
+            CommandTarget::DesktopEntry(entry) if entry.contains('/') => {
+                Path::new(shellexpand::tilde(entry).as_ref()).is_file()
+            }
+            CommandTarget::DesktopEntry(entry) => self
+                .application_dirs
+                .iter()
+                .any(|dir| dir.join(entry).is_file()),
+        }
+    }
+}
+
+/// Finds what an `exec` command starts
+///
+/// # Arguments
+/// * `command` - The arguments of an `exec` binding
+///
+/// # Returns
+/// The target, or `None` when the command is empty or computed at runtime
+pub fn command_target(command: &str) -> Option<CommandTarget> {
+    let words = canonical_args(command);
+    let mut rest: &[String] = &words;
+
+    // `VAR=value` assignments come before the program
+    while rest.first().is_some_and(|word| is_env_assignment(word)) {
+        rest = &rest[1..];
+    }
+
+    let wrappers = build_safe_wrappers();
+    loop {
+        let program = rest.first()?;
+
+        if SHELLS.contains(&program.as_str()) && rest.get(1).is_some_and(|flag| flag == "-c") {
+            let inner = rest.get(2)?;
+            return command_target(unquote(inner));
+        }
+
+        if DESKTOP_LAUNCHERS.contains(&program.as_str()) {
+            let entry = rest.get(1..)?.iter().find(|word| !word.starts_with('-'))?;
+            return Some(desktop_entry(entry));
+        }
+
+        // Wrappers are looked through even without their required option:
+        // the wrapped program has to exist either way
+        let Some(wrapper) = wrappers.iter().find(|wrapper| {
+            rest.len() > wrapper.prefix.len()
+                && rest
+                    .iter()
+                    .zip(wrapper.prefix)
+                    .all(|(word, prefix)| word == prefix)
+        }) else {
+            break;
+        };
+        rest = &rest[wrapper.prefix.len()..];
+        while let Some(word) = rest.first() {
+            if word == "--" {
+                rest = &rest[1..];
+                break;
+            }
+            if !word.starts_with('-') {
+                break;
+            }
+            let takes_value = !word.contains('=') && wrapper.value_options.contains(&word.as_str());
+            rest = rest
+                .get(if takes_value { 2 } else { 1 }..)
+                .unwrap_or_default();
+        }
+
+        let wrapped = rest.first()?;
+        if wrapper.app_id {
+            return Some(CommandTarget::Flatpak(wrapped.clone()));
+        }
+        // `uwsm app` also starts desktop entries, optionally with an action
+        if wrapped.contains(".desktop") {
+            return Some(desktop_entry(wrapped));
+        }
+    }
+
+    let program = rest.first()?;
+    (!program.contains(['$', '`', '('])).then(|| CommandTarget::Program(program.clone()))
+}
+
+/// Finds the `exec` bindings whose target cannot be started
+///
+/// # Arguments
+/// * `bindings` - Bindings to check; other dispatchers are skipped
+/// * `resolver` - Where to look targets up
+///
+/// # Returns
+/// One entry per binding with a missing target, in binding order
+pub fn find_missing_programs(
+    bindings: &[Keybinding],
+    resolver: &CommandResolver,
+) -> Vec<MissingProgram> {
+    bindings
+        .iter()
+        .filter_map(|binding| missing_program(binding, resolver))
+        .collect()
+}
+
+/// The missing target of one binding, if it is an `exec` binding with one
+pub fn missing_program(binding: &Keybinding, resolver: &CommandResolver) -> Option<MissingProgram> {
+    if !matches!(binding.dispatcher.trim(), "exec" | "execr") {
+        return None;
+    }
+    let target = command_target(binding.args.as_deref()?)?;
+    (!resolver.resolves(&target)).then(|| MissingProgram {
+        binding: binding.clone(),
+        target,
+    })
+}
+
+/// `foot.desktop:new-window` and `foot` both name `foot.desktop`; paths
+/// are kept as they are
+fn desktop_entry(word: &str) -> CommandTarget {
+    let name = word.split(':').next().unwrap_or(word);
+    CommandTarget::DesktopEntry(if name.ends_with(".desktop") || name.contains('/') {
+        name.to_string()
+    } else {
+        format!("{}.desktop", name)
+    })
+}
+
+/// Strips the quotes `canonical_args` keeps around non-plain quoted parts
+fn unquote(word: &str) -> &str {
+    for quote in ['\'', '"'] {
+        if let Some(inner) = word
+            .strip_prefix(quote)
+            .and_then(|word| word.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    word
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
@@ -0,0 +1,331 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Application settings
+//!
+//! Display, checking, editing and backup settings chosen in the preferences dialog that survive
+//! between runs. They are stored as TOML at
+//! `$XDG_CONFIG_HOME/hypr-keybind-manager/settings.toml` (falling back to
+//! `~/.config/hypr-keybind-manager/settings.toml`, or the flatpak
+//! equivalent; see [`crate::config::paths`]):
+//!
+//! ```toml
+//! high_contrast = true
+//! reduce_motion = false
+//! color_scheme = "dark"
+//! danger_policy = "dangerous"
+//! default_bind_type = "bindl"
+//! confirm_delete = true
+//! reserved_combos = true
+//! review_changes = true
+//! live_apply = false
+//! strict = true
+//! notify_drift = false
+//! backup_mode = "git"
+//! backup_compression = "zstd"
+//!
+//! [backup_retention]
+//! max_count = 50
+//! max_age_days = 0
+//! max_size_mb = 0
+//! ```
+//!
+//! A backup limit of 0 means no limit.
+//!
+//! Missing settings keep their default, and unknown names and values of the
+//! wrong kind are ignored, so a hand-edited file never loses more than the
+//! settings it got wrong.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    config::{
+        backup::{BackupCompression, BackupMode, RetentionPolicy, BYTES_PER_MB},
+        danger::DangerLevel,
+        paths::RuntimePaths,
+        ConfigError,
+    },
+    core::types::BindType,
+};
+
+const SETTINGS_HEADER: &str = "# hypr-keybind-manager settings\n";
+
+/// Light or dark appearance of the GUI
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorScheme {
+    /// Whatever the desktop prefers
+    #[default]
+    System,
+    /// Light, whatever the desktop prefers
+    Light,
+    /// Dark, whatever the desktop prefers
+    Dark,
+}
+
+impl fmt::Display for ColorScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ColorScheme::System => "system",
+            ColorScheme::Light => "light",
+            ColorScheme::Dark => "dark",
+        })
+    }
+}
+
+impl FromStr for ColorScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "system" => Ok(ColorScheme::System),
+            "light" => Ok(ColorScheme::Light),
+            "dark" => Ok(ColorScheme::Dark),
+            other => Err(format!("Unknown colour scheme: {other}")),
+        }
+    }
+}
+
+/// Which commands the GUI refuses to save in a binding
+///
+/// Critical commands are always refused (see
+/// [`ConfigTransaction`](crate::config::ConfigTransaction)); the stricter
+/// policies refuse less dangerous ones too.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DangerPolicy {
+    /// Only Critical commands
+    #[default]
+    Critical,
+    /// Dangerous and Critical commands
+    Dangerous,
+    /// Every command that is not Safe
+    Suspicious,
+}
+
+impl DangerPolicy {
+    /// Returns whether a command of this danger level is refused
+    pub fn blocks(self, level: DangerLevel) -> bool {
+        let lowest_blocked = match self {
+            DangerPolicy::Critical => DangerLevel::Critical,
+            DangerPolicy::Dangerous => DangerLevel::Dangerous,
+            DangerPolicy::Suspicious => DangerLevel::Suspicious,
+        };
+        level >= lowest_blocked
+    }
+}
+
+impl fmt::Display for DangerPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DangerPolicy::Critical => "critical",
+            DangerPolicy::Dangerous => "dangerous",
+            DangerPolicy::Suspicious => "suspicious",
+        })
+    }
+}
+
+impl FromStr for DangerPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "critical" => Ok(DangerPolicy::Critical),
+            "dangerous" => Ok(DangerPolicy::Dangerous),
+            "suspicious" => Ok(DangerPolicy::Suspicious),
+            other => Err(format!("Unknown danger policy: {other}")),
+        }
+    }
+}
+
+/// Settings of the GUI and the CLI
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Stronger colours and borders for readability
+    pub high_contrast: bool,
+    /// No animated transitions, whatever the system setting
+    pub reduce_motion: bool,
+    /// Light or dark appearance
+    pub color_scheme: ColorScheme,
+    /// Which commands new and edited bindings may not run
+    pub danger_policy: DangerPolicy,
+    /// Bind type of bindings added in the GUI
+    pub default_bind_type: BindType,
+    /// Ask before deleting a binding
+    pub confirm_delete: bool,
+    /// Flag bindings on combos reserved by Hyprland or common tools
+    pub reserved_combos: bool,
+    /// Show a diff of each edit before it is saved
+    pub review_changes: bool,
+    /// Send each added, edited or deleted binding to the running Hyprland
+    pub live_apply: bool,
+    /// Refuse writes while the config has parse diagnostics, as `--strict`
+    pub strict: bool,
+    /// `watch`: notify when the config is changed by another program
+    pub notify_external_changes: bool,
+    /// `watch`: notify when a changed config fails validation
+    pub notify_validation_failures: bool,
+    /// `watch`: notify when Hyprland's live bindings differ from the config
+    pub notify_drift: bool,
+    /// How the config is backed up before each write
+    pub backup_mode: BackupMode,
+    /// How timestamped backups are compressed
+    pub backup_compression: BackupCompression,
+    /// Which timestamped backups are kept after each write
+    #[serde(with = "retention_limits")]
+    pub backup_retention: RetentionPolicy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            high_contrast: false,
+            reduce_motion: false,
+            color_scheme: ColorScheme::System,
+            danger_policy: DangerPolicy::Critical,
+            default_bind_type: BindType::Bind,
+            confirm_delete: true,
+            reserved_combos: true,
+            review_changes: true,
+            live_apply: false,
+            strict: false,
+            notify_external_changes: true,
+            notify_validation_failures: true,
+            notify_drift: true,
+            backup_mode: BackupMode::Timestamped,
+            backup_compression: BackupCompression::None,
+            backup_retention: RetentionPolicy::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Returns the default settings file location
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - Path under [`RuntimePaths::config_dir`]
+    /// * `None` - Neither `XDG_CONFIG_HOME` nor `HOME` is set
+    pub fn default_path() -> Option<PathBuf> {
+        Some(RuntimePaths::from_env().config_dir()?.join("settings.toml"))
+    }
+
+    /// Loads settings from disk
+    ///
+    /// A missing file gives the defaults, and a setting with a value of the
+    /// wrong kind keeps its default.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::Io` if the file exists but cannot be read, or
+    /// `ConfigError::InvalidSettings` if it is not valid TOML.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(ConfigError::Io(e)),
+        };
+
+        let invalid = |e: toml::de::Error| ConfigError::InvalidSettings(e.to_string());
+        let mut table: toml::Table = toml::from_str(&content).map_err(invalid)?;
+        table.retain(|name, value| {
+            let setting = toml::Table::from_iter([(name.to_string(), value.clone())]);
+            toml::Value::Table(setting).try_into::<Self>().is_ok()
+        });
+        toml::Value::Table(table).try_into().map_err(invalid)
+    }
+
+    /// Writes the settings to disk
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::Io` if the directory cannot be created or the
+    /// file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), ConfigError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content =
+            toml::to_string(self).map_err(|e| ConfigError::InvalidSettings(e.to_string()))?;
+        fs::write(path, format!("{SETTINGS_HEADER}{content}"))?;
+        Ok(())
+    }
+}
+
+/// Backup limits as stored: whole counts, days and megabytes, 0 for no limit
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct RetentionLimits {
+    max_count: u64,
+    max_age_days: u64,
+    max_size_mb: u64,
+}
+
+impl Default for RetentionLimits {
+    fn default() -> Self {
+        RetentionLimits::from(RetentionPolicy::default())
+    }
+}
+
+impl From<RetentionPolicy> for RetentionLimits {
+    fn from(policy: RetentionPolicy) -> Self {
+        Self {
+            max_count: policy.max_count.map_or(0, |count| count as u64),
+            max_age_days: policy.max_age_days.map_or(0, u64::from),
+            max_size_mb: policy
+                .max_total_bytes
+                .map_or(0, |bytes| bytes.div_ceil(BYTES_PER_MB)),
+        }
+    }
+}
+
+impl From<RetentionLimits> for RetentionPolicy {
+    fn from(limits: RetentionLimits) -> Self {
+        Self {
+            max_count: (limits.max_count > 0).then_some(limits.max_count as usize),
+            max_age_days: (limits.max_age_days > 0)
+                .then_some(limits.max_age_days.min(u32::MAX.into()) as u32),
+            max_total_bytes: (limits.max_size_mb > 0)
+                .then_some(limits.max_size_mb.saturating_mul(BYTES_PER_MB)),
+        }
+    }
+}
+
+/// Stores [`Settings::backup_retention`] as [`RetentionLimits`]
+mod retention_limits {
+    use super::{
+        Deserialize, Deserializer, RetentionLimits, RetentionPolicy, Serialize, Serializer,
+    };
+
+    pub fn serialize<S: Serializer>(
+        policy: &RetentionPolicy,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        RetentionLimits::from(*policy).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<RetentionPolicy, D::Error> {
+        RetentionLimits::deserialize(deserializer).map(RetentionPolicy::from)
+    }
+}
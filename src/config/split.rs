@@ -0,0 +1,466 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Moving the bindings of a config into a file of their own
+//!
+//! Splitting turns a single `hyprland.conf` into a main config that
+//! `source`s a dedicated bindings file, e.g. `binds.conf`:
+//! - Runs of bind, unbind and submap lines move, together with the
+//!   comments and blank lines between them. Blank lines around a run stay,
+//!   and so do comments that follow its last bind line
+//! - A `source = path` line takes the place of the first run
+//! - Variables the moved lines use move along when nothing else in the
+//!   main config uses them, and are copied otherwise, so the bindings file
+//!   also parses on its own and can be managed with `-c binds.conf`
+//!
+//! The split is planned in memory and verified before anything is written:
+//! the new layout must yield exactly the bindings of the old one, in the
+//! same order. [`ConfigManager::split_bindings`] then applies it as a
+//! transaction.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::config::split::plan_split;
+//! use std::path::Path;
+//!
+//! let config = "monitor = ,preferred,auto,1\n\n$mainMod = SUPER\nbind = $mainMod, Q, killactive\n";
+//! let plan = plan_split(
+//!     config,
+//!     Path::new("/etc/hypr/hyprland.conf"),
+//!     Path::new("/etc/hypr/binds.conf"),
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(plan.main_content, "monitor = ,preferred,auto,1\n\nsource = /etc/hypr/binds.conf\n");
+//! assert_eq!(plan.moved_variables, vec!["mainMod".to_string()]);
+//! ```
+
+use atomic_write_file::AtomicWriteFile;
+use std::{
+    collections::BTreeSet,
+    env,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config::{state::AppState, ConfigError, ConfigManager, ConfigTransaction},
+    core::{
+        parser::{
+            is_bind_line, parse_config_file, parse_config_with_sources, parse_submap_line,
+            parse_unbind_line, VariableTable,
+        },
+        Keybinding,
+    },
+};
+
+/// A split worked out in memory, ready to apply
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitPlan {
+    /// The new bindings file
+    pub binds_path: PathBuf,
+    /// Main config after the split
+    pub main_content: String,
+    /// Content of the new bindings file
+    pub binds_content: String,
+    /// Number of bind, unbind and submap lines moved
+    pub moved_lines: usize,
+    /// Variables whose definition moved to the bindings file
+    pub moved_variables: Vec<String>,
+    /// Variables defined in both files, as the main config still uses them
+    pub copied_variables: Vec<String>,
+}
+
+/// How a line takes part in a split
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LineRole {
+    /// Bind, unbind or submap line
+    Binding,
+    /// Comment line
+    Comment,
+    /// Blank line
+    Blank,
+    /// Anything else
+    Other,
+}
+
+impl LineRole {
+    fn of(line: &str) -> Self {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            LineRole::Blank
+        } else if trimmed.starts_with('#') {
+            LineRole::Comment
+        } else if is_bind_line(trimmed)
+            || parse_unbind_line(trimmed).is_some()
+            || parse_submap_line(trimmed).is_some()
+        {
+            LineRole::Binding
+        } else {
+            LineRole::Other
+        }
+    }
+}
+
+/// Works out how to move the bindings of a config into `binds_path`
+///
+/// # Arguments
+/// * `original` - Current content of the main config
+/// * `config_path` - Path of the main config (for resolving its sources)
+/// * `binds_path` - Path of the bindings file to create
+///
+/// # Errors
+/// * `ConfigError::SplitFailed` - The config has no bind lines, or the
+///   split would change the bindings (e.g. an unbind line that overrides a
+///   sourced file would end up above its `source` line)
+/// * `ConfigError::ValidationFailed` - The config does not parse
+pub fn plan_split(
+    original: &str,
+    config_path: &Path,
+    binds_path: &Path,
+) -> Result<SplitPlan, ConfigError> {
+    let lines: Vec<&str> = original.lines().collect();
+    let roles: Vec<LineRole> = lines.iter().map(|line| LineRole::of(line)).collect();
+
+    let runs = binding_runs(&roles);
+    if runs.is_empty() {
+        return Err(ConfigError::SplitFailed(
+            "the config has no bind lines to move".to_string(),
+        ));
+    }
+
+    let moved: BTreeSet<usize> = runs.iter().flat_map(|&(start, end)| start..=end).collect();
+    let moved_text: Vec<&str> = moved.iter().map(|&index| lines[index]).collect();
+    let kept_text: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .filter(|(index, line)| !moved.contains(index) && !line.trim().starts_with('$'))
+        .map(|(_, line)| *line)
+        .collect();
+
+    // Variables the moved lines need, including those their values use
+    let variables = VariableTable::from_config(original);
+    let mut needed: BTreeSet<&str> = BTreeSet::new();
+    let mut pending: Vec<&str> = moved_text.clone();
+    while let Some(text) = pending.pop() {
+        for (name, value) in variables.iter() {
+            if !needed.contains(name) && references(text, name) {
+                needed.insert(name);
+                pending.push(value);
+            }
+        }
+    }
+
+    let definition_lines = |name: &str| -> Vec<usize> {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| defines(line, name))
+            .map(|(index, _)| index)
+            .collect()
+    };
+
+    let mut moved_variables = Vec::new();
+    let mut copied_variables = Vec::new();
+    let mut header = Vec::new();
+    let mut dropped: BTreeSet<usize> = BTreeSet::new();
+    for (name, _) in variables.iter().filter(|(name, _)| needed.contains(name)) {
+        let definitions = definition_lines(name);
+        let still_used = kept_text.iter().any(|text| references(text, name))
+            || variables
+                .iter()
+                .any(|(other, value)| !needed.contains(other) && references(value, name));
+
+        if still_used || definitions.len() > 1 {
+            copied_variables.push(name.to_string());
+        } else {
+            moved_variables.push(name.to_string());
+            dropped.extend(definitions.iter().copied());
+        }
+        if let Some(&last) = definitions.last() {
+            header.push(lines[last].trim().to_string());
+        }
+    }
+
+    let main_content = main_after_split(&lines, &runs, &dropped, &source_reference(binds_path));
+    let binds_content = binds_file(&lines, &runs, &header, config_path);
+    let moved_lines = moved
+        .iter()
+        .filter(|&&index| roles[index] == LineRole::Binding)
+        .count();
+
+    let plan = SplitPlan {
+        binds_path: binds_path.to_path_buf(),
+        main_content,
+        binds_content,
+        moved_lines,
+        moved_variables,
+        copied_variables,
+    };
+    plan.verify(original, config_path)?;
+    Ok(plan)
+}
+
+impl SplitPlan {
+    /// Checks that the split keeps every binding as it was
+    ///
+    /// The main config is parsed with the bindings file spliced in where
+    /// its `source` line is, and must yield the same bindings, in the same
+    /// order, as the original. The bindings file must also parse on its own.
+    fn verify(&self, original: &str, config_path: &Path) -> Result<(), ConfigError> {
+        let parse = |content: &str| {
+            parse_config_with_sources(content, config_path)
+                .map_err(|e| ConfigError::ValidationFailed(e.to_string()))
+        };
+
+        let before = parse(original)?;
+        let source_line = format!("source = {}", source_reference(&self.binds_path));
+        let spliced = self
+            .main_content
+            .replacen(&source_line, &self.binds_content, 1);
+        if parse(&spliced)? != before {
+            return Err(ConfigError::SplitFailed(
+                "the bindings would change, e.g. because an unbind line would move above the source line it overrides"
+                    .to_string(),
+            ));
+        }
+
+        parse_config_file(&self.binds_content, &self.binds_path).map_err(|e| {
+            ConfigError::SplitFailed(format!("the bindings file would not parse: {}", e))
+        })?;
+        Ok(())
+    }
+}
+
+impl ConfigManager {
+    /// Moves the bindings of the config into a new file it sources
+    ///
+    /// Plans the split with [`plan_split`], then applies it transactionally:
+    /// the main config is backed up, the bindings file is created, and the
+    /// main config is rewritten. If rewriting fails, or the files on disk do
+    /// not yield the original bindings afterwards, the main config is
+    /// restored and the bindings file removed. The new file is recorded as
+    /// managed, so its first write does not keep a "pristine" copy of a
+    /// file the tool created itself.
+    ///
+    /// Write hooks are not run: the bindings only change files.
+    ///
+    /// # Arguments
+    /// * `binds_path` - Path of the bindings file to create
+    ///
+    /// # Returns
+    /// The plan that was applied
+    ///
+    /// # Errors
+    /// * `ConfigError::SplitFailed` - `binds_path` already exists, or see
+    ///   [`plan_split`]
+    /// * Any error from backing up or writing the files
+    pub fn split_bindings(&self, binds_path: &Path) -> Result<SplitPlan, ConfigError> {
+        if binds_path.exists() {
+            return Err(ConfigError::SplitFailed(format!(
+                "{} already exists",
+                binds_path.display()
+            )));
+        }
+
+        let original = self.read_config()?;
+        let plan = plan_split(&original, &self.config_path, binds_path)?;
+        let before = parse_config_with_sources(&original, &self.config_path)
+            .map_err(|e| ConfigError::ValidationFailed(e.to_string()))?;
+
+        let binds_name = binds_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let transaction = ConfigTransaction::begin(self)?
+            .with_message(format!("Move keybindings into {binds_name}"));
+        write_new_file(binds_path, &plan.binds_content)?;
+        if let Err(e) = transaction.commit(&plan.main_content) {
+            std::fs::remove_file(binds_path)?;
+            return Err(e);
+        }
+
+        let after = parse_config_with_sources(&self.read_config()?, &self.config_path)
+            .map_err(|e| ConfigError::ValidationFailed(e.to_string()));
+        // Only where the bindings come from may differ
+        let unchanged = after.is_ok_and(|after| {
+            after
+                .into_iter()
+                .map(|binding| Keybinding {
+                    source_file: None,
+                    ..binding
+                })
+                .eq(before.iter().cloned())
+        });
+        if !unchanged {
+            // Put the original back; this keeps a backup of the failed split
+            ConfigTransaction::begin(self)?
+                .with_message("Undo failed split")
+                .commit(&original)?;
+            std::fs::remove_file(binds_path)?;
+            return Err(ConfigError::SplitFailed(
+                "the bindings on disk differ from the original; nothing was changed".to_string(),
+            ));
+        }
+
+        if let Some(state_path) = &self.state_path {
+            let mut state = AppState::load(state_path.clone())?;
+            state.mark_managed(binds_path);
+            state.save()?;
+        }
+
+        Ok(plan)
+    }
+}
+
+/// Finds the runs of lines to move, as inclusive line index ranges
+///
+/// A run is a stretch of bind, comment and blank lines holding at least one
+/// bind line, without the blank lines at its start and without whatever
+/// follows its last bind line.
+fn binding_runs(roles: &[LineRole]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut index = 0;
+
+    while index < roles.len() {
+        if roles[index] == LineRole::Other {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < roles.len() && roles[index] != LineRole::Other {
+            index += 1;
+        }
+        let stretch = start..index;
+
+        let first = stretch.clone().find(|&i| roles[i] != LineRole::Blank);
+        let last = stretch.rev().find(|&i| roles[i] == LineRole::Binding);
+        if let (Some(first), Some(last)) = (first, last) {
+            runs.push((first, last));
+        }
+    }
+
+    runs
+}
+
+/// Main config content with the runs and moved definitions taken out
+///
+/// The first run becomes the `source` line. Where taking a run out would
+/// leave two blank lines in a row, one of them goes too.
+fn main_after_split(
+    lines: &[&str],
+    runs: &[(usize, usize)],
+    dropped: &BTreeSet<usize>,
+    source: &str,
+) -> String {
+    let mut kept: Vec<String> = Vec::new();
+    let mut index = 0;
+
+    while index < lines.len() {
+        if let Some(&(start, end)) = runs.iter().find(|(start, _)| *start == index) {
+            if start == runs[0].0 {
+                kept.push(format!("source = {}", source));
+            }
+            index = end + 1;
+
+            let blank_before = kept.last().is_none_or(|line| line.trim().is_empty());
+            if blank_before && lines.get(index).is_some_and(|line| line.trim().is_empty()) {
+                index += 1;
+            }
+            continue;
+        }
+
+        if !dropped.contains(&index) {
+            kept.push(lines[index].to_string());
+        }
+        index += 1;
+    }
+
+    let mut content = kept.join("\n");
+    content.push('\n');
+    content
+}
+
+/// Content of the bindings file: variable definitions, then the runs
+fn binds_file(
+    lines: &[&str],
+    runs: &[(usize, usize)],
+    header: &[String],
+    config_path: &Path,
+) -> String {
+    let origin = config_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| config_path.display().to_string());
+
+    let mut content = format!("# Keybindings, moved here from {}\n", origin);
+    if !header.is_empty() {
+        content.push('\n');
+        for definition in header {
+            content.push_str(definition);
+            content.push('\n');
+        }
+    }
+
+    for &(start, end) in runs {
+        content.push('\n');
+        for line in &lines[start..=end] {
+            content.push_str(line);
+            content.push('\n');
+        }
+    }
+
+    content
+}
+
+/// Returns whether `text` uses `$name` (and not a longer name)
+fn references(text: &str, name: &str) -> bool {
+    let pattern = format!("${}", name);
+    text.match_indices(&pattern).any(|(at, _)| {
+        !text[at + pattern.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// Returns whether `line` is a `$name = value` definition
+fn defines(line: &str, name: &str) -> bool {
+    line.trim()
+        .strip_prefix('$')
+        .and_then(|definition| definition.split_once('='))
+        .is_some_and(|(defined, _)| defined.trim() == name)
+}
+
+/// The path a `source` line should use: `~/...` under the home directory
+fn source_reference(path: &Path) -> String {
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .filter(|home| !home.as_os_str().is_empty())
+        .and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf))
+        .map(|relative| format!("~/{}", relative.display()))
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Creates a file that must not exist yet, atomically
+fn write_new_file(path: &Path, content: &str) -> Result<(), ConfigError> {
+    let mut file = AtomicWriteFile::options().open(path).map_err(|e| {
+        ConfigError::WriteFailed(format!("Failed to create {}: {}", path.display(), e))
+    })?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| ConfigError::WriteFailed(format!("Failed to write content: {}", e)))?;
+    file.commit()
+        .map_err(|e| ConfigError::WriteFailed(format!("Failed to commit atomic write: {}", e)))
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistent application state
+//!
+//! Records facts about the user's configs that must survive between runs
+//! and must not live inside the config directory itself. Currently this is
+//! the set of config files the tool has written to at least once, which
+//! decides whether a write is the "first-ever" write that needs a pristine
+//! backup.
+//!
+//! The state file is plain text with one canonical config path per line,
+//! stored at `$XDG_STATE_HOME/hypr-keybind-manager/state` (falling back to
+//! `~/.local/state/hypr-keybind-manager/state`).
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::config::{paths::RuntimePaths, ConfigError};
+
+const STATE_HEADER: &str = "# hypr-keybind-manager state: configs managed by this tool\n";
+
+/// Application state persisted across runs
+#[derive(Debug)]
+pub struct AppState {
+    /// Location of the state file on disk
+    path: PathBuf,
+    /// Canonical paths of configs that have been written at least once
+    managed_configs: BTreeSet<PathBuf>,
+}
+
+impl AppState {
+    /// Returns the default state file location
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - Path under [`RuntimePaths::state_dir`]
+    /// * `None` - Neither `XDG_STATE_HOME` nor `HOME` is set
+    pub fn default_path() -> Option<PathBuf> {
+        Some(RuntimePaths::from_env().state_dir()?.join("state"))
+    }
+
+    /// Loads state from disk
+    ///
+    /// A missing state file is not an error: it simply means the tool has
+    /// never written to any config yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Location of the state file
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::Io` if the file exists but cannot be read.
+    pub fn load(path: PathBuf) -> Result<Self, ConfigError> {
+        let managed_configs = match fs::read_to_string(&path) {
+            Ok(content) => content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(PathBuf::from)
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeSet::new(),
+            Err(e) => return Err(ConfigError::Io(e)),
+        };
+
+        Ok(Self {
+            path,
+            managed_configs,
+        })
+    }
+
+    /// Returns whether the tool has written to this config before
+    pub fn is_managed(&self, config_path: &Path) -> bool {
+        self.managed_configs.contains(&canonical(config_path))
+    }
+
+    /// Records that the tool has written to this config
+    pub fn mark_managed(&mut self, config_path: &Path) {
+        self.managed_configs.insert(canonical(config_path));
+    }
+
+    /// Writes the state back to disk
+    ///
+    /// Entries for configs that no longer exist are dropped so the file does
+    /// not grow without bound as configs are moved or deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::Io` if the state directory cannot be created or
+    /// the file cannot be written.
+    pub fn save(&mut self) -> Result<(), ConfigError> {
+        self.managed_configs.retain(|path| path.exists());
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut content = String::from(STATE_HEADER);
+        for path in &self.managed_configs {
+            content.push_str(&path.to_string_lossy());
+            content.push('\n');
+        }
+
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+/// Resolves symlinks so the same config is recognised via any path
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,70 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the audit log
+
+use std::fs;
+
+use chrono::{Duration, Local};
+use tempfile::TempDir;
+
+use crate::config::audit::{AuditLog, HealthTrend};
+
+#[test]
+fn test_health_records_only_changes_per_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let log = AuditLog::new(temp_dir.path().join("state/audit.log"));
+    let config = temp_dir.path().join("hyprland.conf");
+    let other = temp_dir.path().join("other.conf");
+    fs::write(&config, "").unwrap();
+    fs::write(&other, "").unwrap();
+
+    let now = Local::now();
+    assert!(log.record_health(&config, 70, now).unwrap());
+    assert!(!log.record_health(&config, 70, now).unwrap());
+    assert!(log.record_health(&other, 50, now).unwrap());
+    assert!(log.record_health(&config, 85, now).unwrap());
+
+    let scores: Vec<u8> = log
+        .health_history(&config)
+        .unwrap()
+        .iter()
+        .map(|record| record.score)
+        .collect();
+    assert_eq!(scores, vec![70, 85]);
+    assert_eq!(log.health_history(&other).unwrap().len(), 1);
+}
+
+#[test]
+fn test_trend_compares_with_last_different_score() {
+    let temp_dir = TempDir::new().unwrap();
+    let log = AuditLog::new(temp_dir.path().join("audit.log"));
+    let config = temp_dir.path().join("hyprland.conf");
+
+    // Missing log means no history
+    assert!(log.health_history(&config).unwrap().is_empty());
+
+    let earlier = Local::now() - Duration::days(3);
+    log.record_health(&config, 60, earlier).unwrap();
+    log.record_health(&config, 75, Local::now()).unwrap();
+    let history = log.health_history(&config).unwrap();
+
+    // Reopening at the same score still reports the last improvement
+    let trend = HealthTrend::from_history(&history, 75).unwrap();
+    assert_eq!(trend.change, 15);
+    assert_eq!(trend.since.timestamp(), earlier.timestamp());
+
+    assert_eq!(HealthTrend::from_history(&history, 60).unwrap().change, -15);
+    assert!(HealthTrend::from_history(&history[..1], 60).is_none());
+}
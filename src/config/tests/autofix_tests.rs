@@ -0,0 +1,125 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path};
+
+use tempfile::TempDir;
+
+use crate::{
+    config::{
+        autofix::{bindings_to_drop, is_fixable, skip_reason, FixMode, FixStrategy},
+        writer::drop_bind_lines,
+        ConfigError, ConfigManager,
+    },
+    core::{
+        conflict::{Conflict, ConflictDetector},
+        parser::parse_config_file,
+    },
+};
+
+const DUPLICATES: &str = "$mainMod = SUPER\n\
+                          bind = $mainMod, K, exec, kitty\n\
+                          \x20   bind = $mainMod, K, exec, alacritty # newer\n\
+                          bind = $mainMod, Q, killactive\n\
+                          bind = $mainMod, K, exec, foot\n";
+
+fn conflicts(content: &str, path: &Path) -> Vec<Conflict> {
+    let mut detector = ConflictDetector::new();
+    for binding in parse_config_file(content, path).unwrap() {
+        detector.add_binding(binding);
+    }
+    detector.find_conflicts()
+}
+
+#[test]
+fn test_strategies_pick_the_first_or_last_binding() {
+    let conflict = &conflicts(DUPLICATES, Path::new("hyprland.conf"))[0];
+
+    assert_eq!("keep-first".parse(), Ok(FixStrategy::KeepFirst));
+    assert_eq!("keep-last".parse(), Ok(FixStrategy::KeepLast));
+    assert!("keep-middle".parse::<FixStrategy>().is_err());
+
+    let keep = FixStrategy::KeepLast.keep_index(conflict);
+    let dropped: Vec<_> = bindings_to_drop(conflict, keep)
+        .into_iter()
+        .map(|binding| binding.args.unwrap())
+        .collect();
+    assert_eq!(dropped, ["kitty", "alacritty # newer"]);
+}
+
+#[test]
+fn test_press_and_release_and_sourced_conflicts_are_skipped() {
+    let press_and_release = "bind = SUPER, K, exec, kitty\n\
+                             bindr = SUPER, K, exec, foot\n";
+    let conflict = &conflicts(press_and_release, Path::new("hyprland.conf"))[0];
+    assert!(!is_fixable(conflict));
+
+    let mut sourced = conflicts(DUPLICATES, Path::new("hyprland.conf")).remove(0);
+    assert!(is_fixable(&sourced));
+    sourced.conflicting_bindings[1].source_file = Some("keys.conf".into());
+    assert_eq!(
+        skip_reason(&sourced),
+        Some("a binding is in a sourced file, which is never edited")
+    );
+}
+
+#[test]
+fn test_dropped_lines_are_removed_or_commented_out_in_place() {
+    let conflict = &conflicts(DUPLICATES, Path::new("hyprland.conf"))[0];
+    let dropped = bindings_to_drop(conflict, 0);
+
+    assert_eq!(
+        drop_bind_lines(DUPLICATES, &dropped, FixMode::Remove).unwrap(),
+        "$mainMod = SUPER\n\
+         bind = $mainMod, K, exec, kitty\n\
+         bind = $mainMod, Q, killactive\n"
+    );
+    assert_eq!(
+        drop_bind_lines(DUPLICATES, &dropped, FixMode::CommentOut).unwrap(),
+        "$mainMod = SUPER\n\
+         bind = $mainMod, K, exec, kitty\n\
+         \x20   # bind = $mainMod, K, exec, alacritty # newer\n\
+         bind = $mainMod, Q, killactive\n\
+         # bind = $mainMod, K, exec, foot\n"
+    );
+
+    // A line that no longer holds the binding is not touched
+    let mut stale = dropped.clone();
+    stale[0].location.as_mut().unwrap().line += 1;
+    assert_eq!(drop_bind_lines(DUPLICATES, &stale, FixMode::Remove), None);
+}
+
+#[test]
+fn test_drop_bindings_writes_through_a_transaction_with_backup() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, DUPLICATES).unwrap();
+    let mut manager = ConfigManager::new(config_path.clone())
+        .unwrap()
+        .with_hooks_dir(temp_dir.path().join("hooks"));
+
+    let conflict = &conflicts(DUPLICATES, &config_path)[0];
+    manager
+        .drop_bindings(&bindings_to_drop(conflict, 0), FixMode::Remove)
+        .unwrap();
+
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(conflicts(&content, &config_path).is_empty());
+    assert!(!manager.list_backups().unwrap().is_empty());
+
+    // The dropped lines are gone now, so a second attempt is refused
+    let result = manager.drop_bindings(&bindings_to_drop(conflict, 0), FixMode::Remove);
+    assert!(matches!(result, Err(ConfigError::ValidationFailed(_))));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), content);
+}
@@ -0,0 +1,471 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tempfile::TempDir;
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::config::{
+    backup::{
+        checksum_path, dated_backups, describe_change, read_backup, BackupBackend,
+        BackupCompression, BackupInfo, BackupMode, RetentionPolicy, TimestampedBackups,
+    },
+    ConfigError, ConfigManager,
+};
+use crate::core::parser::parse_config_file;
+
+const CONFIG: &str = "$mod = SUPER\nbind = $mod, Q, killactive\n";
+
+fn git_manager() -> (TempDir, PathBuf, ConfigManager) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, CONFIG).unwrap();
+
+    let manager = ConfigManager::new(config_path.clone())
+        .unwrap()
+        .with_state_path(temp_dir.path().join("state"))
+        .with_hooks_dir(temp_dir.path().join("hooks"))
+        .with_backup_mode(BackupMode::Git);
+    (temp_dir, config_path, manager)
+}
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn commit_subjects(dir: &Path) -> Vec<String> {
+    git(dir, &["log", "--format=%s"])
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn add_binding(manager: &mut ConfigManager, line: &str) {
+    let content = format!("{}{line}\n", manager.read_config().unwrap());
+    let bindings = parse_config_file(&content, manager.config_path()).unwrap();
+    manager.write_bindings(&bindings).unwrap();
+}
+
+#[test]
+fn test_git_backups_commit_each_write_with_a_description() {
+    let (temp_dir, _, mut manager) = git_manager();
+
+    add_binding(&mut manager, "bind = $mod, B, exec, firefox");
+
+    assert_eq!(
+        commit_subjects(temp_dir.path()),
+        ["Add SUPER+B", "Track hyprland.conf"]
+    );
+    assert_eq!(
+        git(
+            temp_dir.path(),
+            &["status", "--porcelain", "--", "hyprland.conf"]
+        ),
+        "",
+        "the write itself is committed"
+    );
+    assert!(
+        manager.list_backups().unwrap().is_empty(),
+        "no timestamped copies in git mode"
+    );
+}
+
+#[test]
+fn test_git_backups_commit_outside_edits_before_a_write() {
+    let (temp_dir, config_path, mut manager) = git_manager();
+    add_binding(&mut manager, "bind = $mod, B, exec, firefox");
+
+    let edited = format!("{}# my note\n", fs::read_to_string(&config_path).unwrap());
+    fs::write(&config_path, edited).unwrap();
+    add_binding(&mut manager, "bind = $mod, T, exec, kitty");
+
+    assert_eq!(
+        commit_subjects(temp_dir.path()),
+        [
+            "Add SUPER+T",
+            "Save changes to hyprland.conf made outside hypr-keybind-manager",
+            "Add SUPER+B",
+            "Track hyprland.conf",
+        ]
+    );
+}
+
+#[test]
+fn test_backup_now_keeps_hand_edits() {
+    let (temp_dir, config_path, mut manager) = git_manager();
+    assert_eq!(manager.backup_now().unwrap(), None);
+    fs::write(&config_path, format!("{CONFIG}# my note\n")).unwrap();
+    manager.backup_now().unwrap();
+    manager.backup_now().unwrap();
+
+    assert_eq!(
+        commit_subjects(temp_dir.path()),
+        [
+            "Save changes to hyprland.conf made outside hypr-keybind-manager",
+            "Track hyprland.conf",
+        ]
+    );
+
+    manager.set_backup_mode(BackupMode::Timestamped);
+    let backup = manager.backup_now().unwrap().unwrap();
+    assert_eq!(
+        read_backup(&backup).unwrap(),
+        format!("{CONFIG}# my note\n")
+    );
+    assert_eq!(manager.backup_now().unwrap().unwrap(), backup);
+}
+
+#[test]
+fn test_git_backups_leave_other_changes_in_an_existing_repository_alone() {
+    let (temp_dir, _, mut manager) = git_manager();
+    git(temp_dir.path(), &["init", "--quiet"]);
+    fs::write(temp_dir.path().join("notes.txt"), "mine\n").unwrap();
+    git(temp_dir.path(), &["add", "notes.txt"]);
+
+    add_binding(&mut manager, "bind = $mod, B, exec, firefox");
+
+    assert_eq!(
+        git(
+            temp_dir.path(),
+            &["status", "--porcelain", "--untracked-files=no"]
+        ),
+        "A  notes.txt\n",
+        "staged changes of other files stay staged and uncommitted"
+    );
+}
+
+#[test]
+fn test_git_backups_reuse_an_enclosing_repository() {
+    let outer = TempDir::new().unwrap();
+    git(outer.path(), &["init", "--quiet"]);
+    let config_dir = outer.path().join("hypr");
+    fs::create_dir(&config_dir).unwrap();
+    let config_path = config_dir.join("hyprland.conf");
+    fs::write(&config_path, CONFIG).unwrap();
+    fs::write(outer.path().join("notes"), "untracked\n").unwrap();
+    let mut manager = ConfigManager::new(config_path)
+        .unwrap()
+        .with_hooks_dir(outer.path().join("hooks"))
+        .with_backup_mode(BackupMode::Git);
+
+    add_binding(&mut manager, "bind = $mod, B, exec, firefox");
+
+    assert!(
+        !config_dir.join(".git").exists(),
+        "no repository is nested inside the enclosing one"
+    );
+    assert_eq!(
+        commit_subjects(outer.path()),
+        ["Add SUPER+B", "Track hyprland.conf"]
+    );
+    assert_eq!(
+        git(outer.path(), &["ls-files"]),
+        "hypr/hyprland.conf\n",
+        "only the config is committed"
+    );
+}
+
+#[test]
+fn test_timestamped_backups_copy_the_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, CONFIG).unwrap();
+
+    let backend = TimestampedBackups::new(temp_dir.path().to_path_buf());
+    let backup = backend.backup(&config_path).unwrap().unwrap();
+
+    assert!(backup
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .starts_with("hyprland.conf.20"));
+    assert_eq!(fs::read_to_string(backup).unwrap(), CONFIG);
+}
+
+#[test]
+fn test_compressed_backups_read_back_as_the_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    let backup_dir = temp_dir.path().join("backups");
+    fs::create_dir(&backup_dir).unwrap();
+
+    for (index, (compression, extension)) in [
+        (BackupCompression::Gzip, "gz"),
+        (BackupCompression::Zstd, "zst"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        // Different content each time, so neither backup is deduplicated
+        let content = format!("{CONFIG}# {index}\n");
+        fs::write(&config_path, &content).unwrap();
+        let backend = TimestampedBackups::new(backup_dir.clone()).with_compression(compression);
+        let backup = backend.backup(&config_path).unwrap().unwrap();
+
+        assert_eq!(backup.extension().unwrap(), extension);
+        assert_eq!(BackupCompression::of(&backup), compression);
+        assert_ne!(fs::read(&backup).unwrap(), content.as_bytes());
+        assert_eq!(read_backup(&backup).unwrap(), content);
+        assert!(checksum_path(&backup).is_file());
+    }
+    assert_eq!(
+        dated_backups(&backup_dir).unwrap().len(),
+        2,
+        "compressed backups are listed, checksum files are not"
+    );
+}
+
+#[test]
+fn test_unchanged_config_reuses_the_newest_backup() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, CONFIG).unwrap();
+    let backup_dir = temp_dir.path().join("backups");
+    fs::create_dir(&backup_dir).unwrap();
+    // Taken before checksums were kept: the content is compared instead
+    let old = backup_dir.join("hyprland.conf.2025-03-16_120000");
+    fs::write(&old, CONFIG).unwrap();
+
+    let backend = TimestampedBackups::new(backup_dir.clone());
+    assert_eq!(backend.backup(&config_path).unwrap().unwrap(), old);
+
+    fs::write(&config_path, format!("{CONFIG}# edited\n")).unwrap();
+    let new = backend.backup(&config_path).unwrap().unwrap();
+    assert_ne!(new, old);
+    assert_eq!(backend.backup(&config_path).unwrap().unwrap(), new);
+    assert_eq!(dated_backups(&backup_dir).unwrap().len(), 2);
+}
+
+#[test]
+fn test_restore_refuses_a_damaged_backup() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, CONFIG).unwrap();
+    let mut manager = ConfigManager::new(config_path.clone())
+        .unwrap()
+        .with_state_path(temp_dir.path().join("state"))
+        .with_hooks_dir(temp_dir.path().join("hooks"));
+    add_binding(&mut manager, "bind = $mod, B, exec, firefox");
+    let written = fs::read_to_string(&config_path).unwrap();
+
+    let backup = manager.list_backups().unwrap().remove(0);
+    fs::write(&backup, CONFIG.replace("killactive", "exit")).unwrap();
+
+    assert!(matches!(
+        manager.restore_backup(&backup),
+        Err(ConfigError::ChecksumMismatch(path)) if path == backup
+    ));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), written);
+}
+
+#[test]
+fn test_pruning_removes_checksum_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, CONFIG).unwrap();
+    let manager = ConfigManager::new(config_path.clone()).unwrap();
+    let backup_dir = temp_dir.path().join("backups");
+    let old = backup_dir.join("hyprland.conf.2025-03-16_120000.gz");
+    fs::write(&old, b"").unwrap();
+    fs::write(checksum_path(&old), "0\n").unwrap();
+    let backend = TimestampedBackups::new(backup_dir.clone());
+    let new = backend.backup(&config_path).unwrap().unwrap();
+
+    let deleted = manager
+        .prune_backups(&RetentionPolicy {
+            max_count: Some(1),
+            ..RetentionPolicy::KEEP_ALL
+        })
+        .unwrap();
+
+    assert_eq!(deleted, [old.as_path()]);
+    assert!(!checksum_path(&old).exists());
+    assert!(checksum_path(&new).exists());
+}
+
+#[test]
+fn test_describe_change_lists_each_changed_combo() {
+    let path = Path::new("hyprland.conf");
+    let after = "$mod = SUPER\nbind = $mod, Q, exec, kitty\nbind = $mod, B, exec, firefox\n\
+                 bind = $mod, T, exec, foot\n";
+
+    assert_eq!(
+        describe_change(path, CONFIG, after, "Update"),
+        "Update keybindings (2 added, 1 changed)\n\n\
+         Change SUPER+Q\nAdd SUPER+B\nAdd SUPER+T"
+    );
+    assert_eq!(
+        describe_change(path, after, CONFIG, "Update")
+            .lines()
+            .next(),
+        Some("Update keybindings (2 removed, 1 changed)")
+    );
+}
+
+#[test]
+fn test_describe_change_falls_back_without_binding_changes() {
+    let after = format!("{CONFIG}general {{\n    gaps_in = 4\n}}\n");
+    assert_eq!(
+        describe_change(
+            Path::new("hyprland.conf"),
+            CONFIG,
+            &after,
+            "Update hyprland.conf"
+        ),
+        "Update hyprland.conf"
+    );
+}
+
+#[test]
+fn test_backup_mode_round_trips_through_its_name() {
+    for mode in [BackupMode::Timestamped, BackupMode::Git] {
+        assert_eq!(mode.to_string().parse::<BackupMode>(), Ok(mode));
+    }
+    assert!("svn".parse::<BackupMode>().is_err());
+}
+
+#[test]
+fn test_backup_compression_round_trips_through_its_name() {
+    for compression in [
+        BackupCompression::None,
+        BackupCompression::Gzip,
+        BackupCompression::Zstd,
+    ] {
+        assert_eq!(
+            compression.to_string().parse::<BackupCompression>(),
+            Ok(compression)
+        );
+    }
+    assert!("xz".parse::<BackupCompression>().is_err());
+}
+
+fn day(day: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 3, day)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+}
+
+/// Backups of 100 bytes, one a day from the 20th back to the 16th
+fn daily_backups() -> Vec<BackupInfo> {
+    (16..=20)
+        .rev()
+        .map(|d| BackupInfo {
+            path: PathBuf::from(format!("hyprland.conf.2025-03-{d}_120000")),
+            created: day(d),
+            size: 100,
+        })
+        .collect()
+}
+
+/// Days of the month of backups from [`daily_backups`]
+fn days(paths: &[PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|path| path.to_string_lossy()[22..24].to_string())
+        .collect()
+}
+
+#[test]
+fn test_retention_drops_backups_past_each_limit() {
+    let backups = daily_backups();
+    let now = day(21);
+    let policy = |max_count, max_age_days, max_total_bytes| RetentionPolicy {
+        max_count,
+        max_age_days,
+        max_total_bytes,
+    };
+
+    assert_eq!(
+        days(&policy(Some(3), None, None).select(&backups, now)),
+        ["17", "16"]
+    );
+    assert_eq!(
+        days(&policy(None, Some(3), None).select(&backups, now)),
+        ["17", "16"],
+        "the 18th is exactly three days old and stays"
+    );
+    assert_eq!(
+        days(&policy(None, None, Some(250)).select(&backups, now)),
+        ["18", "17", "16"]
+    );
+    assert_eq!(
+        days(&policy(Some(4), Some(10), Some(250)).select(&backups, now)),
+        ["18", "17", "16"],
+        "the strictest limit wins"
+    );
+    assert!(RetentionPolicy::KEEP_ALL.select(&backups, now).is_empty());
+}
+
+#[test]
+fn test_retention_always_keeps_the_newest_backup() {
+    let backups = daily_backups();
+    let policy = RetentionPolicy {
+        max_count: Some(0),
+        max_age_days: Some(0),
+        max_total_bytes: Some(1),
+    };
+
+    assert_eq!(
+        days(&policy.select(&backups, day(30))),
+        ["19", "18", "17", "16"]
+    );
+}
+
+#[test]
+fn test_commits_prune_backups_by_the_retention_policy() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, CONFIG).unwrap();
+    let mut manager = ConfigManager::new(config_path)
+        .unwrap()
+        .with_state_path(temp_dir.path().join("state"))
+        .with_hooks_dir(temp_dir.path().join("hooks"))
+        .with_retention(RetentionPolicy {
+            max_count: Some(2),
+            ..RetentionPolicy::KEEP_ALL
+        });
+    let backup_dir = temp_dir.path().join("backups");
+    // Older content than the config's, so the write's backup is not deduplicated
+    for d in 16..=18 {
+        fs::write(
+            backup_dir.join(format!("hyprland.conf.2025-03-{d}_120000")),
+            "bind = SUPER, Q, exit\n",
+        )
+        .unwrap();
+    }
+
+    add_binding(&mut manager, "bind = $mod, B, exec, firefox");
+
+    let kept = manager.list_backups().unwrap();
+    assert_eq!(kept.len(), 2);
+    assert!(kept[1].ends_with("hyprland.conf.2025-03-18_120000"));
+    assert!(
+        manager.pristine_backup_path().exists(),
+        "the pristine backup is never pruned"
+    );
+}
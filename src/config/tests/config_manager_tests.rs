@@ -13,9 +13,9 @@
 // limitations under the License.
 
 use super::super::*;
-use std::{fs, os::unix::fs::symlink, path::PathBuf, thread, time::Duration};
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
+use std::{fs, os::unix::fs::symlink, path::PathBuf, thread, time::Duration};
 use tempfile::TempDir;
 
 /// Helper: Creates a temporary config file for testing.
@@ -156,7 +156,9 @@ fn test_permission_warnings_for_unexpected_owner() {
     );
 
     assert!(
-        warnings.iter().any(|warning| warning.contains("owned by uid")),
+        warnings
+            .iter()
+            .any(|warning| warning.contains("owned by uid")),
         "Expected an ownership warning, got: {warnings:?}",
     );
 }
@@ -190,7 +192,7 @@ fn test_create_timestamped_backup() {
     let manager = ConfigManager::new(config_path.clone()).unwrap();
 
     // Create a backup
-    let backup_path = manager.create_timestamped_backup().unwrap();
+    let backup_path = manager.create_backup().unwrap().unwrap();
 
     // Verify: Backup file exists
     assert!(backup_path.exists(), "Backup file should exist");
@@ -240,14 +242,14 @@ fn test_multiple_backups_dont_overwrite() {
     let manager = ConfigManager::new(config_path.clone()).unwrap();
 
     // Create first backup
-    let backup1 = manager.create_timestamped_backup().unwrap();
+    let backup1 = manager.create_backup().unwrap().unwrap();
 
     // Wait 1 second to ensure different timestamp
     thread::sleep(Duration::from_secs(1));
 
     // Modify config and create second backup
     fs::write(&config_path, "modified content").unwrap();
-    let backup2 = manager.create_timestamped_backup().unwrap();
+    let backup2 = manager.create_backup().unwrap().unwrap();
 
     // Verify: Both backups exist
     assert!(backup1.exists(), "First backup should exist");
@@ -271,15 +273,15 @@ fn test_list_backups_sorted_newest_first() {
     let manager = ConfigManager::new(config_path.clone()).unwrap();
 
     // Create 3 backups with delays to ensure different timestamps
-    let backup1 = manager.create_timestamped_backup().unwrap();
+    let backup1 = manager.create_backup().unwrap().unwrap();
     thread::sleep(Duration::from_secs(1));
 
     fs::write(&config_path, "second\n").unwrap();
-    let backup2 = manager.create_timestamped_backup().unwrap();
+    let backup2 = manager.create_backup().unwrap().unwrap();
     thread::sleep(Duration::from_secs(1));
 
     fs::write(&config_path, "third\n").unwrap();
-    let backup3 = manager.create_timestamped_backup().unwrap();
+    let backup3 = manager.create_backup().unwrap().unwrap();
 
     // List backups
     let backups = manager.list_backups().unwrap();
@@ -323,7 +325,7 @@ fn test_list_backups_ignores_invalid_files() {
     let manager = ConfigManager::new(config_path.clone()).unwrap();
 
     // Create one valid backup
-    let valid_backup = manager.create_timestamped_backup().unwrap();
+    let valid_backup = manager.create_backup().unwrap().unwrap();
 
     // Create some invalid files in backup directory
     fs::write(backup_dir.join("random.txt"), "not a backup").unwrap();
@@ -355,7 +357,7 @@ fn test_cleanup_keeps_n_most_recent() {
     let mut backup_paths = Vec::new();
     for i in 1..=5 {
         fs::write(&config_path, format!("version {}\n", i)).unwrap();
-        let backup = manager.create_timestamped_backup().unwrap();
+        let backup = manager.create_backup().unwrap().unwrap();
         backup_paths.push(backup);
         thread::sleep(Duration::from_secs(1));
     }
@@ -403,7 +405,7 @@ fn test_cleanup_when_fewer_than_keep() {
     // Create only 3 backups
     for i in 1..=3 {
         fs::write(&config_path, format!("version {}\n", i)).unwrap();
-        manager.create_timestamped_backup().unwrap();
+        manager.create_backup().unwrap().unwrap();
         thread::sleep(Duration::from_secs(1));
     }
 
@@ -431,7 +433,7 @@ fn test_restore_backup_basic() {
     let manager = ConfigManager::new(config_path.clone()).unwrap();
 
     // Create backup of original content
-    let backup_path = manager.create_timestamped_backup().unwrap();
+    let backup_path = manager.create_backup().unwrap().unwrap();
 
     // Verify backup contains original content
     let backup_content = fs::read_to_string(&backup_path).unwrap();
@@ -470,7 +472,7 @@ fn test_restore_creates_safety_backup() {
     let manager = ConfigManager::new(config_path.clone()).unwrap();
 
     // Create first backup (the one being restored from)
-    let first_backup = manager.create_timestamped_backup().unwrap();
+    let first_backup = manager.create_backup().unwrap().unwrap();
 
     // Small delay to ensure different timestamps
     thread::sleep(Duration::from_secs(1));
@@ -606,7 +608,7 @@ fn test_restore_preserves_exact_content() {
     let manager = ConfigManager::new(config_path.clone()).unwrap();
 
     // Create backup of tricky content
-    let backup = manager.create_timestamped_backup().unwrap();
+    let backup = manager.create_backup().unwrap().unwrap();
 
     // Modify config to something simple
     fs::write(&config_path, "simple content\n").unwrap();
@@ -638,3 +640,81 @@ fn test_restore_preserves_exact_content() {
     );
     assert!(restored.contains("\n\n"), "Should preserve empty lines");
 }
+
+#[test]
+fn test_first_write_creates_pristine_backup() {
+    let (temp_dir, config_path) = create_test_config();
+    let state_path = temp_dir.path().join("state");
+    let original = fs::read_to_string(&config_path).unwrap();
+
+    let manager = ConfigManager::new(config_path.clone())
+        .unwrap()
+        .with_state_path(state_path.clone());
+
+    let tx = ConfigTransaction::begin(&manager).unwrap();
+    tx.commit("bind = SUPER, W, exec, kitty\n").unwrap();
+
+    let pristine = manager.pristine_backup_path();
+    assert_eq!(fs::read_to_string(&pristine).unwrap(), original);
+    assert!(fs::read_to_string(&state_path)
+        .unwrap()
+        .contains("hyprland.conf"));
+
+    // Second write must leave the pristine copy alone
+    let tx = ConfigTransaction::begin(&manager).unwrap();
+    tx.commit("bind = SUPER, E, exec, thunar\n").unwrap();
+    assert_eq!(fs::read_to_string(&pristine).unwrap(), original);
+}
+
+#[test]
+fn test_pristine_backup_not_recreated_once_managed() {
+    let (temp_dir, config_path) = create_test_config();
+    let manager = ConfigManager::new(config_path.clone())
+        .unwrap()
+        .with_state_path(temp_dir.path().join("state"));
+
+    let tx = ConfigTransaction::begin(&manager).unwrap();
+    tx.commit("bind = SUPER, W, exec, kitty\n").unwrap();
+
+    // User deletes the pristine backup; the tool must not replace it with
+    // a config it has already modified
+    fs::remove_file(manager.pristine_backup_path()).unwrap();
+    assert_eq!(manager.ensure_pristine_backup().unwrap(), None);
+    assert!(!manager.pristine_backup_path().exists());
+}
+
+#[test]
+fn test_without_state_file_only_the_backup_guards_the_pristine_copy() {
+    let (_temp_dir, config_path) = create_test_config();
+    let manager = ConfigManager::new(config_path.clone()).unwrap();
+
+    let tx = ConfigTransaction::begin(&manager).unwrap();
+    tx.commit("bind = SUPER, W, exec, kitty\n").unwrap();
+    assert!(manager.pristine_backup_path().exists());
+
+    // Nothing recorded the config as managed, so a deleted copy comes back
+    fs::remove_file(manager.pristine_backup_path()).unwrap();
+    assert_eq!(
+        manager.ensure_pristine_backup().unwrap(),
+        Some(manager.pristine_backup_path())
+    );
+}
+
+#[test]
+fn test_pristine_backup_survives_cleanup() {
+    let (temp_dir, config_path) = create_test_config();
+    let manager = ConfigManager::new(config_path.clone())
+        .unwrap()
+        .with_state_path(temp_dir.path().join("state"));
+
+    let tx = ConfigTransaction::begin(&manager).unwrap();
+    tx.commit("bind = SUPER, W, exec, kitty\n").unwrap();
+
+    assert!(!manager
+        .list_backups()
+        .unwrap()
+        .contains(&manager.pristine_backup_path()));
+
+    manager.cleanup_old_backups(0).unwrap();
+    assert!(manager.pristine_backup_path().exists());
+}
@@ -0,0 +1,176 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proptest::prelude::*;
+
+use crate::config::diff::{ConfigDiff, LineChange};
+
+/// Rebuilds the new content from the old one and the hunks, checking
+/// that every unchanged and removed line is where the hunk says
+fn apply(old: &str, diff: &ConfigDiff) -> Vec<String> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let mut new_lines: Vec<String> = Vec::new();
+    let mut next_old = 0;
+    for hunk in diff.hunks() {
+        let hunk_start = hunk.old_start - 1;
+        assert!(hunk_start >= next_old, "hunks overlap or are out of order");
+        new_lines.extend(
+            old_lines[next_old..hunk_start]
+                .iter()
+                .map(|l| l.to_string()),
+        );
+        next_old = hunk_start;
+        for line in &hunk.lines {
+            match line.change {
+                LineChange::Unchanged => {
+                    assert_eq!(old_lines[next_old], line.text);
+                    new_lines.push(line.text.clone());
+                    next_old += 1;
+                }
+                LineChange::Removed => {
+                    assert_eq!(old_lines[next_old], line.text);
+                    next_old += 1;
+                }
+                LineChange::Added => new_lines.push(line.text.clone()),
+            }
+        }
+    }
+    new_lines.extend(old_lines[next_old..].iter().map(|l| l.to_string()));
+    new_lines
+}
+
+/// Length of the longest common subsequence of the lines
+fn common_lines(old: &str, new: &str) -> usize {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut table = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in 0..old.len() {
+        for j in 0..new.len() {
+            table[i + 1][j + 1] = if old[i] == new[j] {
+                table[i][j] + 1
+            } else {
+                table[i][j + 1].max(table[i + 1][j])
+            };
+        }
+    }
+    table[old.len()][new.len()]
+}
+
+fn config() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop::sample::select(vec![
+            "",
+            "# Apps",
+            "$mainMod = SUPER",
+            "bind = $mainMod, Q, killactive",
+            "bind = $mainMod, Return, exec, kitty",
+            "bind = $mainMod, E, exec, nautilus",
+            "exec-once = waybar",
+        ]),
+        0..30,
+    )
+    .prop_map(|lines| lines.iter().map(|line| format!("{}\n", line)).collect())
+}
+
+#[test]
+fn test_same_content_gives_an_empty_diff() {
+    let content = "bind = SUPER, Q, killactive\n";
+    let diff = ConfigDiff::between(content, content);
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_unified("a", "b"), "");
+}
+
+#[test]
+fn test_distant_changes_get_their_own_hunks() {
+    let old: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+    let new = old
+        .replace("line 2\n", "line two\n")
+        .replace("line 18\n", "");
+
+    let diff = ConfigDiff::between(&old, &new);
+
+    assert_eq!(diff.hunks().len(), 2);
+    assert_eq!(diff.hunks()[0].header(), "@@ -1,5 +1,5 @@");
+    assert_eq!(diff.hunks()[1].header(), "@@ -15,6 +15,5 @@");
+    assert_eq!((diff.removed(), diff.added()), (2, 1));
+}
+
+#[test]
+fn test_nearby_changes_share_a_hunk() {
+    let old: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+    let new = old
+        .replace("line 5\n", "line five\n")
+        .replace("line 10\n", "line ten\n");
+
+    let diff = ConfigDiff::between(&old, &new);
+
+    assert_eq!(diff.hunks().len(), 1);
+    assert_eq!(diff.hunks()[0].header(), "@@ -2,12 +2,12 @@");
+}
+
+#[test]
+fn test_unified_output_marks_a_missing_final_newline() {
+    let diff = ConfigDiff::between("bind = SUPER, Q, killactive", "");
+
+    assert_eq!(
+        diff.to_unified("hyprland.conf", "hyprland.conf (new)"),
+        "--- hyprland.conf\n\
+         +++ hyprland.conf (new)\n\
+         @@ -1 +0,0 @@\n\
+         -bind = SUPER, Q, killactive\n\
+         \\ No newline at end of file\n"
+    );
+}
+
+#[test]
+fn test_appended_lines_are_numbered_after_the_last_line() {
+    let diff = ConfigDiff::between("a\nb\n", "a\nb\nc\n");
+
+    assert_eq!(diff.hunks()[0].header(), "@@ -1,2 +1,3 @@");
+    let new_only = ConfigDiff::with_context("a\nb\n", "a\nb\nc\n", 0);
+    assert_eq!(new_only.hunks()[0].header(), "@@ -2,0 +3 @@");
+}
+
+#[test]
+fn test_large_rewrites_fall_back_to_replacing_the_region() {
+    let old: String = (0..3000).map(|i| format!("old {}\n", i)).collect();
+    let new: String = (0..3000).map(|i| format!("new {}\n", i)).collect();
+
+    let diff = ConfigDiff::between(&old, &new);
+
+    assert_eq!((diff.removed(), diff.added()), (3000, 3000));
+    assert_eq!(apply(&old, &diff), new.lines().collect::<Vec<_>>());
+}
+
+proptest! {
+    #[test]
+    fn prop_hunks_rebuild_the_new_content(
+        old in config(),
+        new in config(),
+        context in 0usize..4,
+    ) {
+        let diff = ConfigDiff::with_context(&old, &new, context);
+        prop_assert_eq!(apply(&old, &diff), new.lines().collect::<Vec<_>>());
+        prop_assert_eq!(diff.is_empty(), old == new);
+    }
+
+    #[test]
+    fn prop_diff_changes_as_few_lines_as_possible(old in config(), new in config()) {
+        let diff = ConfigDiff::between(&old, &new);
+        let common = common_lines(&old, &new);
+        prop_assert_eq!(diff.removed(), old.lines().count() - common);
+        prop_assert_eq!(diff.added(), new.lines().count() - common);
+    }
+}
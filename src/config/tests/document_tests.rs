@@ -0,0 +1,264 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proptest::prelude::*;
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{
+    config::{
+        document::{ConfigDocument, Directive, LineKind},
+        ConfigManager,
+    },
+    core::{
+        parser::parse_config_file,
+        types::{BindType, KeyCombo, Keybinding, Modifier},
+    },
+};
+
+/// Lines a config may contain, including ones the parser cannot read
+fn config_line() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::new()),
+        Just("   ".to_string()),
+        Just("# Apps".to_string()),
+        Just("# @category: media".to_string()),
+        Just("$mainMod = SUPER".to_string()),
+        Just("exec-once = waybar".to_string()),
+        Just("env = XCURSOR_SIZE,24".to_string()),
+        Just("windowrulev2 = float, class:pavucontrol".to_string()),
+        Just("general {".to_string()),
+        Just("    gaps_in = 5".to_string()),
+        Just("}".to_string()),
+        Just("submap = resize".to_string()),
+        Just("submap = reset".to_string()),
+        Just("unbind = SUPER, Q".to_string()),
+        Just("bind = $mainMod, Q, killactive".to_string()),
+        Just("  binde = , XF86AudioRaiseVolume, exec, pamixer -i 5".to_string()),
+        Just("bindd = SUPER, B, Browser, exec, firefox".to_string()),
+        Just("bindx = SUPER, X, exec, broken".to_string()),
+        Just("bind = SUPER".to_string()),
+        Just("device {\n    name = mouse\n    bind = , mouse:276, exec, obs\n}".to_string()),
+        "[ -~]{0,40}",
+    ]
+}
+
+/// A whole config: lines joined by `\n` or `\r\n`, with or without a
+/// final line break
+fn config() -> impl Strategy<Value = String> {
+    (
+        prop::collection::vec(config_line(), 0..24),
+        any::<bool>(),
+        any::<bool>(),
+    )
+        .prop_map(|(lines, crlf, trailing)| {
+            let separator = if crlf { "\r\n" } else { "\n" };
+            let mut content = lines.join(separator);
+            if trailing && !lines.is_empty() {
+                content.push_str(separator);
+            }
+            content
+        })
+}
+
+/// Bindings grouped by input device, in order within each device
+fn by_device(bindings: &[Keybinding]) -> BTreeMap<Option<String>, Vec<Keybinding>> {
+    let mut devices: BTreeMap<Option<String>, Vec<Keybinding>> = BTreeMap::new();
+    for binding in bindings {
+        devices
+            .entry(binding.device.clone())
+            .or_default()
+            .push(binding.clone());
+    }
+    devices
+}
+
+fn binding() -> impl Strategy<Value = Keybinding> {
+    (
+        prop::array::uniform3(any::<bool>()),
+        prop::sample::select(vec!["K", "Q", "Return", "1", "F"]),
+        prop::sample::select(vec![
+            ("exec", Some("kitty")),
+            ("exec", Some("firefox --new-window")),
+            ("killactive", None),
+            ("workspace", Some("2")),
+        ]),
+        any::<bool>(),
+        prop::option::of(prop::sample::select(vec!["media", "window management"])),
+        prop::option::of(prop::sample::select(vec!["mouse", "keyboard"])),
+    )
+        .prop_map(
+            |(held, key, (dispatcher, args), repeat, category, device)| Keybinding {
+                bind_type: if repeat {
+                    BindType::BindE
+                } else {
+                    BindType::Bind
+                },
+                category: category.map(str::to_string),
+                device: device.map(str::to_string),
+                ..Keybinding::new(
+                    KeyCombo::new(
+                        [Modifier::Super, Modifier::Shift, Modifier::Ctrl]
+                            .into_iter()
+                            .zip(held)
+                            .filter_map(|(modifier, held)| held.then_some(modifier))
+                            .collect(),
+                        key,
+                    ),
+                    dispatcher,
+                    args,
+                )
+            },
+        )
+}
+
+proptest! {
+    #[test]
+    fn prop_document_renders_its_source_exactly(content in config()) {
+        prop_assert_eq!(ConfigDocument::parse(&content).to_string(), content);
+    }
+
+    #[test]
+    fn prop_document_bindings_match_the_parser(content in config()) {
+        let document = ConfigDocument::parse(&content);
+        let has_broken = document
+            .lines()
+            .iter()
+            .any(|line| line.kind == LineKind::BrokenBind);
+
+        match parse_config_file(&content, Path::new("test.conf")) {
+            Ok(bindings) => prop_assert_eq!(document.bindings(), bindings),
+            Err(_) => prop_assert!(has_broken),
+        }
+    }
+
+    #[test]
+    fn prop_writing_parsed_bindings_changes_nothing(content in config()) {
+        let mut document = ConfigDocument::parse(&content);
+        let bindings = document.bindings();
+        document.set_bindings(&bindings);
+
+        prop_assert_eq!(document.bindings(), bindings);
+        if ConfigDocument::parse(&content).lines().iter().any(|line| matches!(line.kind, LineKind::Bind(_))) {
+            prop_assert_eq!(document.to_string(), content);
+        }
+    }
+
+    #[test]
+    fn prop_parse_write_parse_yields_the_written_bindings(
+        content in config(),
+        bindings in prop::collection::vec(binding(), 0..12),
+    ) {
+        let rebuilt = ConfigManager::rebuild_config(&content, &bindings);
+        let document = ConfigDocument::parse(&rebuilt);
+
+        // Unparseable lines, directives and other content survive the write
+        let kept = |text: &str| {
+            text.lines()
+                .filter(|line| {
+                    let line = line.trim();
+                    !line.is_empty()
+                        && (Directive::from_line(line).is_some()
+                            || line.starts_with("bindx")
+                            || line == "bind = SUPER")
+                })
+                .map(|line| line.trim().to_string())
+                .collect::<Vec<_>>()
+        };
+        prop_assert_eq!(kept(&rebuilt), kept(&content));
+
+        // Every written binding comes back, unless an unbind below cancels
+        // it; device sections keep the order within each device
+        if !content.contains("unbind") {
+            prop_assert_eq!(by_device(&document.bindings()), by_device(&bindings));
+        }
+    }
+}
+
+const MIXED: &str = "$mainMod = SUPER\n\
+                     env = XCURSOR_SIZE,24\n\
+                     exec-once = waybar\n\
+                     bind = $mainMod, Q, killactive\n\
+                     # notifications\n\
+                     exec-once = mako\n\
+                     \n\
+                     windowrulev2 = float, class:pavucontrol\n\
+                     bind = $mainMod, F, fullscreen\n";
+
+#[test]
+fn test_directives_are_classified_into_protected_blocks() {
+    let document = ConfigDocument::parse(MIXED);
+    let kinds: Vec<_> = document.lines().iter().map(|line| &line.kind).collect();
+
+    assert_eq!(kinds[1], &LineKind::Directive(Directive::Env));
+    assert_eq!(kinds[2], &LineKind::Directive(Directive::ExecOnce));
+    assert_eq!(kinds[7], &LineKind::Directive(Directive::WindowRule));
+    assert_eq!(Directive::from_line("monitor = , preferred, auto, 1"), None);
+
+    // The blank line ends the first block; the bind line inside it is part
+    // of it, the one after the window rule is not
+    assert_eq!(document.protected_blocks(), vec![1..6, 7..8]);
+}
+
+#[test]
+fn test_rebuild_writes_new_bindings_below_protected_blocks() {
+    let mut bindings = ConfigDocument::parse(MIXED).bindings();
+    let mut new = bindings[0].clone();
+    new.key_combo = KeyCombo::new(vec![Modifier::Super], "K");
+    new.dispatcher = "exec".to_string();
+    new.args = Some("kitty".to_string());
+    bindings.insert(1, new.clone());
+    bindings.insert(
+        0,
+        Keybinding {
+            args: Some("foot".to_string()),
+            ..new
+        },
+    );
+
+    let rebuilt = ConfigManager::rebuild_config(MIXED, &bindings);
+    assert_eq!(
+        rebuilt,
+        "$mainMod = SUPER\n\
+         bind = $mainMod, K, exec, foot\n\
+         env = XCURSOR_SIZE,24\n\
+         exec-once = waybar\n\
+         bind = $mainMod, Q, killactive\n\
+         # notifications\n\
+         exec-once = mako\n\
+         bind = $mainMod, K, exec, kitty\n\
+         \n\
+         windowrulev2 = float, class:pavucontrol\n\
+         bind = $mainMod, F, fullscreen\n"
+    );
+    assert_eq!(ConfigDocument::parse(&rebuilt).bindings(), bindings);
+}
+
+#[test]
+fn test_bind_block_is_not_written_into_a_protected_block() {
+    let mut document = ConfigDocument::parse(MIXED);
+    document.replace_bind_block("# Windows\nbind = SUPER, Q, killactive\n");
+
+    assert_eq!(
+        document.to_string(),
+        "$mainMod = SUPER\n\
+         env = XCURSOR_SIZE,24\n\
+         exec-once = waybar\n\
+         # notifications\n\
+         exec-once = mako\n\
+         # Windows\n\
+         bind = SUPER, Q, killactive\n\
+         \n\
+         windowrulev2 = float, class:pavucontrol\n"
+    );
+}
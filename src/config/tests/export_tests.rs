@@ -0,0 +1,143 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Export format tests
+
+use std::{fs, path::Path};
+
+use tempfile::TempDir;
+
+use crate::config::{
+    export::{ExportDocument, ExportFormat},
+    ConfigManager,
+};
+use crate::core::{parser::parse_config_file, Keybinding};
+
+const CONFIG: &str = "\
+$mod = SUPER
+bind = $mod, Q, killactive
+bindd = $mod, B, Browser, exec, firefox
+submap = resize
+binde = , right, resizeactive, 10 0
+submap = reset
+";
+
+fn bindings() -> Vec<Keybinding> {
+    parse_config_file(CONFIG, Path::new("hyprland.conf")).unwrap()
+}
+
+#[test]
+fn test_structured_exports_read_back_as_the_bindings() {
+    let bindings = bindings();
+
+    for format in [ExportFormat::Json, ExportFormat::Yaml, ExportFormat::Toml] {
+        let exported = format.render(&bindings).unwrap();
+        let document: ExportDocument = match format {
+            ExportFormat::Json => serde_json::from_str(&exported).unwrap(),
+            ExportFormat::Yaml => serde_yaml::from_str(&exported).unwrap(),
+            _ => toml::from_str(&exported).unwrap(),
+        };
+        assert_eq!(document.bindings, bindings, "{format} export");
+    }
+}
+
+#[test]
+fn test_hyprland_export_has_config_lines() {
+    let exported = ExportFormat::Hyprland.render(&bindings()).unwrap();
+
+    assert!(exported.starts_with("# Exported Hyprland Keybindings\n"));
+    assert!(exported.contains("bind = SUPER, Q, killactive\n"));
+    assert!(exported.contains("submap = resize\n"));
+    assert_eq!(
+        parse_config_file(&exported, Path::new("export.conf")).unwrap(),
+        bindings()
+    );
+}
+
+#[test]
+fn test_export_format_follows_the_file_extension() {
+    for (file, format) in [
+        ("binds.json", ExportFormat::Json),
+        ("binds.YAML", ExportFormat::Yaml),
+        ("binds.yml", ExportFormat::Yaml),
+        ("binds.toml", ExportFormat::Toml),
+        ("binds.conf", ExportFormat::Hyprland),
+        ("binds", ExportFormat::Hyprland),
+    ] {
+        assert_eq!(ExportFormat::from_path(Path::new(file)), format, "{file}");
+    }
+    for format in ExportFormat::ALL {
+        assert_eq!(format.to_string().parse::<ExportFormat>(), Ok(format));
+    }
+}
+
+#[test]
+fn test_export_to_writes_the_chosen_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, CONFIG).unwrap();
+    let manager = ConfigManager::new(config_path).unwrap();
+    let export_path = temp_dir.path().join("binds.yaml");
+
+    manager
+        .export_to(&export_path, &bindings(), ExportFormat::Yaml)
+        .unwrap();
+
+    let document: ExportDocument =
+        serde_yaml::from_str(&fs::read_to_string(export_path).unwrap()).unwrap();
+    assert_eq!(document.bindings.len(), 3);
+}
+
+#[test]
+fn test_structured_exports_read_back() {
+    for format in [ExportFormat::Json, ExportFormat::Yaml, ExportFormat::Toml] {
+        let content = format.render(&bindings()).unwrap();
+        let imported = format.read(&content, Path::new("import")).unwrap();
+
+        assert_eq!(imported.bindings, bindings(), "{format}");
+        assert!(imported.errors.is_empty(), "{format}");
+        assert!(imported.bindings.iter().all(|b| b.location.is_none()));
+    }
+}
+
+#[test]
+fn test_broken_entries_are_reported_one_by_one() {
+    let content = r#"{"bindings": [
+        {"key_combo": {"modifiers": ["Super"], "key": "K"}, "bind_type": "bind", "dispatcher": "killactive"},
+        {"key_combo": {"modifiers": ["Super"], "key": "L"}, "bind_type": "bind"},
+        {"key_combo": {"modifiers": ["Hyper"], "key": "M"}, "bind_type": "bind", "dispatcher": "exec"}
+    ]}"#;
+    let imported = ExportFormat::Json
+        .read(content, Path::new("import.json"))
+        .unwrap();
+
+    assert_eq!(imported.bindings.len(), 1);
+    assert_eq!(imported.errors.len(), 2);
+    assert!(imported.errors[0].starts_with("Binding 2:"));
+    assert!(imported.errors[0].contains("dispatcher"));
+    assert!(imported.errors[1].starts_with("Binding 3:"));
+}
+
+#[test]
+fn test_files_without_a_bindings_list_are_refused() {
+    assert!(ExportFormat::Json
+        .read(r#"{"binds": []}"#, Path::new("import.json"))
+        .is_err());
+    assert!(ExportFormat::Toml
+        .read("bindings = 3\n", Path::new("import.toml"))
+        .is_err());
+    assert!(ExportFormat::Yaml
+        .read("bindings: [", Path::new("import.yaml"))
+        .is_err());
+}
@@ -0,0 +1,162 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path};
+
+use tempfile::TempDir;
+
+use crate::{
+    config::fixtures::{
+        check_fixture, fixtures_from_conflicts, parse_expectation, write_fixtures,
+        FixtureExpectation, FIXTURE_DIR,
+    },
+    core::{parser::parse_config_file, ConflictDetector},
+};
+
+fn conflicts_in(config: &str) -> Vec<crate::core::Conflict> {
+    let mut detector = ConflictDetector::new();
+    for binding in parse_config_file(config, Path::new("test.conf")).unwrap() {
+        detector.add_binding(binding);
+    }
+    detector.find_conflicts()
+}
+
+#[test]
+fn test_fixtures_are_minimal_and_reproduce_the_conflict() {
+    let config = "$mod = SUPER\n\
+                  general {\n    gaps_in = 5\n}\n\
+                  bind = $mod, K, exec, firefox\n\
+                  bind = $mod, Q, killactive\n\
+                  bind = $mod, K, exec, kitty\n\
+                  submap = resize\n\
+                  bind = , ESCAPE, submap, reset\n\
+                  bind = , ESCAPE, exec, notify-send bye\n\
+                  submap = reset\n";
+
+    let fixtures = fixtures_from_conflicts(&conflicts_in(config));
+    let names: Vec<&str> = fixtures.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "multiple-actions-escape-in-resize",
+            "multiple-actions-super-k"
+        ]
+    );
+
+    assert_eq!(
+        fixtures[1].content,
+        "# Conflict regression fixture\n\
+         # expect: multiple-actions SUPER+K 2\n\
+         bind = SUPER, K, exec, firefox\n\
+         bind = SUPER, K, exec, kitty\n",
+        "Only the clashing binds, with variables expanded"
+    );
+    assert!(fixtures[0].content.contains("submap = resize\n"));
+
+    for fixture in &fixtures {
+        assert_eq!(check_fixture(&fixture.content), Ok(()), "{}", fixture.name);
+    }
+}
+
+#[test]
+fn test_catchall_shadowing_fixture_keeps_each_binding_in_its_submap() {
+    let config = "bindu = SUPER, V, exec, volume-osd\n\
+                  submap = passthrough\n\
+                  bind = SUPER, catchall, exec, notify-send blocked\n\
+                  bind = , escape, submap, reset\n\
+                  submap = reset\n";
+
+    let fixtures = fixtures_from_conflicts(&conflicts_in(config));
+    assert_eq!(fixtures.len(), 1);
+    assert_eq!(
+        fixtures[0].content,
+        "# Conflict regression fixture\n\
+         # expect: catchall-shadowing SUPER+V 2\n\
+         bindu = SUPER, V, exec, volume-osd\n\
+         submap = passthrough\n\
+         bind = SUPER, catchall, exec, notify-send blocked\n\
+         submap = reset\n"
+    );
+    assert_eq!(check_fixture(&fixtures[0].content), Ok(()));
+}
+
+#[test]
+fn test_check_fixture_reports_mismatches() {
+    let wrong_kind = "# expect: redundant-duplicate SUPER+K 2\n\
+                      bind = SUPER, K, exec, firefox\n\
+                      bind = SUPER, K, exec, kitty\n";
+    assert_eq!(
+        check_fixture(wrong_kind),
+        Err("expected redundant-duplicate SUPER+K 2, found multiple-actions SUPER+K 2".to_string())
+    );
+
+    assert!(check_fixture("bind = SUPER, K, exec, firefox\n").is_err());
+    assert_eq!(
+        parse_expectation("# expect: press-and-release ALT+F4 3"),
+        Some(FixtureExpectation {
+            kind: "press-and-release".to_string(),
+            key_combo: "ALT+F4".to_string(),
+            bindings: 3,
+        })
+    );
+}
+
+#[test]
+fn test_write_fixtures_skips_identical_and_renames_different() {
+    let temp_dir = TempDir::new().unwrap();
+    let fixtures = fixtures_from_conflicts(&conflicts_in(
+        "bind = SUPER, K, exec, firefox\nbind = SUPER, K, exec, kitty\n",
+    ));
+
+    let written = write_fixtures(temp_dir.path(), &fixtures).unwrap();
+    assert_eq!(
+        written,
+        vec![temp_dir.path().join("multiple-actions-super-k.conf")]
+    );
+
+    // Generating again from the same config adds nothing
+    assert!(write_fixtures(temp_dir.path(), &fixtures)
+        .unwrap()
+        .is_empty());
+
+    // A different conflict on the same combo gets its own file
+    let other = fixtures_from_conflicts(&conflicts_in(
+        "bind = SUPER, K, exec, firefox\nbind = SUPER, K, exec, alacritty\n",
+    ));
+    let written = write_fixtures(temp_dir.path(), &other).unwrap();
+    assert_eq!(
+        written,
+        vec![temp_dir.path().join("multiple-actions-super-k-2.conf")]
+    );
+}
+
+/// Every committed fixture must still reproduce its conflict
+#[test]
+fn test_committed_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(FIXTURE_DIR);
+    let mut checked = 0;
+
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "conf") {
+            let content = fs::read_to_string(&path).unwrap();
+            if let Err(e) = check_fixture(&content) {
+                panic!("{}: {}", path.display(), e);
+            }
+            checked += 1;
+        }
+    }
+
+    assert!(checked > 0, "No fixtures found in {}", dir.display());
+}
@@ -0,0 +1,113 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the config health score
+
+use std::path::Path;
+
+use crate::{
+    config::{
+        danger::DangerDetector,
+        health::{assess_health, is_dead_binding, HealthReport},
+    },
+    core::{parser::parse_config_file, ConflictDetector},
+};
+
+/// Scores a config, treating only `kitty` and `firefox` as installed
+fn assess(content: &str) -> HealthReport {
+    let bindings = parse_config_file(content, Path::new("test.conf")).unwrap();
+    let mut detector = ConflictDetector::new();
+    for binding in bindings.iter().cloned() {
+        detector.add_binding(binding);
+    }
+
+    assess_health(
+        content,
+        &bindings,
+        &detector.find_conflicts(),
+        &DangerDetector::new(),
+        |program| matches!(program, "kitty" | "firefox"),
+    )
+}
+
+#[test]
+fn test_clean_described_config_scores_full_marks() {
+    let report = assess(
+        "# Terminal\n\
+         bind = SUPER, Return, exec, kitty\n\
+         bind = SUPER, B, exec, firefox # Browser\n\
+         # Close window\n\
+         bind = SUPER, Q, killactive\n\
+         bindd = SUPER, F, Toggle fullscreen, fullscreen\n",
+    );
+
+    assert_eq!(report.score, 100);
+    assert_eq!(report.description_coverage(), 1.0);
+    assert!(report.components().iter().all(|c| c.penalty == 0));
+}
+
+#[test]
+fn test_each_category_lowers_the_score() {
+    let report = assess(
+        "bind = SUPER, Return, exec, kitty\n\
+         bind = SUPER, Return, exec, firefox\n\
+         bind = SUPER, X, exec, sudo reboot\n\
+         bind = SUPER, M, exec, missing-app --flag\n\
+         bindm = SUPER, mouse:272, killactive\n",
+    );
+
+    assert_eq!(report.conflicts, 1);
+    assert_eq!(report.dangerous, 1);
+    assert_eq!(report.missing_executables, vec!["sudo", "missing-app"]);
+    assert_eq!(report.dead_bindings, 1);
+    assert_eq!(report.described, 0);
+
+    // 5 (conflict) + 10 (danger) + 10 (two missing) + 5 (dead) + 10 (undescribed)
+    assert_eq!(report.score, 60);
+}
+
+#[test]
+fn test_penalties_are_capped_per_category() {
+    let content: String = (0..20)
+        .map(|i| format!("bind = SUPER, {}, exec, missing-{}\n", i, i))
+        .collect();
+    let report = assess(&content);
+
+    let missing = report
+        .components()
+        .into_iter()
+        .find(|c| c.label == "Missing executables")
+        .unwrap();
+    assert_eq!(report.missing_executables.len(), 20);
+    assert_eq!(missing.penalty, missing.max_penalty);
+    assert_eq!(report.score, 70);
+}
+
+#[test]
+fn test_dead_bindings() {
+    let bindings = parse_config_file(
+        "bindm = SUPER, mouse:272, movewindow\n\
+         bindm = SUPER, mouse:273, exec, kitty\n\
+         bind = SUPER, E, exec,\n\
+         bind = , catchall, submap, reset\n\
+         submap = launch\n\
+         bind = , catchall, submap, reset\n\
+         submap = reset\n",
+        Path::new("test.conf"),
+    )
+    .unwrap();
+
+    let dead: Vec<bool> = bindings.iter().map(is_dead_binding).collect();
+    assert_eq!(dead, vec![false, true, true, true, false]);
+}
@@ -0,0 +1,134 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for pre-/post-write hooks and binding snapshots
+
+use std::{
+    fs,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+use tempfile::TempDir;
+
+use crate::{
+    config::{
+        hooks::{HookEvent, Snapshot},
+        ConfigError, ConfigManager,
+    },
+    core::{parser::parse_config_file, KeyCombo, Keybinding, Modifier},
+};
+
+/// Helper: Config with one binding, plus a hooks directory
+fn setup() -> (TempDir, PathBuf, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, "bind = SUPER, Q, exec, firefox\n").unwrap();
+
+    let hooks_dir = temp_dir.path().join("hooks");
+    fs::create_dir(&hooks_dir).unwrap();
+
+    (temp_dir, config_path, hooks_dir)
+}
+
+fn install_hook(hooks_dir: &Path, name: &str, script: &str) {
+    let path = hooks_dir.join(name);
+    fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+fn kitty_binding() -> Keybinding {
+    Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "Return"),
+        "exec",
+        Some("kitty"),
+    )
+}
+
+#[test]
+fn test_snapshot_reports_added_and_removed_bindings() {
+    let snapshot = Snapshot::new(
+        HookEvent::PreWrite,
+        Path::new("hyprland.conf"),
+        "bind = SUPER, Q, exec, firefox\nbind = SUPER, F, togglefloating\n",
+        "bind = SUPER, Q, exec, firefox\nbind = SUPER, Q, exec, kitty\n",
+    );
+
+    assert_eq!(snapshot.added.len(), 1);
+    assert_eq!(snapshot.added[0].args.as_deref(), Some("kitty"));
+    assert_eq!(snapshot.removed[0].dispatcher, "togglefloating");
+    assert_eq!(snapshot.after.conflicts.len(), 1);
+
+    let json: serde_json::Value = serde_json::from_str(&snapshot.to_json().unwrap()).unwrap();
+    assert_eq!(json["event"], "pre-write");
+    assert_eq!(json["version"], 1);
+    assert_eq!(json["after"]["conflicts"][0]["kind"], "multiple-actions");
+}
+
+#[test]
+fn test_hooks_receive_snapshot_on_stdin_and_file() {
+    let (temp_dir, config_path, hooks_dir) = setup();
+    let out = temp_dir.path();
+    install_hook(
+        &hooks_dir,
+        "pre-write",
+        &format!("cat > '{}/pre.json'", out.display()),
+    );
+    install_hook(
+        &hooks_dir,
+        "post-write",
+        &format!(
+            "cp \"$HYPR_KEYBIND_SNAPSHOT\" '{}/post.json'; echo \"$1\" > '{}/arg'",
+            out.display(),
+            out.display()
+        ),
+    );
+
+    let mut manager = ConfigManager::new(config_path)
+        .unwrap()
+        .with_state_path(temp_dir.path().join("state"))
+        .with_hooks_dir(hooks_dir);
+    let firefox = parse_config_file(&manager.read_config().unwrap(), Path::new("")).unwrap();
+    manager
+        .write_bindings(&[firefox, vec![kitty_binding()]].concat())
+        .unwrap();
+
+    let pre: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out.join("pre.json")).unwrap()).unwrap();
+    let post: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out.join("post.json")).unwrap()).unwrap();
+
+    assert_eq!(pre["event"], "pre-write");
+    assert_eq!(post["event"], "post-write");
+    assert_eq!(post["added"][0]["args"], "kitty");
+    assert_eq!(post["before"]["bindings"].as_array().unwrap().len(), 1);
+    assert_eq!(fs::read_to_string(out.join("arg")).unwrap(), "post-write\n");
+}
+
+#[test]
+fn test_failing_pre_write_hook_aborts_write() {
+    let (temp_dir, config_path, hooks_dir) = setup();
+    install_hook(&hooks_dir, "pre-write", "exit 3");
+
+    let original = fs::read_to_string(&config_path).unwrap();
+    let mut manager = ConfigManager::new(config_path.clone())
+        .unwrap()
+        .with_state_path(temp_dir.path().join("state"))
+        .with_hooks_dir(hooks_dir);
+
+    let result = manager.write_bindings(&[kitty_binding()]);
+
+    assert!(matches!(result, Err(ConfigError::HookFailed(_))));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+}
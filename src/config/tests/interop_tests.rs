@@ -0,0 +1,180 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! sway/i3 import and export tests
+
+use std::path::Path;
+
+use crate::{
+    config::interop::ForeignConfig,
+    core::{parser::parse_config_file, BindFlag, Modifier},
+};
+
+const SWAY: &str = r#"
+set $mod Mod4
+set $left h
+
+# Basics
+bindsym $mod+Shift+q kill
+bindsym $mod+d exec --no-startup-id wofi --show drun
+bindsym $mod+$left focus left
+bindsym $mod+Shift+Left move left
+bindsym $mod+3 workspace number 3
+bindsym $mod+Shift+3 move container to workspace number 3
+bindsym $mod+Tab workspace back_and_forth
+bindsym --locked XF86AudioMute exec pactl set-sink-mute @DEFAULT_SINK@ toggle
+bindcode $mod+24 fullscreen toggle
+
+mode "resize" {
+    bindsym $left resize shrink width 10px
+    bindsym Escape mode "default"
+}
+bindsym $mod+r mode "resize"
+
+bar {
+    bindsym button4 nop
+}
+"#;
+
+#[test]
+fn test_bind_lines_are_translated() {
+    let imported = ForeignConfig::Sway.translate(SWAY);
+    assert!(imported.errors.is_empty(), "{:?}", imported.errors);
+
+    let lines: Vec<String> = imported.bindings.iter().map(|b| b.to_string()).collect();
+    assert_eq!(
+        lines,
+        [
+            "bind = SHIFT+SUPER+Q, killactive",
+            "bind = SUPER+D, exec, wofi --show drun",
+            "bind = SUPER+H, movefocus, l",
+            "bind = SHIFT+SUPER+LEFT, movewindow, l",
+            "bind = SUPER+3, workspace, 3",
+            "bind = SHIFT+SUPER+3, movetoworkspace, 3",
+            "bind = SUPER+TAB, workspace, previous",
+            "bindl = XF86AUDIOMUTE, exec, pactl set-sink-mute @DEFAULT_SINK@ toggle",
+            "bind = SUPER+code:24, fullscreen",
+            "bind = H, resizeactive, -10 0",
+            "bind = ESCAPE, submap, reset",
+            "bind = SUPER+R, submap, resize",
+        ]
+    );
+    assert!(imported.bindings[7].bind_type.contains(BindFlag::Locked));
+    assert_eq!(imported.bindings[9].submap.as_deref(), Some("resize"));
+    assert_eq!(imported.bindings[11].submap, None);
+}
+
+#[test]
+fn test_untranslatable_lines_are_reported_and_skipped() {
+    let config = "bindsym Mod4+a focus parent\n\
+                  bindsym Mod4+b [class=\"Firefox\"] focus\n\
+                  bindsym Mod4+c split h; exec foot\n\
+                  bindsym Mod4+g resize grow width 10 ppt\n\
+                  bindsym Mod5+d kill\n\
+                  bindsym --input-device=1:1:kbd Mod4+e kill\n\
+                  bindsym Mod4+f \\\n    kill\n";
+    let imported = ForeignConfig::I3.translate(config);
+
+    assert_eq!(imported.bindings.len(), 1);
+    assert_eq!(imported.bindings[0].key_combo.modifiers, [Modifier::Super]);
+    assert_eq!(imported.errors.len(), 6);
+    assert!(imported.errors[0].starts_with("line 1: no Hyprland equivalent"));
+    assert!(imported.errors[1].contains("criteria"));
+    assert!(imported.errors[2].contains("chained"));
+    assert!(imported.errors[3].contains("ppt"));
+    assert!(imported.errors[4].contains("Mod5"));
+    assert!(imported.errors[5].starts_with("line 6: per-device"));
+}
+
+#[test]
+fn test_window_manager_names() {
+    assert_eq!("sway".parse(), Ok(ForeignConfig::Sway));
+    assert_eq!("i3".parse(), Ok(ForeignConfig::I3));
+    assert!("awesome".parse::<ForeignConfig>().is_err());
+    assert!(ForeignConfig::I3.default_path().ends_with("i3/config"));
+}
+
+const HYPRLAND: &str = "\
+$mod = SUPER
+bindd = $mod, Return, Terminal, exec, foot
+bind = $mod SHIFT, Q, killactive
+bind = $mod, left, movefocus, l
+bind = $mod SHIFT, 2, movetoworkspacesilent, 2
+bind = $mod, mouse_down, workspace, e+1
+bindl = , XF86AudioMute, exec, pactl set-sink-mute @DEFAULT_SINK@ toggle
+bind = $mod, P, pseudo
+bindm = $mod, mouse:272, movewindow
+bind = $mod, R, submap, resize
+submap = resize
+binde = , right, resizeactive, 10 0
+bind = , escape, submap, reset
+submap = reset
+";
+
+#[test]
+fn test_bindings_are_exported_as_sway_config() {
+    let bindings = parse_config_file(HYPRLAND, Path::new("hyprland.conf")).unwrap();
+    let export = ForeignConfig::Sway.export(&bindings);
+
+    assert_eq!(
+        export.content,
+        "# sway keybindings exported by hypr-keybind-manager\n\
+         \n\
+         set $mod Mod4\n\
+         \n\
+         # Terminal\n\
+         bindsym $mod+Return exec foot\n\
+         bindsym $mod+Shift+q kill\n\
+         bindsym $mod+Left focus left\n\
+         bindsym $mod+Shift+2 move container to workspace number 2\n\
+         bindsym $mod+button5 workspace next\n\
+         bindsym --locked XF86AUDIOMUTE exec pactl set-sink-mute @DEFAULT_SINK@ toggle\n\
+         bindsym $mod+r mode \"resize\"\n\
+         \n\
+         mode \"resize\" {\n    \
+         bindsym Right resize grow width 10 px\n    \
+         bindsym Escape mode \"default\"\n\
+         }\n\
+         \n\
+         # Not exported (no sway equivalent):\n\
+         # bind = SUPER+P, pseudo: no sway equivalent for 'pseudo'\n\
+         # bindm = SUPER+Mouse Left, movewindow: no sway equivalent for mouse bindings\n"
+    );
+    assert_eq!(export.skipped.len(), 2);
+
+    // i3 has no lock screen bindings and needs --no-startup-id
+    let i3 = ForeignConfig::I3.export(&bindings).content;
+    assert!(i3.contains("bindsym $mod+Return exec --no-startup-id foot\n"));
+    assert!(i3.contains("bindsym XF86AUDIOMUTE exec --no-startup-id pactl"));
+}
+
+#[test]
+fn test_exported_bindings_import_back_unchanged() {
+    let bindings = parse_config_file(HYPRLAND, Path::new("hyprland.conf")).unwrap();
+    let export = ForeignConfig::Sway.export(&bindings);
+    let imported = ForeignConfig::Sway.translate(&export.content);
+    assert!(imported.errors.is_empty(), "{:?}", imported.errors);
+
+    let original: Vec<String> = bindings
+        .iter()
+        .filter(|b| !matches!(b.dispatcher.as_str(), "pseudo" | "movewindow"))
+        .map(|b| format!("{} {:?}", b.key_combo, b.args))
+        .collect();
+    let round_trip: Vec<String> = imported
+        .bindings
+        .iter()
+        .map(|b| format!("{} {:?}", b.key_combo, b.args))
+        .collect();
+    assert_eq!(round_trip, original);
+}
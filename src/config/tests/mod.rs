@@ -15,15 +15,98 @@
 //! Config module tests
 //!
 //! Contains test suites for configuration management:
+//! - Audit log tests (health history and trends)
+//! - Autofix tests (`check --fix` strategies and dropped bind lines)
+//! - Backup tests (timestamped copies, git commits and their messages, compression, retention)
 //! - ConfigManager tests (file operations, backups, restoration)
+//! - Conflict fixture tests (generation and the committed regression fixtures)
+//! - Diff tests (unified hunks of config changes, property-based)
+//! - Doctor tests (report sections, severities and exit codes)
+//! - Document tests (lossless round trips, property-based)
+//! - Export tests (config lines, JSON, YAML and TOML exports)
+//! - Health score tests (scoring categories and caps)
+//! - Hook tests (snapshots passed to pre-/post-write scripts)
+//! - Import review tests (validation of imported and pasted bindings)
+//! - Interop tests (sway/i3 bind lines translated into Hyprland bindings)
+//! - Notification tests (notify-send arguments, per-kind switches)
+//! - Override tests (bindings of read-only sourced files unbound in the main config)
+//! - Path tests (XDG, flatpak and AppImage directory resolution)
+//! - Profile tests (saving, listing and comparing named binding sets)
+//! - Resolve tests (exec command targets and whether they are installed)
+//! - Settings tests (preferences persisted between runs)
+//! - Split tests (moving the bindings into a sourced file)
 //! - Transaction tests (atomic writes, rollback, ACID guarantees)
 //! - Validator tests (defence-in-depth security validation)
+//! - Writer tests (position-preserving bind line rewrites)
+
+#[cfg(test)]
+mod audit_tests;
+
+#[cfg(test)]
+mod autofix_tests;
+
+#[cfg(test)]
+mod backup_tests;
 
 #[cfg(test)]
 mod config_manager_tests;
 
+#[cfg(test)]
+mod diff_tests;
+
+#[cfg(test)]
+mod discovery_tests;
+
+#[cfg(test)]
+mod doctor_tests;
+
+#[cfg(test)]
+mod document_tests;
+
+#[cfg(test)]
+mod export_tests;
+
+#[cfg(test)]
+mod fixtures_tests;
+
+#[cfg(test)]
+mod health_tests;
+
+#[cfg(test)]
+mod hooks_tests;
+
+#[cfg(test)]
+mod import_review_tests;
+
+#[cfg(test)]
+mod interop_tests;
+
+#[cfg(test)]
+mod notifications_tests;
+
+#[cfg(test)]
+mod overrides_tests;
+
+#[cfg(test)]
+mod paths_tests;
+
+#[cfg(test)]
+mod profiles_tests;
+
+#[cfg(test)]
+mod resolve_tests;
+
+#[cfg(test)]
+mod settings_tests;
+
+#[cfg(test)]
+mod split_tests;
+
 #[cfg(test)]
 mod transaction_tests;
 
 #[cfg(test)]
 mod validator_tests;
+
+#[cfg(test)]
+mod writer_tests;
@@ -0,0 +1,95 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use crate::config::{
+    notifications::{notify_send_args, NotificationAction, NotificationEvent},
+    settings::Settings,
+};
+
+fn problems(count: usize) -> NotificationEvent {
+    NotificationEvent::ValidationFailed {
+        problems: (1..=count).map(|n| format!("problem {}", n)).collect(),
+    }
+}
+
+#[test]
+fn test_args_end_with_summary_and_body() {
+    let event = NotificationEvent::ExternalChange {
+        config_path: PathBuf::from("/home/user/.config/hypr/hyprland.conf"),
+    };
+    let args = notify_send_args(&event, false);
+
+    assert!(args.contains(&"--urgency=normal".to_string()));
+    assert!(!args.iter().any(|arg| arg.starts_with("--action")));
+    assert!(!args.contains(&"--wait".to_string()));
+    assert_eq!(
+        args[args.len() - 2..],
+        [
+            "Keybinding config changed".to_string(),
+            "/home/user/.config/hypr/hyprland.conf was changed by another program.".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_actions_add_buttons_and_wait() {
+    let args = notify_send_args(&problems(1), true);
+
+    assert!(args.contains(&"--urgency=critical".to_string()));
+    assert!(args.contains(&"--action=open-gui=Open GUI".to_string()));
+    assert!(args.contains(&"--action=ignore=Ignore".to_string()));
+    assert!(args.contains(&"--wait".to_string()));
+}
+
+#[test]
+fn test_long_bodies_are_truncated() {
+    assert_eq!(problems(1).summary(), "Keybinding config has 1 problem");
+
+    let event = problems(8);
+    assert_eq!(event.summary(), "Keybinding config has 8 problems");
+    let body = event.body();
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines.len(), 6);
+    assert_eq!(lines[4], "problem 5");
+    assert_eq!(lines[5], "... and 3 more");
+}
+
+#[test]
+fn test_action_ids_round_trip() {
+    for action in NotificationAction::ALL {
+        assert_eq!(NotificationAction::from_id(action.id()), Some(action));
+    }
+    assert_eq!(
+        NotificationAction::from_id("open-gui\n"),
+        Some(NotificationAction::OpenGui)
+    );
+    assert_eq!(NotificationAction::from_id(""), None);
+}
+
+#[test]
+fn test_preferences_switch_each_kind_off() {
+    let drift = NotificationEvent::Drift {
+        differences: vec!["not in config: SUPER+E → exec nautilus".to_string()],
+    };
+    let settings = Settings {
+        notify_drift: false,
+        ..Settings::default()
+    };
+
+    assert!(drift.is_enabled(&Settings::default()));
+    assert!(!drift.is_enabled(&settings));
+    assert!(problems(1).is_enabled(&settings));
+}
@@ -0,0 +1,149 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::PathBuf};
+use tempfile::TempDir;
+
+use crate::{
+    config::{
+        overrides::{override_content, read_only_sources},
+        ConfigError, ConfigManager,
+    },
+    core::{parser::parse_config_with_sources, Keybinding},
+};
+
+const DEFAULTS: &str = "\
+bind = SUPER, Q, killactive
+submap = resize
+binde = , right, resizeactive, 10 0
+submap = reset
+";
+
+/// A config sourcing `defaults.conf` (marked read-only) and `extra.conf`
+fn setup(config: &str) -> (TempDir, PathBuf, Vec<Keybinding>) {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("defaults.conf"), DEFAULTS).unwrap();
+    fs::write(
+        temp_dir.path().join("extra.conf"),
+        "bind = SUPER, X, exec, xeyes\n",
+    )
+    .unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, config).unwrap();
+    let bindings = parse_config_with_sources(config, &config_path).unwrap();
+    (temp_dir, config_path, bindings)
+}
+
+const CONFIG: &str = "\
+$mainMod = SUPER
+# @read-only
+source = ./defaults.conf
+source = ./extra.conf
+";
+
+#[test]
+fn test_only_the_source_line_below_the_annotation_is_read_only() {
+    let (temp_dir, config_path, _) = setup(CONFIG);
+
+    assert_eq!(
+        read_only_sources(CONFIG, &config_path),
+        [temp_dir.path().join("./defaults.conf")]
+    );
+    assert!(
+        read_only_sources("# @read-only\n\nsource = ./defaults.conf\n", &config_path).is_empty()
+    );
+}
+
+#[test]
+fn test_override_unbinds_the_original_and_appends_the_edit() {
+    let (_temp_dir, config_path, bindings) = setup(CONFIG);
+    let mut edited = bindings[1].clone();
+    edited.args = Some("20 0".to_string());
+
+    let content =
+        override_content(CONFIG, &config_path, &bindings[1], Some(&edited), &bindings).unwrap();
+
+    assert_eq!(
+        content,
+        format!(
+            "{CONFIG}\nunbind = , RIGHT\nsubmap = resize\n\
+             binde = , RIGHT, resizeactive, 20 0\nsubmap = reset\n"
+        )
+    );
+    let reparsed = parse_config_with_sources(&content, &config_path).unwrap();
+    assert_eq!(reparsed.len(), 3);
+    assert_eq!(reparsed[2].args.as_deref(), Some("20 0"));
+    assert_eq!(reparsed[2].submap.as_deref(), Some("resize"));
+    assert_eq!(reparsed[2].source_file, None);
+}
+
+#[test]
+fn test_deleting_only_unbinds_with_the_config_variables() {
+    let (_temp_dir, config_path, bindings) = setup(CONFIG);
+
+    let content = override_content(CONFIG, &config_path, &bindings[0], None, &bindings).unwrap();
+
+    assert_eq!(content, format!("{CONFIG}\nunbind = $mainMod, Q\n"));
+    assert_eq!(
+        parse_config_with_sources(&content, &config_path)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_override_is_refused_unless_it_is_safe() {
+    let (_temp_dir, config_path, mut bindings) = setup(CONFIG);
+    let refused = |old: &Keybinding, bindings: &[Keybinding]| {
+        matches!(
+            override_content(CONFIG, &config_path, old, None, bindings),
+            Err(ConfigError::ValidationFailed(_))
+        )
+    };
+
+    assert!(
+        refused(&bindings[2], &bindings),
+        "extra.conf is not read-only"
+    );
+    let mut same_combo = bindings[0].clone();
+    same_combo.submap = Some("resize".to_string());
+    assert!(
+        refused(&bindings[0], &[bindings[0].clone(), same_combo]),
+        "the unbind would remove another binding"
+    );
+    bindings[0].device = Some("my-keyboard".to_string());
+    assert!(refused(&bindings[0], &bindings), "device sections");
+}
+
+#[test]
+fn test_manager_backs_up_and_leaves_the_read_only_file_alone() {
+    let (temp_dir, config_path, bindings) = setup(CONFIG);
+    let mut manager = ConfigManager::new(config_path.clone()).unwrap();
+
+    assert!(manager.is_read_only_source(&temp_dir.path().join("defaults.conf")));
+    assert!(!manager.is_read_only_source(&temp_dir.path().join("extra.conf")));
+    manager
+        .write_source_override(&bindings[0], None, &bindings)
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("defaults.conf")).unwrap(),
+        DEFAULTS
+    );
+    assert!(fs::read_to_string(&config_path)
+        .unwrap()
+        .ends_with("unbind = $mainMod, Q\n"));
+    assert_eq!(manager.list_backups().unwrap().len(), 1);
+}
@@ -0,0 +1,207 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{ffi::OsString, fs, path::PathBuf};
+
+use tempfile::TempDir;
+
+use crate::config::paths::{Packaging, RuntimePaths};
+
+const APP_ID: &str = "io.github.tidynest.HyprKeybindManager";
+
+/// Paths resolved from the given variables only
+fn paths(vars: &[(&str, &str)]) -> RuntimePaths {
+    RuntimePaths::from_vars(|name| {
+        vars.iter()
+            .find(|(var, _)| *var == name)
+            .map(|(_, value)| OsString::from(value))
+    })
+}
+
+#[test]
+fn test_native_falls_back_to_home() {
+    let paths = paths(&[("HOME", "/home/user")]);
+
+    assert_eq!(paths.packaging(), &Packaging::Native);
+    assert_eq!(
+        paths.config_dir(),
+        Some(PathBuf::from("/home/user/.config/hypr-keybind-manager"))
+    );
+    assert_eq!(
+        paths.state_dir(),
+        Some(PathBuf::from(
+            "/home/user/.local/state/hypr-keybind-manager"
+        ))
+    );
+    assert_eq!(
+        paths.cache_dir(),
+        Some(PathBuf::from("/home/user/.cache/hypr-keybind-manager"))
+    );
+    assert_eq!(paths.icon_dir(), None);
+}
+
+#[test]
+fn test_xdg_variables_take_precedence() {
+    let paths = paths(&[
+        ("HOME", "/home/user"),
+        ("XDG_CONFIG_HOME", "/xdg/config"),
+        ("XDG_STATE_HOME", "/xdg/state"),
+        ("XDG_CACHE_HOME", "/xdg/cache"),
+    ]);
+
+    assert_eq!(
+        paths.config_dir(),
+        Some(PathBuf::from("/xdg/config/hypr-keybind-manager"))
+    );
+    assert_eq!(
+        paths.state_dir(),
+        Some(PathBuf::from("/xdg/state/hypr-keybind-manager"))
+    );
+    assert_eq!(
+        paths.cache_dir(),
+        Some(PathBuf::from("/xdg/cache/hypr-keybind-manager"))
+    );
+}
+
+#[test]
+fn test_empty_and_relative_xdg_variables_are_ignored() {
+    let paths = paths(&[
+        ("HOME", "/home/user"),
+        ("XDG_CONFIG_HOME", ""),
+        ("XDG_STATE_HOME", "relative/state"),
+    ]);
+
+    assert_eq!(
+        paths.config_dir(),
+        Some(PathBuf::from("/home/user/.config/hypr-keybind-manager"))
+    );
+    assert_eq!(
+        paths.state_dir(),
+        Some(PathBuf::from(
+            "/home/user/.local/state/hypr-keybind-manager"
+        ))
+    );
+}
+
+#[test]
+fn test_no_home_and_no_xdg_gives_none() {
+    let paths = paths(&[]);
+
+    assert_eq!(paths.config_dir(), None);
+    assert_eq!(paths.state_dir(), None);
+    assert_eq!(paths.cache_dir(), None);
+}
+
+#[test]
+fn test_flatpak_falls_back_to_app_directory() {
+    let paths = paths(&[("HOME", "/home/user"), ("FLATPAK_ID", APP_ID)]);
+    let app = PathBuf::from("/home/user/.var/app").join(APP_ID);
+
+    assert_eq!(
+        paths.packaging(),
+        &Packaging::Flatpak {
+            app_id: APP_ID.to_string()
+        }
+    );
+    assert_eq!(
+        paths.config_dir(),
+        Some(app.join("config/hypr-keybind-manager"))
+    );
+    assert_eq!(
+        paths.state_dir(),
+        Some(app.join(".local/state/hypr-keybind-manager"))
+    );
+    assert_eq!(
+        paths.cache_dir(),
+        Some(app.join("cache/hypr-keybind-manager"))
+    );
+}
+
+#[test]
+fn test_flatpak_honours_xdg_variables() {
+    let config_home = format!("/home/user/.var/app/{}/config", APP_ID);
+    let paths = paths(&[
+        ("HOME", "/home/user"),
+        ("FLATPAK_ID", APP_ID),
+        ("XDG_CONFIG_HOME", &config_home),
+    ]);
+
+    assert_eq!(
+        paths.config_dir(),
+        Some(PathBuf::from(config_home).join("hypr-keybind-manager"))
+    );
+}
+
+#[test]
+fn test_appimage_uses_native_layout_and_bundled_icons() {
+    let paths = paths(&[("HOME", "/home/user"), ("APPDIR", "/tmp/.mount_hkm")]);
+
+    assert_eq!(
+        paths.config_dir(),
+        Some(PathBuf::from("/home/user/.config/hypr-keybind-manager"))
+    );
+    assert_eq!(
+        paths.icon_dir(),
+        Some(PathBuf::from("/tmp/.mount_hkm/usr/share/icons"))
+    );
+}
+
+#[test]
+fn test_backups_live_beside_the_config() {
+    assert_eq!(
+        RuntimePaths::backup_dir(&PathBuf::from("/home/user/.config/hypr/hyprland.conf")),
+        Some(PathBuf::from("/home/user/.config/hypr/backups"))
+    );
+    assert_eq!(RuntimePaths::backup_dir(&PathBuf::from("/")), None);
+}
+
+#[test]
+fn test_hidden_host_config_warns_with_override_command() {
+    let paths = paths(&[("HOME", "/home/user"), ("FLATPAK_ID", APP_ID)]);
+    let warning = paths
+        .sandbox_access_warning(&PathBuf::from("/home/user/.config/hypr/hyprland.conf"))
+        .expect("config outside the sandbox is hidden");
+
+    assert!(warning.contains("outside the flatpak sandbox"));
+    assert!(warning.contains(&format!(
+        "flatpak override --user --filesystem=xdg-config/hypr {}",
+        APP_ID
+    )));
+
+    let warning = paths
+        .sandbox_access_warning(&PathBuf::from("/home/user/dotfiles/hypr/hyprland.conf"))
+        .unwrap();
+    assert!(warning.contains("--filesystem=~/dotfiles/hypr "));
+}
+
+#[test]
+fn test_reachable_or_unsandboxed_config_gives_no_warning() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = temp_dir.path().join("hyprland.conf");
+    fs::write(&config, "bind = SUPER, K, exec, kitty\n").unwrap();
+
+    let flatpak = paths(&[("HOME", "/home/user"), ("FLATPAK_ID", APP_ID)]);
+    assert_eq!(flatpak.sandbox_access_warning(&config), None);
+
+    let inside = PathBuf::from("/home/user/.var/app")
+        .join(APP_ID)
+        .join("config/hypr/hyprland.conf");
+    assert_eq!(flatpak.sandbox_access_warning(&inside), None);
+
+    let native = paths(&[("HOME", "/home/user")]);
+    assert_eq!(
+        native.sandbox_access_warning(&PathBuf::from("/home/user/.config/hypr/hyprland.conf")),
+        None
+    );
+}
@@ -0,0 +1,129 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path};
+
+use tempfile::TempDir;
+
+use crate::config::{profiles::ProfileStore, ConfigError};
+use crate::core::{compare::RowStatus, parser::parse_config_with_sources};
+
+#[test]
+fn test_missing_directory_has_no_profiles() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = ProfileStore::new(temp_dir.path().join("profiles"));
+    assert!(store.list().unwrap().is_empty());
+    assert!(matches!(
+        store.load("work"),
+        Err(ConfigError::ProfileNotFound(name)) if name == "work"
+    ));
+}
+
+#[test]
+fn test_names_cannot_leave_the_profiles_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = ProfileStore::new(temp_dir.path().to_path_buf());
+
+    for name in ["", "../hyprland", "work/late", "gaming.conf", "a b"] {
+        assert!(
+            matches!(store.path(name), Err(ConfigError::InvalidProfileName(_))),
+            "{name:?} should be refused"
+        );
+    }
+    assert_eq!(
+        store.path("laptop_2-dock").unwrap(),
+        temp_dir.path().join("laptop_2-dock.conf")
+    );
+}
+
+#[test]
+fn test_save_keeps_own_bindings_with_variables_expanded() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        temp_dir.path().join("extra.conf"),
+        "bind = SUPER, E, exec, thunar\n",
+    )
+    .unwrap();
+    let config = "$mod = SUPER\n\
+                  source = ./extra.conf\n\
+                  # @category: launchers\n\
+                  bind = $mod, T, exec, kitty\n\
+                  submap = resize\n\
+                  binde = , L, resizeactive, 10 0\n\
+                  submap = reset\n";
+    let bindings = parse_config_with_sources(config, &config_path).unwrap();
+    assert_eq!(bindings.len(), 3);
+
+    let store = ProfileStore::new(temp_dir.path().join("profiles"));
+    store.save("work", &bindings).unwrap();
+
+    let saved = fs::read_to_string(temp_dir.path().join("profiles/work.conf")).unwrap();
+    assert!(saved.contains("# @category: launchers\nbind = SUPER, T, exec, kitty\n"));
+    assert!(saved.contains("submap = resize\n"));
+    assert!(!saved.contains("thunar"), "sourced bindings are not saved");
+
+    let loaded = store.load("work").unwrap();
+    let own: Vec<_> = bindings
+        .into_iter()
+        .filter(|binding| binding.source_file.is_none())
+        .collect();
+    assert_eq!(loaded, own);
+}
+
+#[test]
+fn test_list_is_sorted_and_skips_other_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = ProfileStore::new(temp_dir.path().to_path_buf());
+    store.save("work", &[]).unwrap();
+    store.save("gaming", &[]).unwrap();
+    fs::write(temp_dir.path().join("notes.txt"), "").unwrap();
+    fs::write(temp_dir.path().join("not a name.conf"), "").unwrap();
+
+    assert_eq!(store.list().unwrap(), ["gaming", "work"]);
+}
+
+#[test]
+fn test_diff_compares_two_profiles() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = ProfileStore::new(temp_dir.path().to_path_buf());
+    let parse = |content: &str| parse_config_with_sources(content, Path::new("")).unwrap();
+    store
+        .save(
+            "work",
+            &parse("bind = SUPER, T, exec, kitty\nbind = SUPER, M, exec, slack\n"),
+        )
+        .unwrap();
+    store
+        .save(
+            "gaming",
+            &parse("bind = SUPER, T, exec, foot\nbind = SUPER, G, exec, steam\n"),
+        )
+        .unwrap();
+
+    let statuses: Vec<_> = store
+        .diff("work", "gaming")
+        .unwrap()
+        .iter()
+        .map(|row| (row.key_combo.to_string(), row.status()))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("SUPER+T".to_string(), RowStatus::Changed),
+            ("SUPER+M".to_string(), RowStatus::Removed),
+            ("SUPER+G".to_string(), RowStatus::Added),
+        ]
+    );
+}
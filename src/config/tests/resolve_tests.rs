@@ -0,0 +1,109 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, os::unix::fs::PermissionsExt, path::Path};
+
+use tempfile::TempDir;
+
+use crate::config::resolve::{
+    command_target, find_missing_programs, CommandResolver, CommandTarget,
+};
+use crate::core::parser::parse_config_file;
+
+fn program(name: &str) -> Option<CommandTarget> {
+    Some(CommandTarget::Program(name.to_string()))
+}
+
+#[test]
+fn test_command_target_looks_through_wrappers_and_shells() {
+    assert_eq!(command_target("firefox --new-window"), program("firefox"));
+    assert_eq!(command_target("'my app' --flag"), program("my app"));
+    assert_eq!(
+        command_target("MOZ_ENABLE_WAYLAND=1 firefox"),
+        program("firefox")
+    );
+    assert_eq!(
+        command_target("uwsm app -- kitty -e btop"),
+        program("kitty")
+    );
+    assert_eq!(
+        command_target("systemd-run --user -u job rsync"),
+        program("rsync")
+    );
+    assert_eq!(
+        command_target("bash -c \"wofi --show drun\""),
+        program("wofi")
+    );
+    assert_eq!(
+        command_target("flatpak run --command=spotify com.spotify.Client"),
+        Some(CommandTarget::Flatpak("com.spotify.Client".to_string()))
+    );
+    assert_eq!(
+        command_target("uwsm app -- foot.desktop:new-window"),
+        Some(CommandTarget::DesktopEntry("foot.desktop".to_string()))
+    );
+    assert_eq!(
+        command_target("gtk-launch org.gnome.Nautilus"),
+        Some(CommandTarget::DesktopEntry(
+            "org.gnome.Nautilus.desktop".to_string()
+        ))
+    );
+
+    // Computed at runtime, so not judged
+    assert_eq!(command_target("$TERMINAL"), None);
+    assert_eq!(command_target(""), None);
+}
+
+#[test]
+fn test_missing_programs_are_reported() {
+    let temp_dir = TempDir::new().unwrap();
+    let bin = temp_dir.path().join("bin");
+    let applications = temp_dir.path().join("applications");
+    let flatpak = temp_dir.path().join("flatpak");
+    fs::create_dir_all(&bin).unwrap();
+    fs::create_dir_all(&applications).unwrap();
+    fs::create_dir_all(flatpak.join("app/org.gimp.GIMP")).unwrap();
+    fs::write(applications.join("foot.desktop"), "[Desktop Entry]\n").unwrap();
+    for (name, mode) in [("kitty", 0o755), ("notes.txt", 0o644)] {
+        fs::write(bin.join(name), "").unwrap();
+        fs::set_permissions(bin.join(name), fs::Permissions::from_mode(mode)).unwrap();
+    }
+    let resolver = CommandResolver {
+        path_dirs: vec![bin],
+        application_dirs: vec![applications],
+        flatpak_dirs: vec![flatpak],
+    };
+
+    let config = "bind = SUPER, Return, exec, kitty\n\
+                  bind = SUPER, N, exec, notes.txt\n\
+                  bind = SUPER, G, exec, flatpak run org.gimp.GIMP\n\
+                  bind = SUPER, S, exec, flatpak run com.spotify.Client\n\
+                  bind = SUPER, F, exec, uwsm app -- foot.desktop\n\
+                  bind = SUPER, W, exec, gtk-launch wofi\n\
+                  bind = SUPER, Q, killactive\n";
+    let bindings = parse_config_file(config, Path::new("")).unwrap();
+
+    let missing: Vec<String> = find_missing_programs(&bindings, &resolver)
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        missing,
+        vec![
+            "SUPER+N runs a missing program: 'notes.txt' is not in PATH",
+            "SUPER+S runs a missing program: Flatpak app 'com.spotify.Client' is not installed",
+            "SUPER+W runs a missing program: no desktop entry 'wofi.desktop' is installed",
+        ]
+    );
+}
@@ -0,0 +1,157 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use tempfile::TempDir;
+
+use crate::{
+    config::{
+        backup::{BackupCompression, BackupMode, RetentionPolicy},
+        danger::DangerLevel,
+        settings::{ColorScheme, DangerPolicy, Settings},
+        ConfigError,
+    },
+    core::types::BindType,
+};
+
+#[test]
+fn test_missing_file_gives_defaults() {
+    let temp_dir = TempDir::new().unwrap();
+    let settings = Settings::load(&temp_dir.path().join("settings.toml")).unwrap();
+    assert_eq!(settings, Settings::default());
+}
+
+#[test]
+fn test_save_and_load_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("nested").join("preferences");
+    let settings = Settings {
+        high_contrast: true,
+        reduce_motion: false,
+        color_scheme: ColorScheme::Dark,
+        danger_policy: DangerPolicy::Dangerous,
+        default_bind_type: BindType::BindEL,
+        confirm_delete: false,
+        reserved_combos: false,
+        review_changes: false,
+        live_apply: true,
+        strict: true,
+        notify_external_changes: true,
+        notify_validation_failures: false,
+        notify_drift: true,
+        backup_mode: BackupMode::Git,
+        backup_compression: BackupCompression::Zstd,
+        backup_retention: RetentionPolicy {
+            max_count: None,
+            max_age_days: Some(30),
+            max_total_bytes: Some(20_000_000),
+        },
+    };
+
+    settings.save(&path).unwrap();
+    assert_eq!(Settings::load(&path).unwrap(), settings);
+}
+
+#[test]
+fn test_hand_edits_with_unknown_or_bad_values_are_tolerated() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("settings.toml");
+    fs::write(
+        &path,
+        "# mine\nreduce_motion = true\nhigh_contrast = \"maybe\"\nfont_size = 12\nbackup_mode = \"svn\"\n",
+    )
+    .unwrap();
+
+    let settings = Settings::load(&path).unwrap();
+    assert!(settings.reduce_motion);
+    assert!(!settings.high_contrast);
+    assert!(settings.reserved_combos, "missing keys keep their default");
+    assert_eq!(settings.backup_mode, BackupMode::Timestamped);
+}
+
+#[test]
+fn test_backup_limits_of_zero_mean_no_limit() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("settings.toml");
+    fs::write(
+        &path,
+        "[backup_retention]\nmax_count = 0\nmax_age_days = 14\n",
+    )
+    .unwrap();
+
+    let retention = Settings::load(&path).unwrap().backup_retention;
+    assert_eq!(
+        retention,
+        RetentionPolicy {
+            max_count: None,
+            max_age_days: Some(14),
+            max_total_bytes: RetentionPolicy::default().max_total_bytes,
+        }
+    );
+}
+
+#[test]
+fn test_danger_policy_blocks_its_level_and_above() {
+    assert!(!DangerPolicy::Critical.blocks(DangerLevel::Dangerous));
+    assert!(DangerPolicy::Critical.blocks(DangerLevel::Critical));
+    assert!(DangerPolicy::Dangerous.blocks(DangerLevel::Dangerous));
+    assert!(!DangerPolicy::Dangerous.blocks(DangerLevel::Suspicious));
+    assert!(DangerPolicy::Suspicious.blocks(DangerLevel::Suspicious));
+    assert!(!DangerPolicy::Suspicious.blocks(DangerLevel::Safe));
+}
+
+#[test]
+fn test_unknown_editing_values_keep_the_defaults() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("settings.toml");
+    fs::write(
+        &path,
+        "color_scheme = \"sepia\"\ndanger_policy = \"lax\"\ndefault_bind_type = \"bindx\"\nconfirm_delete = \"no\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(Settings::load(&path).unwrap(), Settings::default());
+}
+
+#[test]
+fn test_file_that_is_not_toml_is_an_error() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("settings.toml");
+    fs::write(&path, "high_contrast = yes please\n").unwrap();
+
+    assert!(matches!(
+        Settings::load(&path),
+        Err(ConfigError::InvalidSettings(_))
+    ));
+}
+
+#[test]
+fn test_saved_file_is_toml() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("settings.toml");
+    Settings {
+        color_scheme: ColorScheme::Light,
+        ..Settings::default()
+    }
+    .save(&path)
+    .unwrap();
+
+    let table: toml::Table = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(table["color_scheme"].as_str(), Some("light"));
+    assert_eq!(
+        table["backup_retention"]["max_count"].as_integer(),
+        Some(50)
+    );
+}
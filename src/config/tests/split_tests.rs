@@ -0,0 +1,129 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use tempfile::TempDir;
+
+use crate::{
+    config::{split::plan_split, state::AppState, ConfigError, ConfigManager},
+    core::parser::parse_config_with_sources,
+};
+
+const CONFIG: &str = "\
+monitor = ,preferred,auto,1
+$mainMod = SUPER
+$terminal = kitty
+exec-once = $terminal
+
+# Applications
+bind = $mainMod, Return, exec, $terminal
+bind = $mainMod, Q, killactive
+# Window rules
+windowrulev2 = float, class:pavucontrol
+
+bind = $mainMod, F, fullscreen
+
+misc {
+    disable_hyprland_logo = true
+}
+";
+
+#[test]
+fn test_plan_split_moves_binding_runs_and_their_variables() {
+    let plan = plan_split(
+        CONFIG,
+        std::path::Path::new("/etc/hypr/hyprland.conf"),
+        std::path::Path::new("/etc/hypr/binds.conf"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        plan.main_content,
+        "\
+monitor = ,preferred,auto,1
+$terminal = kitty
+exec-once = $terminal
+
+source = /etc/hypr/binds.conf
+# Window rules
+windowrulev2 = float, class:pavucontrol
+
+misc {
+    disable_hyprland_logo = true
+}
+"
+    );
+    assert_eq!(
+        plan.binds_content,
+        "\
+# Keybindings, moved here from hyprland.conf
+
+$mainMod = SUPER
+$terminal = kitty
+
+# Applications
+bind = $mainMod, Return, exec, $terminal
+bind = $mainMod, Q, killactive
+
+bind = $mainMod, F, fullscreen
+"
+    );
+    assert_eq!(plan.moved_lines, 3);
+    assert_eq!(plan.moved_variables, vec!["mainMod".to_string()]);
+    assert_eq!(plan.copied_variables, vec!["terminal".to_string()]);
+
+    assert!(matches!(
+        plan_split(
+            "monitor = ,preferred,auto,1\n",
+            std::path::Path::new("/etc/hypr/hyprland.conf"),
+            std::path::Path::new("/etc/hypr/binds.conf"),
+        ),
+        Err(ConfigError::SplitFailed(_))
+    ));
+}
+
+#[test]
+fn test_split_bindings_keeps_every_binding() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    let binds_path = temp_dir.path().join("binds.conf");
+    let state_path = temp_dir.path().join("state");
+    fs::write(&config_path, CONFIG).unwrap();
+    let before = parse_config_with_sources(CONFIG, &config_path).unwrap();
+
+    let manager = ConfigManager::new(config_path.clone())
+        .unwrap()
+        .with_state_path(state_path.clone());
+    manager.split_bindings(&binds_path).unwrap();
+
+    let main = fs::read_to_string(&config_path).unwrap();
+    let after = parse_config_with_sources(&main, &config_path).unwrap();
+    assert_eq!(after.len(), before.len());
+    for (after, before) in after.iter().zip(&before) {
+        assert_eq!(after.key_combo, before.key_combo);
+        assert_eq!(after.args, before.args);
+        assert_eq!(after.source_file.as_deref(), Some(binds_path.as_path()));
+    }
+
+    // The original is backed up, and the new file counts as managed
+    assert_eq!(manager.list_backups().unwrap().len(), 1);
+    assert!(AppState::load(state_path).unwrap().is_managed(&binds_path));
+
+    // An existing file is never overwritten
+    assert!(matches!(
+        manager.split_bindings(&binds_path),
+        Err(ConfigError::SplitFailed(_))
+    ));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), main);
+}
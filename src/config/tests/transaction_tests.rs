@@ -16,16 +16,11 @@ use super::super::*;
 use std::{fs, thread, time::Duration};
 use tempfile::TempDir;
 
-use crate::{BindType, KeyCombo, Modifier::Super};
+use crate::{KeyCombo, Modifier::Super};
 
 /// Helper to create a test keybinding
 fn create_test_binding() -> Keybinding {
-    Keybinding {
-        key_combo: KeyCombo::new(vec![Super], "M"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some("kitty".to_string()),
-    }
+    Keybinding::new(KeyCombo::new(vec![Super], "M"), "exec", Some("kitty"))
 }
 
 // ============================================================================
@@ -406,20 +401,9 @@ bind = SUPER, F, togglefloating
 
 #[test]
 fn test_format_binding_with_modifiers() {
-    let temp_dir = TempDir::new().unwrap();
-    let config_path = temp_dir.path().join("hyprland.conf");
-    fs::write(&config_path, "initial\n").unwrap();
+    let binding = Keybinding::new(KeyCombo::new(vec![Super], "K"), "exec", Some("firefox"));
 
-    let manager = ConfigManager::new(config_path).unwrap();
-
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Super], "K"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some("firefox".to_string()),
-    };
-
-    let formatted = manager.format_binding(&binding);
+    let formatted = format_bind_line_with_variables(&binding, &VariableTable::default());
 
     // Should match Hyprland format: bind = SUPER, K, exec, firefox
     assert!(formatted.contains("bind"));
@@ -433,20 +417,13 @@ fn test_format_binding_with_modifiers() {
 
 #[test]
 fn test_format_binding_multiple_modifiers() {
-    let temp_dir = TempDir::new().unwrap();
-    let config_path = temp_dir.path().join("hyprland.conf");
-    fs::write(&config_path, "initial\n").unwrap();
-
-    let manager = ConfigManager::new(config_path).unwrap();
-
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Super, Shift], "M"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some("kitty".to_string()),
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Super, Shift], "M"),
+        "exec",
+        Some("kitty"),
+    );
 
-    let formatted = manager.format_binding(&binding);
+    let formatted = format_bind_line_with_variables(&binding, &VariableTable::default());
 
     // Should have both modifiers joined with underscore
     assert!(formatted.contains("SUPER") || formatted.contains("SHIFT"));
@@ -454,21 +431,20 @@ fn test_format_binding_multiple_modifiers() {
 }
 
 #[test]
-fn test_format_binding_no_args() {
-    let temp_dir = TempDir::new().unwrap();
-    let config_path = temp_dir.path().join("hyprland.conf");
-    fs::write(&config_path, "initial\n").unwrap();
+fn test_format_binding_writes_modifiers_in_canonical_order() {
+    let mut binding = Keybinding::new(KeyCombo::new(vec![], "M"), "exec", Some("kitty"));
+    binding.key_combo.modifiers = vec![Super, Shift, Super];
 
-    let manager = ConfigManager::new(config_path).unwrap();
+    let formatted = format_bind_line_with_variables(&binding, &VariableTable::default());
 
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Super], "Q"),
-        bind_type: BindType::Bind,
-        dispatcher: "killactive".to_string(),
-        args: None,
-    };
+    assert_eq!(formatted, "bind = SHIFT_SUPER, M, exec, kitty");
+}
+
+#[test]
+fn test_format_binding_no_args() {
+    let binding = Keybinding::new(KeyCombo::new(vec![Super], "Q"), "killactive", None);
 
-    let formatted = manager.format_binding(&binding);
+    let formatted = format_bind_line_with_variables(&binding, &VariableTable::default());
 
     // Should have dispatcher but no args
     assert!(formatted.contains("killactive"));
@@ -502,18 +478,10 @@ decoration {
 
     // New bindings (modified)
     let new_bindings = vec![
-        Keybinding {
-            key_combo: KeyCombo::new(vec![Super], "K"),
-            bind_type: BindType::Bind,
-            dispatcher: "exec".to_string(),
-            args: Some("brave".to_string()), // Changed from firefox
-        },
-        Keybinding {
-            key_combo: KeyCombo::new(vec![Super], "M"),
-            bind_type: BindType::Bind,
-            dispatcher: "exec".to_string(),
-            args: Some("alacritty".to_string()), // Changed from kitty
-        },
+        // Changed from firefox
+        Keybinding::new(KeyCombo::new(vec![Super], "K"), "exec", Some("brave")),
+        // Changed from kitty
+        Keybinding::new(KeyCombo::new(vec![Super], "M"), "exec", Some("alacritty")),
     ];
 
     // Write new bindings
@@ -586,6 +554,77 @@ bind = SUPER, K, exec, firefox
     assert!(!result.contains("firefox"));
 }
 
+#[test]
+fn test_write_bindings_keeps_submap_sections() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+
+    let initial = "bind = SUPER, R, submap, resize\n\
+                   submap = resize\n\
+                   binde = , RIGHT, resizeactive, 10 0\n\
+                   bind = , ESCAPE, submap, reset\n\
+                   submap = reset\n\
+                   \n\
+                   # Other settings\n\
+                   general {\n\
+                   }\n";
+    fs::write(&config_path, initial).unwrap();
+
+    let mut manager = ConfigManager::new(config_path.clone()).unwrap();
+    let mut bindings = crate::core::parser::parse_config_file(initial, &config_path).unwrap();
+    bindings.push(create_test_binding());
+
+    manager.write_bindings(&bindings).unwrap();
+    let result = fs::read_to_string(&config_path).unwrap();
+
+    assert_eq!(
+        result,
+        "bind = SUPER, R, submap, resize\n\
+         bind = SUPER, M, exec, kitty\n\
+         submap = resize\n\
+         binde = , RIGHT, resizeactive, 10 0\n\
+         bind = , ESCAPE, submap, reset\n\
+         submap = reset\n\
+         \n\
+         # Other settings\n\
+         general {\n\
+         }\n",
+        "New global bindings stay outside the submap section"
+    );
+
+    // The new global binding follows the last global bind line
+    let reparsed = crate::core::parser::parse_config_file(&result, &config_path).unwrap();
+    assert_eq!(reparsed.len(), bindings.len());
+    assert!(bindings.iter().all(|binding| reparsed.contains(binding)));
+}
+
+#[test]
+fn test_write_bindings_leaves_sourced_files_alone() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    let keybinds_path = temp_dir.path().join("keybinds.conf");
+
+    let initial = "source = keybinds.conf\nbind = SUPER, Q, killactive\n# End\n";
+    let sourced = "bind = SUPER, K, exec, firefox\n";
+    fs::write(&config_path, initial).unwrap();
+    fs::write(&keybinds_path, sourced).unwrap();
+
+    let mut manager = ConfigManager::new(config_path.clone()).unwrap();
+    let mut bindings =
+        crate::core::parser::parse_config_with_sources(initial, &config_path).unwrap();
+    assert_eq!(bindings.len(), 2);
+    bindings.push(create_test_binding());
+
+    manager.write_bindings(&bindings).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "source = keybinds.conf\nbind = SUPER, Q, killactive\nbind = SUPER, M, exec, kitty\n# End\n",
+        "Sourced bindings must not be copied into the main config"
+    );
+    assert_eq!(fs::read_to_string(&keybinds_path).unwrap(), sourced);
+}
+
 #[test]
 fn test_write_bindings_creates_backup() {
     let temp_dir = TempDir::new().unwrap();
@@ -615,3 +654,25 @@ fn test_write_bindings_creates_backup() {
         "Backup should have original binding"
     );
 }
+
+#[test]
+fn test_preview_shows_the_change_without_writing() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    let original_content = "# Apps\nbind = SUPER, K, exec, firefox\n";
+    fs::write(&config_path, original_content).unwrap();
+
+    let manager = ConfigManager::new(config_path.clone()).unwrap();
+    let preview = manager.preview_bindings(&[create_test_binding()]).unwrap();
+    assert_eq!(
+        preview.to_unified("old", "new"),
+        "--- old\n+++ new\n@@ -1,2 +1,2 @@\n # Apps\n-bind = SUPER, K, exec, firefox\n+bind = SUPER, M, exec, kitty\n"
+    );
+    assert_eq!(manager.list_backups().unwrap().len(), 0);
+
+    let tx = ConfigTransaction::begin(&manager).unwrap();
+    let new_content = "# Apps\nbind = SUPER, M, exec, kitty\n";
+    assert_eq!(tx.preview(new_content).unwrap(), preview);
+    assert!(tx.preview(original_content).unwrap().is_empty());
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), original_content);
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::config::{danger::DangerLevel, validator::ConfigValidator};
+use crate::core::compat::HyprlandVersion;
 
 #[test]
 fn test_injection_blocked_by_layer1() {
@@ -148,3 +149,41 @@ bind = SUPER, F, togglefloating
     );
     assert_eq!(report.highest_danger, DangerLevel::Safe);
 }
+
+#[test]
+fn test_workspace_warnings_allow_commit() {
+    // Valid but suspicious workspace targets warn without blocking
+    let validator = ConfigValidator::new();
+    let config = "bind = SUPER, 1, workspace, 1\nbind = SUPER, 0, workspace, 11\n";
+
+    let report = validator.validate_config(config);
+
+    assert!(!report.has_errors(), "Workspace warnings are not errors");
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].binding_index, 1);
+    assert!(report.issues[0]
+        .message
+        .starts_with("Workspace: workspace 11"));
+}
+
+#[test]
+fn test_compatibility_warnings_allow_commit() {
+    // Dispatchers the targeted release lacks warn without blocking
+    let config = "bind = SUPER, F, fullscreenstate, 2 2\nbind = SUPER, Q, killactive\n";
+
+    let report = ConfigValidator::new()
+        .with_hyprland_version(HyprlandVersion::new(0, 41, 0))
+        .validate_config(config);
+
+    assert!(
+        !report.has_errors(),
+        "Compatibility warnings are not errors"
+    );
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].binding_index, 0);
+    assert!(report.issues[0].message.starts_with("Compatibility:"));
+
+    // Without a version, dispatchers are not checked
+    let report = ConfigValidator::new().validate_config(config);
+    assert!(report.issues.is_empty());
+}
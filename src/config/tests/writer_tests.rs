@@ -0,0 +1,322 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::{
+    config::writer::{replace_bind_line, rewrite_bind_lines},
+    core::{parser::parse_config_file, KeyCombo, Keybinding, Modifier},
+};
+
+const GROUPED: &str = "$mainMod = SUPER\n\
+                       \n\
+                       # Apps\n\
+                       bind = $mainMod, B, exec, firefox\n\
+                       bind = $mainMod, RETURN, exec, kitty\n\
+                       \n\
+                       # Windows\n\
+                       \x20   bind = $mainMod, Q, killactive # close\n\
+                       bind = $mainMod, F, fullscreen\n";
+
+fn parse(content: &str) -> Vec<Keybinding> {
+    parse_config_file(content, Path::new("hyprland.conf")).unwrap()
+}
+
+#[test]
+fn test_unchanged_bindings_round_trip_byte_for_byte() {
+    let bindings = parse(GROUPED);
+    assert_eq!(rewrite_bind_lines(GROUPED, &bindings).unwrap(), GROUPED);
+
+    let no_trailing_newline = GROUPED.trim_end();
+    assert_eq!(
+        rewrite_bind_lines(no_trailing_newline, &bindings).unwrap(),
+        no_trailing_newline
+    );
+}
+
+#[test]
+fn test_edit_rewrites_only_the_changed_line_in_place() {
+    let mut bindings = parse(GROUPED);
+    bindings[2].dispatcher = "closewindow".to_string();
+
+    let result = rewrite_bind_lines(GROUPED, &bindings).unwrap();
+    assert_eq!(
+        result,
+        GROUPED.replace(
+            "    bind = $mainMod, Q, killactive # close",
+            "    bind = $mainMod, Q, closewindow"
+        )
+    );
+}
+
+#[test]
+fn test_delete_and_add_keep_other_lines_in_place() {
+    let mut bindings = parse(GROUPED);
+    let removed = bindings.remove(0);
+    bindings.insert(
+        3,
+        Keybinding {
+            args: Some("thunar".to_string()),
+            ..removed
+        },
+    );
+
+    let result = rewrite_bind_lines(GROUPED, &bindings).unwrap();
+    assert_eq!(
+        result,
+        "$mainMod = SUPER\n\
+         \n\
+         # Apps\n\
+         bind = $mainMod, RETURN, exec, kitty\n\
+         \n\
+         # Windows\n\
+         \x20   bind = $mainMod, Q, killactive # close\n\
+         bind = $mainMod, F, fullscreen\n\
+         bind = $mainMod, B, exec, thunar\n"
+    );
+}
+
+#[test]
+fn test_new_submap_binding_opens_a_section() {
+    let mut bindings = parse(GROUPED);
+    bindings.push(Keybinding {
+        submap: Some("resize".to_string()),
+        ..parse("bind = , ESCAPE, submap, reset")[0].clone()
+    });
+
+    let result = rewrite_bind_lines(GROUPED, &bindings).unwrap();
+    assert!(result.starts_with(GROUPED));
+    assert!(result.ends_with("\nsubmap = resize\nbind = , ESCAPE, submap, reset\nsubmap = reset\n"));
+    assert_eq!(parse(&result), bindings);
+
+    // Removing its only binding removes the section again
+    bindings.pop();
+    assert_eq!(rewrite_bind_lines(&result, &bindings).unwrap(), GROUPED);
+}
+
+#[test]
+fn test_written_lines_use_config_variables() {
+    let content = "$mainMod = SUPER\n\
+                   $terminal = kitty\n\
+                   bind = $mainMod, RETURN, exec, $terminal\n";
+    let bindings = parse("bind = SUPER_SHIFT, RETURN, exec, kitty\nbind = SUPER, 1, workspace, 1");
+
+    let result = rewrite_bind_lines(content, &bindings).unwrap();
+    assert_eq!(
+        result,
+        "$mainMod = SUPER\n\
+         $terminal = kitty\n\
+         bind = $mainMod SHIFT, RETURN, exec, $terminal\n\
+         bind = $mainMod, 1, workspace, 1\n"
+    );
+    assert_eq!(parse(&result), bindings);
+}
+
+#[test]
+fn test_catchall_is_written_in_lowercase() {
+    let content = "submap = launch\nbind = , catchall, submap, reset\nsubmap = reset\n";
+    let mut bindings = parse(content);
+    bindings[0].dispatcher = "exec".to_string();
+    bindings[0].args = Some("notify-send bye".to_string());
+
+    let result = rewrite_bind_lines(content, &bindings).unwrap();
+    assert_eq!(
+        result,
+        "submap = launch\nbind = , catchall, exec, notify-send bye\nsubmap = reset\n"
+    );
+}
+
+#[test]
+fn test_flag_combinations_survive_rewriting() {
+    let content = "bindtn = SUPER, K, exec, kitty\n\
+                   bindd = SUPER, Q, Close window, killactive\n";
+    let mut bindings = parse(content);
+    bindings[0].args = Some("foot".to_string());
+    bindings[1].dispatcher = "closewindow".to_string();
+
+    let result = rewrite_bind_lines(content, &bindings).unwrap();
+    assert_eq!(
+        result,
+        "bindnt = SUPER, K, exec, foot\n\
+         bindd = SUPER, Q, Close window, closewindow\n"
+    );
+    assert_eq!(parse(&result), bindings);
+}
+
+#[test]
+fn test_replace_bind_line_touches_only_the_located_line() {
+    let bindings = parse(GROUPED);
+    let old = &bindings[2];
+    let mut new = old.clone();
+    new.dispatcher = "closewindow".to_string();
+
+    let result = replace_bind_line(GROUPED, old, &new).unwrap();
+    assert_eq!(
+        result,
+        GROUPED.replace(
+            "    bind = $mainMod, Q, killactive # close",
+            "    bind = $mainMod, Q, closewindow"
+        )
+    );
+
+    // A location that no longer holds the binding is not trusted
+    let mut stale = old.clone();
+    stale.location.as_mut().unwrap().line -= 1;
+    assert_eq!(replace_bind_line(GROUPED, &stale, &new), None);
+    // Neither is a binding that was never read from a line
+    stale.location = None;
+    assert_eq!(replace_bind_line(GROUPED, &stale, &new), None);
+}
+
+#[test]
+fn test_unbind_lines_and_the_lines_they_cancel_are_kept() {
+    let content = "bind = SUPER, Q, killactive\n\
+                   bind = SUPER, F, fullscreen\n\
+                   unbind = SUPER, Q\n";
+    let mut bindings = parse(content);
+    assert_eq!(bindings.len(), 1);
+    bindings[0].dispatcher = "togglefloating".to_string();
+
+    let result = rewrite_bind_lines(content, &bindings).unwrap();
+    assert_eq!(
+        result,
+        "bind = SUPER, Q, killactive\n\
+         bind = SUPER, F, togglefloating\n\
+         unbind = SUPER, Q\n"
+    );
+
+    // A new SUPER+Q goes below the unbind, where it takes effect
+    let mut added = parse(&result);
+    added.push(Keybinding {
+        dispatcher: "exec".to_string(),
+        args: Some("kitty".to_string()),
+        ..parse("bind = SUPER, Q, killactive\n").remove(0)
+    });
+    let result = rewrite_bind_lines(&result, &added).unwrap();
+    assert!(result.ends_with("unbind = SUPER, Q\nbind = SUPER, Q, exec, kitty\n"));
+    assert_eq!(parse(&result), added);
+}
+
+#[test]
+fn test_category_annotations_stay_with_their_bind_lines() {
+    let content = "# @category: launchers\n\
+                   bind = SUPER, B, exec, firefox\n\
+                   #@category:media\n\
+                   bind = SUPER, P, exec, playerctl play-pause\n";
+    let mut bindings = parse(content);
+    assert_eq!(bindings[1].category.as_deref(), Some("media"));
+
+    // An unchanged category keeps the annotation exactly as written
+    bindings[1].args = Some("playerctl pause".to_string());
+    let result = rewrite_bind_lines(content, &bindings).unwrap();
+    assert_eq!(result, content.replace("play-pause", "pause"));
+
+    // A new binding written above the first line does not take its category
+    let mut added = bindings.clone();
+    added.insert(
+        0,
+        Keybinding {
+            category: None,
+            ..parse("bind = SUPER, K, exec, kitty")[0].clone()
+        },
+    );
+    let result = rewrite_bind_lines(content, &added).unwrap();
+    assert!(result.starts_with("bind = SUPER, K, exec, kitty\n# @category: launchers\n"));
+    assert_eq!(parse(&result), added);
+
+    // Changing or clearing a category rewrites the annotation; deleting a
+    // binding removes it
+    bindings[0].category = Some("browsers".to_string());
+    bindings.remove(1);
+    let result = rewrite_bind_lines(content, &bindings).unwrap();
+    assert_eq!(
+        result,
+        "# @category: browsers\nbind = SUPER, B, exec, firefox\n"
+    );
+    bindings[0].category = None;
+    assert_eq!(
+        rewrite_bind_lines(&result, &bindings).unwrap(),
+        "bind = SUPER, B, exec, firefox\n"
+    );
+}
+
+#[test]
+fn test_device_bindings_stay_in_their_device_sections() {
+    let config = "device {\n\
+                  \x20   name = stream-deck\n\
+                  \x20   bind = , F13, exec, obs\n\
+                  }\n\
+                  bind = SUPER, Q, killactive\n";
+    let mut bindings = parse(config);
+    bindings[0].args = Some("obs --minimize-to-tray".to_string());
+    let deck = Keybinding {
+        key_combo: KeyCombo::new(vec![], "F14"),
+        ..bindings[0].clone()
+    };
+    let pad = Keybinding {
+        device: Some("macro-pad".to_string()),
+        args: Some("kitty".to_string()),
+        ..deck.clone()
+    };
+    let global = Keybinding {
+        key_combo: KeyCombo::new(vec![Modifier::Super], "F"),
+        dispatcher: "fullscreen".to_string(),
+        ..bindings[1].clone()
+    };
+    bindings.insert(1, deck);
+    bindings.push(global);
+    bindings.push(pad);
+
+    let result = rewrite_bind_lines(config, &bindings).unwrap();
+    assert_eq!(
+        result,
+        "device {\n\
+         \x20   name = stream-deck\n\
+         \x20   bind = , F13, exec, obs --minimize-to-tray\n\
+         \x20   bind = , F14, exec, obs --minimize-to-tray\n\
+         }\n\
+         bind = SUPER, Q, killactive\n\
+         bind = SUPER, F, fullscreen\n\
+         \n\
+         device {\n\
+         \x20   name = macro-pad\n\
+         \x20   bind = , F14, exec, kitty\n\
+         }\n"
+    );
+    assert_eq!(parse(&result), bindings);
+}
+
+#[test]
+fn test_emptied_submap_keeps_its_markers_around_other_lines() {
+    let content = "bind = SUPER, Q, killactive\n\
+                   \n\
+                   submap = resize\n\
+                   unbind = SUPER, Q\n\
+                   bind = , ESCAPE, submap, reset\n\
+                   submap = reset\n";
+    let global: Vec<Keybinding> = parse(content)
+        .into_iter()
+        .filter(|binding| binding.submap.is_none())
+        .collect();
+
+    assert_eq!(
+        rewrite_bind_lines(content, &global).unwrap(),
+        "bind = SUPER, Q, killactive\n\
+         \n\
+         submap = resize\n\
+         unbind = SUPER, Q\n\
+         submap = reset\n",
+        "the unbind stays inside its submap"
+    );
+}
@@ -14,13 +14,16 @@
 
 //! Configuration transaction management with automatic backups
 //!
-//! Provides atomic write operations with ACID guarantees.
+//! Provides atomic write operations with ACID guarantees, and a preview of
+//! what a commit would change.
 
 use atomic_write_file::AtomicWriteFile;
-use std::{fs, io::Write, path::PathBuf};
+use std::{io::Write, path::PathBuf};
 
 use crate::config::{
+    backup::describe_change,
     danger,
+    diff::ConfigDiff,
     validator::{
         ConfigValidator,
         ValidationLevel::{Error, Warning},
@@ -39,7 +42,8 @@ use crate::config::{
 /// # Lifecycle
 ///
 /// 1. `begin()` - Creates timestamped backup immediately
-/// 2. User prepares new content (in memory)
+/// 2. User prepares new content (in memory), optionally checking it with
+///    `preview()`
 /// 3. `commit()` - Writes atomically or `rollback()` - Restores original
 ///
 /// # Example
@@ -67,14 +71,22 @@ use crate::config::{
 #[allow(dead_code)]
 pub struct ConfigTransaction<'a> {
     manager: &'a ConfigManager,
+    /// Timestamped backup made by `begin()` (`None` with git backups)
     backup_path: Option<PathBuf>,
+    /// Config content when the transaction began, the rollback point
+    original: String,
+    /// Describes the write to the backup backend (`None`: derived from the
+    /// binding changes)
+    message: Option<String>,
 }
 
 impl<'a> ConfigTransaction<'a> {
-    /// Begins a new transaction by creating a timestamped backup.
+    /// Begins a new transaction by backing up the config.
     ///
     /// The backup is created immediately when `begin()` is called, ensuring
     /// that a rollback point exists before any modifications are attempted.
+    /// It is a timestamped file or a git commit, depending on the manager's
+    /// [`BackupMode`](crate::config::backup::BackupMode).
     ///
     /// # Arguments
     ///
@@ -91,7 +103,7 @@ impl<'a> ConfigTransaction<'a> {
     /// Returns an error if:
     /// - Backup directory cannot be created
     /// - Config file cannot be read
-    /// - Backup file cannot be written
+    /// - Backup file cannot be written, or the git commit fails
     ///
     /// # Example
     ///
@@ -105,15 +117,73 @@ impl<'a> ConfigTransaction<'a> {
     /// # Ok::<(), hypr_keybind_manager::config::ConfigError>(())
     /// ```
     pub fn begin(manager: &'a ConfigManager) -> Result<Self, ConfigError> {
+        // Keep the original config on the very first write to it
+        manager.ensure_pristine_backup()?;
+
         // Create backup immediately - this is our rollback point
-        let backup_path = manager.create_timestamped_backup()?;
+        let original = manager.read_config()?;
+        let backup_path = manager.create_backup()?;
 
         Ok(Self {
             manager,
-            backup_path: Some(backup_path),
+            backup_path,
+            original,
+            message: None,
         })
     }
 
+    /// Sets how the write is described to the backup backend.
+    ///
+    /// Git backups use it as the commit message. Without one, the message
+    /// lists the bindings the commit adds, removes or changes (see
+    /// [`describe_change`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - What the write does, e.g. `Move keybindings into binds.conf`
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Shows what committing `new_content` would change.
+    ///
+    /// Compares the config as it is now with `new_content`, line by line.
+    /// Nothing is written, so the transaction can still be committed or
+    /// rolled back afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_content` - The complete new configuration content
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ConfigDiff)` - The changes (empty when the content is the same)
+    /// * `Err(ConfigError)` - Config file cannot be read
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypr_keybind_manager::config::{ConfigManager, ConfigTransaction};
+    /// use std::path::PathBuf;
+    ///
+    /// let manager = ConfigManager::new(PathBuf::from("hyprland.conf"))?;
+    /// let tx = ConfigTransaction::begin(&manager)?;
+    ///
+    /// let new_content = "bind = SUPER, K, exec, firefox\n";
+    /// let diff = tx.preview(new_content)?;
+    /// print!("{}", diff.to_unified("hyprland.conf", "hyprland.conf (new)"));
+    ///
+    /// if !diff.is_empty() {
+    ///     tx.commit(new_content)?;
+    /// }
+    /// # Ok::<(), hypr_keybind_manager::config::ConfigError>(())
+    /// ```
+    pub fn preview(&self, new_content: &str) -> Result<ConfigDiff, ConfigError> {
+        let current = self.manager.read_config()?;
+        Ok(ConfigDiff::between(&current, new_content))
+    }
+
     /// Commits the transaction with comprehensive validation
     ///
     /// This method validates the config before committing:
@@ -223,7 +293,9 @@ impl<'a> ConfigTransaction<'a> {
     ///
     /// The write operation is atomic at the filesystem level (temp file + rename),
     /// ensuring the config is never in a partially-written state. The backup created
-    /// during `begin()` remains available for manual rollback if needed.
+    /// during `begin()` remains available for manual rollback if needed; older
+    /// backups the manager's [`RetentionPolicy`](crate::config::backup::RetentionPolicy)
+    /// does not keep are deleted afterwards.
     ///
     /// This method consumes the transaction, preventing accidental double-commits.
     ///
@@ -280,12 +352,27 @@ impl<'a> ConfigTransaction<'a> {
             ConfigError::WriteFailed(format!("Failed to commit atomic write: {}", e))
         })?;
 
-        // Backup remains in backup directory for future rollback if needed
-        // Cleanup is handled separately by cleanup_old_backups()
+        // Backup remains in backup directory for future rollback if needed;
+        // older ones go by the manager's retention policy
+        let config_path = &self.manager.config_path;
+        let message = self.message.unwrap_or_else(|| {
+            let file_name = config_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            describe_change(
+                config_path,
+                &self.original,
+                new_content,
+                &format!("Update {file_name}"),
+            )
+        });
+        self.manager.record_write(&message);
+        self.manager.apply_retention();
         Ok(())
     }
 
-    /// Rolls back to the backup created during `begin()`.
+    /// Rolls back to the config as it was during `begin()`.
     ///
     /// Atomically restores the configuration file to its state when the transaction
     /// began. This can be called after a failed commit or to manually undo changes.
@@ -295,15 +382,12 @@ impl<'a> ConfigTransaction<'a> {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Config restored successfully from backup
-    /// * `Err(ConfigError)` - Rollback failed (backup unreadable or write failed)
+    /// * `Ok(())` - Config restored successfully
+    /// * `Err(ConfigError)` - Rollback failed (atomic write failed)
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - No backup path is available (should not happen in normal usage)
-    /// - Backup file cannot be read
-    /// - Atomic write of backup content fails
+    /// Returns an error if the atomic write of the original content fails.
     ///
     /// # Example
     ///
@@ -322,32 +406,21 @@ impl<'a> ConfigTransaction<'a> {
     /// # Ok::<(), hypr_keybind_manager::config::ConfigError>(())
     /// ```
     pub fn rollback(&self) -> Result<(), ConfigError> {
-        // Check if backup path is available
-        if let Some(backup_path) = &self.backup_path {
-            // Read backup content
-            let backup_content = fs::read_to_string(backup_path)?;
-
-            // Open file for atomic writing
-            let mut file = AtomicWriteFile::options()
-                .open(&self.manager.config_path)
-                .map_err(|e| {
-                    ConfigError::WriteFailed(format!("Failed to open for atomic write: {}", e))
-                })?;
+        // Open file for atomic writing
+        let mut file = AtomicWriteFile::options()
+            .open(&self.manager.config_path)
+            .map_err(|e| {
+                ConfigError::WriteFailed(format!("Failed to open for atomic write: {}", e))
+            })?;
 
-            // Write backup content
-            file.write_all(backup_content.as_bytes())
-                .map_err(|e| ConfigError::WriteFailed(format!("Failed to write content: {}", e)))?;
+        // Write the content the backup was made of
+        file.write_all(self.original.as_bytes())
+            .map_err(|e| ConfigError::WriteFailed(format!("Failed to write content: {}", e)))?;
 
-            // Commit atomically
-            file.commit()
-                .map_err(|e| ConfigError::WriteFailed(format!("Failed to commit: {}", e)))?;
+        // Commit atomically
+        file.commit()
+            .map_err(|e| ConfigError::WriteFailed(format!("Failed to commit: {}", e)))?;
 
-            Ok(())
-        } else {
-            // This should not happen in normal usage (begin() always creates backup)
-            Err(ConfigError::BackupFailed(
-                "No backup available for rollback".to_string(),
-            ))
-        }
+        Ok(())
     }
 }
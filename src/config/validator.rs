@@ -38,7 +38,11 @@
 //! ```
 
 use crate::config::danger::{DangerAssessment, DangerDetector, DangerLevel};
-use crate::core::{parser::parse_config_file, validator as injection_validator};
+use crate::core::{
+    compat::{self, HyprlandVersion},
+    parser::parse_config_file,
+    validator as injection_validator, Keybinding,
+};
 use std::path::Path;
 
 /// Validation severity level
@@ -186,6 +190,8 @@ impl ValidationReport {
 #[allow(dead_code)]
 pub struct ConfigValidator {
     danger_detector: DangerDetector,
+    /// Release whose dispatchers bindings are checked against, if known
+    hyprland_version: Option<HyprlandVersion>,
 }
 
 impl Default for ConfigValidator {
@@ -200,9 +206,33 @@ impl ConfigValidator {
     pub fn new() -> Self {
         Self {
             danger_detector: DangerDetector::new(),
+            hyprland_version: None,
         }
     }
 
+    /// Also warns about dispatchers a Hyprland release lacks
+    ///
+    /// Typically the version the running Hyprland reports over IPC; without
+    /// one, dispatchers are not checked against any release. The warnings
+    /// never block a commit, as a config may be shared between machines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hypr_keybind_manager::config::validator::ConfigValidator;
+    /// use hypr_keybind_manager::core::compat::HyprlandVersion;
+    ///
+    /// let validator = ConfigValidator::new().with_hyprland_version(HyprlandVersion::new(0, 41, 0));
+    /// let report = validator.validate_config("bind = SUPER, F, fullscreenstate, 2 2");
+    ///
+    /// assert!(!report.has_errors());
+    /// assert!(report.issues[0].message.starts_with("Compatibility:"));
+    /// ```
+    pub fn with_hyprland_version(mut self, version: HyprlandVersion) -> Self {
+        self.hyprland_version = Some(version);
+        self
+    }
+
     /// Validates a complete config file content
     ///
     /// Runs all validation layers:
@@ -236,19 +266,34 @@ impl ConfigValidator {
     /// ```
     #[allow(dead_code)]
     pub fn validate_config(&self, content: &str) -> ValidationReport {
-        let mut report = ValidationReport::new();
-
         // Step 1: Parse the config file
-        let bindings = match parse_config_file(content, Path::new("")) {
-            Ok(b) => b,
+        match parse_config_file(content, Path::new("")) {
+            // Step 2: Validate each binding
+            Ok(bindings) => self.validate_bindings(&bindings),
             Err(e) => {
                 // Parse error - add as error and return immediately
+                let mut report = ValidationReport::new();
                 report.add_error(0, format!("Parse error: {}", e));
-                return report;
+                report
             }
-        };
+        }
+    }
+
+    /// Validates already parsed bindings
+    ///
+    /// Steps 2-4 of [`Self::validate_config`], for callers that hold the
+    /// bindings already (and for measuring validation on its own).
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - Bindings to check; issues refer to their indices
+    ///
+    /// # Returns
+    ///
+    /// A `ValidationReport` as returned by [`Self::validate_config`]
+    pub fn validate_bindings(&self, bindings: &[Keybinding]) -> ValidationReport {
+        let mut report = ValidationReport::new();
 
-        // Step 2: Validate each binding
         for (binding_index, binding) in bindings.iter().enumerate() {
             // Layer 1: Injection prevention check
             if let Err(e) = injection_validator::validate_keybinding(binding) {
@@ -257,6 +302,25 @@ impl ConfigValidator {
                 continue;
             }
 
+            // Valid workspace targets that are probably mistakes
+            for warning in injection_validator::workspace_warnings(
+                &binding.dispatcher,
+                binding.args.as_deref(),
+            ) {
+                report.add_warning(
+                    binding_index,
+                    format!("Workspace: {}", warning),
+                    warning.suggestion(),
+                );
+            }
+
+            // Dispatchers the targeted release does not have
+            if let Some(version) = self.hyprland_version {
+                if let Some(warning) = compat::dispatcher_warning(&binding.dispatcher, version) {
+                    report.add_warning(binding_index, format!("Compatibility: {}", warning), None);
+                }
+            }
+
             // Layer 2: Danger assessment (only for exec dispatcher)
             if binding.dispatcher == "exec" {
                 if let Some(args) = &binding.args {
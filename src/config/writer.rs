@@ -0,0 +1,695 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Position-preserving bind line writer
+//!
+//! Rewrites only the bind lines that actually changed, so grouped sections,
+//! comments above binds and variables such as `$mainMod` survive a write.
+//!
+//! The bind lines of the original config are aligned with the new binding
+//! list using a longest common subsequence, the way a text diff aligns
+//! lines:
+//!
+//! - Bind lines whose binding is still present are kept byte-for-byte
+//! - Between two kept lines, changed bindings replace the old lines in place
+//!   (keeping their indentation), extra old lines are removed and extra new
+//!   bindings are inserted after the last line before them
+//! - New bindings of a submap go into that submap's section; submaps the
+//!   config does not have yet get a new section after the last bind line
+//! - Likewise, new bindings of an input device go into that device's
+//!   section, or a new `device { name = ... }` section; other new bindings
+//!   never go inside a device section
+//! - Written lines use the config's variables (`$mainMod`) where they fit,
+//!   see [`format_bind_line_with_variables`]
+//! - A `# @category:` annotation stays with its bind line: it is kept
+//!   while the category is unchanged, rewritten when it changes and removed
+//!   along with the line. New bindings with a category get one above them
+//! - `unbind` lines are kept, and so are the bind lines they cancel, which
+//!   the parser never returns. New bindings of an unbound key combo go
+//!   after the last unbind of it, so the unbind does not cancel them
+//! - New bindings never go inside a block of `exec-once`, `env` or rule
+//!   lines ([`ConfigDocument::protected_blocks`]). Bindings next to a bind
+//!   line a user put inside such a block go below the block (or above it,
+//!   for bindings before the line), unless another bind line is in the way
+//!   and moving them would change their order
+//!
+//! Lines are classified once, by [`ConfigDocument`]; bind lines the parser
+//! cannot read count as bind lines for positioning but are never rewritten.
+//!
+//! A single edited binding that knows its line ([`Keybinding::location`])
+//! is replaced on that line directly by [`replace_bind_line`], without
+//! aligning the whole config, and bindings dropped by `check --fix` are
+//! removed or commented out on their lines by [`drop_bind_lines`].
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Range,
+};
+
+use crate::{
+    config::{
+        autofix::FixMode,
+        document::{ConfigDocument, DocumentLine, LineKind},
+        format_annotated_bind_line, format_bind_line_with_variables, ConfigManager,
+    },
+    core::{
+        parser::{parse_category_annotation, VariableTable},
+        types::{submap_names, Keybinding, Unbind, SUBMAP_RESET},
+    },
+};
+
+/// A bind line of the original config
+struct BindLine {
+    /// Line index (0-based)
+    index: usize,
+    /// The binding as the parser reads the line
+    binding: Keybinding,
+}
+
+/// Rewrites the bind lines of `original` to hold exactly `bindings`
+///
+/// Lines that are not bind lines are never touched, and neither are bind
+/// lines whose binding is unchanged.
+///
+/// # Arguments
+/// * `original` - Current config content
+/// * `bindings` - Bindings the config should contain, in order
+///
+/// # Returns
+/// * `Some(content)` - The rewritten config
+/// * `None` - The config has no bind lines to position new ones against
+pub fn rewrite_bind_lines(original: &str, bindings: &[Keybinding]) -> Option<String> {
+    rewrite_document(&ConfigDocument::parse(original), bindings)
+}
+
+/// [`rewrite_bind_lines`] for a parsed document
+///
+/// # Returns
+/// * `Some(content)` - The rewritten config
+/// * `None` - The document has no bind lines to position new ones against
+pub(crate) fn rewrite_document(
+    document: &ConfigDocument,
+    bindings: &[Keybinding],
+) -> Option<String> {
+    let lines = texts(document);
+    let (existing, unbinds) = bind_lines(document);
+    let variables = document.variables();
+    let device_blocks = document.device_blocks();
+    let device_block_at = |index: usize| {
+        device_blocks
+            .iter()
+            .find(|(range, _)| range.contains(&index))
+            .map(|(range, _)| range.clone())
+    };
+
+    // New global lines and sections go outside device sections
+    let first_line = document.lines().iter().position(is_bind_or_submap_line)?;
+    let first_line = device_block_at(first_line).map_or(first_line, |block| block.start);
+    let last_line = document.lines().iter().rposition(is_bind_or_submap_line)?;
+    let last_line = device_block_at(last_line).map_or(last_line, |block| block.end - 1);
+
+    let mut replaced: HashMap<usize, String> = HashMap::new();
+    let mut removed: HashSet<usize> = HashSet::new();
+    let mut after: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    let mut before: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    let mut new_sections: Vec<&Keybinding> = Vec::new();
+    let mut new_devices: Vec<&Keybinding> = Vec::new();
+
+    // Latest line (in new order) holding a binding of each submap and device
+    let mut latest: HashMap<(Option<&str>, Option<&str>), usize> = HashMap::new();
+
+    // Last unbind in the binding's section that would cancel it if written
+    // above; the binding has to go below it
+    let barrier = |binding: &Keybinding| {
+        unbinds
+            .iter()
+            .filter(|line| line.submap == binding.submap && line.unbind.cancels(binding))
+            .map(|line| line.index)
+            .max()
+    };
+
+    let matches = longest_common_subsequence(&existing, bindings);
+    let mut old_start = 0;
+    let mut new_start = 0;
+
+    for (old_end, new_end) in matches
+        .iter()
+        .copied()
+        .chain(std::iter::once((existing.len(), bindings.len())))
+    {
+        let old_gap = &existing[old_start..old_end];
+        let new_gap = &bindings[new_start..new_end];
+
+        for k in 0..old_gap.len().max(new_gap.len()) {
+            match (old_gap.get(k), new_gap.get(k)) {
+                (Some(old), Some(new))
+                    if old.binding.submap == new.submap
+                        && old.binding.device == new.device
+                        && barrier(new).is_none_or(|unbind| unbind < old.index) =>
+                {
+                    let replacement = rewrite_line(document, old, new, &variables, &mut removed);
+                    replaced.insert(old.index, replacement);
+                    latest.insert(scope_of(new), old.index);
+                }
+                (old, new) => {
+                    if let Some(old) = old {
+                        removed.insert(old.index);
+                        removed.extend(document.annotation_lines(old.index));
+                    }
+                    let Some(new) = new else { continue };
+
+                    let scope = scope_of(new);
+                    let barrier = barrier(new);
+                    if let Some(&line) = latest
+                        .get(&scope)
+                        .filter(|&&line| barrier.is_none_or(|unbind| unbind < line))
+                    {
+                        after.entry(line).or_default().push(with_indent(
+                            lines[line],
+                            new,
+                            &variables,
+                        ));
+                    } else if let Some(unbind) = barrier {
+                        after.entry(unbind).or_default().push(with_indent(
+                            lines[unbind],
+                            new,
+                            &variables,
+                        ));
+                    } else if let Some(first) = existing
+                        .iter()
+                        .find(|bind| scope_of(&bind.binding) == scope)
+                    {
+                        before.entry(first.index).or_default().push(with_indent(
+                            lines[first.index],
+                            new,
+                            &variables,
+                        ));
+                    } else if new.device.is_some() {
+                        new_devices.push(new);
+                    } else if new.submap.is_none() {
+                        before
+                            .entry(first_line)
+                            .or_default()
+                            .push(format_annotated_bind_line(new, &variables));
+                    } else {
+                        new_sections.push(new);
+                    }
+                }
+            }
+        }
+
+        if let Some(matched) = existing.get(old_end) {
+            latest.insert(scope_of(&matched.binding), matched.index);
+        }
+        old_start = old_end + 1;
+        new_start = new_end + 1;
+    }
+
+    if !new_sections.is_empty() {
+        let sections: Vec<Keybinding> = new_sections.into_iter().cloned().collect();
+        let mut block = Vec::new();
+        for name in submap_names(&sections) {
+            block.push(String::new());
+            block.push(format!("submap = {}", name));
+            block.extend(
+                sections
+                    .iter()
+                    .filter(|binding| binding.submap.as_deref() == Some(name))
+                    .map(|binding| format_annotated_bind_line(binding, &variables)),
+            );
+            block.push(format!("submap = {}", SUBMAP_RESET));
+        }
+        after.entry(last_line).or_default().extend(block);
+    }
+    if !new_devices.is_empty() {
+        let devices: Vec<Keybinding> = new_devices.into_iter().cloned().collect();
+        let block = ConfigManager::render_devices(&devices, &variables);
+
+        // Below the bindings, unless a submap section is left open there
+        let submap_open = document.lines()[..=last_line]
+            .iter()
+            .rev()
+            .find_map(|line| match &line.kind {
+                LineKind::Submap(submap) => Some(submap.is_some()),
+                _ => None,
+            })
+            .unwrap_or(false);
+        if submap_open {
+            let lines = before.entry(first_line).or_default();
+            lines.extend(block.lines().skip(1).map(str::to_string));
+            lines.push(String::new());
+        } else {
+            after
+                .entry(last_line)
+                .or_default()
+                .extend(block.lines().map(str::to_string));
+        }
+    }
+
+    // Sections whose bindings were all removed lose their markers too
+    // (sections that were empty to begin with are left alone, and so are
+    // those whose markers keep a dangling annotation off the next line).
+    // Any other line left inside, such as an `unbind`, keeps the section:
+    // without its markers it would apply outside the submap.
+    for (start, end) in submap_sections(document) {
+        let is_annotation = |index: usize| parse_category_annotation(lines[index]).is_some();
+        let dangling = (0..start)
+            .rev()
+            .find(|index| !removed.contains(index))
+            .is_some_and(is_annotation)
+            || (start + 1..end)
+                .rev()
+                .find(|index| !removed.contains(index))
+                .is_some_and(is_annotation);
+        let emptied = !dangling
+            && (start + 1..end).any(|index| removed.contains(&index))
+            && (start + 1..end).all(|index| {
+                matches!(
+                    document.lines()[index].kind,
+                    LineKind::Blank | LineKind::Comment
+                ) || removed.contains(&index)
+            })
+            && !replaced.keys().any(|index| (start..=end).contains(index))
+            && !after.keys().any(|index| (start..end).contains(index))
+            && !before.keys().any(|index| (start + 1..=end).contains(index));
+        if emptied {
+            removed.insert(start);
+            removed.insert(end);
+
+            // Drop the blank line that separated the section, unless the
+            // following content relies on it
+            let blank = |index: usize| lines.get(index).is_none_or(|line| line.trim().is_empty());
+            if start > 0 && blank(start - 1) && blank(end + 1) {
+                removed.insert(start - 1);
+            }
+        }
+    }
+
+    for block in document.protected_blocks() {
+        keep_out_of_block(document, &device_blocks, &block, &mut before, &mut after);
+    }
+    keep_clear_of_annotations(&lines, &removed, &mut before);
+
+    let mut result = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        for inserted in before.get(&index).into_iter().flatten() {
+            result.push_str(inserted);
+            result.push('\n');
+        }
+        if let Some(replacement) = replaced.get(&index) {
+            result.push_str(replacement);
+            result.push('\n');
+        } else if !removed.contains(&index) {
+            result.push_str(line);
+            result.push('\n');
+        }
+        for inserted in after.get(&index).into_iter().flatten() {
+            result.push_str(inserted);
+            result.push('\n');
+        }
+    }
+    if !document.ends_with_newline() {
+        result.pop();
+    }
+
+    Some(result)
+}
+
+/// Submap and input device a binding is declared in
+fn scope_of(binding: &Keybinding) -> (Option<&str>, Option<&str>) {
+    (binding.submap.as_deref(), binding.device.as_deref())
+}
+
+/// Replaces the line `old` was read from with `new`
+///
+/// The line keeps its indentation, and its category annotation unless the
+/// category changes. Nothing else in the config changes.
+///
+/// # Arguments
+/// * `original` - Current config content, the file `old.location` names
+/// * `old` - Binding being replaced, with its location
+/// * `new` - Binding to write in its place
+///
+/// # Returns
+/// * `Some(content)` - The config with the one line replaced
+/// * `None` - `old` has no location, its line no longer holds it (the
+///   location is stale), `new` belongs to another submap or device
+///   section, or an `unbind` below the line would cancel `new`
+pub fn replace_bind_line(original: &str, old: &Keybinding, new: &Keybinding) -> Option<String> {
+    let index = old.location.as_ref()?.line.checked_sub(1)?;
+    if new.submap != old.submap || new.device != old.device {
+        return None;
+    }
+
+    let document = ConfigDocument::parse(original);
+    let lines = texts(&document);
+    let (binds, unbinds) = bind_lines(&document);
+    let current = binds.into_iter().find(|bind| bind.index == index)?;
+    let cancelled = unbinds
+        .iter()
+        .any(|line| line.index > index && line.unbind.cancels(new));
+    if current.binding != *old || cancelled {
+        return None;
+    }
+
+    let mut removed = HashSet::new();
+    let replacement = rewrite_line(
+        &document,
+        &current,
+        new,
+        &document.variables(),
+        &mut removed,
+    );
+
+    let mut result = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if removed.contains(&i) {
+            continue;
+        }
+        result.push_str(if i == index { &replacement } else { line });
+        result.push('\n');
+    }
+    if !original.ends_with('\n') {
+        result.pop();
+    }
+
+    Some(result)
+}
+
+/// Removes or comments out the lines of located bindings
+///
+/// Every other line is kept byte-for-byte.
+///
+/// # Arguments
+/// * `original` - Current config content, the file the locations name
+/// * `dropped` - Bindings to drop, each with the location it was read from
+/// * `mode` - Whether the lines are deleted or commented out
+///
+/// # Returns
+/// * `Some(content)` - The config without the dropped bindings
+/// * `None` - A binding has no location, or its line no longer holds it
+pub fn drop_bind_lines(original: &str, dropped: &[Keybinding], mode: FixMode) -> Option<String> {
+    let document = ConfigDocument::parse(original);
+    let lines = texts(&document);
+    let (binds, _) = bind_lines(&document);
+
+    let mut indices = HashSet::new();
+    for binding in dropped {
+        let index = binding.location.as_ref()?.line.checked_sub(1)?;
+        binds
+            .iter()
+            .find(|bind| bind.index == index && bind.binding == *binding)?;
+        indices.insert(index);
+    }
+    // Removed lines take their category annotation with them
+    let annotations: HashSet<usize> = match mode {
+        FixMode::Remove => indices
+            .iter()
+            .flat_map(|&index| document.annotation_lines(index))
+            .collect(),
+        FixMode::CommentOut => HashSet::new(),
+    };
+
+    let mut result = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if annotations.contains(&i) {
+            continue;
+        }
+        if indices.contains(&i) {
+            match mode {
+                FixMode::Remove => continue,
+                FixMode::CommentOut => {
+                    let content = line.trim_start();
+                    result.push_str(&line[..line.len() - content.len()]);
+                    result.push_str("# ");
+                    result.push_str(content);
+                }
+            }
+        } else {
+            result.push_str(line);
+        }
+        result.push('\n');
+    }
+    if !original.ends_with('\n') {
+        result.pop();
+    }
+
+    Some(result)
+}
+
+/// An `unbind` line of the original config
+struct UnbindLine {
+    /// Line index (0-based)
+    index: usize,
+    /// Submap section the line is in
+    submap: Option<String>,
+    unbind: Unbind,
+}
+
+/// The bind and unbind lines of a document, the way the parser reads them
+///
+/// Bind lines cancelled by a later `unbind` line are left out, as the
+/// parser leaves their bindings out.
+///
+/// # Returns
+/// The bind lines and the unbind lines, in line order
+fn bind_lines(document: &ConfigDocument) -> (Vec<BindLine>, Vec<UnbindLine>) {
+    let mut current_submap: Option<String> = None;
+    let mut bind_lines: Vec<BindLine> = Vec::new();
+    let mut unbind_lines = Vec::new();
+
+    for (index, line) in document.lines().iter().enumerate() {
+        match &line.kind {
+            LineKind::Submap(submap) => current_submap = submap.clone(),
+            LineKind::Unbind(unbind) => {
+                bind_lines.retain(|bind| !unbind.cancels(&bind.binding));
+                unbind_lines.push(UnbindLine {
+                    index,
+                    submap: current_submap.clone(),
+                    unbind: unbind.clone(),
+                });
+            }
+            LineKind::Bind(binding) => bind_lines.push(BindLine {
+                index,
+                binding: (**binding).clone(),
+            }),
+            _ => {}
+        }
+    }
+
+    (bind_lines, unbind_lines)
+}
+
+/// The text of every line of a document
+fn texts(document: &ConfigDocument) -> Vec<&str> {
+    document
+        .lines()
+        .iter()
+        .map(|line| line.text.as_str())
+        .collect()
+}
+
+/// Finds `submap = name` ... `submap = reset` sections
+///
+/// Only sections opened from the global bindings count: a marker that
+/// switches from one submap straight to another does not start a section
+/// whose markers could be dropped without changing what follows.
+///
+/// # Returns
+/// Line indices of each section's opening and closing marker
+fn submap_sections(document: &ConfigDocument) -> Vec<(usize, usize)> {
+    let mut sections = Vec::new();
+    let mut open: Option<usize> = None;
+    let mut in_submap = false;
+
+    for (index, line) in document.lines().iter().enumerate() {
+        match line.kind {
+            LineKind::Submap(Some(_)) => {
+                open = (!in_submap).then_some(index);
+                in_submap = true;
+            }
+            LineKind::Submap(None) => {
+                if let Some(start) = open.take() {
+                    sections.push((start, index));
+                }
+                in_submap = false;
+            }
+            _ => {}
+        }
+    }
+
+    sections
+}
+
+/// Moves insertions inside a protected block to its edges
+///
+/// Lines inserted after a line of the block go below its last line, and
+/// lines inserted before one go above its first line. An insertion stays
+/// where it is if a bind, unbind or submap line lies between it and the
+/// edge, as the bindings would change order (or section) otherwise; so
+/// does the edge of a device section.
+fn keep_out_of_block(
+    document: &ConfigDocument,
+    device_blocks: &[(Range<usize>, String)],
+    block: &Range<usize>,
+    before: &mut BTreeMap<usize, Vec<String>>,
+    after: &mut BTreeMap<usize, Vec<String>>,
+) {
+    let is_binding_line = |index: usize| {
+        matches!(
+            document.lines()[index].kind,
+            LineKind::Bind(_) | LineKind::BrokenBind | LineKind::Submap(_) | LineKind::Unbind(_)
+        ) || device_blocks
+            .iter()
+            .any(|(range, _)| index == range.start || index + 1 == range.end)
+    };
+    if block.len() < 2 {
+        return;
+    }
+    let (first, last) = (block.start, block.end - 1);
+
+    let inside: Vec<usize> = after.range(first..last).map(|(&line, _)| line).collect();
+    for line in inside {
+        if !(line + 1..=last).any(is_binding_line) {
+            let moved = after.remove(&line).unwrap_or_default();
+            after.entry(last).or_default().extend(moved);
+        }
+    }
+
+    let inside: Vec<usize> = before
+        .range(first + 1..=last)
+        .map(|(&line, _)| line)
+        .collect();
+    for line in inside.into_iter().rev() {
+        if !(first..line).any(is_binding_line) {
+            let mut moved = before.remove(&line).unwrap_or_default();
+            moved.extend(before.remove(&first).unwrap_or_default());
+            before.insert(first, moved);
+        }
+    }
+}
+
+/// Returns whether a line is a bind line or a submap section marker
+fn is_bind_or_submap_line(line: &DocumentLine) -> bool {
+    matches!(
+        line.kind,
+        LineKind::Bind(_) | LineKind::BrokenBind | LineKind::Submap(_)
+    )
+}
+
+/// Formats a binding (and its annotation) with the indentation of a
+/// neighbouring line
+fn with_indent(neighbour: &str, binding: &Keybinding, variables: &VariableTable) -> String {
+    let indent = indent_of(neighbour);
+    format_annotated_bind_line(binding, variables)
+        .lines()
+        .map(|line| format!("{}{}", indent, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The leading whitespace of a line
+fn indent_of(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Formats `new` to replace the bind line of `old` in place
+///
+/// With an unchanged category the annotation above the line is kept as it
+/// is and only the bind line is written. Otherwise the old annotation lines
+/// (if any) are added to `removed` and the new one (if any) is written with
+/// the line.
+fn rewrite_line(
+    document: &ConfigDocument,
+    old: &BindLine,
+    new: &Keybinding,
+    variables: &VariableTable,
+    removed: &mut HashSet<usize>,
+) -> String {
+    let line = &document.lines()[old.index].text;
+    if old.binding.category == new.category {
+        return format!(
+            "{}{}",
+            indent_of(line),
+            format_bind_line_with_variables(new, variables)
+        );
+    }
+
+    removed.extend(document.annotation_lines(old.index));
+    with_indent(line, new, variables)
+}
+
+/// Moves insertions above the category annotations right above them
+///
+/// A line written below an annotation would take its category, whether
+/// the annotation belongs to a bind line or dangles.
+fn keep_clear_of_annotations(
+    lines: &[&str],
+    removed: &HashSet<usize>,
+    before: &mut BTreeMap<usize, Vec<String>>,
+) {
+    let annotated: Vec<usize> = before.keys().copied().collect();
+    for index in annotated {
+        // Removed lines in between no longer separate the two
+        let mut target = index;
+        let mut above = index;
+        while above > 0 {
+            above -= 1;
+            if removed.contains(&above) {
+                continue;
+            }
+            if parse_category_annotation(lines[above]).is_none() {
+                break;
+            }
+            target = above;
+        }
+        if target != index {
+            let moved = before.remove(&index).unwrap_or_default();
+            before.entry(target).or_default().extend(moved);
+        }
+    }
+}
+
+/// Aligns old bind lines with new bindings
+///
+/// # Returns
+/// Index pairs `(old, new)` of equal bindings, increasing in both
+fn longest_common_subsequence(old: &[BindLine], new: &[Keybinding]) -> Vec<(usize, usize)> {
+    // lengths[i][j]: LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i].binding == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i].binding == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
@@ -0,0 +1,405 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hyprland `binds` options
+//!
+//! Options in the `binds { ... }` section change what some bindings do:
+//! `pass_mouse_when_bound` lets clicks reach the window despite a mouse
+//! bind, `movefocus_cycles_fullscreen` makes `movefocus` cycle windows of a
+//! fullscreen workspace, and so on. This module knows which options affect
+//! which bindings, reads their values from a config and sets them.
+//!
+//! Options may be set inside the section or as `binds:name = value`; as in
+//! Hyprland, the last assignment wins.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::binds_options::{set_binds_option, BindsOptions};
+//!
+//! let config = "binds {\n    workspace_back_and_forth = true\n}\n";
+//! let options = BindsOptions::from_config(config);
+//! assert_eq!(options.value("workspace_back_and_forth"), Some("true"));
+//!
+//! let updated = set_binds_option(config, "workspace_back_and_forth", "false");
+//! assert_eq!(updated, "binds {\n    workspace_back_and_forth = false\n}\n");
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::core::types::{BindFlag, Keybinding};
+
+/// Value type of an option
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionKind {
+    /// `true`/`false` (Hyprland also accepts `yes`/`no`, `on`/`off`, `1`/`0`)
+    Bool,
+    /// A whole number
+    Int,
+}
+
+/// A `binds` option that changes how some bindings behave
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BindsOption {
+    /// Option name inside the `binds` section
+    pub name: &'static str,
+    pub kind: OptionKind,
+    /// Hyprland's default value
+    pub default: &'static str,
+    /// What the option changes, for display
+    pub description: &'static str,
+    /// Dispatchers whose behaviour the option changes
+    pub dispatchers: &'static [&'static str],
+    /// Whether the option changes mouse bindings (`bindm`, `mouse:` keys)
+    pub mouse: bool,
+}
+
+impl BindsOption {
+    /// Returns whether the option changes what a binding does
+    pub fn affects(&self, binding: &Keybinding) -> bool {
+        let mouse_binding =
+            binding.bind_type.contains(BindFlag::Mouse) || binding.key_combo.key.is_mouse();
+        self.dispatchers.contains(&binding.dispatcher.as_str()) || (self.mouse && mouse_binding)
+    }
+
+    /// Checks a value against the option's kind
+    pub fn accepts(&self, value: &str) -> bool {
+        match self.kind {
+            OptionKind::Bool => parse_bool(value).is_some(),
+            OptionKind::Int => value.parse::<i64>().is_ok(),
+        }
+    }
+}
+
+const FOCUS_DISPATCHERS: &[&str] = &["movefocus"];
+const WORKSPACE_DISPATCHERS: &[&str] = &["workspace"];
+const GROUP_DISPATCHERS: &[&str] = &["moveintogroup", "moveoutofgroup", "movewindoworgroup"];
+const DIRECTION_DISPATCHERS: &[&str] = &["movefocus", "movewindow", "swapwindow"];
+
+/// `binds` options that change what bindings do
+///
+/// Source: https://wiki.hyprland.org/Configuring/Variables/#binds
+pub const BINDS_OPTIONS: &[BindsOption] = &[
+    BindsOption {
+        name: "pass_mouse_when_bound",
+        kind: OptionKind::Bool,
+        default: "false",
+        description: "Clicks also reach the window under the cursor when a bind uses them",
+        dispatchers: &[],
+        mouse: true,
+    },
+    BindsOption {
+        name: "scroll_event_delay",
+        kind: OptionKind::Int,
+        default: "300",
+        description: "Milliseconds to ignore further scroll events after a scroll bind fired",
+        dispatchers: &[],
+        mouse: true,
+    },
+    BindsOption {
+        name: "drag_threshold",
+        kind: OptionKind::Int,
+        default: "0",
+        description: "Pixels the cursor must move before a click bind becomes a drag",
+        dispatchers: &[],
+        mouse: true,
+    },
+    BindsOption {
+        name: "workspace_back_and_forth",
+        kind: OptionKind::Bool,
+        default: "false",
+        description: "Switching to the current workspace goes back to the previous one",
+        dispatchers: WORKSPACE_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "allow_workspace_cycles",
+        kind: OptionKind::Bool,
+        default: "false",
+        description: "'workspace, previous' keeps cycling between the last two workspaces",
+        dispatchers: WORKSPACE_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "hide_special_on_workspace_change",
+        kind: OptionKind::Bool,
+        default: "false",
+        description: "Changing workspace hides an open special workspace",
+        dispatchers: WORKSPACE_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "workspace_center_on",
+        kind: OptionKind::Int,
+        default: "0",
+        description: "Cursor lands on the centre (0) or the last active window (1)",
+        dispatchers: WORKSPACE_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "focus_preferred_method",
+        kind: OptionKind::Int,
+        default: "0",
+        description: "Picks the next window by history (0) or by overlap length (1)",
+        dispatchers: FOCUS_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "movefocus_cycles_fullscreen",
+        kind: OptionKind::Bool,
+        default: "false",
+        description: "On a fullscreen workspace, cycles through its windows",
+        dispatchers: FOCUS_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "movefocus_cycles_groupfirst",
+        kind: OptionKind::Bool,
+        default: "false",
+        description: "Cycles through the windows of a group before leaving it",
+        dispatchers: FOCUS_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "window_direction_monitor_fallback",
+        kind: OptionKind::Bool,
+        default: "true",
+        description: "Moves on to the next monitor when no window lies in the direction",
+        dispatchers: DIRECTION_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "ignore_group_lock",
+        kind: OptionKind::Bool,
+        default: "false",
+        description: "Windows move into and out of locked groups too",
+        dispatchers: GROUP_DISPATCHERS,
+        mouse: false,
+    },
+    BindsOption {
+        name: "allow_pin_fullscreen",
+        kind: OptionKind::Bool,
+        default: "false",
+        description: "Fullscreen windows can be pinned",
+        dispatchers: &["pin"],
+        mouse: false,
+    },
+];
+
+/// Looks up an option by name
+pub fn binds_option(name: &str) -> Option<&'static BindsOption> {
+    BINDS_OPTIONS.iter().find(|option| option.name == name)
+}
+
+/// Checks that an option is known and the value fits its kind
+///
+/// # Errors
+/// A message naming the unknown option or the expected kind of value
+pub fn validate_binds_option(name: &str, value: &str) -> Result<(), String> {
+    let option = binds_option(name).ok_or_else(|| format!("Unknown binds option '{}'", name))?;
+    if option.accepts(value) {
+        Ok(())
+    } else {
+        let expected = match option.kind {
+            OptionKind::Bool => "true or false",
+            OptionKind::Int => "a whole number",
+        };
+        Err(format!(
+            "Invalid value '{}' for binds:{} (expected {})",
+            value, name, expected
+        ))
+    }
+}
+
+/// Reads a Hyprland boolean
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// `binds` options as set in a config
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BindsOptions {
+    /// Option name to the value assigned last
+    values: BTreeMap<String, String>,
+}
+
+impl BindsOptions {
+    /// Reads the `binds` section and `binds:name = value` lines
+    pub fn from_config(content: &str) -> Self {
+        let mut options = Self::default();
+        for assignment in assignments(content) {
+            options
+                .values
+                .insert(assignment.name.to_string(), assignment.value.to_string());
+        }
+        options
+    }
+
+    /// Returns the value the config sets, if any
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Returns the value in effect: the config's, else Hyprland's default
+    pub fn effective(&self, option: &BindsOption) -> String {
+        self.value(option.name)
+            .unwrap_or(option.default)
+            .to_string()
+    }
+
+    /// Returns the known options affecting a binding, with values in effect
+    pub fn relevant_to(&self, binding: &Keybinding) -> Vec<(&'static BindsOption, String)> {
+        BINDS_OPTIONS
+            .iter()
+            .filter(|option| option.affects(binding))
+            .map(|option| (option, self.effective(option)))
+            .collect()
+    }
+}
+
+/// One assignment of a `binds` option in a config
+struct Assignment<'a> {
+    /// Line index (0-based)
+    index: usize,
+    name: &'a str,
+    value: &'a str,
+}
+
+/// Finds the `binds` option assignments of a config, in line order
+fn assignments(content: &str) -> Vec<Assignment<'_>> {
+    let mut found = Vec::new();
+    let mut sections: Vec<&str> = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let code = strip_comment(line).trim();
+        if code == "}" {
+            sections.pop();
+            continue;
+        }
+        if let Some(header) = code.strip_suffix('{') {
+            sections.push(header.trim());
+            continue;
+        }
+
+        let Some((key, value)) = code.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        let name = match sections.as_slice() {
+            ["binds"] => key,
+            [] => match key.strip_prefix("binds:") {
+                Some(name) => name,
+                None => continue,
+            },
+            _ => continue,
+        };
+        found.push(Assignment {
+            index,
+            name,
+            value: value.trim(),
+        });
+    }
+
+    found
+}
+
+/// Removes a trailing `# comment` (Hyprland escapes a literal `#` as `##`)
+pub(crate) fn strip_comment(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'#' {
+            if bytes.get(i + 1) == Some(&b'#') {
+                i += 2;
+                continue;
+            }
+            return &line[..i];
+        }
+        i += 1;
+    }
+    line
+}
+
+/// Sets a `binds` option in a config
+///
+/// The assignment in effect (the last one) is rewritten in place, keeping
+/// its form, indentation and comment. Without one, the option is added to
+/// the last `binds` section, or a new section is appended.
+///
+/// # Arguments
+/// * `content` - The full config file content
+/// * `name` - Option name inside the `binds` section
+/// * `value` - New value, as written in the config
+///
+/// # Returns
+/// The updated config content
+pub fn set_binds_option(content: &str, name: &str, value: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    if let Some(assignment) = assignments(content)
+        .into_iter()
+        .rev()
+        .find(|assignment| assignment.name == name)
+    {
+        let line = &lines[assignment.index];
+        let (key, _) = line.split_once('=').unwrap_or((line, ""));
+        let comment = &line[strip_comment(line).len()..];
+        let separator = if comment.is_empty() { "" } else { " " };
+        lines[assignment.index] = format!("{} = {}{}{}", key.trim_end(), value, separator, comment);
+    } else if let Some(end) = last_binds_section_end(&lines) {
+        let indent = lines[..end]
+            .last()
+            .filter(|line| !line.trim_end().ends_with('{'))
+            .map(|line| line[..line.len() - line.trim_start().len()].to_string())
+            .unwrap_or_else(|| "    ".to_string());
+        lines.insert(end, format!("{}{} = {}", indent, name, value));
+    } else {
+        if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push("binds {".to_string());
+        lines.push(format!("    {} = {}", name, value));
+        lines.push("}".to_string());
+    }
+
+    let mut result = lines.join("\n");
+    if content.is_empty() || content.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Returns the line index of the closing brace of the last `binds` section
+fn last_binds_section_end(lines: &[String]) -> Option<usize> {
+    let mut sections: Vec<(&str, usize)> = Vec::new();
+    let mut end = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let code = strip_comment(line).trim();
+        if code == "}" {
+            if let Some((name, depth)) = sections.pop() {
+                if name == "binds" && depth == 0 {
+                    end = Some(index);
+                }
+            }
+        } else if let Some(header) = code.strip_suffix('{') {
+            sections.push((header.trim(), sections.len()));
+        }
+    }
+
+    end
+}
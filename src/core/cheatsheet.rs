@@ -0,0 +1,274 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Printable cheat sheets of the bindings
+//!
+//! A cheat sheet is one table per section, for printing or for a dotfiles
+//! README. Bindings tagged with a `# @category:` keep their category as the
+//! section; the rest are grouped by what they do, using the same heuristic
+//! as group suggestions (see [`crate::core::grouping`]). Within a section,
+//! bindings are sorted by their modifiers, fewest first, so plain `SUPER`
+//! bindings stay together ahead of `SHIFT+SUPER` ones.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{cheatsheet::CheatSheetFormat, parser::parse_config_file};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, Q, killactive\nbind = SUPER, Return, exec, kitty\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//! let sheet = CheatSheetFormat::Markdown.render(&bindings);
+//!
+//! assert!(sheet.contains("## Window Management"));
+//! assert!(sheet.contains("| SUPER+RETURN |  | `exec kitty` |"));
+//! ```
+
+use std::{fmt, path::Path, str::FromStr};
+
+use crate::core::{
+    grouping::{classify_binding, BindingGroup},
+    types::Keybinding,
+};
+
+/// Title of every cheat sheet
+const TITLE: &str = "Hyprland Keybindings";
+
+/// Print-friendly styling of the HTML cheat sheet
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h2 { margin-top: 1.5em; border-bottom: 1px solid #ccc; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 0.25em 0.75em; border-bottom: 1px solid #eee; }
+td:first-child { white-space: nowrap; font-weight: bold; }
+section { break-inside: avoid; }
+";
+
+/// File format of a cheat sheet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CheatSheetFormat {
+    /// GitHub-flavoured Markdown tables
+    #[default]
+    Markdown,
+    /// A standalone HTML page, styled for printing
+    Html,
+}
+
+/// One table of a cheat sheet
+#[derive(Clone, Debug, PartialEq)]
+pub struct CheatSheetSection {
+    /// Heading: the category, or the suggested group for the rest
+    pub title: String,
+    /// Bindings sorted by modifiers, in config order otherwise
+    pub bindings: Vec<Keybinding>,
+}
+
+impl CheatSheetFormat {
+    /// Usual file extension of the format
+    pub fn extension(self) -> &'static str {
+        match self {
+            CheatSheetFormat::Markdown => "md",
+            CheatSheetFormat::Html => "html",
+        }
+    }
+
+    /// Picks the format from a file's extension
+    ///
+    /// # Returns
+    ///
+    /// HTML for `.html` and `.htm` files, Markdown for any other
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("html" | "htm") => CheatSheetFormat::Html,
+            _ => CheatSheetFormat::Markdown,
+        }
+    }
+
+    /// Renders the cheat sheet of the bindings
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - Bindings to list
+    ///
+    /// # Returns
+    ///
+    /// The whole document: a title, then one table per section
+    pub fn render(self, bindings: &[Keybinding]) -> String {
+        let sections = cheat_sheet_sections(bindings);
+        match self {
+            CheatSheetFormat::Markdown => render_markdown(&sections),
+            CheatSheetFormat::Html => render_html(&sections),
+        }
+    }
+}
+
+impl fmt::Display for CheatSheetFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CheatSheetFormat::Markdown => "markdown",
+            CheatSheetFormat::Html => "html",
+        })
+    }
+}
+
+impl FromStr for CheatSheetFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(CheatSheetFormat::Markdown),
+            "html" => Ok(CheatSheetFormat::Html),
+            _ => Err(format!("Unknown cheat sheet format: {s}")),
+        }
+    }
+}
+
+/// Splits bindings into the sections of a cheat sheet
+///
+/// Categories come first, in the order they appear in the config, then the
+/// suggested groups in their usual order. A category named like a group
+/// shares its section. Empty sections are left out.
+pub fn cheat_sheet_sections(bindings: &[Keybinding]) -> Vec<CheatSheetSection> {
+    let mut titles: Vec<String> = Vec::new();
+    for category in bindings.iter().filter_map(|b| b.category.as_deref()) {
+        if !titles.iter().any(|title| title == category) {
+            titles.push(category.to_string());
+        }
+    }
+    for group in BindingGroup::ALL {
+        if !titles.iter().any(|title| title == group.title()) {
+            titles.push(group.title().to_string());
+        }
+    }
+
+    titles
+        .into_iter()
+        .map(|title| {
+            let mut members: Vec<Keybinding> = bindings
+                .iter()
+                .filter(|binding| section_title(binding) == title)
+                .cloned()
+                .collect();
+            // Stable, so bindings with the same modifiers keep config order
+            members.sort_by_key(|binding| {
+                let modifiers = binding.key_combo.canonical_modifiers();
+                (modifiers.len(), modifiers)
+            });
+            CheatSheetSection {
+                title,
+                bindings: members,
+            }
+        })
+        .filter(|section| !section.bindings.is_empty())
+        .collect()
+}
+
+/// Title of the section a binding is listed in
+fn section_title(binding: &Keybinding) -> &str {
+    match &binding.category {
+        Some(category) => category,
+        None => classify_binding(binding).title(),
+    }
+}
+
+/// The cells of a binding's row: keys, description, command
+fn row(binding: &Keybinding) -> [String; 3] {
+    let mut keys = binding.key_combo.to_string();
+    if let Some(submap) = &binding.submap {
+        keys.push_str(&format!(" ({submap})"));
+    }
+    let command = match &binding.args {
+        Some(args) => format!("{} {}", binding.dispatcher, args),
+        None => binding.dispatcher.clone(),
+    };
+    [
+        keys,
+        binding.description.clone().unwrap_or_default(),
+        command,
+    ]
+}
+
+fn render_markdown(sections: &[CheatSheetSection]) -> String {
+    // Pipes would end the cell, even inside a code span
+    let escape = |text: &str| text.replace('|', "\\|");
+
+    let mut out = format!("# {TITLE}\n");
+    for section in sections {
+        out.push_str(&format!("\n## {}\n\n", escape(&section.title)));
+        out.push_str("| Keys | Description | Command |\n|---|---|---|\n");
+        for binding in &section.bindings {
+            let [keys, description, command] = row(binding);
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                escape(&keys),
+                escape(&description),
+                markdown_code(&escape(&command))
+            ));
+        }
+    }
+    out
+}
+
+/// Wraps text in a code span, with enough backticks to hold the text's own
+fn markdown_code(text: &str) -> String {
+    let longest_run = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    if longest_run == 0 {
+        return format!("`{text}`");
+    }
+    let fence = "`".repeat(longest_run + 1);
+    format!("{fence} {text} {fence}")
+}
+
+fn render_html(sections: &[CheatSheetSection]) -> String {
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{TITLE}</title>\n<style>\n{HTML_STYLE}</style>\n</head>\n\
+         <body>\n<h1>{TITLE}</h1>\n"
+    );
+    for section in sections {
+        out.push_str(&format!(
+            "<section>\n<h2>{}</h2>\n<table>\n\
+             <tr><th>Keys</th><th>Description</th><th>Command</th></tr>\n",
+            html_escape(&section.title)
+        ));
+        for binding in &section.bindings {
+            let [keys, description, command] = row(binding);
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td><code>{}</code></td></tr>\n",
+                html_escape(&keys),
+                html_escape(&description),
+                html_escape(&command)
+            ));
+        }
+        out.push_str("</table>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Escapes text for an HTML element's content
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,295 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compact binding storage for long-running processes
+//!
+//! The GUI stays resident and keeps up to 20 undo and 20 redo snapshots of
+//! every binding. Stored as `Vec<Keybinding>`, each snapshot owns its own
+//! copy of every key, dispatcher and argument string, so a config with a
+//! few thousand bindings costs tens of megabytes of history.
+//!
+//! [`CompactBindings`] stores the same data with:
+//! - **Interned strings**: keys, dispatchers, arguments, submaps,
+//!   descriptions, categories and devices are shared `Rc<str>`s from one
+//!   [`Interner`], so `exec`, `workspace` or a repeated argument is stored
+//!   once
+//! - **Interned bindings**: a binding unchanged between snapshots is
+//!   stored once, and each snapshot holds one pointer per binding
+//! - **Modifier bitmasks**: one byte instead of a `Vec<Modifier>`
+//! - **Exact-size slices**: no spare `Vec` capacity
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{
+//!     compact::{CompactBindings, Interner},
+//!     parser::parse_config_file,
+//! };
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, 1, workspace, 1\nbind = SUPER SHIFT, 1, movetoworkspace, 1\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//!
+//! let mut interner = Interner::default();
+//! let compact = CompactBindings::new(&bindings, &mut interner);
+//! assert_eq!(compact.to_bindings(), bindings);
+//! // "1" (key and argument of both bindings), "workspace", "movetoworkspace"
+//! assert_eq!(interner.len(), 3);
+//!
+//! // A second snapshot of the same bindings adds nothing to the interner
+//! let again = CompactBindings::new(&bindings, &mut interner);
+//! assert_eq!(interner.len(), 3);
+//! assert_eq!(again.heap_bytes(), 2 * std::mem::size_of::<usize>());
+//! ```
+
+use std::{
+    collections::HashSet,
+    mem::{size_of, size_of_val},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::core::types::{BindType, Key, KeyCombo, Keybinding, Modifier, SourceLocation};
+
+/// Modifiers in the order `KeyCombo::new` sorts them
+const MODIFIER_ORDER: [Modifier; 4] = [
+    Modifier::Alt,
+    Modifier::Ctrl,
+    Modifier::Shift,
+    Modifier::Super,
+];
+
+/// Deduplicating store of shared strings and bindings
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: HashSet<Rc<str>>,
+    paths: Vec<Rc<Path>>,
+    bindings: HashSet<Rc<CompactBinding>>,
+}
+
+impl Interner {
+    /// Returns the shared copy of `value`, storing it on first use
+    pub fn intern(&mut self, value: &str) -> Rc<str> {
+        if let Some(existing) = self.strings.get(value) {
+            return Rc::clone(existing);
+        }
+        let shared: Rc<str> = Rc::from(value);
+        self.strings.insert(Rc::clone(&shared));
+        shared
+    }
+
+    /// Returns the shared copy of a source file path
+    fn intern_path(&mut self, path: &Path) -> Rc<Path> {
+        if let Some(existing) = self.paths.iter().find(|p| p.as_ref() == path) {
+            return Rc::clone(existing);
+        }
+        let shared: Rc<Path> = Rc::from(path);
+        self.paths.push(Rc::clone(&shared));
+        shared
+    }
+
+    /// Returns the shared copy of a compacted binding
+    fn intern_binding(&mut self, binding: CompactBinding) -> Rc<CompactBinding> {
+        if let Some(existing) = self.bindings.get(&binding) {
+            return Rc::clone(existing);
+        }
+        let shared = Rc::new(binding);
+        self.bindings.insert(Rc::clone(&shared));
+        shared
+    }
+
+    /// Drops bindings and strings no snapshot uses any more
+    pub fn purge(&mut self) {
+        // Bindings first: they hold the last references to their strings
+        self.bindings.retain(|b| Rc::strong_count(b) > 1);
+        self.strings.retain(|s| Rc::strong_count(s) > 1);
+        self.paths.retain(|p| Rc::strong_count(p) > 1);
+    }
+
+    /// Number of distinct strings stored
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns whether no strings are stored
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Approximate heap usage of the stored strings and bindings, in bytes
+    ///
+    /// Counts each value and its reference counts once; the hash tables
+    /// themselves are not included.
+    pub fn heap_bytes(&self) -> usize {
+        let rc_header = 2 * size_of::<usize>();
+        let strings: usize = self.strings.iter().map(|s| rc_header + s.len()).sum();
+        let paths: usize = self
+            .paths
+            .iter()
+            .map(|p| rc_header + p.as_os_str().len())
+            .sum();
+        strings + paths + self.bindings.len() * (rc_header + size_of::<CompactBinding>())
+    }
+}
+
+/// A [`Keybinding`] with interned strings and a modifier bitmask
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct CompactBinding {
+    modifiers: u8,
+    key: Rc<str>,
+    bind_type: BindType,
+    dispatcher: Rc<str>,
+    args: Option<Rc<str>>,
+    submap: Option<Rc<str>>,
+    source_file: Option<Rc<Path>>,
+    description: Option<Rc<str>>,
+    category: Option<Rc<str>>,
+    device: Option<Rc<str>>,
+    location: Option<(Rc<Path>, usize)>,
+}
+
+/// A snapshot of bindings in compact form
+#[derive(Clone, Debug, Default)]
+pub struct CompactBindings {
+    bindings: Box<[Rc<CompactBinding>]>,
+}
+
+impl CompactBindings {
+    /// Compacts the bindings, interning their strings
+    ///
+    /// # Arguments
+    /// * `bindings` - Bindings to store
+    /// * `interner` - Shared string store; reuse one for all snapshots
+    pub fn new(bindings: &[Keybinding], interner: &mut Interner) -> Self {
+        let bindings = bindings
+            .iter()
+            .map(|binding| {
+                let compact = CompactBinding {
+                    modifiers: modifier_mask(&binding.key_combo.modifiers),
+                    key: interner.intern(&binding.key_combo.key.config_name()),
+                    bind_type: binding.bind_type,
+                    dispatcher: interner.intern(&binding.dispatcher),
+                    args: binding.args.as_deref().map(|s| interner.intern(s)),
+                    submap: binding.submap.as_deref().map(|s| interner.intern(s)),
+                    source_file: binding
+                        .source_file
+                        .as_deref()
+                        .map(|p| interner.intern_path(p)),
+                    description: binding.description.as_deref().map(|s| interner.intern(s)),
+                    category: binding.category.as_deref().map(|s| interner.intern(s)),
+                    device: binding.device.as_deref().map(|s| interner.intern(s)),
+                    location: binding
+                        .location
+                        .as_ref()
+                        .map(|l| (interner.intern_path(&l.file), l.line)),
+                };
+                interner.intern_binding(compact)
+            })
+            .collect();
+
+        Self { bindings }
+    }
+
+    /// Expands the snapshot back into bindings
+    ///
+    /// Modifiers come back in `KeyCombo::new` order.
+    pub fn to_bindings(&self) -> Vec<Keybinding> {
+        self.bindings
+            .iter()
+            .map(|binding| Keybinding {
+                key_combo: KeyCombo {
+                    modifiers: MODIFIER_ORDER
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(bit, _)| binding.modifiers & (1 << bit) != 0)
+                        .map(|(_, modifier)| modifier)
+                        .collect(),
+                    key: Key::parse(&binding.key),
+                },
+                bind_type: binding.bind_type,
+                dispatcher: binding.dispatcher.to_string(),
+                args: binding.args.as_deref().map(str::to_string),
+                submap: binding.submap.as_deref().map(str::to_string),
+                source_file: binding.source_file.as_deref().map(Path::to_path_buf),
+                description: binding.description.as_deref().map(str::to_string),
+                category: binding.category.as_deref().map(str::to_string),
+                device: binding.device.as_deref().map(str::to_string),
+                location: binding
+                    .location
+                    .as_ref()
+                    .map(|(file, line)| SourceLocation {
+                        file: file.to_path_buf(),
+                        line: *line,
+                    }),
+            })
+            .collect()
+    }
+
+    /// Number of bindings in the snapshot
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Returns whether the snapshot has no bindings
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Heap usage of the snapshot itself, in bytes
+    ///
+    /// The bindings and strings live in the [`Interner`] and are counted
+    /// there.
+    pub fn heap_bytes(&self) -> usize {
+        self.bindings.len() * size_of::<Rc<CompactBinding>>()
+    }
+}
+
+fn modifier_mask(modifiers: &[Modifier]) -> u8 {
+    MODIFIER_ORDER
+        .into_iter()
+        .enumerate()
+        .filter(|(_, modifier)| modifiers.contains(modifier))
+        .fold(0, |mask, (bit, _)| mask | 1 << bit)
+}
+
+/// Approximate heap usage of bindings stored as a `Vec<Keybinding>`, in bytes
+///
+/// Counts the vector and the capacity of every owned string and modifier
+/// list, for comparison with [`CompactBindings::heap_bytes`].
+pub fn keybindings_heap_bytes(bindings: &[Keybinding]) -> usize {
+    let string = |s: &String| s.capacity();
+    let optional = |s: &Option<String>| s.as_ref().map_or(0, String::capacity);
+
+    size_of_val(bindings)
+        + bindings
+            .iter()
+            .map(|binding| {
+                binding.key_combo.modifiers.capacity() * size_of::<Modifier>()
+                    + match &binding.key_combo.key {
+                        Key::Named(name) => name.capacity(),
+                        _ => 0,
+                    }
+                    + string(&binding.dispatcher)
+                    + optional(&binding.args)
+                    + optional(&binding.submap)
+                    + optional(&binding.description)
+                    + optional(&binding.category)
+                    + optional(&binding.device)
+                    + binding.source_file.as_ref().map_or(0, PathBuf::capacity)
+                    + binding
+                        .location
+                        .as_ref()
+                        .map_or(0, |location| location.file.capacity())
+            })
+            .sum::<usize>()
+}
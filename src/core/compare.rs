@@ -0,0 +1,163 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Side-by-side comparison of two binding sets
+//!
+//! Aligns two sets of bindings (e.g. the current config and a backup) by
+//! key combo and submap, so each row holds whatever both sides bind to the
+//! same keys. Rows can then be merged by taking either side per row.
+//!
+//! Statuses describe the change from the left side to the right side.
+//! Bindings that differ only in formatting (see
+//! [`crate::core::equivalence`]) count as unchanged.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{
+//!     compare::{compare_bindings, merge_rows, RowStatus, Side},
+//!     parser::parse_config_file,
+//! };
+//! use std::path::Path;
+//!
+//! let left = parse_config_file("bind = SUPER, K, exec, kitty", Path::new("")).unwrap();
+//! let right = parse_config_file("bind = SUPER, K, exec, foot", Path::new("")).unwrap();
+//!
+//! let rows = compare_bindings(&left, &right);
+//! assert_eq!(rows[0].status(), RowStatus::Changed);
+//!
+//! let merged = merge_rows(&rows, &[Side::Right]);
+//! assert_eq!(merged, right);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::core::types::{KeyCombo, Keybinding};
+
+/// How a row differs between the two sides
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowStatus {
+    /// Both sides bind the keys identically
+    Unchanged,
+    /// Only the right side binds the keys
+    Added,
+    /// Only the left side binds the keys
+    Removed,
+    /// Both sides bind the keys, differently
+    Changed,
+}
+
+impl RowStatus {
+    /// Single-character marker for display (`+`, `−`, `~` or blank)
+    pub fn marker(&self) -> &'static str {
+        match self {
+            RowStatus::Unchanged => " ",
+            RowStatus::Added => "+",
+            RowStatus::Removed => "−",
+            RowStatus::Changed => "~",
+        }
+    }
+}
+
+/// Which side of a comparison to take for a row
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Side {
+    #[default]
+    Left,
+    Right,
+}
+
+/// Everything both sides bind to one key combo in one submap
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComparisonRow {
+    pub key_combo: KeyCombo,
+    pub submap: Option<String>,
+    /// Left-side bindings (more than one if the keys conflict)
+    pub left: Vec<Keybinding>,
+    /// Right-side bindings
+    pub right: Vec<Keybinding>,
+}
+
+impl ComparisonRow {
+    /// Compares the two sides, ignoring binding order and formatting
+    pub fn status(&self) -> RowStatus {
+        match (self.left.is_empty(), self.right.is_empty()) {
+            (true, _) => RowStatus::Added,
+            (_, true) => RowStatus::Removed,
+            _ if self.left.len() == self.right.len()
+                && self
+                    .left
+                    .iter()
+                    .all(|binding| self.right.iter().any(|other| binding.is_equivalent(other))) =>
+            {
+                RowStatus::Unchanged
+            }
+            _ => RowStatus::Changed,
+        }
+    }
+
+    /// Returns the bindings of one side
+    pub fn side(&self, side: Side) -> &[Keybinding] {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+}
+
+/// Aligns two binding sets by key combo and submap
+///
+/// Rows follow the left side's order, followed by the keys only the right
+/// side binds, in the right side's order.
+pub fn compare_bindings(left: &[Keybinding], right: &[Keybinding]) -> Vec<ComparisonRow> {
+    let mut rows: Vec<ComparisonRow> = Vec::new();
+    let mut index: HashMap<(KeyCombo, Option<String>), usize> = HashMap::new();
+
+    for (binding, side) in left
+        .iter()
+        .map(|binding| (binding, Side::Left))
+        .chain(right.iter().map(|binding| (binding, Side::Right)))
+    {
+        let key = (binding.key_combo.normalised(), binding.submap.clone());
+        let row = *index.entry(key).or_insert_with(|| {
+            rows.push(ComparisonRow {
+                key_combo: binding.key_combo.clone(),
+                submap: binding.submap.clone(),
+                left: Vec::new(),
+                right: Vec::new(),
+            });
+            rows.len() - 1
+        });
+
+        match side {
+            Side::Left => rows[row].left.push(binding.clone()),
+            Side::Right => rows[row].right.push(binding.clone()),
+        }
+    }
+
+    rows
+}
+
+/// Builds the merged binding list from a choice per row
+///
+/// # Arguments
+///
+/// * `rows` - Rows from [`compare_bindings`]
+/// * `choices` - Side to take per row; missing entries take the left side
+pub fn merge_rows(rows: &[ComparisonRow], choices: &[Side]) -> Vec<Keybinding> {
+    rows.iter()
+        .enumerate()
+        .flat_map(|(i, row)| row.side(choices.get(i).copied().unwrap_or_default()))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,272 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dispatcher availability by Hyprland version
+//!
+//! Hyprland adds and removes dispatchers between releases. A binding that
+//! uses a dispatcher the running compositor does not know is silently
+//! ignored, so the version reported over IPC is checked against a table of
+//! when each dispatcher appeared or went away.
+//!
+//! Dispatchers without a version in the table are treated as available in
+//! every release. Every bind variant the parser understands (`bind`,
+//! `binde`, `bindm`, `bindr`, `bindl`, `bindel`) predates the oldest
+//! release in the table, so only dispatchers are checked.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::compat::{is_dispatcher_available, HyprlandVersion};
+//!
+//! let version = HyprlandVersion::parse("v0.41.2").unwrap();
+//! assert!(is_dispatcher_available("exec", version));
+//! assert!(!is_dispatcher_available("fullscreenstate", version));
+//! ```
+
+use std::fmt;
+
+use crate::core::Keybinding;
+
+/// A Hyprland release version
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HyprlandVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl HyprlandVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses a version or release tag
+    ///
+    /// Accepts `0.41.2`, `v0.41.2` and git-describe tags such as
+    /// `v0.41.2-12-gdeadbeef`. A missing patch number counts as 0.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(HyprlandVersion)` - The text starts with a version number
+    /// * `None` - No version could be read
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let text = text.strip_prefix('v').unwrap_or(text);
+        let core = text.split(['-', '+']).next()?;
+
+        let mut numbers = core.split('.').map(|part| part.parse::<u32>());
+        let major = numbers.next()?.ok()?;
+        let minor = numbers.next()?.ok()?;
+        let patch = match numbers.next() {
+            Some(patch) => patch.ok()?,
+            None => 0,
+        };
+
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for HyprlandVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// When a dispatcher was available
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DispatcherAvailability {
+    /// Dispatcher name as written in the config
+    pub name: &'static str,
+    /// First release with the dispatcher (`None`: every supported release)
+    pub since: Option<HyprlandVersion>,
+    /// First release without it (`None`: still available)
+    pub removed: Option<HyprlandVersion>,
+}
+
+impl DispatcherAvailability {
+    /// Returns whether the dispatcher exists in a release
+    pub fn is_available_in(&self, version: HyprlandVersion) -> bool {
+        self.since.is_none_or(|since| version >= since)
+            && self.removed.is_none_or(|removed| version < removed)
+    }
+}
+
+const fn always(name: &'static str) -> DispatcherAvailability {
+    DispatcherAvailability {
+        name,
+        since: None,
+        removed: None,
+    }
+}
+
+const fn since(name: &'static str, version: HyprlandVersion) -> DispatcherAvailability {
+    DispatcherAvailability {
+        name,
+        since: Some(version),
+        removed: None,
+    }
+}
+
+const fn removed(name: &'static str, version: HyprlandVersion) -> DispatcherAvailability {
+    DispatcherAvailability {
+        name,
+        since: None,
+        removed: Some(version),
+    }
+}
+
+/// Known dispatchers and the releases they are available in
+///
+/// Source: https://wiki.hyprland.org/Configuring/Dispatchers/ and the
+/// Hyprland release notes. Add a row when a release adds or removes a
+/// dispatcher.
+pub const DISPATCHERS: &[DispatcherAvailability] = &[
+    always("exec"),
+    always("execr"),
+    always("pass"),
+    since("sendshortcut", HyprlandVersion::new(0, 36, 0)),
+    since("sendkeystate", HyprlandVersion::new(0, 49, 0)),
+    always("killactive"),
+    since("forcekillactive", HyprlandVersion::new(0, 46, 0)),
+    always("closewindow"),
+    since("signal", HyprlandVersion::new(0, 44, 0)),
+    since("signalwindow", HyprlandVersion::new(0, 44, 0)),
+    always("workspace"),
+    always("movetoworkspace"),
+    always("movetoworkspacesilent"),
+    always("togglefloating"),
+    always("setfloating"),
+    always("settiled"),
+    always("fullscreen"),
+    since("fullscreenstate", HyprlandVersion::new(0, 42, 0)),
+    removed("fakefullscreen", HyprlandVersion::new(0, 42, 0)),
+    always("dpms"),
+    always("pin"),
+    always("movefocus"),
+    always("movewindow"),
+    always("swapwindow"),
+    always("centerwindow"),
+    always("resizeactive"),
+    always("moveactive"),
+    always("resizewindowpixel"),
+    always("movewindowpixel"),
+    always("cyclenext"),
+    always("swapnext"),
+    since("tagwindow", HyprlandVersion::new(0, 36, 0)),
+    always("focuswindow"),
+    always("focusmonitor"),
+    always("splitratio"),
+    always("movecursortocorner"),
+    since("movecursor", HyprlandVersion::new(0, 34, 0)),
+    always("renameworkspace"),
+    always("exit"),
+    always("forcerendererreload"),
+    always("movecurrentworkspacetomonitor"),
+    always("focusworkspaceoncurrentmonitor"),
+    always("moveworkspacetomonitor"),
+    always("swapactiveworkspaces"),
+    always("bringactivetotop"),
+    always("alterzorder"),
+    always("togglespecialworkspace"),
+    always("focusurgentorlast"),
+    always("togglegroup"),
+    always("changegroupactive"),
+    always("focuscurrentorlast"),
+    always("lockgroups"),
+    always("lockactivegroup"),
+    always("moveintogroup"),
+    always("moveoutofgroup"),
+    always("movewindoworgroup"),
+    always("movegroupwindow"),
+    always("denywindowfromgroup"),
+    always("setignoregrouplock"),
+    always("global"),
+    always("submap"),
+    since("event", HyprlandVersion::new(0, 38, 0)),
+    since("setprop", HyprlandVersion::new(0, 42, 0)),
+    always("toggleopaque"),
+    always("pseudo"),
+    always("layoutmsg"),
+];
+
+/// Looks up a dispatcher in [`DISPATCHERS`] (case-insensitive)
+pub fn dispatcher_availability(name: &str) -> Option<&'static DispatcherAvailability> {
+    let name = name.trim();
+    DISPATCHERS
+        .iter()
+        .find(|entry| entry.name.eq_ignore_ascii_case(name))
+}
+
+/// Returns whether a dispatcher can be used with a Hyprland release
+///
+/// Unknown dispatchers are not judged here; the validator decides whether
+/// they are allowed at all.
+pub fn is_dispatcher_available(name: &str, version: HyprlandVersion) -> bool {
+    dispatcher_availability(name).is_none_or(|entry| entry.is_available_in(version))
+}
+
+/// Returns the known dispatchers available in a release, in table order
+pub fn available_dispatchers(version: HyprlandVersion) -> Vec<&'static str> {
+    DISPATCHERS
+        .iter()
+        .filter(|entry| entry.is_available_in(version))
+        .map(|entry| entry.name)
+        .collect()
+}
+
+/// Explains why a dispatcher does not work with a Hyprland release
+///
+/// # Returns
+///
+/// * `Some(String)` - The dispatcher is too new or was removed
+/// * `None` - The dispatcher is fine (or unknown)
+pub fn dispatcher_warning(dispatcher: &str, version: HyprlandVersion) -> Option<String> {
+    let entry = dispatcher_availability(dispatcher)?;
+
+    if let Some(since) = entry.since.filter(|since| version < *since) {
+        return Some(format!(
+            "'{}' needs Hyprland {} or newer (running {})",
+            entry.name, since, version
+        ));
+    }
+    if let Some(removed) = entry.removed.filter(|removed| version >= *removed) {
+        return Some(format!(
+            "'{}' was removed in Hyprland {} (running {})",
+            entry.name, removed, version
+        ));
+    }
+
+    None
+}
+
+/// Collects the bindings that do not work with a Hyprland release
+///
+/// # Returns
+///
+/// Each affected binding with the reason from [`dispatcher_warning`]
+pub fn version_warnings(
+    bindings: &[Keybinding],
+    version: HyprlandVersion,
+) -> Vec<(Keybinding, String)> {
+    bindings
+        .iter()
+        .filter_map(|binding| {
+            let warning = dispatcher_warning(&binding.dispatcher, version)?;
+            Some((binding.clone(), warning))
+        })
+        .collect()
+}
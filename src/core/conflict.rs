@@ -18,34 +18,310 @@
 //! indexing. When multiple keybindings use the same key combination,
 //! they are flagged as conflicts for user resolution.
 //!
+//! Bindings in different submaps are never active at the same time, so a
+//! combo only conflicts with bindings of the same submap.
+//!
+//! Combos are compared in normalised form: `SUPER, Return`, `SUPER, RETURN`
+//! and `SUPER, enter` are the same key to Hyprland and conflict here too
+//! (see [`crate::core::keysyms`]).
+//!
+//! Universal bindings (`bindu`) are the exception: they work in every
+//! submap, so a `catchall` in a submap that does not bind their combo
+//! itself catches their key too, and is reported as shadowing them.
+//!
+//! Optionally, the detector also flags bindings on combos reserved by
+//! Hyprland, the system or common tools (see [`crate::core::reserved`]).
+//!
+//! A second index, keyed by action, finds the opposite of a conflict: one
+//! action (dispatcher and arguments) reachable from several key combos.
+//! That is not an error, but often a leftover worth cleaning up (see
+//! [`DuplicateAction`]).
+//!
+//! Every conflict has a [`ConflictKind`] and, derived from it, a
+//! [`ConflictSeverity`] for sorting and colour-coding: exact duplicates are
+//! low, catchall shadowing and press/release pairs medium, and different
+//! actions on one key high.
+//!
 //! # Performance
 //! - Add binding: O(1) average case
 //! - Check conflict: O(1) average case
 //! - List all conflicts: O(n) where n = number of unique key combos
+//! - List duplicate actions: O(a) where a = number of unique actions
 //!
 //! For typical configs (100-500 bindings), conflict checking completes
 //! in <5 microseconds.
 
-use crate::core::types::{KeyCombo, Keybinding};
-use std::collections::HashMap;
+use crate::core::{
+    equivalence::canonical_args,
+    explanations::{self, Explanation},
+    reserved::{self, ReservedShadow},
+    types::{BindFlag, KeyCombo, Keybinding},
+};
+use serde::Serialize;
+use std::{collections::HashMap, fmt, path::PathBuf};
+
+/// Dispatchers that are bound to several keys by design
+///
+/// Leaving a submap from both Escape and Return is the usual idiom, not
+/// a leftover.
+const REPEATABLE_DISPATCHERS: &[&str] = &["submap"];
 
 /// Detects keybinding conflicts in O(1) time using HashMap-based indexing.
 ///
-/// Uses a HashMap where keys are KeyCombos and values are vectors of all
-/// bindings using that combo. A conflict exists when any vector has length > 1.
+/// Uses a HashMap keyed by submap and KeyCombo, whose values are all
+/// bindings using that combo in that submap. A conflict exists when any
+/// vector has length > 1.
 pub struct ConflictDetector {
-    /// Maps KeyCombo to all bindings using that combination.
-    bindings: HashMap<KeyCombo, Vec<Keybinding>>,
+    /// Maps (submap, KeyCombo) to all bindings using that combination there.
+    bindings: HashMap<ScopedCombo, Vec<Keybinding>>,
+    /// Maps (submap, dispatcher, arguments) to all bindings performing it.
+    actions: HashMap<ScopedAction, Vec<Keybinding>>,
+    /// Every submap seen, for lookups across all of them (`None`: global)
+    submaps: Vec<Option<String>>,
+    /// Whether [`find_reserved_shadows`](Self::find_reserved_shadows) checks
+    check_reserved: bool,
 }
 
+/// A key combo within one submap (`None`: the global bindings)
+type ScopedCombo = (Option<String>, KeyCombo);
+
+/// An action within one submap: lowercase dispatcher and canonical arguments
+type ScopedAction = (Option<String>, String, Vec<String>);
+
 /// Represents a detected conflict between keybindings.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Conflict {
     /// The key combination that has conflicts
     pub key_combo: KeyCombo,
 
+    /// Submap the clashing bindings are declared in (`None`: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submap: Option<String>,
+
     /// All bindings using this key combo (always 2 or more)
     pub conflicting_bindings: Vec<Keybinding>,
+
+    /// How Hyprland will treat the clashing bindings
+    pub kind: ConflictKind,
+
+    /// How much attention the conflict needs (follows from `kind`)
+    pub severity: ConflictSeverity,
+}
+
+/// How the conflicts of a config changed between two checks, for `watch`
+///
+/// Conflicts are told apart by submap and key combo. One whose bindings
+/// differ (a binding added, removed or edited) has changed; bindings that
+/// only moved to other lines have not.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConflictChanges {
+    /// Conflicts that were not there before
+    pub added: Vec<Conflict>,
+    /// Conflicts still there, with different bindings
+    pub changed: Vec<Conflict>,
+    /// Conflicts that are gone (as they were before)
+    pub resolved: Vec<Conflict>,
+}
+
+impl ConflictChanges {
+    /// Compares the conflicts of two checks
+    ///
+    /// # Arguments
+    ///
+    /// * `before` - Conflicts found by the earlier check
+    /// * `after` - Conflicts found now
+    pub fn between(before: &[Conflict], after: &[Conflict]) -> Self {
+        let find = |conflicts: &[Conflict], wanted: &Conflict| {
+            conflicts
+                .iter()
+                .find(|conflict| {
+                    conflict.submap == wanted.submap && conflict.key_combo == wanted.key_combo
+                })
+                .cloned()
+        };
+        let bind_lines = |conflict: &Conflict| {
+            conflict
+                .conflicting_bindings
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let mut changes = ConflictChanges::default();
+        for conflict in after {
+            match find(before, conflict) {
+                None => changes.added.push(conflict.clone()),
+                Some(earlier) if bind_lines(&earlier) != bind_lines(conflict) => {
+                    changes.changed.push(conflict.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        changes.resolved = before
+            .iter()
+            .filter(|conflict| find(after, conflict).is_none())
+            .cloned()
+            .collect();
+        changes
+    }
+
+    /// Whether nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.resolved.is_empty()
+    }
+}
+
+/// One action bound to several key combos in the same submap
+///
+/// Arguments are compared in canonical form, so `exec, kitty` and
+/// `exec, "kitty"` are the same action (see [`crate::core::equivalence`]).
+/// Bind flags are not compared: `bindl` and `bind` running the same
+/// command still make one of the keys redundant.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DuplicateAction {
+    /// Dispatcher shared by the bindings, as the first one spells it
+    pub dispatcher: String,
+
+    /// Arguments shared by the bindings, as the first one spells them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+
+    /// Submap the bindings are declared in (`None`: global)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submap: Option<String>,
+
+    /// All bindings performing the action, in file and line order
+    pub bindings: Vec<Keybinding>,
+}
+
+impl DuplicateAction {
+    /// The distinct key combos reaching the action (always 2 or more), as
+    /// first written
+    pub fn key_combos(&self) -> Vec<KeyCombo> {
+        let mut seen: Vec<KeyCombo> = Vec::new();
+        let mut combos = Vec::new();
+        for binding in &self.bindings {
+            let normalised = binding.key_combo.normalised();
+            if !seen.contains(&normalised) {
+                seen.push(normalised);
+                combos.push(binding.key_combo.clone());
+            }
+        }
+        combos
+    }
+}
+
+impl fmt::Display for DuplicateAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.dispatcher)?;
+        if let Some(args) = &self.args {
+            write!(f, " {}", args)?;
+        }
+        let combos: Vec<String> = self.key_combos().iter().map(ToString::to_string).collect();
+        write!(f, " is bound to {}", combos.join(", "))?;
+        if let Some(submap) = &self.submap {
+            write!(f, " (submap {})", submap)?;
+        }
+        Ok(())
+    }
+}
+
+/// How much attention a conflict needs
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictSeverity {
+    /// Harmless apart from repeating the action; safe to clean up
+    Low,
+    /// Possibly intended, but worth a look
+    Medium,
+    /// Different actions compete for one key
+    High,
+}
+
+impl ConflictSeverity {
+    /// Short lowercase name, as used in CSS classes and reports
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictSeverity::Low => "low",
+            ConflictSeverity::Medium => "medium",
+            ConflictSeverity::High => "high",
+        }
+    }
+}
+
+/// Classification of a conflict by its runtime effect
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictKind {
+    /// Different actions on the same combo; all of them run in config order
+    MultipleActions,
+    /// Every binding performs the identical action, so it runs repeatedly
+    RedundantDuplicate,
+    /// Release (`bindr`) and press binds share the combo
+    PressAndRelease,
+    /// A submap's `catchall` also catches the key of a universal binding
+    CatchallShadowing,
+}
+
+impl ConflictKind {
+    /// Classifies the bindings sharing one key combo
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - All bindings for the combo (2 or more)
+    pub fn classify(bindings: &[Keybinding]) -> Self {
+        // Formatting differences (quoting, spacing) do not make a new action
+        let action = |binding: &Keybinding| {
+            let canonical = binding.canonical();
+            (canonical.bind_type, canonical.dispatcher, canonical.args)
+        };
+        let first = action(&bindings[0]);
+        let identical = bindings.iter().all(|binding| action(binding) == first);
+        if identical {
+            return ConflictKind::RedundantDuplicate;
+        }
+
+        let releases = bindings
+            .iter()
+            .filter(|binding| binding.bind_type.contains(BindFlag::Release))
+            .count();
+        if releases > 0 && releases < bindings.len() {
+            return ConflictKind::PressAndRelease;
+        }
+
+        ConflictKind::MultipleActions
+    }
+
+    /// Stable identifier of the embedded explanation for this kind
+    pub fn explanation_id(&self) -> &'static str {
+        self.explanation().id
+    }
+
+    /// How much attention this kind of conflict needs
+    pub fn severity(&self) -> ConflictSeverity {
+        match self {
+            ConflictKind::RedundantDuplicate => ConflictSeverity::Low,
+            ConflictKind::PressAndRelease | ConflictKind::CatchallShadowing => {
+                ConflictSeverity::Medium
+            }
+            ConflictKind::MultipleActions => ConflictSeverity::High,
+        }
+    }
+
+    /// Embedded explanation of how Hyprland resolves this kind of conflict
+    pub fn explanation(&self) -> &'static Explanation {
+        match self {
+            ConflictKind::MultipleActions => &explanations::MULTIPLE_ACTIONS,
+            ConflictKind::RedundantDuplicate => &explanations::REDUNDANT_DUPLICATE,
+            ConflictKind::PressAndRelease => &explanations::PRESS_AND_RELEASE,
+            ConflictKind::CatchallShadowing => &explanations::CATCHALL_SHADOWING,
+        }
+    }
+
+    /// Upstream documentation for this kind of conflict
+    pub fn docs_url(&self) -> &'static str {
+        self.explanation().docs_url
+    }
 }
 
 impl ConflictDetector {
@@ -53,42 +329,258 @@ impl ConflictDetector {
     pub fn new() -> Self {
         Self {
             bindings: HashMap::new(),
+            actions: HashMap::new(),
+            submaps: Vec::new(),
+            check_reserved: false,
         }
     }
 
+    /// Enables or disables the reserved combo check (off by default)
+    pub fn set_check_reserved(&mut self, enabled: bool) {
+        self.check_reserved = enabled;
+    }
+
+    /// Returns whether the reserved combo check is enabled
+    pub fn checks_reserved(&self) -> bool {
+        self.check_reserved
+    }
+
+    /// Finds bindings on reserved combos that do something else
+    ///
+    /// # Returns
+    ///
+    /// Flagged bindings in file and line order; none while the check is
+    /// disabled (see [`set_check_reserved`](Self::set_check_reserved))
+    pub fn find_reserved_shadows(&self) -> Vec<ReservedShadow> {
+        if !self.check_reserved {
+            return Vec::new();
+        }
+
+        let mut bindings: Vec<Keybinding> = self.bindings.values().flatten().cloned().collect();
+        bindings.sort_by_key(Self::position);
+        reserved::reserved_shadows(&bindings)
+    }
+
     /// Adds a keybinding to the detector.
     ///
     /// Time complexity: O(1) average case
     pub fn add_binding(&mut self, binding: Keybinding) {
-        // Clone KeyCombo for HashMap ownership
+        if !self.submaps.contains(&binding.submap) {
+            self.submaps.push(binding.submap.clone());
+        }
+
+        self.actions
+            .entry(Self::action_of(&binding))
+            .or_default()
+            .push(binding.clone());
+
+        // Normalised copy of the KeyCombo for HashMap ownership
         self.bindings
-            .entry(binding.key_combo.clone())
+            .entry((binding.submap.clone(), binding.key_combo.normalised()))
             .or_default()
             .push(binding);
     }
 
-    /// Finds all conflicts (key combos with 2 or more bindings).
+    /// Finds all conflicts (key combos with 2 or more bindings in the
+    /// same submap).
+    ///
+    /// Bindings in different device sections do not conflict with each
+    /// other (see [`Keybinding::shares_device_with`]), only with bindings
+    /// of their own device or of every device.
     ///
-    /// Time complexity: O(n) where n = number of unique key combos.
+    /// A `catchall` binding only handles keys nothing else in its submap
+    /// binds, so it never conflicts with that submap's own keys; only two
+    /// catchall bindings can conflict with each other. It does shadow
+    /// universal bindings from elsewhere whose combo the submap leaves
+    /// unbound (see [`ConflictKind::CatchallShadowing`]).
+    ///
+    /// Time complexity: O(n) where n = number of unique key combos, plus
+    /// O(c × u) for c catchall and u universal bindings.
     pub fn find_conflicts(&self) -> Vec<Conflict> {
-        self.bindings
+        let mut conflicts: Vec<Conflict> = self
+            .bindings
             .iter()
+            .map(|(scope, bindings)| (scope, Self::clashing(bindings)))
             .filter(|(_, bindings)| bindings.len() > 1)
-            .map(|(key_combo, bindings)| Conflict {
-                key_combo: key_combo.clone(),
-                conflicting_bindings: bindings.clone(),
+            .map(|((submap, key_combo), bindings)| {
+                let kind = ConflictKind::classify(&bindings);
+                Conflict {
+                    key_combo: key_combo.clone(),
+                    submap: submap.clone(),
+                    conflicting_bindings: bindings,
+                    kind,
+                    severity: kind.severity(),
+                }
+            })
+            .collect();
+
+        conflicts.extend(self.catchall_shadowing());
+        conflicts
+    }
+
+    /// Finds actions reachable from 2 or more distinct key combos in the
+    /// same submap.
+    ///
+    /// Bindings on the same combo are conflicts, not duplicate actions, so
+    /// they only count once. Catchall bindings and dispatchers commonly
+    /// bound to several keys on purpose (`submap`) are left out.
+    ///
+    /// Time complexity: O(a) where a = number of unique actions
+    ///
+    /// # Returns
+    ///
+    /// Duplicate actions ordered by where their first binding is declared
+    pub fn find_duplicate_actions(&self) -> Vec<DuplicateAction> {
+        let mut duplicates: Vec<DuplicateAction> = self
+            .actions
+            .iter()
+            .filter(|((_, dispatcher, _), _)| {
+                !REPEATABLE_DISPATCHERS.contains(&dispatcher.as_str())
+            })
+            .filter_map(|((submap, _, _), bindings)| {
+                let mut bindings: Vec<Keybinding> = bindings
+                    .iter()
+                    .filter(|binding| !binding.key_combo.is_catchall())
+                    .cloned()
+                    .collect();
+                bindings.sort_by_key(Self::position);
+                let first = bindings.first()?.clone();
+                let duplicate = DuplicateAction {
+                    dispatcher: first.dispatcher.trim().to_string(),
+                    args: first.args.clone(),
+                    submap: submap.clone(),
+                    bindings,
+                };
+                (duplicate.key_combos().len() > 1).then_some(duplicate)
+            })
+            .collect();
+
+        duplicates.sort_by_key(|duplicate| Self::position(&duplicate.bindings[0]));
+        duplicates
+    }
+
+    /// Returns whether the binding's action is reachable from another key
+    /// combo in its submap.
+    ///
+    /// Time complexity: O(k) average case, k = bindings performing the action
+    pub fn has_duplicate_action(&self, binding: &Keybinding) -> bool {
+        let action = Self::action_of(binding);
+        if binding.key_combo.is_catchall() || REPEATABLE_DISPATCHERS.contains(&action.1.as_str()) {
+            return false;
+        }
+        let combo = binding.key_combo.normalised();
+        self.actions.get(&action).is_some_and(|bindings| {
+            bindings.iter().any(|other| {
+                !other.key_combo.is_catchall() && other.key_combo.normalised() != combo
+            })
+        })
+    }
+
+    /// Key of the action index for a binding
+    fn action_of(binding: &Keybinding) -> ScopedAction {
+        (
+            binding.submap.clone(),
+            binding.dispatcher.trim().to_lowercase(),
+            binding
+                .args
+                .as_deref()
+                .map(canonical_args)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// The bindings of one combo that clash with at least one other
+    ///
+    /// Bindings on the same combo only clash if they can fire on the same
+    /// input device.
+    fn clashing(bindings: &[Keybinding]) -> Vec<Keybinding> {
+        bindings
+            .iter()
+            .enumerate()
+            .filter(|(index, binding)| {
+                bindings.iter().enumerate().any(|(other_index, other)| {
+                    other_index != *index && binding.shares_device_with(other)
+                })
             })
+            .map(|(_, binding)| binding.clone())
             .collect()
     }
 
+    /// File and line a binding is declared at, for stable ordering
+    fn position(binding: &Keybinding) -> Option<(PathBuf, usize)> {
+        binding
+            .location
+            .as_ref()
+            .map(|location| (location.file.clone(), location.line))
+    }
+
+    /// Universal bindings a submap's catchall also catches
+    fn catchall_shadowing(&self) -> Vec<Conflict> {
+        let universal: Vec<&Keybinding> = self
+            .bindings
+            .values()
+            .flatten()
+            .filter(|binding| {
+                binding.bind_type.contains(BindFlag::Universal) && !binding.key_combo.is_catchall()
+            })
+            .collect();
+        if universal.is_empty() {
+            return Vec::new();
+        }
+
+        let mut conflicts = Vec::new();
+        for ((submap, key_combo), catchalls) in &self.bindings {
+            if submap.is_none() || !key_combo.is_catchall() {
+                continue;
+            }
+
+            for binding in &universal {
+                let combo = binding.key_combo.normalised();
+                if binding.submap == *submap
+                    || combo.modifiers != key_combo.modifiers
+                    || self.bindings.contains_key(&(submap.clone(), combo.clone()))
+                {
+                    continue;
+                }
+
+                let mut conflicting_bindings = vec![(*binding).clone()];
+                conflicting_bindings.extend(catchalls.iter().cloned());
+                conflicts.push(Conflict {
+                    key_combo: combo,
+                    submap: submap.clone(),
+                    conflicting_bindings,
+                    kind: ConflictKind::CatchallShadowing,
+                    severity: ConflictKind::CatchallShadowing.severity(),
+                });
+            }
+        }
+        conflicts
+    }
+
     /// Checks if a specific key combo has conflicts.
     ///
-    /// Returns true if this KeyCombo has 2 or more bindings.
+    /// Returns true if this KeyCombo has 2 or more bindings in one submap
+    /// that can fire on the same input device.
+    ///
+    /// Time complexity: O(s) where s = number of submaps.
     pub fn has_conflict(&self, key_combo: &KeyCombo) -> bool {
+        let key_combo = key_combo.normalised();
+        self.submaps.iter().any(|submap| {
+            self.bindings
+                .get(&(submap.clone(), key_combo.clone()))
+                .is_some_and(|bindings| Self::clashing(bindings).len() > 1)
+        })
+    }
+
+    /// Returns every tracked binding that uses the given key combo in the
+    /// given submap (`None`: global bindings).
+    ///
+    /// Time complexity: O(1) average case
+    pub fn bindings_for(&self, key_combo: &KeyCombo, submap: Option<&str>) -> &[Keybinding] {
         self.bindings
-            .get(key_combo)
-            .map(|bindings| bindings.len() > 1)
-            .unwrap_or(false)
+            .get(&(submap.map(str::to_string), key_combo.normalised()))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
     }
 
     /// Returns the total number of bindings tracked.
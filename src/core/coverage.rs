@@ -0,0 +1,263 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parse coverage of a config
+//!
+//! Classifies every non-blank, non-comment line of a config by what the
+//! parser makes of it, so users can see what the tool might mishandle
+//! before trusting it to write:
+//!
+//! - **Understood**: bind lines, variables, submap markers, `source` lines,
+//!   known Hyprland keywords (`monitor`, `exec-once`, `windowrulev2`, ...),
+//!   `category { ... }` sections and the settings inside them
+//! - **Partial**: bind lines that parsed, but with diagnostics (see
+//!   [`diagnose_config`](crate::core::parser::diagnose_config))
+//! - **Unknown**: everything else, e.g. unknown bind flags (`bindz`) or
+//!   unrecognised keywords
+//!
+//! Only lines the tool writes (bind lines) are at risk of being rewritten
+//! incorrectly; other lines are always preserved verbatim.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::coverage::assess_coverage;
+//!
+//! let report = assess_coverage("$mod = SUPER\nbind = $mod, K, exec, kitty\nbindz = $mod, Q, killactive\n");
+//! assert_eq!(report.total(), 3);
+//! assert_eq!(report.unknown.len(), 1);
+//! assert_eq!(report.unknown[0].line, 3);
+//! ```
+
+use std::{collections::BTreeSet, fmt};
+
+use serde::Serialize;
+
+use crate::core::parser::{
+    collect_variables, diagnose_config, is_bind_line, parse_bind_line, parse_source_line,
+    parse_submap_line, substitute_variables,
+};
+use crate::core::types::BindType;
+
+/// Top-level keywords that are not bindings but are known to Hyprland
+pub const KNOWN_KEYWORDS: &[&str] = &[
+    "monitor",
+    "workspace",
+    "exec",
+    "exec-once",
+    "execr",
+    "execr-once",
+    "exec-shutdown",
+    "env",
+    "envd",
+    "windowrule",
+    "windowrulev2",
+    "layerrule",
+    "animation",
+    "bezier",
+    "unbind",
+    "plugin",
+    "permission",
+    "gesture",
+    "blurls",
+];
+
+/// Section names Hyprland accepts (`name { ... }`), including nested ones
+pub const KNOWN_SECTIONS: &[&str] = &[
+    "general",
+    "decoration",
+    "blur",
+    "shadow",
+    "animations",
+    "input",
+    "touchpad",
+    "touchdevice",
+    "tablet",
+    "gestures",
+    "group",
+    "groupbar",
+    "misc",
+    "binds",
+    "xwayland",
+    "opengl",
+    "render",
+    "cursor",
+    "ecosystem",
+    "experimental",
+    "debug",
+    "dwindle",
+    "master",
+    "device",
+    "plugin",
+    "quirks",
+];
+
+/// A line the parser does not understand
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UnknownConstruct {
+    /// Line number (1-based)
+    pub line: usize,
+    /// The line as written (trimmed)
+    pub text: String,
+    /// Why it is not understood
+    pub reason: String,
+}
+
+impl fmt::Display for UnknownConstruct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} ({})", self.line, self.text, self.reason)
+    }
+}
+
+/// How much of a config the parser understood
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CoverageReport {
+    /// Bind lines parsed without diagnostics
+    pub binds: usize,
+    /// Bind lines parsed with diagnostics
+    pub partial_binds: usize,
+    /// `$name = value` definitions
+    pub variables: usize,
+    /// `submap = ...` markers
+    pub submaps: usize,
+    /// `source = ...` lines
+    pub sources: usize,
+    /// Known keywords, sections and settings
+    pub other_known: usize,
+    /// Lines not understood, in line order
+    pub unknown: Vec<UnknownConstruct>,
+}
+
+impl CoverageReport {
+    /// Number of lines classified (blank lines and comments are not)
+    pub fn total(&self) -> usize {
+        self.understood() + self.partial_binds + self.unknown.len()
+    }
+
+    /// Number of lines fully understood
+    pub fn understood(&self) -> usize {
+        self.binds + self.variables + self.submaps + self.sources + self.other_known
+    }
+
+    /// Percentage of lines fully understood (100 for an empty config)
+    pub fn percentage(&self) -> f64 {
+        match self.total() {
+            0 => 100.0,
+            total => self.understood() as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+/// Classifies every line of a config
+///
+/// # Arguments
+/// * `content` - The full config file content
+///
+/// # Returns
+/// Counts per category and the unknown constructs
+pub fn assess_coverage(content: &str) -> CoverageReport {
+    let variables = collect_variables(content);
+    let partial_lines: BTreeSet<usize> = diagnose_config(content)
+        .into_iter()
+        .map(|diagnostic| diagnostic.line)
+        .collect();
+
+    let mut report = CoverageReport::default();
+    let mut section_depth = 0usize;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let mut unknown = |reason: String| {
+            report.unknown.push(UnknownConstruct {
+                line: line_num,
+                text: trimmed.to_string(),
+                reason,
+            })
+        };
+
+        // Sections: `name {` ... `}`
+        if trimmed == "}" {
+            match section_depth.checked_sub(1) {
+                Some(depth) => {
+                    section_depth = depth;
+                    report.other_known += 1;
+                }
+                None => unknown("closing brace without a section".to_string()),
+            }
+            continue;
+        }
+        if let Some(header) = trimmed.strip_suffix('{') {
+            section_depth += 1;
+            let name = header.trim().split(':').next().unwrap_or_default().trim();
+            if KNOWN_SECTIONS.contains(&name) {
+                report.other_known += 1;
+            } else {
+                unknown(format!("unknown section '{}'", name));
+            }
+            continue;
+        }
+        if section_depth > 0 {
+            if trimmed.contains('=') {
+                report.other_known += 1;
+            } else {
+                unknown("not a 'key = value' setting".to_string());
+            }
+            continue;
+        }
+
+        if trimmed.starts_with('$') {
+            report.variables += 1;
+            continue;
+        }
+
+        let substituted = substitute_variables(trimmed, &variables);
+        if parse_submap_line(&substituted).is_some() {
+            report.submaps += 1;
+            continue;
+        }
+        if parse_source_line(&substituted).is_some() {
+            report.sources += 1;
+            continue;
+        }
+
+        let Some((keyword, _)) = trimmed.split_once('=') else {
+            unknown("not a 'keyword = value' line".to_string());
+            continue;
+        };
+        let keyword = keyword.trim();
+
+        if is_bind_line(trimmed) {
+            if BindType::from_keyword(keyword).is_none() {
+                unknown(format!("bind variant '{}' is not supported", keyword));
+            } else if parse_bind_line(&substituted).is_err() {
+                unknown("bind line does not parse".to_string());
+            } else if partial_lines.contains(&line_num) {
+                report.partial_binds += 1;
+            } else {
+                report.binds += 1;
+            }
+        } else if KNOWN_KEYWORDS.contains(&keyword) || keyword.contains(':') {
+            // `category:option = value` sets an option outside its section
+            report.other_known += 1;
+        } else {
+            unknown(format!("unknown keyword '{}'", keyword));
+        }
+    }
+
+    report
+}
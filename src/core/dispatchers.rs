@@ -0,0 +1,485 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Catalogue of Hyprland dispatchers and the arguments they take
+//!
+//! Every dispatcher the manager accepts is listed in [`DISPATCHER_CATALOGUE`]
+//! with the shape of its arguments, a one-line summary and an example, so
+//! bindings can be checked beyond "the dispatcher exists": `movefocus`
+//! needs a direction, `resizeactive` two deltas, `workspace` a workspace
+//! selector and `killactive` nothing at all.
+//!
+//! The catalogue is also the dispatcher whitelist of
+//! [`validator`](crate::core::validator) and feeds the dispatcher picker of
+//! the edit dialog. Which Hyprland release has which dispatcher is tracked
+//! separately in [`compat`](crate::core::compat).
+//!
+//! Shapes are only as strict as Hyprland itself is predictable: arguments
+//! with many valid forms (window and monitor selectors, layout messages)
+//! are only required to be present.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::dispatchers::validate_arguments;
+//!
+//! assert!(validate_arguments("movefocus", Some("l")).is_ok());
+//! assert!(validate_arguments("movefocus", Some("sideways")).is_err());
+//! assert!(validate_arguments("killactive", None).is_ok());
+//! ```
+
+use crate::core::workspace::parse_workspace_target;
+use ArgumentShape::*;
+
+/// What a dispatcher expects as its arguments
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArgumentShape {
+    /// Takes no arguments
+    Nothing,
+    /// A command line (`exec`, `execr`)
+    Command,
+    /// A workspace selector, optionally followed by `,window`
+    Workspace,
+    /// A direction: `l`, `r`, `u`, `d` (or `left`, `right`, `up`, `down`)
+    Direction,
+    /// A direction or a monitor as `mon:NAME`
+    DirectionOrMonitor,
+    /// Two pixel or percentage deltas, or `exact` and two values
+    Delta,
+    /// A whole number
+    Integer,
+    /// One of a fixed set of words, optionally followed by more arguments
+    Keyword(&'static [&'static str]),
+    /// Anything non-empty (selectors with too many forms to check)
+    Free,
+}
+
+impl ArgumentShape {
+    /// What the arguments look like, for people (e.g. in the help window)
+    pub fn describe(self) -> String {
+        match self {
+            Nothing => "no arguments".to_string(),
+            Command => "a command line".to_string(),
+            Workspace => "a workspace: 3, name:web, special:scratch, +1, e+1, previous".to_string(),
+            Direction => "a direction: l, r, u or d".to_string(),
+            DirectionOrMonitor => "a direction (l, r, u or d) or a monitor (mon:NAME)".to_string(),
+            Delta => "two values such as 10 -10, or exact and two values".to_string(),
+            Integer => "a whole number".to_string(),
+            Keyword(words) => format!("one of {}", words.join(", ")),
+            Free => "a selector or free text".to_string(),
+        }
+    }
+
+    /// Words the first argument can be chosen from, e.g. in a dropdown
+    ///
+    /// Empty for shapes that are not a fixed choice.
+    pub fn choices(self) -> &'static [&'static str] {
+        match self {
+            Direction | DirectionOrMonitor => DIRECTIONS,
+            Keyword(words) => words,
+            _ => &[],
+        }
+    }
+}
+
+/// Directions as written in bindings, in the order they are offered
+const DIRECTIONS: &[&str] = &["l", "r", "u", "d"];
+
+/// A dispatcher and what it takes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DispatcherSpec {
+    /// Dispatcher name as written in the config
+    pub name: &'static str,
+    /// Shape of the arguments
+    pub arguments: ArgumentShape,
+    /// Whether arguments can be left out
+    pub optional: bool,
+    /// What the dispatcher does, in a few words
+    pub summary: &'static str,
+    /// Example arguments (empty for dispatchers without arguments)
+    pub example: &'static str,
+}
+
+const fn takes(
+    name: &'static str,
+    arguments: ArgumentShape,
+    summary: &'static str,
+    example: &'static str,
+) -> DispatcherSpec {
+    DispatcherSpec {
+        name,
+        arguments,
+        optional: false,
+        summary,
+        example,
+    }
+}
+
+const fn may_take(
+    name: &'static str,
+    arguments: ArgumentShape,
+    summary: &'static str,
+    example: &'static str,
+) -> DispatcherSpec {
+    DispatcherSpec {
+        name,
+        arguments,
+        optional: true,
+        summary,
+        example,
+    }
+}
+
+const fn bare(name: &'static str, summary: &'static str) -> DispatcherSpec {
+    DispatcherSpec {
+        name,
+        arguments: ArgumentShape::Nothing,
+        optional: true,
+        summary,
+        example: "",
+    }
+}
+
+const SWITCH: &[&str] = &["on", "off", "toggle"];
+const LOCK: &[&str] = &["lock", "unlock", "toggle"];
+
+/// Every dispatcher the manager accepts
+///
+/// Source: https://wiki.hyprland.org/Configuring/Dispatchers/
+/// Last verified: October 2025. Keep in step with
+/// [`compat::DISPATCHERS`](crate::core::compat::DISPATCHERS).
+pub const DISPATCHER_CATALOGUE: &[DispatcherSpec] = &[
+    takes("exec", Command, "Run a command", "kitty"),
+    takes("execr", Command, "Run a raw command (no rules)", "kitty"),
+    takes("pass", Free, "Pass the key to a window", "class:obs"),
+    takes(
+        "sendshortcut",
+        Free,
+        "Send a shortcut to a window",
+        "CTRL, C, class:kitty",
+    ),
+    takes(
+        "sendkeystate",
+        Free,
+        "Send a key state to a window",
+        "CTRL, C, down, class:kitty",
+    ),
+    bare("killactive", "Close the active window"),
+    bare("forcekillactive", "Kill the active window's process"),
+    takes("closewindow", Free, "Close a window", "class:firefox"),
+    takes("signal", Integer, "Send a signal to the active window", "9"),
+    takes(
+        "signalwindow",
+        Free,
+        "Send a signal to a window",
+        "class:firefox, 9",
+    ),
+    takes("workspace", Workspace, "Switch workspace", "3"),
+    takes(
+        "movetoworkspace",
+        Workspace,
+        "Move the window to a workspace",
+        "3",
+    ),
+    takes(
+        "movetoworkspacesilent",
+        Workspace,
+        "Move the window, staying here",
+        "3",
+    ),
+    may_take("togglefloating", Free, "Toggle floating", "class:mpv"),
+    may_take("setfloating", Free, "Make a window floating", "class:mpv"),
+    may_take("settiled", Free, "Make a window tiled", "class:mpv"),
+    may_take(
+        "fullscreen",
+        Keyword(&["0", "1", "2"]),
+        "Toggle fullscreen (1: maximise)",
+        "0",
+    ),
+    takes(
+        "fullscreenstate",
+        Free,
+        "Set internal and client fullscreen",
+        "2 0",
+    ),
+    bare("fakefullscreen", "Toggle fake fullscreen"),
+    takes("dpms", Keyword(SWITCH), "Switch monitors on or off", "off"),
+    may_take(
+        "pin",
+        Free,
+        "Pin a floating window to all workspaces",
+        "class:mpv",
+    ),
+    takes("movefocus", Direction, "Move focus", "l"),
+    // Without arguments, the mouse dispatchers of `bindm`
+    may_take("movewindow", DirectionOrMonitor, "Move the window", "l"),
+    bare("resizewindow", "Resize the window with the mouse (bindm)"),
+    takes(
+        "swapwindow",
+        Direction,
+        "Swap with the window in a direction",
+        "l",
+    ),
+    may_take(
+        "centerwindow",
+        Keyword(&["1"]),
+        "Centre a floating window",
+        "1",
+    ),
+    takes("resizeactive", Delta, "Resize the active window", "10 -10"),
+    takes("moveactive", Delta, "Move the active window", "10 -10"),
+    takes(
+        "resizewindowpixel",
+        Free,
+        "Resize a window",
+        "exact 50% 50%, class:mpv",
+    ),
+    takes(
+        "movewindowpixel",
+        Free,
+        "Move a window",
+        "exact 0 0, class:mpv",
+    ),
+    may_take("cyclenext", Free, "Focus the next window", "prev"),
+    may_take(
+        "swapnext",
+        Keyword(&["prev"]),
+        "Swap with the next window",
+        "prev",
+    ),
+    takes("tagwindow", Free, "Tag a window", "+work"),
+    takes("focuswindow", Free, "Focus a window", "class:firefox"),
+    takes("focusmonitor", Free, "Focus a monitor", "+1"),
+    takes("splitratio", Free, "Change the split ratio", "+0.1"),
+    takes(
+        "movecursortocorner",
+        Keyword(&["0", "1", "2", "3"]),
+        "Move the cursor to a corner",
+        "2",
+    ),
+    takes("movecursor", Free, "Move the cursor", "100 100"),
+    takes("renameworkspace", Free, "Rename a workspace", "1 web"),
+    bare("exit", "Exit Hyprland"),
+    bare("forcerendererreload", "Reload the renderer"),
+    takes(
+        "movecurrentworkspacetomonitor",
+        Free,
+        "Move the workspace to a monitor",
+        "+1",
+    ),
+    takes(
+        "focusworkspaceoncurrentmonitor",
+        Workspace,
+        "Show a workspace here",
+        "3",
+    ),
+    takes(
+        "moveworkspacetomonitor",
+        Free,
+        "Move a workspace to a monitor",
+        "3 DP-1",
+    ),
+    takes(
+        "swapactiveworkspaces",
+        Free,
+        "Swap two monitors' workspaces",
+        "DP-1 DP-2",
+    ),
+    bare("bringactivetotop", "Raise the active window"),
+    takes("alterzorder", Free, "Raise or lower a window", "top"),
+    may_take(
+        "togglespecialworkspace",
+        Free,
+        "Show or hide a special workspace",
+        "scratch",
+    ),
+    bare("focusurgentorlast", "Focus the urgent or last window"),
+    bare("togglegroup", "Toggle the window group"),
+    may_take("changegroupactive", Free, "Switch window in the group", "f"),
+    bare("focuscurrentorlast", "Focus the previous window"),
+    takes("lockgroups", Keyword(LOCK), "Lock all groups", "toggle"),
+    takes(
+        "lockactivegroup",
+        Keyword(LOCK),
+        "Lock the active group",
+        "toggle",
+    ),
+    takes(
+        "moveintogroup",
+        Direction,
+        "Move the window into a group",
+        "l",
+    ),
+    may_take(
+        "moveoutofgroup",
+        Free,
+        "Move a window out of its group",
+        "class:mpv",
+    ),
+    takes(
+        "movewindoworgroup",
+        Direction,
+        "Move the window or group",
+        "l",
+    ),
+    takes(
+        "movegroupwindow",
+        Keyword(&["f", "b"]),
+        "Swap windows within the group",
+        "f",
+    ),
+    takes(
+        "denywindowfromgroup",
+        Keyword(SWITCH),
+        "Keep windows out of the group",
+        "toggle",
+    ),
+    takes(
+        "setignoregrouplock",
+        Keyword(SWITCH),
+        "Ignore group locks",
+        "toggle",
+    ),
+    takes("global", Free, "Trigger a global shortcut", "obs:record"),
+    takes("submap", Free, "Enter a submap", "resize"),
+    takes("event", Free, "Emit a custom event", "my-event"),
+    takes(
+        "setprop",
+        Free,
+        "Set a window property",
+        "active opaque toggle",
+    ),
+    bare("toggleopaque", "Toggle opacity"),
+    may_take("pseudo", Free, "Toggle pseudotiling", "class:mpv"),
+    takes("layoutmsg", Free, "Send a layout message", "togglesplit"),
+    takes(
+        "workspaceopt",
+        Keyword(&["allfloat", "allpseudo"]),
+        "Set a workspace option",
+        "allfloat",
+    ),
+];
+
+/// Looks up a dispatcher in [`DISPATCHER_CATALOGUE`] (case-insensitive)
+pub fn dispatcher_spec(name: &str) -> Option<&'static DispatcherSpec> {
+    let name = name.trim();
+    DISPATCHER_CATALOGUE
+        .iter()
+        .find(|spec| spec.name.eq_ignore_ascii_case(name))
+}
+
+/// Checks dispatcher arguments against the catalogue
+///
+/// # Arguments
+/// * `dispatcher` - Dispatcher name
+/// * `args` - Its arguments (`None` or blank: none given)
+///
+/// # Returns
+/// * `Ok(())` - The arguments fit, or the dispatcher is not catalogued
+///   (the whitelist rejects those separately)
+/// * `Err(String)` - Why they do not fit
+pub fn validate_arguments(dispatcher: &str, args: Option<&str>) -> Result<(), String> {
+    let Some(spec) = dispatcher_spec(dispatcher) else {
+        return Ok(());
+    };
+    let args = args.map(str::trim).filter(|args| !args.is_empty());
+
+    let Some(args) = args else {
+        return if spec.optional {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} needs arguments, e.g. '{}'",
+                spec.name, spec.example
+            ))
+        };
+    };
+
+    let first = args
+        .split([',', ' '])
+        .find(|word| !word.is_empty())
+        .unwrap_or_default();
+    match spec.arguments {
+        ArgumentShape::Nothing => Err(format!("{} takes no arguments", spec.name)),
+        ArgumentShape::Command | ArgumentShape::Free => Ok(()),
+        ArgumentShape::Workspace => parse_workspace_target(args).map(|_| ()),
+        ArgumentShape::Direction => is_direction(args)
+            .then_some(())
+            .ok_or_else(|| format!("{} needs a direction: l, r, u or d", spec.name)),
+        ArgumentShape::DirectionOrMonitor => (is_direction(args) || args.starts_with("mon:"))
+            .then_some(())
+            .ok_or_else(|| {
+                format!(
+                    "{} needs a direction (l, r, u or d) or a monitor (mon:NAME)",
+                    spec.name
+                )
+            }),
+        ArgumentShape::Delta => is_delta(args).then_some(()).ok_or_else(|| {
+            format!(
+                "{} needs two values such as '10 -10' or 'exact 50% 50%'",
+                spec.name
+            )
+        }),
+        ArgumentShape::Integer => args
+            .parse::<i64>()
+            .map(|_| ())
+            .map_err(|_| format!("{} needs a whole number, not '{}'", spec.name, args)),
+        ArgumentShape::Keyword(words) => words
+            .iter()
+            .any(|word| first.eq_ignore_ascii_case(word))
+            .then_some(())
+            .ok_or_else(|| format!("{} takes {}, not '{}'", spec.name, words.join(", "), first)),
+    }
+}
+
+/// Turns the `Exec` line of a desktop entry into an `exec` command
+///
+/// Drops the field codes (`%U`, `%f`, ...) that only a launcher fills in,
+/// and unescapes `%%`.
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::dispatchers::desktop_exec_command;
+///
+/// assert_eq!(desktop_exec_command("/usr/bin/firefox %u"), "/usr/bin/firefox");
+/// assert_eq!(desktop_exec_command("printf 100%% %F"), "printf 100%");
+/// ```
+pub fn desktop_exec_command(exec: &str) -> String {
+    let mut command = String::with_capacity(exec.len());
+    let mut chars = exec.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            command.push(c);
+        } else if let Some('%') = chars.next() {
+            command.push('%');
+        }
+    }
+    command.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_direction(args: &str) -> bool {
+    matches!(
+        args.to_lowercase().as_str(),
+        "l" | "r" | "u" | "d" | "t" | "b" | "left" | "right" | "up" | "down"
+    )
+}
+
+/// Whether arguments are `X Y` or `exact X Y`, each a number or percentage
+fn is_delta(args: &str) -> bool {
+    let mut words: Vec<&str> = args.split_whitespace().collect();
+    if words.first() == Some(&"exact") {
+        words.remove(0);
+    }
+    let is_value = |word: &&str| word.trim_end_matches('%').parse::<f64>().is_ok();
+    words.len() == 2 && words.iter().all(is_value)
+}
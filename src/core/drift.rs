@@ -0,0 +1,273 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Drift between the config and the bindings Hyprland has loaded
+//!
+//! Hyprland's bindings can stop matching the config: a reload failed or was
+//! never triggered, a plugin or script added binds with `hyprctl keyword`,
+//! or a dotfile sync replaced the file behind its back. `hyprctl binds`
+//! reports what is actually live as [`LiveBind`]s, and [`find_drift`] lists
+//! the differences both ways. [`LiveBind::to_keybinding`] turns a live bind
+//! back into a [`Keybinding`], flags and description included.
+//!
+//! Bindings are compared on submap, normalised key combo, dispatcher and
+//! arguments (as shell words, see [`crate::core::equivalence`]); bind
+//! flags and descriptions are not compared, as Hyprland reports them
+//! differently from how configs spell them.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{drift::{find_drift, LiveBind}, parser::parse_config_file};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, Q, killactive\nbind = SUPER, T, exec, kitty\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//! let live: Vec<LiveBind> = serde_json::from_str(
+//!     r#"[{"modmask": 64, "submap": "", "key": "Q", "keycode": 0,
+//!          "catch_all": false, "dispatcher": "killactive", "arg": ""}]"#,
+//! )
+//! .unwrap();
+//!
+//! let drift = find_drift(&bindings, &live);
+//! assert_eq!(drift.missing.len(), 1);
+//! assert_eq!(drift.missing[0].args.as_deref(), Some("kitty"));
+//! assert!(drift.unexpected.is_empty());
+//! ```
+
+use std::{collections::HashSet, fmt};
+
+use serde::Deserialize;
+
+use crate::core::{
+    equivalence::canonical_args,
+    types::{BindFlag, BindType, KeyCombo, Keybinding, Modifier},
+};
+
+/// Modifier bits of Hyprland's `modmask`
+const MODMASK_BITS: [(u32, Modifier); 4] = [
+    (1, Modifier::Shift),
+    (4, Modifier::Ctrl),
+    (8, Modifier::Alt),
+    (64, Modifier::Super),
+];
+
+/// A binding as `hyprctl binds -j` reports it
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct LiveBind {
+    /// `l` flag: works on the lock screen
+    pub locked: bool,
+    /// `m` flag: mouse binding
+    pub mouse: bool,
+    /// `r` flag: triggers on key release
+    pub release: bool,
+    /// `e` flag: repeats while the key is held
+    pub repeat: bool,
+    /// `o` flag: triggers on a long press
+    #[serde(rename = "longPress")]
+    pub long_press: bool,
+    /// `n` flag: passes the key event on to the focused window
+    pub non_consuming: bool,
+    /// `d` flag: has a description
+    pub has_description: bool,
+    /// Modifier bitmask (SHIFT 1, CTRL 4, ALT 8, SUPER 64)
+    pub modmask: u32,
+    /// Submap name, empty for global bindings
+    pub submap: String,
+    /// Key name, empty when bound by keycode
+    pub key: String,
+    /// Keycode, 0 when bound by name
+    pub keycode: u32,
+    /// Whether this is a submap's `catchall`
+    pub catch_all: bool,
+    /// Dispatcher, e.g. `exec`
+    pub dispatcher: String,
+    /// Dispatcher arguments, empty if none
+    pub arg: String,
+    /// Description of a `bindd` binding, empty if none
+    pub description: String,
+}
+
+impl LiveBind {
+    /// The key combo, as the parser would have built it
+    pub fn key_combo(&self) -> KeyCombo {
+        let modifiers = MODMASK_BITS
+            .into_iter()
+            .filter(|(bit, _)| self.modmask & bit != 0)
+            .map(|(_, modifier)| modifier)
+            .collect();
+        let key = if self.catch_all {
+            "catchall".to_string()
+        } else if self.keycode != 0 && self.key.is_empty() {
+            format!("code:{}", self.keycode)
+        } else {
+            self.key.clone()
+        };
+        KeyCombo::new(modifiers, &key)
+    }
+
+    /// The bind type, from the flags Hyprland reports
+    pub fn bind_type(&self) -> BindType {
+        [
+            (self.repeat, BindFlag::Repeat),
+            (self.locked, BindFlag::Locked),
+            (self.release, BindFlag::Release),
+            (self.mouse, BindFlag::Mouse),
+            (self.long_press, BindFlag::LongPress),
+            (self.non_consuming, BindFlag::NonConsuming),
+            (self.has_description, BindFlag::Description),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .fold(BindType::Bind, |bind_type, (_, flag)| bind_type.with(flag))
+    }
+
+    /// The binding as the parser would have read it from a config line
+    ///
+    /// Live bindings have no source file or location, and no category or
+    /// device: Hyprland does not report them.
+    pub fn to_keybinding(&self) -> Keybinding {
+        Keybinding {
+            key_combo: self.key_combo(),
+            bind_type: self.bind_type(),
+            dispatcher: self.dispatcher.clone(),
+            args: Some(self.arg.clone()).filter(|arg| !arg.is_empty()),
+            submap: Some(self.submap.clone()).filter(|submap| !submap.is_empty()),
+            source_file: None,
+            description: Some(self.description.clone())
+                .filter(|description| !description.is_empty()),
+            category: None,
+            device: None,
+            location: None,
+        }
+    }
+
+    fn identity(&self) -> BindIdentity {
+        BindIdentity {
+            submap: Some(self.submap.clone()).filter(|submap| !submap.is_empty()),
+            key_combo: self.key_combo().normalised(),
+            dispatcher: self.dispatcher.trim().to_lowercase(),
+            args: canonical_args(&self.arg),
+        }
+    }
+}
+
+impl fmt::Display for LiveBind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} → {}", self.key_combo(), self.dispatcher)?;
+        if !self.arg.is_empty() {
+            write!(f, " {}", self.arg)?;
+        }
+        if !self.submap.is_empty() {
+            write!(f, " (submap {})", self.submap)?;
+        }
+        Ok(())
+    }
+}
+
+/// What a live bind and a config binding are compared on
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct BindIdentity {
+    submap: Option<String>,
+    key_combo: KeyCombo,
+    dispatcher: String,
+    args: Vec<String>,
+}
+
+impl BindIdentity {
+    fn of(binding: &Keybinding) -> Self {
+        Self {
+            submap: binding.submap.clone(),
+            key_combo: binding.key_combo.normalised(),
+            dispatcher: binding.dispatcher.trim().to_lowercase(),
+            args: binding
+                .args
+                .as_deref()
+                .map(canonical_args)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Differences between the config and Hyprland's live bindings
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Drift {
+    /// Config bindings Hyprland does not have
+    pub missing: Vec<Keybinding>,
+    /// Live bindings the config does not declare
+    pub unexpected: Vec<LiveBind>,
+}
+
+impl Drift {
+    /// Returns whether the config and Hyprland agree
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+
+    /// One line per difference, config bindings first
+    pub fn describe(&self) -> Vec<String> {
+        let missing = self.missing.iter().map(|binding| {
+            let location = binding
+                .location
+                .as_ref()
+                .map(|location| format!(" ({})", location))
+                .unwrap_or_default();
+            format!("not loaded: {}{}", binding, location)
+        });
+        let unexpected = self
+            .unexpected
+            .iter()
+            .map(|bind| format!("not in config: {}", bind));
+        missing.chain(unexpected).collect()
+    }
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} binding{} not loaded by Hyprland, {} live binding{} not in the config",
+            self.missing.len(),
+            if self.missing.len() == 1 { "" } else { "s" },
+            self.unexpected.len(),
+            if self.unexpected.len() == 1 { "" } else { "s" },
+        )
+    }
+}
+
+/// Compares the config's bindings with Hyprland's live bindings
+///
+/// # Arguments
+/// * `bindings` - Bindings parsed from the config, sourced files included
+/// * `live` - Bindings reported by `hyprctl binds -j`
+///
+/// # Returns
+/// The bindings only one side has, each in its original order
+pub fn find_drift(bindings: &[Keybinding], live: &[LiveBind]) -> Drift {
+    let configured: HashSet<BindIdentity> = bindings.iter().map(BindIdentity::of).collect();
+    let loaded: HashSet<BindIdentity> = live.iter().map(LiveBind::identity).collect();
+
+    Drift {
+        missing: bindings
+            .iter()
+            .filter(|binding| !loaded.contains(&BindIdentity::of(binding)))
+            .cloned()
+            .collect(),
+        unexpected: live
+            .iter()
+            .filter(|bind| !configured.contains(&bind.identity()))
+            .cloned()
+            .collect(),
+    }
+}
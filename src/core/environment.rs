@@ -0,0 +1,130 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! What a running Hyprland reports about its environment
+//!
+//! Replies of the read-only `activewindow` and `devices` queries, as
+//! returned by [`HyprlandClient`](crate::ipc::HyprlandClient). The active
+//! workspace is a [`WorkspaceInfo`](crate::core::workspace::WorkspaceInfo),
+//! like every other workspace.
+//!
+//! Only the fields this tool shows are kept; Hyprland reports many more.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::environment::Devices;
+//!
+//! let devices: Devices = serde_json::from_str(
+//!     r#"{"mice": [{"name": "logitech-g502"}],
+//!         "keyboards": [{"name": "at-translated-set-2-keyboard", "layout": "us,de",
+//!                        "active_keymap": "English (US)", "main": true}]}"#,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(devices.main_keyboard().unwrap().active_keymap, "English (US)");
+//! assert_eq!(devices.names().count(), 2);
+//! ```
+
+use std::fmt;
+
+use serde::Deserialize;
+
+/// The focused window, as `hyprctl activewindow -j` reports it
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WindowInfo {
+    /// Window address, empty in the `{}` reply sent when nothing is focused
+    pub address: String,
+    /// Window class, e.g. `kitty`
+    pub class: String,
+    /// Window title
+    pub title: String,
+    /// Workspace the window is on
+    pub workspace: WorkspaceRef,
+}
+
+impl WindowInfo {
+    /// Returns whether this describes a window rather than "nothing focused"
+    pub fn is_window(&self) -> bool {
+        !self.address.is_empty()
+    }
+}
+
+impl fmt::Display for WindowInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.class.is_empty(), self.title.is_empty()) {
+            (false, false) => write!(f, "{} — {}", self.class, self.title),
+            (false, true) => f.write_str(&self.class),
+            (true, _) => f.write_str(&self.title),
+        }
+    }
+}
+
+/// A workspace as referenced from a window
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WorkspaceRef {
+    /// Workspace id (negative for special workspaces)
+    pub id: i32,
+    /// Workspace name
+    pub name: String,
+}
+
+/// Input devices, as `hyprctl devices -j` reports them
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct Devices {
+    /// Keyboards, including virtual ones such as on-screen keyboards
+    pub keyboards: Vec<KeyboardInfo>,
+    /// Mice and touchpads
+    pub mice: Vec<MouseInfo>,
+}
+
+impl Devices {
+    /// The keyboard Hyprland treats as the main one
+    pub fn main_keyboard(&self) -> Option<&KeyboardInfo> {
+        self.keyboards.iter().find(|keyboard| keyboard.main)
+    }
+
+    /// Names of every keyboard and mouse, as bindings restricted to a
+    /// device refer to them
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.keyboards
+            .iter()
+            .map(|keyboard| keyboard.name.as_str())
+            .chain(self.mice.iter().map(|mouse| mouse.name.as_str()))
+    }
+}
+
+/// A keyboard and its layouts
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct KeyboardInfo {
+    /// Device name, e.g. `at-translated-set-2-keyboard`
+    pub name: String,
+    /// Configured layouts, comma-separated (`kb_layout`)
+    pub layout: String,
+    /// Layout in use, by its display name, e.g. `English (US)`
+    pub active_keymap: String,
+    /// Whether this is the main keyboard
+    pub main: bool,
+}
+
+/// A mouse or touchpad
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MouseInfo {
+    /// Device name, e.g. `logitech-g502`
+    pub name: String,
+}
@@ -0,0 +1,225 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bindings that differ only in formatting
+//!
+//! After an external edit, `bind = SUPER SHIFT, Return, exec, kitty` and
+//! `bind = SHIFT SUPER, enter, exec,   "kitty"` do exactly the same thing,
+//! but compare unequal field by field. [`CanonicalBinding`] is the form
+//! such bindings share:
+//!
+//! - **Key combo**: normalised (sorted modifiers, key aliases resolved)
+//! - **Arguments**: split into shell words, so runs of whitespace and
+//!   quotes around plain words do not matter (`"kitty"` is `kitty`).
+//!   Quotes around anything a shell would expand (`"$HOME"`, `'*'`) are
+//!   kept, because there they change the meaning
+//! - **Description**: runs of whitespace collapsed
+//!
+//! The dispatcher, bind flags and submap are compared as they are; where a
+//! binding is declared (file and line) is ignored.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{equivalence::redundant_copies, parser::parse_config_file};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER SHIFT, Return, exec, kitty --single-instance\n\
+//!               bind = SHIFT SUPER, enter, exec, \"kitty\"   --single-instance\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//!
+//! assert!(bindings[0].is_equivalent(&bindings[1]));
+//! assert_eq!(redundant_copies(&bindings), vec![1]);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::core::types::{BindType, KeyCombo, Keybinding};
+
+/// Characters a quoted word may contain and still mean the same unquoted
+const PLAIN_WORD_PUNCTUATION: &str = "-_./:,=+@%^#";
+
+/// The formatting-independent form of a binding
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CanonicalBinding {
+    pub key_combo: KeyCombo,
+    pub bind_type: BindType,
+    pub dispatcher: String,
+    /// Arguments as shell words
+    pub args: Vec<String>,
+    pub submap: Option<String>,
+    pub description: Option<String>,
+}
+
+impl CanonicalBinding {
+    /// Builds the canonical form of a binding
+    pub fn new(binding: &Keybinding) -> Self {
+        Self {
+            key_combo: binding.key_combo.normalised(),
+            bind_type: binding.bind_type,
+            dispatcher: binding.dispatcher.trim().to_string(),
+            args: binding
+                .args
+                .as_deref()
+                .map(canonical_args)
+                .unwrap_or_default(),
+            submap: binding.submap.clone(),
+            description: binding
+                .description
+                .as_deref()
+                .map(|description| description.split_whitespace().collect::<Vec<_>>().join(" ")),
+        }
+    }
+}
+
+impl Keybinding {
+    /// Returns the formatting-independent form of this binding
+    pub fn canonical(&self) -> CanonicalBinding {
+        CanonicalBinding::new(self)
+    }
+
+    /// Returns whether the two bindings differ only in formatting
+    pub fn is_equivalent(&self, other: &Keybinding) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+/// Splits arguments into shell words
+///
+/// Quotes and backslashes are removed where they only protect plain words
+/// or whitespace. A quoted part containing anything else is kept verbatim,
+/// quotes included. Unbalanced quotes fall back to splitting on whitespace.
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::equivalence::canonical_args;
+///
+/// assert_eq!(canonical_args("firefox  'a b'"), vec!["firefox", "a b"]);
+/// assert_eq!(canonical_args("echo \"$HOME\""), vec!["echo", "\"$HOME\""]);
+/// ```
+pub fn canonical_args(args: &str) -> Vec<String> {
+    shell_words(args).unwrap_or_else(|| args.split_whitespace().map(str::to_string).collect())
+}
+
+fn shell_words(args: &str) -> Option<Vec<String>> {
+    let is_plain = |text: &str| {
+        text.chars()
+            .all(|c| c.is_alphanumeric() || c.is_whitespace() || PLAIN_WORD_PUNCTUATION.contains(c))
+    };
+
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = args.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                let mut quoted = String::new();
+                loop {
+                    match chars.next()? {
+                        close if close == c => break,
+                        '\\' if c == '"' => {
+                            let escaped = chars.next()?;
+                            if !matches!(escaped, '"' | '\\' | '$' | '`') {
+                                quoted.push('\\');
+                            }
+                            quoted.push(escaped);
+                        }
+                        other => quoted.push(other),
+                    }
+                }
+                if is_plain(&quoted) {
+                    word.push_str(&quoted);
+                } else {
+                    // Same quoting style, so '$x' and "$x" stay different
+                    word.push(c);
+                    word.push_str(&quoted);
+                    word.push(c);
+                }
+                in_word = true;
+            }
+            '\\' => {
+                let escaped = chars.next()?;
+                if escaped.is_whitespace() || is_plain(&escaped.to_string()) {
+                    word.push(escaped);
+                } else {
+                    word.push('\\');
+                    word.push(escaped);
+                }
+                in_word = true;
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+/// Finds bindings that repeat an earlier binding in different formatting
+///
+/// Of each group of equivalent bindings one is kept: the first declared in
+/// a sourced file if there is one (those cannot be edited here), otherwise
+/// the first. Copies in sourced files are never reported.
+///
+/// # Returns
+///
+/// Indices of the redundant copies, in ascending order
+pub fn redundant_copies(bindings: &[Keybinding]) -> Vec<usize> {
+    let mut groups: HashMap<CanonicalBinding, Vec<usize>> = HashMap::new();
+    for (i, binding) in bindings.iter().enumerate() {
+        groups.entry(binding.canonical()).or_default().push(i);
+    }
+
+    let mut redundant: Vec<usize> = groups
+        .values()
+        .filter(|group| group.len() > 1)
+        .flat_map(|group| {
+            let kept = group
+                .iter()
+                .copied()
+                .find(|&i| bindings[i].source_file.is_some())
+                .unwrap_or(group[0]);
+            group
+                .iter()
+                .copied()
+                .filter(move |&i| i != kept && bindings[i].source_file.is_none())
+        })
+        .collect();
+    redundant.sort_unstable();
+    redundant
+}
+
+/// Returns the bindings without their redundant copies
+///
+/// See [`redundant_copies`] for which copy is kept.
+pub fn merge_duplicates(bindings: &[Keybinding]) -> Vec<Keybinding> {
+    let redundant = redundant_copies(bindings);
+    bindings
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| redundant.binary_search(i).is_err())
+        .map(|(_, binding)| binding.clone())
+        .collect()
+}
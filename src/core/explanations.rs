@@ -0,0 +1,125 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Embedded explanations of how Hyprland resolves conflicts
+//!
+//! Each conflict kind links to one entry here by a stable `id`. The GUI's
+//! "Learn more" expander and `check --explain` both render these entries,
+//! so the wording lives in one place and works offline. `docs_url` points at
+//! the upstream wiki section for readers who want the full story.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::explanations::explanation;
+//!
+//! let entry = explanation("multiple-actions").unwrap();
+//! assert!(entry.docs_url.starts_with("https://wiki.hyprland.org/"));
+//! ```
+
+/// Hyprland wiki page describing bind syntax and precedence
+const BINDS_DOCS: &str = "https://wiki.hyprland.org/Configuring/Binds/";
+
+/// A single embedded explanation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    /// Stable identifier (used by `check --explain` and the GUI)
+    pub id: &'static str,
+    /// Short heading
+    pub title: &'static str,
+    /// One-line summary of what happens at runtime
+    pub summary: &'static str,
+    /// Full explanation, plain text with paragraph breaks
+    pub details: &'static str,
+    /// Upstream documentation for further reading
+    pub docs_url: &'static str,
+}
+
+/// Several actions bound to one key combo
+pub const MULTIPLE_ACTIONS: Explanation = Explanation {
+    id: "multiple-actions",
+    title: "Several actions on one key",
+    summary: "Hyprland runs every matching bind, top to bottom.",
+    details: "Hyprland does not let a later bind replace an earlier one. When a key \
+              combination is pressed, every bind for it runs, in the order the binds \
+              appear in the config.\n\n\
+              This is occasionally intentional (e.g. `cyclenext` followed by \
+              `bringactivetotop`), but usually one of the binds is left over from an \
+              older config. Delete the one you no longer want, or move it to a free \
+              key combination.",
+    docs_url: BINDS_DOCS,
+};
+
+/// The same action bound twice to one key combo
+pub const REDUNDANT_DUPLICATE: Explanation = Explanation {
+    id: "redundant-duplicate",
+    title: "Exact duplicate",
+    summary: "The same action is bound more than once, so it repeats on every press.",
+    details: "Every bind uses the same dispatcher and arguments. Hyprland runs every \
+              matching bind, so the action is repeated each time the key \
+              combination is pressed: an `exec` starts several copies of the program \
+              and a pair of toggles such as `togglefloating` cancel each other out.\n\n\
+              Deleting the extra copies is safe.",
+    docs_url: BINDS_DOCS,
+};
+
+/// Press and release binds on one key combo
+pub const PRESS_AND_RELEASE: Explanation = Explanation {
+    id: "press-and-release",
+    title: "Press and release binds",
+    summary: "`bindr` fires on release, the others fire on press; all of them run.",
+    details: "Binds with the `r` flag (`bindr`) trigger when the key is released, while \
+              plain binds trigger when it is pressed. Hyprland runs all of them, so a \
+              single tap performs every action.\n\n\
+              If you meant to replace the press action with a release action, delete \
+              the plain bind. If you want both, this warning can be ignored.",
+    docs_url: BINDS_DOCS,
+};
+
+/// A universal bind caught by a submap's catchall
+pub const CATCHALL_SHADOWING: Explanation = Explanation {
+    id: "catchall-shadowing",
+    title: "Shadowed by a catchall",
+    summary: "Inside the submap, its catchall also catches this universal bind's key.",
+    details: "A universal bind (`u` flag) is meant to work in every submap, and a \
+              `catchall` fires for every key its submap does not bind itself. In a \
+              submap with a catchall, pressing the universal bind's key therefore \
+              triggers the catchall as well, which usually defeats the point of it \
+              (a catchall is typically there to swallow stray keys).\n\n\
+              If the bind is not needed inside the submap, drop its `u` flag. \
+              Otherwise make sure the catchall's action is harmless for this key, or \
+              bind the key in the submap itself so the catchall skips it.",
+    docs_url: BINDS_DOCS,
+};
+
+/// All conflict explanations, one per [`ConflictKind`](crate::core::conflict::ConflictKind)
+pub static CONFLICT_EXPLANATIONS: &[Explanation] = &[
+    MULTIPLE_ACTIONS,
+    REDUNDANT_DUPLICATE,
+    PRESS_AND_RELEASE,
+    CATCHALL_SHADOWING,
+];
+
+/// Looks up an explanation by id
+///
+/// # Arguments
+///
+/// * `id` - Stable explanation identifier
+///
+/// # Returns
+///
+/// * `Some(&Explanation)` - The matching entry
+/// * `None` - Unknown id
+pub fn explanation(id: &str) -> Option<&'static Explanation> {
+    CONFLICT_EXPLANATIONS.iter().find(|entry| entry.id == id)
+}
@@ -0,0 +1,251 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Heuristic grouping of keybindings
+//!
+//! Configs that grew over time often list every bind in one undifferentiated
+//! block. This module sorts bindings into familiar groups by dispatcher, key
+//! and exec target, so the user can accept the suggestion and have the
+//! groups written back as comment headers.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{
+//!     grouping::{classify_binding, BindingGroup},
+//!     KeyCombo, Keybinding,
+//! };
+//!
+//! let binding = Keybinding::new(
+//!     KeyCombo::new(vec![], "Print"),
+//!     "exec",
+//!     Some("grim -g \"$(slurp)\""),
+//! );
+//!
+//! assert_eq!(classify_binding(&binding), BindingGroup::Screenshots);
+//! ```
+
+use std::fmt;
+
+use crate::core::{parser::is_bind_line, types::Keybinding};
+
+/// Suggested group for a keybinding, in the order groups are written
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BindingGroup {
+    /// Focus, move, resize, float, fullscreen, close
+    WindowManagement,
+    /// Switching and moving to workspaces
+    Workspaces,
+    /// Application launchers and programs
+    Launchers,
+    /// Volume, playback and brightness
+    Media,
+    /// Screenshots and screen recording
+    Screenshots,
+    /// Anything the heuristics do not recognise
+    Other,
+}
+
+impl BindingGroup {
+    /// All groups in write order
+    pub const ALL: [BindingGroup; 6] = [
+        BindingGroup::WindowManagement,
+        BindingGroup::Workspaces,
+        BindingGroup::Launchers,
+        BindingGroup::Media,
+        BindingGroup::Screenshots,
+        BindingGroup::Other,
+    ];
+
+    /// Heading used for the comment header
+    pub fn title(&self) -> &'static str {
+        match self {
+            BindingGroup::WindowManagement => "Window Management",
+            BindingGroup::Workspaces => "Workspaces",
+            BindingGroup::Launchers => "Launchers",
+            BindingGroup::Media => "Media",
+            BindingGroup::Screenshots => "Screenshots",
+            BindingGroup::Other => "Other",
+        }
+    }
+}
+
+impl fmt::Display for BindingGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title())
+    }
+}
+
+/// A suggested group with its bindings, in their original order
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupSuggestion {
+    pub group: BindingGroup,
+    pub bindings: Vec<Keybinding>,
+}
+
+const WINDOW_DISPATCHERS: &[&str] = &[
+    "killactive",
+    "closewindow",
+    "togglefloating",
+    "setfloating",
+    "settiled",
+    "fullscreen",
+    "fakefullscreen",
+    "pseudo",
+    "pin",
+    "centerwindow",
+    "togglesplit",
+    "swapsplit",
+    "splitratio",
+    "movefocus",
+    "movewindow",
+    "movewindoworgroup",
+    "swapwindow",
+    "resizeactive",
+    "moveactive",
+    "resizewindow",
+    "resizewindowpixel",
+    "movewindowpixel",
+    "cyclenext",
+    "swapnext",
+    "focuswindow",
+    "bringactivetotop",
+    "alterzorder",
+    "togglegroup",
+    "changegroupactive",
+    "moveintogroup",
+    "moveoutofgroup",
+    "lockgroups",
+    "lockactivegroup",
+    "layoutmsg",
+];
+
+const WORKSPACE_DISPATCHERS: &[&str] = &[
+    "workspace",
+    "movetoworkspace",
+    "movetoworkspacesilent",
+    "togglespecialworkspace",
+    "focusworkspaceoncurrentmonitor",
+    "movecurrentworkspacetomonitor",
+    "moveworkspacetomonitor",
+    "swapactiveworkspaces",
+    "renameworkspace",
+];
+
+const MEDIA_PROGRAMS: &[&str] = &[
+    "playerctl",
+    "pactl",
+    "wpctl",
+    "pamixer",
+    "amixer",
+    "brightnessctl",
+    "light",
+    "mpc",
+    "swayosd-client",
+];
+
+const SCREENSHOT_PROGRAMS: &[&str] = &[
+    "grim",
+    "slurp",
+    "grimblast",
+    "hyprshot",
+    "flameshot",
+    "swappy",
+    "satty",
+    "wf-recorder",
+    "wl-screenrec",
+];
+
+/// Classifies a single binding
+///
+/// Keys win over dispatchers (an `XF86AudioMute` bind is Media whatever it
+/// runs), then the dispatcher decides, and for `exec` the program name.
+pub fn classify_binding(binding: &Keybinding) -> BindingGroup {
+    // Named keys are stored upper-cased by `KeyCombo::new`
+    let key = binding.key_combo.key.config_name();
+    if key.starts_with("XF86AUDIO") || key.starts_with("XF86MONBRIGHTNESS") {
+        return BindingGroup::Media;
+    }
+
+    let dispatcher = binding.dispatcher.trim().to_lowercase();
+    if WINDOW_DISPATCHERS.contains(&dispatcher.as_str()) {
+        return BindingGroup::WindowManagement;
+    }
+    if WORKSPACE_DISPATCHERS.contains(&dispatcher.as_str()) {
+        return BindingGroup::Workspaces;
+    }
+
+    if dispatcher == "exec" {
+        let program = binding.args.as_deref().and_then(exec_program);
+        return match program {
+            Some(program) if SCREENSHOT_PROGRAMS.contains(&program) => BindingGroup::Screenshots,
+            Some(program) if MEDIA_PROGRAMS.contains(&program) => BindingGroup::Media,
+            _ if key == "PRINT" => BindingGroup::Screenshots,
+            Some(_) => BindingGroup::Launchers,
+            None => BindingGroup::Other,
+        };
+    }
+
+    BindingGroup::Other
+}
+
+/// Groups bindings by their suggested group
+///
+/// Empty groups are omitted; bindings keep their relative order.
+pub fn suggest_groups(bindings: &[Keybinding]) -> Vec<GroupSuggestion> {
+    BindingGroup::ALL
+        .iter()
+        .map(|group| GroupSuggestion {
+            group: *group,
+            bindings: bindings
+                .iter()
+                .filter(|binding| classify_binding(binding) == *group)
+                .cloned()
+                .collect(),
+        })
+        .filter(|suggestion| !suggestion.bindings.is_empty())
+        .collect()
+}
+
+/// Returns whether the config already organises its binds with comments
+///
+/// A config counts as grouped when a comment line sits anywhere between its
+/// first and last bind line. A single header above all binds does not count.
+pub fn has_comment_groups(content: &str) -> bool {
+    let lines: Vec<&str> = content.lines().map(str::trim).collect();
+    let is_bind = |line: &&str| is_bind_line(line);
+
+    let (Some(first), Some(last)) = (
+        lines.iter().position(is_bind),
+        lines.iter().rposition(is_bind),
+    ) else {
+        return false;
+    };
+
+    lines[first..last]
+        .iter()
+        .any(|line| line.starts_with('#') && !line.trim_start_matches('#').trim().is_empty())
+}
+
+/// Extracts the program name from exec arguments
+///
+/// Skips `VAR=value` assignments and returns the basename of the executable.
+fn exec_program(args: &str) -> Option<&str> {
+    let program = args.split_whitespace().find(|word| {
+        !word
+            .split_once('=')
+            .is_some_and(|(name, _)| !name.is_empty() && !name.starts_with('-'))
+    })?;
+
+    Some(program.rsplit('/').next().unwrap_or(program))
+}
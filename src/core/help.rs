@@ -0,0 +1,232 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Searchable help topics for the GUI's Help window
+//!
+//! Topics are not written by hand: they come from the same data the rest of
+//! the code uses, so they cannot drift out of date.
+//! - Bind flags from [`BindFlag::ALL`] and [`BindFlag::description`]
+//! - Dispatchers from the [`DISPATCHER_CATALOGUE`] the validator checks against
+//! - Conflicts from the embedded [`CONFLICT_EXPLANATIONS`]
+//! - This tool's features from the README's "Features in Detail" sections,
+//!   split into topics by the build script (`build.rs`)
+//!
+//! The danger rules live in `config::danger`, which adds its own topics
+//! (see [`crate::config::danger::patterns::danger_rule_topics`]).
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::help::HelpIndex;
+//!
+//! let index = HelpIndex::new(Vec::new());
+//! let found = index.search("repeat held");
+//! assert_eq!(found[0].title, "e: repeat");
+//! ```
+
+use std::fmt;
+
+use crate::core::{
+    dispatchers::DISPATCHER_CATALOGUE, explanations::CONFLICT_EXPLANATIONS, types::BindFlag,
+};
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/help_features.rs"));
+}
+
+/// Part of the documentation a topic belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HelpSection {
+    /// This tool's features
+    Features,
+    /// Bind flags (`e`, `l`, `r`, ...)
+    BindFlags,
+    /// Dispatchers and their arguments
+    Dispatchers,
+    /// How Hyprland resolves conflicting bindings
+    Conflicts,
+    /// Commands the danger assessment flags
+    DangerRules,
+}
+
+impl HelpSection {
+    /// All sections, in the order the Help window lists them
+    pub const ALL: [HelpSection; 5] = [
+        HelpSection::Features,
+        HelpSection::BindFlags,
+        HelpSection::Dispatchers,
+        HelpSection::Conflicts,
+        HelpSection::DangerRules,
+    ];
+}
+
+impl fmt::Display for HelpSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            HelpSection::Features => "Features",
+            HelpSection::BindFlags => "Bind Flags",
+            HelpSection::Dispatchers => "Dispatchers",
+            HelpSection::Conflicts => "Conflicts",
+            HelpSection::DangerRules => "Danger Rules",
+        })
+    }
+}
+
+/// One page of help
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HelpTopic {
+    /// Section the topic is listed under
+    pub section: HelpSection,
+    /// Short heading
+    pub title: String,
+    /// Plain text, with paragraph breaks
+    pub body: String,
+}
+
+impl HelpTopic {
+    /// Creates a topic
+    pub fn new(section: HelpSection, title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            section,
+            title: title.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// All help topics, searchable by their full text
+#[derive(Clone, Debug, Default)]
+pub struct HelpIndex {
+    topics: Vec<HelpTopic>,
+}
+
+impl HelpIndex {
+    /// Builds the index from the built-in topics plus `extra` ones
+    ///
+    /// # Arguments
+    /// * `extra` - Topics from outside `core`, such as the danger rules
+    ///
+    /// # Returns
+    /// An index with topics ordered by [`HelpSection::ALL`], then as given
+    pub fn new(extra: Vec<HelpTopic>) -> Self {
+        let mut topics = feature_topics();
+        topics.extend(bind_flag_topics());
+        topics.extend(dispatcher_topics());
+        topics.extend(conflict_topics());
+        topics.extend(extra);
+        topics.sort_by_key(|topic| {
+            HelpSection::ALL
+                .iter()
+                .position(|section| *section == topic.section)
+        });
+        Self { topics }
+    }
+
+    /// All topics
+    pub fn topics(&self) -> &[HelpTopic] {
+        &self.topics
+    }
+
+    /// Finds topics containing every word of a query
+    ///
+    /// Matching ignores case. Topics with all words in the title come first,
+    /// each group in index order.
+    ///
+    /// # Arguments
+    /// * `query` - Words to look for; blank returns every topic
+    pub fn search(&self, query: &str) -> Vec<&HelpTopic> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        let contains_all = |text: &str| {
+            words
+                .iter()
+                .all(|word| text.to_lowercase().contains(word.as_str()))
+        };
+
+        let mut found: Vec<&HelpTopic> = self
+            .topics
+            .iter()
+            .filter(|topic| contains_all(&format!("{}\n{}", topic.title, topic.body)))
+            .collect();
+        // Stable, so index order is kept within each group
+        found.sort_by_key(|topic| !contains_all(&topic.title));
+        found
+    }
+}
+
+/// One topic per README feature section
+fn feature_topics() -> Vec<HelpTopic> {
+    generated::FEATURE_SECTIONS
+        .iter()
+        .map(|(title, body)| HelpTopic::new(HelpSection::Features, *title, *body))
+        .collect()
+}
+
+/// One topic per bind flag
+fn bind_flag_topics() -> Vec<HelpTopic> {
+    BindFlag::ALL
+        .iter()
+        .map(|flag| {
+            HelpTopic::new(
+                HelpSection::BindFlags,
+                format!("{}: {}", flag.letter(), flag.label()),
+                format!(
+                    "{}\n\nAdd the letter to the bind keyword, e.g. bind{} = SUPER, K, ...; \
+                     flags combine in any order.",
+                    flag.description(),
+                    flag.letter()
+                ),
+            )
+        })
+        .collect()
+}
+
+/// One topic per catalogued dispatcher
+fn dispatcher_topics() -> Vec<HelpTopic> {
+    DISPATCHER_CATALOGUE
+        .iter()
+        .map(|spec| {
+            let mut body = format!(
+                "{}.\n\nArguments: {}",
+                spec.summary,
+                spec.arguments.describe()
+            );
+            if spec.optional {
+                body.push_str(" (optional)");
+            }
+            let example = if spec.example.is_empty() {
+                format!("bind = SUPER, K, {}", spec.name)
+            } else {
+                format!("bind = SUPER, K, {}, {}", spec.name, spec.example)
+            };
+            body.push_str(&format!("\nExample: {}", example));
+            HelpTopic::new(HelpSection::Dispatchers, spec.name, body)
+        })
+        .collect()
+}
+
+/// One topic per conflict explanation
+fn conflict_topics() -> Vec<HelpTopic> {
+    CONFLICT_EXPLANATIONS
+        .iter()
+        .map(|explanation| {
+            HelpTopic::new(
+                HelpSection::Conflicts,
+                explanation.title,
+                format!(
+                    "{}\n\n{}\n\nMore: {}",
+                    explanation.summary, explanation.details, explanation.docs_url
+                ),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,139 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bindings that shell out to Hyprland's own dispatchers
+//!
+//! `bind = SUPER, 3, exec, hyprctl dispatch workspace 3` does what
+//! `bind = SUPER, 3, workspace, 3` does, but starts a shell and a `hyprctl`
+//! process on every key press, and hides the dispatcher from conflict
+//! explanations, version checks and the danger assessment (which only sees
+//! an `exec`).
+//!
+//! [`idiomatic_rewrite`] turns such a binding into the direct form. Only
+//! plain commands are rewritten: anything with quoting, pipes, chaining or
+//! `hyprctl` options other than `-q`/`-j` is left alone, because the
+//! direct form could not express it.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{idioms::builtin_duplicates, parser::parse_config_file};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, 3, exec, hyprctl dispatch workspace 3\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//!
+//! let duplicates = builtin_duplicates(&bindings);
+//! assert_eq!(duplicates.len(), 1);
+//! assert_eq!(duplicates[0].rewrite.dispatcher, "workspace");
+//! assert_eq!(duplicates[0].rewrite.args.as_deref(), Some("3"));
+//! ```
+
+use std::fmt;
+
+use crate::core::{
+    types::Keybinding,
+    validator::{check_shell_metacharacters, validate_keybinding},
+};
+
+/// `hyprctl` options that do not change what a dispatch does
+const HARMLESS_OPTIONS: &[&str] = &["-q", "--quiet", "-j"];
+
+/// A binding running `hyprctl dispatch` where the dispatcher would do
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuiltinDuplicate {
+    /// The binding as written
+    pub binding: Keybinding,
+    /// The same binding using the dispatcher directly
+    pub rewrite: Keybinding,
+}
+
+impl fmt::Display for BuiltinDuplicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} runs 'hyprctl dispatch' from exec; use '{}' directly",
+            self.binding.key_combo,
+            dispatch_text(&self.rewrite)
+        )
+    }
+}
+
+/// The dispatcher and its arguments, as written on a bind line
+pub fn dispatch_text(binding: &Keybinding) -> String {
+    match &binding.args {
+        Some(args) => format!("{}, {}", binding.dispatcher, args),
+        None => binding.dispatcher.clone(),
+    }
+}
+
+/// Rewrites an `exec, hyprctl dispatch ...` binding to use the dispatcher
+///
+/// Everything but the dispatcher and arguments (key, bind type, submap,
+/// description, origin) is kept.
+///
+/// # Returns
+/// * `Some(Keybinding)` - The direct form, which passes validation
+/// * `None` - Not a `hyprctl dispatch` call, or not one that can be rewritten
+pub fn idiomatic_rewrite(binding: &Keybinding) -> Option<Keybinding> {
+    if !binding.dispatcher.eq_ignore_ascii_case("exec") {
+        return None;
+    }
+    let command = binding.args.as_deref()?;
+
+    // Quoting, variables, pipes and chaining all need the shell
+    check_shell_metacharacters(command).ok()?;
+
+    let mut words = command.split_whitespace();
+    let program = words.next()?;
+    if program.rsplit('/').next() != Some("hyprctl") {
+        return None;
+    }
+
+    let mut word = words.next()?;
+    while HARMLESS_OPTIONS.contains(&word) {
+        word = words.next()?;
+    }
+    if word != "dispatch" {
+        return None;
+    }
+
+    let dispatcher = words.next()?;
+    let args = words.collect::<Vec<_>>().join(" ");
+
+    let mut rewrite = binding.clone();
+    rewrite.dispatcher = dispatcher.to_string();
+    rewrite.args = (!args.is_empty()).then_some(args);
+
+    validate_keybinding(&rewrite).ok()?;
+    Some(rewrite)
+}
+
+/// Finds bindings that run `hyprctl dispatch` and can use the dispatcher
+///
+/// # Arguments
+/// * `bindings` - All bindings of the config
+///
+/// # Returns
+/// One entry per rewritable binding, in config order
+pub fn builtin_duplicates(bindings: &[Keybinding]) -> Vec<BuiltinDuplicate> {
+    bindings
+        .iter()
+        .filter_map(|binding| {
+            idiomatic_rewrite(binding).map(|rewrite| BuiltinDuplicate {
+                binding: binding.clone(),
+                rewrite,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,193 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Free key combos to move a conflicting binding to
+//!
+//! [`KeySuggestionEngine`] knows which combos a config occupies, per submap,
+//! and searches the combos people commonly bind for free ones. Suggestions
+//! stay close to the combo they replace:
+//!
+//! 1. The same key with one more modifier (`SUPER+K` → `SUPER+SHIFT+K`)
+//! 2. The same key with two more modifiers
+//! 3. Another key with the same modifiers, from letters, digits and
+//!    function keys
+//!
+//! A combo is free in a submap when no binding of that submap and no
+//! universal (`bindu`) binding uses it.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{
+//!     key_suggestions::KeySuggestionEngine, parser::parse_config_file, KeyCombo, Modifier,
+//! };
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, K, exec, kitty\n\
+//!               bind = SUPER SHIFT, K, exec, firefox\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//! let engine = KeySuggestionEngine::new(&bindings);
+//!
+//! let suggestions = engine.suggest(&KeyCombo::new(vec![Modifier::Super], "K"), None, 2);
+//! assert_eq!(
+//!     suggestions,
+//!     vec![
+//!         KeyCombo::new(vec![Modifier::Super, Modifier::Ctrl], "K"),
+//!         KeyCombo::new(vec![Modifier::Super, Modifier::Alt], "K"),
+//!     ]
+//! );
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::{
+    conflict::{Conflict, ConflictKind},
+    types::{BindFlag, KeyCombo, Keybinding, Modifier},
+};
+
+/// Modifiers tried when adding one to a combo, most natural first
+const SUGGESTED_MODIFIERS: [Modifier; 4] = [
+    Modifier::Super,
+    Modifier::Shift,
+    Modifier::Ctrl,
+    Modifier::Alt,
+];
+
+/// Keys tried when looking for a free combo with given modifiers
+pub const CANDIDATE_KEYS: [&str; 48] = [
+    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+    "T", "U", "V", "W", "X", "Y", "Z", "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "F1",
+    "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12",
+];
+
+/// Finds free key combos in a set of bindings
+#[derive(Clone, Debug, Default)]
+pub struct KeySuggestionEngine {
+    /// Normalised combos in use, by submap (`None`: global)
+    occupied: HashMap<Option<String>, HashSet<KeyCombo>>,
+    /// Normalised combos of universal bindings, in use in every submap
+    universal: HashSet<KeyCombo>,
+}
+
+impl KeySuggestionEngine {
+    /// Records the combos the given bindings occupy
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - Bindings in use; leave out a binding being edited so
+    ///   its own combo counts as free
+    pub fn new<'a>(bindings: impl IntoIterator<Item = &'a Keybinding>) -> Self {
+        let mut engine = Self::default();
+        for binding in bindings {
+            let combo = binding.key_combo.normalised();
+            if binding.bind_type.contains(BindFlag::Universal) {
+                engine.universal.insert(combo.clone());
+            }
+            engine
+                .occupied
+                .entry(binding.submap.clone())
+                .or_default()
+                .insert(combo);
+        }
+        engine
+    }
+
+    /// Returns whether no binding uses the combo in the given submap
+    pub fn is_free(&self, key_combo: &KeyCombo, submap: Option<&str>) -> bool {
+        let combo = key_combo.normalised();
+        !self.universal.contains(&combo)
+            && self
+                .occupied
+                .get(&submap.map(str::to_string))
+                .is_none_or(|combos| !combos.contains(&combo))
+    }
+
+    /// Free combos with exactly the given modifiers, in [`CANDIDATE_KEYS`] order
+    pub fn free_keys<'a>(
+        &'a self,
+        modifiers: &'a [Modifier],
+        submap: Option<&'a str>,
+    ) -> impl Iterator<Item = KeyCombo> + 'a {
+        CANDIDATE_KEYS
+            .into_iter()
+            .map(|key| KeyCombo::new(modifiers.to_vec(), key))
+            .filter(move |candidate| self.is_free(candidate, submap))
+    }
+
+    /// Suggests free combos to use instead of `key_combo`, closest first
+    ///
+    /// # Arguments
+    ///
+    /// * `key_combo` - The busy combo
+    /// * `submap` - Submap the new combo must be free in (`None`: global)
+    /// * `limit` - Maximum number of suggestions
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` free combos; none for a `catchall`, which has no key
+    /// to keep
+    pub fn suggest(
+        &self,
+        key_combo: &KeyCombo,
+        submap: Option<&str>,
+        limit: usize,
+    ) -> Vec<KeyCombo> {
+        if key_combo.is_catchall() {
+            return Vec::new();
+        }
+        let combo = key_combo.normalised();
+        let key = combo.key.config_name();
+        let with_modifiers = |extra: &[Modifier]| {
+            let mut modifiers = combo.modifiers.clone();
+            modifiers.extend_from_slice(extra);
+            KeyCombo::new(modifiers, &key)
+        };
+
+        let missing: Vec<Modifier> = SUGGESTED_MODIFIERS
+            .into_iter()
+            .filter(|modifier| !combo.modifiers.contains(modifier))
+            .collect();
+        let one_more = missing.iter().map(|&modifier| with_modifiers(&[modifier]));
+        let two_more = missing.iter().enumerate().flat_map(|(i, &first)| {
+            missing[i + 1..]
+                .iter()
+                .map(move |&second| with_modifiers(&[first, second]))
+        });
+        let other_keys = CANDIDATE_KEYS
+            .into_iter()
+            .map(|key| KeyCombo::new(combo.modifiers.clone(), key));
+
+        let mut seen = HashSet::new();
+        one_more
+            .chain(two_more)
+            .chain(other_keys)
+            .filter(|candidate| *candidate != combo && self.is_free(candidate, submap))
+            .filter(|candidate| seen.insert(candidate.clone()))
+            .take(limit)
+            .collect()
+    }
+
+    /// Suggests free combos for one of the bindings of a conflict
+    ///
+    /// Only conflicts that moving a binding resolves get suggestions: exact
+    /// duplicates are resolved by deleting a copy, and catchall shadowing
+    /// is about which submaps a universal binding should reach.
+    pub fn suggest_for_conflict(&self, conflict: &Conflict, limit: usize) -> Vec<KeyCombo> {
+        match conflict.kind {
+            ConflictKind::MultipleActions | ConflictKind::PressAndRelease => {
+                self.suggest(&conflict.key_combo, conflict.submap.as_deref(), limit)
+            }
+            ConflictKind::RedundantDuplicate | ConflictKind::CatchallShadowing => Vec::new(),
+        }
+    }
+}
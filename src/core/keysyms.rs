@@ -0,0 +1,150 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keysym name normalisation
+//!
+//! Hyprland resolves key names with XKB, ignoring case, so `Return`,
+//! `RETURN` and `return` are one key. Configs also use names XKB does not
+//! spell that way (`Enter`, `Esc`, `PageUp`) for keys it knows under
+//! another name. Without folding both, `SUPER, Return` and `SUPER, enter`
+//! look like different combos and their conflict goes unreported.
+//!
+//! [`normalise_keysym`] maps every spelling to one stored form: the XKB
+//! name, uppercase. `KeyCombo::new` applies it, so the parser, the edit
+//! dialog and the conflict detector all see the same key.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::keysyms::normalise_keysym;
+//!
+//! assert_eq!(normalise_keysym("Return"), "RETURN");
+//! assert_eq!(normalise_keysym("enter"), "RETURN");
+//! assert_eq!(normalise_keysym("PgUp"), "PRIOR");
+//! assert_eq!(normalise_keysym("XF86AudioMute"), "XF86AUDIOMUTE");
+//! ```
+//!
+//! [`captured_combo`] turns a key pressed in the edit dialog into a combo,
+//! so keys can be recorded instead of typed.
+
+use crate::core::types::{KeyCombo, Modifier};
+
+/// XKB keysym names and the other spellings used for the same key
+///
+/// Keypad keys (`KP_Enter`, `KP_Prior`, ...) are separate keys and have no
+/// aliases here.
+pub const KEYSYM_ALIASES: &[(&str, &[&str])] = &[
+    ("Return", &["Enter", "Ret"]),
+    ("Escape", &["Esc"]),
+    ("BackSpace", &["Back_Space"]),
+    ("Delete", &["Del"]),
+    ("Insert", &["Ins"]),
+    ("Prior", &["Page_Up", "PageUp", "PgUp"]),
+    ("Next", &["Page_Down", "PageDown", "PgDn"]),
+    ("Print", &["PrintScreen", "Print_Screen", "PrtSc"]),
+    ("Caps_Lock", &["CapsLock"]),
+    ("Num_Lock", &["NumLock"]),
+    ("Scroll_Lock", &["ScrollLock"]),
+    ("space", &["Spacebar"]),
+    ("Left", &["ArrowLeft"]),
+    ("Right", &["ArrowRight"]),
+    ("Up", &["ArrowUp"]),
+    ("Down", &["ArrowDown"]),
+];
+
+/// Returns the stored form of a key name
+///
+/// # Arguments
+/// * `name` - Key name as written (any case, XKB name or alias)
+///
+/// # Returns
+/// The XKB name in uppercase; unknown names are only uppercased
+pub fn normalise_keysym(name: &str) -> String {
+    let name = name.trim();
+    KEYSYM_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name)))
+        .map_or(name, |(keysym, _)| keysym)
+        .to_uppercase()
+}
+
+/// Modifier keys by keysym, and the modifier each one sets
+///
+/// Some layouts report Alt as `Meta` and Super as `Hyper`.
+pub const MODIFIER_KEYSYMS: &[(&str, Modifier)] = &[
+    ("Super_L", Modifier::Super),
+    ("Super_R", Modifier::Super),
+    ("Hyper_L", Modifier::Super),
+    ("Hyper_R", Modifier::Super),
+    ("Control_L", Modifier::Ctrl),
+    ("Control_R", Modifier::Ctrl),
+    ("Shift_L", Modifier::Shift),
+    ("Shift_R", Modifier::Shift),
+    ("Alt_L", Modifier::Alt),
+    ("Alt_R", Modifier::Alt),
+    ("Meta_L", Modifier::Alt),
+    ("Meta_R", Modifier::Alt),
+];
+
+/// Keysyms reported for a pressed key that configs spell differently
+const CAPTURED_ALIASES: &[(&str, &str)] = &[("ISO_Left_Tab", "Tab")];
+
+/// Returns the modifier a modifier key sets
+///
+/// # Returns
+/// `None` for every other key
+pub fn keysym_modifier(name: &str) -> Option<Modifier> {
+    MODIFIER_KEYSYMS
+        .iter()
+        .find(|(keysym, _)| keysym.eq_ignore_ascii_case(name.trim()))
+        .map(|(_, modifier)| *modifier)
+}
+
+/// Builds the combo for a key pressed on the keyboard
+///
+/// A modifier key captured on its own (Super pressed and released) is
+/// bound with its own modifier, the way Hyprland expects: `SUPER, Super_L`.
+///
+/// # Arguments
+/// * `keysym` - Keysym name of the key, as the layout has it without
+///   modifiers (`m` for Shift+M, `1` rather than `exclam`)
+/// * `held` - Modifiers held while it was pressed
+///
+/// # Returns
+/// `None` for keys without a name, which cannot be bound
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::keysyms::captured_combo;
+/// use hypr_keybind_manager::core::Modifier;
+///
+/// let combo = captured_combo("m", &[Modifier::Shift, Modifier::Super]).unwrap();
+/// assert_eq!(combo.to_string(), "SHIFT+SUPER+M");
+///
+/// let combo = captured_combo("Super_L", &[]).unwrap();
+/// assert_eq!(combo.to_string(), "SUPER+SUPER_L");
+/// ```
+pub fn captured_combo(keysym: &str, held: &[Modifier]) -> Option<KeyCombo> {
+    let keysym = keysym.trim();
+    if keysym.is_empty() || keysym == "VoidSymbol" {
+        return None;
+    }
+    let keysym = CAPTURED_ALIASES
+        .iter()
+        .find(|(reported, _)| *reported == keysym)
+        .map_or(keysym, |(_, name)| name);
+
+    let mut modifiers = held.to_vec();
+    modifiers.extend(keysym_modifier(keysym));
+    Some(KeyCombo::new(modifiers, keysym))
+}
@@ -0,0 +1,306 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keyboard layout awareness for bound keys
+//!
+//! Hyprland matches a named key against the keysym the active layout
+//! produces, so with `kb_layout = us,ru` a `SUPER+Q` binding works until the
+//! user switches to Russian, where that key types `й` instead. Binding the
+//! keycode (`code:24`) works in every layout.
+//!
+//! Only keys whose availability is known are judged: the letters `A`–`Z`
+//! (missing from non-Latin layouts), the digits (shifted on AZERTY layouts)
+//! and a handful of layout-specific keysyms such as `adiaeresis`.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::layouts::{configured_layouts, layout_warnings};
+//! use hypr_keybind_manager::core::parser::parse_config_file;
+//! use std::path::Path;
+//!
+//! let config = "input {\n    kb_layout = us,ru\n}\nbind = SUPER, Q, killactive\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//!
+//! let warnings = layout_warnings(&bindings, &configured_layouts(config));
+//! assert_eq!(warnings[0].missing_from, vec!["ru".to_string()]);
+//! assert_eq!(warnings[0].keycode, Some(24));
+//! ```
+
+use std::fmt;
+
+use crate::core::binds_options::strip_comment;
+use crate::core::types::{Key, Keybinding};
+
+/// Layouts whose base level has no Latin letters
+const NON_LATIN_LAYOUTS: &[&str] = &[
+    "am", "ara", "bg", "by", "ge", "gr", "il", "in", "ir", "kg", "kz", "mk", "mn", "rs", "ru",
+    "th", "tj", "ua",
+];
+
+/// Layouts whose digits need Shift, so the digit keysyms are never produced
+const SHIFTED_DIGIT_LAYOUTS: &[&str] = &["be", "fr"];
+
+/// Layouts that swap Y and Z
+const QWERTZ_LAYOUTS: &[&str] = &["at", "ch", "cz", "de", "hr", "hu", "si", "sk"];
+
+/// Layouts that swap A/Q and Z/W and move M
+const AZERTY_LAYOUTS: &[&str] = &["be", "fr"];
+
+/// Keysyms only some layouts produce, by keysym prefix
+const LAYOUT_KEYSYMS: &[(&str, &[&str])] = &[
+    ("ADIAERESIS", &["at", "ch", "de", "ee", "fi", "se"]),
+    (
+        "ODIAERESIS",
+        &["at", "ch", "de", "ee", "fi", "hu", "se", "tr"],
+    ),
+    ("UDIAERESIS", &["at", "ch", "de", "ee", "hu", "tr"]),
+    ("SSHARP", &["at", "de"]),
+    ("ARING", &["dk", "fi", "no", "se"]),
+    ("AE", &["dk", "no"]),
+    ("OSLASH", &["dk", "no"]),
+    ("NTILDE", &["es", "latam"]),
+    ("CCEDILLA", &["fr", "pt", "tr"]),
+    (
+        "CYRILLIC_",
+        &["bg", "by", "kg", "kz", "mk", "mn", "rs", "ru", "tj", "ua"],
+    ),
+    ("GREEK_", &["gr"]),
+    ("HEBREW_", &["il"]),
+    ("ARABIC_", &["ara", "ir"]),
+    ("THAI_", &["th"]),
+];
+
+/// Keycodes of the letters on a QWERTY keyboard
+const QWERTY_KEYCODES: &[(char, u32)] = &[
+    ('Q', 24),
+    ('W', 25),
+    ('E', 26),
+    ('R', 27),
+    ('T', 28),
+    ('Y', 29),
+    ('U', 30),
+    ('I', 31),
+    ('O', 32),
+    ('P', 33),
+    ('A', 38),
+    ('S', 39),
+    ('D', 40),
+    ('F', 41),
+    ('G', 42),
+    ('H', 43),
+    ('J', 44),
+    ('K', 45),
+    ('L', 46),
+    ('Z', 52),
+    ('X', 53),
+    ('C', 54),
+    ('V', 55),
+    ('B', 56),
+    ('N', 57),
+    ('M', 58),
+];
+
+/// A binding whose key some configured layouts do not produce
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayoutWarning {
+    /// The binding as written
+    pub binding: Keybinding,
+    /// Configured layouts without the key, in config order
+    pub missing_from: Vec<String>,
+    /// Keycode of the key in the first layout that has it, if known
+    pub keycode: Option<u32>,
+}
+
+impl fmt::Display for LayoutWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let layouts: Vec<String> = self
+            .missing_from
+            .iter()
+            .map(|layout| format!("'{}'", layout))
+            .collect();
+        write!(
+            f,
+            "{} stops working after switching to the {} layout{}, which {} no {} key",
+            self.binding.key_combo,
+            layouts.join(", "),
+            if layouts.len() == 1 { "" } else { "s" },
+            if layouts.len() == 1 { "has" } else { "have" },
+            self.binding.key_combo.key
+        )?;
+        if let Some(code) = self.keycode {
+            write!(f, "; bind code:{} instead", code)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the layouts of `kb_layout` from the `input` section
+///
+/// Both the `input { kb_layout = ... }` block and the `input:kb_layout`
+/// form are read; the last assignment wins. Per-device sections are
+/// ignored.
+///
+/// # Arguments
+/// * `content` - The full config file content
+///
+/// # Returns
+/// The layouts in config order, lowercased; `["us"]` (Hyprland's default)
+/// when the config does not set any
+pub fn configured_layouts(content: &str) -> Vec<String> {
+    let mut value = None;
+    let mut sections: Vec<&str> = Vec::new();
+
+    for line in content.lines() {
+        let code = strip_comment(line).trim();
+        if code == "}" {
+            sections.pop();
+            continue;
+        }
+        if let Some(header) = code.strip_suffix('{') {
+            sections.push(header.trim());
+            continue;
+        }
+
+        let Some((key, assigned)) = code.split_once('=') else {
+            continue;
+        };
+        let is_layout = match sections.as_slice() {
+            ["input"] => key.trim() == "kb_layout",
+            [] => key.trim() == "input:kb_layout",
+            _ => false,
+        };
+        if is_layout {
+            value = Some(assigned.trim());
+        }
+    }
+
+    let layouts: Vec<String> = value
+        .unwrap_or_default()
+        .split(',')
+        .map(|layout| layout.trim().to_lowercase())
+        .filter(|layout| !layout.is_empty())
+        .collect();
+    if layouts.is_empty() {
+        vec!["us".to_string()]
+    } else {
+        layouts
+    }
+}
+
+/// Finds bindings whose key only exists in some of the configured layouts
+///
+/// A single layout never warns: there is nothing to switch to.
+///
+/// # Arguments
+/// * `bindings` - All bindings of the config
+/// * `layouts` - The configured layouts (see [`configured_layouts`])
+///
+/// # Returns
+/// One warning per affected binding, in binding order
+pub fn layout_warnings(bindings: &[Keybinding], layouts: &[String]) -> Vec<LayoutWarning> {
+    if layouts.len() < 2 {
+        return Vec::new();
+    }
+
+    bindings
+        .iter()
+        .filter_map(|binding| {
+            let Key::Named(key) = &binding.key_combo.key else {
+                return None;
+            };
+            let presence: Vec<(&String, bool)> = layouts
+                .iter()
+                .filter_map(|layout| layout_has_key(layout, key).map(|has| (layout, has)))
+                .collect();
+            let first_with_key = presence.iter().find(|(_, has)| *has)?.0;
+            let missing_from: Vec<String> = presence
+                .iter()
+                .filter(|(_, has)| !has)
+                .map(|(layout, _)| layout.to_string())
+                .collect();
+            if missing_from.is_empty() {
+                return None;
+            }
+
+            Some(LayoutWarning {
+                binding: binding.clone(),
+                missing_from,
+                keycode: keycode_in(first_with_key, key),
+            })
+        })
+        .collect()
+}
+
+/// Whether a layout produces a keysym (`None`: not known)
+///
+/// # Arguments
+/// * `layout` - Layout name as in `kb_layout` (e.g. "de")
+/// * `key` - Normalised keysym name (uppercase)
+pub fn layout_has_key(layout: &str, key: &str) -> Option<bool> {
+    if is_letter(key) {
+        return Some(!NON_LATIN_LAYOUTS.contains(&layout));
+    }
+    if is_digit(key) {
+        return Some(!SHIFTED_DIGIT_LAYOUTS.contains(&layout));
+    }
+    LAYOUT_KEYSYMS
+        .iter()
+        .find(|(keysym, _)| key == *keysym || (keysym.ends_with('_') && key.starts_with(keysym)))
+        .map(|(_, layouts)| layouts.contains(&layout))
+}
+
+/// Keycode of a letter or digit in a layout
+fn keycode_in(layout: &str, key: &str) -> Option<u32> {
+    if is_digit(key) {
+        let digit: u32 = key.parse().ok()?;
+        return Some(if digit == 0 { 19 } else { 9 + digit });
+    }
+    if !is_letter(key) {
+        return None;
+    }
+
+    let letter = key.chars().next()?;
+    let physical = if QWERTZ_LAYOUTS.contains(&layout) {
+        match letter {
+            'Y' => 'Z',
+            'Z' => 'Y',
+            other => other,
+        }
+    } else if AZERTY_LAYOUTS.contains(&layout) {
+        match letter {
+            'A' => 'Q',
+            'Q' => 'A',
+            'Z' => 'W',
+            'W' => 'Z',
+            // M sits right of L, where QWERTY has the semicolon
+            'M' => return Some(47),
+            other => other,
+        }
+    } else {
+        letter
+    };
+
+    QWERTY_KEYCODES
+        .iter()
+        .find(|(qwerty, _)| *qwerty == physical)
+        .map(|(_, code)| *code)
+}
+
+fn is_letter(key: &str) -> bool {
+    key.len() == 1 && key.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn is_digit(key: &str) -> bool {
+    key.len() == 1 && key.chars().all(|c| c.is_ascii_digit())
+}
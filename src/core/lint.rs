@@ -0,0 +1,447 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Style and usability lints for bindings
+//!
+//! Danger detection and validation decide whether a binding is safe to
+//! save; lints point out bindings that are valid but probably not what the
+//! user wants. Each [`LintRule`] has a [`LintLevel`]:
+//!
+//! - **Allow**: the rule is not checked
+//! - **Warn**: findings are shown (`check`, the GUI problems banner)
+//! - **Deny**: findings are shown as errors and make `check` fail
+//!
+//! The levels come from a lint policy file, `name = level` lines stored at
+//! `$XDG_CONFIG_HOME/hypr-keybind-manager/lint` ([`LINT_POLICY_FILE`]):
+//!
+//! ```text
+//! tui-without-terminal = deny
+//! mixed-modifier-style = warn
+//! missing-description = allow
+//! ```
+//!
+//! Rules not named keep their default level. Unknown rules and levels are
+//! ignored, as in the settings file, so a typo never stops `check`.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{
+//!     lint::{lint_bindings, LintLevel, LintPolicy, LintRule},
+//!     parser::parse_config_file,
+//! };
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, B, exec, btop\n";
+//! let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+//!
+//! let findings = lint_bindings(config, &bindings, &LintPolicy::default());
+//! assert_eq!(findings[0].rule, LintRule::TuiWithoutTerminal);
+//! assert_eq!(findings[0].level, LintLevel::Warn);
+//!
+//! let policy = LintPolicy::parse("tui-without-terminal = allow\n");
+//! assert!(lint_bindings(config, &bindings, &policy).is_empty());
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    config::{
+        paths::RuntimePaths,
+        resolve::{command_target, CommandTarget},
+    },
+    core::{
+        parser::modifier_from_str,
+        types::{BindFlag, Keybinding, Modifier},
+    },
+};
+
+/// File name of the lint policy in the tool's config directory
+pub const LINT_POLICY_FILE: &str = "lint";
+
+/// Programs that draw in a terminal and show nothing when started without one
+const TUI_PROGRAMS: &[&str] = &[
+    "aerc",
+    "alsamixer",
+    "bluetuith",
+    "btm",
+    "btop",
+    "calcurse",
+    "cmus",
+    "gdu",
+    "helix",
+    "htop",
+    "hx",
+    "k9s",
+    "lazydocker",
+    "lazygit",
+    "lf",
+    "mc",
+    "micro",
+    "mutt",
+    "nano",
+    "ncdu",
+    "ncmpcpp",
+    "neomutt",
+    "newsboat",
+    "nmtui",
+    "nnn",
+    "nvim",
+    "nvtop",
+    "pulsemixer",
+    "ranger",
+    "tig",
+    "top",
+    "vi",
+    "vim",
+    "yazi",
+];
+
+/// How much a lint matters
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintLevel {
+    /// Not checked
+    Allow,
+    /// Reported
+    Warn,
+    /// Reported as an error; `check` fails
+    Deny,
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LintLevel::Allow => "allow",
+            LintLevel::Warn => "warn",
+            LintLevel::Deny => "deny",
+        })
+    }
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "allow" => Ok(LintLevel::Allow),
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            other => Err(format!(
+                "Unknown lint level '{}' (allow, warn or deny)",
+                other
+            )),
+        }
+    }
+}
+
+/// Something a lint checks for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// `exec` of a terminal program (`btop`, `nvim`) without a terminal
+    TuiWithoutTerminal,
+    /// Modifiers written out (`SUPER`) where other bindings use a
+    /// variable for them (`$mainMod`)
+    MixedModifierStyle,
+    /// A binding with no `bindd` description and no comment above or
+    /// beside it
+    MissingDescription,
+}
+
+impl LintRule {
+    /// Every rule, in the order findings are reported
+    pub const ALL: [LintRule; 3] = [
+        LintRule::TuiWithoutTerminal,
+        LintRule::MixedModifierStyle,
+        LintRule::MissingDescription,
+    ];
+
+    /// Name used in the policy file and in reports
+    pub fn name(self) -> &'static str {
+        match self {
+            LintRule::TuiWithoutTerminal => "tui-without-terminal",
+            LintRule::MixedModifierStyle => "mixed-modifier-style",
+            LintRule::MissingDescription => "missing-description",
+        }
+    }
+
+    /// Level used when the policy does not name the rule
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            LintRule::TuiWithoutTerminal | LintRule::MixedModifierStyle => LintLevel::Warn,
+            // Most configs describe few bindings; opt in
+            LintRule::MissingDescription => LintLevel::Allow,
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Level of each lint rule
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LintPolicy {
+    /// Levels set by the policy file; other rules use their default
+    levels: HashMap<LintRule, LintLevel>,
+}
+
+impl LintPolicy {
+    /// Reads a policy from `name = level` lines
+    ///
+    /// `#` comments, unknown rules and unknown levels are skipped.
+    pub fn parse(content: &str) -> Self {
+        let mut policy = Self::default();
+        for (name, level) in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+        {
+            let rule = LintRule::ALL
+                .into_iter()
+                .find(|rule| rule.name() == name.trim());
+            if let (Some(rule), Ok(level)) = (rule, level.parse()) {
+                policy.set(rule, level);
+            }
+        }
+        policy
+    }
+
+    /// Returns the default policy file location
+    ///
+    /// # Returns
+    ///
+    /// * `Some(PathBuf)` - [`LINT_POLICY_FILE`] under [`RuntimePaths::config_dir`]
+    /// * `None` - Neither `XDG_CONFIG_HOME` nor `HOME` is set
+    pub fn default_path() -> Option<PathBuf> {
+        Some(
+            RuntimePaths::from_env()
+                .config_dir()?
+                .join(LINT_POLICY_FILE),
+        )
+    }
+
+    /// Loads the policy file; a missing or unreadable file gives the defaults
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    /// Level of a rule
+    pub fn level(&self, rule: LintRule) -> LintLevel {
+        self.levels
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+
+    /// Sets the level of a rule
+    pub fn set(&mut self, rule: LintRule, level: LintLevel) {
+        self.levels.insert(rule, level);
+    }
+}
+
+/// A binding a lint rule objects to
+#[derive(Clone, Debug, PartialEq)]
+pub struct LintFinding {
+    /// The rule
+    pub rule: LintRule,
+    /// Its level in the policy (never `Allow`)
+    pub level: LintLevel,
+    /// What is wrong, naming the binding
+    pub message: String,
+    /// The binding
+    pub binding: Keybinding,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.message, self.rule)?;
+        if let Some(location) = &self.binding.location {
+            write!(f, " ({})", location)?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the lint rules the policy does not allow
+///
+/// Rules that look at how a line is written (modifier style, comments)
+/// only check bindings of the main config, whose text is `content`.
+///
+/// # Arguments
+///
+/// * `content` - The main config
+/// * `bindings` - Its bindings, with `source`d ones
+/// * `policy` - Level of each rule
+///
+/// # Returns
+///
+/// Findings grouped by rule in [`LintRule::ALL`] order, then in binding
+/// order
+pub fn lint_bindings(
+    content: &str,
+    bindings: &[Keybinding],
+    policy: &LintPolicy,
+) -> Vec<LintFinding> {
+    let lines: Vec<&str> = content.lines().collect();
+    // Text of a binding's line in the main config
+    let line_of = |binding: &Keybinding| {
+        let location = binding.location.as_ref()?;
+        if binding.source_file.is_some() {
+            return None;
+        }
+        lines
+            .get(location.line.checked_sub(1)?)
+            .map(|line| line.trim())
+    };
+    let modifier_variables = modifier_variables(&lines);
+
+    let mut findings = Vec::new();
+    for rule in LintRule::ALL {
+        let level = policy.level(rule);
+        if level == LintLevel::Allow {
+            continue;
+        }
+        let mut report = |binding: &Keybinding, message: String| {
+            findings.push(LintFinding {
+                rule,
+                level,
+                message: format!("{}: {}", binding.key_combo, message),
+                binding: binding.clone(),
+            })
+        };
+
+        match rule {
+            LintRule::TuiWithoutTerminal => {
+                for binding in bindings {
+                    if !matches!(binding.dispatcher.trim(), "exec" | "execr") {
+                        continue;
+                    }
+                    let Some(CommandTarget::Program(program)) =
+                        binding.args.as_deref().and_then(command_target)
+                    else {
+                        continue;
+                    };
+                    let name = program.rsplit('/').next().unwrap_or(&program);
+                    if TUI_PROGRAMS.contains(&name) {
+                        report(
+                            binding,
+                            format!(
+                                "'{}' runs in a terminal, but none is started (e.g. `kitty -e {}`)",
+                                name, name
+                            ),
+                        );
+                    }
+                }
+            }
+            LintRule::MixedModifierStyle => {
+                // Variables standing for modifiers that bind lines use
+                let used: Vec<&(String, Vec<Modifier>)> = modifier_variables
+                    .iter()
+                    .filter(|(name, _)| {
+                        bindings.iter().filter_map(&line_of).any(|line| {
+                            modifier_words(raw_modifiers(line)).any(|word| word == name)
+                        })
+                    })
+                    .collect();
+                for binding in bindings {
+                    let Some(written) = line_of(binding).map(raw_modifiers) else {
+                        continue;
+                    };
+                    if written.contains('$') {
+                        continue;
+                    }
+                    let modifiers = binding.key_combo.canonical_modifiers();
+                    if let Some((name, _)) = used.iter().find(|(_, stands_for)| {
+                        stands_for
+                            .iter()
+                            .all(|modifier| modifiers.contains(modifier))
+                    }) {
+                        report(
+                            binding,
+                            format!("modifiers written out where other bindings use {}", name),
+                        );
+                    }
+                }
+            }
+            LintRule::MissingDescription => {
+                for binding in bindings {
+                    if binding.bind_type.contains(BindFlag::Description)
+                        || binding.description.is_some()
+                    {
+                        continue;
+                    }
+                    let Some(location) = &binding.location else {
+                        continue;
+                    };
+                    let Some(line) = line_of(binding) else {
+                        continue;
+                    };
+                    let comment_above = location
+                        .line
+                        .checked_sub(2)
+                        .and_then(|above| lines.get(above))
+                        .is_some_and(|above| above.trim().starts_with('#'));
+                    if !comment_above && !line.contains(" #") {
+                        report(
+                            binding,
+                            "no description (use bindd or a comment above it)".to_string(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    findings
+}
+
+/// Variables whose value is only modifiers, e.g. `$mainMod = SUPER`
+fn modifier_variables(lines: &[&str]) -> Vec<(String, Vec<Modifier>)> {
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with('$'))
+        .filter_map(|line| line.split_once('='))
+        .filter_map(|(name, value)| {
+            let modifiers: Option<Vec<Modifier>> =
+                modifier_words(value).map(modifier_from_str).collect();
+            modifiers
+                .filter(|modifiers| !modifiers.is_empty())
+                .map(|modifiers| (name.trim().to_string(), modifiers))
+        })
+        .collect()
+}
+
+/// The words of a modifiers field (`SUPER_SHIFT`, `$mainMod + ALT`)
+fn modifier_words(field: &str) -> impl Iterator<Item = &str> {
+    field
+        .split(|c: char| c == '_' || c == '+' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+}
+
+/// The modifiers field of a bind line as written, e.g. `$mainMod SHIFT`
+fn raw_modifiers(line: &str) -> &str {
+    line.split_once('=')
+        .and_then(|(_, rest)| rest.split(',').next())
+        .map(str::trim)
+        .unwrap_or_default()
+}
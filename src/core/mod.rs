@@ -20,21 +20,71 @@
 //! for keybinding management, including:
 //! - Type definitions for keybindings and key combinations
 //! - Conflict detection using HashMap-based O(1) lookup
+//! - Hyprland `binds` options that change what bindings do
+//! - Printable Markdown and HTML cheat sheets of the bindings
+//! - Compact, string-interned binding snapshots for the resident GUI
+//! - Side-by-side comparison and merging of two binding sets
+//! - Equivalence of bindings that differ only in formatting
+//! - Drift between the config and Hyprland's live bindings
+//! - Focused window and input devices of a running Hyprland
+//! - Dispatcher catalogue with the arguments each dispatcher takes
+//! - Dispatcher availability by Hyprland version
+//! - Embedded explanations of how Hyprland resolves conflicts
+//! - Heuristic grouping of bindings for unorganised configs
+//! - Searchable help topics generated from the code's own tables
+//! - Free key combo suggestions for resolving conflicts
+//! - Combos reserved by Hyprland, the system or common tools
+//! - Binding scopes (global, laptop, external keyboard, ...)
+//! - Bindings that run `hyprctl dispatch` instead of the dispatcher
+//! - Submap escape checks (reset bindings and `catchall`)
+//! - Keysym name normalisation (case and aliases such as `enter`)
+//! - Keys that only some configured keyboard layouts produce
+//! - Fuzzy lookup of bindings by name for the `run` command palette
+//! - Style lints with allow/warn/deny levels from a policy file
 //! - Input validation with security whitelisting
 //! - Configuration parsing
+//! - Parse coverage reporting (what the parser understood)
+//! - jq-style queries over the parsed bindings for scripts
+//! - Statistics (bindings per modifier, dispatcher and submap, free keys)
+//! - Workspace selector parsing for workspace dispatchers
 //!
 //! All business logic is isolated from UI and I/O concerns to enable
 //! comprehensive unit testing without requiring a display server.
 
+pub mod binds_options;
+pub mod cheatsheet;
+pub mod compact;
+pub mod compare;
+pub mod compat;
 pub mod conflict;
+pub mod coverage;
+pub mod dispatchers;
+pub mod drift;
+pub mod environment;
+pub mod equivalence;
+pub mod explanations;
+pub mod grouping;
+pub mod help;
+pub mod idioms;
+pub mod key_suggestions;
+pub mod keysyms;
+pub mod layouts;
+pub mod lint;
+pub mod palette;
 pub mod parser;
+pub mod query;
+pub mod reserved;
 pub mod sandbox;
+pub mod scope;
+pub mod stats;
+pub mod submap;
 pub mod types;
 pub mod validator;
+pub mod workspace;
 
 pub use validator::{validate_keybinding, ValidationError};
 pub use {
-    conflict::{Conflict, ConflictDetector},
+    conflict::{Conflict, ConflictDetector, ConflictKind, ConflictSeverity, DuplicateAction},
     types::*,
 };
 
@@ -0,0 +1,232 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Finding bindings by name, as a command palette does
+//!
+//! `hypr-keybind-manager run browser` runs whichever binding best matches
+//! "browser", so every binding doubles as a named command. A binding is
+//! matched on its `bindd` description, its arguments and its dispatcher:
+//! the query's characters must appear in that order (`ffx` matches
+//! `firefox`), and matches that are contiguous, start words or come from
+//! the description rank higher.
+//!
+//! Mouse bindings (`bindm`) are never offered: their dispatchers only work
+//! while a mouse button is held.
+//!
+//! The same scoring drives searching, for `hypr-keybind-manager find` and
+//! the GUI search bar ([`search_bindings`]). A search also looks at the key
+//! combo, the category and the input device, and finds every kind of
+//! binding.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{palette::find_bindings, parser::parse_config_file};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, B, exec, firefox\n\
+//!               bindd = SUPER, T, Open terminal, exec, kitty\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//!
+//! let matches = find_bindings(&bindings, "term");
+//! assert_eq!(matches[0].binding.args.as_deref(), Some("kitty"));
+//!
+//! let matches = find_bindings(&bindings, "ffx");
+//! assert_eq!(matches[0].binding.args.as_deref(), Some("firefox"));
+//! ```
+
+use crate::core::types::{BindFlag, Keybinding};
+
+/// Points for each matched character
+const CHAR_SCORE: u32 = 1;
+
+/// Extra points for a character right after the previous match
+const CONSECUTIVE_BONUS: u32 = 4;
+
+/// Extra points for a character starting a word
+const WORD_START_BONUS: u32 = 6;
+
+/// Extra points when the query appears unbroken in the text
+const SUBSTRING_BONUS: u32 = 10;
+
+/// Extra points for a match in the description, which names the binding
+const DESCRIPTION_BONUS: u32 = 5;
+
+/// A binding matching a palette query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaletteMatch<'a> {
+    /// The matching binding
+    pub binding: &'a Keybinding,
+    /// How well it matches; higher is better
+    pub score: u32,
+}
+
+/// Scores how well `text` matches a fuzzy `query`
+///
+/// Case is ignored and whitespace in the query is skipped.
+///
+/// # Returns
+///
+/// * `Some(score)` - Every query character appears in `text`, in order
+/// * `None` - The query does not match, or is empty
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return None;
+    }
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+
+    let mut score = 0;
+    let mut wanted = query.iter().peekable();
+    let mut previous_match = None;
+    for (i, c) in text.iter().enumerate() {
+        let Some(&&next) = wanted.peek() else {
+            break;
+        };
+        if *c != next {
+            continue;
+        }
+        wanted.next();
+
+        score += CHAR_SCORE;
+        if i > 0 && previous_match == Some(i - 1) {
+            score += CONSECUTIVE_BONUS;
+        }
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += WORD_START_BONUS;
+        }
+        previous_match = Some(i);
+    }
+    if wanted.peek().is_some() {
+        return None;
+    }
+
+    // The greedy pass above may split a query the text contains whole
+    // ("browser" in "web browser" starts at the "b" of "web")
+    let unbroken = (0..=text.len() - query.len())
+        .filter(|&start| text[start..start + query.len()] == query[..])
+        .map(|start| {
+            let word_start = start == 0 || !text[start - 1].is_alphanumeric();
+            query.len() as u32 * CHAR_SCORE
+                + (query.len() as u32 - 1) * CONSECUTIVE_BONUS
+                + if word_start { WORD_START_BONUS } else { 0 }
+                + SUBSTRING_BONUS
+        })
+        .max();
+
+    Some(unbroken.map_or(score, |unbroken| unbroken.max(score)))
+}
+
+/// Scores a binding against a query, using its best matching field
+fn binding_score(binding: &Keybinding, query: &str) -> Option<u32> {
+    let description = binding
+        .description
+        .as_deref()
+        .and_then(|description| fuzzy_score(query, description))
+        .map(|score| score + DESCRIPTION_BONUS);
+    let args = binding
+        .args
+        .as_deref()
+        .and_then(|args| fuzzy_score(query, args));
+    let dispatcher = fuzzy_score(query, &binding.dispatcher);
+
+    [description, args, dispatcher].into_iter().flatten().max()
+}
+
+/// Scores a key combo against a query
+///
+/// Each part of the query (split at `+` and spaces) must start a different
+/// part of the combo, in any order, as modifiers have no fixed order:
+/// `sup+ret` matches `SUPER+Return`, but `super+t` does not, though its
+/// characters appear in that order.
+fn combo_score(query: &str, combo: &str) -> Option<u32> {
+    let parts: Vec<String> = combo.split('+').map(str::to_lowercase).collect();
+    let mut used = vec![false; parts.len()];
+    let mut score = None;
+    for wanted in query
+        .split(|c: char| c == '+' || c.is_whitespace())
+        .filter(|wanted| !wanted.is_empty())
+    {
+        let wanted = wanted.to_lowercase();
+        let index = (0..parts.len()).find(|&i| !used[i] && parts[i].starts_with(&wanted))?;
+        used[index] = true;
+        score = Some(score.unwrap_or(0) + fuzzy_score(&wanted, &parts[index])?);
+    }
+    score
+}
+
+/// Sorts matches best first, keeping config order on ties
+fn rank(mut matches: Vec<PaletteMatch<'_>>) -> Vec<PaletteMatch<'_>> {
+    matches.sort_by_key(|m| std::cmp::Reverse(m.score));
+    matches
+}
+
+/// Returns the bindings matching a query, best match first
+///
+/// Bindings with equal scores keep their config order.
+///
+/// # Arguments
+///
+/// * `bindings` - Bindings to search
+/// * `query` - What the user typed, e.g. `browser`
+pub fn find_bindings<'a>(bindings: &'a [Keybinding], query: &str) -> Vec<PaletteMatch<'a>> {
+    rank(
+        bindings
+            .iter()
+            .filter(|binding| !binding.bind_type.contains(BindFlag::Mouse))
+            .filter_map(|binding| {
+                binding_score(binding, query).map(|score| PaletteMatch { binding, score })
+            })
+            .collect(),
+    )
+}
+
+/// Returns the bindings matching a search, best match first
+///
+/// Unlike [`find_bindings`], the key combo (`SUPER+K`), category and input
+/// device are matched too, and mouse bindings are included. A combo matches
+/// when every part of the query starts one of its parts, in any order, so
+/// `super+t` finds `SUPER+T` but not `SUPER+Return`. Bindings with equal scores keep
+/// their config order.
+///
+/// # Arguments
+///
+/// * `bindings` - Bindings to search
+/// * `query` - What the user typed, e.g. `fire` or `super+k`
+pub fn search_bindings<'a>(bindings: &'a [Keybinding], query: &str) -> Vec<PaletteMatch<'a>> {
+    rank(
+        bindings
+            .iter()
+            .filter_map(|binding| {
+                let combo = combo_score(query, &binding.key_combo.to_string());
+                let category = binding
+                    .category
+                    .as_deref()
+                    .and_then(|category| fuzzy_score(query, category));
+                let device = binding
+                    .device
+                    .as_deref()
+                    .and_then(|device| fuzzy_score(query, device));
+                [binding_score(binding, query), combo, category, device]
+                    .into_iter()
+                    .flatten()
+                    .max()
+                    .map(|score| PaletteMatch { binding, score })
+            })
+            .collect(),
+    )
+}
@@ -19,9 +19,16 @@
 //! This module parses Hyprland config files to extract keybindings.
 //! It handles:
 //! - All bind types (bind, binde, bindl, bindm, bindr, bindel)
+//! - Submap sections (`submap = name` ... `submap = reset`)
+//! - `source = path` includes, followed recursively with cycle detection
+//! - `unbind = MODS, KEY` lines, which remove the bindings declared before
+//! - `# @category: name` annotations, which tag the bind line below them
 //! - Variable substitution ($mainMod)
 //! - Comments and whitespace
-//! - Line numbers for error reporting
+//! - Line numbers for error reporting, and the location of every binding
+//! - Non-fatal diagnostics for lines that were only partially understood
+//! - A recovering mode ([`parse_config_report`]) that skips bad lines and
+//!   reports them instead of failing
 //!
 //! # Architecture
 //! The parser uses nom combinators for composable, type-safe parsing.
@@ -33,19 +40,27 @@
 //! The parser only reads and structures data - it never executes commands
 //! or modifies files. All validation happens in validator.rs after parsing.
 
-use nom::{
-    branch::alt,
-    bytes::complete::{tag, take_until, take_while1},
-};
+use nom::bytes::complete::{take_until, take_while1};
 use nom::{
     character::complete::{char, space0},
-    combinator::{map, opt},
+    combinator::{map_opt, opt},
 };
 use nom::{sequence::preceded, IResult, Parser};
-use std::{collections::HashMap, path::Path};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
-use crate::core::types::{BindType, KeyCombo, Keybinding, Modifier};
+use crate::core::binds_options::strip_comment;
+use crate::core::types::{
+    BindFlag, BindType, KeyCombo, Keybinding, Modifier, SourceLocation, Unbind, SUBMAP_RESET,
+};
+
+/// Comment tag that sets the category of the bind line below it
+pub const CATEGORY_ANNOTATION: &str = "@category:";
 
 /// Parse errors with line number context
 #[derive(Debug, Error)]
@@ -58,10 +73,135 @@ pub enum ParseError {
 
     #[error("IO error reading config: {0}")]
     IoError(#[from] std::io::Error),
+
+    #[error("Cannot read sourced file '{}' (line {line}): {source}", path.display())]
+    SourceUnreadable {
+        path: PathBuf,
+        line: usize,
+        source: std::io::Error,
+    },
+
+    #[error("Source cycle: '{}' sources itself (line {line})", path.display())]
+    SourceCycle { path: PathBuf, line: usize },
+}
+
+impl ParseError {
+    /// Line the error was found on (`None` for I/O errors)
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParseError::InvalidSyntax { line, .. }
+            | ParseError::UndefinedVariable { line, .. }
+            | ParseError::SourceUnreadable { line, .. }
+            | ParseError::SourceCycle { line, .. } => Some(*line),
+            ParseError::IoError(_) => None,
+        }
+    }
+}
+
+/// A non-fatal problem found in a bind line
+///
+/// Diagnostics describe lines the parser accepted but only partially
+/// understood - for example an unknown modifier that was dropped, or a
+/// variable with no definition. The resulting bindings may not match what
+/// Hyprland itself sees, so callers can choose to refuse writes until the
+/// diagnostics are resolved (see strict mode in the Controller).
+///
+/// Diagnostics from [`parse_config_report`] also cover lines that were
+/// skipped outright, and name the file they were found in.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ParseDiagnostic {
+    /// What kind of problem this is
+    pub kind: DiagnosticKind,
+    /// File the line belongs to (`None` for content parsed on its own)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+    /// Line number (1-based) the diagnostic refers to
+    pub line: usize,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl ParseDiagnostic {
+    /// Creates a diagnostic that is not tied to a file
+    pub fn new(kind: DiagnosticKind, line: usize, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            file: None,
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// Returns whether the line was dropped rather than partially understood
+    pub fn is_skipped_line(&self) -> bool {
+        matches!(
+            self.kind,
+            DiagnosticKind::Unparseable | DiagnosticKind::UnknownFlag | DiagnosticKind::Source
+        )
+    }
+}
+
+impl fmt::Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}: {}", file.display(), self.line, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+/// The kind of problem a [`ParseDiagnostic`] describes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// A bind line that does not parse; it was skipped
+    Unparseable,
+    /// A bind keyword with a flag letter Hyprland does not know; the line
+    /// was skipped
+    UnknownFlag,
+    /// A modifier name that was dropped from the key combo
+    UnknownModifier,
+    /// A variable that is referenced but never defined
+    UndefinedVariable,
+    /// A bind line with an empty key
+    MissingKey,
+    /// A `source = path` line that could not be followed
+    Source,
+}
+
+/// Everything [`parse_config_report`] found in a config
+///
+/// Unlike [`parse_config_with_sources`], which stops at the first line it
+/// cannot parse, the report keeps every binding that did parse and lists
+/// the rest as diagnostics.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ParseReport {
+    /// Bindings that parsed, in config order
+    pub bindings: Vec<Keybinding>,
+    /// Problems found, grouped by file and in line order within a file
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl ParseReport {
+    /// Returns whether every line was fully understood
+    pub fn is_clean(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Number of lines that were skipped rather than partially understood
+    pub fn skipped_lines(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_skipped_line())
+            .count()
+    }
 }
 
 /// Parse a complete Hyprland config file
 ///
+/// Only `content` itself is parsed; `source = path` lines are skipped. Use
+/// [`parse_config_with_sources`] to follow them.
+///
 /// # Arguments
 /// * `content` - The full config file content as a string
 /// * `file_path` - Path to the config file (for error messages)
@@ -74,24 +214,228 @@ pub enum ParseError {
 /// let config = std::fs::read_to_string("hyprland.conf")?;
 /// let bindings = parse_config_file(&config, Path::new("hyprland.conf"))?;
 /// ```
-pub fn parse_config_file(content: &str, _file_path: &Path) -> Result<Vec<Keybinding>, ParseError> {
+pub fn parse_config_file(content: &str, file_path: &Path) -> Result<Vec<Keybinding>, ParseError> {
+    let mut keybindings = Vec::new();
+    parse_lines(
+        content,
+        file_path,
+        None,
+        &HashMap::new(),
+        None,
+        &mut keybindings,
+        None,
+    )?;
+    Ok(keybindings)
+}
+
+/// Parse a Hyprland config file together with the files it sources
+///
+/// Every `source = path` line is read and parsed in place, recursively.
+/// Relative paths are resolved against the directory of the file that
+/// sources them, and `~` is expanded. Variables defined before a `source`
+/// line are visible in the sourced file, so `$mainMod` from the main config
+/// works in an included `keybinds.conf`.
+///
+/// Bindings from sourced files record the file in
+/// [`Keybinding::source_file`]; bindings from `content` itself have `None`.
+/// Every binding records its file and line in [`Keybinding::location`].
+///
+/// An `unbind` line removes earlier bindings from every file, so an unbind
+/// in the main config cancels a binding from a file sourced above it.
+///
+/// # Arguments
+/// * `content` - Content of the main config file
+/// * `file_path` - Path of the main config file (for resolving sources)
+///
+/// # Errors
+/// * `ParseError::SourceUnreadable` - A sourced file cannot be read
+/// * `ParseError::SourceCycle` - A file sources itself, directly or not
+/// * Any error from parsing the bind lines of a file
+pub fn parse_config_with_sources(
+    content: &str,
+    file_path: &Path,
+) -> Result<Vec<Keybinding>, ParseError> {
+    let mut stack = vec![canonical_path(file_path)];
+    let mut keybindings = Vec::new();
+    parse_lines(
+        content,
+        file_path,
+        None,
+        &HashMap::new(),
+        Some(&mut stack),
+        &mut keybindings,
+        None,
+    )?;
+    Ok(keybindings)
+}
+
+/// Parse a Hyprland config file and its sources without giving up on errors
+///
+/// Follows `source = path` lines like [`parse_config_with_sources`], but
+/// every problem is recorded instead of aborting the parse: bind lines that
+/// do not parse (including unknown bind flags) are skipped, sourced files
+/// that cannot be read or form a cycle are skipped, and the partial
+/// understandings reported by [`diagnose_config`] are included for every
+/// file. Each diagnostic names the file it was found in.
+///
+/// # Arguments
+/// * `content` - Content of the main config file
+/// * `file_path` - Path of the main config file
+///
+/// # Returns
+/// The bindings that parsed, together with the diagnostics
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::parser::{parse_config_report, DiagnosticKind};
+/// use std::path::Path;
+///
+/// let config = "bind = SUPER, K, exec, kitty\nbindx = SUPER, Q, killactive\n";
+/// let report = parse_config_report(config, Path::new("hyprland.conf"));
+///
+/// assert_eq!(report.bindings.len(), 1);
+/// assert_eq!(report.diagnostics[0].kind, DiagnosticKind::UnknownFlag);
+/// assert_eq!(report.diagnostics[0].line, 2);
+/// ```
+pub fn parse_config_report(content: &str, file_path: &Path) -> ParseReport {
+    let mut stack = vec![canonical_path(file_path)];
+    let mut report = ParseReport::default();
+    let parsed = parse_lines(
+        content,
+        file_path,
+        None,
+        &HashMap::new(),
+        Some(&mut stack),
+        &mut report.bindings,
+        Some(&mut report.diagnostics),
+    );
+
+    // Problems are recorded as diagnostics and their lines skipped, so no
+    // error is expected here; should one escape, it is reported the same way
+    if let Err(e) = parsed {
+        report.diagnostics.push(ParseDiagnostic {
+            file: Some(file_path.to_path_buf()),
+            ..ParseDiagnostic::new(
+                DiagnosticKind::Unparseable,
+                e.line().unwrap_or_default(),
+                e.to_string(),
+            )
+        });
+    }
+    report
+}
+
+/// Parses the bind lines of one file
+///
+/// # Arguments
+/// * `file_path` - Path of the file, for resolving relative sources
+/// * `source_file` - Recorded on each binding (`None` for the main config)
+/// * `inherited` - Variables defined before the file was sourced
+/// * `stack` - Files currently being parsed; `None` skips `source` lines
+/// * `keybindings` - Bindings declared so far, in all files; parsed
+///   bindings are appended and `unbind` lines remove from it
+/// * `diagnostics` - When given, problems are appended here and the line
+///   is skipped instead of returning an error
+fn parse_lines(
+    content: &str,
+    file_path: &Path,
+    source_file: Option<&Path>,
+    inherited: &HashMap<String, String>,
+    mut stack: Option<&mut Vec<PathBuf>>,
+    keybindings: &mut Vec<Keybinding>,
+    mut diagnostics: Option<&mut Vec<ParseDiagnostic>>,
+) -> Result<(), ParseError> {
     // First pass: Collect variable definitions
-    let variables = collect_variables(content);
+    let mut variables = inherited.clone();
+    variables.extend(collect_variables(content));
+
+    // Problems in this file, appended to `diagnostics` once it is done
+    let mut file_diagnostics = match diagnostics {
+        Some(_) => diagnose_lines(content, &variables),
+        None => Vec::new(),
+    };
 
     // Second pass: Parse bindings with variable substitution
-    let mut keybindings = Vec::new();
+    let devices = device_sections(content);
+    let mut current_submap: Option<String> = None;
+    let mut annotation: Option<String> = None;
 
     for (line_num, line) in content.lines().enumerate() {
         let line_num = line_num + 1; // Human-readable numbers start at 1
 
-        // Skip empty lines and comments
+        // An annotation only applies to the line right below it
+        let category = annotation.take();
         let line_trimmed = line.trim();
+        if let Some(name) = parse_category_annotation(line_trimmed) {
+            annotation = Some(name);
+            continue;
+        }
+
+        // Skip empty lines and comments
         if line_trimmed.is_empty() || line_trimmed.starts_with('#') {
             continue;
         }
 
+        // Track which submap section following bindings belong to
+        if let Some(submap) = parse_submap_line(&substitute_variables(line_trimmed, &variables)) {
+            current_submap = submap;
+            continue;
+        }
+
+        // Follow includes in place
+        if let Some(source) = parse_source_line(&substitute_variables(line_trimmed, &variables)) {
+            if let Some(stack) = stack.as_deref_mut() {
+                let path = resolve_source_path(&source, file_path);
+                let canonical = canonical_path(&path);
+                let sourced = if stack.contains(&canonical) {
+                    Err(ParseError::SourceCycle {
+                        path: path.clone(),
+                        line: line_num,
+                    })
+                } else {
+                    fs::read_to_string(&path).map_err(|e| ParseError::SourceUnreadable {
+                        path: path.clone(),
+                        line: line_num,
+                        source: e,
+                    })
+                };
+
+                let sourced = match (sourced, diagnostics.is_some()) {
+                    (Ok(sourced), _) => sourced,
+                    (Err(e), true) => {
+                        file_diagnostics.push(ParseDiagnostic::new(
+                            DiagnosticKind::Source,
+                            line_num,
+                            format!("{}; the file was skipped", e),
+                        ));
+                        continue;
+                    }
+                    (Err(e), false) => return Err(e),
+                };
+
+                stack.push(canonical);
+                parse_lines(
+                    &sourced,
+                    &path,
+                    Some(&path),
+                    &variables,
+                    Some(&mut *stack),
+                    keybindings,
+                    diagnostics.as_deref_mut(),
+                )?;
+                stack.pop();
+            }
+            continue;
+        }
+
+        // Drop the bindings an unbind line cancels
+        if let Some(unbind) = parse_unbind_line(&substitute_variables(line_trimmed, &variables)) {
+            keybindings.retain(|binding| !unbind.cancels(binding));
+            continue;
+        }
+
         // Only process bind lines
-        if !line_trimmed.starts_with("bind") {
+        if !is_bind_line(line_trimmed) {
             continue;
         }
 
@@ -100,7 +444,22 @@ pub fn parse_config_file(content: &str, _file_path: &Path) -> Result<Vec<Keybind
 
         // Parse the bind line
         match parse_bind_line(&substituted) {
-            Ok((_, binding)) => keybindings.push(binding),
+            Ok((_, binding)) => keybindings.push(Keybinding {
+                submap: current_submap.clone(),
+                source_file: source_file.map(Path::to_path_buf),
+                category,
+                device: devices.get(line_num - 1).cloned().flatten(),
+                location: Some(SourceLocation {
+                    file: file_path.to_path_buf(),
+                    line: line_num,
+                }),
+                ..binding
+            }),
+            Err(_) if diagnostics.is_some() => {
+                // A skipped line replaces whatever was partially understood
+                file_diagnostics.retain(|diagnostic| diagnostic.line != line_num);
+                file_diagnostics.push(skipped_bind_diagnostic(&substituted, line_num));
+            }
             Err(e) => {
                 return Err(ParseError::InvalidSyntax {
                     line: line_num,
@@ -110,7 +469,256 @@ pub fn parse_config_file(content: &str, _file_path: &Path) -> Result<Vec<Keybind
         }
     }
 
-    Ok(keybindings)
+    if let Some(diagnostics) = diagnostics {
+        file_diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+        diagnostics.extend(
+            file_diagnostics
+                .into_iter()
+                .map(|diagnostic| ParseDiagnostic {
+                    file: Some(file_path.to_path_buf()),
+                    ..diagnostic
+                }),
+        );
+    }
+
+    Ok(())
+}
+
+/// Describes why a bind line that does not parse was skipped
+fn skipped_bind_diagnostic(line: &str, line_num: usize) -> ParseDiagnostic {
+    let keyword = line.split_once('=').map(|(k, _)| k.trim()).unwrap_or(line);
+    if BindType::from_keyword(keyword).is_none() {
+        let unknown: String = keyword
+            .strip_prefix("bind")
+            .unwrap_or_default()
+            .chars()
+            .filter(|&letter| BindFlag::from_letter(letter).is_none())
+            .collect();
+        return ParseDiagnostic::new(
+            DiagnosticKind::UnknownFlag,
+            line_num,
+            format!(
+                "Unknown bind flag '{}' in '{}'; the line was skipped",
+                unknown, keyword
+            ),
+        );
+    }
+
+    ParseDiagnostic::new(
+        DiagnosticKind::Unparseable,
+        line_num,
+        "Bind line does not parse (expected MODS, KEY, DISPATCHER[, ARGS]); the line was skipped",
+    )
+}
+
+/// Returns whether a line is a bind line (`bind = ...`, `binde = ...`, ...)
+///
+/// Checks the keyword only, so a line that does not parse still counts.
+/// The `binds { ... }` section and `binds:option = value` lines are not
+/// bind lines.
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::parser::is_bind_line;
+///
+/// assert!(is_bind_line("  binde = , XF86AudioRaiseVolume, exec, pamixer -i 5"));
+/// assert!(!is_bind_line("binds {"));
+/// assert!(!is_bind_line("binds:pass_mouse_when_bound = true"));
+/// ```
+pub fn is_bind_line(line: &str) -> bool {
+    line.trim()
+        .split_once('=')
+        .map(|(keyword, _)| keyword.trim())
+        .is_some_and(|keyword| {
+            keyword.starts_with("bind") && keyword.chars().all(|c| c.is_ascii_alphabetic())
+        })
+}
+
+/// Parse a `source = path` line
+///
+/// # Returns
+///
+/// * `Some(path)` - The path as written (not yet resolved)
+/// * `None` - Not a source line
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::parser::parse_source_line;
+///
+/// assert_eq!(parse_source_line("source = ~/.config/hypr/keybinds.conf"),
+///            Some("~/.config/hypr/keybinds.conf".to_string()));
+/// assert_eq!(parse_source_line("bind = SUPER, K, exec, kitty"), None);
+/// ```
+pub fn parse_source_line(line: &str) -> Option<String> {
+    let (keyword, path) = line.trim().split_once('=')?;
+    if keyword.trim() != "source" {
+        return None;
+    }
+
+    let path = path.split('#').next().unwrap_or_default().trim();
+    (!path.is_empty()).then(|| path.to_string())
+}
+
+/// Parse an `unbind = MODS, KEY` line
+///
+/// # Returns
+///
+/// * `Some(unbind)` - The unbound key combo
+/// * `None` - Not an unbind line, or one without a key
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::{parser::parse_unbind_line, KeyCombo, Modifier};
+///
+/// let unbind = parse_unbind_line("unbind = SUPER SHIFT, q").unwrap();
+/// assert_eq!(unbind.key_combo, KeyCombo::new(vec![Modifier::Super, Modifier::Shift], "Q"));
+/// assert_eq!(parse_unbind_line("bind = SUPER, Q, killactive"), None);
+/// ```
+pub fn parse_unbind_line(line: &str) -> Option<Unbind> {
+    let (keyword, combo) = line.trim().split_once('=')?;
+    if keyword.trim() != "unbind" {
+        return None;
+    }
+
+    let combo = combo.split('#').next().unwrap_or_default();
+    let (modifier_str, key) = combo.split_once(',')?;
+    let key = key.split(',').next().unwrap_or_default().trim();
+    if key.is_empty() {
+        return None;
+    }
+
+    let modifiers = parse_modifiers(modifier_str.trim()).ok()?;
+    Some(Unbind {
+        key_combo: KeyCombo::new(modifiers, key),
+    })
+}
+
+/// Parse a `# @category: name` annotation
+///
+/// An annotation tags the bind line right below it
+/// ([`Keybinding::category`]); any other line in between detaches it.
+///
+/// # Returns
+///
+/// * `Some(name)` - The category, trimmed
+/// * `None` - Not an annotation, or one without a name
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::parser::parse_category_annotation;
+///
+/// assert_eq!(parse_category_annotation("# @category: media"), Some("media".to_string()));
+/// assert_eq!(parse_category_annotation("#@category:window management"),
+///            Some("window management".to_string()));
+/// assert_eq!(parse_category_annotation("# Media keys"), None);
+/// ```
+pub fn parse_category_annotation(line: &str) -> Option<String> {
+    let comment = line.trim().strip_prefix('#')?.trim_start();
+    let name = comment.strip_prefix(CATEGORY_ANNOTATION)?.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Resolves a sourced path against the directory of the sourcing file
+pub(crate) fn resolve_source_path(source: &str, file_path: &Path) -> PathBuf {
+    let expanded = PathBuf::from(shellexpand::tilde(source).as_ref());
+    if expanded.is_absolute() {
+        return expanded;
+    }
+
+    file_path
+        .parent()
+        .map(|dir| dir.join(&expanded))
+        .unwrap_or(expanded)
+}
+
+/// Canonical form of a path for cycle detection (the path itself if it
+/// cannot be resolved)
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Collect non-fatal diagnostics for a Hyprland config file
+///
+/// Runs the same variable substitution as [`parse_config_file`] and then
+/// inspects the modifier and key fields of every bind line for things the
+/// parser silently tolerates:
+/// - Modifier names it does not recognise (dropped from the key combo)
+/// - Variables that are referenced but never defined
+/// - Bind lines with an empty key
+///
+/// Arguments are deliberately not inspected: `$HOME` and friends are
+/// expanded by the shell at runtime, not by Hyprland.
+///
+/// # Arguments
+/// * `content` - The full config file content as a string
+///
+/// # Returns
+/// Diagnostics in line order (empty when the config is fully understood)
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::parser::diagnose_config;
+///
+/// let diagnostics = diagnose_config("bind = HYPER, K, exec, kitty");
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].line, 1);
+/// ```
+pub fn diagnose_config(content: &str) -> Vec<ParseDiagnostic> {
+    diagnose_lines(content, &collect_variables(content))
+}
+
+/// [`diagnose_config`] with the variables already collected (including
+/// any inherited from the file that sourced this one)
+fn diagnose_lines(content: &str, variables: &HashMap<String, String>) -> Vec<ParseDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (line_num, line) in content.lines().enumerate() {
+        let line_num = line_num + 1;
+        let line_trimmed = line.trim();
+        if !is_bind_line(line_trimmed) {
+            continue;
+        }
+
+        let substituted = substitute_variables(line_trimmed, variables);
+
+        // Only the "<modifiers>, <key>" fields after '=' are of interest
+        let Some((_, rest)) = substituted.split_once('=') else {
+            continue;
+        };
+        let mut fields = rest.splitn(3, ',');
+        let modifier_str = fields.next().unwrap_or_default().trim();
+        let key = fields.next().unwrap_or_default().trim();
+
+        for field in [modifier_str, key] {
+            if let Some(variable) = field.split_whitespace().find(|part| part.starts_with('$')) {
+                diagnostics.push(ParseDiagnostic::new(
+                    DiagnosticKind::UndefinedVariable,
+                    line_num,
+                    format!("Undefined variable '{}' was left unexpanded", variable),
+                ));
+            }
+        }
+
+        for part in modifier_str.split(['_', ' ']).map(str::trim) {
+            if !part.is_empty() && !part.starts_with('$') && modifier_from_str(part).is_none() {
+                diagnostics.push(ParseDiagnostic::new(
+                    DiagnosticKind::UnknownModifier,
+                    line_num,
+                    format!("Unknown modifier '{}' was ignored", part),
+                ));
+            }
+        }
+
+        if key.is_empty() {
+            diagnostics.push(ParseDiagnostic::new(
+                DiagnosticKind::MissingKey,
+                line_num,
+                "Bind line has no key",
+            ));
+        }
+    }
+
+    diagnostics
 }
 
 /// Collect variable definitions from config
@@ -123,36 +731,201 @@ pub fn parse_config_file(content: &str, _file_path: &Path) -> Result<Vec<Keybind
 ///
 /// Returns a HashMap mapping variable names to their values
 pub fn collect_variables(contents: &str) -> HashMap<String, String> {
-    let mut variables = HashMap::new();
+    VariableTable::from_config(contents).to_map()
+}
 
-    for line in contents.lines() {
-        let line_trimmed = line.trim();
+/// Substitute variables in a line
+///
+/// Replaces $varName with its value from the variables HashMap. Longer
+/// names are replaced first, so `$mod` never clobbers part of `$modShift`.
+pub fn substitute_variables(line: &str, variables: &HashMap<String, String>) -> String {
+    let mut names: Vec<&String> = variables.keys().collect();
+    names.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+
+    let mut result = line.to_string();
+    for var_name in names {
+        let pattern = format!("${}", var_name);
+        result = result.replace(&pattern, &variables[var_name]);
+    }
+
+    result
+}
+
+/// Variables defined in a config, in definition order
+///
+/// Used in both directions: expanding `$mainMod` while parsing, and
+/// finding the variable for a value when writing a binding back, so that
+/// rewritten lines keep the config's symbolic style.
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::parser::VariableTable;
+///
+/// let variables = VariableTable::from_config("$mainMod = SUPER\n$terminal = kitty\n");
+/// assert_eq!(variables.get("mainMod"), Some("SUPER"));
+/// assert_eq!(variables.name_for("kitty"), Some("terminal"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VariableTable {
+    /// `(name, value)` pairs; a redefined name keeps its first position
+    definitions: Vec<(String, String)>,
+}
+
+impl VariableTable {
+    /// Collects the `$name = value` lines of a config
+    ///
+    /// A variable defined twice takes the later value.
+    pub fn from_config(contents: &str) -> Self {
+        let mut table = Self::default();
+
+        for line in contents.lines() {
+            let line_trimmed = line.trim();
 
-        // Variable definition format: $name = value
-        if line_trimmed.starts_with('$') {
-            if let Some(equals_pos) = line_trimmed.find('=') {
-                let var_name = line_trimmed[1..equals_pos].trim().to_string();
-                let var_value = line_trimmed[equals_pos + 1..].trim().to_string();
-                variables.insert(var_name, var_value);
+            // Variable definition format: $name = value
+            let Some(definition) = line_trimmed.strip_prefix('$') else {
+                continue;
+            };
+            let Some((name, value)) = definition.split_once('=') else {
+                continue;
+            };
+            let (name, value) = (name.trim().to_string(), value.trim().to_string());
+
+            match table.definitions.iter_mut().find(|(n, _)| *n == name) {
+                Some(existing) => existing.1 = value,
+                None => table.definitions.push((name, value)),
             }
         }
+
+        table
+    }
+
+    /// Returns the value of a variable (name without `$`)
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.definitions
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
     }
 
-    variables
+    /// Returns the first variable whose value is exactly `value`
+    pub fn name_for(&self, value: &str) -> Option<&str> {
+        self.definitions
+            .iter()
+            .find(|(_, v)| v == value)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Iterates over `(name, value)` pairs in definition order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.definitions
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Returns whether no variables are defined
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    /// Converts to the map used by [`substitute_variables`]
+    pub fn to_map(&self) -> HashMap<String, String> {
+        self.definitions.iter().cloned().collect()
+    }
 }
 
-/// Substitute variables in a line
+/// Parse a submap section line
 ///
-/// Replaces $varName with its value from the variables HashMap
-pub fn substitute_variables(line: &str, variables: &HashMap<String, String>) -> String {
-    let mut result = line.to_string();
+/// # Returns
+///
+/// * `Some(Some(name))` - `submap = name` starts a submap section
+/// * `Some(None)` - `submap = reset` returns to the global bindings
+/// * `None` - Not a submap line
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::parser::parse_submap_line;
+///
+/// assert_eq!(parse_submap_line("submap = resize"), Some(Some("resize".to_string())));
+/// assert_eq!(parse_submap_line("submap = reset"), Some(None));
+/// assert_eq!(parse_submap_line("bind = SUPER, R, submap, resize"), None);
+/// ```
+pub fn parse_submap_line(line: &str) -> Option<Option<String>> {
+    let (keyword, name) = line.trim().split_once('=')?;
+    if keyword.trim() != "submap" {
+        return None;
+    }
 
-    for (var_name, var_value) in variables {
-        let pattern = format!("${}", var_name);
-        result = result.replace(&pattern, var_value);
+    // Trailing comments are not part of the name
+    let name = name.split('#').next().unwrap_or_default().trim();
+    Some((name != SUBMAP_RESET && !name.is_empty()).then(|| name.to_string()))
+}
+
+/// Finds the input device section each line of a config is in
+///
+/// Both the `device { name = ... }` block and the older `device:name { ... }`
+/// header are recognised. The name may come anywhere in the block, so it
+/// applies to the lines above it too.
+///
+/// # Returns
+///
+/// One entry per line of `content` (as [`str::lines`] splits it): the
+/// device name, or `None` outside device sections and in sections without
+/// a name
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::parser::device_sections;
+///
+/// let config = "device {\n    bind = , F1, exec, obs\n    name = stream-deck\n}\nbind = , F1, exec, foot";
+/// let devices = device_sections(config);
+/// assert_eq!(devices[1].as_deref(), Some("stream-deck"));
+/// assert_eq!(devices[4], None);
+/// ```
+pub fn device_sections(content: &str) -> Vec<Option<String>> {
+    // Device sections seen so far, and which one (if any) each line is in
+    let mut names: Vec<Option<String>> = Vec::new();
+    let mut section_of: Vec<Option<usize>> = Vec::new();
+    // Open sections, with the index of those that are device sections
+    let mut open: Vec<Option<usize>> = Vec::new();
+
+    for line in content.lines() {
+        let code = strip_comment(line).trim();
+
+        if let Some(header) = code
+            .strip_suffix('{')
+            .filter(|header| !header.contains('='))
+        {
+            let header = header.trim();
+            let device = match header.strip_prefix("device:") {
+                Some(name) => Some(Some(name.trim().to_string()).filter(|name| !name.is_empty())),
+                None => (header == "device").then_some(None),
+            };
+            if let Some(name) = device {
+                names.push(name);
+                open.push(Some(names.len() - 1));
+            } else {
+                open.push(None);
+            }
+        }
+
+        // The closing brace still belongs to the section it closes
+        let current = open.iter().flatten().next().copied();
+        section_of.push(current);
+
+        if code == "}" {
+            open.pop();
+        } else if let (Some(index), Some((key, value))) = (current, code.split_once('=')) {
+            // Only the device block's own `name`, not one of a nested section
+            if key.trim() == "name" && open.last() == Some(&Some(index)) {
+                names[index] = Some(value.trim().to_string()).filter(|name| !name.is_empty());
+            }
+        }
     }
 
-    result
+    section_of
+        .into_iter()
+        .map(|section| section.and_then(|index| names[index].clone()))
+        .collect()
 }
 
 /// Parse a single bind line
@@ -160,13 +933,23 @@ pub fn substitute_variables(line: &str, variables: &HashMap<String, String>) ->
 /// Format: bind = MODIFIERS, KEY, DISPATCHER, ARGS
 /// Example: bind = SUPER, K, exec, firefox
 ///
+/// With the `d` flag a description follows the key:
+/// `bindd = SUPER, K, Open browser, exec, firefox`
+///
 /// Returns a Keybinding struct or nom error
 pub fn parse_bind_line(input: &str) -> IResult<&str, Keybinding> {
-    // Parse: <bind_type> = <key_combo>, <dispatcher>, <args>
+    // Parse: <bind_type> = <key_combo>, [<description>,] <dispatcher>, <args>
     let (input, bind_type) = parse_bind_type(input)?;
     let (input, _) = (space0, char('='), space0).parse(input)?;
     let (input, key_combo) = parse_key_combo(input)?;
     let (input, _) = (space0, char(','), space0).parse(input)?;
+    let (input, description) = if bind_type.contains(BindFlag::Description) {
+        let (input, description) = take_until(",")(input)?;
+        let (input, _) = (char(','), space0).parse(input)?;
+        (input, Some(description.trim().to_string()))
+    } else {
+        (input, None)
+    };
     let (input, (dispatcher, args)) = parse_dispatcher(input)?;
 
     Ok((
@@ -176,39 +959,29 @@ pub fn parse_bind_line(input: &str) -> IResult<&str, Keybinding> {
             bind_type,
             dispatcher,
             args,
+            submap: None,
+            source_file: None,
+            description,
+            category: None,
+            device: None,
+            location: None,
         },
     ))
 }
 
-/// Parse bind_type (bind, binde, bindl, bindm, bindr, bindel)
+/// Parse bind_type (`bind` followed by any flags, e.g. bindel, bindtn, bindd)
 ///
-/// Recognizes all six Hyprland binding types and converts them to
-/// the corresponding BindType enum variant. The order matters: `bindel`
-/// must be checked before `binde` to avoid partial matches.
+/// Hyprland accepts the flag letters in any order, so `bindle` and `bindel`
+/// parse to the same BindType.
 ///
 /// # Returns
 ///
-/// The parsed BindType variant, or a nom parsing error if the input
-/// doesn't start with a valid bind type keyword.
+/// The parsed BindType, or a nom parsing error if the input doesn't start
+/// with `bind` or the keyword contains an unknown flag letter.
 pub fn parse_bind_type(input: &str) -> IResult<&str, BindType> {
-    map(
-        alt((
-            tag("bindel"), // Must come before "binde" due to being a longer match
-            tag("binde"),
-            tag("bindl"),
-            tag("bindm"),
-            tag("bindr"),
-            tag("bind"),
-        )),
-        |s: &str| match s {
-            "bind" => BindType::Bind,
-            "binde" => BindType::BindE,
-            "bindl" => BindType::BindL,
-            "bindm" => BindType::BindM,
-            "bindr" => BindType::BindR,
-            "bindel" => BindType::BindEL,
-            _ => unreachable!(),
-        },
+    map_opt(
+        take_while1(|c: char| c.is_ascii_alphabetic()),
+        BindType::from_keyword,
     )
     .parse(input)
 }
@@ -257,19 +1030,25 @@ pub fn parse_modifiers(input: &str) -> Result<Vec<Modifier>, nom::Err<nom::error
     };
 
     for part in parts {
-        let modifier = match part.trim().to_uppercase().as_str() {
-            "SUPER" | "MOD4" | "WIN" => Modifier::Super,
-            "CTRL" | "CONTROL" => Modifier::Ctrl,
-            "SHIFT" => Modifier::Shift,
-            "ALT" | "MOD1" => Modifier::Alt,
-            _ => continue,
-        };
-        modifiers.push(modifier);
+        if let Some(modifier) = modifier_from_str(part) {
+            modifiers.push(modifier);
+        }
     }
 
     Ok(modifiers)
 }
 
+/// Maps a single modifier name (including Hyprland's aliases) to a Modifier
+pub fn modifier_from_str(part: &str) -> Option<Modifier> {
+    match part.trim().to_uppercase().as_str() {
+        "SUPER" | "MOD4" | "WIN" => Some(Modifier::Super),
+        "CTRL" | "CONTROL" => Some(Modifier::Ctrl),
+        "SHIFT" => Some(Modifier::Shift),
+        "ALT" | "MOD1" => Some(Modifier::Alt),
+        _ => None,
+    }
+}
+
 /// Parse dispatcher and arguments
 ///
 /// Format: DISPATCHER, ARGS (args are optional)
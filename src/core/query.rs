@@ -0,0 +1,739 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scripting queries over the parsed bindings for the `query` command
+//!
+//! Queries use a small subset of jq's syntax, enough for a shell script to
+//! pull out exactly the fields it needs without exporting everything:
+//!
+//! - **Paths**: `.` is the input, `.name` a field (`null` when absent),
+//!   `[]` every element, `[2]` one element (negative counts from the end),
+//!   e.g. `.bindings[].key_combo.key`
+//! - **Pipes**: `a | b` feeds every result of `a` into `b`; `a, b`
+//!   outputs the results of both
+//! - **Filters**: `select(cond)` keeps inputs for which `cond` is true
+//!   (anything but `false` and `null`)
+//! - **Comparisons**: `==`, `!=`, `<`, `<=`, `>`, `>=`, combined with `and`
+//!   and `or`; strings, numbers, `true`, `false` and `null` literals
+//! - **Functions**: `length`, `not`, `contains(s)`, `startswith(s)`;
+//!   `contains` and `startswith` are false for a missing field
+//! - **Collecting**: `[ ... ]` gathers results into one array, e.g.
+//!   `[.bindings[] | select(.dispatcher == "exec")] | length`
+//!
+//! The input is built by [`query_model`]: `{"bindings": [...],
+//! "conflicts": [...]}`, each binding in its JSON form plus a `combo`
+//! field holding the combo as displayed (`SUPER+K`).
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{parser::parse_config_file, query::{query_model, Query}};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, Return, exec, kitty\n\
+//!               bind = SUPER, Q, killactive\n";
+//! let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+//! let model = query_model(&bindings, &[]);
+//!
+//! let query: Query = r#".bindings[] | select(.dispatcher == "exec") | .args"#.parse().unwrap();
+//! assert_eq!(query.run(&model).unwrap(), vec![serde_json::json!("kitty")]);
+//! ```
+
+use std::{cmp::Ordering, str::FromStr};
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::core::{conflict::Conflict, Keybinding};
+
+/// Errors from parsing or running a query
+#[derive(Debug, Error, PartialEq)]
+pub enum QueryError {
+    #[error("Query syntax error at column {column}: {message}")]
+    Syntax { column: usize, message: String },
+
+    #[error("Query failed: {0}")]
+    Type(String),
+}
+
+/// A parsed query, ready to run against a model
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    expr: Expr,
+}
+
+impl Query {
+    /// Runs the query against a model
+    ///
+    /// # Arguments
+    /// * `input` - Usually the output of [`query_model`]
+    ///
+    /// # Returns
+    /// Every result, in order
+    ///
+    /// # Errors
+    /// `QueryError::Type` when a step does not fit its input, such as
+    /// iterating over a string
+    pub fn run(&self, input: &Value) -> Result<Vec<Value>, QueryError> {
+        eval(&self.expr, input)
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenise(s)?,
+            position: 0,
+            end: s.chars().count() + 1,
+        };
+        let expr = parser.pipe()?;
+        match parser.peek() {
+            None => Ok(Query { expr }),
+            Some((column, token)) => Err(QueryError::Syntax {
+                column: *column,
+                message: format!("unexpected {}", token.describe()),
+            }),
+        }
+    }
+}
+
+/// Builds the value queries run against
+///
+/// # Arguments
+/// * `bindings` - Parsed bindings, in config order
+/// * `conflicts` - Conflicts among them
+///
+/// # Returns
+/// `{"bindings": [...], "conflicts": [...]}`, with a `combo` display string
+/// added to every binding and conflict
+pub fn query_model(bindings: &[Keybinding], conflicts: &[Conflict]) -> Value {
+    let conflicts: Vec<Value> = conflicts
+        .iter()
+        .map(|conflict| {
+            let mut value = json!(conflict);
+            value["combo"] = json!(conflict.key_combo.to_string());
+            value["conflicting_bindings"] = Value::Array(
+                conflict
+                    .conflicting_bindings
+                    .iter()
+                    .map(binding_value)
+                    .collect(),
+            );
+            value
+        })
+        .collect();
+
+    json!({
+        "bindings": bindings.iter().map(binding_value).collect::<Vec<_>>(),
+        "conflicts": conflicts,
+    })
+}
+
+/// A binding in its JSON form plus its `combo` display string
+fn binding_value(binding: &Keybinding) -> Value {
+    let mut value = json!(binding);
+    value["combo"] = json!(binding.key_combo.to_string());
+    value
+}
+
+/// One step of a path
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    /// `.name`
+    Field(String),
+    /// `[]`
+    Iterate,
+    /// `[n]`
+    Index(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Function {
+    Length,
+    Not,
+    Contains,
+    StartsWith,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Expr {
+    Path(Vec<Segment>),
+    Literal(Value),
+    Pipe(Box<Expr>, Box<Expr>),
+    Comma(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, Comparison, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Select(Box<Expr>),
+    Collect(Box<Expr>),
+    Call(Function, Option<Box<Expr>>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Dot,
+    Field(String),
+    LeftBracket,
+    RightBracket,
+    LeftParen,
+    RightParen,
+    Pipe,
+    Comma,
+    Compare(Comparison),
+    Word(String),
+    Literal(Value),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Dot => "'.'".to_string(),
+            Token::Field(name) => format!("'.{}'", name),
+            Token::LeftBracket => "'['".to_string(),
+            Token::RightBracket => "']'".to_string(),
+            Token::LeftParen => "'('".to_string(),
+            Token::RightParen => "')'".to_string(),
+            Token::Pipe => "'|'".to_string(),
+            Token::Comma => "','".to_string(),
+            Token::Compare(_) => "comparison".to_string(),
+            Token::Word(word) => format!("'{}'", word),
+            Token::Literal(value) => value.to_string(),
+        }
+    }
+}
+
+/// Splits a query into tokens, each with its column (1-based)
+fn tokenise(query: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    while i < chars.len() {
+        let column = i + 1;
+        let c = chars[i];
+        let two: String = chars[i..chars.len().min(i + 2)].iter().collect();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '.' => {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && is_word(chars[end]) {
+                    end += 1;
+                }
+                i = end;
+                if end == start {
+                    Token::Dot
+                } else {
+                    Token::Field(chars[start..end].iter().collect())
+                }
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => {
+                            return Err(QueryError::Syntax {
+                                column,
+                                message: "unterminated string".to_string(),
+                            })
+                        }
+                        Some('"') => break,
+                        Some('\\') => {
+                            i += 1;
+                            match chars.get(i) {
+                                Some('n') => text.push('\n'),
+                                Some('t') => text.push('\t'),
+                                Some(&other) => text.push(other),
+                                None => continue,
+                            }
+                        }
+                        Some(&other) => text.push(other),
+                    }
+                    i += 1;
+                }
+                i += 1;
+                Token::Literal(Value::String(text))
+            }
+            '[' | ']' | '(' | ')' | '|' | ',' => {
+                i += 1;
+                match c {
+                    '[' => Token::LeftBracket,
+                    ']' => Token::RightBracket,
+                    '(' => Token::LeftParen,
+                    ')' => Token::RightParen,
+                    '|' => Token::Pipe,
+                    _ => Token::Comma,
+                }
+            }
+            '=' | '!' | '<' | '>' => {
+                let (comparison, len) = match two.as_str() {
+                    "==" => (Comparison::Equal, 2),
+                    "!=" => (Comparison::NotEqual, 2),
+                    "<=" => (Comparison::LessOrEqual, 2),
+                    ">=" => (Comparison::GreaterOrEqual, 2),
+                    _ if c == '<' => (Comparison::Less, 1),
+                    _ if c == '>' => (Comparison::Greater, 1),
+                    _ => {
+                        return Err(QueryError::Syntax {
+                            column,
+                            message: format!("unknown operator '{}' (use == or !=)", c),
+                        })
+                    }
+                };
+                i += len;
+                Token::Compare(comparison)
+            }
+            c if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(char::is_ascii_digit)) =>
+            {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse::<i64>()
+                    .map(Value::from)
+                    .or_else(|_| text.parse::<f64>().map(Value::from))
+                    .map_err(|_| QueryError::Syntax {
+                        column,
+                        message: format!("invalid number '{}'", text),
+                    })?;
+                Token::Literal(number)
+            }
+            c if is_word(c) => {
+                let start = i;
+                while i < chars.len() && is_word(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                match word.as_str() {
+                    "true" => Token::Literal(Value::Bool(true)),
+                    "false" => Token::Literal(Value::Bool(false)),
+                    "null" => Token::Literal(Value::Null),
+                    _ => Token::Word(word),
+                }
+            }
+            other => {
+                return Err(QueryError::Syntax {
+                    column,
+                    message: format!("unexpected character '{}'", other),
+                })
+            }
+        };
+        tokens.push((column, token));
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser, loosest binding first: pipe, comma, or,
+/// and, comparison, term
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    /// Column just past the query, for errors at its end
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&(usize, Token)> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<(usize, Token)> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn next_is(&self, token: &Token) -> bool {
+        self.peek().is_some_and(|(_, next)| next == token)
+    }
+
+    fn next_is_word(&self, word: &str) -> bool {
+        self.next_is(&Token::Word(word.to_string()))
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), QueryError> {
+        match self.next() {
+            Some((_, next)) if next == token => Ok(()),
+            Some((column, next)) => Err(QueryError::Syntax {
+                column,
+                message: format!("expected {}, found {}", token.describe(), next.describe()),
+            }),
+            None => Err(self.end_of_query(&format!("expected {}", token.describe()))),
+        }
+    }
+
+    fn end_of_query(&self, message: &str) -> QueryError {
+        QueryError::Syntax {
+            column: self.end,
+            message: format!("{} at the end of the query", message),
+        }
+    }
+
+    fn pipe(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.comma()?;
+        while self.next_is(&Token::Pipe) {
+            self.position += 1;
+            expr = Expr::Pipe(Box::new(expr), Box::new(self.comma()?));
+        }
+        Ok(expr)
+    }
+
+    fn comma(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.or()?;
+        while self.next_is(&Token::Comma) {
+            self.position += 1;
+            expr = Expr::Comma(Box::new(expr), Box::new(self.or()?));
+        }
+        Ok(expr)
+    }
+
+    fn or(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.and()?;
+        while self.next_is_word("or") {
+            self.position += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, QueryError> {
+        let mut expr = self.comparison()?;
+        while self.next_is_word("and") {
+            self.position += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.comparison()?));
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, QueryError> {
+        let left = self.term()?;
+        if let Some((_, Token::Compare(comparison))) = self.peek() {
+            let comparison = *comparison;
+            self.position += 1;
+            let right = self.term()?;
+            return Ok(Expr::Compare(Box::new(left), comparison, Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, QueryError> {
+        let (column, token) = self
+            .next()
+            .ok_or_else(|| self.end_of_query("expected a path, value or function"))?;
+
+        match token {
+            Token::Dot => {
+                let mut segments = Vec::new();
+                self.segments(&mut segments)?;
+                Ok(Expr::Path(segments))
+            }
+            Token::Field(name) => {
+                let mut segments = vec![Segment::Field(name)];
+                self.segments(&mut segments)?;
+                Ok(Expr::Path(segments))
+            }
+            Token::Literal(value) => Ok(Expr::Literal(value)),
+            Token::LeftParen => {
+                let expr = self.pipe()?;
+                self.expect(Token::RightParen)?;
+                Ok(expr)
+            }
+            Token::LeftBracket => {
+                let expr = self.pipe()?;
+                self.expect(Token::RightBracket)?;
+                Ok(Expr::Collect(Box::new(expr)))
+            }
+            Token::Word(word) => {
+                let function = match word.as_str() {
+                    "select" => {
+                        self.expect(Token::LeftParen)?;
+                        let condition = self.pipe()?;
+                        self.expect(Token::RightParen)?;
+                        return Ok(Expr::Select(Box::new(condition)));
+                    }
+                    "length" => return Ok(Expr::Call(Function::Length, None)),
+                    "not" => return Ok(Expr::Call(Function::Not, None)),
+                    "contains" => Function::Contains,
+                    "startswith" => Function::StartsWith,
+                    _ => {
+                        return Err(QueryError::Syntax {
+                            column,
+                            message: format!("unknown function '{}'", word),
+                        })
+                    }
+                };
+                self.expect(Token::LeftParen)?;
+                let argument = self.pipe()?;
+                self.expect(Token::RightParen)?;
+                Ok(Expr::Call(function, Some(Box::new(argument))))
+            }
+            other => Err(QueryError::Syntax {
+                column,
+                message: format!("unexpected {}", other.describe()),
+            }),
+        }
+    }
+
+    /// Reads the rest of a path: `.name`, `[]` and `[n]` steps
+    fn segments(&mut self, segments: &mut Vec<Segment>) -> Result<(), QueryError> {
+        loop {
+            match self.peek() {
+                Some((_, Token::Field(name))) => {
+                    segments.push(Segment::Field(name.clone()));
+                    self.position += 1;
+                }
+                Some((_, Token::LeftBracket)) => {
+                    self.position += 1;
+                    match self.next() {
+                        Some((_, Token::RightBracket)) => segments.push(Segment::Iterate),
+                        Some((_, Token::Literal(Value::Number(n)))) if n.is_i64() => {
+                            segments.push(Segment::Index(n.as_i64().unwrap_or_default()));
+                            self.expect(Token::RightBracket)?;
+                        }
+                        Some((column, token)) => {
+                            return Err(QueryError::Syntax {
+                                column,
+                                message: format!(
+                                    "expected ']' or an index, found {}",
+                                    token.describe()
+                                ),
+                            })
+                        }
+                        None => return Err(self.end_of_query("expected ']'")),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+}
+
+/// jq's notion of truth: everything but `false` and `null`
+fn truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn eval(expr: &Expr, input: &Value) -> Result<Vec<Value>, QueryError> {
+    match expr {
+        Expr::Path(segments) => {
+            let mut values = vec![input.clone()];
+            for segment in segments {
+                let mut next = Vec::new();
+                for value in &values {
+                    step(segment, value, &mut next)?;
+                }
+                values = next;
+            }
+            Ok(values)
+        }
+        Expr::Literal(value) => Ok(vec![value.clone()]),
+        Expr::Pipe(left, right) => {
+            let mut results = Vec::new();
+            for value in eval(left, input)? {
+                results.extend(eval(right, &value)?);
+            }
+            Ok(results)
+        }
+        Expr::Comma(left, right) => {
+            let mut results = eval(left, input)?;
+            results.extend(eval(right, input)?);
+            Ok(results)
+        }
+        Expr::Compare(left, comparison, right) => {
+            let rights = eval(right, input)?;
+            let mut results = Vec::new();
+            for left in eval(left, input)? {
+                for right in &rights {
+                    results.push(Value::Bool(compare(&left, *comparison, right)?));
+                }
+            }
+            Ok(results)
+        }
+        Expr::And(left, right) | Expr::Or(left, right) => {
+            let is_and = matches!(expr, Expr::And(..));
+            let mut results = Vec::new();
+            for left in eval(left, input)? {
+                // Short-circuits like jq: `false and x` never evaluates x
+                if truthy(&left) != is_and {
+                    results.push(Value::Bool(!is_and));
+                    continue;
+                }
+                for right in eval(right, input)? {
+                    results.push(Value::Bool(truthy(&right)));
+                }
+            }
+            Ok(results)
+        }
+        Expr::Select(condition) => {
+            let keep = eval(condition, input)?.iter().any(truthy);
+            Ok(if keep {
+                vec![input.clone()]
+            } else {
+                Vec::new()
+            })
+        }
+        Expr::Collect(inner) => Ok(vec![Value::Array(eval(inner, input)?)]),
+        Expr::Call(function, argument) => {
+            let arguments = match argument {
+                Some(argument) => eval(argument, input)?,
+                None => vec![Value::Null],
+            };
+            arguments
+                .iter()
+                .map(|argument| call(*function, input, argument))
+                .collect()
+        }
+    }
+}
+
+/// Applies one path step to a value, adding its results to `out`
+fn step(segment: &Segment, value: &Value, out: &mut Vec<Value>) -> Result<(), QueryError> {
+    match (segment, value) {
+        (Segment::Field(_) | Segment::Index(_), Value::Null) => out.push(Value::Null),
+        (Segment::Field(name), Value::Object(map)) => {
+            out.push(map.get(name).cloned().unwrap_or(Value::Null))
+        }
+        (Segment::Index(index), Value::Array(items)) => {
+            let position = if *index < 0 {
+                items.len().checked_sub(index.unsigned_abs() as usize)
+            } else {
+                Some(*index as usize)
+            };
+            out.push(
+                position
+                    .and_then(|position| items.get(position))
+                    .cloned()
+                    .unwrap_or(Value::Null),
+            );
+        }
+        (Segment::Iterate, Value::Array(items)) => out.extend(items.iter().cloned()),
+        (Segment::Iterate, Value::Object(map)) => out.extend(map.values().cloned()),
+        (Segment::Field(name), other) => {
+            return Err(QueryError::Type(format!(
+                "cannot read field '{}' of a {}",
+                name,
+                type_name(other)
+            )))
+        }
+        (Segment::Index(_), other) => {
+            return Err(QueryError::Type(format!(
+                "cannot index a {}",
+                type_name(other)
+            )))
+        }
+        (Segment::Iterate, other) => {
+            return Err(QueryError::Type(format!(
+                "cannot iterate over a {}",
+                type_name(other)
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn compare(left: &Value, comparison: Comparison, right: &Value) -> Result<bool, QueryError> {
+    let ordering = || match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .zip(b.as_f64())
+            .and_then(|(a, b)| a.partial_cmp(&b))
+            .ok_or_else(|| QueryError::Type("cannot compare these numbers".to_string())),
+        (Value::String(a), Value::String(b)) => Ok(a.cmp(b)),
+        _ => Err(QueryError::Type(format!(
+            "cannot order a {} against a {}",
+            type_name(left),
+            type_name(right)
+        ))),
+    };
+
+    Ok(match comparison {
+        Comparison::Equal => values_equal(left, right),
+        Comparison::NotEqual => !values_equal(left, right),
+        Comparison::Less => ordering()? == Ordering::Less,
+        Comparison::LessOrEqual => ordering()? != Ordering::Greater,
+        Comparison::Greater => ordering()? == Ordering::Greater,
+        Comparison::GreaterOrEqual => ordering()? != Ordering::Less,
+    })
+}
+
+/// Equality with `1 == 1.0`, as in jq
+fn values_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => left == right,
+    }
+}
+
+fn call(function: Function, input: &Value, argument: &Value) -> Result<Value, QueryError> {
+    match (function, input, argument) {
+        (Function::Length, Value::Null, _) => Ok(json!(0)),
+        (Function::Length, Value::String(s), _) => Ok(json!(s.chars().count())),
+        (Function::Length, Value::Array(items), _) => Ok(json!(items.len())),
+        (Function::Length, Value::Object(map), _) => Ok(json!(map.len())),
+        (Function::Not, value, _) => Ok(Value::Bool(!truthy(value))),
+        // A missing field matches nothing rather than failing the query
+        (Function::Contains | Function::StartsWith, Value::Null, _) => Ok(Value::Bool(false)),
+        (Function::Contains, Value::String(s), Value::String(part)) => {
+            Ok(Value::Bool(s.contains(part.as_str())))
+        }
+        (Function::Contains, Value::Array(items), item) => Ok(Value::Bool(
+            items.iter().any(|value| values_equal(value, item)),
+        )),
+        (Function::StartsWith, Value::String(s), Value::String(prefix)) => {
+            Ok(Value::Bool(s.starts_with(prefix.as_str())))
+        }
+        (function, input, _) => Err(QueryError::Type(format!(
+            "{} does not apply to a {}",
+            match function {
+                Function::Length => "length",
+                Function::Not => "not",
+                Function::Contains => "contains",
+                Function::StartsWith => "startswith",
+            },
+            type_name(input)
+        ))),
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key combos reserved by Hyprland, the system or common desktop tools
+//!
+//! Some combos are taken before a config's own bindings get a say, or are
+//! what users of common tools expect them to do:
+//!
+//! - **Hyprland**: `CTRL ALT, F1`–`F12` switch virtual terminals
+//! - **XKB**: `CTRL ALT, BackSpace` ends the session when the
+//!   `terminate:ctrl_alt_bksp` option is set
+//! - **Desktop tools**: logout menus, screen lockers, screenshot tools and
+//!   the media keys, by wide convention
+//!
+//! [`RESERVED_COMBOS`] is a curated list of these. A binding on one of them
+//! is flagged unless it runs a tool that provides what the combo is
+//! reserved for (`SUPER, L` running `hyprlock` is the lock shortcut, not a
+//! clash with it). The check is opt-in on [`ConflictDetector`]; `check`
+//! and the GUI enable it unless told not to.
+//!
+//! [`ConflictDetector`]: crate::core::ConflictDetector
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{parser::parse_config_file, reserved::reserved_shadows};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, L, exec, hyprlock\n\
+//!               bind = CTRL ALT, F2, exec, kitty\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//!
+//! let shadows = reserved_shadows(&bindings);
+//! assert_eq!(shadows.len(), 1);
+//! assert_eq!(shadows[0].reserved.owner, "Hyprland");
+//! ```
+
+use std::fmt;
+
+use crate::core::types::{KeyCombo, Keybinding, Modifier};
+
+/// A combo, or family of combos, something other than the config relies on
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReservedCombo {
+    /// Modifiers of every combo in the entry
+    pub modifiers: &'static [Modifier],
+    /// Keys, each reserved with [`modifiers`](Self::modifiers)
+    pub keys: &'static [&'static str],
+    /// Who reserves the combos, e.g. `Hyprland` or `hyprlock`
+    pub owner: &'static str,
+    /// What the combos do, completing "CTRL+ALT+F1 ..."
+    pub purpose: &'static str,
+    /// Commands providing the purpose; a binding running one is not flagged
+    pub provided_by: &'static [&'static str],
+}
+
+const VOLUME_TOOLS: &[&str] = &["wpctl", "pactl", "pamixer", "amixer", "swayosd", "volume"];
+
+/// The curated reserved combos
+pub const RESERVED_COMBOS: &[ReservedCombo] = &[
+    ReservedCombo {
+        modifiers: &[Modifier::Ctrl, Modifier::Alt],
+        keys: &[
+            "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12",
+        ],
+        owner: "Hyprland",
+        purpose: "switches to a virtual terminal, which Hyprland handles itself",
+        provided_by: &[],
+    },
+    ReservedCombo {
+        modifiers: &[Modifier::Ctrl, Modifier::Alt],
+        keys: &["BackSpace"],
+        owner: "XKB",
+        purpose: "ends the session when the terminate:ctrl_alt_bksp option is set",
+        provided_by: &[],
+    },
+    ReservedCombo {
+        modifiers: &[Modifier::Ctrl, Modifier::Alt],
+        keys: &["Delete"],
+        owner: "wlogout",
+        purpose: "opens the logout menu by convention",
+        provided_by: &["wlogout", "wleave", "nwg-bar", "exit"],
+    },
+    ReservedCombo {
+        modifiers: &[Modifier::Super],
+        keys: &["L"],
+        owner: "hyprlock",
+        purpose: "locks the screen by convention",
+        provided_by: &["hyprlock", "swaylock", "gtklock", "lock-session"],
+    },
+    ReservedCombo {
+        modifiers: &[],
+        keys: &["Print"],
+        owner: "screenshot tools",
+        purpose: "takes a screenshot by convention",
+        provided_by: &[
+            "grim",
+            "hyprshot",
+            "grimblast",
+            "flameshot",
+            "satty",
+            "screenshot",
+        ],
+    },
+    ReservedCombo {
+        modifiers: &[],
+        keys: &[
+            "XF86AudioRaiseVolume",
+            "XF86AudioLowerVolume",
+            "XF86AudioMute",
+            "XF86AudioMicMute",
+        ],
+        owner: "media keys",
+        purpose: "changes the volume, as labelled on the keyboard",
+        provided_by: VOLUME_TOOLS,
+    },
+    ReservedCombo {
+        modifiers: &[],
+        keys: &[
+            "XF86AudioPlay",
+            "XF86AudioPause",
+            "XF86AudioNext",
+            "XF86AudioPrev",
+        ],
+        owner: "media keys",
+        purpose: "controls playback, as labelled on the keyboard",
+        provided_by: &["playerctl", "mpc"],
+    },
+    ReservedCombo {
+        modifiers: &[],
+        keys: &["XF86MonBrightnessUp", "XF86MonBrightnessDown"],
+        owner: "media keys",
+        purpose: "changes the screen brightness, as labelled on the keyboard",
+        provided_by: &["brightnessctl", "light", "swayosd", "brightness"],
+    },
+];
+
+impl ReservedCombo {
+    /// Returns whether the combo is one of this entry's
+    pub fn covers(&self, key_combo: &KeyCombo) -> bool {
+        let combo = key_combo.normalised();
+        self.keys
+            .iter()
+            .any(|key| KeyCombo::new(self.modifiers.to_vec(), key) == combo)
+    }
+
+    /// Returns whether the binding runs a tool providing the purpose
+    pub fn is_provided_by(&self, binding: &Keybinding) -> bool {
+        let action = format!(
+            "{} {}",
+            binding.dispatcher,
+            binding.args.as_deref().unwrap_or("")
+        )
+        .to_lowercase();
+        self.provided_by.iter().any(|tool| action.contains(tool))
+    }
+}
+
+/// Returns the reserved entry covering a combo, if any
+pub fn reserved_combo(key_combo: &KeyCombo) -> Option<&'static ReservedCombo> {
+    RESERVED_COMBOS
+        .iter()
+        .find(|reserved| reserved.covers(key_combo))
+}
+
+/// A binding on a reserved combo that does something else
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReservedShadow {
+    /// The binding
+    pub binding: Keybinding,
+    /// What the combo is reserved for
+    pub reserved: &'static ReservedCombo,
+}
+
+impl fmt::Display for ReservedShadow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is reserved by {}: it {}",
+            self.binding.key_combo, self.reserved.owner, self.reserved.purpose
+        )
+    }
+}
+
+/// Checks one binding against the reserved combos
+///
+/// # Returns
+/// * `Some(ReservedShadow)` - The binding is on a reserved combo and does
+///   something else
+/// * `None` - The combo is not reserved, or the binding provides its purpose
+pub fn reserved_shadow(binding: &Keybinding) -> Option<ReservedShadow> {
+    let reserved = reserved_combo(&binding.key_combo)?;
+    (!reserved.is_provided_by(binding)).then(|| ReservedShadow {
+        binding: binding.clone(),
+        reserved,
+    })
+}
+
+/// Finds bindings on reserved combos that do something else
+///
+/// # Arguments
+/// * `bindings` - All bindings of the config
+///
+/// # Returns
+/// One entry per flagged binding, in config order
+pub fn reserved_shadows(bindings: &[Keybinding]) -> Vec<ReservedShadow> {
+    bindings.iter().filter_map(reserved_shadow).collect()
+}
@@ -13,6 +13,36 @@
 // limitations under the License.
 
 //! Bubblewrap sandbox helpers for exec bindings.
+//!
+//! Besides wrapping exec commands for the config, this module can run a
+//! command once inside the same sandbox to preview its output: no shell,
+//! no stdin, no network, a hard timeout and size-capped captured output.
+
+use std::{
+    io::Read,
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Default time limit for a test run
+pub const TEST_RUN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Captured output is truncated beyond this many bytes per stream
+const MAX_CAPTURED_BYTES: u64 = 64 * 1024;
+
+/// Output of a command run with [`run_captured`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CapturedRun {
+    /// Exit code (`None` if killed by a signal or the timeout)
+    pub exit_code: Option<i32>,
+    /// Standard output (lossy UTF-8, capped)
+    pub stdout: String,
+    /// Standard error (lossy UTF-8, capped)
+    pub stderr: String,
+    /// Whether the command was killed for exceeding the timeout
+    pub timed_out: bool,
+}
 
 const BWRAP_PREFIX: &[&str] = &[
     "bwrap",
@@ -61,3 +91,108 @@ pub fn unwrap_command(command_line: &str) -> Option<String> {
 pub fn is_wrapped(command_line: &str) -> bool {
     unwrap_command(command_line).is_some()
 }
+
+/// Builds the argument vector for running a command inside the sandbox
+///
+/// Arguments are split on whitespace exactly like [`wrap_command`]; no shell
+/// is involved, so quotes and metacharacters are passed through literally.
+pub fn sandbox_argv(command_line: &str) -> Result<Vec<String>, String> {
+    let trimmed = command_line.trim();
+    if trimmed.is_empty() {
+        return Err("There is no command to test".to_string());
+    }
+
+    Ok(BWRAP_PREFIX
+        .iter()
+        .copied()
+        .chain(trimmed.split_whitespace())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Runs a command once inside the Bubblewrap sandbox and captures its output
+///
+/// # Arguments
+///
+/// * `command_line` - Exec arguments as written in the binding
+/// * `timeout` - The command is killed once this has elapsed
+///
+/// # Errors
+///
+/// Returns an error if the command is empty or Bubblewrap cannot be started.
+pub fn test_run(command_line: &str, timeout: Duration) -> Result<CapturedRun, String> {
+    run_captured(&sandbox_argv(command_line)?, timeout)
+}
+
+/// Runs a program without a shell, capturing stdout and stderr
+///
+/// Stdin is closed so interactive programs cannot block, and the child is
+/// killed if it runs longer than `timeout`.
+///
+/// # Arguments
+///
+/// * `argv` - Program followed by its arguments
+/// * `timeout` - Maximum run time
+pub fn run_captured(argv: &[String], timeout: Duration) -> Result<CapturedRun, String> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| "There is no command to test".to_string())?;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+
+    // Drain both pipes on their own threads so a chatty child cannot fill
+    // one pipe and stall while we wait for it to exit
+    let stdout = child.stdout.take().map(capture_stream);
+    let stderr = child.stderr.take().map(capture_stream);
+
+    let (exit_code, timed_out) = wait_with_timeout(&mut child, timeout)?;
+
+    let collect = |handle: Option<thread::JoinHandle<String>>| {
+        handle
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default()
+    };
+
+    Ok(CapturedRun {
+        exit_code,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        timed_out,
+    })
+}
+
+/// Reads a stream to the end, keeping at most `MAX_CAPTURED_BYTES`
+fn capture_stream<R: Read + Send + 'static>(stream: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let mut limited = stream.take(MAX_CAPTURED_BYTES);
+        let _ = limited.read_to_end(&mut buffer);
+        // Keep draining so the child never blocks on a full pipe
+        let _ = std::io::copy(&mut limited.into_inner(), &mut std::io::sink());
+        String::from_utf8_lossy(&buffer).into_owned()
+    })
+}
+
+/// Waits for the child, killing it once the timeout has elapsed
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<(Option<i32>, bool), String> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok((status.code(), false)),
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Ok((None, true));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("Failed to wait for command: {}", e)),
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Binding scopes for switching between setups
+//!
+//! A laptop used on its own and the same laptop docked with an external
+//! keyboard often want different bindings: brightness keys only exist on
+//! one, a `SUPER, F1` fallback only matters on the other. Scopes name such
+//! setups. Bindings outside any scope are **global** and active in every
+//! scope; a scoped binding is only active in its own.
+//!
+//! Scopes are comment sections, so Hyprland ignores them and the config
+//! stays valid. Two forms are read:
+//!
+//! ```text
+//! # scope: laptop
+//! bind = , XF86MonBrightnessUp, exec, brightnessctl s +5%
+//! # scope: global
+//!
+//! # hyprlang if EXTERNAL_KB
+//! bind = SUPER, F1, exec, pavucontrol
+//! # hyprlang endif
+//! ```
+//!
+//! - **Tags**: `# scope: name` starts a section, `# scope: global` (or
+//!   `# scope: end`) ends it
+//! - **Conditionals**: a hyprlang `# hyprlang if VAR` block is a scope named
+//!   after its condition (`EXTERNAL_KB`, or `!EXTERNAL_KB` when negated),
+//!   which Hyprland itself also honours; nested blocks take the innermost
+//!
+//! Sections are per file: a `source`d file starts out global.
+//!
+//! Scopes are looked up by where a binding is declared
+//! ([`Keybinding::location`]), so bindings without a location are global.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{parser::parse_config_file, scope::ScopeIndex};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, Return, exec, kitty\n\
+//!               ## scope: laptop\n\
+//!               bind = , XF86MonBrightnessUp, exec, brightnessctl s +5%\n";
+//! let path = Path::new("hyprland.conf");
+//! let bindings = parse_config_file(config, path).unwrap();
+//! let scopes = ScopeIndex::from_content(config, path);
+//!
+//! assert_eq!(scopes.scope_of(&bindings[0]), None);
+//! assert_eq!(scopes.scope_of(&bindings[1]), Some("laptop"));
+//! assert_eq!(scopes.active_in(&bindings, "desk").len(), 1);
+//! ```
+
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::core::types::{Keybinding, SourceLocation};
+
+/// Scope of bindings outside any scope section
+pub const GLOBAL_SCOPE: &str = "global";
+
+/// Tag that starts (or, with `global`/`end`, ends) a scope section
+const SCOPE_TAG: &str = "scope:";
+
+/// Keyword of hyprlang's conditional comments
+const HYPRLANG_DIRECTIVE: &str = "hyprlang";
+
+/// A comment line that changes the scope of the lines below it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScopeDirective {
+    /// `# scope: name`: following lines are in the named scope
+    Tag(Option<String>),
+    /// `# hyprlang if CONDITION`: a conditional block starts
+    If(String),
+    /// `# hyprlang endif`: the innermost conditional block ends
+    EndIf,
+}
+
+/// Reads a scope directive from a config line
+///
+/// # Returns
+///
+/// * `Some(directive)` - The line starts, ends or switches a scope section
+///   (`Tag(None)`: back to global)
+/// * `None` - Any other line
+///
+/// # Example
+/// ```
+/// use hypr_keybind_manager::core::scope::{parse_scope_directive, ScopeDirective};
+///
+/// assert_eq!(parse_scope_directive("# scope: laptop"),
+///            Some(ScopeDirective::Tag(Some("laptop".to_string()))));
+/// assert_eq!(parse_scope_directive("#scope: global"), Some(ScopeDirective::Tag(None)));
+/// assert_eq!(parse_scope_directive("# hyprlang endif"), Some(ScopeDirective::EndIf));
+/// assert_eq!(parse_scope_directive("# the laptop scope is below"), None);
+/// ```
+pub fn parse_scope_directive(line: &str) -> Option<ScopeDirective> {
+    let comment = line.trim().strip_prefix('#')?.trim();
+
+    if let Some(name) = comment.strip_prefix(SCOPE_TAG) {
+        let name = name.trim();
+        let global = name.is_empty() || name == GLOBAL_SCOPE || name == "end";
+        return Some(ScopeDirective::Tag((!global).then(|| name.to_string())));
+    }
+
+    let directive = comment.strip_prefix(HYPRLANG_DIRECTIVE)?;
+    let mut words = directive.split_whitespace();
+    match (words.next(), words.next()) {
+        (Some("if"), Some(condition)) => Some(ScopeDirective::If(condition.to_string())),
+        (Some("endif"), None) => Some(ScopeDirective::EndIf),
+        _ => None,
+    }
+}
+
+/// The scope of every scoped line of one or more config files
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScopeIndex {
+    /// Scope of each line inside a scope section (global lines are absent)
+    scopes: HashMap<SourceLocation, String>,
+}
+
+impl ScopeIndex {
+    /// Indexes the scope sections of one file
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The file's content
+    /// * `file` - Its path, as bindings read from it record it
+    pub fn from_content(content: &str, file: &Path) -> Self {
+        let mut index = Self::default();
+        index.add_file(content, file);
+        index
+    }
+
+    /// Indexes every file the bindings were read from
+    ///
+    /// Files that cannot be read are left out, so their bindings are global.
+    pub fn from_bindings(bindings: &[Keybinding]) -> Self {
+        let files: HashSet<&PathBuf> = bindings
+            .iter()
+            .filter_map(|binding| binding.location.as_ref())
+            .map(|location| &location.file)
+            .collect();
+
+        let mut index = Self::default();
+        for file in files {
+            if let Ok(content) = fs::read_to_string(file) {
+                index.add_file(&content, file);
+            }
+        }
+        index
+    }
+
+    fn add_file(&mut self, content: &str, file: &Path) {
+        let mut tag: Option<String> = None;
+        let mut conditions: Vec<String> = Vec::new();
+
+        for (line_num, line) in content.lines().enumerate() {
+            match parse_scope_directive(line) {
+                Some(ScopeDirective::Tag(name)) => tag = name,
+                Some(ScopeDirective::If(condition)) => conditions.push(condition),
+                Some(ScopeDirective::EndIf) => {
+                    conditions.pop();
+                }
+                None => {
+                    if let Some(scope) = conditions.last().or(tag.as_ref()) {
+                        let location = SourceLocation {
+                            file: file.to_path_buf(),
+                            line: line_num + 1,
+                        };
+                        self.scopes.insert(location, scope.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the scope a binding is declared in (`None`: global)
+    pub fn scope_of(&self, binding: &Keybinding) -> Option<&str> {
+        binding
+            .location
+            .as_ref()
+            .and_then(|location| self.scopes.get(location))
+            .map(String::as_str)
+    }
+
+    /// Returns every scope used, sorted by name (global not included)
+    pub fn scopes(&self) -> Vec<String> {
+        let names: BTreeSet<&String> = self.scopes.values().collect();
+        names.into_iter().cloned().collect()
+    }
+
+    /// Returns whether no line is in a scope section
+    pub fn is_empty(&self) -> bool {
+        self.scopes.is_empty()
+    }
+
+    /// Returns whether a binding is active in a scope
+    ///
+    /// Global bindings are active in every scope.
+    pub fn is_active(&self, binding: &Keybinding, scope: &str) -> bool {
+        self.scope_of(binding).is_none_or(|own| own == scope)
+    }
+
+    /// Returns the bindings active in a scope, in their original order
+    pub fn active_in(&self, bindings: &[Keybinding], scope: &str) -> Vec<Keybinding> {
+        bindings
+            .iter()
+            .filter(|binding| self.is_active(binding, scope))
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Statistics about a set of bindings, for `stats` and the GUI's
+//! Statistics window
+//!
+//! [`BindingStats::collect`] counts the bindings by modifier combination,
+//! dispatcher and submap, measures how much of each common modifier layer
+//! is still free, and lists the bindings with the longest arguments (the
+//! ones most worth moving into a script).
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{parser::parse_config_file, stats::BindingStats};
+//! use std::path::Path;
+//!
+//! let config = "bind = SUPER, Return, exec, kitty\n\
+//!               bind = SUPER, Q, killactive\n\
+//!               bind = SUPER SHIFT, Q, exit\n";
+//! let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+//! let stats = BindingStats::collect(&bindings);
+//!
+//! assert_eq!(stats.total, 3);
+//! assert_eq!(stats.by_modifiers[0].name, "SUPER");
+//! assert_eq!(stats.by_modifiers[0].count, 2);
+//! ```
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::core::{
+    key_suggestions::{KeySuggestionEngine, CANDIDATE_KEYS},
+    types::{Keybinding, Modifier},
+};
+
+/// Modifier layers whose free space is measured, most used first
+pub const MODIFIER_LAYERS: [&[Modifier]; 7] = [
+    &[Modifier::Super],
+    &[Modifier::Super, Modifier::Shift],
+    &[Modifier::Super, Modifier::Ctrl],
+    &[Modifier::Super, Modifier::Alt],
+    &[Modifier::Super, Modifier::Ctrl, Modifier::Shift],
+    &[Modifier::Super, Modifier::Alt, Modifier::Shift],
+    &[Modifier::Ctrl, Modifier::Alt],
+];
+
+/// Number of bindings with the longest arguments that are listed
+pub const LONGEST_ARGS: usize = 5;
+
+/// Name shown for bindings without modifiers
+pub const NO_MODIFIERS: &str = "(none)";
+
+/// Name shown for bindings outside any submap
+pub const GLOBAL_SUBMAP: &str = "(global)";
+
+/// How many bindings share one value, e.g. a dispatcher
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Tally {
+    /// The shared value, e.g. `exec` or `SHIFT+SUPER`
+    pub name: String,
+    /// Number of bindings with it
+    pub count: usize,
+}
+
+/// How much of a modifier layer is in use
+///
+/// Only the keys [`KeySuggestionEngine`] offers (letters, digits and
+/// function keys) are counted, in the global scope.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ModifierSpace {
+    /// The modifiers, e.g. `SHIFT+SUPER`
+    pub modifiers: String,
+    /// Keys bound with exactly these modifiers
+    pub used: usize,
+    /// Keys still free
+    pub free: usize,
+}
+
+/// Everything `stats` reports about a set of bindings
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BindingStats {
+    /// Number of bindings
+    pub total: usize,
+    /// Bindings with a `bindd` description
+    pub described: usize,
+    /// Bindings per modifier combination, most first
+    pub by_modifiers: Vec<Tally>,
+    /// Bindings per dispatcher, most first
+    pub by_dispatcher: Vec<Tally>,
+    /// Bindings per submap, most first
+    pub by_submap: Vec<Tally>,
+    /// Free keys in each of [`MODIFIER_LAYERS`]
+    pub modifier_space: Vec<ModifierSpace>,
+    /// Bindings with the longest arguments, longest first
+    pub longest_args: Vec<Keybinding>,
+}
+
+impl BindingStats {
+    /// Computes the statistics of a set of bindings
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - Bindings to describe
+    ///
+    /// # Returns
+    ///
+    /// The statistics; ties in the tallies are ordered by name
+    pub fn collect(bindings: &[Keybinding]) -> Self {
+        let by_modifiers = tally(
+            bindings
+                .iter()
+                .map(|binding| modifier_name(&binding.key_combo.canonical_modifiers())),
+        );
+        let by_dispatcher = tally(
+            bindings
+                .iter()
+                .map(|binding| binding.dispatcher.trim().to_lowercase()),
+        );
+        let by_submap = tally(bindings.iter().map(|binding| {
+            binding
+                .submap
+                .clone()
+                .unwrap_or_else(|| GLOBAL_SUBMAP.to_string())
+        }));
+
+        let engine = KeySuggestionEngine::new(bindings);
+        let modifier_space = MODIFIER_LAYERS
+            .iter()
+            .map(|modifiers| {
+                let free = engine.free_keys(modifiers, None).count();
+                let mut canonical = modifiers.to_vec();
+                canonical.sort();
+                ModifierSpace {
+                    modifiers: modifier_name(&canonical),
+                    used: CANDIDATE_KEYS.len() - free,
+                    free,
+                }
+            })
+            .collect();
+
+        let mut longest_args: Vec<&Keybinding> = bindings
+            .iter()
+            .filter(|binding| binding.args.as_deref().is_some_and(|args| !args.is_empty()))
+            .collect();
+        // Stable, so equally long arguments keep their config order
+        longest_args.sort_by_key(|binding| {
+            std::cmp::Reverse(
+                binding
+                    .args
+                    .as_deref()
+                    .map_or(0, |args| args.chars().count()),
+            )
+        });
+
+        BindingStats {
+            total: bindings.len(),
+            described: bindings
+                .iter()
+                .filter(|binding| binding.description.is_some())
+                .count(),
+            by_modifiers,
+            by_dispatcher,
+            by_submap,
+            modifier_space,
+            longest_args: longest_args
+                .into_iter()
+                .take(LONGEST_ARGS)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// Modifiers joined with `+`, as in a combo, or [`NO_MODIFIERS`]
+fn modifier_name(modifiers: &[Modifier]) -> String {
+    if modifiers.is_empty() {
+        return NO_MODIFIERS.to_string();
+    }
+    modifiers
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+/// Counts equal values, most frequent first and then by name
+fn tally(values: impl Iterator<Item = String>) -> Vec<Tally> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    let mut tallies: Vec<Tally> = counts
+        .into_iter()
+        .map(|(name, count)| Tally { name, count })
+        .collect();
+    tallies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    tallies
+}
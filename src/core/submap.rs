@@ -0,0 +1,108 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Submap escape checks
+//!
+//! A submap only lets go of the keyboard through a binding that resets it
+//! (`bind = , escape, submap, reset`). A `catchall` binding handles every
+//! key the submap does not bind, typically to reset on any other key.
+//! A submap with neither traps the user until Hyprland is reloaded.
+//!
+//! `catchall` only works inside submaps; Hyprland ignores it elsewhere.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::{parser::parse_config_file, submap::submap_warnings};
+//! use std::path::Path;
+//!
+//! let config = "submap = resize\nbinde = , right, resizeactive, 10 0\nsubmap = reset\n";
+//! let bindings = parse_config_file(config, Path::new("")).unwrap();
+//!
+//! let warnings = submap_warnings(&bindings);
+//! assert_eq!(warnings.len(), 1);
+//! assert_eq!(warnings[0].submap(), Some("resize"));
+//! ```
+
+use std::fmt;
+
+use crate::core::types::{submap_names, KeyCombo, Keybinding};
+
+/// A problem with how a submap is left, or where a catchall is bound
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmapWarning {
+    /// The submap has neither a catchall nor a reset binding
+    NoEscape { submap: String },
+    /// A catchall binding outside any submap, which never fires
+    CatchallOutsideSubmap { key_combo: KeyCombo },
+}
+
+impl SubmapWarning {
+    /// The submap the warning is about (`None`: global bindings)
+    pub fn submap(&self) -> Option<&str> {
+        match self {
+            SubmapWarning::NoEscape { submap } => Some(submap),
+            SubmapWarning::CatchallOutsideSubmap { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for SubmapWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmapWarning::NoEscape { submap } => write!(
+                f,
+                "submap '{}' has no catchall and no 'submap, reset' binding, so it cannot be left",
+                submap
+            ),
+            SubmapWarning::CatchallOutsideSubmap { key_combo } => write!(
+                f,
+                "{} is outside any submap; catchall only works inside submaps",
+                key_combo
+            ),
+        }
+    }
+}
+
+/// Finds submaps that cannot be left and misplaced catchall bindings
+///
+/// # Arguments
+/// * `bindings` - All bindings of the config
+///
+/// # Returns
+/// Warnings for submaps in order of first use, then misplaced catchalls
+pub fn submap_warnings(bindings: &[Keybinding]) -> Vec<SubmapWarning> {
+    let mut warnings: Vec<SubmapWarning> = submap_names(bindings)
+        .into_iter()
+        .filter(|name| {
+            !bindings
+                .iter()
+                .filter(|binding| binding.submap.as_deref() == Some(*name))
+                .any(|binding| binding.key_combo.is_catchall() || binding.is_submap_reset())
+        })
+        .map(|name| SubmapWarning::NoEscape {
+            submap: name.to_string(),
+        })
+        .collect();
+
+    warnings.extend(
+        bindings
+            .iter()
+            .filter(|binding| binding.submap.is_none() && binding.key_combo.is_catchall())
+            .map(|binding| SubmapWarning::CatchallOutsideSubmap {
+                key_combo: binding.key_combo.clone(),
+            }),
+    );
+
+    warnings
+}
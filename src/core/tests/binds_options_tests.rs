@@ -0,0 +1,97 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::{
+    binds_options::{binds_option, set_binds_option, BindsOptions},
+    BindType, KeyCombo, Keybinding, Modifier,
+};
+
+fn binding(bind_type: BindType, key: &str, dispatcher: &str) -> Keybinding {
+    Keybinding {
+        bind_type,
+        ..Keybinding::new(KeyCombo::new(vec![Modifier::Super], key), dispatcher, None)
+    }
+}
+
+#[test]
+fn test_reads_section_and_prefixed_assignments() {
+    let config = "binds {\n\
+                  \x20   workspace_back_and_forth = true # toggle\n\
+                  \x20   scroll_event_delay = 100\n\
+                  }\n\
+                  input {\n\
+                  \x20   touchpad {\n\
+                  \x20       scroll_event_delay = 5\n\
+                  \x20   }\n\
+                  }\n\
+                  binds:scroll_event_delay = 200\n";
+    let options = BindsOptions::from_config(config);
+
+    assert_eq!(options.value("workspace_back_and_forth"), Some("true"));
+    // The last assignment wins; other sections are ignored
+    assert_eq!(options.value("scroll_event_delay"), Some("200"));
+    assert_eq!(options.value("pass_mouse_when_bound"), None);
+}
+
+#[test]
+fn test_relevant_options_follow_dispatcher_and_mouse_binds() {
+    let options = BindsOptions::from_config("binds:movefocus_cycles_fullscreen = yes\n");
+
+    let relevant = options.relevant_to(&binding(BindType::Bind, "L", "movefocus"));
+    let names: Vec<&str> = relevant.iter().map(|(option, _)| option.name).collect();
+    assert!(names.contains(&"movefocus_cycles_fullscreen"));
+    assert!(!names.contains(&"pass_mouse_when_bound"));
+    assert!(relevant.contains(&(
+        binds_option("movefocus_cycles_fullscreen").unwrap(),
+        "yes".to_string()
+    )));
+
+    let mouse = options.relevant_to(&binding(BindType::BindM, "mouse:272", "movewindow"));
+    assert!(mouse
+        .iter()
+        .any(|(option, value)| option.name == "pass_mouse_when_bound" && value == "false"));
+
+    assert!(options
+        .relevant_to(&binding(BindType::Bind, "K", "exec"))
+        .is_empty());
+}
+
+#[test]
+fn test_set_rewrites_adds_or_creates_section() {
+    // Rewrites the assignment in effect, keeping its form and comment
+    let config = "binds:pass_mouse_when_bound = false # clicks\n";
+    assert_eq!(
+        set_binds_option(config, "pass_mouse_when_bound", "true"),
+        "binds:pass_mouse_when_bound = true # clicks\n"
+    );
+
+    // Adds to the existing section with its indentation
+    let config = "binds {\n  drag_threshold = 10\n}\n";
+    assert_eq!(
+        set_binds_option(config, "allow_pin_fullscreen", "true"),
+        "binds {\n  drag_threshold = 10\n  allow_pin_fullscreen = true\n}\n"
+    );
+
+    // Creates a section after the rest of the config
+    let config = "bind = SUPER, K, exec, kitty\n";
+    let updated = set_binds_option(config, "ignore_group_lock", "true");
+    assert_eq!(
+        updated,
+        "bind = SUPER, K, exec, kitty\n\nbinds {\n    ignore_group_lock = true\n}\n"
+    );
+    assert_eq!(
+        BindsOptions::from_config(&updated).value("ignore_group_lock"),
+        Some("true")
+    );
+}
@@ -0,0 +1,109 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cheat sheet tests
+
+use std::path::Path;
+
+use crate::core::{
+    cheatsheet::{cheat_sheet_sections, CheatSheetFormat},
+    parser::parse_config_file,
+    Keybinding,
+};
+
+const CONFIG: &str = "\
+bind = SUPER SHIFT, Q, exit
+bind = SUPER, Q, killactive
+bind = , Print, exec, grim
+# @category: Apps
+bindd = SUPER, B, Browser, exec, firefox
+bind = SUPER, 1, workspace, 1
+";
+
+fn bindings() -> Vec<Keybinding> {
+    parse_config_file(CONFIG, Path::new("hyprland.conf")).unwrap()
+}
+
+#[test]
+fn test_categories_come_first_then_suggested_groups() {
+    let sections = cheat_sheet_sections(&bindings());
+    let titles: Vec<&str> = sections.iter().map(|s| s.title.as_str()).collect();
+
+    assert_eq!(
+        titles,
+        [
+            "Apps",
+            "Window Management",
+            "Workspaces",
+            "Screenshots",
+            "Other"
+        ]
+    );
+}
+
+#[test]
+fn test_sections_are_sorted_by_modifiers() {
+    let config = "bind = SUPER SHIFT, H, movefocus, l\n\
+                  bind = SUPER, L, movefocus, r\n\
+                  bind = ALT, J, movefocus, d\n";
+    let bindings = parse_config_file(config, Path::new("")).unwrap();
+    let sections = cheat_sheet_sections(&bindings);
+
+    let keys: Vec<String> = sections[0]
+        .bindings
+        .iter()
+        .map(|b| b.key_combo.to_string())
+        .collect();
+    assert_eq!(keys, ["ALT+J", "SUPER+L", "SHIFT+SUPER+H"]);
+}
+
+#[test]
+fn test_markdown_escapes_table_cells() {
+    let config = "bind = SUPER, T, exec, sh -c \"ls | wc -l\"\n";
+    let bindings = parse_config_file(config, Path::new("")).unwrap();
+    let sheet = CheatSheetFormat::Markdown.render(&bindings);
+
+    assert!(sheet.starts_with("# Hyprland Keybindings\n"));
+    assert!(sheet.contains("| Keys | Description | Command |"));
+    assert!(sheet.contains(r#"`exec sh -c "ls \| wc -l"`"#));
+}
+
+#[test]
+fn test_html_is_a_standalone_escaped_page() {
+    let sheet = CheatSheetFormat::Html.render(&bindings());
+
+    assert!(sheet.starts_with("<!DOCTYPE html>"));
+    assert!(sheet.contains("<h2>Apps</h2>"));
+    assert!(sheet.contains("<td>SUPER+B</td><td>Browser</td><td><code>exec firefox</code></td>"));
+    assert!(sheet.ends_with("</html>\n"));
+
+    let config = "bind = SUPER, T, exec, notify-send \"<b>hi</b>\"\n";
+    let bindings = parse_config_file(config, Path::new("")).unwrap();
+    let sheet = CheatSheetFormat::Html.render(&bindings);
+    assert!(sheet.contains("&lt;b&gt;hi&lt;/b&gt;"));
+}
+
+#[test]
+fn test_format_from_path_and_name() {
+    assert_eq!(
+        CheatSheetFormat::from_path(Path::new("keys.HTML")),
+        CheatSheetFormat::Html
+    );
+    assert_eq!(
+        CheatSheetFormat::from_path(Path::new("README.md")),
+        CheatSheetFormat::Markdown
+    );
+    assert_eq!("md".parse(), Ok(CheatSheetFormat::Markdown));
+    assert!("pdf".parse::<CheatSheetFormat>().is_err());
+}
@@ -0,0 +1,180 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::core::{
+    compact::{keybindings_heap_bytes, CompactBindings, Interner},
+    parser::parse_config_file,
+    Keybinding,
+};
+
+/// Undo and redo snapshots the GUI keeps at most
+const HISTORY_SNAPSHOTS: usize = 40;
+
+/// Budget for the current bindings plus a full history, in bytes
+const MEMORY_BUDGET: usize = 10 * 1024 * 1024;
+
+/// Resident set size of this process (`VmRSS`), in bytes
+fn resident_bytes() -> Option<usize> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// A config with `count` bindings, most of them sharing dispatchers and
+/// arguments as real configs do
+fn large_config(count: usize) -> String {
+    let mut config = String::from("$mainMod = SUPER\n");
+    let keys = ["Q", "W", "E", "R", "T", "Y", "U", "I", "O", "P", "A", "S"];
+    for i in 0..count {
+        let key = keys[i % keys.len()];
+        let line = match i % 4 {
+            0 => format!(
+                "bind = $mainMod, {}{}, exec, kitty --class term{}\n",
+                key,
+                i,
+                i % 7
+            ),
+            1 => format!(
+                "bind = $mainMod SHIFT, {}{}, workspace, {}\n",
+                key,
+                i,
+                i % 10
+            ),
+            2 => format!("binde = $mainMod ALT, {}{}, resizeactive, 10 0\n", key, i),
+            _ => format!("bindd = CTRL, {}{}, Focus left, movefocus, l\n", key, i),
+        };
+        config.push_str(&line);
+    }
+    config
+}
+
+#[test]
+fn test_snapshots_round_trip_every_field() {
+    let config = "submap = resize\n\
+                  bindd = , Escape, Leave resize, submap, reset\n\
+                  submap = reset\n\
+                  bindtn = SUPER ALT SHIFT, K, exec, kitty\n\
+                  bind = , catchall, killactive\n";
+    let mut bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+    bindings[2].source_file = Some(PathBuf::from("/tmp/extra.conf"));
+
+    let mut interner = Interner::default();
+    let compact = CompactBindings::new(&bindings, &mut interner);
+
+    assert_eq!(compact.len(), 3);
+    let restored = compact.to_bindings();
+    assert_eq!(restored, bindings);
+    // Equality ignores locations, so compare them separately
+    for (restored, original) in restored.iter().zip(&bindings) {
+        assert_eq!(restored.location, original.location);
+    }
+}
+
+#[test]
+fn test_purge_drops_strings_of_dropped_snapshots() {
+    let parse = |config: &str| parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+    let kept = parse("bind = SUPER, K, exec, kitty\n");
+    let dropped = parse("bind = SUPER, F, fullscreen\n");
+
+    let mut interner = Interner::default();
+    let snapshot = CompactBindings::new(&kept, &mut interner);
+    let old = CompactBindings::new(&dropped, &mut interner);
+    assert_eq!(interner.len(), 5);
+
+    drop(old);
+    interner.purge();
+    assert_eq!(interner.len(), 3, "K, exec and kitty remain");
+    assert_eq!(snapshot.to_bindings(), kept);
+}
+
+/// Memory benchmark, run in CI: the heap bytes of a 5000-binding config
+/// with a full undo/redo history, as the types count them, must fit the
+/// budget. Run with `--nocapture` to see the figures.
+#[test]
+fn test_history_of_large_config_fits_memory_budget() {
+    let mut bindings: Vec<Keybinding> =
+        parse_config_file(&large_config(5000), Path::new("hyprland.conf")).unwrap();
+    let current = keybindings_heap_bytes(&bindings);
+
+    // Every snapshot differs from the one before by a single edit
+    let mut interner = Interner::default();
+    let mut history = Vec::new();
+    let mut expanded = 0;
+    for i in 0..HISTORY_SNAPSHOTS {
+        bindings[i].args = Some(format!("edited {}", i));
+        expanded += keybindings_heap_bytes(&bindings);
+        history.push(CompactBindings::new(&bindings, &mut interner));
+    }
+    let compact = interner.heap_bytes()
+        + history
+            .iter()
+            .map(CompactBindings::heap_bytes)
+            .sum::<usize>();
+
+    println!(
+        "{} bindings: current {} KiB, history {} KiB compact vs {} KiB expanded",
+        bindings.len(),
+        current / 1024,
+        compact / 1024,
+        expanded / 1024
+    );
+    assert!(current + compact < MEMORY_BUDGET);
+    assert!(
+        compact * 5 < expanded,
+        "compact history should be far smaller"
+    );
+    assert_eq!(history[HISTORY_SNAPSHOTS - 1].to_bindings(), bindings);
+}
+
+/// Memory benchmark, run alone in CI: loading a 5000-binding config and
+/// filling the undo/redo history must grow the process RSS by less than the
+/// budget. Other tests allocating at the same time would skew the figure,
+/// hence `#[ignore]`; run it with `--ignored --exact --test-threads=1`.
+#[test]
+#[ignore]
+fn test_resident_memory_of_large_config_history_fits_budget() {
+    let before = resident_bytes().expect("VmRSS is only read on Linux");
+
+    let content = large_config(5000);
+    let mut bindings: Vec<Keybinding> =
+        parse_config_file(&content, Path::new("hyprland.conf")).unwrap();
+    drop(content);
+    let mut interner = Interner::default();
+    let mut history = Vec::new();
+    for i in 0..HISTORY_SNAPSHOTS {
+        bindings[i].args = Some(format!("edited {}", i));
+        history.push(CompactBindings::new(&bindings, &mut interner));
+    }
+
+    let grown = resident_bytes().unwrap().saturating_sub(before);
+    println!(
+        "{} bindings with {} snapshots: RSS grew by {} KiB",
+        bindings.len(),
+        history.len(),
+        grown / 1024
+    );
+    assert!(grown < MEMORY_BUDGET);
+}
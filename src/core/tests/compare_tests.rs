@@ -0,0 +1,94 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::{
+    compare::{compare_bindings, merge_rows, RowStatus, Side},
+    KeyCombo, Keybinding, Modifier,
+};
+
+fn binding(key: &str, args: &str) -> Keybinding {
+    Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], key),
+        "exec",
+        Some(args),
+    )
+}
+
+#[test]
+fn test_compare_aligns_rows_by_key_combo() {
+    let left = vec![
+        binding("K", "kitty"),
+        binding("Q", "quit"),
+        binding("B", "firefox"),
+    ];
+    let right = vec![
+        binding("B", "firefox"),
+        binding("K", "foot"),
+        binding("N", "notes"),
+    ];
+
+    let rows = compare_bindings(&left, &right);
+    let summary: Vec<(String, RowStatus)> = rows
+        .iter()
+        .map(|row| (row.key_combo.to_string(), row.status()))
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            ("SUPER+K".to_string(), RowStatus::Changed),
+            ("SUPER+Q".to_string(), RowStatus::Removed),
+            ("SUPER+B".to_string(), RowStatus::Unchanged),
+            ("SUPER+N".to_string(), RowStatus::Added),
+        ]
+    );
+}
+
+#[test]
+fn test_submaps_get_separate_rows() {
+    let global = binding("K", "kitty");
+    let in_submap = Keybinding {
+        submap: Some("resize".to_string()),
+        ..binding("K", "kitty")
+    };
+
+    let rows = compare_bindings(std::slice::from_ref(&global), &[in_submap]);
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].status(), RowStatus::Removed);
+    assert_eq!(rows[1].status(), RowStatus::Added);
+}
+
+#[test]
+fn test_merge_takes_chosen_side_per_row() {
+    let left = vec![binding("K", "kitty"), binding("Q", "quit")];
+    let right = vec![binding("K", "foot"), binding("N", "notes")];
+    let rows = compare_bindings(&left, &right);
+
+    // Missing choices default to the left side
+    assert_eq!(merge_rows(&rows, &[]), left);
+
+    let merged = merge_rows(&rows, &[Side::Right, Side::Left, Side::Right]);
+    assert_eq!(
+        merged,
+        vec![
+            binding("K", "foot"),
+            binding("Q", "quit"),
+            binding("N", "notes")
+        ]
+    );
+
+    // Taking the right side of a removed row drops it
+    let merged = merge_rows(&rows, &[Side::Left, Side::Right, Side::Left]);
+    assert_eq!(merged, vec![binding("K", "kitty")]);
+}
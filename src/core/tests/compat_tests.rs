@@ -0,0 +1,92 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::{
+    compat::{
+        available_dispatchers, dispatcher_warning, is_dispatcher_available, version_warnings,
+        HyprlandVersion,
+    },
+    KeyCombo, Keybinding, Modifier,
+};
+
+fn binding(key: &str, dispatcher: &str) -> Keybinding {
+    Keybinding::new(KeyCombo::new(vec![Modifier::Super], key), dispatcher, None)
+}
+
+#[test]
+fn test_version_parses_tags_and_plain_versions() {
+    assert_eq!(
+        HyprlandVersion::parse("v0.41.2"),
+        Some(HyprlandVersion::new(0, 41, 2))
+    );
+    assert_eq!(
+        HyprlandVersion::parse("0.45.0"),
+        Some(HyprlandVersion::new(0, 45, 0))
+    );
+    assert_eq!(
+        HyprlandVersion::parse("v0.39.1-87-g3f4a2b1"),
+        Some(HyprlandVersion::new(0, 39, 1))
+    );
+    assert_eq!(
+        HyprlandVersion::parse("v0.50"),
+        Some(HyprlandVersion::new(0, 50, 0))
+    );
+    assert_eq!(HyprlandVersion::parse("unknown"), None);
+    assert!(HyprlandVersion::new(0, 42, 0) > HyprlandVersion::new(0, 41, 9));
+}
+
+#[test]
+fn test_dispatcher_availability_follows_version() {
+    let old = HyprlandVersion::new(0, 41, 2);
+    let new = HyprlandVersion::new(0, 45, 0);
+
+    // Added in 0.42
+    assert!(!is_dispatcher_available("fullscreenstate", old));
+    assert!(is_dispatcher_available("fullscreenstate", new));
+
+    // Removed in 0.42
+    assert!(is_dispatcher_available("fakefullscreen", old));
+    assert!(!is_dispatcher_available("fakefullscreen", new));
+
+    // Long-standing and unknown dispatchers are never flagged
+    assert!(is_dispatcher_available("exec", old));
+    assert!(is_dispatcher_available("somepluginthing", old));
+
+    let offered = available_dispatchers(new);
+    assert!(offered.contains(&"fullscreenstate"));
+    assert!(!offered.contains(&"fakefullscreen"));
+}
+
+#[test]
+fn test_version_warnings_name_the_required_release() {
+    let version = HyprlandVersion::new(0, 41, 0);
+    let bindings = vec![
+        binding("F", "fullscreenstate"),
+        binding("Q", "killactive"),
+        binding("G", "fakefullscreen"),
+    ];
+
+    let warnings = version_warnings(&bindings, version);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].0, bindings[0]);
+    assert_eq!(
+        warnings[0].1,
+        "'fullscreenstate' needs Hyprland 0.42.0 or newer (running 0.41.0)"
+    );
+
+    assert_eq!(
+        dispatcher_warning("fakefullscreen", HyprlandVersion::new(0, 42, 1)).as_deref(),
+        Some("'fakefullscreen' was removed in Hyprland 0.42.0 (running 0.42.1)")
+    );
+}
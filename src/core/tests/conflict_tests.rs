@@ -12,19 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::path::Path;
+
 use crate::core::{
-    types::{BindType, Modifier},
-    ConflictDetector, KeyCombo, Keybinding,
+    conflict::ConflictChanges,
+    explanations::{explanation, CONFLICT_EXPLANATIONS},
+    parser::parse_config_file,
+    types::{BindType, Key, Modifier},
+    Conflict, ConflictDetector, ConflictKind, ConflictSeverity, KeyCombo, Keybinding,
 };
 
 /// Helper to create test bindings
 fn test_binding(modifiers: Vec<Modifier>, key: &str, app: &str) -> Keybinding {
-    Keybinding {
-        key_combo: KeyCombo::new(modifiers, key),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some(app.to_string()),
-    }
+    Keybinding::new(KeyCombo::new(modifiers, key), "exec", Some(app))
 }
 
 #[test]
@@ -102,6 +102,31 @@ fn test_modifier_order_independence() {
     assert_eq!(conflicts[0].conflicting_bindings.len(), 2);
 }
 
+#[test]
+fn test_modifier_order_in_the_config_does_not_split_conflicts() {
+    let bindings = parse_config_file(
+        "bind = SHIFT_SUPER, K, exec, app1\n\
+         bind = SUPER_SHIFT, K, exec, app2\n\
+         bind = SHIFT SUPER, K, exec, app3\n",
+        Path::new("hyprland.conf"),
+    )
+    .unwrap();
+
+    let mut detector = ConflictDetector::new();
+    for binding in bindings {
+        detector.add_binding(binding);
+    }
+    // Added as a struct literal, so never sorted by `KeyCombo::new`
+    let mut literal = test_binding(vec![], "K", "app4");
+    literal.key_combo.modifiers = vec![Modifier::Super, Modifier::Shift];
+    detector.add_binding(literal);
+
+    let conflicts = detector.find_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].conflicting_bindings.len(), 4);
+    assert_eq!(conflicts[0].key_combo.to_string(), "SHIFT+SUPER+K");
+}
+
 #[test]
 fn test_has_conflict_method() {
     let mut detector = ConflictDetector::new();
@@ -145,3 +170,372 @@ fn test_total_bindings_count() {
 
     assert_eq!(detector.total_bindings(), 3);
 }
+
+#[test]
+fn test_bindings_for_key_combo() {
+    let mut detector = ConflictDetector::new();
+
+    detector.add_binding(test_binding(vec![Modifier::Super], "K", "firefox"));
+    detector.add_binding(test_binding(vec![Modifier::Super], "K", "chrome"));
+    detector.add_binding(test_binding(vec![Modifier::Super], "J", "kitty"));
+
+    let combo = KeyCombo::new(vec![Modifier::Super], "K");
+    assert_eq!(detector.bindings_for(&combo, None).len(), 2);
+
+    let unused = KeyCombo::new(vec![Modifier::Alt], "K");
+    assert!(detector.bindings_for(&unused, None).is_empty());
+}
+
+#[test]
+fn test_conflict_kind_classification() {
+    let mut detector = ConflictDetector::new();
+
+    // Same action twice
+    detector.add_binding(test_binding(vec![Modifier::Super], "K", "firefox"));
+    detector.add_binding(test_binding(vec![Modifier::Super], "K", "firefox"));
+
+    // Press and release actions
+    detector.add_binding(test_binding(vec![Modifier::Super], "M", "kitty"));
+    detector.add_binding(Keybinding {
+        bind_type: BindType::BindR,
+        ..test_binding(vec![Modifier::Super], "M", "rofi")
+    });
+
+    // Different actions
+    detector.add_binding(test_binding(vec![Modifier::Super], "Q", "kitty"));
+    detector.add_binding(test_binding(vec![Modifier::Super], "Q", "alacritty"));
+
+    let kind_of = |key: &str| {
+        detector
+            .find_conflicts()
+            .into_iter()
+            .find(|conflict| conflict.key_combo.key == Key::parse(key))
+            .map(|conflict| conflict.kind)
+    };
+
+    assert_eq!(kind_of("K"), Some(ConflictKind::RedundantDuplicate));
+    assert_eq!(kind_of("M"), Some(ConflictKind::PressAndRelease));
+    assert_eq!(kind_of("Q"), Some(ConflictKind::MultipleActions));
+}
+
+#[test]
+fn test_every_conflict_kind_has_explanation() {
+    let kinds = [
+        ConflictKind::MultipleActions,
+        ConflictKind::RedundantDuplicate,
+        ConflictKind::PressAndRelease,
+        ConflictKind::CatchallShadowing,
+    ];
+
+    for kind in kinds {
+        let entry = kind.explanation();
+        assert_eq!(explanation(kind.explanation_id()), Some(entry));
+        assert!(!entry.summary.is_empty());
+        assert!(!entry.details.is_empty());
+        assert!(kind.docs_url().starts_with("https://"));
+    }
+
+    // Ids are unique so lookups are unambiguous
+    let mut ids: Vec<_> = CONFLICT_EXPLANATIONS.iter().map(|e| e.id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), CONFLICT_EXPLANATIONS.len());
+    assert!(explanation("no-such-id").is_none());
+}
+
+#[test]
+fn test_same_combo_in_different_submaps_does_not_conflict() {
+    let mut detector = ConflictDetector::new();
+    let in_submap = |name: &str| Keybinding {
+        submap: Some(name.to_string()),
+        ..test_binding(vec![], "ESCAPE", "reset")
+    };
+
+    detector.add_binding(test_binding(vec![], "ESCAPE", "kitty"));
+    detector.add_binding(in_submap("resize"));
+    detector.add_binding(in_submap("move"));
+
+    let combo = KeyCombo::new(vec![], "ESCAPE");
+    assert!(detector.find_conflicts().is_empty());
+    assert!(!detector.has_conflict(&combo));
+    for submap in [None, Some("resize"), Some("move")] {
+        assert_eq!(detector.bindings_for(&combo, submap).len(), 1);
+    }
+
+    // A second binding in the same submap does conflict
+    detector.add_binding(in_submap("resize"));
+    let conflicts = detector.find_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].submap.as_deref(), Some("resize"));
+    assert!(conflicts[0]
+        .conflicting_bindings
+        .iter()
+        .all(|binding| binding.submap.as_deref() == Some("resize")));
+    assert!(detector.has_conflict(&combo));
+}
+
+#[test]
+fn test_catchall_only_conflicts_with_another_catchall() {
+    let mut detector = ConflictDetector::new();
+    let in_resize = |key: &str, app: &str| Keybinding {
+        submap: Some("resize".to_string()),
+        ..test_binding(vec![], key, app)
+    };
+
+    detector.add_binding(in_resize("catchall", "reset"));
+    detector.add_binding(in_resize("ESCAPE", "reset"));
+    detector.add_binding(in_resize("RIGHT", "grow"));
+    assert!(detector.find_conflicts().is_empty());
+
+    detector.add_binding(in_resize("catchall", "notify-send stuck"));
+    let conflicts = detector.find_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert!(conflicts[0].key_combo.is_catchall());
+}
+
+#[test]
+fn test_conflicts_carry_their_submap() {
+    let config = "bind = SUPER, H, movefocus, l\n\
+                  bind = SUPER, H, exec, kitty\n\
+                  submap = resize\n\
+                  binde = SUPER, H, resizeactive, -10 0\n\
+                  binde = SUPER, H, resizeactive, -20 0\n\
+                  bind = , escape, submap, reset\n\
+                  submap = reset\n";
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+
+    let mut detector = ConflictDetector::new();
+    for binding in bindings {
+        detector.add_binding(binding);
+    }
+
+    let mut conflicts = detector.find_conflicts();
+    conflicts.sort_by(|a, b| a.submap.cmp(&b.submap));
+    assert_eq!(conflicts.len(), 2);
+    assert_eq!(conflicts[0].submap, None);
+    assert_eq!(conflicts[1].submap.as_deref(), Some("resize"));
+    for conflict in &conflicts {
+        assert_eq!(
+            conflict.key_combo,
+            KeyCombo::new(vec![Modifier::Super], "H")
+        );
+        assert!(conflict
+            .conflicting_bindings
+            .iter()
+            .all(|binding| binding.submap == conflict.submap));
+    }
+
+    // Global conflicts serialise as before; submap ones name their submap
+    let global = serde_json::to_value(&conflicts[0]).unwrap();
+    assert!(global.get("submap").is_none());
+    let resize = serde_json::to_value(&conflicts[1]).unwrap();
+    assert_eq!(resize["submap"], "resize");
+}
+
+#[test]
+fn test_severity_follows_kind() {
+    let config = "bind = SUPER, K, exec, kitty\n\
+                  bind = SUPER, K, exec, kitty\n\
+                  bind = SUPER, M, exec, kitty\n\
+                  bindr = SUPER, M, exec, rofi\n\
+                  bind = SUPER, Q, killactive\n\
+                  bind = SUPER, Q, exec, kitty\n";
+    let mut detector = ConflictDetector::new();
+    for binding in parse_config_file(config, Path::new("hyprland.conf")).unwrap() {
+        detector.add_binding(binding);
+    }
+
+    let severity_of = |key: &str| {
+        detector
+            .find_conflicts()
+            .into_iter()
+            .find(|conflict| conflict.key_combo.key == Key::parse(key))
+            .map(|conflict| (conflict.severity, conflict.kind.severity()))
+    };
+    assert_eq!(
+        severity_of("K"),
+        Some((ConflictSeverity::Low, ConflictSeverity::Low))
+    );
+    assert_eq!(
+        severity_of("M"),
+        Some((ConflictSeverity::Medium, ConflictSeverity::Medium))
+    );
+    assert_eq!(
+        severity_of("Q"),
+        Some((ConflictSeverity::High, ConflictSeverity::High))
+    );
+    assert!(ConflictSeverity::High > ConflictSeverity::Medium);
+    assert!(ConflictSeverity::Medium > ConflictSeverity::Low);
+}
+
+#[test]
+fn test_catchall_shadows_universal_binding() {
+    let config = "bindu = SUPER, V, exec, volume-osd\n\
+                  bindu = SUPER, L, exec, hyprlock\n\
+                  bindu = ALT, V, exec, clipboard\n\
+                  submap = passthrough\n\
+                  bind = SUPER, catchall, exec, notify-send blocked\n\
+                  bind = SUPER, L, exec, hyprlock\n\
+                  bind = , escape, submap, reset\n\
+                  submap = reset\n";
+    let mut detector = ConflictDetector::new();
+    for binding in parse_config_file(config, Path::new("hyprland.conf")).unwrap() {
+        detector.add_binding(binding);
+    }
+
+    // SUPER+L is bound in the submap and ALT+V has other modifiers, so
+    // only SUPER+V is caught
+    let conflicts = detector.find_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    let conflict = &conflicts[0];
+    assert_eq!(conflict.kind, ConflictKind::CatchallShadowing);
+    assert_eq!(conflict.severity, ConflictSeverity::Medium);
+    assert_eq!(conflict.submap.as_deref(), Some("passthrough"));
+    assert_eq!(
+        conflict.key_combo,
+        KeyCombo::new(vec![Modifier::Super], "V")
+    );
+    assert_eq!(conflict.conflicting_bindings.len(), 2);
+    assert_eq!(conflict.conflicting_bindings[0].submap, None);
+    assert!(conflict.conflicting_bindings[1].key_combo.is_catchall());
+}
+
+#[test]
+fn test_duplicate_actions_span_combos_not_formatting() {
+    let config = "bind = SUPER, Return, exec, kitty\n\
+                  bind = SUPER, T, exec, \"kitty\"\n\
+                  bind = SUPER, F, exec, firefox\n\
+                  bind = SUPER, Q, killactive\n\
+                  bind = SUPER SHIFT, C, killactive,\n";
+    let mut detector = ConflictDetector::new();
+    for binding in parse_config_file(config, Path::new("hyprland.conf")).unwrap() {
+        detector.add_binding(binding);
+    }
+
+    let duplicates = detector.find_duplicate_actions();
+    assert_eq!(duplicates.len(), 2);
+    assert_eq!(
+        duplicates[0].to_string(),
+        "exec kitty is bound to SUPER+RETURN, SUPER+T"
+    );
+    assert_eq!(duplicates[1].dispatcher, "killactive");
+    assert_eq!(duplicates[1].bindings.len(), 2);
+    assert!(detector.find_conflicts().is_empty());
+}
+
+#[test]
+fn test_duplicate_actions_exclude_same_combo_submaps_and_idioms() {
+    let config = "bind = SUPER, K, exec, kitty\n\
+                  bind = SUPER, k, exec, kitty\n\
+                  bind = SUPER, R, submap, resize\n\
+                  submap = resize\n\
+                  bind = , K, exec, kitty\n\
+                  bind = , escape, submap, reset\n\
+                  bind = , Return, submap, reset\n\
+                  submap = reset\n";
+    let mut detector = ConflictDetector::new();
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+    for binding in bindings.iter().cloned() {
+        detector.add_binding(binding);
+    }
+
+    // Same combo twice is a conflict; the submap's copy is a different
+    // context; leaving a submap from two keys is the usual idiom
+    assert!(detector.find_duplicate_actions().is_empty());
+    assert!(bindings
+        .iter()
+        .all(|binding| !detector.has_duplicate_action(binding)));
+    assert_eq!(detector.find_conflicts().len(), 1);
+}
+
+#[test]
+fn test_has_duplicate_action_ignores_bind_flags() {
+    let mut detector = ConflictDetector::new();
+    let mut press = test_binding(vec![Modifier::Super], "M", "playerctl play-pause");
+    press.bind_type = BindType::BindL;
+    let media = test_binding(vec![], "XF86AudioPlay", "playerctl play-pause");
+    let other = test_binding(vec![Modifier::Super], "B", "firefox");
+    detector.add_binding(press.clone());
+    detector.add_binding(media.clone());
+    detector.add_binding(other.clone());
+
+    assert!(detector.has_duplicate_action(&press));
+    assert!(detector.has_duplicate_action(&media));
+    assert!(!detector.has_duplicate_action(&other));
+}
+
+#[test]
+fn test_bindings_on_different_devices_do_not_conflict() {
+    let config = "device {\n\
+                  \x20   name = stream-deck\n\
+                  \x20   bind = , F13, exec, obs\n\
+                  }\n\
+                  device {\n\
+                  \x20   name = macro-pad\n\
+                  \x20   bind = , F13, exec, kitty\n\
+                  }\n";
+    let mut detector = ConflictDetector::new();
+    for binding in parse_config_file(config, Path::new("")).unwrap() {
+        detector.add_binding(binding);
+    }
+    let combo = KeyCombo::new(vec![], "F13");
+    assert!(detector.find_conflicts().is_empty());
+    assert!(!detector.has_conflict(&combo));
+
+    // A binding without a device fires on every device, so it clashes
+    // with both
+    let global = parse_config_file("bind = , F13, exec, foot", Path::new("")).unwrap();
+    detector.add_binding(global[0].clone());
+    let conflicts = detector.find_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].conflicting_bindings.len(), 3);
+    assert!(detector.has_conflict(&combo));
+}
+
+#[test]
+fn test_conflict_changes_between_checks() {
+    let conflicts = |config: &str| {
+        let mut detector = ConflictDetector::new();
+        for binding in parse_config_file(config, Path::new("hyprland.conf")).unwrap() {
+            detector.add_binding(binding);
+        }
+        detector.find_conflicts()
+    };
+    let before = conflicts(
+        "bind = SUPER, K, exec, kitty\n\
+         bind = SUPER, K, exec, firefox\n\
+         bind = SUPER, Q, killactive\n\
+         bind = SUPER, Q, exec, foot\n",
+    );
+    // SUPER+K moved down a line, SUPER+Q is resolved and SUPER+F is new
+    let after = conflicts(
+        "\n\
+         bind = SUPER, K, exec, kitty\n\
+         bind = SUPER, K, exec, firefox\n\
+         bind = SUPER, Q, killactive\n\
+         bind = SUPER, F, fullscreen\n\
+         bind = SUPER, F, togglefloating\n",
+    );
+
+    let changes = ConflictChanges::between(&before, &after);
+    let combos = |list: &[Conflict]| -> Vec<String> {
+        list.iter().map(|c| c.key_combo.to_string()).collect()
+    };
+    assert_eq!(combos(&changes.added), ["SUPER+F"]);
+    assert!(changes.changed.is_empty());
+    assert_eq!(combos(&changes.resolved), ["SUPER+Q"]);
+
+    // A third binding on SUPER+K changes that conflict
+    let third = conflicts(
+        "bind = SUPER, K, exec, kitty\n\
+         bind = SUPER, K, exec, firefox\n\
+         bind = SUPER, K, exec, foot\n\
+         bind = SUPER, Q, killactive\n\
+         bind = SUPER, Q, exec, foot\n",
+    );
+    let changes = ConflictChanges::between(&before, &third);
+    assert_eq!(combos(&changes.changed), ["SUPER+K"]);
+    assert!(changes.added.is_empty() && changes.resolved.is_empty());
+
+    assert!(ConflictChanges::between(&after, &after).is_empty());
+}
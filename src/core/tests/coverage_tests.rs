@@ -0,0 +1,83 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::coverage::assess_coverage;
+
+const CONFIG: &str = r#"
+# Monitors
+monitor = ,preferred,auto,1
+source = ~/.config/hypr/colours.conf
+$mainMod = SUPER
+exec-once = waybar
+
+general {
+    gaps_in = 5
+    col.active_border = rgba(33ccffee)
+}
+
+decoration:blur:enabled = true
+
+bind = $mainMod, K, exec, kitty
+bind = HYPER, J, exec, foot
+bindz = $mainMod, Q, killactive
+submap = resize
+binde = , RIGHT, resizeactive, 10 0
+submap = reset
+hyprexpo {
+    columns = 3
+}
+frobnicate = yes
+"#;
+
+#[test]
+fn test_lines_are_classified() {
+    let report = assess_coverage(CONFIG);
+
+    assert_eq!(report.binds, 2);
+    assert_eq!(report.partial_binds, 1, "HYPER is an unknown modifier");
+    assert_eq!(report.variables, 1);
+    assert_eq!(report.submaps, 2);
+    assert_eq!(report.sources, 1);
+    // monitor, exec-once, general { + 2 settings + }, decoration:blur:enabled,
+    // settings and closing brace of the unknown section
+    assert_eq!(report.other_known, 9);
+    assert_eq!(report.total(), 19);
+}
+
+#[test]
+fn test_unknown_constructs_are_listed_with_reasons() {
+    let report = assess_coverage(CONFIG);
+    let unknown: Vec<(usize, &str)> = report
+        .unknown
+        .iter()
+        .map(|construct| (construct.line, construct.reason.as_str()))
+        .collect();
+
+    assert_eq!(
+        unknown,
+        vec![
+            (17, "bind variant 'bindz' is not supported"),
+            (21, "unknown section 'hyprexpo'"),
+            (24, "unknown keyword 'frobnicate'"),
+        ]
+    );
+    assert!((report.percentage() - 15.0 * 100.0 / 19.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_empty_config_is_fully_covered() {
+    let report = assess_coverage("# only a comment\n\n");
+    assert_eq!(report.total(), 0);
+    assert_eq!(report.percentage(), 100.0);
+}
@@ -0,0 +1,137 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use crate::core::{
+    compat::DISPATCHERS,
+    dispatchers::{
+        desktop_exec_command, dispatcher_spec, validate_arguments, DISPATCHER_CATALOGUE,
+    },
+    validator::{validate_keybinding, ValidationError},
+    KeyCombo, Keybinding, Modifier,
+};
+
+#[test]
+fn test_catalogue_covers_every_versioned_dispatcher_once() {
+    let mut names = HashSet::new();
+    for spec in DISPATCHER_CATALOGUE {
+        assert!(names.insert(spec.name), "{} is listed twice", spec.name);
+    }
+    for entry in DISPATCHERS {
+        assert!(
+            dispatcher_spec(entry.name).is_some(),
+            "{} has no catalogue entry",
+            entry.name
+        );
+    }
+}
+
+#[test]
+fn test_catalogue_examples_fit_their_own_shapes() {
+    for spec in DISPATCHER_CATALOGUE {
+        let example = Some(spec.example).filter(|example| !example.is_empty());
+        assert_eq!(
+            validate_arguments(spec.name, example),
+            Ok(()),
+            "example of {}",
+            spec.name
+        );
+    }
+}
+
+#[test]
+fn test_arguments_are_checked_against_the_dispatcher() {
+    assert!(validate_arguments("MoveFocus", Some("l")).is_ok());
+    assert_eq!(
+        validate_arguments("movefocus", Some("sideways")),
+        Err("movefocus needs a direction: l, r, u or d".to_string())
+    );
+    assert!(validate_arguments("movewindow", Some("mon:DP-1")).is_ok());
+
+    assert!(validate_arguments("resizeactive", Some("exact 50% 50%")).is_ok());
+    assert!(validate_arguments("resizeactive", Some("-10 20")).is_ok());
+    assert!(validate_arguments("resizeactive", Some("10")).is_err());
+
+    assert!(validate_arguments("workspace", Some("name:web")).is_ok());
+    assert_eq!(
+        validate_arguments("workspace", Some("0")),
+        Err("Workspace ids start at 1, got 0".to_string())
+    );
+
+    assert!(validate_arguments("dpms", Some("off DP-1")).is_ok());
+    assert!(validate_arguments("dpms", Some("maybe")).is_err());
+
+    assert_eq!(
+        validate_arguments("exec", None),
+        Err("exec needs arguments, e.g. 'kitty'".to_string())
+    );
+    assert_eq!(
+        validate_arguments("killactive", Some("now")),
+        Err("killactive takes no arguments".to_string())
+    );
+    assert!(validate_arguments("togglespecialworkspace", Some("  ")).is_ok());
+}
+
+#[test]
+fn test_validator_rejects_arguments_that_do_not_fit() {
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "H"),
+        "movefocus",
+        Some("west"),
+    );
+
+    assert!(matches!(
+        validate_keybinding(&binding),
+        Err(ValidationError::InvalidArguments(_))
+    ));
+}
+
+#[test]
+fn test_every_offered_choice_is_accepted() {
+    for spec in DISPATCHER_CATALOGUE {
+        for choice in spec.arguments.choices() {
+            assert_eq!(
+                validate_arguments(spec.name, Some(choice)),
+                Ok(()),
+                "{} {}",
+                spec.name,
+                choice
+            );
+        }
+    }
+    assert_eq!(
+        dispatcher_spec("movefocus").unwrap().arguments.choices(),
+        ["l", "r", "u", "d"]
+    );
+    assert!(dispatcher_spec("exec")
+        .unwrap()
+        .arguments
+        .choices()
+        .is_empty());
+}
+
+#[test]
+fn test_desktop_exec_lines_become_commands() {
+    assert_eq!(desktop_exec_command("kitty"), "kitty");
+    assert_eq!(
+        desktop_exec_command("flatpak run org.gimp.GIMP %U"),
+        "flatpak run org.gimp.GIMP"
+    );
+    assert_eq!(
+        desktop_exec_command("code --new-window %F --"),
+        "code --new-window --"
+    );
+    assert_eq!(desktop_exec_command("echo 50%%"), "echo 50%");
+}
@@ -0,0 +1,160 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{
+    drift::{find_drift, LiveBind},
+    parser::parse_config_file,
+    BindType, KeyCombo, Keybinding, Modifier,
+};
+
+fn parse(config: &str) -> Vec<Keybinding> {
+    parse_config_file(config, Path::new("hyprland.conf")).unwrap()
+}
+
+fn live(modmask: u32, key: &str, dispatcher: &str, arg: &str) -> LiveBind {
+    LiveBind {
+        modmask,
+        key: key.to_string(),
+        dispatcher: dispatcher.to_string(),
+        arg: arg.to_string(),
+        ..LiveBind::default()
+    }
+}
+
+#[test]
+fn test_live_binds_parse_from_hyprctl_json() {
+    let json = r#"[{"locked": false, "mouse": false, "release": false, "repeat": false,
+        "non_consuming": false, "has_description": false, "modmask": 65, "submap": "",
+        "key": "Q", "keycode": 0, "catch_all": false, "description": "",
+        "dispatcher": "killactive", "arg": ""}]"#;
+    let binds: Vec<LiveBind> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(binds, [live(65, "Q", "killactive", "")]);
+    assert_eq!(
+        binds[0].key_combo(),
+        KeyCombo::new(vec![Modifier::Super, Modifier::Shift], "Q")
+    );
+}
+
+#[test]
+fn test_live_key_combos_cover_keycodes_and_catchall() {
+    let by_code = LiveBind {
+        keycode: 10,
+        ..live(8 | 4, "", "workspace", "1")
+    };
+    assert_eq!(
+        by_code.key_combo(),
+        KeyCombo::new(vec![Modifier::Ctrl, Modifier::Alt], "code:10")
+    );
+
+    let catchall = LiveBind {
+        catch_all: true,
+        ..live(0, "", "submap", "reset")
+    };
+    assert!(catchall.key_combo().is_catchall());
+}
+
+#[test]
+fn test_matching_config_has_no_drift() {
+    let bindings = parse(
+        "bind = SUPER, Return, exec, kitty\n         bind = SUPER SHIFT, q, killactive\n         bind = SUPER, 1, workspace,1\n",
+    );
+    let loaded = [
+        live(64, "Return", "exec", "kitty"),
+        live(65, "Q", "killactive", ""),
+        live(64, "1", "workspace", "1"),
+    ];
+
+    let drift = find_drift(&bindings, &loaded);
+    assert!(drift.is_empty(), "{:?}", drift);
+}
+
+#[test]
+fn test_drift_reports_both_sides() {
+    let bindings =
+        parse("bind = SUPER, Return, exec, kitty\n         bind = SUPER, E, exec, thunar\n");
+    let loaded = [
+        live(64, "Return", "exec", "kitty"),
+        live(64, "E", "exec", "nautilus"),
+    ];
+
+    let drift = find_drift(&bindings, &loaded);
+    assert_eq!(drift.missing.len(), 1);
+    assert_eq!(drift.missing[0].args.as_deref(), Some("thunar"));
+    assert_eq!(drift.unexpected, [live(64, "E", "exec", "nautilus")]);
+    assert_eq!(
+        drift.to_string(),
+        "1 binding not loaded by Hyprland, 1 live binding not in the config"
+    );
+
+    let lines = drift.describe();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("not loaded: "), "{}", lines[0]);
+    assert!(lines[0].contains("hyprland.conf:2"), "{}", lines[0]);
+    assert!(
+        lines[1].starts_with("not in config: SUPER+E"),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn test_submaps_must_match() {
+    let bindings = parse(
+        "submap = resize\n         binde = , right, resizeactive, 10 0\n         submap = reset\n",
+    );
+
+    let global = [live(0, "right", "resizeactive", "10 0")];
+    let drift = find_drift(&bindings, &global);
+    assert_eq!(drift.missing.len(), 1);
+    assert_eq!(drift.unexpected.len(), 1);
+
+    let in_submap = [LiveBind {
+        submap: "resize".to_string(),
+        ..live(0, "right", "resizeactive", "10 0")
+    }];
+    assert!(find_drift(&bindings, &in_submap).is_empty());
+}
+
+#[test]
+fn test_live_binds_convert_to_keybindings() {
+    let json = r#"[{"locked": true, "mouse": false, "release": false, "repeat": true,
+        "non_consuming": false, "has_description": true, "modmask": 64, "submap": "",
+        "key": "XF86AudioRaiseVolume", "keycode": 0, "catch_all": false,
+        "description": "Volume up", "dispatcher": "exec",
+        "arg": "wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+"}]"#;
+    let binds: Vec<LiveBind> = serde_json::from_str(json).unwrap();
+    let binding = binds[0].to_keybinding();
+
+    assert_eq!(
+        binding.bind_type,
+        BindType::from_keyword("bindeld").unwrap()
+    );
+    assert_eq!(binding.description.as_deref(), Some("Volume up"));
+    assert_eq!(binding.submap, None);
+    assert_eq!(binding.location, None);
+
+    let config = parse(
+        "bindeld = SUPER, XF86AudioRaiseVolume, Volume up, exec, wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+\n",
+    );
+    assert!(find_drift(&config, &binds).is_empty());
+    assert_eq!(binding.key_combo, config[0].key_combo);
+    assert_eq!(binding.args, config[0].args);
+
+    let bare = live(0, "Escape", "submap", "").to_keybinding();
+    assert_eq!(bare.bind_type, BindType::Bind);
+    assert_eq!(bare.args, None);
+}
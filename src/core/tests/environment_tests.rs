@@ -0,0 +1,85 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the focused window and input device replies
+
+use crate::core::{
+    environment::{Devices, WindowInfo, WorkspaceRef},
+    workspace::WorkspaceInfo,
+};
+
+#[test]
+fn test_active_window_reply_parses() {
+    let window: WindowInfo = serde_json::from_str(
+        r#"{"address": "0x55d1c2a0", "mapped": true, "at": [0, 0],
+            "workspace": {"id": 2, "name": "2"}, "floating": false,
+            "class": "kitty", "title": "vim: main.rs", "pid": 4242}"#,
+    )
+    .unwrap();
+
+    assert!(window.is_window());
+    assert_eq!(
+        window.workspace,
+        WorkspaceRef {
+            id: 2,
+            name: "2".to_string()
+        }
+    );
+    assert_eq!(window.to_string(), "kitty — vim: main.rs");
+}
+
+#[test]
+fn test_nothing_focused_is_not_a_window() {
+    let window: WindowInfo = serde_json::from_str("{}").unwrap();
+    assert!(!window.is_window());
+}
+
+#[test]
+fn test_active_workspace_reply_parses_as_workspace() {
+    let workspace: WorkspaceInfo = serde_json::from_str(
+        r#"{"id": 3, "name": "web", "monitor": "DP-1", "monitorID": 0,
+            "windows": 2, "hasfullscreen": false, "lastwindowtitle": "Firefox"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(workspace.id, 3);
+    assert_eq!(workspace.windows, 2);
+}
+
+#[test]
+fn test_devices_reply_lists_names_and_main_keyboard() {
+    let devices: Devices = serde_json::from_str(
+        r#"{"mice": [{"address": "0x1", "name": "elan-touchpad", "defaultSpeed": 0.0}],
+            "keyboards": [
+              {"name": "power-button", "layout": "us", "active_keymap": "English (US)",
+               "main": false},
+              {"name": "at-translated-set-2-keyboard", "layout": "us,de",
+               "active_keymap": "German", "capsLock": false, "main": true}
+            ],
+            "tablets": [], "touch": [], "switches": []}"#,
+    )
+    .unwrap();
+
+    let main = devices.main_keyboard().unwrap();
+    assert_eq!(main.name, "at-translated-set-2-keyboard");
+    assert_eq!(main.layout, "us,de");
+    assert_eq!(
+        devices.names().collect::<Vec<_>>(),
+        [
+            "power-button",
+            "at-translated-set-2-keyboard",
+            "elan-touchpad"
+        ]
+    );
+}
@@ -0,0 +1,124 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+use crate::core::{
+    compare::{compare_bindings, RowStatus},
+    equivalence::{canonical_args, merge_duplicates, redundant_copies},
+    parser::parse_config_file,
+    ConflictDetector, ConflictKind, Keybinding,
+};
+
+fn parse(config: &str) -> Vec<Keybinding> {
+    parse_config_file(config, Path::new("hyprland.conf")).unwrap()
+}
+
+#[test]
+fn test_canonical_args_ignore_spacing_and_plain_quotes() {
+    assert_eq!(
+        canonical_args("  kitty   --class  term "),
+        ["kitty", "--class", "term"]
+    );
+    assert_eq!(
+        canonical_args("\"kitty\" '--class' term"),
+        ["kitty", "--class", "term"]
+    );
+    assert_eq!(
+        canonical_args("notify-send 'hello world'"),
+        ["notify-send", "hello world"]
+    );
+    assert_eq!(
+        canonical_args("notify-send hello\\ world"),
+        ["notify-send", "hello world"]
+    );
+    assert_ne!(
+        canonical_args("notify-send 'hello world'"),
+        canonical_args("notify-send hello world")
+    );
+}
+
+#[test]
+fn test_canonical_args_keep_meaningful_quotes() {
+    // Quotes change how a shell expands these
+    assert_ne!(
+        canonical_args("echo \"$HOME\""),
+        canonical_args("echo $HOME")
+    );
+    assert_ne!(
+        canonical_args("echo '$HOME'"),
+        canonical_args("echo \"$HOME\"")
+    );
+    assert_ne!(canonical_args("ls '*'"), canonical_args("ls *"));
+
+    // Unbalanced quotes fall back to whitespace splitting
+    assert_eq!(canonical_args("echo 'oops"), ["echo", "'oops"]);
+}
+
+#[test]
+fn test_equivalence_covers_whole_binding() {
+    let bindings = parse(
+        "bind = SUPER SHIFT, Return, exec, kitty\n\
+         bind = SHIFT SUPER, enter, exec,   'kitty'\n\
+         bindd = SUPER, D, Open   launcher, exec, wofi\n\
+         bindd = SUPER, D, Open launcher, exec, wofi\n\
+         bind = SUPER, E, exec, kitty\n\
+         binde = SUPER, E, exec, kitty\n",
+    );
+
+    assert!(bindings[0].is_equivalent(&bindings[1]));
+    assert_ne!(bindings[0], bindings[1]);
+    assert!(bindings[2].is_equivalent(&bindings[3]));
+    assert!(!bindings[0].is_equivalent(&bindings[4]), "different key");
+    assert!(!bindings[4].is_equivalent(&bindings[5]), "different flags");
+}
+
+#[test]
+fn test_redundant_copies_keep_first_and_sourced() {
+    let mut bindings = parse(
+        "bind = SUPER, K, exec, kitty\n\
+         bind = SUPER, Q, killactive\n\
+         bind = SUPER, K, exec, \"kitty\"\n\
+         bind = SUPER, Q,   killactive\n\
+         bind = SUPER, Q, killactive\n",
+    );
+    assert_eq!(redundant_copies(&bindings), vec![2, 3, 4]);
+
+    // A copy in a sourced file cannot be removed here, so it is the one kept
+    bindings[3].source_file = Some(PathBuf::from("/tmp/keys.conf"));
+    assert_eq!(redundant_copies(&bindings), vec![1, 2, 4]);
+
+    let merged = merge_duplicates(&bindings);
+    assert_eq!(merged.len(), 2);
+    assert_eq!(merged[0].args.as_deref(), Some("kitty"));
+    assert!(merged[1].source_file.is_some());
+}
+
+#[test]
+fn test_formatting_copies_are_redundant_duplicates_and_unchanged() {
+    let before = parse("bind = SUPER SHIFT, Return, exec, kitty\n");
+    let after = parse("bind = SHIFT SUPER, enter, exec, \"kitty\"\n");
+
+    let rows = compare_bindings(&before, &after);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].status(), RowStatus::Unchanged);
+
+    let mut detector = ConflictDetector::new();
+    for binding in before.into_iter().chain(after) {
+        detector.add_binding(binding);
+    }
+    let conflicts = detector.find_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::RedundantDuplicate);
+}
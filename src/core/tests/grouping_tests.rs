@@ -0,0 +1,109 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::{
+    grouping::{classify_binding, has_comment_groups, suggest_groups, BindingGroup},
+    KeyCombo, Keybinding, Modifier,
+};
+
+fn binding(key: &str, dispatcher: &str, args: Option<&str>) -> Keybinding {
+    Keybinding::new(KeyCombo::new(vec![Modifier::Super], key), dispatcher, args)
+}
+
+#[test]
+fn test_classify_by_dispatcher() {
+    assert_eq!(
+        classify_binding(&binding("Q", "killactive", None)),
+        BindingGroup::WindowManagement
+    );
+    assert_eq!(
+        classify_binding(&binding("left", "movefocus", Some("l"))),
+        BindingGroup::WindowManagement
+    );
+    assert_eq!(
+        classify_binding(&binding("1", "movetoworkspace", Some("1"))),
+        BindingGroup::Workspaces
+    );
+    assert_eq!(
+        classify_binding(&binding("E", "dpms", Some("off"))),
+        BindingGroup::Other
+    );
+}
+
+#[test]
+fn test_classify_exec_by_program() {
+    assert_eq!(
+        classify_binding(&binding("Return", "exec", Some("kitty"))),
+        BindingGroup::Launchers
+    );
+    assert_eq!(
+        classify_binding(&binding("P", "exec", Some("playerctl play-pause"))),
+        BindingGroup::Media
+    );
+    assert_eq!(
+        classify_binding(&binding("S", "exec", Some("/usr/bin/hyprshot -m region"))),
+        BindingGroup::Screenshots
+    );
+    // Environment assignments are skipped
+    assert_eq!(
+        classify_binding(&binding("S", "exec", Some("GRIM_DEFAULT_DIR=~/shots grim"))),
+        BindingGroup::Screenshots
+    );
+}
+
+#[test]
+fn test_media_keys_and_print_override_program() {
+    assert_eq!(
+        classify_binding(&binding(
+            "XF86AudioRaiseVolume",
+            "exec",
+            Some("my-volume up")
+        )),
+        BindingGroup::Media
+    );
+    assert_eq!(
+        classify_binding(&binding("Print", "exec", Some("~/bin/capture.sh"))),
+        BindingGroup::Screenshots
+    );
+}
+
+#[test]
+fn test_suggest_groups_keeps_order_and_skips_empty() {
+    let bindings = vec![
+        binding("Return", "exec", Some("kitty")),
+        binding("Q", "killactive", None),
+        binding("B", "exec", Some("firefox")),
+    ];
+
+    let groups = suggest_groups(&bindings);
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].group, BindingGroup::WindowManagement);
+    assert_eq!(groups[1].group, BindingGroup::Launchers);
+    assert_eq!(
+        groups[1].bindings,
+        vec![bindings[0].clone(), bindings[2].clone()]
+    );
+}
+
+#[test]
+fn test_has_comment_groups() {
+    let flat = "# My config\nbind = SUPER, Q, killactive\nbind = SUPER, 1, workspace, 1\n";
+    let grouped =
+        "# Windows\nbind = SUPER, Q, killactive\n\n# Workspaces\nbind = SUPER, 1, workspace, 1\n";
+
+    assert!(!has_comment_groups(flat));
+    assert!(has_comment_groups(grouped));
+    assert!(!has_comment_groups("general {\n}\n"));
+}
@@ -0,0 +1,80 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::{
+    dispatchers::DISPATCHER_CATALOGUE,
+    help::{HelpIndex, HelpSection, HelpTopic},
+    types::BindFlag,
+};
+
+#[test]
+fn test_index_covers_every_flag_and_dispatcher() {
+    let index = HelpIndex::new(Vec::new());
+    let count = |section: HelpSection| {
+        index
+            .topics()
+            .iter()
+            .filter(|topic| topic.section == section)
+            .count()
+    };
+
+    assert_eq!(count(HelpSection::BindFlags), BindFlag::ALL.len());
+    assert_eq!(count(HelpSection::Dispatchers), DISPATCHER_CATALOGUE.len());
+    assert!(count(HelpSection::Conflicts) > 0);
+
+    // The README's feature sections are generated into the index at build time
+    let features: Vec<&str> = index
+        .topics()
+        .iter()
+        .filter(|topic| topic.section == HelpSection::Features)
+        .map(|topic| topic.title.as_str())
+        .collect();
+    assert!(features.contains(&"Conflict Detection"));
+    assert!(features.contains(&"Bind Flags"));
+    assert_eq!(index.topics()[0].section, HelpSection::Features);
+}
+
+#[test]
+fn test_search_matches_all_words_and_ranks_titles_first() {
+    let extra = vec![
+        HelpTopic::new(
+            HelpSection::DangerRules,
+            "Body mention",
+            "about movefocus here",
+        ),
+        HelpTopic::new(HelpSection::DangerRules, "movefocus rules", "nothing else"),
+    ];
+    let index = HelpIndex::new(extra);
+
+    let found = index.search("MOVEFOCUS");
+    assert_eq!(found[0].title, "movefocus");
+    assert!(found.iter().any(|topic| topic.title == "Body mention"));
+    let title_matches = found
+        .iter()
+        .take_while(|topic| topic.title.to_lowercase().contains("movefocus"))
+        .count();
+    assert_eq!(title_matches, 2);
+
+    let dispatcher = index
+        .search("movefocus direction")
+        .into_iter()
+        .find(|topic| topic.section == HelpSection::Dispatchers)
+        .unwrap();
+    assert_eq!(
+        dispatcher.body.lines().nth(2),
+        Some("Arguments: a direction: l, r, u or d")
+    );
+    assert!(index.search("no-such-word-anywhere").is_empty());
+    assert_eq!(index.search("  ").len(), index.topics().len());
+}
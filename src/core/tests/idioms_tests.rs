@@ -0,0 +1,67 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{
+    idioms::{builtin_duplicates, idiomatic_rewrite},
+    parser::parse_config_file,
+};
+
+#[test]
+fn test_hyprctl_dispatch_is_rewritten_to_the_dispatcher() {
+    let config = "submap = move\n\
+                  bindd = SUPER, 3, Go to 3, exec, hyprctl dispatch workspace 3\n\
+                  submap = reset\n\
+                  bindl = , F11, exec, /usr/bin/hyprctl -q dispatch fullscreen\n\
+                  bind = SUPER, Q, exec, hyprctl dispatch movewindow l\n";
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+
+    let rewrite = idiomatic_rewrite(&bindings[0]).unwrap();
+    assert_eq!(rewrite.dispatcher, "workspace");
+    assert_eq!(rewrite.args.as_deref(), Some("3"));
+    assert_eq!(rewrite.bind_type, bindings[0].bind_type);
+    assert_eq!(rewrite.submap.as_deref(), Some("move"));
+    assert_eq!(rewrite.description.as_deref(), Some("Go to 3"));
+    assert_eq!(rewrite.location, bindings[0].location);
+
+    let rewrite = idiomatic_rewrite(&bindings[1]).unwrap();
+    assert_eq!(rewrite.dispatcher, "fullscreen");
+    assert_eq!(rewrite.args, None);
+
+    let duplicates = builtin_duplicates(&bindings);
+    assert_eq!(duplicates.len(), 3);
+    assert_eq!(
+        duplicates[2].to_string(),
+        "SUPER+Q runs 'hyprctl dispatch' from exec; use 'movewindow, l' directly"
+    );
+}
+
+#[test]
+fn test_commands_the_dispatcher_cannot_express_are_left_alone() {
+    let config = "bind = SUPER, 1, exec, hyprctl dispatch workspace 1 && notify-send hi\n\
+                  bind = SUPER, 2, exec, hyprctl dispatch exec \"kitty -e htop\"\n\
+                  bind = SUPER, 3, exec, hyprctl -i 1 dispatch workspace 3\n\
+                  bind = SUPER, 4, exec, hyprctl keyword general:gaps_in 4\n\
+                  bind = SUPER, 5, exec, hyprctl dispatch\n\
+                  bind = SUPER, 6, exec, hyprctl dispatch notadispatcher\n\
+                  bind = SUPER, 7, exec, myhyprctl dispatch workspace 7\n\
+                  bind = SUPER, 8, workspace, 8\n";
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+
+    for binding in &bindings {
+        assert_eq!(idiomatic_rewrite(binding), None, "{}", binding);
+    }
+    assert!(builtin_duplicates(&bindings).is_empty());
+}
@@ -0,0 +1,125 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{
+    key_suggestions::KeySuggestionEngine, parser::parse_config_file, ConflictDetector,
+    ConflictKind, KeyCombo, Keybinding, Modifier,
+};
+
+fn parse(config: &str) -> Vec<Keybinding> {
+    parse_config_file(config, Path::new("hyprland.conf")).unwrap()
+}
+
+fn combo(modifiers: &[Modifier], key: &str) -> KeyCombo {
+    KeyCombo::new(modifiers.to_vec(), key)
+}
+
+#[test]
+fn test_suggestions_add_modifiers_before_changing_key() {
+    let bindings = parse(
+        "bind = SUPER, K, exec, kitty\n\
+         bind = SUPER, K, exec, firefox\n\
+         bind = SUPER SHIFT, K, exec, thunar\n\
+         bind = SUPER CTRL, K, exec, wofi\n\
+         bind = SUPER ALT, K, exec, grim\n",
+    );
+    let engine = KeySuggestionEngine::new(&bindings);
+
+    let suggestions = engine.suggest(&combo(&[Modifier::Super], "K"), None, 4);
+    assert_eq!(
+        suggestions,
+        vec![
+            combo(&[Modifier::Super, Modifier::Shift, Modifier::Ctrl], "K"),
+            combo(&[Modifier::Super, Modifier::Shift, Modifier::Alt], "K"),
+            combo(&[Modifier::Super, Modifier::Ctrl, Modifier::Alt], "K"),
+            combo(&[Modifier::Super], "A"),
+        ]
+    );
+}
+
+#[test]
+fn test_free_keys_skip_occupied_and_aliases() {
+    let bindings = parse(
+        "bind = SUPER, a, exec, kitty\n\
+         bind = SUPER, B, exec, firefox\n\
+         bindu = SUPER, C, exec, thunar\n",
+    );
+    let engine = KeySuggestionEngine::new(&bindings);
+
+    let free: Vec<KeyCombo> = engine.free_keys(&[Modifier::Super], None).take(2).collect();
+    assert_eq!(
+        free,
+        vec![
+            combo(&[Modifier::Super], "D"),
+            combo(&[Modifier::Super], "E")
+        ]
+    );
+    assert!(!engine.is_free(&combo(&[Modifier::Super], "A"), None));
+}
+
+#[test]
+fn test_suggestions_are_scoped_to_submap() {
+    let bindings = parse(
+        "bind = SUPER, A, exec, kitty\n\
+         bindu = , B, exec, firefox\n\
+         submap = resize\n\
+         binde = , A, resizeactive, 10 0\n\
+         submap = reset\n",
+    );
+    let engine = KeySuggestionEngine::new(&bindings);
+
+    // Global bindings do not occupy combos in a submap, universal ones do
+    assert!(engine.is_free(&combo(&[Modifier::Super], "A"), Some("resize")));
+    assert!(!engine.is_free(&combo(&[], "A"), Some("resize")));
+    assert!(!engine.is_free(&combo(&[], "B"), Some("resize")));
+    assert!(engine.is_free(&combo(&[], "A"), None));
+}
+
+#[test]
+fn test_excluded_binding_frees_its_combo() {
+    let bindings = parse("bind = SUPER, A, exec, kitty\n");
+    let engine = KeySuggestionEngine::new(bindings.iter().filter(|_| false));
+
+    assert!(engine.is_free(&bindings[0].key_combo, None));
+}
+
+#[test]
+fn test_conflict_suggestions_follow_kind() {
+    let bindings = parse(
+        "bind = SUPER, K, exec, kitty\n\
+         bind = SUPER, K, exec, firefox\n\
+         bind = SUPER, D, exec, wofi\n\
+         bind = SUPER, D, exec, wofi\n\
+         bind = , catchall, exec, true\n\
+         bind = , catchall, exec, false\n",
+    );
+    let engine = KeySuggestionEngine::new(&bindings);
+    let mut detector = ConflictDetector::new();
+    for binding in bindings {
+        detector.add_binding(binding);
+    }
+
+    for conflict in detector.find_conflicts() {
+        let suggestions = engine.suggest_for_conflict(&conflict, 3);
+        match conflict.kind {
+            ConflictKind::MultipleActions if conflict.key_combo.is_catchall() => {
+                assert!(suggestions.is_empty(), "a catchall has no key to keep")
+            }
+            ConflictKind::MultipleActions => assert_eq!(suggestions.len(), 3),
+            _ => assert!(suggestions.is_empty(), "{:?}", conflict.kind),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{
+    keysyms::{captured_combo, keysym_modifier, normalise_keysym, KEYSYM_ALIASES},
+    parser::parse_config_file,
+    ConflictDetector, Key, KeyCombo, Modifier,
+};
+
+#[test]
+fn test_aliases_and_case_fold_to_the_xkb_name() {
+    for (keysym, aliases) in KEYSYM_ALIASES {
+        let expected = keysym.to_uppercase();
+        assert_eq!(normalise_keysym(keysym), expected);
+        assert_eq!(normalise_keysym(&keysym.to_lowercase()), expected);
+        for alias in *aliases {
+            assert_eq!(normalise_keysym(alias), expected, "{}", alias);
+            assert_eq!(normalise_keysym(&alias.to_uppercase()), expected);
+        }
+    }
+
+    // Keypad keys are keys of their own
+    assert_eq!(normalise_keysym("KP_Enter"), "KP_ENTER");
+    assert_eq!(normalise_keysym("q"), "Q");
+}
+
+#[test]
+fn test_equivalent_spellings_conflict() {
+    let config = "bind = SUPER, Return, exec, kitty\n\
+                  bind = SUPER, RETURN, exec, foot\n\
+                  bind = SUPER, enter, exec, alacritty\n\
+                  bind = SUPER, KP_Enter, exec, wezterm\n";
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+
+    let mut detector = ConflictDetector::new();
+    for binding in bindings {
+        detector.add_binding(binding);
+    }
+
+    let conflicts = detector.find_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].key_combo.key, Key::Named("RETURN".to_string()));
+    assert_eq!(conflicts[0].conflicting_bindings.len(), 3);
+
+    // Hand-built combos are normalised on lookup
+    let combo = KeyCombo {
+        modifiers: vec![Modifier::Super],
+        key: Key::Named("Enter".to_string()),
+    };
+    assert!(detector.has_conflict(&combo));
+    assert_eq!(detector.bindings_for(&combo, None).len(), 3);
+}
+
+#[test]
+fn test_captured_keys_match_parsed_keys() {
+    let bindings = parse_config_file(
+        "bind = SUPER SHIFT, Return, exec, kitty\nbind = ALT, Tab, cyclenext\n",
+        Path::new("hyprland.conf"),
+    )
+    .unwrap();
+
+    let combo = captured_combo("Return", &[Modifier::Super, Modifier::Shift]).unwrap();
+    assert_eq!(combo, bindings[0].key_combo);
+
+    // Shift+Tab is reported as ISO_Left_Tab on some layouts
+    let combo = captured_combo("ISO_Left_Tab", &[Modifier::Alt]).unwrap();
+    assert_eq!(combo, bindings[1].key_combo);
+}
+
+#[test]
+fn test_lone_modifier_is_captured_with_its_own_modifier() {
+    assert_eq!(keysym_modifier("Control_R"), Some(Modifier::Ctrl));
+    assert_eq!(keysym_modifier("Meta_L"), Some(Modifier::Alt));
+    assert_eq!(keysym_modifier("q"), None);
+
+    let combo = captured_combo("Super_L", &[Modifier::Super]).unwrap();
+    assert_eq!(combo.modifiers, vec![Modifier::Super]);
+    assert_eq!(combo.key, Key::Named("SUPER_L".to_string()));
+
+    assert!(captured_combo("VoidSymbol", &[]).is_none());
+    assert!(captured_combo("", &[Modifier::Super]).is_none());
+}
@@ -0,0 +1,73 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{
+    layouts::{configured_layouts, layout_has_key, layout_warnings},
+    parser::parse_config_file,
+};
+
+#[test]
+fn test_configured_layouts_reads_both_forms() {
+    let block = "input {\n    kb_layout = us, DE # work\n    touchpad {\n        kb_layout = fr\n    }\n}\n";
+    assert_eq!(configured_layouts(block), vec!["us", "de"]);
+
+    let inline = "input:kb_layout = us\ninput:kb_layout = us,ru\n";
+    assert_eq!(configured_layouts(inline), vec!["us", "ru"]);
+
+    assert_eq!(
+        configured_layouts("bind = SUPER, Q, killactive\n"),
+        vec!["us"]
+    );
+}
+
+#[test]
+fn test_layout_knowledge() {
+    assert_eq!(layout_has_key("ru", "Q"), Some(false));
+    assert_eq!(layout_has_key("de", "Q"), Some(true));
+    assert_eq!(layout_has_key("fr", "1"), Some(false));
+    assert_eq!(layout_has_key("ru", "1"), Some(true));
+    assert_eq!(layout_has_key("us", "ADIAERESIS"), Some(false));
+    assert_eq!(layout_has_key("ua", "CYRILLIC_I"), Some(true));
+    assert_eq!(layout_has_key("us", "RETURN"), None);
+}
+
+#[test]
+fn test_warnings_only_for_keys_some_layouts_lack() {
+    let config = "bind = SUPER, Q, killactive\n\
+                  bind = SUPER, 1, workspace, 1\n\
+                  bind = SUPER, Return, exec, kitty\n\
+                  bind = SUPER, code:25, exec, firefox\n\
+                  bind = SUPER, Z, togglefloating\n";
+    let bindings = parse_config_file(config, Path::new("")).unwrap();
+
+    // A single layout has nothing to switch to
+    assert!(layout_warnings(&bindings, &["us".to_string()]).is_empty());
+
+    let warnings = layout_warnings(&bindings, &["de".to_string(), "ru".to_string()]);
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[0].missing_from, vec!["ru".to_string()]);
+    assert_eq!(warnings[0].keycode, Some(24));
+    // Z sits where QWERTY has Y on the German layout
+    assert_eq!(warnings[1].keycode, Some(29));
+    assert!(warnings[1]
+        .to_string()
+        .contains("switching to the 'ru' layout, which has no Z key; bind code:29 instead"));
+
+    let warnings = layout_warnings(&bindings, &["us".to_string(), "fr".to_string()]);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].binding.args.as_deref(), Some("1"));
+    assert_eq!(warnings[0].keycode, Some(10));
+}
@@ -0,0 +1,128 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lint rule and lint policy tests
+
+use std::path::Path;
+
+use crate::core::{
+    lint::{lint_bindings, LintLevel, LintPolicy, LintRule},
+    parser::parse_config_file,
+};
+
+/// Runs the lints over `config` and returns the rule and key of each finding
+fn lint(config: &str, policy: &LintPolicy) -> Vec<(LintRule, String)> {
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+    lint_bindings(config, &bindings, policy)
+        .into_iter()
+        .map(|finding| (finding.rule, finding.binding.key_combo.key.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_tui_program_without_terminal_is_flagged() {
+    let config = "\
+bind = SUPER, B, exec, btop
+bind = SUPER, T, exec, kitty -e btop
+bind = SUPER, V, exec, /usr/bin/nvim notes.md
+bind = SUPER, F, exec, firefox
+";
+    assert_eq!(
+        lint(config, &LintPolicy::default()),
+        [
+            (LintRule::TuiWithoutTerminal, "B".to_string()),
+            (LintRule::TuiWithoutTerminal, "V".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_written_out_modifiers_are_flagged_when_a_variable_is_used() {
+    let config = "\
+$mainMod = SUPER
+bind = $mainMod, Q, killactive
+bind = SUPER SHIFT, E, exit
+bind = ALT, Tab, cyclenext
+";
+    assert_eq!(
+        lint(config, &LintPolicy::default()),
+        [(LintRule::MixedModifierStyle, "E".to_string())]
+    );
+}
+
+#[test]
+fn test_unused_modifier_variable_is_not_a_style() {
+    let config = "\
+$mainMod = SUPER
+bind = SUPER, Q, killactive
+";
+    assert!(lint(config, &LintPolicy::default()).is_empty());
+}
+
+#[test]
+fn test_missing_description_is_opt_in() {
+    let config = "\
+# Close the window
+bind = SUPER, Q, killactive
+bindd = SUPER, M, Exit Hyprland, exit
+bind = SUPER, P, pseudo
+";
+    assert!(lint(config, &LintPolicy::default()).is_empty());
+
+    let mut policy = LintPolicy::default();
+    policy.set(LintRule::MissingDescription, LintLevel::Warn);
+    assert_eq!(
+        lint(config, &policy),
+        [(LintRule::MissingDescription, "P".to_string())]
+    );
+}
+
+#[test]
+fn test_findings_carry_the_policy_level() {
+    let config = "bind = SUPER, B, exec, htop\n";
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+    let policy = LintPolicy::parse("tui-without-terminal = deny\n");
+
+    let findings = lint_bindings(config, &bindings, &policy);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].level, LintLevel::Deny);
+    assert!(findings[0].to_string().contains("[tui-without-terminal]"));
+}
+
+#[test]
+fn test_policy_skips_comments_and_unknown_entries() {
+    let policy = LintPolicy::parse(
+        "\
+# House style
+mixed-modifier-style = DENY
+missing-description = sometimes
+no-such-rule = deny
+",
+    );
+    assert_eq!(policy.level(LintRule::MixedModifierStyle), LintLevel::Deny);
+    assert_eq!(
+        policy.level(LintRule::MissingDescription),
+        LintRule::MissingDescription.default_level()
+    );
+    assert_eq!(policy.level(LintRule::TuiWithoutTerminal), LintLevel::Warn);
+}
+
+#[test]
+fn test_missing_policy_file_gives_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        LintPolicy::load(&dir.path().join("lint")),
+        LintPolicy::default()
+    );
+}
@@ -18,6 +18,28 @@
 //! - Conflict detection tests
 //! - Input validation tests
 //! - Type tests (KeyCombo, Keybinding, etc.)
+//! - Workspace selector tests
+//! - Dispatcher availability by Hyprland version
+//! - Binding set comparison and merging
+//! - Parse coverage reporting
+//! - `binds` options (reading, setting, which bindings they affect)
+//! - Submap escape checks
+//! - Compact binding snapshots and their memory use
+//! - Rewriting `hyprctl dispatch` bindings to the dispatcher
+//! - Keysym aliases and case folding
+//! - Fuzzy binding lookup for the command palette
+//! - Formatting-independent binding equivalence and duplicate merging
+//! - Free key combo suggestions
+//! - Reserved combo checks
+//! - Drift between the config and live bindings
+//! - Binding scope sections (tags and hyprlang conditionals)
+//! - Scripting queries over the parsed bindings
+//! - Dispatcher catalogue and argument shapes
+//! - Keys missing from some configured keyboard layouts
+//! - Help topics and their full-text search
+//! - Markdown and HTML cheat sheets
+//! - Binding statistics (tallies, free modifier space, longest arguments)
+//! - Focused window and input device replies
 
 #[cfg(test)]
 mod conflict_tests;
@@ -33,3 +55,75 @@ mod parser_tests;
 
 #[cfg(test)]
 mod sandbox_tests;
+
+#[cfg(test)]
+mod workspace_tests;
+
+#[cfg(test)]
+mod grouping_tests;
+
+#[cfg(test)]
+mod compat_tests;
+
+#[cfg(test)]
+mod compare_tests;
+
+#[cfg(test)]
+mod coverage_tests;
+
+#[cfg(test)]
+mod binds_options_tests;
+
+#[cfg(test)]
+mod submap_tests;
+
+#[cfg(test)]
+mod compact_tests;
+
+#[cfg(test)]
+mod idioms_tests;
+
+#[cfg(test)]
+mod equivalence_tests;
+
+#[cfg(test)]
+mod keysyms_tests;
+
+#[cfg(test)]
+mod palette_tests;
+
+#[cfg(test)]
+mod key_suggestions_tests;
+
+#[cfg(test)]
+mod reserved_tests;
+
+#[cfg(test)]
+mod drift_tests;
+
+#[cfg(test)]
+mod scope_tests;
+
+#[cfg(test)]
+mod query_tests;
+
+#[cfg(test)]
+mod dispatchers_tests;
+
+#[cfg(test)]
+mod layouts_tests;
+
+#[cfg(test)]
+mod help_tests;
+
+#[cfg(test)]
+mod cheatsheet_tests;
+
+#[cfg(test)]
+mod stats_tests;
+
+#[cfg(test)]
+mod lint_tests;
+
+#[cfg(test)]
+mod environment_tests;
@@ -0,0 +1,142 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{
+    palette::{find_bindings, fuzzy_score, search_bindings},
+    parser::parse_config_file,
+    Keybinding,
+};
+
+fn parse(config: &str) -> Vec<Keybinding> {
+    parse_config_file(config, Path::new("hyprland.conf")).unwrap()
+}
+
+#[test]
+fn test_fuzzy_score_requires_characters_in_order() {
+    assert!(fuzzy_score("ffx", "firefox").is_some());
+    assert!(fuzzy_score("FIRE", "firefox").is_some());
+    assert!(fuzzy_score("xff", "firefox").is_none());
+    assert!(fuzzy_score("", "firefox").is_none());
+    assert!(fuzzy_score("  ", "firefox").is_none());
+}
+
+#[test]
+fn test_fuzzy_score_prefers_contiguous_and_word_start_matches() {
+    let contiguous = fuzzy_score("term", "open terminal").unwrap();
+    let scattered = fuzzy_score("term", "the ferment room").unwrap();
+    assert!(contiguous > scattered);
+
+    let word_starts = fuzzy_score("ps", "print screen").unwrap();
+    let inside = fuzzy_score("ps", "pause").unwrap();
+    assert!(word_starts > inside);
+}
+
+#[test]
+fn test_description_outranks_arguments() {
+    let bindings = parse(
+        "bind = SUPER, W, exec, browser-sync\n\
+         bindd = SUPER, B, Web browser, exec, firefox\n",
+    );
+
+    let matches = find_bindings(&bindings, "browser");
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].binding.args.as_deref(), Some("firefox"));
+}
+
+#[test]
+fn test_matches_dispatcher_and_keeps_config_order_on_ties() {
+    let bindings = parse(
+        "bind = SUPER, 1, workspace, 1\n\
+         bind = SUPER, 2, workspace, 2\n\
+         bind = SUPER, F, fullscreen\n",
+    );
+
+    let matches = find_bindings(&bindings, "workspace");
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].score, matches[1].score);
+    assert_eq!(matches[0].binding.args.as_deref(), Some("1"));
+    assert_eq!(matches[1].binding.args.as_deref(), Some("2"));
+}
+
+#[test]
+fn test_mouse_bindings_are_not_offered() {
+    let bindings = parse(
+        "bindm = SUPER, mouse:272, movewindow\n\
+         bind = SUPER SHIFT, M, movewindow, l\n",
+    );
+
+    let matches = find_bindings(&bindings, "movewindow");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].binding.args.as_deref(), Some("l"));
+}
+
+#[test]
+fn test_no_match_gives_nothing() {
+    let bindings = parse("bind = SUPER, Q, killactive\n");
+    assert!(find_bindings(&bindings, "firefox").is_empty());
+}
+
+#[test]
+fn test_search_matches_combos_categories_and_mouse_bindings() {
+    let bindings = parse(
+        "bind = SUPER, B, exec, firefox\n\
+         bindm = SUPER, mouse:272, movewindow\n\
+         # @category: Media\n\
+         bind = , XF86AudioPlay, exec, playerctl play-pause\n\
+         bind = SUPER SHIFT, B, exec, brave\n",
+    );
+
+    let matches = search_bindings(&bindings, "super+b");
+    let args: Vec<_> = matches.iter().map(|m| m.binding.args.as_deref()).collect();
+    assert_eq!(args, [Some("firefox"), Some("brave")]);
+
+    assert_eq!(search_bindings(&bindings, "mouse").len(), 1);
+    assert_eq!(
+        search_bindings(&bindings, "media")[0]
+            .binding
+            .args
+            .as_deref(),
+        Some("playerctl play-pause")
+    );
+    assert_eq!(
+        search_bindings(&bindings, "fire")[0]
+            .binding
+            .args
+            .as_deref(),
+        Some("firefox")
+    );
+    assert!(search_bindings(&bindings, "zzz").is_empty());
+}
+
+#[test]
+fn test_search_matches_combo_parts_by_prefix() {
+    let bindings = parse(
+        "bind = SUPER, Return, exec, kitty\n\
+         bind = SUPER, T, exec, kitty\n",
+    );
+
+    let matches = search_bindings(&bindings, "SUPER+T");
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].binding.key_combo.to_string(), "SUPER+T");
+    assert_eq!(search_bindings(&bindings, "sup+ret").len(), 1);
+    assert_eq!(search_bindings(&bindings, "super").len(), 2);
+
+    // Modifiers match in any order
+    let bindings = parse("bind = SUPER SHIFT, Q, killactive\n");
+    assert_eq!(search_bindings(&bindings, "shift+super+q").len(), 1);
+    assert_eq!(search_bindings(&bindings, "super shift q").len(), 1);
+    assert!(search_bindings(&bindings, "super+super").is_empty());
+}
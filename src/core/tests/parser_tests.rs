@@ -21,12 +21,16 @@
 //! - Dispatcher and arguments parsing
 //! - Variable substitution
 //! - Full config file parsing
+//! - Submap sections
+//! - `source = path` includes
+//! - Recovering parse reports
 
 use crate::core::{
     parser::*,
-    types::{BindType, Modifier},
+    types::{BindFlag, BindType, Key, Keybinding, Modifier},
 };
-use std::path::Path;
+use std::{fs, path::Path};
+use tempfile::TempDir;
 
 #[test]
 fn test_parse_bind_type() {
@@ -44,6 +48,41 @@ fn test_parse_bind_type() {
     ));
 }
 
+#[test]
+fn test_parse_bind_type_accepts_any_flag_combination() {
+    // Flag order does not matter
+    assert_eq!(parse_bind_type("bindle").unwrap().1, BindType::BindEL);
+
+    let (rest, bind_type) = parse_bind_type("bindtn = , K").unwrap();
+    assert_eq!(rest, " = , K");
+    assert!(bind_type.contains(BindFlag::Transparent));
+    assert!(bind_type.contains(BindFlag::NonConsuming));
+    assert!(!bind_type.contains(BindFlag::Repeat));
+    assert_eq!(bind_type.to_string(), "bindnt");
+
+    assert!(parse_bind_type("bindz = SUPER, K").is_err());
+    assert!(
+        parse_bind_type("binds:drag_threshold = 10").is_ok_and(|(rest, _)| rest.starts_with(':'))
+    );
+}
+
+#[test]
+fn test_parse_bindd_reads_description() {
+    let (_, binding) = parse_bind_line("bindd = SUPER, Q, Close the window, killactive").unwrap();
+    assert!(binding.bind_type.contains(BindFlag::Description));
+    assert_eq!(binding.description.as_deref(), Some("Close the window"));
+    assert_eq!(binding.dispatcher, "killactive");
+    assert_eq!(binding.args, None);
+
+    let (_, binding) = parse_bind_line("bindde = , XF86AudioRaiseVolume, Volume up, exec, wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+").unwrap();
+    assert_eq!(binding.description.as_deref(), Some("Volume up"));
+    assert_eq!(binding.dispatcher, "exec");
+    assert_eq!(
+        binding.args.as_deref(),
+        Some("wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+")
+    );
+}
+
 #[test]
 fn test_parse_modifiers() {
     let mods = parse_modifiers("SUPER").unwrap();
@@ -57,6 +96,15 @@ fn test_parse_modifiers() {
     assert_eq!(mods.len(), 2);
 }
 
+#[test]
+fn test_modifier_names_and_aliases() {
+    assert_eq!(modifier_from_str("super"), Some(Modifier::Super));
+    assert_eq!(modifier_from_str("MOD4"), Some(Modifier::Super));
+    assert_eq!(modifier_from_str(" Control "), Some(Modifier::Ctrl));
+    assert_eq!(modifier_from_str("MOD1"), Some(Modifier::Alt));
+    assert_eq!(modifier_from_str("HYPER"), None);
+}
+
 #[test]
 fn test_dispatcher() {
     let (_, (disp, args)) = parse_dispatcher("exec, firefox").unwrap();
@@ -75,7 +123,7 @@ fn test_parse_bind_line() {
 
     let (_, binding) = result.unwrap();
     assert!(matches!(binding.bind_type, BindType::Bind));
-    assert_eq!(binding.key_combo.key, "K");
+    assert_eq!(binding.key_combo.key, Key::Named("K".to_string()));
     assert_eq!(binding.dispatcher, "exec");
     assert_eq!(binding.args, Some("firefox".to_string()));
 }
@@ -100,6 +148,36 @@ fn test_variable_substitution() {
     assert_eq!(substituted, "bind = SUPER, K");
 }
 
+#[test]
+fn test_binds_section_is_not_a_bind_line() {
+    let config = "binds {\n    pass_mouse_when_bound = true\n}\n\
+                  binds:scroll_event_delay = 100\n\
+                  binde = , XF86AudioRaiseVolume, exec, pamixer -i 5\n";
+    let bindings = parse_config_file(config, Path::new("test.conf")).unwrap();
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings[0].bind_type, BindType::BindE);
+}
+
+#[test]
+fn test_variable_substitution_prefers_longest_name() {
+    let vars = collect_variables("$mod = SUPER\n$modShift = SUPER_SHIFT");
+    let substituted = substitute_variables("bind = $modShift, K", &vars);
+    assert_eq!(substituted, "bind = SUPER_SHIFT, K");
+}
+
+#[test]
+fn test_variable_table_keeps_definition_order() {
+    let table = VariableTable::from_config("$b = two\n$a = one\n$b = three");
+
+    assert_eq!(table.get("b"), Some("three"));
+    assert_eq!(table.name_for("one"), Some("a"));
+    assert_eq!(table.name_for("two"), None);
+    assert_eq!(
+        table.iter().collect::<Vec<_>>(),
+        vec![("b", "three"), ("a", "one")]
+    );
+}
+
 #[test]
 fn test_parse_config_file() {
     let config = r#"
@@ -115,3 +193,245 @@ binde = $mainMod SHIFT, R, exec, wofi
     let bindings = result.unwrap();
     assert_eq!(bindings.len(), 2);
 }
+
+#[test]
+fn test_parse_config_file_tracks_submaps() {
+    let config = r#"
+bind = SUPER, R, submap, resize
+
+submap = resize
+binde = , right, resizeactive, 10 0
+bind = , escape, submap, reset
+submap = reset
+
+bind = SUPER, K, exec, firefox
+"#;
+    let bindings = parse_config_file(config, Path::new("test.conf")).unwrap();
+    let submaps: Vec<Option<&str>> = bindings
+        .iter()
+        .map(|binding| binding.submap.as_deref())
+        .collect();
+
+    assert_eq!(
+        submaps,
+        vec![None, Some("resize"), Some("resize"), None],
+        "Bindings between `submap = resize` and `submap = reset` belong to the submap"
+    );
+}
+
+#[test]
+fn test_diagnose_clean_config() {
+    let config = r#"
+$mainMod = SUPER
+bind = $mainMod, K, exec, $HOME/bin/launcher
+bind = SUPER_SHIFT, Q, killactive
+"#;
+
+    assert!(diagnose_config(config).is_empty());
+}
+
+#[test]
+fn test_diagnose_unknown_modifier() {
+    let config = "bind = SUPER_HYPER, K, exec, kitty";
+    let diagnostics = diagnose_config(config);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 1);
+    assert!(diagnostics[0].message.contains("HYPER"));
+}
+
+#[test]
+fn test_diagnose_undefined_variable() {
+    let config = "\nbind = $undefinedMod, K, exec, kitty";
+    let diagnostics = diagnose_config(config);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 2);
+    assert!(diagnostics[0].message.contains("$undefinedMod"));
+}
+
+#[test]
+fn test_parse_config_with_sources_records_origin_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let main_path = temp_dir.path().join("hyprland.conf");
+    let keybinds_path = temp_dir.path().join("keybinds.conf");
+    fs::write(&keybinds_path, "bind = $mainMod, K, exec, kitty\n").unwrap();
+
+    let main = "$mainMod = SUPER\nbind = $mainMod, Q, killactive\nsource = keybinds.conf\n";
+    let bindings = parse_config_with_sources(main, &main_path).unwrap();
+
+    assert_eq!(bindings.len(), 2);
+    assert_eq!(bindings[0].source_file, None);
+    assert_eq!(bindings[1].source_file, Some(keybinds_path.clone()));
+    // Variables from the main config reach the sourced file
+    assert_eq!(bindings[1].key_combo.modifiers, vec![Modifier::Super]);
+    // Each binding knows its own file and line
+    let location = |binding: &Keybinding| binding.location.clone().unwrap();
+    assert_eq!(
+        location(&bindings[0]).to_string(),
+        format!("{}:2", main_path.display())
+    );
+    assert_eq!(location(&bindings[1]).file, keybinds_path);
+    assert_eq!(location(&bindings[1]).line, 1);
+
+    // The plain parser ignores source lines
+    assert_eq!(parse_config_file(main, &main_path).unwrap().len(), 1);
+}
+
+#[test]
+fn test_unbind_cancels_earlier_bindings_across_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let main_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        temp_dir.path().join("defaults.conf"),
+        "bind = SUPER, Q, killactive\nbind = SUPER, F, fullscreen\n",
+    )
+    .unwrap();
+
+    let main = "$mainMod = SUPER\n\
+                source = defaults.conf\n\
+                unbind = $mainMod, q\n\
+                bind = $mainMod, Q, exec, kitty\n";
+    let bindings = parse_config_with_sources(main, &main_path).unwrap();
+
+    // The default SUPER+Q is gone, so it no longer conflicts with the new one
+    let dispatchers: Vec<&str> = bindings.iter().map(|b| b.dispatcher.as_str()).collect();
+    assert_eq!(dispatchers, vec!["fullscreen", "exec"]);
+    assert!(parse_unbind_line("unbind = SUPER,").is_none());
+}
+
+#[test]
+fn test_parse_config_with_sources_detects_cycles() {
+    let temp_dir = TempDir::new().unwrap();
+    let main_path = temp_dir.path().join("hyprland.conf");
+    let a_path = temp_dir.path().join("a.conf");
+    fs::write(&a_path, "source = ./hyprland.conf\n").unwrap();
+    let main = "source = a.conf\n";
+    fs::write(&main_path, main).unwrap();
+
+    let result = parse_config_with_sources(main, &main_path);
+    assert!(matches!(
+        result,
+        Err(ParseError::SourceCycle { line: 1, .. })
+    ));
+
+    let missing = parse_config_with_sources("source = missing.conf\n", &main_path);
+    assert!(matches!(missing, Err(ParseError::SourceUnreadable { .. })));
+}
+
+#[test]
+fn test_parse_report_skips_bad_lines_and_keeps_the_rest() {
+    let config = "\
+bind = SUPER, K, exec, kitty
+bindx = SUPER, Q, killactive
+bind = SUPER_HYPER, M, exec, firefox
+bind = SUPER
+";
+    let path = Path::new("/home/user/.config/hypr/hyprland.conf");
+    let report = parse_config_report(config, path);
+
+    // The strict parser gives up on the first bad line
+    assert!(parse_config_file(config, path).is_err());
+
+    assert_eq!(report.bindings.len(), 2);
+    let kinds: Vec<(usize, DiagnosticKind)> = report
+        .diagnostics
+        .iter()
+        .map(|diagnostic| (diagnostic.line, diagnostic.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (2, DiagnosticKind::UnknownFlag),
+            (3, DiagnosticKind::UnknownModifier),
+            (4, DiagnosticKind::Unparseable),
+        ]
+    );
+    assert_eq!(report.skipped_lines(), 2);
+    assert!(report.diagnostics[0].message.contains("'x'"));
+    assert!(report.diagnostics[0]
+        .to_string()
+        .starts_with("/home/user/.config/hypr/hyprland.conf:2: "));
+}
+
+#[test]
+fn test_parse_report_names_sourced_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let main_path = temp_dir.path().join("hyprland.conf");
+    let keybinds_path = temp_dir.path().join("keybinds.conf");
+    fs::write(
+        &keybinds_path,
+        "bind = $mainMod, K, exec, kitty\nbindq = , X, exec, xterm\n",
+    )
+    .unwrap();
+
+    let main = "$mainMod = SUPER\nsource = keybinds.conf\nsource = missing.conf\n";
+    let report = parse_config_report(main, &main_path);
+
+    assert_eq!(report.bindings.len(), 1);
+    assert_eq!(report.diagnostics.len(), 2);
+    assert_eq!(report.diagnostics[0].file, Some(keybinds_path));
+    assert_eq!(report.diagnostics[0].line, 2);
+    assert_eq!(report.diagnostics[1].kind, DiagnosticKind::Source);
+    assert_eq!(report.diagnostics[1].file, Some(main_path));
+    assert_eq!(report.diagnostics[1].line, 3);
+}
+
+#[test]
+fn test_category_annotation_tags_the_bind_line_below() {
+    let config = "# @category: media\n\
+                  bind = , XF86AudioPlay, exec, playerctl play-pause\n\
+                  bind = , XF86AudioNext, exec, playerctl next\n\
+                  # @category: launchers\n\
+                  \n\
+                  bind = SUPER, Return, exec, kitty\n\
+                  # @category: windows\n\
+                  # @category: layout\n\
+                  bind = SUPER, F, fullscreen\n";
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+    let categories: Vec<Option<&str>> = bindings
+        .iter()
+        .map(|binding| binding.category.as_deref())
+        .collect();
+
+    // Only the line right below; a blank line detaches it; the last wins
+    assert_eq!(categories, [Some("media"), None, None, Some("layout")]);
+    assert_eq!(parse_category_annotation("# @category:   "), None);
+}
+
+#[test]
+fn test_bindings_in_device_sections_record_the_device() {
+    let config = "device {\n\
+                  \x20   bind = , F13, exec, obs-cmd scene switch Live\n\
+                  \x20   name = elgato-stream-deck\n\
+                  \x20   sensitivity = 0\n\
+                  }\n\
+                  device:logitech-g502 {\n\
+                  \x20   bind = , mouse:276, exec, obs-cmd record toggle\n\
+                  }\n\
+                  input {\n\
+                  \x20   touchpad {\n\
+                  \x20       name = not-a-device\n\
+                  \x20   }\n\
+                  }\n\
+                  device {\n\
+                  \x20   bind = , F14, exec, echo unnamed\n\
+                  }\n\
+                  bind = , F13, exec, foot\n";
+    let bindings = parse_config_file(config, Path::new("hyprland.conf")).unwrap();
+    let devices: Vec<Option<&str>> = bindings
+        .iter()
+        .map(|binding| binding.device.as_deref())
+        .collect();
+
+    // The name may follow the bind line; unnamed sections count as global
+    assert_eq!(
+        devices,
+        [
+            Some("elgato-stream-deck"),
+            Some("logitech-g502"),
+            None,
+            None
+        ]
+    );
+}
@@ -0,0 +1,105 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::core::{
+    conflict::ConflictDetector,
+    parser::parse_config_file,
+    query::{query_model, Query, QueryError},
+};
+
+const CONFIG: &str = "bind = SUPER, Return, exec, kitty\n\
+                      bind = SUPER SHIFT, Q, killactive\n\
+                      bind = SUPER, B, exec, firefox\n\
+                      bind = SUPER, B, exec, chromium\n\
+                      bindl = , XF86AudioMute, exec, wpctl set-mute @DEFAULT_SINK@ toggle\n";
+
+fn run(query: &str) -> Result<Vec<Value>, QueryError> {
+    let bindings = parse_config_file(CONFIG, Path::new("hyprland.conf")).unwrap();
+    let mut detector = ConflictDetector::new();
+    for binding in bindings.clone() {
+        detector.add_binding(binding);
+    }
+    let model = query_model(&bindings, &detector.find_conflicts());
+    query.parse::<Query>()?.run(&model)
+}
+
+#[test]
+fn test_paths_and_select_extract_fields() {
+    assert_eq!(
+        run(r#".bindings[] | select(.dispatcher == "exec") | .args"#).unwrap(),
+        [
+            "kitty",
+            "firefox",
+            "chromium",
+            "wpctl set-mute @DEFAULT_SINK@ toggle"
+        ]
+    );
+    assert_eq!(run(".bindings[1].combo").unwrap(), ["SHIFT+SUPER+Q"]);
+    assert_eq!(run(".bindings[-1].bind_type").unwrap(), ["bindl"]);
+    assert_eq!(run(".bindings[0].location.line").unwrap(), [json!(1)]);
+    // Absent fields read as null
+    assert_eq!(run(".bindings[1].args").unwrap(), [Value::Null]);
+}
+
+#[test]
+fn test_conditions_functions_and_collecting() {
+    assert_eq!(
+        run(r#"[.bindings[] | select(.dispatcher == "exec" and (.args | startswith("f")))] | length"#)
+            .unwrap(),
+        [json!(1)]
+    );
+    assert_eq!(
+        run(r#".bindings[] | select(.args | contains("DEFAULT") | not) | .combo"#).unwrap(),
+        ["SUPER+RETURN", "SHIFT+SUPER+Q", "SUPER+B", "SUPER+B"]
+    );
+    assert_eq!(
+        run(".bindings[] | select(.location.line >= 4 or .dispatcher == \"killactive\") | .location.line")
+            .unwrap(),
+        [json!(2), json!(4), json!(5)]
+    );
+    assert_eq!(
+        run(".conflicts[] | [.combo, (.conflicting_bindings | length)]").unwrap(),
+        [json!(["SUPER+B", 2])]
+    );
+}
+
+#[test]
+fn test_errors_name_the_problem() {
+    assert_eq!(
+        run(".bindings[] | .foo(").unwrap_err(),
+        QueryError::Syntax {
+            column: 19,
+            message: "unexpected '('".to_string()
+        }
+    );
+    assert!(matches!(
+        run("select(.x) | frobnicate"),
+        Err(QueryError::Syntax { column: 14, .. })
+    ));
+    assert_eq!(
+        run(".bindings[").unwrap_err(),
+        QueryError::Syntax {
+            column: 11,
+            message: "expected ']' at the end of the query".to_string()
+        }
+    );
+    assert_eq!(
+        run(".bindings[0].combo[]").unwrap_err(),
+        QueryError::Type("cannot iterate over a string".to_string())
+    );
+}
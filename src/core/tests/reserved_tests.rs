@@ -0,0 +1,107 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{
+    parser::parse_config_file,
+    reserved::{reserved_combo, reserved_shadows, RESERVED_COMBOS},
+    ConflictDetector, KeyCombo, Keybinding, Modifier,
+};
+
+fn parse(config: &str) -> Vec<Keybinding> {
+    parse_config_file(config, Path::new("hyprland.conf")).unwrap()
+}
+
+#[test]
+fn test_every_reserved_key_is_a_single_normalised_combo() {
+    for reserved in RESERVED_COMBOS {
+        assert!(!reserved.keys.is_empty(), "{}", reserved.owner);
+        for key in reserved.keys {
+            let combo = KeyCombo::new(reserved.modifiers.to_vec(), key);
+            assert!(reserved.covers(&combo), "{}", combo);
+            assert_eq!(
+                reserved_combo(&combo),
+                Some(reserved),
+                "{} is listed twice",
+                combo
+            );
+        }
+    }
+}
+
+#[test]
+fn test_reserved_combos_match_any_spelling() {
+    let combo = KeyCombo::new(vec![Modifier::Alt, Modifier::Ctrl], "Del");
+    assert_eq!(reserved_combo(&combo).map(|r| r.owner), Some("wlogout"));
+
+    let combo = KeyCombo::new(vec![Modifier::Super], "l");
+    assert_eq!(reserved_combo(&combo).map(|r| r.owner), Some("hyprlock"));
+
+    assert!(reserved_combo(&KeyCombo::new(vec![Modifier::Super, Modifier::Shift], "L")).is_none());
+}
+
+#[test]
+fn test_bindings_providing_the_purpose_are_not_flagged() {
+    let bindings = parse(
+        "bind = SUPER, L, exec, hyprlock\n\
+         bind = CTRL ALT, Delete, exec, wlogout -b 2\n\
+         bind = , Print, exec, grim -g \"$(slurp)\"\n\
+         bindel = , XF86AudioRaiseVolume, exec, wpctl set-volume @DEFAULT_AUDIO_SINK@ 5%+\n\
+         bindl = , XF86AudioPlay, exec, playerctl play-pause\n",
+    );
+    assert!(reserved_shadows(&bindings).is_empty());
+}
+
+#[test]
+fn test_other_actions_on_reserved_combos_are_flagged() {
+    let bindings = parse(
+        "bind = SUPER, L, movefocus, r\n\
+         bind = CTRL ALT, F3, exec, kitty\n\
+         bind = , XF86AudioMute, exec, firefox\n\
+         bind = SUPER, K, exec, kitty\n",
+    );
+
+    let shadows = reserved_shadows(&bindings);
+    let owners: Vec<&str> = shadows.iter().map(|s| s.reserved.owner).collect();
+    assert_eq!(owners, ["hyprlock", "Hyprland", "media keys"]);
+    assert_eq!(
+        shadows[1].to_string(),
+        "ALT+CTRL+F3 is reserved by Hyprland: it switches to a virtual terminal, \
+         which Hyprland handles itself"
+    );
+}
+
+#[test]
+fn test_detector_checks_reserved_combos_only_when_enabled() {
+    let bindings = parse(
+        "bind = SUPER, L, movefocus, r\n\
+         bind = CTRL ALT, BackSpace, exec, kitty\n",
+    );
+    let mut detector = ConflictDetector::new();
+    for binding in bindings {
+        detector.add_binding(binding);
+    }
+
+    assert!(!detector.checks_reserved());
+    assert!(detector.find_reserved_shadows().is_empty());
+    assert!(detector.find_conflicts().is_empty());
+
+    detector.set_check_reserved(true);
+    let shadows = detector.find_reserved_shadows();
+    assert_eq!(shadows.len(), 2);
+    // Config order, whatever the detector's internal order
+    assert_eq!(shadows[0].reserved.owner, "hyprlock");
+    assert_eq!(shadows[1].reserved.owner, "XKB");
+}
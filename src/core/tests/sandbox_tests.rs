@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::{Duration, Instant};
+
 use crate::core::sandbox;
 
 #[test]
@@ -33,3 +35,42 @@ fn test_unwrap_command_recovers_original_command() {
 fn test_is_wrapped_rejects_plain_exec_command() {
     assert!(!sandbox::is_wrapped("firefox"));
 }
+
+#[test]
+fn test_sandbox_argv_runs_without_shell() {
+    let argv = sandbox::sandbox_argv("notify-send 'hello world'").unwrap();
+
+    assert_eq!(argv[0], "bwrap");
+    assert!(argv.contains(&"--unshare-net".to_string()));
+    // Quotes are passed through literally, never interpreted by a shell
+    assert_eq!(argv[argv.len() - 2..], ["'hello", "world'"]);
+    assert!(sandbox::sandbox_argv("   ").is_err());
+}
+
+#[test]
+fn test_run_captured_collects_output() {
+    let argv = vec!["echo".to_string(), "preview".to_string()];
+    let run = sandbox::run_captured(&argv, Duration::from_secs(5)).unwrap();
+
+    assert_eq!(run.exit_code, Some(0));
+    assert_eq!(run.stdout.trim(), "preview");
+    assert!(run.stderr.is_empty());
+    assert!(!run.timed_out);
+}
+
+#[test]
+fn test_run_captured_kills_on_timeout() {
+    let argv = vec!["sleep".to_string(), "10".to_string()];
+    let started = Instant::now();
+    let run = sandbox::run_captured(&argv, Duration::from_millis(200)).unwrap();
+
+    assert!(run.timed_out);
+    assert_eq!(run.exit_code, None);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn test_run_captured_reports_missing_program() {
+    let argv = vec!["definitely-not-a-real-program".to_string()];
+    assert!(sandbox::run_captured(&argv, Duration::from_secs(1)).is_err());
+}
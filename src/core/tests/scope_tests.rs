@@ -0,0 +1,124 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fs, path::Path};
+
+use tempfile::TempDir;
+
+use crate::core::{
+    parser::{parse_config_file, parse_config_with_sources},
+    scope::{parse_scope_directive, ScopeDirective, ScopeIndex},
+    Keybinding,
+};
+
+fn parse(config: &str) -> Vec<Keybinding> {
+    parse_config_file(config, Path::new("hyprland.conf")).unwrap()
+}
+
+fn scopes_of(config: &str) -> Vec<Option<String>> {
+    let index = ScopeIndex::from_content(config, Path::new("hyprland.conf"));
+    parse(config)
+        .iter()
+        .map(|binding| index.scope_of(binding).map(str::to_string))
+        .collect()
+}
+
+#[test]
+fn test_scope_directives() {
+    assert_eq!(
+        parse_scope_directive("  #  scope:   external-kb "),
+        Some(ScopeDirective::Tag(Some("external-kb".to_string())))
+    );
+    assert_eq!(
+        parse_scope_directive("# scope: end"),
+        Some(ScopeDirective::Tag(None))
+    );
+    assert_eq!(
+        parse_scope_directive("# hyprlang if !DOCKED"),
+        Some(ScopeDirective::If("!DOCKED".to_string()))
+    );
+    assert_eq!(parse_scope_directive("# hyprlang noerror true"), None);
+    assert_eq!(parse_scope_directive("bind = SUPER, K, exec, kitty"), None);
+}
+
+#[test]
+fn test_tag_sections_scope_the_lines_below() {
+    let config = "bind = SUPER, Return, exec, kitty\n                  # scope: laptop\n                  bind = , XF86MonBrightnessUp, exec, brightnessctl s +5%\n                  # scope: external-kb\n                  bind = SUPER, F1, exec, pavucontrol\n                  # scope: global\n                  bind = SUPER, Q, killactive\n";
+
+    assert_eq!(
+        scopes_of(config),
+        [
+            None,
+            Some("laptop".to_string()),
+            Some("external-kb".to_string()),
+            None
+        ]
+    );
+    let index = ScopeIndex::from_content(config, Path::new("hyprland.conf"));
+    assert_eq!(index.scopes(), ["external-kb", "laptop"]);
+}
+
+#[test]
+fn test_hyprlang_conditionals_nest_inside_tags() {
+    let config = "# scope: laptop\n                  bind = , XF86MonBrightnessUp, exec, brightnessctl s +5%\n                  # hyprlang if DOCKED\n                  bind = SUPER, F1, exec, pavucontrol\n                  # hyprlang endif\n                  bind = , XF86MonBrightnessDown, exec, brightnessctl s 5%-\n";
+
+    assert_eq!(
+        scopes_of(config),
+        [
+            Some("laptop".to_string()),
+            Some("DOCKED".to_string()),
+            Some("laptop".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_global_bindings_are_active_in_every_scope() {
+    let config = "bind = SUPER, Return, exec, kitty\n                  # scope: laptop\n                  bind = , XF86MonBrightnessUp, exec, brightnessctl s +5%\n                  # scope: external-kb\n                  bind = SUPER, F1, exec, pavucontrol\n";
+    let bindings = parse(config);
+    let index = ScopeIndex::from_content(config, Path::new("hyprland.conf"));
+
+    let laptop = index.active_in(&bindings, "laptop");
+    assert_eq!(laptop.len(), 2);
+    assert_eq!(laptop[1].args.as_deref(), Some("brightnessctl s +5%"));
+    assert_eq!(index.active_in(&bindings, "external-kb").len(), 2);
+    assert_eq!(index.active_in(&bindings, "tablet").len(), 1);
+}
+
+#[test]
+fn test_index_reads_sourced_files_separately() {
+    let temp_dir = TempDir::new().unwrap();
+    let main = temp_dir.path().join("hyprland.conf");
+    let sourced = temp_dir.path().join("laptop.conf");
+    fs::write(
+        &main,
+        "# scope: laptop\nsource = laptop.conf\nbind = SUPER, L, exec, hyprlock\n",
+    )
+    .unwrap();
+    fs::write(
+        &sourced,
+        "bind = SUPER, K, exec, kitty\n# scope: desk\nbind = SUPER, D, exec, foot\n",
+    )
+    .unwrap();
+
+    let bindings = parse_config_with_sources(&fs::read_to_string(&main).unwrap(), &main).unwrap();
+    let index = ScopeIndex::from_bindings(&bindings);
+    let scopes: Vec<Option<&str>> = bindings
+        .iter()
+        .map(|binding| index.scope_of(binding))
+        .collect();
+
+    // A sourced file starts out global
+    assert_eq!(scopes, [None, Some("desk"), Some("laptop")]);
+}
@@ -0,0 +1,118 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Binding statistics tests
+
+use std::path::Path;
+
+use crate::core::{
+    key_suggestions::CANDIDATE_KEYS,
+    parser::parse_config_file,
+    stats::{BindingStats, Tally, GLOBAL_SUBMAP, NO_MODIFIERS},
+};
+
+const CONFIG: &str = "\
+bind = SUPER, Return, exec, kitty
+bind = SUPER, Q, killactive
+bind = SUPER SHIFT, Q, exit
+bind = SHIFT SUPER, E, exec, wofi --show drun
+bindd = , XF86AudioMute, Mute, exec, wpctl set-mute @DEFAULT_AUDIO_SINK@ toggle
+bind = SUPER, R, submap, resize
+submap = resize
+binde = , right, resizeactive, 10 0
+bind = , escape, submap, reset
+submap = reset
+";
+
+fn tally(name: &str, count: usize) -> Tally {
+    Tally {
+        name: name.to_string(),
+        count,
+    }
+}
+
+#[test]
+fn test_tallies_count_most_first_then_by_name() {
+    let bindings = parse_config_file(CONFIG, Path::new("hyprland.conf")).unwrap();
+    let stats = BindingStats::collect(&bindings);
+
+    assert_eq!(stats.total, 8);
+    assert_eq!(stats.described, 1);
+    // Modifier order in the config does not matter
+    assert_eq!(
+        stats.by_modifiers,
+        [
+            tally(NO_MODIFIERS, 3),
+            tally("SUPER", 3),
+            tally("SHIFT+SUPER", 2)
+        ]
+    );
+    assert_eq!(
+        stats.by_dispatcher,
+        [
+            tally("exec", 3),
+            tally("submap", 2),
+            tally("exit", 1),
+            tally("killactive", 1),
+            tally("resizeactive", 1),
+        ]
+    );
+    assert_eq!(
+        stats.by_submap,
+        [tally(GLOBAL_SUBMAP, 6), tally("resize", 2)]
+    );
+}
+
+#[test]
+fn test_modifier_space_and_longest_arguments() {
+    let bindings = parse_config_file(CONFIG, Path::new("hyprland.conf")).unwrap();
+    let stats = BindingStats::collect(&bindings);
+
+    // Return is not one of the counted keys; Q and R are
+    let super_layer = &stats.modifier_space[0];
+    assert_eq!(super_layer.modifiers, "SUPER");
+    assert_eq!(super_layer.used, 2);
+    assert_eq!(super_layer.free, CANDIDATE_KEYS.len() - 2);
+    assert_eq!(stats.modifier_space[1].modifiers, "SHIFT+SUPER");
+    assert_eq!(stats.modifier_space[1].used, 2);
+
+    let longest: Vec<_> = stats
+        .longest_args
+        .iter()
+        .map(|binding| binding.args.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        longest,
+        [
+            "wpctl set-mute @DEFAULT_AUDIO_SINK@ toggle",
+            "wofi --show drun",
+            "resize",
+            // Equally long arguments keep their config order
+            "kitty",
+            "reset"
+        ]
+    );
+}
+
+#[test]
+fn test_no_bindings_gives_empty_statistics() {
+    let stats = BindingStats::collect(&[]);
+    assert_eq!(stats.total, 0);
+    assert!(stats.by_dispatcher.is_empty());
+    assert!(stats.longest_args.is_empty());
+    assert!(stats
+        .modifier_space
+        .iter()
+        .all(|space| space.free == CANDIDATE_KEYS.len()));
+}
@@ -0,0 +1,63 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{
+    parser::parse_config_file,
+    submap::{submap_warnings, SubmapWarning},
+    KeyCombo,
+};
+
+#[test]
+fn test_submap_escapes_by_reset_or_catchall() {
+    let config = "bind = SUPER, R, submap, resize\n\
+                  submap = resize\n\
+                  binde = , right, resizeactive, 10 0\n\
+                  bind = , escape, submap, reset\n\
+                  submap = reset\n\
+                  submap = launch\n\
+                  bind = , F, exec, firefox\n\
+                  bind = , catchall, submap, reset\n\
+                  submap = reset\n\
+                  submap = trap\n\
+                  bind = , T, exec, kitty\n\
+                  submap = reset\n";
+    let bindings = parse_config_file(config, Path::new("")).unwrap();
+
+    assert_eq!(
+        submap_warnings(&bindings),
+        vec![SubmapWarning::NoEscape {
+            submap: "trap".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_catchall_outside_submap_is_flagged() {
+    let bindings =
+        parse_config_file("bind = , catchall, exec, notify-send hi\n", Path::new("")).unwrap();
+
+    let warnings = submap_warnings(&bindings);
+    assert_eq!(
+        warnings,
+        vec![SubmapWarning::CatchallOutsideSubmap {
+            key_combo: KeyCombo::new(vec![], "catchall")
+        }]
+    );
+    assert_eq!(warnings[0].submap(), None);
+    assert!(warnings[0]
+        .to_string()
+        .contains("only works inside submaps"));
+}
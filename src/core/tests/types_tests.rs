@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::core::types::{BindType, KeyCombo, Keybinding, Modifier};
+use crate::core::types::{BindType, Key, KeyCombo, Keybinding, Modifier, ScrollDirection};
 
 #[test]
 fn test_modifier_display() {
@@ -26,6 +26,23 @@ fn test_bind_type_display() {
     assert_eq!(format!("{}", BindType::BindEL), "bindel");
 }
 
+#[test]
+fn test_bind_type_serialises_as_keyword_and_reads_old_names() {
+    let bind_type = BindType::from_keyword("bindlt").unwrap();
+    assert_eq!(serde_json::to_string(&bind_type).unwrap(), "\"bindlt\"");
+    assert_eq!(
+        serde_json::from_str::<BindType>("\"bindtl\"").unwrap(),
+        bind_type
+    );
+
+    // Exports written before flag sets used the enum variant names
+    assert_eq!(
+        serde_json::from_str::<BindType>("\"BindEL\"").unwrap(),
+        BindType::BindEL
+    );
+    assert!(serde_json::from_str::<BindType>("\"bindz\"").is_err());
+}
+
 #[test]
 fn test_key_combo_normalization() {
     // Test that order doesn't matter
@@ -35,6 +52,27 @@ fn test_key_combo_normalization() {
     assert_eq!(combo1, combo2); // Should be equal after normalization
 }
 
+#[test]
+fn test_key_combo_literals_compare_as_modifier_sets() {
+    use std::collections::HashSet;
+
+    // Built without `new`, e.g. deserialised from a hand-edited export
+    let literal = KeyCombo {
+        modifiers: vec![Modifier::Super, Modifier::Shift, Modifier::Super],
+        key: Key::Named("K".to_string()),
+    };
+    let built = KeyCombo::new(vec![Modifier::Shift, Modifier::Super], "K");
+
+    assert_eq!(literal, built);
+    assert_eq!(HashSet::from([literal.clone(), built]).len(), 1);
+    assert_eq!(
+        literal.canonical_modifiers(),
+        [Modifier::Shift, Modifier::Super]
+    );
+    assert_eq!(literal.to_string(), "SHIFT+SUPER+K");
+    assert_ne!(literal, KeyCombo::new(vec![Modifier::Super], "K"));
+}
+
 #[test]
 fn test_key_combo_display() {
     let combo = KeyCombo::new(vec![Modifier::Super, Modifier::Shift], "K");
@@ -46,14 +84,54 @@ fn test_key_combo_display() {
     assert!(display.contains("K"));
 }
 
+#[test]
+fn test_mouse_and_scroll_keys_parse_display_and_write_back() {
+    let cases = [
+        ("mouse:272", Key::MouseButton(272), "Mouse Left"),
+        ("MOUSE:273", Key::MouseButton(273), "Mouse Right"),
+        ("mouse:300", Key::MouseButton(300), "Mouse 300"),
+        (
+            "mouse_down",
+            Key::Scroll(ScrollDirection::Down),
+            "Scroll Down",
+        ),
+        ("code:28", Key::Keycode(28), "code:28"),
+        ("Return", Key::Named("RETURN".to_string()), "RETURN"),
+    ];
+
+    for (config, key, display) in cases {
+        let parsed = Key::parse(config);
+        assert_eq!(parsed, key, "{}", config);
+        assert_eq!(parsed.to_string(), display);
+        // What the GUI shows can be typed back in
+        assert_eq!(Key::parse(display), key, "{}", display);
+        assert_eq!(Key::parse(&key.config_name()), key);
+    }
+
+    let combo = KeyCombo::new(vec![Modifier::Super], "mouse:272");
+    assert_eq!(combo.to_string(), "SUPER+Mouse Left");
+    assert_eq!(combo.config_key(), "mouse:272");
+    assert_eq!(combo, KeyCombo::new(vec![Modifier::Super], "Mouse Left"));
+    assert_ne!(
+        KeyCombo::new(vec![], "mouse_up"),
+        KeyCombo::new(vec![], "mouse_down")
+    );
+
+    // Exports store the config spelling; older ones the uppercase one
+    assert_eq!(serde_json::to_string(&combo.key).unwrap(), "\"mouse:272\"");
+    assert_eq!(
+        serde_json::from_str::<Key>("\"MOUSE_UP\"").unwrap(),
+        Key::Scroll(ScrollDirection::Up)
+    );
+}
+
 #[test]
 fn test_keybinding_display() {
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some("firefox".to_string()),
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "K"),
+        "exec",
+        Some("firefox"),
+    );
 
     let display = format!("{}", binding);
     assert!(display.contains("bind"));
@@ -65,14 +143,29 @@ fn test_keybinding_display() {
 
 #[test]
 fn test_keybinding_no_args() {
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], "Q"),
-        bind_type: BindType::Bind,
-        dispatcher: "killactive".to_string(),
-        args: None,
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "Q"),
+        "killactive",
+        None,
+    );
 
     let display = format!("{}", binding);
     assert!(display.contains("killactive"));
     assert!(!display.ends_with(",")); // No trailing comma when no args
 }
+
+#[test]
+fn test_keybinding_new_is_a_plain_global_bind() {
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "K"),
+        "exec",
+        Some("firefox"),
+    );
+
+    assert_eq!(binding.bind_type, BindType::Bind);
+    assert_eq!(binding.dispatcher, "exec");
+    assert_eq!(binding.args.as_deref(), Some("firefox"));
+    assert!(binding.submap.is_none() && binding.source_file.is_none());
+    assert!(binding.description.is_none() && binding.category.is_none());
+    assert!(binding.device.is_none() && binding.location.is_none());
+}
@@ -16,7 +16,7 @@ use crate::core::{
     types::{BindType, KeyCombo, Keybinding, Modifier},
     validator::{
         check_shell_metacharacters, validate_dispatcher, validate_key, validate_keybinding,
-        ValidationError,
+        workspace_warnings, ValidationError, WorkspaceWarning,
     },
 };
 
@@ -103,12 +103,11 @@ fn test_invalid_keys() {
 #[test]
 fn test_argument_length_limit() {
     let long_arg = "a".repeat(1001);
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some(long_arg),
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "K"),
+        "exec",
+        Some(&long_arg),
+    );
 
     assert!(matches!(
         validate_keybinding(&binding),
@@ -118,24 +117,22 @@ fn test_argument_length_limit() {
 
 #[test]
 fn test_validates_complete_binding_success() {
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some("firefox".to_string()),
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "K"),
+        "exec",
+        Some("firefox"),
+    );
 
     assert!(validate_keybinding(&binding).is_ok());
 }
 
 #[test]
 fn test_validates_complete_binding_invalid_dispatcher() {
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-        bind_type: BindType::Bind,
-        dispatcher: "evil".to_string(),
-        args: Some("firefox".to_string()),
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "K"),
+        "evil",
+        Some("firefox"),
+    );
 
     assert!(matches!(
         validate_keybinding(&binding),
@@ -145,15 +142,115 @@ fn test_validates_complete_binding_invalid_dispatcher() {
 
 #[test]
 fn test_validates_complete_binding_shell_injection() {
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some("firefox; rm -rf /".to_string()),
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "K"),
+        "exec",
+        Some("firefox; rm -rf /"),
+    );
 
     assert!(matches!(
         validate_keybinding(&binding),
         Err(ValidationError::ShellMetacharacters(_))
     ));
 }
+
+#[test]
+fn test_mouse_bindings_need_a_known_mouse_button() {
+    let binding = |bind_type: BindType, key: &str| Keybinding {
+        bind_type,
+        ..Keybinding::new(
+            KeyCombo::new(vec![Modifier::Super], key),
+            "movewindow",
+            None,
+        )
+    };
+
+    assert!(validate_keybinding(&binding(BindType::BindM, "mouse:272")).is_ok());
+    assert!(validate_keybinding(&binding(BindType::Bind, "mouse_down")).is_ok());
+    assert_eq!(
+        validate_keybinding(&binding(BindType::BindM, "K")),
+        Err(ValidationError::MouseBindingKey("K".to_string()))
+    );
+    assert_eq!(
+        validate_keybinding(&binding(BindType::BindM, "mouse_up")),
+        Err(ValidationError::MouseBindingKey("mouse_up".to_string()))
+    );
+    assert_eq!(
+        validate_keybinding(&binding(BindType::Bind, "mouse:5")),
+        Err(ValidationError::InvalidKey("mouse:5".to_string()))
+    );
+}
+
+#[test]
+fn test_section_names_cannot_break_out_of_their_line() {
+    let binding = |submap: Option<&str>, device: Option<&str>| Keybinding {
+        submap: submap.map(str::to_string),
+        device: device.map(str::to_string),
+        ..Keybinding::new(
+            KeyCombo::new(vec![Modifier::Super], "K"),
+            "killactive",
+            None,
+        )
+    };
+
+    assert!(validate_keybinding(&binding(Some("resize"), Some("my-keyboard"))).is_ok());
+    assert_eq!(
+        validate_keybinding(&binding(Some("resize\nexec-once = evil"), None)),
+        Err(ValidationError::InvalidSection(
+            "resize\nexec-once = evil".to_string()
+        ))
+    );
+    assert_eq!(
+        validate_keybinding(&binding(None, Some("kbd }"))),
+        Err(ValidationError::InvalidSection("kbd }".to_string()))
+    );
+}
+
+#[test]
+fn test_workspace_warnings_for_suspicious_targets() {
+    assert!(workspace_warnings("workspace", Some("10")).is_empty());
+    assert!(workspace_warnings("movetoworkspace", Some("e+1")).is_empty());
+    assert!(workspace_warnings("movetoworkspace", Some("special:scratch")).is_empty());
+    assert_eq!(
+        workspace_warnings("workspace", Some("11")),
+        vec![WorkspaceWarning::OutOfRange { id: 11 }]
+    );
+    assert_eq!(
+        workspace_warnings("focusworkspaceoncurrentmonitor", Some("e+0")),
+        vec![WorkspaceWarning::ZeroOffset {
+            selector: "e+0".to_string()
+        }]
+    );
+    assert_eq!(
+        workspace_warnings("workspace", Some("-20")),
+        vec![WorkspaceWarning::LargeOffset {
+            selector: "-20".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_workspace_warnings_for_malformed_targets() {
+    let warnings = workspace_warnings("workspace", Some("name:3, silent"));
+    assert_eq!(
+        warnings,
+        vec![
+            WorkspaceWarning::NumericName {
+                name: "3".to_string()
+            },
+            WorkspaceWarning::IgnoredText {
+                text: "silent".to_string()
+            },
+        ]
+    );
+    assert_eq!(
+        warnings[0].suggestion(),
+        Some("Use '3' for the numbered workspace".to_string())
+    );
+
+    // The window after the comma is the second argument of moves
+    assert!(workspace_warnings("movetoworkspacesilent", Some("3,class:mpv")).is_empty());
+    // Targets that do not parse are errors, not warnings
+    assert!(workspace_warnings("workspace", Some("special:")).is_empty());
+    assert!(workspace_warnings("exec", Some("11")).is_empty());
+}
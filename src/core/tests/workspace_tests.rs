@@ -0,0 +1,118 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::workspace::{
+    is_workspace_dispatcher, parse_workspace_target, workspace_status, WorkspaceInfo,
+    WorkspaceStatus, WorkspaceTarget,
+};
+
+fn running_workspaces() -> Vec<WorkspaceInfo> {
+    vec![
+        WorkspaceInfo {
+            id: 1,
+            name: "1".to_string(),
+            windows: 3,
+        },
+        WorkspaceInfo {
+            id: 4,
+            name: "web".to_string(),
+            windows: 1,
+        },
+        WorkspaceInfo {
+            id: -98,
+            name: "special:scratch".to_string(),
+            windows: 0,
+        },
+    ]
+}
+
+#[test]
+fn test_parse_workspace_targets() {
+    let cases = vec![
+        ("3", WorkspaceTarget::Id(3)),
+        (" 3 ,address:0x1234", WorkspaceTarget::Id(3)),
+        ("name:web", WorkspaceTarget::Name("web".to_string())),
+        ("special", WorkspaceTarget::Special(None)),
+        (
+            "special:scratch",
+            WorkspaceTarget::Special(Some("scratch".to_string())),
+        ),
+        ("+1", WorkspaceTarget::Relative("+1".to_string())),
+        ("-1", WorkspaceTarget::Relative("-1".to_string())),
+        ("e+1", WorkspaceTarget::Relative("e+1".to_string())),
+        ("m-2", WorkspaceTarget::Relative("m-2".to_string())),
+        (
+            "previous",
+            WorkspaceTarget::Relative("previous".to_string()),
+        ),
+        ("empty", WorkspaceTarget::Relative("empty".to_string())),
+    ];
+
+    for (args, expected) in cases {
+        assert_eq!(parse_workspace_target(args), Ok(expected), "args: {}", args);
+    }
+}
+
+#[test]
+fn test_parse_workspace_target_rejects_malformed() {
+    for args in ["", "0", "name:", "special:", "web", "e+", "x+1"] {
+        assert!(
+            parse_workspace_target(args).is_err(),
+            "'{}' should be rejected",
+            args
+        );
+    }
+}
+
+#[test]
+fn test_workspace_status_against_running_instance() {
+    let running = running_workspaces();
+
+    assert_eq!(
+        workspace_status(&WorkspaceTarget::Id(1), &running),
+        WorkspaceStatus::Exists { windows: 3 }
+    );
+    assert_eq!(
+        workspace_status(&WorkspaceTarget::Name("web".to_string()), &running),
+        WorkspaceStatus::Exists { windows: 1 }
+    );
+    assert_eq!(
+        workspace_status(
+            &WorkspaceTarget::Special(Some("scratch".to_string())),
+            &running
+        ),
+        WorkspaceStatus::Exists { windows: 0 }
+    );
+    assert_eq!(
+        workspace_status(&WorkspaceTarget::Name("chat".to_string()), &running),
+        WorkspaceStatus::WillBeCreated
+    );
+    assert_eq!(
+        workspace_status(&WorkspaceTarget::Id(7), &running),
+        WorkspaceStatus::WillBeCreated
+    );
+    assert_eq!(
+        workspace_status(&WorkspaceTarget::Relative("e+1".to_string()), &running),
+        WorkspaceStatus::Dynamic
+    );
+}
+
+#[test]
+fn test_workspace_dispatchers() {
+    assert!(is_workspace_dispatcher("workspace"));
+    assert!(is_workspace_dispatcher(" movetoworkspace "));
+    assert!(is_workspace_dispatcher("movetoworkspacesilent"));
+    assert!(!is_workspace_dispatcher("exec"));
+    assert!(!is_workspace_dispatcher("togglespecialworkspace"));
+}
@@ -18,30 +18,42 @@
 //!
 //! This module defines the fundamental types used throughout the application:
 //! - `Modifier`: Keyboard modifier keys (SUPER, CTRL, SHIFT, ALT)
+//! - `Key`: A key name, keycode, mouse button or scroll direction
 //! - `KeyCombo`: A combination of modifiers and a key
-//! - `BindType`: Different types of Hyprland bindings (bind, bindl, binde, etc.)
+//! - `BindType`: Hyprland bind keyword flags (bind, bindl, bindtn, etc.)
 //! - `Keybinding`: Complete keybinding with action and metadata
+//! - `SourceLocation`: File and line a binding was read from
+//! - `Unbind`: An `unbind = MODS, KEY` line cancelling earlier bindings
 //!
 //! All types implement serialization for config persistence and are designed
 //! with security in mind (validation, normalization, consistent hashing).
 
-use serde::{Deserialize, Serialize};
-use std::fmt;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+};
+
+use crate::core::keysyms::normalise_keysym;
 
 /// Keyboard modifier keys
 ///
 /// Represents the four standard modifier keys used in keybindings.
 /// These are mapped from various Hyprland names (e.g., MOD4, WIN → Super).
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+///
+/// The variants are declared in canonical order: a [`KeyCombo`] keeps its
+/// modifiers sorted by it, so `SHIFT_SUPER` and `SUPER_SHIFT` are one combo.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub enum Modifier {
-    /// Super/Windows/Command key (MOD4)
-    Super,
+    /// Alt key (MOD1)
+    Alt,
     /// Control key
     Ctrl,
     /// Shift key
     Shift,
-    /// Alt key (MOD1)
-    Alt,
+    /// Super/Windows/Command key (MOD4)
+    Super,
 }
 
 impl fmt::Display for Modifier {
@@ -55,70 +67,457 @@ impl fmt::Display for Modifier {
     }
 }
 
-/// Type of keybinding
+/// A single bind flag, one of the letters after `bind` in a bind keyword
+///
+/// Hyprland accepts the flags in any order (`bindle` and `bindel` are the
+/// same binding), so a [`BindType`] stores them as a set.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BindFlag {
+    /// `e`: repeats while the key is held
+    Repeat,
+    /// `l`: works on the lock screen
+    Locked,
+    /// `r`: triggers on key release
+    Release,
+    /// `m`: mouse binding
+    Mouse,
+    /// `c`: triggers on a click of the mouse key
+    Click,
+    /// `g`: triggers on a drag with the mouse key
+    Drag,
+    /// `o`: triggers on a long press
+    LongPress,
+    /// `n`: passes the key event on to the focused window
+    NonConsuming,
+    /// `t`: transparent, cannot be shadowed by other bindings
+    Transparent,
+    /// `i`: ignores the modifiers
+    IgnoreMods,
+    /// `s`: separate keys, combining arbitrary keys
+    Separate,
+    /// `d`: has a description (an extra field after the key)
+    Description,
+    /// `p`: bypasses the app's shortcut inhibitor
+    Bypass,
+    /// `u`: universal, works in every submap
+    Universal,
+}
+
+impl BindFlag {
+    /// All flags, in the order they are written in a bind keyword
+    pub const ALL: [BindFlag; 14] = [
+        BindFlag::Repeat,
+        BindFlag::Locked,
+        BindFlag::Release,
+        BindFlag::Mouse,
+        BindFlag::Click,
+        BindFlag::Drag,
+        BindFlag::LongPress,
+        BindFlag::NonConsuming,
+        BindFlag::Transparent,
+        BindFlag::IgnoreMods,
+        BindFlag::Separate,
+        BindFlag::Description,
+        BindFlag::Bypass,
+        BindFlag::Universal,
+    ];
+
+    /// The letter of the flag in a bind keyword
+    pub fn letter(self) -> char {
+        match self {
+            BindFlag::Repeat => 'e',
+            BindFlag::Locked => 'l',
+            BindFlag::Release => 'r',
+            BindFlag::Mouse => 'm',
+            BindFlag::Click => 'c',
+            BindFlag::Drag => 'g',
+            BindFlag::LongPress => 'o',
+            BindFlag::NonConsuming => 'n',
+            BindFlag::Transparent => 't',
+            BindFlag::IgnoreMods => 'i',
+            BindFlag::Separate => 's',
+            BindFlag::Description => 'd',
+            BindFlag::Bypass => 'p',
+            BindFlag::Universal => 'u',
+        }
+    }
+
+    /// Looks up a flag by its letter
+    pub fn from_letter(letter: char) -> Option<Self> {
+        BindFlag::ALL
+            .into_iter()
+            .find(|flag| flag.letter() == letter)
+    }
+
+    /// Short human-readable name, for display
+    pub fn label(self) -> &'static str {
+        match self {
+            BindFlag::Repeat => "repeat",
+            BindFlag::Locked => "locked",
+            BindFlag::Release => "release",
+            BindFlag::Mouse => "mouse",
+            BindFlag::Click => "click",
+            BindFlag::Drag => "drag",
+            BindFlag::LongPress => "long press",
+            BindFlag::NonConsuming => "non-consuming",
+            BindFlag::Transparent => "transparent",
+            BindFlag::IgnoreMods => "ignore mods",
+            BindFlag::Separate => "separate",
+            BindFlag::Description => "description",
+            BindFlag::Bypass => "bypass inhibitor",
+            BindFlag::Universal => "universal",
+        }
+    }
+
+    /// What the flag does, in a sentence (for the help window)
+    pub fn description(self) -> &'static str {
+        match self {
+            BindFlag::Repeat => "Repeats the action while the key is held down.",
+            BindFlag::Locked => {
+                "Also works while an input inhibitor such as a lock screen is active."
+            }
+            BindFlag::Release => "Triggers when the key is released instead of pressed.",
+            BindFlag::Mouse => {
+                "Mouse binding: the action follows the pointer while the mouse button is held \
+                 (movewindow, resizewindow)."
+            }
+            BindFlag::Click => {
+                "Triggers on a click of the key (pressed and released without moving the mouse)."
+            }
+            BindFlag::Drag => "Triggers on a drag: the key is released after the mouse moved.",
+            BindFlag::LongPress => "Triggers when the key is held down for a while.",
+            BindFlag::NonConsuming => "Passes the key event on to the focused window as well.",
+            BindFlag::Transparent => "Cannot be shadowed: other bindings on the same key also run.",
+            BindFlag::IgnoreMods => "Triggers whatever modifiers are held.",
+            BindFlag::Separate => {
+                "Separate keys: the combo may combine arbitrary keys (`SUPER_L&A`) instead of \
+                 modifiers and one key."
+            }
+            BindFlag::Description => "Takes a description as an extra field after the key.",
+            BindFlag::Bypass => "Bypasses the shortcut inhibitor of the focused app.",
+            BindFlag::Universal => "Works in every submap, not only the one it is declared in.",
+        }
+    }
+
+    fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+/// Type of keybinding: `bind` plus any combination of flags
 ///
-/// Hyprland supports six different binding types with different behaviours:
+/// Hyprland allows any combination of [`BindFlag`]s in any order, e.g.
+/// `bindle`, `bindtn` or `bindd`. The common combinations are available as
+/// constants named after the keyword:
 /// - `Bind`: Standard binding
 /// - `BindE`: Repeat while key is held (e for "repeat")
 /// - `BindL`: Works on locked screen (l for "locked")
 /// - `BindM`: Mouse binding (m for "mouse")
 /// - `BindR`: Trigger on key release (r for "release")
 /// - `BindEL`: Combination of BindE and BindL
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
-pub enum BindType {
+///
+/// Displays and serialises as the keyword (`bindel`). Deserialising also
+/// accepts the names of the former enum variants (`"BindEL"`), so older
+/// exports still load.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BindType {
+    flags: u16,
+}
+
+#[allow(non_upper_case_globals)]
+impl BindType {
     /// Standard keybinding
-    Bind,
+    pub const Bind: BindType = BindType { flags: 0 };
     /// Repeat on hold
-    BindE,
+    pub const BindE: BindType = BindType {
+        flags: 1 << BindFlag::Repeat as u16,
+    };
     /// Works on locked screen
-    BindL,
+    pub const BindL: BindType = BindType {
+        flags: 1 << BindFlag::Locked as u16,
+    };
     /// Mouse binding
-    BindM,
+    pub const BindM: BindType = BindType {
+        flags: 1 << BindFlag::Mouse as u16,
+    };
     /// Trigger on release
-    BindR,
+    pub const BindR: BindType = BindType {
+        flags: 1 << BindFlag::Release as u16,
+    };
     /// Repeat on hold + locked screen
-    BindEL,
+    pub const BindEL: BindType = BindType {
+        flags: 1 << BindFlag::Repeat as u16 | 1 << BindFlag::Locked as u16,
+    };
+}
+
+impl BindType {
+    /// Parses a bind keyword (`bind` followed by flag letters)
+    ///
+    /// # Returns
+    /// The bind type, or `None` if the keyword is not `bind` or contains an
+    /// unknown flag letter
+    ///
+    /// # Example
+    /// ```
+    /// use hypr_keybind_manager::core::types::{BindFlag, BindType};
+    ///
+    /// let bind_type = BindType::from_keyword("bindle").unwrap();
+    /// assert_eq!(bind_type, BindType::BindEL);
+    /// assert!(bind_type.contains(BindFlag::Locked));
+    /// assert_eq!(BindType::from_keyword("bindx"), None);
+    /// ```
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        let letters = keyword.strip_prefix("bind")?;
+        letters
+            .chars()
+            .try_fold(BindType::Bind, |bind_type, letter| {
+                BindFlag::from_letter(letter).map(|flag| bind_type.with(flag))
+            })
+    }
+
+    /// Returns whether the flag is set
+    pub fn contains(self, flag: BindFlag) -> bool {
+        self.flags & flag.bit() != 0
+    }
+
+    /// Returns the bind type with the flag set
+    pub fn with(self, flag: BindFlag) -> Self {
+        BindType {
+            flags: self.flags | flag.bit(),
+        }
+    }
+
+    /// Returns the bind type with the flag cleared
+    pub fn without(self, flag: BindFlag) -> Self {
+        BindType {
+            flags: self.flags & !flag.bit(),
+        }
+    }
+
+    /// The set flags, in keyword order
+    pub fn flags(self) -> impl Iterator<Item = BindFlag> {
+        BindFlag::ALL
+            .into_iter()
+            .filter(move |flag| self.contains(*flag))
+    }
 }
 
 impl fmt::Display for BindType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bind")?;
+        for flag in self.flags() {
+            write!(f, "{}", flag.letter())?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for BindType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BindType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keyword = String::deserialize(deserializer)?;
+        // Former enum variant names lowercase to their keywords
+        BindType::from_keyword(&keyword.to_lowercase())
+            .ok_or_else(|| de::Error::custom(format!("invalid bind type '{}'", keyword)))
+    }
+}
+
+/// Direction of a scroll wheel binding (`mouse_up`, `mouse_down`, ...)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ScrollDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl ScrollDirection {
+    /// All directions, in config order
+    pub const ALL: [ScrollDirection; 4] = [
+        ScrollDirection::Up,
+        ScrollDirection::Down,
+        ScrollDirection::Left,
+        ScrollDirection::Right,
+    ];
+
+    /// Lowercase direction name (`up`, `down`, `left`, `right`)
+    pub fn name(self) -> &'static str {
+        match self {
+            ScrollDirection::Up => "up",
+            ScrollDirection::Down => "down",
+            ScrollDirection::Left => "left",
+            ScrollDirection::Right => "right",
+        }
+    }
+}
+
+/// Mouse buttons by their Linux input event code, with display names
+///
+/// Hyprland binds mouse buttons as `mouse:<code>`; these are the codes
+/// mice actually send (`BTN_LEFT` to `BTN_TASK`).
+const MOUSE_BUTTONS: [(u32, &str); 8] = [
+    (272, "Left"),
+    (273, "Right"),
+    (274, "Middle"),
+    (275, "Side"),
+    (276, "Extra"),
+    (277, "Forward"),
+    (278, "Back"),
+    (279, "Task"),
+];
+
+/// The non-modifier part of a key combination
+///
+/// Config spellings are case-insensitive; named keys are stored as their
+/// uppercase XKB name (see [`normalise_keysym`]) for consistent hashing.
+///
+/// | Config | Key | Display |
+/// |--------|-----|---------|
+/// | `K`, `Return`/`enter`, `XF86AudioMute` | `Named` | `K`, `RETURN`, `XF86AUDIOMUTE` |
+/// | `code:28` | `Keycode(28)` | `code:28` |
+/// | `mouse:272` | `MouseButton(272)` | `Mouse Left` |
+/// | `mouse_down` | `Scroll(Down)` | `Scroll Down` |
+///
+/// [`Key::parse`] also reads the display names back, so a combo shown in
+/// the GUI can be typed into the edit dialog as it appears.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Key {
+    /// A key by its XKB name (uppercase, aliases resolved)
+    Named(String),
+    /// A raw keycode (`code:<n>`)
+    Keycode(u32),
+    /// A mouse button by input event code (`mouse:<n>`)
+    MouseButton(u32),
+    /// A scroll wheel direction (`mouse_<direction>`)
+    Scroll(ScrollDirection),
+}
+
+impl Key {
+    /// Reads a key as written in a config or shown by `Display`
+    pub fn parse(name: &str) -> Self {
+        let name = name.trim();
+        let lower = name.to_lowercase();
+
+        if let Some(code) = lower.strip_prefix("code:") {
+            if let Ok(code) = code.trim().parse() {
+                return Key::Keycode(code);
+            }
+        }
+
+        if let Some(button) = lower
+            .strip_prefix("mouse:")
+            .or_else(|| lower.strip_prefix("mouse "))
+        {
+            let button = button.trim();
+            if let Ok(code) = button.parse() {
+                return Key::MouseButton(code);
+            }
+            if let Some((code, _)) = MOUSE_BUTTONS
+                .iter()
+                .find(|(_, label)| label.eq_ignore_ascii_case(button))
+            {
+                return Key::MouseButton(*code);
+            }
+        }
+
+        if let Some(direction) = lower
+            .strip_prefix("mouse_")
+            .or_else(|| lower.strip_prefix("scroll "))
+        {
+            if let Some(direction) = ScrollDirection::ALL
+                .into_iter()
+                .find(|d| d.name() == direction.trim())
+            {
+                return Key::Scroll(direction);
+            }
+        }
+
+        Key::Named(normalise_keysym(name))
+    }
+
+    /// The key as written in a config (`K`, `code:28`, `mouse:272`, `mouse_down`)
+    pub fn config_name(&self) -> String {
+        match self {
+            Key::Named(name) => name.clone(),
+            Key::Keycode(code) => format!("code:{}", code),
+            Key::MouseButton(code) => format!("mouse:{}", code),
+            Key::Scroll(direction) => format!("mouse_{}", direction.name()),
+        }
+    }
+
+    /// Returns whether the key is a mouse button or scroll direction
+    pub fn is_mouse(&self) -> bool {
+        matches!(self, Key::MouseButton(_) | Key::Scroll(_))
+    }
+
+    /// Returns whether a mouse button code is one mice send
+    pub fn is_known_mouse_button(code: u32) -> bool {
+        MOUSE_BUTTONS.iter().any(|(known, _)| *known == code)
+    }
+}
+
+impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            BindType::Bind => write!(f, "bind"),
-            BindType::BindE => write!(f, "binde"),
-            BindType::BindL => write!(f, "bindl"),
-            BindType::BindM => write!(f, "bindm"),
-            BindType::BindR => write!(f, "bindr"),
-            BindType::BindEL => write!(f, "bindel"),
+            Key::Named(name) => write!(f, "{}", name),
+            Key::Keycode(code) => write!(f, "code:{}", code),
+            Key::MouseButton(code) => match MOUSE_BUTTONS.iter().find(|(known, _)| known == code) {
+                Some((_, label)) => write!(f, "Mouse {}", label),
+                None => write!(f, "Mouse {}", code),
+            },
+            Key::Scroll(direction) => {
+                let name = direction.name();
+                write!(f, "Scroll {}{}", name[..1].to_uppercase(), &name[1..])
+            }
         }
     }
 }
 
+impl Serialize for Key {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.config_name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Exports before the enum stored the uppercase config name
+        Ok(Key::parse(&String::deserialize(deserializer)?))
+    }
+}
+
 /// A combination of modifier keys and a base key
 ///
 /// Represents a complete key combination like SUPER+SHIFT+K.
 /// Implements Hash and Eq for use as HashMap keys in conflict detection.
 ///
 /// # Hash Implementation
-/// The hash is based on a **sorted** list of modifiers plus the key name.
-/// This ensures that different orderings of the same modifiers produce
-/// the same hash (e.g., SUPER+SHIFT and SHIFT+SUPER are identical).
+/// Modifiers are a set: equality, the hash and the displayed form all use
+/// the sorted, duplicate-free list, however the combo was built. Different
+/// orderings of the same modifiers are therefore one conflict bucket
+/// (e.g., SUPER+SHIFT and SHIFT+SUPER are identical), even for combos
+/// built as struct literals or deserialised from an export.
 ///
 /// # Example
 /// ```ignore
 /// let combo = KeyCombo {
 ///     modifiers: vec![Modifier::Super, Modifier::Shift],
-///     key: "K".to_string(),
+///     key: Key::Named("K".to_string()),
 /// };
 /// ```
-#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct KeyCombo {
     /// Modifier keys (SUPER, CTRL, SHIFT, ALT)
     /// Stored in a Vec to allow multiple modifiers
     pub modifiers: Vec<Modifier>,
 
-    /// Base key name (e.g., "K", "Return", "F1")
-    /// Always stored in uppercase for consistent hashing
-    pub key: String,
+    /// Base key (e.g., "K", "Return", "F1", "mouse:272")
+    pub key: Key,
 }
 
 impl KeyCombo {
@@ -126,18 +525,74 @@ impl KeyCombo {
     ///
     /// Normalisation includes:
     /// - Sorting modifiers for consistent hashing
-    /// - Converting key to uppercase
+    /// - Reading the key with [`Key::parse`] (uppercase XKB names, so
+    ///   `Return`, `RETURN` and `enter` are one key)
     /// - Removing duplicate modifiers
-    pub fn new(mut modifiers: Vec<Modifier>, key: &str) -> Self {
-        // Sort modifiers for consistent hashing
-        modifiers.sort_by_key(|m| format!("{:?}", m));
+    pub fn new(modifiers: Vec<Modifier>, key: &str) -> Self {
+        Self {
+            modifiers: canonical_modifiers(&modifiers),
+            key: Key::parse(key),
+        }
+    }
+
+    /// The modifiers in canonical order, without duplicates
+    ///
+    /// Equal to `modifiers` for combos built with [`KeyCombo::new`].
+    pub fn canonical_modifiers(&self) -> Vec<Modifier> {
+        canonical_modifiers(&self.modifiers)
+    }
+}
 
-        // Remove duplicates
-        modifiers.dedup();
+/// Sorts a modifier list into [`Modifier`] order and drops duplicates
+fn canonical_modifiers(modifiers: &[Modifier]) -> Vec<Modifier> {
+    let mut modifiers = modifiers.to_vec();
+    modifiers.sort();
+    modifiers.dedup();
+    modifiers
+}
 
-        Self {
-            modifiers,
-            key: key.to_uppercase(),
+impl PartialEq for KeyCombo {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.canonical_modifiers() == other.canonical_modifiers()
+    }
+}
+
+impl Eq for KeyCombo {}
+
+impl Hash for KeyCombo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Must agree with `eq`, so the modifiers are hashed as a set
+        self.canonical_modifiers().hash(state);
+        self.key.hash(state);
+    }
+}
+
+impl KeyCombo {
+    /// Returns the combo as `KeyCombo::new` would have built it
+    ///
+    /// Combos built as struct literals or deserialised from an export may
+    /// have unsorted modifiers or an alias key name.
+    pub fn normalised(&self) -> Self {
+        KeyCombo::new(self.modifiers.clone(), &self.key.config_name())
+    }
+
+    /// Returns whether this is a `catchall` combo
+    ///
+    /// Inside a submap, a catchall binding fires for every key that no
+    /// other binding of the submap handles.
+    pub fn is_catchall(&self) -> bool {
+        matches!(&self.key, Key::Named(name) if name == CATCHALL_KEY)
+    }
+
+    /// The key as written in a config
+    ///
+    /// Named keys are stored uppercase, but `catchall` is a keyword rather
+    /// than a key name and is written in lowercase, as Hyprland expects.
+    pub fn config_key(&self) -> String {
+        if self.is_catchall() {
+            "catchall".to_string()
+        } else {
+            self.key.config_name()
         }
     }
 }
@@ -148,7 +603,7 @@ impl fmt::Display for KeyCombo {
             write!(f, "{}", self.key)
         } else {
             let mods = self
-                .modifiers
+                .canonical_modifiers()
                 .iter()
                 .map(|m| m.to_string())
                 .collect::<Vec<_>>()
@@ -165,18 +620,64 @@ impl fmt::Display for KeyCombo {
 /// - The type of binding (bind, binde, etc.)
 /// - The dispatcher (action) to execute
 /// - Optional arguments for the dispatcher
+/// - The submap it belongs to, if any
+/// - The sourced file it was read from, if not the main config
+/// - Its category, if a `# @category:` annotation tags it
+/// - The exact file and line it was read from, if parsed
+///
+/// # Submaps
+///
+/// Hyprland submaps are named sets of bindings that are only active while
+/// the submap is entered (e.g. a "resize" mode). In the config they are
+/// written as sections:
+///
+/// ```text
+/// bind = SUPER, R, submap, resize
+/// submap = resize
+/// binde = , right, resizeactive, 10 0
+/// bind = , escape, submap, reset
+/// submap = reset
+/// ```
+///
+/// Bindings outside any section are global and have `submap: None`.
+///
+/// # Sourced files
+///
+/// Configs can pull in other files with `source = path`. Bindings read from
+/// such a file record it in `source_file`; bindings in the main config have
+/// `source_file: None`. Writes only ever touch the main config.
+///
+/// # Categories
+///
+/// A `# @category: name` comment right above a bind line tags the binding
+/// with a category (`media`, `launchers`, ...). The writer keeps the
+/// annotation with its bind line, and writes it above new lines.
+///
+/// # Devices
+///
+/// Bind lines inside a `device { name = ... }` (or `device:name { ... }`)
+/// section only apply to that input device and record its name in
+/// `device`. Bindings of different devices never conflict; a binding
+/// without a device applies to every device.
+///
+/// # Locations
+///
+/// The parser records the file and line of every binding in `location`.
+/// It is metadata, not identity: equality ignores it, so a binding that
+/// moved to another line is still the same binding. A location goes stale
+/// once lines above it change, so anything relying on it (such as in-place
+/// line replacement) must check the line still holds the binding.
 ///
 /// # Example
 /// ```ignore
-/// let binding = Keybinding {
-///     key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-///     bind_type: BindType::Bind,
-///     dispatcher: "exec".to_string(),
-///     args: Some("firefox".to_string()),
-/// };
+/// let binding = Keybinding::new(
+///     KeyCombo::new(vec![Modifier::Super], "K"),
+///     "exec",
+///     Some("firefox"),
+/// );
 /// // Represents: bind = SUPER, K, exec, firefox
 /// ```
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Keybinding {
     /// The key combination that triggers this binding
     pub key_combo: KeyCombo,
@@ -193,15 +694,177 @@ pub struct Keybinding {
     /// - workspace: Some("3")
     /// - killactive: None
     pub args: Option<String>,
+
+    /// Submap section the binding is declared in (`None`: global)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submap: Option<String>,
+
+    /// File the binding was sourced from (`None`: the main config)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<PathBuf>,
+
+    /// Description field of a `bindd` binding (written after the key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Category from a `# @category: name` line right above the bind line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+
+    /// Input device section the binding is declared in (`None`: every device)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+
+    /// Where the binding was read from (`None`: not parsed from a file)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+}
+
+impl PartialEq for Keybinding {
+    fn eq(&self, other: &Self) -> bool {
+        // Destructured so a new field cannot be left out by accident
+        let Keybinding {
+            key_combo,
+            bind_type,
+            dispatcher,
+            args,
+            submap,
+            source_file,
+            description,
+            category,
+            device,
+            location: _,
+        } = self;
+
+        *key_combo == other.key_combo
+            && *bind_type == other.bind_type
+            && *dispatcher == other.dispatcher
+            && *args == other.args
+            && *submap == other.submap
+            && *source_file == other.source_file
+            && *description == other.description
+            && *category == other.category
+            && *device == other.device
+    }
+}
+
+impl Eq for Keybinding {}
+
+/// An `unbind = MODS, KEY` line
+///
+/// Hyprland removes every binding of the key combo declared before the
+/// line, in any submap. Bindings declared after it are unaffected, so an
+/// unbind followed by a new `bind` replaces a binding from an earlier
+/// (often sourced) file:
+///
+/// ```text
+/// source = ~/.config/hypr/defaults.conf   # bind = SUPER, Q, killactive
+/// unbind = SUPER, Q
+/// bind = SUPER, Q, exec, kitty
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unbind {
+    /// The key combo whose bindings are removed
+    pub key_combo: KeyCombo,
+}
+
+impl Unbind {
+    /// Returns whether the unbind removes `binding` (if declared before it)
+    pub fn cancels(&self, binding: &Keybinding) -> bool {
+        binding.key_combo == self.key_combo
+    }
+}
+
+/// File and line a binding was read from
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SourceLocation {
+    /// File the line is in (the main config or a sourced file)
+    pub file: PathBuf,
+    /// Line number (1-based)
+    pub line: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file.display(), self.line)
+    }
+}
+
+/// Submap name that leaves the current submap (`submap = reset`)
+pub const SUBMAP_RESET: &str = "reset";
+
+/// Key of a binding that catches every otherwise unbound key (normalised)
+pub const CATCHALL_KEY: &str = "CATCHALL";
+
+impl Keybinding {
+    /// Creates a plain `bind` of a key combo to a dispatcher
+    ///
+    /// Every other field is unset: global, from the main config, without
+    /// description, category, device or location. Set them with struct
+    /// update syntax where needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_combo` - Key combination that triggers the binding
+    /// * `dispatcher` - Hyprland dispatcher name
+    /// * `args` - Arguments of the dispatcher, if any
+    pub fn new(key_combo: KeyCombo, dispatcher: &str, args: Option<&str>) -> Self {
+        Self {
+            key_combo,
+            bind_type: BindType::Bind,
+            dispatcher: dispatcher.to_string(),
+            args: args.map(str::to_string),
+            submap: None,
+            source_file: None,
+            description: None,
+            category: None,
+            device: None,
+            location: None,
+        }
+    }
+
+    /// Returns whether the binding leaves the current submap
+    /// (`submap, reset`)
+    pub fn is_submap_reset(&self) -> bool {
+        self.dispatcher.trim() == "submap"
+            && self.args.as_deref().map(str::trim) == Some(SUBMAP_RESET)
+    }
+
+    /// Returns whether both bindings can fire on the same input device
+    ///
+    /// Bindings of two different device sections never do; a binding
+    /// outside any device section fires on every device.
+    pub fn shares_device_with(&self, other: &Keybinding) -> bool {
+        match (&self.device, &other.device) {
+            (Some(device), Some(other)) => device == other,
+            _ => true,
+        }
+    }
+}
+
+/// Returns the submaps used by the bindings, in order of first use
+pub fn submap_names(bindings: &[Keybinding]) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    for name in bindings
+        .iter()
+        .filter_map(|binding| binding.submap.as_deref())
+    {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
 }
 
 impl fmt::Display for Keybinding {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{} = {}, {}",
-            self.bind_type, self.key_combo, self.dispatcher
-        )?;
+        write!(f, "{} = {}", self.bind_type, self.key_combo)?;
+
+        if self.bind_type.contains(BindFlag::Description) {
+            write!(f, ", {}", self.description.as_deref().unwrap_or_default())?;
+        }
+
+        write!(f, ", {}", self.dispatcher)?;
 
         if let Some(args) = &self.args {
             write!(f, ", {}", args)?;
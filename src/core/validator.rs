@@ -23,8 +23,16 @@
 //! We use WHITELIST validation (allow known-good) rather than BLACKLIST
 //! (block known-bad) because blacklists can be bypassed. Only explicitly
 //! allowed dispatchers, keys, and argument formats are accepted.
+//!
+//! Workspace targets that are valid but look like a mistake (`workspace, 11`,
+//! `name:3`, `e+0`) get [`WorkspaceWarning`]s instead, which never block.
 
-use crate::core::types::Keybinding;
+use crate::core::{
+    dispatchers::{dispatcher_spec, validate_arguments, ArgumentShape},
+    types::{BindFlag, Key, Keybinding},
+    workspace::{parse_workspace_target, WorkspaceTarget},
+};
+use std::fmt;
 use thiserror::Error;
 
 /// Validation errors
@@ -45,59 +53,34 @@ pub enum ValidationError {
     /// Argument exceeds maximum length
     #[error("Argument too long: {0} characters (max 1000)")]
     ArgumentTooLong(usize),
-}
 
-/// Hyprland dispatcher whitelist
-///
-/// Source: https://wiki.hyprland.org/Configuring/Dispatchers/
-/// Last verified: October 2025
-const ALLOWED_DISPATCHERS: &[&str] = &[
-    "exec",
-    "execr",
-    "killactive",
-    "closewindow",
-    "workspace",
-    "movetoworkspace",
-    "togglefloating",
-    "fullscreen",
-    "pseudo",
-    "pin",
-    "movefocus",
-    "movewindow",
-    "swapwindow",
-    "centerwindow",
-    "resizeactive",
-    "moveactive",
-    "cyclenext",
-    "focuswindow",
-    "focusmonitor",
-    "splitratio",
-    "toggleopaque",
-    "movecursortocorner",
-    "workspaceopt",
-    "exit",
-    "forcerendererreload",
-    "movecurrentworkspacetomonitor",
-    "focusurgentor",
-    "togglespecialworkspace",
-    "togglegroup",
-    "changegroupactive",
-    "moveintogroup",
-    "moveoutofgroup",
-    "lockgroups",
-    "lockactivegroup",
-    "movegroupwindow",
-    "pass",
-    "sendshortcut",
-    "layoutmsg",
-    "dpms",
-    "submap",
-    "global",
-];
+    /// `bindd` description that would split the bind line
+    #[error("Invalid description '{0}': commas and line breaks are not allowed")]
+    InvalidDescription(String),
+
+    /// Category that would not fit on its `# @category:` line
+    #[error("Invalid category '{0}': line breaks are not allowed")]
+    InvalidCategory(String),
+
+    /// Arguments that do not fit the dispatcher (see the dispatcher catalogue)
+    #[error("Invalid arguments: {0}")]
+    InvalidArguments(String),
+
+    /// Mouse (`bindm`) binding on a key that is not a mouse button
+    #[error("Mouse bindings need a mouse button (e.g. mouse:272), not '{0}'")]
+    MouseBindingKey(String),
+
+    /// Submap or device name that would not fit on its section line
+    #[error("Invalid section name '{0}': braces and line breaks are not allowed")]
+    InvalidSection(String),
+}
 
 /// Validates dispatcher name against whitelist
+///
+/// The whitelist is the dispatcher catalogue,
+/// [`DISPATCHER_CATALOGUE`](crate::core::dispatchers::DISPATCHER_CATALOGUE).
 pub fn validate_dispatcher(name: &str) -> Result<(), ValidationError> {
-    if ALLOWED_DISPATCHERS.contains(&name.to_lowercase().as_str()) {
+    if dispatcher_spec(name).is_some() {
         Ok(())
     } else {
         Err(ValidationError::InvalidDispatcher(name.to_string()))
@@ -160,15 +143,35 @@ pub fn validate_key(key: &str) -> Result<(), ValidationError> {
 ///
 /// Performs all validation checks:
 /// - Dispatcher whitelist
-/// - Key name format
+/// - Key name format (mouse buttons: a code mice send)
+/// - `bindm` bindings use a mouse button
 /// - Argument length limit (1000 chars)
 /// - Shell metacharacter detection
+/// - Arguments fit the dispatcher (see [`validate_arguments`])
+/// - `bindd` description stays a single field
+/// - Category stays on its annotation line
+/// - Submap and device names stay on their section lines
 pub fn validate_keybinding(binding: &Keybinding) -> Result<(), ValidationError> {
     // Validate dispatcher against whitelist
     validate_dispatcher(&binding.dispatcher)?;
 
     // Validate key name
-    validate_key(&binding.key_combo.key)?;
+    match &binding.key_combo.key {
+        Key::Named(name) => validate_key(name)?,
+        Key::MouseButton(code) if !Key::is_known_mouse_button(*code) => {
+            return Err(ValidationError::InvalidKey(binding.key_combo.config_key()));
+        }
+        Key::Keycode(_) | Key::MouseButton(_) | Key::Scroll(_) => {}
+    }
+
+    // Mouse bindings follow the pointer while a button is held
+    if binding.bind_type.contains(BindFlag::Mouse)
+        && !matches!(binding.key_combo.key, Key::MouseButton(_))
+    {
+        return Err(ValidationError::MouseBindingKey(
+            binding.key_combo.config_key(),
+        ));
+    }
 
     // Validate arguments if present
     if let Some(args) = &binding.args {
@@ -181,5 +184,170 @@ pub fn validate_keybinding(binding: &Keybinding) -> Result<(), ValidationError>
         check_shell_metacharacters(args)?;
     }
 
+    // Arguments must have the shape the dispatcher expects
+    validate_arguments(&binding.dispatcher, binding.args.as_deref())
+        .map_err(ValidationError::InvalidArguments)?;
+
+    // A description is its own comma-separated field on the bind line
+    if let Some(description) = &binding.description {
+        if description.len() > 1000 {
+            return Err(ValidationError::ArgumentTooLong(description.len()));
+        }
+        if description.contains([',', '\n', '\r']) {
+            return Err(ValidationError::InvalidDescription(description.clone()));
+        }
+    }
+
+    // A category is written as a comment line of its own
+    if let Some(category) = &binding.category {
+        if category.len() > 1000 {
+            return Err(ValidationError::ArgumentTooLong(category.len()));
+        }
+        if category.contains(['\n', '\r']) || category.trim().is_empty() {
+            return Err(ValidationError::InvalidCategory(category.clone()));
+        }
+    }
+
+    // Submap and device names open a section of their own (`submap = name`,
+    // `device { name = ... }`); imported files can set them to anything
+    for section in [&binding.submap, &binding.device].into_iter().flatten() {
+        if section.len() > 1000 {
+            return Err(ValidationError::ArgumentTooLong(section.len()));
+        }
+        if section.contains(['{', '}', '\n', '\r']) || section.trim().is_empty() {
+            return Err(ValidationError::InvalidSection(section.clone()));
+        }
+    }
+
     Ok(())
 }
+
+/// Highest workspace the number row reaches with the usual bindings
+/// (`SUPER, 1` to `SUPER, 0` for workspaces 1 to 10)
+pub const USUAL_WORKSPACE_LIMIT: i32 = 10;
+
+/// A workspace target that is valid but probably not what was meant
+///
+/// Unlike a [`ValidationError`], a warning never blocks a commit: a config
+/// with 20 workspaces legitimately binds `workspace, 11`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WorkspaceWarning {
+    /// Numbered workspace above [`USUAL_WORKSPACE_LIMIT`]
+    OutOfRange { id: i32 },
+    /// Relative target that stays on the current workspace (`+0`, `e-0`)
+    ZeroOffset { selector: String },
+    /// Relative target jumping past [`USUAL_WORKSPACE_LIMIT`] workspaces
+    LargeOffset { selector: String },
+    /// `name:` followed by a number, a named workspace rather than that id
+    NumericName { name: String },
+    /// Text after the workspace, which the dispatcher ignores
+    IgnoredText { text: String },
+}
+
+impl WorkspaceWarning {
+    /// How to write what was probably meant, if that is clear
+    pub fn suggestion(&self) -> Option<String> {
+        match self {
+            WorkspaceWarning::NumericName { name } => {
+                Some(format!("Use '{}' for the numbered workspace", name))
+            }
+            WorkspaceWarning::IgnoredText { text } => Some(format!("Remove ', {}'", text)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for WorkspaceWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceWarning::OutOfRange { id } => write!(
+                f,
+                "workspace {} is above {}, the last one the number row usually reaches",
+                id, USUAL_WORKSPACE_LIMIT
+            ),
+            WorkspaceWarning::ZeroOffset { selector } => write!(
+                f,
+                "'{}' moves by zero workspaces, so it does nothing",
+                selector
+            ),
+            WorkspaceWarning::LargeOffset { selector } => write!(
+                f,
+                "'{}' jumps more than {} workspaces",
+                selector, USUAL_WORKSPACE_LIMIT
+            ),
+            WorkspaceWarning::NumericName { name } => write!(
+                f,
+                "'name:{}' is a workspace named \"{}\", not workspace {}",
+                name, name, name
+            ),
+            WorkspaceWarning::IgnoredText { text } => {
+                write!(f, "'{}' after the workspace is ignored", text)
+            }
+        }
+    }
+}
+
+/// Checks the workspace argument of a workspace dispatcher for sane values
+///
+/// Only dispatchers that take a workspace (see
+/// [`ArgumentShape::Workspace`]) are checked. Targets that do not parse are
+/// left to [`validate_keybinding`], which rejects them.
+///
+/// # Arguments
+/// * `dispatcher` - Dispatcher name (e.g. "workspace")
+/// * `args` - Dispatcher arguments, if any
+///
+/// # Returns
+/// Warnings about the target, in argument order (empty if it looks right)
+pub fn workspace_warnings(dispatcher: &str, args: Option<&str>) -> Vec<WorkspaceWarning> {
+    let Some(spec) = dispatcher_spec(dispatcher) else {
+        return Vec::new();
+    };
+    let Some(args) = args else {
+        return Vec::new();
+    };
+    if spec.arguments != ArgumentShape::Workspace {
+        return Vec::new();
+    }
+    let Ok(target) = parse_workspace_target(args) else {
+        return Vec::new();
+    };
+
+    let mut warnings = Vec::new();
+    match target {
+        WorkspaceTarget::Id(id) if id > USUAL_WORKSPACE_LIMIT => {
+            warnings.push(WorkspaceWarning::OutOfRange { id });
+        }
+        WorkspaceTarget::Name(name) if name.parse::<i32>().is_ok() => {
+            warnings.push(WorkspaceWarning::NumericName { name });
+        }
+        WorkspaceTarget::Relative(selector) => {
+            // `+1`, `e+1`, `m-2`, `r+1`: the offset follows the sign
+            let offset = selector
+                .find(['+', '-'])
+                .and_then(|sign| selector[sign + 1..].parse::<i32>().ok());
+            match offset {
+                Some(0) => warnings.push(WorkspaceWarning::ZeroOffset { selector }),
+                Some(offset) if offset > USUAL_WORKSPACE_LIMIT => {
+                    warnings.push(WorkspaceWarning::LargeOffset { selector });
+                }
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+
+    // Moving dispatchers take a window after the comma; the others nothing
+    if !spec.name.starts_with("movetoworkspace") {
+        if let Some((_, rest)) = args.split_once(',') {
+            let rest = rest.trim();
+            if !rest.is_empty() {
+                warnings.push(WorkspaceWarning::IgnoredText {
+                    text: rest.to_string(),
+                });
+            }
+        }
+    }
+
+    warnings
+}
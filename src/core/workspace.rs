@@ -0,0 +1,181 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workspace targets of `workspace`-style dispatchers
+//!
+//! Parses the workspace selector in the arguments of `workspace`,
+//! `movetoworkspace` and `movetoworkspacesilent`, and checks it against the
+//! workspaces that currently exist in a running Hyprland instance.
+//!
+//! Hyprland creates numbered and named workspaces on first use, so a target
+//! that does not exist yet is not an error; the check distinguishes
+//! "exists" from "will be created" so the edit dialog can say which.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::core::workspace::{
+//!     parse_workspace_target, workspace_status, WorkspaceInfo, WorkspaceStatus, WorkspaceTarget,
+//! };
+//!
+//! let target = parse_workspace_target("name:web").unwrap();
+//! assert_eq!(target, WorkspaceTarget::Name("web".to_string()));
+//!
+//! let running = vec![WorkspaceInfo { id: 3, name: "web".to_string(), windows: 2 }];
+//! assert_eq!(workspace_status(&target, &running), WorkspaceStatus::Exists { windows: 2 });
+//! ```
+
+use serde::Deserialize;
+
+/// Dispatchers whose first argument is a workspace selector
+pub const WORKSPACE_DISPATCHERS: &[&str] =
+    &["workspace", "movetoworkspace", "movetoworkspacesilent"];
+
+/// A workspace as reported by `hyprctl workspaces`
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub struct WorkspaceInfo {
+    /// Workspace id (negative for special workspaces)
+    pub id: i32,
+    /// Workspace name (the id as text for numbered workspaces)
+    pub name: String,
+    /// Number of windows on the workspace
+    pub windows: u16,
+}
+
+/// Workspace selected by a dispatcher argument
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WorkspaceTarget {
+    /// Numbered workspace (`3`)
+    Id(i32),
+    /// Named workspace (`name:web`)
+    Name(String),
+    /// Special workspace (`special` or `special:scratch`)
+    Special(Option<String>),
+    /// Resolved at runtime (`+1`, `e-1`, `m+1`, `r+1`, `previous`, `empty`)
+    Relative(String),
+}
+
+/// Whether a target exists in the running Hyprland instance
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WorkspaceStatus {
+    /// Already exists, with this many windows
+    Exists { windows: u16 },
+    /// Does not exist yet; Hyprland creates it on first use
+    WillBeCreated,
+    /// Depends on the focused workspace or monitor, so cannot be checked
+    Dynamic,
+}
+
+/// Returns whether a dispatcher takes a workspace selector
+pub fn is_workspace_dispatcher(dispatcher: &str) -> bool {
+    WORKSPACE_DISPATCHERS
+        .iter()
+        .any(|name| dispatcher.trim().eq_ignore_ascii_case(name))
+}
+
+/// Parses the workspace selector from dispatcher arguments
+///
+/// Only the part before the first comma is considered, so
+/// `movetoworkspace, 3,address:0x1234` parses as workspace 3.
+///
+/// # Arguments
+///
+/// * `args` - Dispatcher arguments
+///
+/// # Returns
+///
+/// * `Ok(WorkspaceTarget)` - Recognised selector
+/// * `Err(String)` - Empty or unrecognised selector, with a reason
+pub fn parse_workspace_target(args: &str) -> Result<WorkspaceTarget, String> {
+    let selector = args.split(',').next().unwrap_or_default().trim();
+
+    if selector.is_empty() {
+        return Err("No workspace given".to_string());
+    }
+
+    if let Some(name) = selector.strip_prefix("name:") {
+        return match name.trim() {
+            "" => Err("'name:' needs a workspace name".to_string()),
+            name => Ok(WorkspaceTarget::Name(name.to_string())),
+        };
+    }
+
+    if selector == "special" {
+        return Ok(WorkspaceTarget::Special(None));
+    }
+    if let Some(name) = selector.strip_prefix("special:") {
+        return match name.trim() {
+            "" => Err("'special:' needs a workspace name".to_string()),
+            name => Ok(WorkspaceTarget::Special(Some(name.to_string()))),
+        };
+    }
+
+    if let Ok(id) = selector.parse::<i32>() {
+        // A leading sign means "relative to the current workspace"
+        if selector.starts_with(['+', '-']) {
+            return Ok(WorkspaceTarget::Relative(selector.to_string()));
+        }
+        return match id {
+            1.. => Ok(WorkspaceTarget::Id(id)),
+            _ => Err(format!("Workspace ids start at 1, got {}", id)),
+        };
+    }
+
+    if is_relative_selector(selector) {
+        return Ok(WorkspaceTarget::Relative(selector.to_string()));
+    }
+
+    Err(format!("Unrecognised workspace '{}'", selector))
+}
+
+/// Checks a target against the workspaces of a running instance
+///
+/// # Arguments
+///
+/// * `target` - Parsed workspace selector
+/// * `existing` - Workspaces reported by Hyprland
+pub fn workspace_status(target: &WorkspaceTarget, existing: &[WorkspaceInfo]) -> WorkspaceStatus {
+    let found = match target {
+        WorkspaceTarget::Id(id) => existing.iter().find(|ws| ws.id == *id),
+        WorkspaceTarget::Name(name) => existing.iter().find(|ws| ws.name == *name),
+        WorkspaceTarget::Special(Some(name)) => {
+            let special_name = format!("special:{}", name);
+            existing.iter().find(|ws| ws.name == special_name)
+        }
+        WorkspaceTarget::Special(None) => existing.iter().find(|ws| ws.name == "special"),
+        WorkspaceTarget::Relative(_) => return WorkspaceStatus::Dynamic,
+    };
+
+    match found {
+        Some(ws) => WorkspaceStatus::Exists {
+            windows: ws.windows,
+        },
+        None => WorkspaceStatus::WillBeCreated,
+    }
+}
+
+/// Recognises selectors resolved relative to the current state
+fn is_relative_selector(selector: &str) -> bool {
+    if matches!(
+        selector,
+        "previous" | "previous_per_monitor" | "empty" | "emptym" | "emptyn" | "emptynm"
+    ) {
+        return true;
+    }
+
+    // e+1 / m-2 / r+1: open, monitor and relative-id cycling
+    let mut chars = selector.chars();
+    matches!(chars.next(), Some('e' | 'm' | 'r'))
+        && matches!(chars.next(), Some('+' | '-'))
+        && chars.as_str().parse::<u32>().is_ok()
+}
@@ -0,0 +1,130 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reload debouncing
+//!
+//! Bulk operations and repeated "apply" presses can ask for several Hyprland
+//! reloads within a few milliseconds. Each reload re-reads the whole config
+//! and re-applies every keyword, so a burst of them causes visible flicker.
+//!
+//! [`ReloadDebouncer`] coalesces a burst of requests into a single reload that
+//! is sent once no new request has arrived for the coalescing window. It
+//! holds no timer of its own: callers pass the current time in, which keeps it
+//! usable from both the GTK main loop and plain tests.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::ipc::debounce::ReloadDebouncer;
+//! use std::time::{Duration, Instant};
+//!
+//! let mut debouncer = ReloadDebouncer::new(Duration::from_millis(300));
+//! let start = Instant::now();
+//!
+//! debouncer.request(start);
+//! debouncer.request(start + Duration::from_millis(100));
+//!
+//! // Still inside the window of the latest request
+//! assert!(!debouncer.poll(start + Duration::from_millis(300)));
+//! // Window elapsed: exactly one reload is due
+//! assert!(debouncer.poll(start + Duration::from_millis(400)));
+//! assert!(!debouncer.poll(start + Duration::from_millis(500)));
+//! ```
+
+use std::time::{Duration, Instant};
+
+/// Default coalescing window for Hyprland reloads
+pub const DEFAULT_RELOAD_WINDOW: Duration = Duration::from_millis(300);
+
+/// Coalesces bursts of reload requests into one reload
+#[derive(Debug, Clone)]
+pub struct ReloadDebouncer {
+    /// Quiet period required after the last request
+    window: Duration,
+    /// When the pending reload becomes due (`None` when idle)
+    deadline: Option<Instant>,
+}
+
+impl Default for ReloadDebouncer {
+    fn default() -> Self {
+        Self::new(DEFAULT_RELOAD_WINDOW)
+    }
+}
+
+impl ReloadDebouncer {
+    /// Creates a debouncer with the given coalescing window
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            deadline: None,
+        }
+    }
+
+    /// Returns the coalescing window
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Records a reload request
+    ///
+    /// Each request pushes the deadline back to `now + window`, so a reload
+    /// is only sent once the burst has gone quiet.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Time of the request
+    ///
+    /// # Returns
+    ///
+    /// `true` if this request started a new burst (the caller should arm a
+    /// timer), `false` if it joined one that is already pending.
+    pub fn request(&mut self, now: Instant) -> bool {
+        let started = self.deadline.is_none();
+        self.deadline = Some(now + self.window);
+        started
+    }
+
+    /// Checks whether the pending reload is due
+    ///
+    /// Returns `true` at most once per burst; the debouncer is idle again
+    /// afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time
+    pub fn poll(&mut self, now: Instant) -> bool {
+        match self.deadline {
+            Some(deadline) if now >= deadline => {
+                self.deadline = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns how long until the pending reload is due
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Duration)` - Time remaining (zero if already due)
+    /// * `None` - No reload pending
+    pub fn time_until_due(&self, now: Instant) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// Returns whether a reload is waiting to be sent
+    pub fn is_pending(&self) -> bool {
+        self.deadline.is_some()
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hyprland event socket listener
+//!
+//! Besides the command socket, Hyprland announces what happens on a second
+//! socket (`.socket2.sock`) as `EVENT>>DATA` lines. [`EventListener`] reads
+//! them on a background thread and hands them out as typed
+//! [`HyprlandEvent`]s, so the GUI's main loop and the daemon can react to
+//! Hyprland reloading its config without polling `hyprctl`.
+//!
+//! Listening never writes to the socket, so it cannot change Hyprland's
+//! state whatever the [`ClientMode`](super::ClientMode).
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::ipc::events::HyprlandEvent;
+//!
+//! assert_eq!(
+//!     HyprlandEvent::parse("configreloaded>>"),
+//!     Some(HyprlandEvent::ConfigReloaded)
+//! );
+//! assert_eq!(
+//!     HyprlandEvent::parse("submap>>resize"),
+//!     Some(HyprlandEvent::Submap(Some("resize".to_string())))
+//! );
+//! ```
+
+use std::{
+    io::{BufRead, BufReader, Read},
+    os::unix::net::UnixStream,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use super::instance::{HyprlandInstance, InstanceLocator, SIGNATURE_ENV_VAR};
+use crate::config::ConfigError;
+
+/// Name of the event socket in the instance's socket directory
+pub const EVENT_SOCKET: &str = ".socket2.sock";
+
+/// An event announced on Hyprland's event socket
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HyprlandEvent {
+    /// `configreloaded`: Hyprland re-read its config, e.g. after
+    /// `hyprctl reload` or an edit with auto-reload on
+    ConfigReloaded,
+    /// `submap>>NAME`: a submap was entered; `None` when back in the global
+    /// map
+    Submap(Option<String>),
+    /// `activewindow>>CLASS,TITLE`: the focused window changed
+    ActiveWindow {
+        /// Window class, empty when no window is focused
+        class: String,
+        /// Window title
+        title: String,
+    },
+    /// Any other event, by name and raw data
+    Other {
+        /// Event name, e.g. `workspace`
+        name: String,
+        /// Everything after `>>`
+        data: String,
+    },
+}
+
+impl HyprlandEvent {
+    /// Parses one line of the event socket
+    ///
+    /// # Returns
+    ///
+    /// The event, or `None` for a line without the `>>` separator
+    pub fn parse(line: &str) -> Option<Self> {
+        let (name, data) = line.trim_end().split_once(">>")?;
+
+        Some(match name {
+            "configreloaded" => HyprlandEvent::ConfigReloaded,
+            "submap" => HyprlandEvent::Submap(Some(data.to_string()).filter(|s| !s.is_empty())),
+            "activewindow" => {
+                // Titles may contain commas, classes do not
+                let (class, title) = data.split_once(',').unwrap_or((data, ""));
+                HyprlandEvent::ActiveWindow {
+                    class: class.to_string(),
+                    title: title.to_string(),
+                }
+            }
+            _ => HyprlandEvent::Other {
+                name: name.to_string(),
+                data: data.to_string(),
+            },
+        })
+    }
+}
+
+/// Reads Hyprland's event socket on a background thread
+///
+/// The thread ends when Hyprland closes the socket or the listener is
+/// dropped.
+pub struct EventListener {
+    rx: Receiver<HyprlandEvent>,
+}
+
+impl EventListener {
+    /// Connects to the event socket of the running Hyprland instance
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(EventListener)` - Connected; events arrive as Hyprland sends them
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    pub fn connect() -> Result<Self, ConfigError> {
+        let instance = InstanceLocator::from_env().current().ok_or_else(|| {
+            ConfigError::HyprlandNotRunning(format!("{} socket", SIGNATURE_ENV_VAR))
+        })?;
+
+        Self::connect_to(&instance)
+    }
+
+    /// Connects to the event socket of a specific instance
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(EventListener)` - Connected; events arrive as Hyprland sends them
+    /// * `Err(ConfigError::HyprlandNotRunning)` - The instance does not answer
+    pub fn connect_to(instance: &HyprlandInstance) -> Result<Self, ConfigError> {
+        let socket_path = instance.socket(EVENT_SOCKET);
+        let stream = UnixStream::connect(&socket_path).map_err(|e| {
+            ConfigError::HyprlandNotRunning(format!("{}: {}", socket_path.display(), e))
+        })?;
+
+        Ok(Self::from_reader(stream))
+    }
+
+    /// Listens to events read from `reader` instead of the socket
+    pub(crate) fn from_reader<R: Read + Send + 'static>(reader: R) -> Self {
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if let Some(event) = HyprlandEvent::parse(&line) {
+                    if tx.send(event).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self { rx }
+    }
+
+    /// Returns the next event if one has arrived, without blocking
+    pub fn try_next(&self) -> Option<HyprlandEvent> {
+        self.rx.try_recv().ok()
+    }
+
+    /// Waits up to `timeout` for the next event
+    ///
+    /// # Errors
+    ///
+    /// * `RecvTimeoutError::Timeout` - Nothing happened in time
+    /// * `RecvTimeoutError::Disconnected` - Hyprland closed the socket
+    pub fn next_timeout(&self, timeout: Duration) -> Result<HyprlandEvent, RecvTimeoutError> {
+        self.rx.recv_timeout(timeout)
+    }
+
+    /// Blocks for each event until Hyprland closes the socket
+    pub fn iter(&self) -> impl Iterator<Item = HyprlandEvent> + '_ {
+        self.rx.iter()
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hyprland instance detection
+//!
+//! Every running Hyprland has a directory named after its instance
+//! signature, holding its command and event sockets: under
+//! `$XDG_RUNTIME_DIR/hypr` since Hyprland 0.40, under `/tmp/hypr` before.
+//! The session's own instance is named by `HYPRLAND_INSTANCE_SIGNATURE`
+//! ([`SIGNATURE_ENV_VAR`]), which [`InstanceLocator::current`] follows;
+//! [`InstanceLocator::instances`] lists every instance, e.g. a nested
+//! Hyprland started from another one, so a client can be pointed at a
+//! specific one with
+//! [`HyprlandClient::with_instance`](super::HyprlandClient::with_instance).
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::ipc::instance::InstanceLocator;
+//! use std::ffi::OsString;
+//!
+//! let locator = InstanceLocator::from_vars(|name| match name {
+//!     "XDG_RUNTIME_DIR" => Some(OsString::from("/nonexistent")),
+//!     _ => None,
+//! });
+//! // No signature in the environment: no current instance
+//! assert_eq!(locator.current(), None);
+//! ```
+
+use std::{
+    env,
+    ffi::OsString,
+    fmt, fs,
+    os::unix::net::UnixStream,
+    path::{Path, PathBuf},
+};
+
+use super::COMMAND_SOCKET;
+use crate::core::compat::HyprlandVersion;
+
+/// Environment variable naming the session's Hyprland instance
+pub const SIGNATURE_ENV_VAR: &str = "HYPRLAND_INSTANCE_SIGNATURE";
+
+/// Socket directories of releases before 0.40
+const LEGACY_SOCKET_BASE: &str = "/tmp/hypr";
+
+/// A Hyprland instance, found by its socket directory
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HyprlandInstance {
+    /// Instance signature, the name of its directory
+    pub signature: String,
+    /// Directory holding its sockets
+    pub directory: PathBuf,
+}
+
+impl HyprlandInstance {
+    /// Path of one of the instance's sockets
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Socket file name, e.g. `.socket2.sock`
+    pub fn socket(&self, name: &str) -> PathBuf {
+        self.directory.join(name)
+    }
+
+    /// Returns whether the instance accepts connections on its command
+    /// socket
+    ///
+    /// Connects without sending anything, so a directory left behind by a
+    /// crashed Hyprland does not count as running.
+    pub fn is_running(&self) -> bool {
+        UnixStream::connect(self.socket(COMMAND_SOCKET)).is_ok()
+    }
+}
+
+/// What a client knows about the instance it talks to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstanceInfo {
+    /// The instance
+    pub instance: HyprlandInstance,
+    /// Its version, `None` when it could not be queried (e.g. in DryRun
+    /// mode)
+    pub version: Option<HyprlandVersion>,
+}
+
+impl fmt::Display for InstanceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version {
+            Some(version) => write!(f, "Hyprland {}", version)?,
+            None => f.write_str("Hyprland")?,
+        }
+        write!(f, " ({})", self.instance.signature)
+    }
+}
+
+/// Socket directories resolved from the environment
+#[derive(Clone, Debug)]
+pub struct InstanceLocator {
+    signature: Option<String>,
+    bases: Vec<PathBuf>,
+}
+
+impl InstanceLocator {
+    /// Resolves the locations from the process environment
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| env::var_os(name))
+    }
+
+    /// Resolves the locations from the given variable lookup
+    ///
+    /// A relative or empty `XDG_RUNTIME_DIR` is ignored, as for
+    /// [`RuntimePaths`](crate::config::paths::RuntimePaths).
+    ///
+    /// # Arguments
+    ///
+    /// * `var` - Returns the value of an environment variable, if set
+    pub fn from_vars(var: impl Fn(&str) -> Option<OsString>) -> Self {
+        let runtime_base = var("XDG_RUNTIME_DIR")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .map(|dir| dir.join("hypr"));
+
+        Self {
+            signature: var(SIGNATURE_ENV_VAR)
+                .and_then(|signature| signature.into_string().ok())
+                .filter(|signature| !signature.is_empty()),
+            bases: runtime_base
+                .into_iter()
+                .chain([PathBuf::from(LEGACY_SOCKET_BASE)])
+                .collect(),
+        }
+    }
+
+    /// The instance named by [`SIGNATURE_ENV_VAR`]
+    ///
+    /// # Returns
+    ///
+    /// * `Some(HyprlandInstance)` - Its command socket exists
+    /// * `None` - The variable is not set, or no directory has the socket
+    pub fn current(&self) -> Option<HyprlandInstance> {
+        self.find(self.signature.as_deref()?)
+    }
+
+    /// The instance with the given signature
+    ///
+    /// # Returns
+    ///
+    /// * `Some(HyprlandInstance)` - Its command socket exists
+    /// * `None` - No socket directory of that name has a command socket
+    pub fn find(&self, signature: &str) -> Option<HyprlandInstance> {
+        // A signature is a single directory name
+        if signature.is_empty() || signature.contains('/') || signature.starts_with('.') {
+            return None;
+        }
+
+        self.bases
+            .iter()
+            .map(|base| base.join(signature))
+            .find(|directory| directory.join(COMMAND_SOCKET).exists())
+            .map(|directory| HyprlandInstance {
+                signature: signature.to_string(),
+                directory,
+            })
+    }
+
+    /// Every instance with a command socket, sorted by signature
+    ///
+    /// Sockets of crashed instances may linger; check
+    /// [`HyprlandInstance::is_running`] before relying on one.
+    pub fn instances(&self) -> Vec<HyprlandInstance> {
+        let mut instances: Vec<HyprlandInstance> = Vec::new();
+        for base in &self.bases {
+            for signature in directory_names(base) {
+                if instances.iter().any(|known| known.signature == signature) {
+                    continue;
+                }
+                if let Some(instance) = self.find(&signature) {
+                    instances.push(instance);
+                }
+            }
+        }
+        instances.sort_by(|a, b| a.signature.cmp(&b.signature));
+        instances
+    }
+
+    /// Returns whether `instance` is the one [`SIGNATURE_ENV_VAR`] names
+    pub fn is_current(&self, instance: &HyprlandInstance) -> bool {
+        self.signature.as_deref() == Some(instance.signature.as_str())
+    }
+}
+
+/// Names of the directories in `base`, empty if it cannot be read
+fn directory_names(base: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(base) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect()
+}
@@ -26,26 +26,52 @@
 //! # Example
 //! ```
 //! use hypr_keybind_manager::ipc::{HyprlandClient, ClientMode};
-//! use hypr_keybind_manager::core::{Keybinding, KeyCombo, Modifier, BindType};
+//! use hypr_keybind_manager::core::{Keybinding, KeyCombo, Modifier};
 //!
 //! // Safe: DryRun mode validates but never sends IPC
 //! let client = HyprlandClient::new(ClientMode::DryRun);
 //!
-//! let binding = Keybinding {
-//!     key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-//!     bind_type: BindType::Bind,
-//!     dispatcher: "exec".to_string(),
-//!     args: Some("firefox".to_string()),
-//! };
+//! let binding = Keybinding::new(
+//!     KeyCombo::new(vec![Modifier::Super], "K"),
+//!     "exec",
+//!     Some("firefox"),
+//! );
 //!
 //! // Validates command but doesn't send to Hyprland
 //! assert!(client.add_bind(&binding).is_ok());
 //! ```
 
-use hyprland::dispatch::{Dispatch, DispatchType};
+pub mod debounce;
+pub mod events;
+pub mod instance;
+pub mod temporary;
+
+use std::{
+    io::{Read, Write},
+    os::unix::net::UnixStream,
+    time::Duration,
+};
+
+use serde::Deserialize;
 
 use crate::config::ConfigError;
-use crate::core::{validator as injection_validator, Keybinding, Modifier};
+use crate::core::{
+    binds_options,
+    compat::HyprlandVersion,
+    drift::LiveBind,
+    environment::{Devices, WindowInfo},
+    validator as injection_validator,
+    workspace::WorkspaceInfo,
+    BindFlag, Keybinding, Modifier,
+};
+use instance::{HyprlandInstance, InstanceInfo, InstanceLocator};
+use temporary::TemporaryBind;
+
+/// Name of the command socket in the instance's socket directory
+const COMMAND_SOCKET: &str = ".socket.sock";
+
+/// How long a read-only query may wait for Hyprland to answer
+const QUERY_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// IPC client operation mode
 ///
@@ -83,9 +109,13 @@ pub enum ClientMode {
 /// // Safe for testing - validates but never sends commands
 /// let client = HyprlandClient::new(ClientMode::DryRun);
 /// ```
+#[derive(Clone)]
 pub struct HyprlandClient {
     /// Operation mode (DryRun/ReadOnly/Live)
     mode: ClientMode,
+
+    /// Instance to talk to; `None` follows `HYPRLAND_INSTANCE_SIGNATURE`
+    instance: Option<HyprlandInstance>,
 }
 
 impl HyprlandClient {
@@ -105,7 +135,67 @@ impl HyprlandClient {
     /// // This client will validate but never actually send commands
     /// ```
     pub fn new(mode: ClientMode) -> Self {
-        Self { mode }
+        Self {
+            mode,
+            instance: None,
+        }
+    }
+
+    /// Talks to `instance` instead of the session's own Hyprland
+    ///
+    /// For when several instances run, e.g. a nested Hyprland; list them
+    /// with [`InstanceLocator::instances`].
+    ///
+    /// # Arguments
+    ///
+    /// * `instance` - The instance to send commands and queries to
+    pub fn with_instance(mut self, instance: HyprlandInstance) -> Self {
+        self.instance = Some(instance);
+        self
+    }
+
+    /// The instance this client talks to, if one can be found
+    ///
+    /// Either the one given to [`with_instance`](Self::with_instance) or the
+    /// one `HYPRLAND_INSTANCE_SIGNATURE` names.
+    pub fn instance(&self) -> Option<HyprlandInstance> {
+        self.instance
+            .clone()
+            .or_else(|| InstanceLocator::from_env().current())
+    }
+
+    /// Returns whether the instance accepts connections
+    ///
+    /// Only connects to the command socket, sending nothing, so it is
+    /// allowed in every mode.
+    pub fn is_running(&self) -> bool {
+        self.instance()
+            .is_some_and(|instance| instance.is_running())
+    }
+
+    /// Describes the instance this client talks to
+    ///
+    /// The version is queried when the mode allows it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(InstanceInfo)` - The instance accepts connections
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No instance found, or it
+    ///   does not answer
+    pub fn instance_info(&self) -> Result<InstanceInfo, ConfigError> {
+        let instance = self.require_instance()?;
+        if !instance.is_running() {
+            return Err(ConfigError::HyprlandNotRunning(format!(
+                "{} does not answer",
+                instance.socket(COMMAND_SOCKET).display()
+            )));
+        }
+        let version = match self.mode {
+            ClientMode::DryRun => None,
+            ClientMode::ReadOnly | ClientMode::Live => self.get_version().ok(),
+        };
+
+        Ok(InstanceInfo { instance, version })
     }
 
     /// Adds a keybinding to Hyprland
@@ -130,16 +220,15 @@ impl HyprlandClient {
     /// # Example
     /// ```
     /// use hypr_keybind_manager::ipc::{HyprlandClient, ClientMode};
-    /// use hypr_keybind_manager::core::{Keybinding, KeyCombo, Modifier, BindType};
+    /// use hypr_keybind_manager::core::{Keybinding, KeyCombo, Modifier};
     ///
     /// let client = HyprlandClient::new(ClientMode::DryRun);
     ///
-    /// let binding = Keybinding {
-    ///     key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-    ///     bind_type: BindType::Bind,
-    ///     dispatcher: "exec".to_string(),
-    ///     args: Some("firefox".to_string()),
-    /// };
+    /// let binding = Keybinding::new(
+    ///     KeyCombo::new(vec![Modifier::Super], "K"),
+    ///     "exec",
+    ///     Some("firefox"),
+    /// );
     ///
     /// // Safe: validates but doesn't send in DryRun mode
     /// assert!(client.add_bind(&binding).is_ok());
@@ -148,6 +237,7 @@ impl HyprlandClient {
         // Layer 1: Validate BEFORE IPC (defence-in-depth!)
         injection_validator::validate_keybinding(binding)
             .map_err(|e| ConfigError::ValidationFailed(e.to_string()))?;
+        ensure_global(binding)?;
 
         // Layer 2: Build command safely (no string interpolation)
         let cmd = self.build_keyword_command("bind", binding);
@@ -163,7 +253,7 @@ impl HyprlandClient {
             )),
             ClientMode::Live => {
                 // Layer 4: Actually send to Hyprland
-                self.send_keyword_command("bind", &cmd)
+                self.send_keyword_command(&binding.bind_type.to_string(), &cmd)
             }
         }
     }
@@ -173,7 +263,9 @@ impl HyprlandClient {
     /// # Defence-in-Depth Process
     ///
     /// Same validation layers as `add_bind()`, ensuring that even
-    /// removal commands are validated for safety.
+    /// removal commands are validated for safety. Hyprland's `unbind`
+    /// only takes the modifiers and key, and removes every binding on
+    /// that combo.
     ///
     /// # Arguments
     ///
@@ -187,16 +279,15 @@ impl HyprlandClient {
     /// # Example
     /// ```
     /// use hypr_keybind_manager::ipc::{HyprlandClient, ClientMode};
-    /// use hypr_keybind_manager::core::{Keybinding, KeyCombo, Modifier, BindType};
+    /// use hypr_keybind_manager::core::{Keybinding, KeyCombo, Modifier};
     ///
     /// let client = HyprlandClient::new(ClientMode::DryRun);
     ///
-    /// let binding = Keybinding {
-    ///     key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-    ///     bind_type: BindType::Bind,
-    ///     dispatcher: "exec".to_string(),
-    ///     args: Some("firefox".to_string()),
-    /// };
+    /// let binding = Keybinding::new(
+    ///     KeyCombo::new(vec![Modifier::Super], "K"),
+    ///     "exec",
+    ///     Some("firefox"),
+    /// );
     ///
     /// // Safe: validates but doesn't send in DryRun mode
     /// assert!(client.remove_bind(&binding).is_ok());
@@ -205,6 +296,7 @@ impl HyprlandClient {
         // Layer 1: Validate (even for removal)
         injection_validator::validate_keybinding(binding)
             .map_err(|e| ConfigError::ValidationFailed(e.to_string()))?;
+        ensure_global(binding)?;
 
         // Layer 2: Build unbind command
         let cmd = self.build_keyword_command("unbind", binding);
@@ -222,6 +314,101 @@ impl HyprlandClient {
         }
     }
 
+    /// Replaces a keybinding in Hyprland with an edited version
+    ///
+    /// Unbinds `old` and binds `new`, so only this binding changes instead
+    /// of the whole config being reloaded. Both bindings are validated
+    /// before anything is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The binding Hyprland has now
+    /// * `new` - The binding to put in its place
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Both validated (DryRun) or sent successfully (Live)
+    /// * `Err(ConfigError)` - Validation failed, mode restriction or IPC error
+    ///
+    /// # Example
+    /// ```
+    /// use hypr_keybind_manager::ipc::{HyprlandClient, ClientMode};
+    /// use hypr_keybind_manager::core::{Keybinding, KeyCombo, Modifier};
+    ///
+    /// let client = HyprlandClient::new(ClientMode::DryRun);
+    ///
+    /// let old = Keybinding::new(
+    ///     KeyCombo::new(vec![Modifier::Super], "B"),
+    ///     "exec",
+    ///     Some("firefox"),
+    /// );
+    /// let new = Keybinding {
+    ///     args: Some("brave".to_string()),
+    ///     ..old.clone()
+    /// };
+    ///
+    /// assert!(client.replace_bind(&old, &new).is_ok());
+    /// ```
+    pub fn replace_bind(&self, old: &Keybinding, new: &Keybinding) -> Result<(), ConfigError> {
+        // Layer 1: Validate both, so a bad new binding never leaves the old
+        // one unbound
+        for binding in [old, new] {
+            injection_validator::validate_keybinding(binding)
+                .map_err(|e| ConfigError::ValidationFailed(e.to_string()))?;
+            ensure_global(binding)?;
+        }
+
+        self.remove_bind(old)?;
+        self.add_bind(new)
+    }
+
+    /// Binds a key for a trial, until the returned guard goes away
+    ///
+    /// Bindings Hyprland already has on the combo would fire as well, so
+    /// they are unbound first and rebound by the guard afterwards. Only
+    /// global bindings can be tried, as for [`Self::add_bind`].
+    ///
+    /// # Arguments
+    ///
+    /// * `binding` - The binding to try
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TemporaryBind)` - Bound (Live) or validated (DryRun); dropping
+    ///   or finishing the guard unbinds it
+    /// * `Err(ConfigError::ValidationFailed)` - Injection attempt blocked
+    /// * `Err(ConfigError::IpcCommandFailed)` - Read-only mode, a submap
+    ///   binding, or IPC failure
+    pub fn add_temporary_bind(&self, binding: &Keybinding) -> Result<TemporaryBind, ConfigError> {
+        injection_validator::validate_keybinding(binding)
+            .map_err(|e| ConfigError::ValidationFailed(e.to_string()))?;
+        ensure_global(binding)?;
+
+        let displaced = match self.mode {
+            ClientMode::DryRun => Vec::new(),
+            ClientMode::ReadOnly => {
+                return Err(ConfigError::IpcCommandFailed(
+                    "Client in read-only mode - cannot modify bindings".to_string(),
+                ))
+            }
+            ClientMode::Live => self
+                .list_binds()?
+                .into_iter()
+                .filter(|live| live.submap.is_none() && live.key_combo == binding.key_combo)
+                .collect(),
+        };
+        if !displaced.is_empty() {
+            self.remove_bind(binding)?;
+        }
+
+        // From here on the guard restores the displaced bindings, even if
+        // the trial binding is refused
+        let guard = TemporaryBind::new(self.clone(), binding.clone(), displaced);
+        self.add_bind(binding)?;
+
+        Ok(guard)
+    }
+
     /// Reloads Hyprland configuration from file
     ///
     /// This triggers Hyprland to re-read its config file, applying all
@@ -251,6 +438,305 @@ impl HyprlandClient {
         }
     }
 
+    /// Runs a binding's action in the running Hyprland instance
+    ///
+    /// Sends `dispatch <dispatcher> <args>`, exactly as if the binding's key
+    /// had been pressed. The binding goes through the same injection
+    /// validation as [`Self::add_bind`] first.
+    ///
+    /// # Arguments
+    ///
+    /// * `binding` - The binding whose dispatcher and arguments to run
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Action validated (DryRun) or dispatched (Live)
+    /// * `Err(ConfigError::ValidationFailed)` - Injection attempt blocked
+    /// * `Err(ConfigError::IpcCommandFailed)` - Read-only mode or Hyprland refused it
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    ///
+    /// # Example
+    /// ```
+    /// use hypr_keybind_manager::ipc::{HyprlandClient, ClientMode};
+    /// use hypr_keybind_manager::core::{Keybinding, KeyCombo, Modifier};
+    ///
+    /// let client = HyprlandClient::new(ClientMode::DryRun);
+    ///
+    /// let binding = Keybinding::new(
+    ///     KeyCombo::new(vec![Modifier::Super], "B"),
+    ///     "exec",
+    ///     Some("firefox"),
+    /// );
+    ///
+    /// // Safe: validates but doesn't dispatch in DryRun mode
+    /// assert!(client.dispatch(&binding).is_ok());
+    /// ```
+    pub fn dispatch(&self, binding: &Keybinding) -> Result<(), ConfigError> {
+        // Layer 1: Validate BEFORE IPC (defence-in-depth!)
+        injection_validator::validate_keybinding(binding)
+            .map_err(|e| ConfigError::ValidationFailed(e.to_string()))?;
+
+        // Layer 2: Build the dispatch request
+        let mut request = String::from("dispatch ");
+        request.push_str(&binding.dispatcher);
+        if let Some(args) = &binding.args {
+            request.push(' ');
+            request.push_str(args);
+        }
+
+        // Layer 3: Mode check
+        match self.mode {
+            ClientMode::DryRun => Ok(()),
+            ClientMode::ReadOnly => Err(ConfigError::IpcCommandFailed(
+                "Client in read-only mode - cannot dispatch".to_string(),
+            )),
+            ClientMode::Live => {
+                // Layer 4: Send to Hyprland, which answers "ok" on success
+                let reply = self.send_request(&request)?;
+                if reply.trim() == "ok" {
+                    Ok(())
+                } else {
+                    Err(ConfigError::IpcCommandFailed(format!(
+                        "Hyprland refused the dispatch: {}",
+                        reply.trim()
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Sets a `binds` option in the running Hyprland instance
+    ///
+    /// Sends the `binds:name` keyword, so the option takes effect without a
+    /// reload. The option must be one of
+    /// [`BINDS_OPTIONS`](crate::core::binds_options::BINDS_OPTIONS) and the
+    /// value must fit its kind, so nothing else can be smuggled into the
+    /// keyword.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Option name inside the `binds` section
+    /// * `value` - New value
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Option validated (DryRun) or set (Live)
+    /// * `Err(ConfigError)` - Unknown option, bad value, mode restriction or IPC error
+    ///
+    /// # Example
+    /// ```
+    /// use hypr_keybind_manager::ipc::{HyprlandClient, ClientMode};
+    ///
+    /// let client = HyprlandClient::new(ClientMode::DryRun);
+    ///
+    /// assert!(client.set_binds_option("pass_mouse_when_bound", "true").is_ok());
+    /// assert!(client.set_binds_option("pass_mouse_when_bound", "true; exit").is_err());
+    /// ```
+    pub fn set_binds_option(&self, name: &str, value: &str) -> Result<(), ConfigError> {
+        // Layer 1: Only known options with well-formed values
+        binds_options::validate_binds_option(name, value).map_err(ConfigError::ValidationFailed)?;
+
+        match self.mode {
+            ClientMode::DryRun => Ok(()),
+            ClientMode::ReadOnly => Err(ConfigError::IpcCommandFailed(
+                "Client in read-only mode - cannot change options".to_string(),
+            )),
+            ClientMode::Live => self.send_keyword_command(&format!("binds:{}", name), value),
+        }
+    }
+
+    /// Lists the workspaces of the running Hyprland instance
+    ///
+    /// Read-only: allowed in `ReadOnly` and `Live` mode. `DryRun` never
+    /// talks to Hyprland, so it returns an error instead.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<WorkspaceInfo>)` - Current workspaces with window counts
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    /// * `Err(ConfigError::IpcCommandFailed)` - DryRun mode or unexpected reply
+    ///
+    /// # Example
+    /// ```
+    /// use hypr_keybind_manager::ipc::{HyprlandClient, ClientMode};
+    ///
+    /// // DryRun never queries Hyprland
+    /// let client = HyprlandClient::new(ClientMode::DryRun);
+    /// assert!(client.workspaces().is_err());
+    /// ```
+    pub fn workspaces(&self) -> Result<Vec<WorkspaceInfo>, ConfigError> {
+        let reply = self.query("j/workspaces")?;
+        serde_json::from_str(&reply).map_err(|e| {
+            ConfigError::IpcCommandFailed(format!("Unexpected reply to workspaces query: {}", e))
+        })
+    }
+
+    /// Returns the focused workspace of the running Hyprland instance
+    ///
+    /// Read-only, like [`workspaces`](Self::workspaces).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WorkspaceInfo)` - The workspace on the focused monitor
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    /// * `Err(ConfigError::IpcCommandFailed)` - DryRun mode or unexpected reply
+    pub fn get_active_workspace(&self) -> Result<WorkspaceInfo, ConfigError> {
+        let reply = self.query("j/activeworkspace")?;
+        serde_json::from_str(&reply).map_err(|e| {
+            ConfigError::IpcCommandFailed(format!(
+                "Unexpected reply to activeworkspace query: {}",
+                e
+            ))
+        })
+    }
+
+    /// Returns the focused window of the running Hyprland instance
+    ///
+    /// Read-only, like [`workspaces`](Self::workspaces).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(WindowInfo))` - The focused window
+    /// * `Ok(None)` - No window has focus, e.g. on an empty workspace
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    /// * `Err(ConfigError::IpcCommandFailed)` - DryRun mode or unexpected reply
+    pub fn active_window(&self) -> Result<Option<WindowInfo>, ConfigError> {
+        let reply = self.query("j/activewindow")?;
+        let window: WindowInfo = serde_json::from_str(&reply).map_err(|e| {
+            ConfigError::IpcCommandFailed(format!("Unexpected reply to activewindow query: {}", e))
+        })?;
+
+        Ok(Some(window).filter(WindowInfo::is_window))
+    }
+
+    /// Returns the keyboards and mice of the running Hyprland instance
+    ///
+    /// Read-only, like [`workspaces`](Self::workspaces). The names are the
+    /// ones device-specific bindings refer to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Devices)` - Keyboards with their layouts, and mice
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    /// * `Err(ConfigError::IpcCommandFailed)` - DryRun mode or unexpected reply
+    pub fn get_devices(&self) -> Result<Devices, ConfigError> {
+        let reply = self.query("j/devices")?;
+        serde_json::from_str(&reply).map_err(|e| {
+            ConfigError::IpcCommandFailed(format!("Unexpected reply to devices query: {}", e))
+        })
+    }
+
+    /// Returns the bindings the running Hyprland instance has loaded
+    ///
+    /// Read-only, like [`workspaces`](Self::workspaces).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<LiveBind>)` - Every live binding, as `hyprctl binds -j`
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    /// * `Err(ConfigError::IpcCommandFailed)` - DryRun mode or unexpected reply
+    ///
+    /// # Example
+    /// ```
+    /// use hypr_keybind_manager::ipc::{ClientMode, HyprlandClient};
+    ///
+    /// let client = HyprlandClient::new(ClientMode::DryRun);
+    /// assert!(client.binds().is_err());
+    /// ```
+    pub fn binds(&self) -> Result<Vec<LiveBind>, ConfigError> {
+        let reply = self.query("j/binds")?;
+        serde_json::from_str(&reply).map_err(|e| {
+            ConfigError::IpcCommandFailed(format!("Unexpected reply to binds query: {}", e))
+        })
+    }
+
+    /// Returns the running Hyprland instance's bindings as [`Keybinding`]s
+    ///
+    /// Read-only, like [`binds`](Self::binds), whose reply is converted
+    /// with [`LiveBind::to_keybinding`]. The bindings have no source file
+    /// or location.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Keybinding>)` - Every live binding, in Hyprland's order
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    /// * `Err(ConfigError::IpcCommandFailed)` - DryRun mode or unexpected reply
+    ///
+    /// # Example
+    /// ```
+    /// use hypr_keybind_manager::ipc::{ClientMode, HyprlandClient};
+    ///
+    /// let client = HyprlandClient::new(ClientMode::DryRun);
+    /// assert!(client.list_binds().is_err());
+    /// ```
+    pub fn list_binds(&self) -> Result<Vec<Keybinding>, ConfigError> {
+        Ok(self.binds()?.iter().map(LiveBind::to_keybinding).collect())
+    }
+
+    /// Returns the version of the running Hyprland instance
+    ///
+    /// Read-only, like [`workspaces`](Self::workspaces). Newer releases
+    /// report a `version` field; older ones only the release `tag`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HyprlandVersion)` - Version of the running compositor
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    /// * `Err(ConfigError::IpcCommandFailed)` - DryRun mode or unexpected reply
+    pub fn get_version(&self) -> Result<HyprlandVersion, ConfigError> {
+        let reply = self.query("j/version")?;
+        let reply: VersionReply = serde_json::from_str(&reply).map_err(|e| {
+            ConfigError::IpcCommandFailed(format!("Unexpected reply to version query: {}", e))
+        })?;
+
+        [reply.version, reply.tag]
+            .into_iter()
+            .flatten()
+            .find_map(|text| HyprlandVersion::parse(&text))
+            .ok_or_else(|| {
+                ConfigError::IpcCommandFailed("Version reply has no version number".to_string())
+            })
+    }
+
+    /// Sends a read-only request over the Hyprland command socket
+    ///
+    /// Talks to the socket directly rather than through the `hyprland`
+    /// crate, whose data queries panic when Hyprland is not running.
+    fn query(&self, request: &str) -> Result<String, ConfigError> {
+        if self.mode == ClientMode::DryRun {
+            return Err(ConfigError::IpcCommandFailed(
+                "Client in dry-run mode - never queries Hyprland".to_string(),
+            ));
+        }
+
+        self.send_request(request)
+    }
+
+    /// Sends a request over the Hyprland command socket and returns the reply
+    ///
+    /// Callers check the mode first: this always talks to Hyprland.
+    fn send_request(&self, request: &str) -> Result<String, ConfigError> {
+        let socket_path = self.require_instance()?.socket(COMMAND_SOCKET);
+        let mut stream = UnixStream::connect(&socket_path).map_err(|e| {
+            ConfigError::HyprlandNotRunning(format!("{}: {}", socket_path.display(), e))
+        })?;
+        stream.set_read_timeout(Some(QUERY_TIMEOUT))?;
+        stream.set_write_timeout(Some(QUERY_TIMEOUT))?;
+
+        stream.write_all(request.as_bytes())?;
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply)?;
+
+        Ok(reply)
+    }
+
+    /// The instance to talk to, or why there is none
+    fn require_instance(&self) -> Result<HyprlandInstance, ConfigError> {
+        self.instance().ok_or_else(|| {
+            ConfigError::HyprlandNotRunning(format!("{} socket", instance::SIGNATURE_ENV_VAR))
+        })
+    }
+
     /// Builds a keyword command string safely
     ///
     /// This constructs the command using safe concatenation, NOT string
@@ -267,20 +753,21 @@ impl HyprlandClient {
     /// ```text
     /// MODIFIERS, KEY, dispatcher, args
     /// ```
+    /// or just `MODIFIERS, KEY` for "unbind"
     ///
     /// # Safety
     ///
     /// This method assumes the binding has already passed validation.
     /// It builds the command by concatenating validated components,
     /// not by interpolating user input into a format string.
-    fn build_keyword_command(&self, _keyword: &str, binding: &Keybinding) -> String {
+    fn build_keyword_command(&self, keyword: &str, binding: &Keybinding) -> String {
         // Build modifiers string (e.g., "SUPER_SHIFT")
         let modifiers_str = if binding.key_combo.modifiers.is_empty() {
             String::new()
         } else {
             binding
                 .key_combo
-                .modifiers
+                .canonical_modifiers()
                 .iter()
                 .map(|m| match m {
                     Modifier::Super => "SUPER",
@@ -301,7 +788,17 @@ impl HyprlandClient {
         }
 
         // Add key
-        parts.push(binding.key_combo.key.clone());
+        parts.push(binding.key_combo.config_key());
+
+        // Unbinding only needs the combo
+        if keyword == "unbind" {
+            return parts.join(", ");
+        }
+
+        // Add description (`bindd` and other `d` variants)
+        if binding.bind_type.contains(BindFlag::Description) {
+            parts.push(binding.description.clone().unwrap_or_default());
+        }
 
         // Add dispatcher
         parts.push(binding.dispatcher.clone());
@@ -330,47 +827,61 @@ impl HyprlandClient {
     /// * `Ok(())` - Command sent successfully
     /// * `Err(ConfigError)` - Hyprland not running or command failed
     fn send_keyword_command(&self, keyword: &str, value: &str) -> Result<(), ConfigError> {
-        use hyprland::keyword::Keyword;
-
-        // Attempt to send the command
-        Keyword::set(keyword, value).map_err(|e| {
-            // Check if Hyprland is not running
-            if e.to_string().contains("No such file or directory") {
-                ConfigError::HyprlandNotRunning(
-                    "Hyprland IPC socket not found - is Hyprland running?".to_string(),
-                )
-            } else {
-                ConfigError::IpcCommandFailed(format!("Failed to send keyword command: {}", e))
-            }
-        })?;
-
-        Ok(())
+        // Same socket as queries, so the selected instance is honoured
+        let reply = self.send_request(&["keyword ", keyword, " ", value].concat())?;
+        if reply.trim() == "ok" {
+            Ok(())
+        } else {
+            Err(ConfigError::IpcCommandFailed(format!(
+                "Failed to send keyword command: {}",
+                reply.trim()
+            )))
+        }
     }
 
     /// Sends a reload command to Hyprland
     ///
-    /// Uses the exec dispatcher to trigger a config reload via hyprctl.
-    /// This is the standard way to reload Hyprland configuration.
+    /// Sends `reload` over the command socket, as `hyprctl reload` does,
+    /// without starting a `hyprctl` process.
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Reload command sent successfully
-    /// * `Err(ConfigError)` - Hyprland not running or command failed
+    /// * `Ok(())` - Hyprland answered "ok"
+    /// * `Err(ConfigError::HyprlandNotRunning)` - No Hyprland socket found
+    /// * `Err(ConfigError::IpcCommandFailed)` - Hyprland refused the reload
     fn send_reload_command(&self) -> Result<(), ConfigError> {
-        // Use exec dispatcher to run hyprctl reload
-        Dispatch::call(DispatchType::Exec("hyprctl reload")).map_err(|e| {
-            if e.to_string().contains("No such file or directory") {
-                ConfigError::HyprlandNotRunning(
-                    "Hyprland IPC socket not found - is Hyprland running?".to_string(),
-                )
-            } else {
-                ConfigError::IpcCommandFailed(format!("Failed to reload config: {}", e))
-            }
-        })?;
+        let reply = self.send_request("reload")?;
+        if reply.trim() == "ok" {
+            Ok(())
+        } else {
+            Err(ConfigError::IpcCommandFailed(format!(
+                "Hyprland refused the reload: {}",
+                reply.trim()
+            )))
+        }
+    }
+}
 
-        Ok(())
+/// Refuses bindings inside a submap
+///
+/// A keyword sent over IPC always binds in the global map, so a submap
+/// binding can only be applied by reloading the config.
+fn ensure_global(binding: &Keybinding) -> Result<(), ConfigError> {
+    match &binding.submap {
+        Some(submap) => Err(ConfigError::IpcCommandFailed(format!(
+            "Bindings in submap '{}' cannot be sent live - reload instead",
+            submap
+        ))),
+        None => Ok(()),
     }
 }
 
+/// Fields of the `j/version` reply used to find the version
+#[derive(Deserialize)]
+struct VersionReply {
+    version: Option<String>,
+    tag: Option<String>,
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,127 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bindings registered in Hyprland for a trial
+//!
+//! [`HyprlandClient::add_temporary_bind`](super::HyprlandClient::add_temporary_bind)
+//! binds a key before it is saved, so it can be pressed to see what it
+//! does. The [`TemporaryBind`] guard it returns unbinds it again, and puts
+//! back what Hyprland had on the combo before, when it is finished or
+//! dropped - whichever way the trial ends.
+//!
+//! # Example
+//! ```
+//! use hypr_keybind_manager::ipc::{ClientMode, HyprlandClient};
+//! use hypr_keybind_manager::core::{KeyCombo, Keybinding, Modifier};
+//!
+//! let binding = Keybinding::new(
+//!     KeyCombo::new(vec![Modifier::Super], "K"),
+//!     "exec",
+//!     Some("firefox"),
+//! );
+//!
+//! // DryRun validates but sends nothing, on binding or on cleanup
+//! let client = HyprlandClient::new(ClientMode::DryRun);
+//! let trial = client.add_temporary_bind(&binding).unwrap();
+//! assert!(trial.displaced().is_empty());
+//! trial.finish().unwrap();
+//! ```
+
+use super::HyprlandClient;
+use crate::config::ConfigError;
+use crate::core::Keybinding;
+
+/// A binding registered for a trial, unbound when the guard goes away
+///
+/// Dropping the guard cleans up as [`finish`](Self::finish) does, but can
+/// only log a failure.
+#[must_use = "the binding is unbound again as soon as the guard is dropped"]
+pub struct TemporaryBind {
+    /// Client the binding was sent with, used again for cleanup
+    client: HyprlandClient,
+    /// The binding on trial
+    binding: Keybinding,
+    /// Live bindings on the same combo, unbound for the trial
+    displaced: Vec<Keybinding>,
+    /// Set once cleanup has run
+    finished: bool,
+}
+
+impl TemporaryBind {
+    /// Takes responsibility for removing `binding` and restoring `displaced`
+    pub(super) fn new(
+        client: HyprlandClient,
+        binding: Keybinding,
+        displaced: Vec<Keybinding>,
+    ) -> Self {
+        Self {
+            client,
+            binding,
+            displaced,
+            finished: false,
+        }
+    }
+
+    /// The binding on trial
+    pub fn binding(&self) -> &Keybinding {
+        &self.binding
+    }
+
+    /// Live bindings on the same combo that are put back afterwards
+    pub fn displaced(&self) -> &[Keybinding] {
+        &self.displaced
+    }
+
+    /// Ends the trial now
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The binding was unbound and the displaced ones restored
+    /// * `Err(ConfigError)` - The first failure; the remaining bindings were
+    ///   still restored
+    pub fn finish(mut self) -> Result<(), ConfigError> {
+        self.cleanup()
+    }
+
+    /// Unbinds the trial binding and rebinds the displaced ones, once
+    fn cleanup(&mut self) -> Result<(), ConfigError> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+
+        // Unbinding the combo also removes anything bound alongside it, so
+        // every displaced binding is restored even after a failure
+        let mut result = self.client.remove_bind(&self.binding);
+        for binding in &self.displaced {
+            let restored = self.client.add_bind(binding);
+            if result.is_ok() {
+                result = restored;
+            }
+        }
+
+        result
+    }
+}
+
+impl Drop for TemporaryBind {
+    fn drop(&mut self) {
+        if let Err(e) = self.cleanup() {
+            eprintln!(
+                "⚠️  Could not remove trial binding {}: {}",
+                self.binding.key_combo, e
+            );
+        }
+    }
+}
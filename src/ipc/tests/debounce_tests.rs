@@ -0,0 +1,82 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for reload debouncing
+
+use std::time::{Duration, Instant};
+
+use crate::ipc::debounce::{ReloadDebouncer, DEFAULT_RELOAD_WINDOW};
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+/// Drives the debouncer like the GTK timer would: polls every 10ms and
+/// counts how many reloads would have been sent.
+fn count_reloads(debouncer: &mut ReloadDebouncer, start: Instant, until: Duration) -> usize {
+    (0..=until.as_millis() as u64 / 10)
+        .filter(|step| debouncer.poll(start + ms(step * 10)))
+        .count()
+}
+
+#[test]
+fn test_burst_sends_single_reload() {
+    let mut debouncer = ReloadDebouncer::default();
+    let start = Instant::now();
+
+    // Ten requests 50ms apart, e.g. a bulk import followed by apply presses
+    let mut started = 0;
+    for i in 0..10 {
+        if debouncer.request(start + ms(i * 50)) {
+            started += 1;
+        }
+    }
+
+    assert_eq!(started, 1, "Only the first request should arm the timer");
+    assert_eq!(count_reloads(&mut debouncer, start, ms(2000)), 1);
+    assert!(!debouncer.is_pending());
+}
+
+#[test]
+fn test_reload_waits_for_quiet_window() {
+    let mut debouncer = ReloadDebouncer::new(ms(300));
+    let start = Instant::now();
+
+    debouncer.request(start);
+    debouncer.request(start + ms(200));
+
+    // 300ms after the first request, but only 100ms after the last
+    assert!(!debouncer.poll(start + ms(300)));
+    assert_eq!(debouncer.time_until_due(start + ms(300)), Some(ms(200)));
+
+    assert!(debouncer.poll(start + ms(500)));
+    assert_eq!(debouncer.time_until_due(start + ms(500)), None);
+}
+
+#[test]
+fn test_separate_bursts_each_reload_once() {
+    let mut debouncer = ReloadDebouncer::new(DEFAULT_RELOAD_WINDOW);
+    let start = Instant::now();
+
+    debouncer.request(start);
+    debouncer.request(start + ms(10));
+    assert!(debouncer.poll(start + ms(400)));
+
+    // New burst after the first reload went out
+    assert!(debouncer.request(start + ms(1000)));
+    debouncer.request(start + ms(1100));
+    assert!(!debouncer.poll(start + ms(1300)));
+    assert!(debouncer.poll(start + ms(1400)));
+    assert!(!debouncer.poll(start + ms(2000)));
+}
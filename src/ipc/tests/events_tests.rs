@@ -0,0 +1,67 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the event socket listener
+
+use std::{io::Cursor, sync::mpsc::RecvTimeoutError, time::Duration};
+
+use crate::ipc::events::{EventListener, HyprlandEvent};
+
+#[test]
+fn test_events_parse_by_name() {
+    assert_eq!(
+        HyprlandEvent::parse("configreloaded>>\n"),
+        Some(HyprlandEvent::ConfigReloaded)
+    );
+    assert_eq!(
+        HyprlandEvent::parse("submap>>"),
+        Some(HyprlandEvent::Submap(None))
+    );
+    assert_eq!(
+        HyprlandEvent::parse("activewindow>>kitty,vim: a, b.rs"),
+        Some(HyprlandEvent::ActiveWindow {
+            class: "kitty".to_string(),
+            title: "vim: a, b.rs".to_string(),
+        })
+    );
+    assert_eq!(
+        HyprlandEvent::parse("workspace>>2"),
+        Some(HyprlandEvent::Other {
+            name: "workspace".to_string(),
+            data: "2".to_string(),
+        })
+    );
+    assert_eq!(HyprlandEvent::parse("garbage"), None);
+}
+
+#[test]
+fn test_listener_delivers_events_in_order_then_disconnects() {
+    let socket = Cursor::new("submap>>resize\nnot an event\nconfigreloaded>>\n");
+    let listener = EventListener::from_reader(socket);
+    let timeout = Duration::from_secs(5);
+
+    assert_eq!(
+        listener.next_timeout(timeout),
+        Ok(HyprlandEvent::Submap(Some("resize".to_string())))
+    );
+    assert_eq!(
+        listener.next_timeout(timeout),
+        Ok(HyprlandEvent::ConfigReloaded)
+    );
+    assert_eq!(
+        listener.next_timeout(timeout),
+        Err(RecvTimeoutError::Disconnected)
+    );
+    assert_eq!(listener.try_next(), None);
+}
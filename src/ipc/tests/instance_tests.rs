@@ -0,0 +1,114 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Hyprland instance detection
+
+use std::{ffi::OsString, fs, os::unix::net::UnixListener, path::Path};
+
+use tempfile::TempDir;
+
+use crate::{
+    config::ConfigError,
+    ipc::{
+        instance::{InstanceLocator, SIGNATURE_ENV_VAR},
+        ClientMode, HyprlandClient,
+    },
+};
+
+/// Locator over `runtime_dir`, with `signature` as the session's instance
+fn locator(runtime_dir: &Path, signature: Option<&str>) -> InstanceLocator {
+    InstanceLocator::from_vars(|name| match name {
+        "XDG_RUNTIME_DIR" => Some(runtime_dir.as_os_str().to_owned()),
+        SIGNATURE_ENV_VAR => signature.map(OsString::from),
+        _ => None,
+    })
+}
+
+/// Creates an instance directory with a command socket file
+fn fake_instance(runtime_dir: &Path, signature: &str) {
+    let directory = runtime_dir.join("hypr").join(signature);
+    fs::create_dir_all(&directory).unwrap();
+    fs::write(directory.join(".socket.sock"), "").unwrap();
+}
+
+#[test]
+fn test_current_instance_follows_signature() {
+    let runtime = TempDir::new().unwrap();
+    fake_instance(runtime.path(), "abc_123");
+
+    let current = locator(runtime.path(), Some("abc_123")).current().unwrap();
+    assert_eq!(current.signature, "abc_123");
+    assert_eq!(current.directory, runtime.path().join("hypr/abc_123"));
+
+    assert_eq!(locator(runtime.path(), Some("gone")).current(), None);
+    assert_eq!(locator(runtime.path(), None).current(), None);
+}
+
+#[test]
+fn test_instances_are_listed_by_signature() {
+    let runtime = TempDir::new().unwrap();
+    fake_instance(runtime.path(), "b_nested");
+    fake_instance(runtime.path(), "a_session");
+    fs::create_dir_all(runtime.path().join("hypr/no_socket")).unwrap();
+
+    let locator = locator(runtime.path(), Some("a_session"));
+    let instances: Vec<_> = locator
+        .instances()
+        .into_iter()
+        .filter(|instance| instance.directory.starts_with(runtime.path()))
+        .collect();
+
+    let signatures: Vec<_> = instances.iter().map(|i| i.signature.as_str()).collect();
+    assert_eq!(signatures, ["a_session", "b_nested"]);
+    assert!(locator.is_current(&instances[0]));
+    assert!(!locator.is_current(&instances[1]));
+}
+
+#[test]
+fn test_signatures_cannot_leave_the_socket_directory() {
+    let runtime = TempDir::new().unwrap();
+    fake_instance(runtime.path(), "abc");
+    let locator = locator(runtime.path(), None);
+
+    assert!(locator.find("abc").is_some());
+    assert_eq!(locator.find("../hypr/abc"), None);
+    assert_eq!(locator.find(".."), None);
+    assert_eq!(locator.find(""), None);
+}
+
+#[test]
+fn test_client_reports_selected_instance_status() {
+    let runtime = TempDir::new().unwrap();
+    let directory = runtime.path().join("hypr/live");
+    fs::create_dir_all(&directory).unwrap();
+    let listener = UnixListener::bind(directory.join(".socket.sock")).unwrap();
+
+    let instance = locator(runtime.path(), None).find("live").unwrap();
+    let client = HyprlandClient::new(ClientMode::DryRun).with_instance(instance.clone());
+
+    assert!(client.is_running());
+    let info = client.instance_info().unwrap();
+    assert_eq!(info.instance, instance);
+    // DryRun never queries, so the version stays unknown
+    assert_eq!(info.version, None);
+    assert_eq!(info.to_string(), "Hyprland (live)");
+
+    // A socket left behind by a stopped instance refuses connections
+    drop(listener);
+    assert!(!client.is_running());
+    assert!(matches!(
+        client.instance_info(),
+        Err(ConfigError::HyprlandNotRunning(_))
+    ));
+}
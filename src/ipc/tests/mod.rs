@@ -17,20 +17,24 @@
 //! Contains test suites for Hyprland IPC functionality.
 //! Note: Some tests require a running Hyprland instance and are marked #[ignore].
 
+#[cfg(test)]
+mod debounce_tests;
+#[cfg(test)]
+mod events_tests;
+#[cfg(test)]
+mod instance_tests;
+#[cfg(test)]
+mod temporary_tests;
+
 use crate::{
     config::ConfigError,
-    core::{BindType, KeyCombo, Keybinding, Modifier},
+    core::{KeyCombo, Keybinding, Modifier},
     ipc::{ClientMode, HyprlandClient},
 };
 
 /// Helper: Creates a safe test binding
 fn create_safe_binding(key: &str, app: &str) -> Keybinding {
-    Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], key),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some(app.to_string()),
-    }
+    Keybinding::new(KeyCombo::new(vec![Modifier::Super], key), "exec", Some(app))
 }
 
 #[test]
@@ -50,12 +54,11 @@ fn test_dryrun_mode_blocks_injection() {
     let client = HyprlandClient::new(ClientMode::DryRun);
 
     // Injection attempt with semicolon
-    let malicious = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some("firefox; echo hacked".to_string()),
-    };
+    let malicious = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "K"),
+        "exec",
+        Some("firefox; echo hacked"),
+    );
 
     let result = client.add_bind(&malicious);
     assert!(
@@ -112,12 +115,11 @@ fn test_command_build_single_modifier() {
 #[test]
 fn test_command_building_multiple_modifiers() {
     let client = HyprlandClient::new(ClientMode::DryRun);
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super, Modifier::Shift], "M"),
-        bind_type: BindType::Bind,
-        dispatcher: "exec".to_string(),
-        args: Some("kitty".to_string()),
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super, Modifier::Shift], "M"),
+        "exec",
+        Some("kitty"),
+    );
 
     let cmd = client.build_keyword_command("bind", &binding);
 
@@ -133,12 +135,11 @@ fn test_command_building_multiple_modifiers() {
 fn test_command_building_no_args() {
     let client = HyprlandClient::new(ClientMode::DryRun);
 
-    let binding = Keybinding {
-        key_combo: KeyCombo::new(vec![Modifier::Super], "Q"),
-        bind_type: BindType::Bind,
-        dispatcher: "killactive".to_string(),
-        args: None,
-    };
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "Q"),
+        "killactive",
+        None,
+    );
 
     let cmd = client.build_keyword_command("bind", &binding);
 
@@ -158,6 +159,49 @@ fn test_remove_bind_validates() {
     assert!(result.is_ok(), "Safe binding should validate for removal");
 }
 
+#[test]
+fn test_unbind_command_is_only_the_combo() {
+    let client = HyprlandClient::new(ClientMode::DryRun);
+    let binding = create_safe_binding("X", "firefox");
+
+    assert_eq!(client.build_keyword_command("unbind", &binding), "SUPER, X");
+    assert_eq!(
+        client.build_keyword_command("bind", &binding),
+        "SUPER, X, exec, firefox"
+    );
+}
+
+#[test]
+fn test_replace_bind_validates_both_bindings() {
+    let client = HyprlandClient::new(ClientMode::DryRun);
+    let old = create_safe_binding("B", "firefox");
+
+    assert!(client
+        .replace_bind(&old, &create_safe_binding("B", "brave"))
+        .is_ok());
+    assert!(matches!(
+        client.replace_bind(&old, &create_safe_binding("B", "brave && rm -rf ~")),
+        Err(ConfigError::ValidationFailed(_))
+    ));
+    assert!(HyprlandClient::new(ClientMode::ReadOnly)
+        .replace_bind(&old, &create_safe_binding("B", "brave"))
+        .is_err());
+}
+
+#[test]
+fn test_submap_bindings_are_not_sent_live() {
+    let client = HyprlandClient::new(ClientMode::DryRun);
+    let mut binding = create_safe_binding("R", "kitty");
+    binding.submap = Some("resize".to_string());
+
+    for result in [client.add_bind(&binding), client.remove_bind(&binding)] {
+        match result {
+            Err(ConfigError::IpcCommandFailed(msg)) => assert!(msg.contains("resize")),
+            other => panic!("Expected IpcCommandFailed, got {:?}", other),
+        }
+    }
+}
+
 #[test]
 fn test_reload_in_dryrun() {
     let client = HyprlandClient::new(ClientMode::DryRun);
@@ -174,6 +218,67 @@ fn test_reload_blocked_in_readonly() {
     assert!(result.is_err(), "Reload should be blocked in ReadOnly mode");
 }
 
+#[test]
+fn test_dispatch_validates_in_dryrun_and_blocks_in_readonly() {
+    let binding = create_safe_binding("B", "firefox");
+    assert!(HyprlandClient::new(ClientMode::DryRun)
+        .dispatch(&binding)
+        .is_ok());
+
+    let mut malicious = binding.clone();
+    malicious.args = Some("firefox; rm -rf ~".to_string());
+    assert!(matches!(
+        HyprlandClient::new(ClientMode::DryRun).dispatch(&malicious),
+        Err(ConfigError::ValidationFailed(_))
+    ));
+
+    match HyprlandClient::new(ClientMode::ReadOnly).dispatch(&binding) {
+        Err(ConfigError::IpcCommandFailed(msg)) => assert!(msg.contains("read-only")),
+        other => panic!("Expected IpcCommandFailed, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_set_binds_option_validates_name_and_value() {
+    let client = HyprlandClient::new(ClientMode::DryRun);
+
+    assert!(client
+        .set_binds_option("workspace_back_and_forth", "yes")
+        .is_ok());
+    assert!(client.set_binds_option("scroll_event_delay", "150").is_ok());
+    assert!(matches!(
+        client.set_binds_option("scroll_event_delay", "soon"),
+        Err(ConfigError::ValidationFailed(_))
+    ));
+    assert!(matches!(
+        client.set_binds_option("not_an_option", "true"),
+        Err(ConfigError::ValidationFailed(_))
+    ));
+
+    let readonly = HyprlandClient::new(ClientMode::ReadOnly);
+    assert!(readonly
+        .set_binds_option("workspace_back_and_forth", "true")
+        .is_err());
+}
+
+#[test]
+fn test_queries_are_refused_in_dryrun() {
+    let client = HyprlandClient::new(ClientMode::DryRun);
+
+    for result in [
+        client.binds().map(|_| ()),
+        client.list_binds().map(|_| ()),
+        client.get_active_workspace().map(|_| ()),
+        client.active_window().map(|_| ()),
+        client.get_devices().map(|_| ()),
+    ] {
+        match result {
+            Err(ConfigError::IpcCommandFailed(msg)) => assert!(msg.contains("dry-run")),
+            other => panic!("Expected IpcCommandFailed, got {:?}", other),
+        }
+    }
+}
+
 #[test]
 fn test_multiple_safe_bindings() {
     let client = HyprlandClient::new(ClientMode::DryRun);
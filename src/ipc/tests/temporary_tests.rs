@@ -0,0 +1,113 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for trial bindings and their cleanup
+
+use std::{
+    io::{Read, Write},
+    os::unix::net::UnixListener,
+    path::Path,
+    thread::{self, JoinHandle},
+};
+
+use tempfile::TempDir;
+
+use super::create_safe_binding;
+use crate::{
+    config::ConfigError,
+    ipc::{instance::HyprlandInstance, ClientMode, HyprlandClient},
+};
+
+/// Live bindings of the fake Hyprland: SUPER+K runs firefox
+const LIVE_BINDS: &str = r#"[{"modmask": 64, "submap": "", "key": "K", "keycode": 0,
+    "catch_all": false, "dispatcher": "exec", "arg": "firefox"}]"#;
+
+/// Answers `count` requests on a fake command socket, returning them
+fn fake_hyprland(directory: &Path, count: usize) -> JoinHandle<Vec<String>> {
+    let listener = UnixListener::bind(directory.join(".socket.sock")).unwrap();
+
+    thread::spawn(move || {
+        let mut requests = Vec::new();
+        for stream in listener.incoming().take(count) {
+            let mut stream = stream.unwrap();
+            let mut buffer = [0; 1024];
+            let length = stream.read(&mut buffer).unwrap();
+            let request = String::from_utf8_lossy(&buffer[..length]).to_string();
+
+            let reply = if request == "j/binds" {
+                LIVE_BINDS
+            } else {
+                "ok"
+            };
+            stream.write_all(reply.as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    })
+}
+
+#[test]
+fn test_temporary_bind_validates_in_dryrun() {
+    let client = HyprlandClient::new(ClientMode::DryRun);
+    let binding = create_safe_binding("K", "kitty");
+
+    let trial = client.add_temporary_bind(&binding).unwrap();
+    assert_eq!(trial.binding(), &binding);
+    assert!(trial.displaced().is_empty());
+    assert!(trial.finish().is_ok());
+
+    let mut malicious = binding.clone();
+    malicious.args = Some("kitty; rm -rf ~".to_string());
+    assert!(matches!(
+        client.add_temporary_bind(&malicious),
+        Err(ConfigError::ValidationFailed(_))
+    ));
+
+    let mut in_submap = binding.clone();
+    in_submap.submap = Some("resize".to_string());
+    assert!(client.add_temporary_bind(&in_submap).is_err());
+
+    assert!(HyprlandClient::new(ClientMode::ReadOnly)
+        .add_temporary_bind(&binding)
+        .is_err());
+}
+
+#[test]
+fn test_dropping_temporary_bind_restores_displaced_bindings() {
+    let runtime = TempDir::new().unwrap();
+    let server = fake_hyprland(runtime.path(), 5);
+    let client = HyprlandClient::new(ClientMode::Live).with_instance(HyprlandInstance {
+        signature: "fake".to_string(),
+        directory: runtime.path().to_path_buf(),
+    });
+
+    let trial = client
+        .add_temporary_bind(&create_safe_binding("K", "kitty"))
+        .unwrap();
+    assert_eq!(trial.displaced().len(), 1);
+    assert_eq!(trial.displaced()[0].args.as_deref(), Some("firefox"));
+    drop(trial);
+
+    assert_eq!(
+        server.join().unwrap(),
+        [
+            "j/binds",
+            "keyword unbind SUPER, K",
+            "keyword bind SUPER, K, exec, kitty",
+            // Cleanup: the trial binding goes, firefox comes back
+            "keyword unbind SUPER, K",
+            "keyword bind SUPER, K, exec, firefox",
+        ]
+    );
+}
@@ -84,9 +84,8 @@
 //!
 //! ```no_run
 //! use hypr_keybind_manager::ui::App;
-//! use std::path::PathBuf;
 //!
-//! let app = App::new(PathBuf::from("~/.config/hypr/hyprland.conf"))?;
+//! let app = App::new(None)?; // Finds the config as the CLI does
 //! app.run(); // Blocks until window closes
 //! # Ok::<(), String>(())
 //! ```
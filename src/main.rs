@@ -15,8 +15,13 @@
 //! CLI entry point for Hyprland Keybinding Manager
 //!
 //! Provides a command-line interface for managing Hyprland keybindings with
-//! three main commands: conflict checking, listing bindings, and launching
-//! the graphical user interface.
+//! seven main commands: conflict checking, listing bindings, a config
+//! health report, a parse coverage report, running a binding by name,
+//! querying bindings from scripts, and launching the graphical user
+//! interface.
+//!
+//! This file only parses the arguments and dispatches; each subcommand is
+//! handled by a module of `src/cli/`.
 //!
 //! # Usage
 //!
@@ -24,34 +29,122 @@
 //! # Check for conflicts
 //! hypr-keybind-manager check -c ~/.config/hypr/hyprland.conf
 //!
-//! # List all keybindings
+//! # Explain how Hyprland resolves each conflict
+//! hypr-keybind-manager check --explain
+//!
+//! # Skip the warnings about combos reserved by Hyprland or common tools
+//! hypr-keybind-manager check --no-reserved
+//!
+//! # Drop duplicate bindings, choosing which to keep (a backup is made)
+//! hypr-keybind-manager check --fix
+//!
+//! # Comment out every duplicate but the first, without asking
+//! hypr-keybind-manager check --fix --strategy keep-first --comment-out
+//!
+//! # List all keybindings (wraps to the terminal width, grouped by category)
 //! hypr-keybind-manager list
 //!
+//! # List with one line per binding, or without any width limit
+//! hypr-keybind-manager list --truncate
+//! hypr-keybind-manager list --wide
+//!
+//! # Show the config health score and what lowers it
+//! hypr-keybind-manager doctor
+//!
+//! # Show which config lines the parser does not understand
+//! hypr-keybind-manager coverage
+//!
+//! # Run the binding that best matches a name, as if its key was pressed
+//! hypr-keybind-manager run "browser"
+//!
+//! # Export the bindings as YAML
+//! hypr-keybind-manager export -o keybindings.yaml
+//!
+//! # Add a binding from a script (fails if SUPER+SHIFT+M is already bound)
+//! hypr-keybind-manager add --mods SUPER,SHIFT --key M --dispatcher exec --args kitty
+//!
+//! # Change what a binding runs, then remove another by its list number
+//! hypr-keybind-manager edit SUPER+SHIFT+M --args foot
+//! hypr-keybind-manager list --numbered
+//! hypr-keybind-manager rm 12
+//!
+//! # Print the command of every exec binding, one per line
+//! hypr-keybind-manager query '.bindings[] | select(.dispatcher == "exec") | .args'
+//!
+//! # Back up the config after every hand edit, pruning old backups
+//! hypr-keybind-manager daemon --prune
+//!
 //! # Launch GUI
 //! hypr-keybind-manager gui
 //! ```
 
+mod cli;
+
 use clap::{Parser, Subcommand};
-use colored::*;
-use hypr_keybind_manager::{
-    core::{conflict::ConflictDetector, parser::parse_config_file},
-    ui::App,
+use cli::{
+    backup::{manage_backups, BackupAction},
+    cheatsheet::write_cheat_sheet,
+    check::{check_conflicts, FixOptions, HyprlandTarget},
+    coverage::report_coverage,
+    doctor::run_doctor,
+    edit::{add_binding, edit_binding, remove_bindings, BindingChange, NewBinding},
+    ensure_writable,
+    export::export_bindings,
+    find::find_keybindings,
+    fixtures::generate_fixtures,
+    gui::launch_gui,
+    import::import_bindings,
+    list::list_keybindings,
+    profile::{manage_profiles, ProfileAction},
+    query::run_query,
+    resolve_config,
+    rules::update_rules,
+    run::run_binding,
+    split::split_config,
+    stats::show_stats,
+    table::TableLayout,
+    undo::undo_last_write,
+    watch::{run_daemon, watch_config},
 };
-use std::{
-    fs,
-    path::{Path, PathBuf},
+use hypr_keybind_manager::{
+    config::{
+        autofix::{FixMode, FixStrategy},
+        export::ExportFormat,
+        fixtures::FIXTURE_DIR,
+        interop::ForeignConfig,
+    },
+    core::{cheatsheet::CheatSheetFormat, compat::HyprlandVersion},
 };
+use std::path::PathBuf;
 
 /// Command-line interface for Hyprland Keybinding Manager.
 ///
 /// Provides subcommands for checking conflicts, listing keybindings,
-/// and launching the graphical interface.
+/// and launching the graphical interface. Without a subcommand,
+/// `--undo-last` undoes the last change written to the config.
 #[derive(Parser)]
 #[command(name = "hypr-keybind-manager")]
 #[command(author, version, about, long_about = None)]
+#[command(arg_required_else_help = true, args_conflicts_with_subcommands = true)]
 struct Cli {
+    /// Undo the last change written to the config (run again to redo it)
+    #[arg(long)]
+    undo_last: bool,
+
+    /// Path to Hyprland config file (for --undo-last)
+    #[arg(short, long, requires = "undo_last")]
+    config: Option<PathBuf>,
+
+    /// Undo without asking for confirmation
+    #[arg(short, long, requires = "undo_last")]
+    yes: bool,
+
+    /// Show what the undo would change without writing anything
+    #[arg(long, requires = "undo_last")]
+    dry_run: bool,
+
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
 }
 
 /// Available CLI subcommands.
@@ -60,22 +153,410 @@ enum Commands {
     /// Check for keybinding conflicts
     Check {
         /// Path to Hyprland config file
-        #[arg(short, long, default_value = "~/.config/hypr/hyprland.conf")]
-        config: PathBuf,
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Explain how Hyprland resolves each conflict
+        #[arg(long)]
+        explain: bool,
+
+        /// Don't warn about combos reserved by Hyprland or common tools
+        #[arg(long)]
+        no_reserved: bool,
+
+        /// Also list actions bound to more than one key combo
+        #[arg(long)]
+        duplicates: bool,
+
+        /// Also check that the programs exec bindings start are installed
+        #[arg(long)]
+        resolve_commands: bool,
+
+        /// Also compare the config with the bindings Hyprland has loaded
+        #[arg(long)]
+        runtime: bool,
+
+        /// Check dispatchers against this Hyprland release instead of the running one
+        #[arg(long, value_name = "VERSION", value_parser = parse_hyprland_version)]
+        hyprland_version: Option<HyprlandVersion>,
+
+        /// Drop duplicate bindings, asking which binding of each conflict to keep
+        #[arg(long)]
+        fix: bool,
+
+        /// Fix without asking: keep-first or keep-last
+        #[arg(long, requires = "fix", value_name = "STRATEGY")]
+        strategy: Option<FixStrategy>,
+
+        /// Comment out dropped bindings instead of deleting them
+        #[arg(long, requires = "fix")]
+        comment_out: bool,
+
+        /// Show the fix as a diff without writing anything
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+
+        /// Refuse to fix while the config has parse diagnostics (also the `strict` preference)
+        #[arg(long, requires = "fix")]
+        strict: bool,
     },
 
-    /// List all keybindings
+    /// List all keybindings (one table per `# @category:` if any)
     List {
         /// Path to Hyprland config file
-        #[arg(short, long, default_value = "~/.config/hypr/hyprland.conf")]
-        config: PathBuf,
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Never wrap or truncate, even if wider than the terminal
+        #[arg(long, conflicts_with = "truncate")]
+        wide: bool,
+
+        /// Keep one line per binding, truncating long columns
+        #[arg(long)]
+        truncate: bool,
+
+        /// Number the bindings, for selecting them with `rm` and `edit`
+        #[arg(long)]
+        numbered: bool,
+    },
+
+    /// Show the config health score and a report of every problem (exit code by severity)
+    Doctor {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Show how much of the config the parser understands
+    Coverage {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Show binding statistics: per modifier, dispatcher and submap, free keys
+    Stats {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Print the statistics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Move the bindings into a file of their own, sourced by the config
+    Split {
+        /// File to move the bindings to (must not exist yet)
+        #[arg(long, value_name = "PATH")]
+        into: PathBuf,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Split without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Show what would move without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Refuse to write while the config has parse diagnostics (also the `strict` preference)
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Save, list, switch between and compare named keybinding profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+
+    /// Manage the timestamped backups of a config
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+
+    /// Run the binding that best matches a name, as if its key was pressed
+    Run {
+        /// Words from the binding's description or arguments (fuzzy)
+        query: String,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Run dangerous commands without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Show the match without dispatching it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Fuzzy-search the bindings by combo, dispatcher, arguments or description
+    Find {
+        /// What to look for, e.g. 'fire' or 'super+k' (characters in order)
+        query: String,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Show at most this many results
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+
+    /// Print parts of the parsed bindings selected by a jq-style query
+    Query {
+        /// Query, e.g. '.bindings[] | select(.dispatcher == "exec") | .args'
+        query: String,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Print strings as JSON too (quoted), instead of raw text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export the bindings as config lines, JSON, YAML, TOML or a sway/i3 config
+    Export {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// hyprland, json, yaml, toml, sway or i3 [default: by the output file's extension]
+        #[arg(short, long)]
+        format: Option<ExportFormat>,
+
+        /// File to write [default: print to standard output]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Import bindings from an export, a Hyprland config or a sway/i3 config
+    Import {
+        /// File to import
+        file: PathBuf,
+
+        /// Translate a sway or i3 config [default: an export or Hyprland config, by extension]
+        #[arg(long)]
+        from: Option<ForeignConfig>,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Replace the config's own bindings instead of merging into them
+        #[arg(long)]
+        replace: bool,
+
+        /// Import without asking for confirmation (dangerous commands are left out)
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Refuse to write while the config has parse diagnostics (also the `strict` preference)
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Add a keybinding without the GUI (validated and checked for conflicts)
+    Add {
+        /// Modifiers, comma-separated (SUPER,SHIFT) [default: none]
+        #[arg(long, value_delimiter = ',')]
+        mods: Vec<String>,
+
+        /// Key (M, Return, F1, code:24, mouse:272)
+        #[arg(long)]
+        key: String,
+
+        /// Dispatcher (exec, killactive, workspace, ...)
+        #[arg(long)]
+        dispatcher: String,
+
+        /// Arguments of the dispatcher
+        #[arg(long)]
+        args: Option<String>,
+
+        /// Bind keyword with its flags (bindl, binde, ...)
+        #[arg(long = "type", default_value = "bind")]
+        bind_type: String,
+
+        /// Submap to add the binding to [default: none, a global binding]
+        #[arg(long)]
+        submap: Option<String>,
+
+        /// Description, written as a bindd line
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Category annotation (`# @category:`)
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Add even if the key is already bound or the command is dangerous
+        /// (injection attempts and critical commands are always refused)
+        #[arg(long)]
+        force: bool,
+
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Refuse to write while the config has parse diagnostics (also the `strict` preference)
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Remove keybindings, by key combo or by number from `list --numbered`
+    Rm {
+        /// Key combo (SUPER+SHIFT+Q) or number from `list --numbered`
+        target: String,
+
+        /// Submap of the key combo [default: none, the global bindings]
+        #[arg(long)]
+        submap: Option<String>,
+
+        /// Remove every binding on the key combo when there are several
+        #[arg(long)]
+        all: bool,
+
+        /// Comment the lines out instead of deleting them
+        #[arg(long)]
+        comment_out: bool,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Refuse to write while the config has parse diagnostics (also the `strict` preference)
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Change the dispatcher, arguments or flags of a keybinding
+    Edit {
+        /// Key combo (SUPER+SHIFT+Q) or number from `list --numbered`
+        target: String,
+
+        /// Submap of the key combo [default: none, the global bindings]
+        #[arg(long)]
+        submap: Option<String>,
+
+        /// New dispatcher
+        #[arg(long)]
+        dispatcher: Option<String>,
+
+        /// New arguments of the dispatcher (empty for none)
+        #[arg(long)]
+        args: Option<String>,
+
+        /// New bind keyword with its flags (bindl, binde, ...)
+        #[arg(long = "type")]
+        bind_type: Option<String>,
+
+        /// New description (empty for none)
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Save even if the new command is dangerous (injection attempts
+        /// and critical commands are always refused)
+        #[arg(long)]
+        force: bool,
+
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Refuse to write while the config has parse diagnostics (also the `strict` preference)
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Print a Markdown or HTML cheat sheet of the bindings
+    Cheatsheet {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// markdown or html [default: by the output file's extension]
+        #[arg(short, long)]
+        format: Option<CheatSheetFormat>,
+
+        /// File to write [default: print to standard output]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Watch the config, re-check it on every change and show desktop notifications
+    Watch {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Seconds between checks for drift from Hyprland's live bindings (0: never)
+        #[arg(long, default_value_t = 60)]
+        drift_interval: u64,
+    },
+
+    /// Back up the config whenever another program changes it
+    Daemon {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Also prune old backups by the retention preferences after each one
+        #[arg(long)]
+        prune: bool,
+    },
+
+    /// Fetch and install the signed danger rule update (opt-in, see README)
+    UpdateRules,
+
+    /// Turn detected conflicts into regression test fixtures (for developers)
+    #[command(hide = true)]
+    Fixtures {
+        /// Path to Hyprland config file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Directory to write the fixtures to
+        #[arg(short, long, default_value = FIXTURE_DIR)]
+        out: PathBuf,
     },
 
     /// Launch GUI overlay
     Gui {
         /// Path to Hyprland config file
-        #[arg(short, long, default_value = "~/.config/hypr/hyprland.conf")]
-        config: PathBuf,
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Refuse to write while the config has parse diagnostics (also the `strict` preference)
+        #[arg(long)]
+        strict: bool,
     },
 }
 
@@ -95,187 +576,203 @@ fn main() -> anyhow::Result<(), Box<dyn std::error::Error>> {
 
     let cli = Cli::parse();
 
-    match cli.command {
-        Commands::Check { config } => check_conflicts(&config)?,
-        Commands::List { config } => list_keybindings(&config)?,
-        Commands::Gui { config } => launch_gui(&config)?,
-    }
-
-    Ok(())
-}
-
-/// Checks configuration file for keybinding conflicts.
-///
-/// Parses the Hyprland config, detects duplicate key combinations,
-/// and displays conflicts with coloured output. Exits with code 1
-/// if conflicts are found.
-///
-/// # Arguments
-///
-/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
-///
-/// # Returns
-///
-/// * `Ok(())` - No conflicts found
-/// * `Err(_)` - File read or parse error
-///
-/// # Exits
-///
-/// Exits with code 1 if conflicts are detected
-fn check_conflicts(config_path: &Path) -> anyhow::Result<()> {
-    // Expand tilde in path
-    let expanded_path = shellexpand::tilde(
-        config_path
-            .to_str()
-            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
-    );
-    let path = Path::new(expanded_path.as_ref());
-
-    // Read config file
-    let content =
-        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
-
-    println!("{} Parsing config: {}", "→".cyan(), path.display());
-
-    // Parse bindings
-    let bindings = parse_config_file(&content, path)?;
-
-    println!("{} Found {} keybindings\n", "✓".green(), bindings.len());
-
-    // Build conflict detector
-    let mut detector = ConflictDetector::new();
-    for binding in bindings {
-        detector.add_binding(binding);
-    }
-
-    // Find conflicts
-    let conflicts = detector.find_conflicts();
-
-    if conflicts.is_empty() {
-        println!("{} {}", "✓".green().bold(), "No conflicts detected!".bold());
-        println!("\nYour keybindings are clean! ✓");
-    } else {
-        println!(
-            "{} Found {} conflict{}:\n",
-            "✗".red().bold(),
-            conflicts.len(),
-            if conflicts.len() == 1 { "" } else { "s" }
-        );
-
-        for (i, conflict) in conflicts.iter().enumerate() {
-            println!(
-                "{} {}",
-                format!("Conflict {}", i + 1).yellow().bold(),
-                format!("{}", conflict.key_combo).cyan()
-            );
-
-            for (idx, binding) in conflict.conflicting_bindings.iter().enumerate() {
-                let args = binding.args.as_deref().unwrap_or("");
-
-                println!(
-                    "  {} {} → {} {}",
-                    format!("{}.", idx + 1).dimmed(),
-                    format!("{}", binding.bind_type).magenta(),
-                    binding.dispatcher,
-                    args,
-                );
+    // Without a subcommand, clap only accepts `--undo-last`
+    let Some(command) = cli.command else {
+        undo_last_write(&resolve_config(cli.config)?, cli.yes, cli.dry_run)?;
+        return Ok(());
+    };
+
+    match command {
+        Commands::Check {
+            config,
+            explain,
+            no_reserved,
+            duplicates,
+            resolve_commands,
+            runtime,
+            hyprland_version,
+            fix,
+            strategy,
+            comment_out,
+            dry_run,
+            strict,
+        } => {
+            let fix = fix.then_some(FixOptions {
+                strategy,
+                mode: if comment_out {
+                    FixMode::CommentOut
+                } else {
+                    FixMode::Remove
+                },
+                dry_run,
+                strict,
+            });
+            check_conflicts(
+                &resolve_config(config)?,
+                explain,
+                !no_reserved,
+                duplicates,
+                resolve_commands,
+                HyprlandTarget {
+                    version: hyprland_version,
+                    runtime,
+                },
+                fix,
+            )?
+        }
+        Commands::List {
+            config,
+            wide,
+            truncate,
+            numbered,
+        } => {
+            let layout = if wide {
+                TableLayout::Wide
+            } else if truncate {
+                TableLayout::Truncate
+            } else {
+                TableLayout::Wrap
+            };
+            list_keybindings(&resolve_config(config)?, layout, numbered)?
+        }
+        Commands::Doctor { config } => run_doctor(&resolve_config(config)?)?,
+        Commands::Coverage { config } => report_coverage(&resolve_config(config)?)?,
+        Commands::Stats { config, json } => show_stats(&resolve_config(config)?, json)?,
+        Commands::Split {
+            into,
+            config,
+            yes,
+            dry_run,
+            strict,
+        } => {
+            let config = resolve_config(config)?;
+            ensure_writable(&config, strict)?;
+            split_config(&config, &into, yes, dry_run)?
+        }
+        Commands::Profile { action } => manage_profiles(action)?,
+        Commands::Backup { action } => manage_backups(action)?,
+        Commands::Run {
+            query,
+            config,
+            yes,
+            dry_run,
+        } => run_binding(&resolve_config(config)?, &query, yes, dry_run)?,
+        Commands::Find {
+            query,
+            config,
+            limit,
+        } => find_keybindings(&resolve_config(config)?, &query, limit)?,
+        Commands::Query {
+            query,
+            config,
+            json,
+        } => run_query(&resolve_config(config)?, &query, json)?,
+        Commands::Export {
+            config,
+            format,
+            output,
+        } => export_bindings(&resolve_config(config)?, format, output.as_deref())?,
+        Commands::Import {
+            file,
+            from,
+            config,
+            replace,
+            yes,
+            dry_run,
+            strict,
+        } => {
+            let config = resolve_config(config)?;
+            ensure_writable(&config, strict)?;
+            import_bindings(&config, &file, from, replace, yes, dry_run)?
+        }
+        Commands::Add {
+            mods,
+            key,
+            dispatcher,
+            args,
+            bind_type,
+            submap,
+            description,
+            category,
+            config,
+            force,
+            dry_run,
+            strict,
+        } => {
+            let binding = NewBinding {
+                mods,
+                key,
+                dispatcher,
+                args,
+                bind_type,
+                submap,
+                description,
+                category,
             }
-            println!();
+            .into_keybinding()?;
+            let config = resolve_config(config)?;
+            ensure_writable(&config, strict)?;
+            add_binding(&config, binding, force, dry_run)?
         }
-
-        println!(
-            "{}",
-            "⚠ These keybindings will conflict at runtime!".yellow()
-        );
-        std::process::exit(1);
-    }
-
-    Ok(())
-}
-
-/// Lists all keybindings from the configuration file.
-///
-/// Parses the Hyprland config and displays all keybindings with
-/// formatted, colourised output showing key combinations, dispatchers,
-/// and arguments.
-///
-/// # Arguments
-///
-/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
-///
-/// # Returns
-///
-/// * `Ok(())` - Successfully listed bindings
-/// * `Err(_)` - File read or parse error
-fn list_keybindings(config_path: &Path) -> anyhow::Result<()> {
-    // Expand tilde in path
-    let expanded_path = shellexpand::tilde(
-        config_path
-            .to_str()
-            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
-    );
-    let path = Path::new(expanded_path.as_ref());
-
-    // Read and parse
-    let content =
-        fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read file: {}", e))?;
-
-    let bindings = parse_config_file(&content, path)?;
-
-    println!(
-        "{}",
-        format!("Keybindings from: {}\n", path.display()).bold()
-    );
-
-    let total = bindings.len();
-
-    // Display each binding
-    for binding in bindings {
-        let key_combo = format!("{}", binding.key_combo).cyan().bold();
-        let dispatcher = binding.dispatcher.green();
-        let args = binding.args.unwrap_or_default();
-
-        println!("{} → {} {}", key_combo, dispatcher, args);
+        Commands::Rm {
+            target,
+            submap,
+            all,
+            comment_out,
+            config,
+            dry_run,
+            strict,
+        } => {
+            let mode = if comment_out {
+                FixMode::CommentOut
+            } else {
+                FixMode::Remove
+            };
+            let config = resolve_config(config)?;
+            ensure_writable(&config, strict)?;
+            remove_bindings(&config, &target, submap.as_deref(), all, mode, dry_run)?
+        }
+        Commands::Edit {
+            target,
+            submap,
+            dispatcher,
+            args,
+            bind_type,
+            description,
+            config,
+            force,
+            dry_run,
+            strict,
+        } => {
+            let change = BindingChange {
+                dispatcher,
+                args,
+                bind_type,
+                description,
+            };
+            let config = resolve_config(config)?;
+            ensure_writable(&config, strict)?;
+            edit_binding(&config, &target, submap.as_deref(), change, force, dry_run)?
+        }
+        Commands::Cheatsheet {
+            config,
+            format,
+            output,
+        } => write_cheat_sheet(&resolve_config(config)?, format, output.as_deref())?,
+        Commands::Watch {
+            config,
+            drift_interval,
+        } => watch_config(&resolve_config(config)?, drift_interval)?,
+        Commands::Daemon { config, prune } => run_daemon(&resolve_config(config)?, prune)?,
+        Commands::UpdateRules => update_rules()?,
+        Commands::Fixtures { config, out } => generate_fixtures(&resolve_config(config)?, &out)?,
+        Commands::Gui { config, strict } => launch_gui(config.as_deref(), strict)?,
     }
 
-    println!("\n{} Total: {} bindings", "✓".green(), total);
-
     Ok(())
 }
 
-/// Launches the graphical user interface.
-///
-/// Creates and runs the GTK4 application window for visual keybinding
-/// management with real-time conflict detection and editing capabilities.
-///
-/// # Arguments
-///
-/// * `config_path` - Path to Hyprland configuration file (supports tilde expansion)
-///
-/// # Returns
-///
-/// * `Ok(())` - GUI closed successfully
-/// * `Err(_)` - Failed to create or run application
-///
-/// # Blocking
-///
-/// This function blocks until the GUI window is closed by the user.
-fn launch_gui(config_path: &Path) -> anyhow::Result<()> {
-    // Expand tilde in path
-    let expanded_path = shellexpand::tilde(
-        config_path
-            .to_str()
-            .ok_or_else(|| anyhow::anyhow!("Invalid path encoding"))?,
-    );
-    let expanded_path = PathBuf::from(expanded_path.as_ref());
-
-    eprintln!("{} Launching GUI...", "→".cyan());
-
-    // Create and run app
-    let app =
-        App::new(expanded_path).map_err(|e| anyhow::anyhow!("Failed to create app: {}", e))?;
-
-    app.run();
-
-    Ok(())
+/// Reads a Hyprland version (`0.41.2`, `v0.41`, ...)
+fn parse_hyprland_version(text: &str) -> anyhow::Result<HyprlandVersion> {
+    HyprlandVersion::parse(text).ok_or_else(|| anyhow::anyhow!("Not a Hyprland version: {}", text))
 }
@@ -14,19 +14,35 @@
 
 //! GTK Action setup for the application
 //!
-//! This module contains all GTK action definitions (quit, export, import)
-//! and their setup functions
+//! This module contains all GTK action definitions (quit, export, import,
+//! paste, group suggestions, preferences, help) and their setup functions
 
 use gtk4::{
     gdk,
-    gio::{Cancellable, SimpleAction},
+    gio::{self, Cancellable, SimpleAction},
     prelude::*,
-    Application, ApplicationWindow, Box as GtkBox, Button, CheckButton, EventControllerKey,
-    FileDialog, Label, Orientation, Window,
+    Application, ApplicationWindow, Box as GtkBox, Button, CheckButton, DropDown,
+    EventControllerKey, FileDialog, FileFilter, Label, Orientation, Window,
 };
-use std::{cell::Cell, rc::Rc};
+use std::{cell::Cell, path::PathBuf, rc::Rc, time::Duration};
 
-use crate::ui::{controller::ImportMode, Controller};
+use crate::config::{
+    export::ExportFormat,
+    import_review::{ImportReview, ImportVerdict},
+    interop::ForeignConfig,
+    settings::Settings,
+};
+use crate::core::cheatsheet::CheatSheetFormat;
+use crate::ui::{
+    components::{HelpWindow, PreferencesDialog, StatisticsWindow},
+    controller::{ApplyPoll, ImportMode},
+    theme::Theme,
+    Controller,
+};
+
+/// Import mode chosen in the import dialog, with the window manager of a
+/// sway or i3 config (`None`: a Hyprland config or export)
+type ImportChoice = (ImportMode, Option<ForeignConfig>);
 
 /// Sets up the quit action
 ///
@@ -43,13 +59,77 @@ pub fn setup_quit_action(app: &Application) {
     app.set_accels_for_action("app.quit", &["<Primary>q"]);
 }
 
+/// Sets up the preferences action
+///
+/// Opens the preferences dialog (Ctrl+,), whose switches apply through the
+/// theme and controller as soon as they change.
+pub fn setup_preferences_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    theme: Rc<Theme>,
+    controller: Rc<Controller>,
+    settings: Rc<Cell<Settings>>,
+    path: Option<PathBuf>,
+) {
+    let preferences_action = SimpleAction::new("preferences", None);
+    let window = window.clone();
+
+    preferences_action.connect_activate(move |_, _| {
+        let dialog = PreferencesDialog::new(
+            window.upcast_ref(),
+            theme.clone(),
+            controller.clone(),
+            settings.clone(),
+            path.clone(),
+        );
+        dialog.show();
+    });
+
+    app.add_action(&preferences_action);
+    app.set_accels_for_action("app.preferences", &["<Primary>comma"]);
+}
+
+/// Sets up the help action
+///
+/// Opens the searchable help window (F1).
+pub fn setup_help_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    controller: Rc<Controller>,
+) {
+    let help_action = SimpleAction::new("help", None);
+    let window = window.clone();
+
+    help_action.connect_activate(move |_, _| {
+        HelpWindow::new(window.upcast_ref(), controller.get_help_index()).show();
+    });
+
+    app.add_action(&help_action);
+    app.set_accels_for_action("app.help", &["F1"]);
+}
+
+/// Opens the statistics window for the loaded bindings.
+pub fn setup_statistics_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    controller: Rc<Controller>,
+) {
+    let statistics_action = SimpleAction::new("statistics", None);
+    let window = window.clone();
+
+    statistics_action.connect_activate(move |_, _| {
+        StatisticsWindow::new(window.upcast_ref(), &controller.get_statistics()).show();
+    });
+
+    app.add_action(&statistics_action);
+}
+
 pub fn setup_history_actions(
     app: &Application,
     window: &ApplicationWindow,
     controller: Rc<Controller>,
     keybind_list: Rc<crate::ui::components::KeybindList>,
     details_panel: Rc<crate::ui::components::DetailsPanel>,
-    conflict_panel: Rc<crate::ui::components::ConflictPanel>,
 ) {
     let undo_action = SimpleAction::new("undo", None);
     undo_action.set_enabled(controller.can_undo());
@@ -60,7 +140,6 @@ pub fn setup_history_actions(
     let controller_for_undo = controller.clone();
     let keybind_list_for_undo = keybind_list.clone();
     let details_panel_for_undo = details_panel.clone();
-    let conflict_panel_for_undo = conflict_panel.clone();
     let window_for_undo = window.clone();
     let redo_action_for_undo = redo_action.clone();
     let undo_action_for_undo = undo_action.clone();
@@ -71,7 +150,6 @@ pub fn setup_history_actions(
                 &controller_for_undo,
                 &keybind_list_for_undo,
                 &details_panel_for_undo,
-                &conflict_panel_for_undo,
             );
             update_history_action_state(
                 &undo_action_for_undo,
@@ -85,7 +163,6 @@ pub fn setup_history_actions(
     let controller_for_redo = controller.clone();
     let keybind_list_for_redo = keybind_list.clone();
     let details_panel_for_redo = details_panel.clone();
-    let conflict_panel_for_redo = conflict_panel.clone();
     let window_for_redo = window.clone();
     let redo_action_for_redo = redo_action.clone();
     let undo_action_for_redo = undo_action.clone();
@@ -96,7 +173,6 @@ pub fn setup_history_actions(
                 &controller_for_redo,
                 &keybind_list_for_redo,
                 &details_panel_for_redo,
-                &conflict_panel_for_redo,
             );
             update_history_action_state(
                 &undo_action_for_redo,
@@ -126,10 +202,38 @@ pub fn sync_history_actions(app: &Application, controller: &Controller) {
     }
 }
 
+/// One file filter per export format
+///
+/// The format of an exported or imported file is read from its extension.
+fn format_filters() -> gio::ListStore {
+    let filters = gio::ListStore::new::<FileFilter>();
+    for format in ExportFormat::ALL {
+        let name = match format {
+            ExportFormat::Hyprland => "Hyprland config (*.conf)",
+            ExportFormat::Json => "JSON (*.json)",
+            ExportFormat::Yaml => "YAML (*.yaml)",
+            ExportFormat::Toml => "TOML (*.toml)",
+            // Picked by extension, so `.conf` files stay Hyprland configs;
+            // sway and i3 exports are on the command line and import has
+            // its own chooser
+            ExportFormat::Sway | ExportFormat::I3 => continue,
+        };
+        let filter = FileFilter::new();
+        filter.set_name(Some(name));
+        filter.add_suffix(format.extension());
+        if format == ExportFormat::Yaml {
+            filter.add_suffix("yml");
+        }
+        filters.append(&filter);
+    }
+    filters
+}
+
 /// Sets up the export action
 ///
 /// Creates a GTK action that opens a file save dialog and exports
-/// keybindings to the selected file.
+/// keybindings to the selected file: as JSON, YAML or TOML for those
+/// extensions, as config lines otherwise.
 pub fn setup_export_action(
     app: &Application,
     window: &ApplicationWindow,
@@ -142,9 +246,13 @@ pub fn setup_export_action(
     export_action.connect_activate(move |_, _| {
         eprintln!("💾 Export clicked");
 
+        // One filter per format; the format is read from the file's extension
+        let filters = format_filters();
+
         let file_dialog = FileDialog::builder()
             .title("Export Keybindings")
             .initial_name("hyprland-keybindings.conf")
+            .filters(&filters)
             .build();
 
         let controller_clone = controller_for_export.clone();
@@ -155,10 +263,13 @@ pub fn setup_export_action(
             None::<&Cancellable>,
             move |result| match result {
                 Ok(file) => {
-                    let path = file.path().unwrap();
+                    let Some(path) = file.path() else {
+                        eprintln!("❌ Export failed: only local files can be written");
+                        return;
+                    };
                     eprintln!("💾 Exporting to: {:?}", path);
 
-                    match controller_clone.export_to(&path) {
+                    match controller_clone.export_to(&path, ExportFormat::from_path(&path)) {
                         Ok(()) => eprintln!("✅ Export successful!"),
                         Err(e) => eprintln!("❌ Export failed: {}", e),
                     }
@@ -172,48 +283,122 @@ pub fn setup_export_action(
     app.set_accels_for_action("app.export", &["<Primary>e"]);
 }
 
+/// Sets up the cheat sheet action
+///
+/// Creates a GTK action that opens a file save dialog and writes a cheat
+/// sheet of the keybindings to the selected file: as HTML for `.html`
+/// files, as Markdown otherwise.
+pub fn setup_cheat_sheet_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    controller: Rc<Controller>,
+) {
+    let cheat_sheet_action = SimpleAction::new("cheat-sheet", None);
+    let window_for_cheat_sheet = window.clone();
+
+    cheat_sheet_action.connect_activate(move |_, _| {
+        let filters = gio::ListStore::new::<FileFilter>();
+        for format in [CheatSheetFormat::Markdown, CheatSheetFormat::Html] {
+            let filter = FileFilter::new();
+            filter.set_name(Some(match format {
+                CheatSheetFormat::Markdown => "Markdown (*.md)",
+                CheatSheetFormat::Html => "HTML (*.html)",
+            }));
+            filter.add_suffix(format.extension());
+            filters.append(&filter);
+        }
+
+        let file_dialog = FileDialog::builder()
+            .title("Export Cheat Sheet")
+            .initial_name("keybindings.md")
+            .filters(&filters)
+            .build();
+
+        let controller_clone = controller.clone();
+        file_dialog.save(
+            Some(&window_for_cheat_sheet),
+            None::<&Cancellable>,
+            move |result| match result {
+                Ok(file) => {
+                    let Some(path) = file.path() else {
+                        eprintln!("❌ Cheat sheet failed: only local files can be written");
+                        return;
+                    };
+                    let format = CheatSheetFormat::from_path(&path);
+                    match controller_clone.export_cheat_sheet(&path, format) {
+                        Ok(()) => eprintln!("✅ Cheat sheet written to {:?}", path),
+                        Err(e) => eprintln!("❌ Cheat sheet failed: {}", e),
+                    }
+                }
+                Err(_) => eprintln!("🚫 Cheat sheet cancelled"),
+            },
+        );
+    });
+
+    app.add_action(&cheat_sheet_action);
+}
+
 /// Sets up the import action
 ///
-/// Creates a GTK action that opens a file open dialog and imports
-/// keybindings from the selected file. Refreshes the UI after import.
+/// Creates a GTK action that walks through an import: where the bindings
+/// come from (a Hyprland config or export, or a sway or i3 config to
+/// translate) and how they join the config, then the file, then the review.
+/// Refreshes the UI after import.
 pub fn setup_import_action(
     app: &Application,
     window: &ApplicationWindow,
     controller: Rc<Controller>,
     keybind_list: Rc<crate::ui::components::KeybindList>,
     details_panel: Rc<crate::ui::components::DetailsPanel>,
-    conflict_panel: Rc<crate::ui::components::ConflictPanel>,
 ) {
     let import_action = SimpleAction::new("import", None);
     let controller_for_import = controller.clone();
     let window_for_import = window.clone();
     let keybind_list_for_import = keybind_list.clone();
     let details_panel_for_import = details_panel.clone();
-    let conflict_panel_for_import = conflict_panel.clone();
 
     import_action.connect_activate(move |_, _| {
         eprintln!("📥 Import clicked");
 
-        // Step 1: Show mode selection dialog
+        // Step 1: Show source and mode selection dialog
         let mode_choice = show_import_mode_dialog(&window_for_import);
 
-        let chosen_mode = match mode_choice.get() {
-            Some(mode) => mode,
+        let (chosen_mode, foreign) = match mode_choice.get() {
+            Some(choice) => choice,
             None => {
                 eprintln!("🚫 Import cancelled (no mode selected)");
                 return;
             }
         };
 
-        eprintln!("📋 Import mode: {:?}", chosen_mode);
+        eprintln!("📋 Import mode: {:?} from {:?}", chosen_mode, foreign);
 
         // Step 2: Show file picker
-        let file_dialog = FileDialog::builder().title("Import Keybindings").build();
+        // Any file can be picked; JSON, YAML and TOML are read by extension
+        let all_files = FileFilter::new();
+        all_files.set_name(Some("All files"));
+        all_files.add_pattern("*");
+        let filters = match foreign {
+            Some(_) => gio::ListStore::new::<FileFilter>(),
+            None => format_filters(),
+        };
+        filters.insert(0, &all_files);
+
+        let file_dialog = FileDialog::builder()
+            .title("Import Keybindings")
+            .filters(&filters)
+            .build();
+        // sway and i3 keep their config in one well-known place
+        if let Some(foreign) = foreign {
+            let default_path = foreign.default_path();
+            if default_path.exists() {
+                file_dialog.set_initial_file(Some(&gio::File::for_path(default_path)));
+            }
+        }
 
         let controller_clone = controller_for_import.clone();
         let keybind_list_clone = keybind_list_for_import.clone();
         let details_panel_clone = details_panel_for_import.clone();
-        let conflict_panel_clone = conflict_panel_for_import.clone();
         let window_clone = window_for_import.clone();
         let window_for_state_sync = window_for_import.clone();
 
@@ -222,22 +407,25 @@ pub fn setup_import_action(
             None::<&Cancellable>,
             move |result| match result {
                 Ok(file) => {
-                    let path = file.path().unwrap();
+                    let Some(path) = file.path() else {
+                        eprintln!("❌ Import failed: only local files can be read");
+                        return;
+                    };
                     eprintln!("📥 Importing from: {:?}", path);
 
-                    match controller_clone.import_from(&path, chosen_mode) {
-                        Ok(()) => {
-                            eprintln!("✅ Import successful!");
-                            refresh_main_view(
-                                &controller_clone,
-                                &keybind_list_clone,
-                                &details_panel_clone,
-                                &conflict_panel_clone,
-                            );
-                            if let Some(app) = window_for_state_sync.application() {
-                                sync_history_actions(&app, &controller_clone);
-                            }
-                        }
+                    let review = match foreign {
+                        Some(foreign) => controller_clone.review_foreign_import(&path, foreign),
+                        None => controller_clone.review_import(&path, None),
+                    };
+                    match review {
+                        Ok(review) => import_after_review(
+                            &window_for_state_sync,
+                            controller_clone.clone(),
+                            review,
+                            chosen_mode,
+                            keybind_list_clone.clone(),
+                            details_panel_clone.clone(),
+                        ),
                         Err(e) => eprintln!("❌ Import failed: {}", e),
                     }
                 }
@@ -249,11 +437,12 @@ pub fn setup_import_action(
     app.add_action(&import_action);
     app.set_accels_for_action("app.import", &["<Primary>o"]);
 
-    /// Shows a dialog asking user to choose import mode
+    /// Shows a dialog asking user to choose the source and import mode
     ///
-    /// Returns the chosen ImportMode wrapped in Rc<std::cell::Cell<Option<ImportMode>>>
-    /// so it can be shared across GTK callbacks
-    fn show_import_mode_dialog(parent: &ApplicationWindow) -> Rc<Cell<Option<ImportMode>>> {
+    /// Returns the chosen [`ImportChoice`], wrapped in
+    /// Rc<std::cell::Cell<Option<...>>> so it can be shared across GTK
+    /// callbacks
+    fn show_import_mode_dialog(parent: &ApplicationWindow) -> Rc<Cell<Option<ImportChoice>>> {
         let response = Rc::new(Cell::new(None));
 
         // Create dialog window
@@ -286,6 +475,18 @@ pub fn setup_import_action(
         vbox.set_margin_start(20);
         vbox.set_margin_end(20);
 
+        // Source: a Hyprland config or export, or a config to translate
+        let source_row = GtkBox::new(Orientation::Horizontal, 12);
+        source_row.append(&Label::new(Some("Import from:")));
+        let sources =
+            DropDown::from_strings(&["Hyprland config or export", "sway config", "i3 config"]);
+        sources.set_tooltip_text(Some(
+            "sway and i3 bind lines are translated into Hyprland bindings",
+        ));
+        sources.set_hexpand(true);
+        source_row.append(&sources);
+        vbox.append(&source_row);
+
         // Instruction label
         let label = Label::new(Some("How would you like to import keybindings?"));
         label.set_wrap(true);
@@ -333,7 +534,11 @@ pub fn setup_import_action(
             } else {
                 ImportMode::Merge
             };
-            response_clone.set(Some(mode));
+            // The first entry is Hyprland, then ForeignConfig::ALL in order
+            let foreign = (sources.selected() as usize)
+                .checked_sub(1)
+                .and_then(|index| ForeignConfig::ALL.get(index).copied());
+            response_clone.set(Some((mode, foreign)));
             dialog_for_import.close();
         });
         button_box.append(&import_button);
@@ -352,43 +557,373 @@ pub fn setup_import_action(
     }
 }
 
-/// Sets up the "apply to Hyprland action"
+/// Sets up the "suggest groups" action
 ///
-/// Creates a GTK action that triggers Hyprland to reload its configuration,
-/// applying all pending changes immediately without restart.
-pub fn setup_apply_action(app: &Application, controller: Rc<Controller>) {
-    let apply_action = SimpleAction::new("apply-to-hyprland", None);
-    let controller_for_apply = controller.clone();
+/// For configs without comment groups, shows the heuristic grouping and,
+/// once accepted, writes the bindings back under comment headers.
+pub fn setup_suggest_groups_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    controller: Rc<Controller>,
+    keybind_list: Rc<crate::ui::components::KeybindList>,
+    details_panel: Rc<crate::ui::components::DetailsPanel>,
+) {
+    let suggest_action = SimpleAction::new("suggest-groups", None);
+    let window_for_suggest = window.clone();
+
+    suggest_action.connect_activate(move |_, _| {
+        let Some(groups) = controller.suggest_groups() else {
+            let info_dialog = gtk4::AlertDialog::builder()
+                .modal(true)
+                .message("Nothing to Group")
+                .detail("This config already organises its keybindings with comment headers.")
+                .buttons(vec!["OK"])
+                .build();
+            info_dialog.show(Some(&window_for_suggest));
+            return;
+        };
+
+        let detail = groups
+            .iter()
+            .map(|group| {
+                let bindings: Vec<String> = group
+                    .bindings
+                    .iter()
+                    .map(|binding| format!("    {}", binding))
+                    .collect();
+                format!("# {}\n{}", group.group.title(), bindings.join("\n"))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let dialog = gtk4::AlertDialog::builder()
+            .modal(true)
+            .message("Group Keybindings?")
+            .detail(format!(
+                "Bindings will be reordered and written under these comment headers:\n\n{}",
+                detail
+            ))
+            .buttons(vec!["Cancel", "Apply Groups"])
+            .cancel_button(0)
+            .default_button(1)
+            .build();
 
-    apply_action.connect_activate(move |_, _| {
-        eprintln!("🔄 Applying changes to Hyprland...");
+        let controller = controller.clone();
+        let keybind_list = keybind_list.clone();
+        let details_panel = details_panel.clone();
+        let window = window_for_suggest.clone();
 
-        match controller_for_apply.apply_to_hyprland() {
-            Ok(()) => {
-                eprintln!("✅ Hyprland reloaded successfully!");
-                // TODO: Show success notification in UI
+        dialog.choose(
+            Some(&window_for_suggest),
+            None::<&Cancellable>,
+            move |response| {
+                if !matches!(response, Ok(1)) {
+                    return;
+                }
+
+                match controller.apply_group_suggestions(&groups) {
+                    Ok(()) => {
+                        refresh_main_view(&controller, &keybind_list, &details_panel);
+                        if let Some(app) = window.application() {
+                            sync_history_actions(&app, &controller);
+                        }
+                    }
+                    Err(e) => show_action_error(&window, "Grouping Failed", &e),
+                }
+            },
+        );
+    });
+
+    app.add_action(&suggest_action);
+}
+
+/// Sets up the "merge duplicates" action
+///
+/// Lists the bindings that repeat another binding in different formatting
+/// and, once accepted, removes them in a single undoable write.
+pub fn setup_merge_duplicates_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    controller: Rc<Controller>,
+    keybind_list: Rc<crate::ui::components::KeybindList>,
+    details_panel: Rc<crate::ui::components::DetailsPanel>,
+) {
+    let merge_action = SimpleAction::new("merge-duplicates", None);
+    let window_for_merge = window.clone();
+
+    merge_action.connect_activate(move |_, _| {
+        let copies = controller.find_redundant_copies();
+        if copies.is_empty() {
+            let info_dialog = gtk4::AlertDialog::builder()
+                .modal(true)
+                .message("Nothing to Merge")
+                .detail("No keybinding repeats another in different formatting.")
+                .buttons(vec!["OK"])
+                .build();
+            info_dialog.show(Some(&window_for_merge));
+            return;
+        }
+
+        let detail = copies
+            .iter()
+            .map(|binding| format!("    {}", binding))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let dialog = gtk4::AlertDialog::builder()
+            .modal(true)
+            .message("Merge Duplicate Keybindings?")
+            .detail(format!(
+                "These bindings repeat an earlier one and will be removed:\n\n{}",
+                detail
+            ))
+            .buttons(vec!["Cancel", "Merge"])
+            .cancel_button(0)
+            .default_button(1)
+            .build();
+
+        let controller = controller.clone();
+        let keybind_list = keybind_list.clone();
+        let details_panel = details_panel.clone();
+        let window = window_for_merge.clone();
+
+        dialog.choose(
+            Some(&window_for_merge),
+            None::<&Cancellable>,
+            move |response| {
+                if !matches!(response, Ok(1)) {
+                    return;
+                }
+
+                match controller.merge_duplicates() {
+                    Ok(_) => {
+                        refresh_main_view(&controller, &keybind_list, &details_panel);
+                        if let Some(app) = window.application() {
+                            sync_history_actions(&app, &controller);
+                        }
+                    }
+                    Err(e) => show_action_error(&window, "Merge Failed", &e),
+                }
+            },
+        );
+    });
+
+    app.add_action(&merge_action);
+}
+
+/// Sets up the paste bindings action
+///
+/// Reads bind lines from the clipboard (Ctrl+Shift+V) and merges them into
+/// the config after the same review as an import.
+pub fn setup_paste_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    controller: Rc<Controller>,
+    keybind_list: Rc<crate::ui::components::KeybindList>,
+    details_panel: Rc<crate::ui::components::DetailsPanel>,
+) {
+    let paste_action = SimpleAction::new("paste-bindings", None);
+    let window_for_paste = window.clone();
+
+    paste_action.connect_activate(move |_, _| {
+        eprintln!("📋 Paste bindings clicked");
+
+        let controller = controller.clone();
+        let keybind_list = keybind_list.clone();
+        let details_panel = details_panel.clone();
+        let window = window_for_paste.clone();
+
+        window_for_paste
+            .clipboard()
+            .read_text_async(None::<&Cancellable>, move |result| {
+                let text = match result {
+                    Ok(Some(text)) => text.to_string(),
+                    Ok(None) | Err(_) => {
+                        show_action_error(
+                            &window,
+                            "Nothing to Paste",
+                            "The clipboard holds no text.",
+                        );
+                        return;
+                    }
+                };
+
+                match controller.review_paste(&text) {
+                    Ok(review) => import_after_review(
+                        &window,
+                        controller.clone(),
+                        review,
+                        ImportMode::Merge,
+                        keybind_list.clone(),
+                        details_panel.clone(),
+                    ),
+                    Err(e) => show_action_error(&window, "Nothing to Paste", &e),
+                }
+            });
+    });
+
+    app.add_action(&paste_action);
+    app.set_accels_for_action("app.paste-bindings", &["<Primary><Shift>v"]);
+}
+
+/// Adds reviewed bindings, asking first if the review flagged any
+///
+/// Blocked bindings, and entries of the file that are not bindings, are
+/// listed as left out. Dangerous ones are only added after the user
+/// explicitly chooses "Import Dangerous Too"; the default choice leaves
+/// them out.
+fn import_after_review(
+    window: &ApplicationWindow,
+    controller: Rc<Controller>,
+    review: ImportReview,
+    mode: ImportMode,
+    keybind_list: Rc<crate::ui::components::KeybindList>,
+    details_panel: Rc<crate::ui::components::DetailsPanel>,
+) {
+    let mut dangerous = Vec::new();
+    let mut blocked = Vec::new();
+    for entry in &review.entries {
+        match &entry.verdict {
+            ImportVerdict::Dangerous(assessment) => dangerous.push(format!(
+                "    ⚠ {}\n       {}",
+                entry.binding, assessment.reason
+            )),
+            ImportVerdict::Blocked(reason) => {
+                blocked.push(format!("    ⛔ {}\n       {}", entry.binding, reason))
+            }
+            ImportVerdict::Accepted => {}
+        }
+    }
+
+    let unreadable: Vec<String> = review
+        .unreadable
+        .iter()
+        .map(|error| format!("    ✗ {}", error))
+        .collect();
+
+    let import = {
+        let window = window.clone();
+        move |acknowledged: bool| match controller.import_reviewed(&review, mode, acknowledged) {
+            Ok(added) => {
+                eprintln!("✅ Imported {} binding(s)", added);
+                refresh_main_view(&controller, &keybind_list, &details_panel);
+                if let Some(app) = window.application() {
+                    sync_history_actions(&app, &controller);
+                }
             }
-            Err(e) => {
-                eprintln!("❌ Failed to reload Hyprland: {}", e);
-                // TODO: Show error dialog
+            Err(e) => show_action_error(&window, "Import Failed", &e),
+        }
+    };
+
+    if dangerous.is_empty() && blocked.is_empty() && unreadable.is_empty() {
+        import(false);
+        return;
+    }
+
+    let mut detail = Vec::new();
+    if !dangerous.is_empty() {
+        detail.push(format!(
+            "These commands are dangerous and are only added if you confirm:\n\n{}",
+            dangerous.join("\n")
+        ));
+    }
+    if !blocked.is_empty() {
+        detail.push(format!(
+            "These bindings are never added:\n\n{}",
+            blocked.join("\n")
+        ));
+    }
+    if !unreadable.is_empty() {
+        detail.push(format!(
+            "These entries of the file are not valid bindings:\n\n{}",
+            unreadable.join("\n")
+        ));
+    }
+
+    let buttons = if dangerous.is_empty() {
+        vec!["Cancel", "Import the Rest"]
+    } else {
+        vec!["Cancel", "Import Safe Only", "Import Dangerous Too"]
+    };
+    let dialog = gtk4::AlertDialog::builder()
+        .modal(true)
+        .message("Review Imported Commands")
+        .detail(detail.join("\n\n"))
+        .buttons(buttons)
+        .cancel_button(0)
+        .default_button(1)
+        .build();
+
+    dialog.choose(
+        Some(window),
+        None::<&Cancellable>,
+        move |response| match response {
+            Ok(1) => import(false),
+            Ok(2) => {
+                eprintln!("⚠️  Dangerous imported commands acknowledged");
+                import(true);
             }
+            _ => eprintln!("🚫 Import cancelled after review"),
+        },
+    );
+}
+
+/// Sets up the "apply to Hyprland action"
+///
+/// Creates a GTK action that triggers Hyprland to reload its configuration,
+/// applying all pending changes immediately without restart. Repeated
+/// activations are debounced so a burst results in a single reload. A
+/// failed reload, such as Hyprland not running, is shown in an error dialog.
+pub fn setup_apply_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    controller: Rc<Controller>,
+) {
+    let apply_action = SimpleAction::new("apply-to-hyprland", None);
+    let controller_for_apply = controller.clone();
+    let window_for_apply = window.clone();
+
+    apply_action.connect_activate(move |_, _| match controller_for_apply.request_apply() {
+        Some(delay) => {
+            eprintln!("🔄 Applying changes to Hyprland...");
+            schedule_apply_poll(
+                controller_for_apply.clone(),
+                window_for_apply.clone(),
+                delay,
+            );
         }
+        None => eprintln!("⏳ Apply already pending - coalescing"),
     });
 
     app.add_action(&apply_action);
     app.set_accels_for_action("app.apply-to-hyprland", &["<Primary>r"]);
 }
 
+/// Polls the controller's debounced apply after `delay`, re-arming itself
+/// until the burst has gone quiet and the reload has been sent
+fn schedule_apply_poll(controller: Rc<Controller>, window: ApplicationWindow, delay: Duration) {
+    glib::timeout_add_local_once(delay, move || match controller.poll_pending_apply() {
+        ApplyPoll::Waiting(remaining) => schedule_apply_poll(controller, window, remaining),
+        ApplyPoll::Applied(Ok(())) => {
+            eprintln!("✅ Hyprland reloaded successfully!");
+            // TODO: Show success notification in UI
+        }
+        ApplyPoll::Applied(Err(e)) => {
+            eprintln!("❌ Failed to reload Hyprland: {}", e);
+            show_action_error(&window, "Could Not Apply to Hyprland", &e);
+        }
+        ApplyPoll::Idle => {}
+    });
+}
+
 pub fn refresh_main_view(
     controller: &Controller,
     keybind_list: &crate::ui::components::KeybindList,
     details_panel: &crate::ui::components::DetailsPanel,
-    conflict_panel: &crate::ui::components::ConflictPanel,
 ) {
     let updated_bindings = controller.get_current_view();
     keybind_list.update_with_bindings(updated_bindings);
     details_panel.update_binding(None);
-    conflict_panel.refresh();
 }
 
 fn update_history_action_state(
@@ -26,10 +26,22 @@
 //!   └─ Connects components to Controller
 //! ```
 
-use gtk4::{gdk, prelude::*, Application, ApplicationWindow, CssProvider};
-use std::{path::PathBuf, rc::Rc};
-
-use crate::ui::{actions, builders, file_watcher::FileWatcher, Controller};
+use gtk4::{prelude::*, Application, ApplicationWindow};
+use std::{cell::Cell, path::PathBuf, rc::Rc};
+
+use crate::config::{discovery::discover_config, settings::Settings, state::AppState};
+use crate::core::lint::LintPolicy;
+use crate::ipc::{
+    events::{EventListener, HyprlandEvent},
+    ClientMode,
+};
+use crate::ui::{
+    actions, builders,
+    components::{ConnectionIndicator, HealthIndicator},
+    file_watcher::FileWatcher,
+    theme::Theme,
+    Controller,
+};
 
 /// GTK4 Application for keybinding management
 pub struct App {
@@ -46,12 +58,13 @@ impl App {
     ///
     /// # Arguments
     ///
-    /// * `config_path` - Path to Hyprland configuration file
+    /// * `config_path` - Path to Hyprland configuration file, or `None` to
+    ///   discover it as the CLI does (see [`crate::config::discovery`])
     ///
     /// # Returns
     ///
     /// * `Ok(App)` - Successfully initialised
-    /// * `Err(String)` - Failed to create Controller or App
+    /// * `Err(String)` - No config found, or failed to create Controller
     ///
     /// # Example
     ///
@@ -59,12 +72,12 @@ impl App {
     /// use hypr_keybind_manager::ui::App;
     /// use std::path::PathBuf;
     ///
-    /// let app = App::new(
-    ///     PathBuf::from("~/.config/hypr/hyprland.conf")
-    /// )?;
+    /// let app = App::new(Some(PathBuf::from("~/.config/hypr/hyprland.conf")))?;
     /// # Ok::<(), String>(())
     /// ```
-    pub fn new(config_path: PathBuf) -> Result<Self, String> {
+    pub fn new(config_path: Option<PathBuf>) -> Result<Self, String> {
+        let config_path = discover_config(config_path.as_deref()).map_err(|e| e.to_string())?;
+
         // Create GTK4 Application
         let app = Application::builder()
             .application_id("com.tidynest.hypr-keybind-manager")
@@ -74,6 +87,12 @@ impl App {
         let controller = Controller::new(config_path)
             .map_err(|e| format!("Failed to create controller: {}", e))?;
 
+        // Reloads and live edits go to the running Hyprland
+        controller.set_client_mode(ClientMode::Live);
+        if let Some(state_path) = AppState::default_path() {
+            controller.set_state_path(state_path);
+        }
+
         let controller = Rc::new(controller);
 
         let file_watcher = {
@@ -90,6 +109,13 @@ impl App {
         })
     }
 
+    /// Enables strict mode on the underlying Controller
+    ///
+    /// See [`Controller::set_strict_mode`] for the exact semantics.
+    pub fn set_strict_mode(&self, strict: bool) {
+        self.controller.set_strict_mode(strict);
+    }
+
     /// Runs the GTK4 application
     ///
     /// This starts the GTK4 main loop. Call this after creating the App.
@@ -100,7 +126,7 @@ impl App {
     /// ```no_run
     /// # use hypr_keybind_manager::ui::App;
     /// # use std::path::PathBuf;
-    /// # let app = App::new(PathBuf::from("hyprland.conf"))?;
+    /// # let app = App::new(Some(PathBuf::from("hyprland.conf")))?;
     /// app.run();  // Blocks until window closes
     /// # Ok::<(), String>(())
     /// ```
@@ -117,21 +143,22 @@ impl App {
         self.app.run_with_args::<&str>(&[]);
     }
 
-    /// Loads custom CSS styling for the application
+    /// Loads the saved preferences, and the path they are saved to
     ///
-    /// Applies the CSS from `style.css` to the default display
-    /// at APPLICATION priority level.
-    fn load_css() {
-        let provider = CssProvider::new();
-        let css = include_str!("style.css");
-        provider.load_from_string(css);
-
-        // Apply CSS to the default display
-        gtk4::style_context_add_provider_for_display(
-            &gdk::Display::default().expect("Could not connect to a display"),
-            &provider,
-            gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
-        );
+    /// An unreadable preferences file falls back to the defaults.
+    fn load_settings() -> (Settings, Option<PathBuf>) {
+        let path = Settings::default_path();
+        let settings = path
+            .as_deref()
+            .map(Settings::load)
+            .transpose()
+            .unwrap_or_else(|e| {
+                eprintln!("⚠️  Failed to read preferences: {}", e);
+                None
+            })
+            .unwrap_or_default();
+
+        (settings, path)
     }
 
     /// Builds the main window UI
@@ -152,7 +179,23 @@ impl App {
         // Setup quit action
         actions::setup_quit_action(app);
 
-        Self::load_css();
+        let (settings, settings_path) = Self::load_settings();
+        let Some(theme) = Theme::install(settings) else {
+            eprintln!("Could not connect to a display");
+            return;
+        };
+        let theme = Rc::new(theme);
+        let settings = Rc::new(Cell::new(settings));
+        controller.set_settings(settings.get());
+        if let Some(path) = LintPolicy::default_path() {
+            controller.set_lint_policy(LintPolicy::load(&path));
+        }
+
+        if controller.is_strict_mode() {
+            for diagnostic in controller.get_parse_diagnostics() {
+                eprintln!("⚠️  Strict mode: {}", diagnostic);
+            }
+        }
 
         // Create header bar with menu
         let (header_bar, _undo_button, _redo_button) = builders::build_header_bar();
@@ -167,6 +210,7 @@ impl App {
 
         // Setup export action
         actions::setup_export_action(app, &window, controller.clone());
+        actions::setup_cheat_sheet_action(app, &window, controller.clone());
 
         // Build main layout
         let (
@@ -185,11 +229,7 @@ impl App {
         window.set_child(Some(&main_vbox));
 
         // Connect conflict resolution button
-        conflict_panel.connect_resolve_button(
-            window.upcast_ref(),
-            conflict_panel.clone(),
-            keybind_list.clone(),
-        );
+        conflict_panel.connect_resolve_button(window.upcast_ref(), keybind_list.clone());
 
         // Setup import action (needs widgets to refresh UI after import)
         actions::setup_import_action(
@@ -198,7 +238,30 @@ impl App {
             controller.clone(),
             keybind_list.clone(),
             details_panel.clone(),
-            conflict_panel.clone(),
+        );
+
+        actions::setup_paste_action(
+            app,
+            &window,
+            controller.clone(),
+            keybind_list.clone(),
+            details_panel.clone(),
+        );
+
+        actions::setup_suggest_groups_action(
+            app,
+            &window,
+            controller.clone(),
+            keybind_list.clone(),
+            details_panel.clone(),
+        );
+
+        actions::setup_merge_duplicates_action(
+            app,
+            &window,
+            controller.clone(),
+            keybind_list.clone(),
+            details_panel.clone(),
         );
 
         actions::setup_history_actions(
@@ -207,11 +270,22 @@ impl App {
             controller.clone(),
             keybind_list.clone(),
             details_panel.clone(),
-            conflict_panel.clone(),
         );
 
         // Setup apply to Hyprland action
-        actions::setup_apply_action(app, controller.clone());
+        actions::setup_apply_action(app, &window, controller.clone());
+
+        actions::setup_preferences_action(
+            app,
+            &window,
+            theme,
+            controller.clone(),
+            settings,
+            settings_path,
+        );
+
+        actions::setup_help_action(app, &window, controller.clone());
+        actions::setup_statistics_action(app, &window, controller.clone());
 
         // Wire up all event handlers
         builders::wire_up_handlers(
@@ -219,7 +293,6 @@ impl App {
             controller.clone(),
             keybind_list.clone(),
             details_panel.clone(),
-            conflict_panel.clone(),
             &add_keybinding_button,
             &backup_button,
         );
@@ -229,7 +302,23 @@ impl App {
         keybind_list.update_with_bindings(all_bindings);
         actions::sync_history_actions(app, &controller);
 
-        // Update conflict panel
+        // Health score in the header, recomputed whenever the bindings change
+        let health_indicator = Rc::new(HealthIndicator::new(controller.clone()));
+        header_bar.pack_end(health_indicator.widget());
+        health_indicator.refresh();
+        controller.on_bindings_changed(move |_| health_indicator.refresh());
+
+        // Connection status next to it, probed every few seconds so it
+        // notices Hyprland stopping or starting
+        let connection_indicator = ConnectionIndicator::new(controller.clone());
+        header_bar.pack_end(connection_indicator.widget());
+        connection_indicator.refresh();
+        glib::timeout_add_seconds_local(5, move || {
+            connection_indicator.refresh();
+            glib::ControlFlow::Continue
+        });
+
+        // Initial conflict state; later changes reach the panel on their own
         conflict_panel.refresh();
 
         // Setup file watcher polling (if available)
@@ -238,7 +327,6 @@ impl App {
             let controller_clone = controller.clone();
             let keybind_list_clone = keybind_list.clone();
             let details_panel_clone = details_panel.clone();
-            let conflict_panel_clone = conflict_panel.clone();
 
             glib::timeout_add_local(std::time::Duration::from_secs(1), move || {
                 if file_watcher.check_for_changes() {
@@ -248,10 +336,9 @@ impl App {
                         eprintln!("❌ Failed to reload: {}", e);
                     } else {
                         controller_clone.clear_history();
-                        let all_bindings = controller_clone.get_keybindings();
+                        let all_bindings = controller_clone.get_current_view();
                         keybind_list_clone.update_with_bindings(all_bindings);
                         details_panel_clone.update_binding(None);
-                        conflict_panel_clone.refresh();
                         actions::sync_history_actions(&app_for_watcher, &controller_clone);
                         eprintln!("✅ Config reloaded successfully");
                     }
@@ -260,6 +347,24 @@ impl App {
             });
         }
 
+        // Follow Hyprland's own reloads, e.g. from `hyprctl reload` in a
+        // terminal, so views depending on its live state stay current
+        match EventListener::connect() {
+            Ok(events) => {
+                let controller_for_events = controller.clone();
+                glib::timeout_add_local(std::time::Duration::from_millis(500), move || {
+                    while let Some(event) = events.try_next() {
+                        if event == HyprlandEvent::ConfigReloaded {
+                            eprintln!("🔄 Hyprland reloaded its config");
+                        }
+                        controller_for_events.handle_hyprland_event(&event);
+                    }
+                    glib::ControlFlow::Continue
+                });
+            }
+            Err(e) => eprintln!("⚠️  Not following Hyprland events: {}", e),
+        }
+
         // Show window
         window.present();
     }
@@ -16,14 +16,20 @@
 //!
 //! Wires up all event handlers for the main UI:
 //! - Row selection
+//! - Row badges (conflict / danger)
 //! - Keyboard navigation
-//! - Delete/Edit/Add buttons
+//! - Delete/Edit/Add buttons, with a review of the change before saving
+//! - Rewriting `hyprctl dispatch` bindings to the dispatcher
 //! - Backup manager
 
 use crate::{
-    core::types::{BindType, KeyCombo, Keybinding},
+    config::diff::ConfigDiff,
+    core::types::{KeyCombo, Keybinding},
     ui::{
-        components::{BackupDialog, ConflictPanel, DetailsPanel, EditDialog, KeybindList},
+        components::{
+            conflict_resolution_dialog::ConflictResolutionDialog, BackupDialog, CompareDialog,
+            DetailsPanel, DiffDialog, EditDialog, KeybindList, ReviewDialog, RowBadge,
+        },
         Controller,
     },
 };
@@ -34,9 +40,12 @@ use std::rc::Rc;
 ///
 /// Sets up:
 /// - Row selection in keybind list
+/// - Badge clicks on list rows
+/// - Dragging list rows to reorder bindings
 /// - Keyboard navigation (Up/Down/Enter)
 /// - Delete button click handler
 /// - Edit button click handler
+/// - Rewrite button click handler
 /// - Add button click handler
 /// - Backup button click handler
 pub fn wire_up_handlers(
@@ -44,7 +53,6 @@ pub fn wire_up_handlers(
     controller: Rc<Controller>,
     keybind_list: Rc<KeybindList>,
     details_panel: Rc<DetailsPanel>,
-    conflict_panel: Rc<ConflictPanel>,
     add_button: &Button,
     backup_button: &Button,
 ) {
@@ -71,6 +79,73 @@ pub fn wire_up_handlers(
             }
         });
 
+    // ============================================================================
+    // Row badge handler
+    // ============================================================================
+    // Conflict badges open the resolution dialog limited to that combo; danger
+    // badges select the row so the details panel shows the assessment.
+    let window_for_badges = window.clone();
+    let controller_for_badges = controller.clone();
+    let keybind_list_for_badges = Rc::downgrade(&keybind_list);
+
+    keybind_list.connect_badge_clicked(move |badge, binding| {
+        let Some(keybind_list) = keybind_list_for_badges.upgrade() else {
+            return;
+        };
+
+        match badge {
+            RowBadge::Conflict => {
+                let dialog = ConflictResolutionDialog::new(
+                    window_for_badges.upcast_ref(),
+                    controller_for_badges.clone(),
+                    keybind_list,
+                    Some(&binding.key_combo),
+                );
+                dialog.show();
+            }
+            RowBadge::Danger => {
+                keybind_list.select_binding(binding);
+            }
+        }
+    });
+
+    // ============================================================================
+    // Row reordering handler
+    // ============================================================================
+    // A row dropped onto another moves its binding there; the list is
+    // rebuilt from the controller so it shows the order as written.
+    let window_for_move = window.clone();
+    let controller_for_move = controller.clone();
+    let keybind_list_for_move = Rc::downgrade(&keybind_list);
+
+    keybind_list.connect_binding_moved(move |moved, target| {
+        let Some(keybind_list) = keybind_list_for_move.upgrade() else {
+            return;
+        };
+
+        match controller_for_move.move_keybinding(moved, target) {
+            Ok(()) => {
+                keybind_list.update_with_bindings(controller_for_move.get_current_view());
+                keybind_list.select_binding(moved);
+                if let Some(app) = window_for_move.application() {
+                    crate::ui::actions::sync_history_actions(&app, &controller_for_move);
+                }
+                eprintln!("✅ Moved {} to {}", moved.key_combo, target.key_combo);
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to move: {}", e);
+
+                let error_dialog = gtk4::AlertDialog::builder()
+                    .modal(true)
+                    .message("Move Failed")
+                    .detail(format!("Failed to move keybinding:\n\n{}", e))
+                    .buttons(vec!["OK"])
+                    .build();
+                error_dialog.show(Some(&window_for_move));
+            }
+        }
+    });
+
     // ============================================================================
     // Keyboard navigation
     // ============================================================================
@@ -120,7 +195,6 @@ pub fn wire_up_handlers(
     let controller_for_delete = controller.clone();
     let keybind_list_for_delete = keybind_list.clone();
     let details_panel_for_delete = details_panel.clone();
-    let conflict_panel_for_delete = conflict_panel.clone();
 
     details_panel.connect_delete(move |binding| {
         eprintln!("🗑️  Delete button clicked for: {}", binding.key_combo);
@@ -128,10 +202,39 @@ pub fn wire_up_handlers(
         let controller_clone = controller_for_delete.clone();
         let keybind_list_clone = keybind_list_for_delete.clone();
         let details_panel_clone = details_panel_for_delete.clone();
-        let conflict_panel_clone = conflict_panel_for_delete.clone();
         let binding_clone = binding.clone();
         let window_clone = window_for_delete.clone();
 
+        let window_for_inner = window_clone.clone();
+        let delete = move || match controller_clone.delete_keybinding(&binding_clone) {
+            Ok(()) => {
+                let updated = controller_clone.get_current_view();
+                keybind_list_clone.update_with_bindings(updated);
+                details_panel_clone.update_binding(None);
+                if let Some(app) = window_for_inner.application() {
+                    crate::ui::actions::sync_history_actions(&app, &controller_clone);
+                }
+                eprintln!("✅ Keybinding deleted successfully");
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to delete: {}", e);
+
+                let error_dialog = gtk4::AlertDialog::builder()
+                    .modal(true)
+                    .message("Delete Failed")
+                    .detail(format!("Failed to delete keybinding:\n{}", e))
+                    .buttons(vec!["OK"])
+                    .build();
+                error_dialog.show(Some(&window_for_inner));
+            }
+        };
+
+        // The "Confirm deletions" preference can skip the question
+        if !controller_for_delete.settings().confirm_delete {
+            delete();
+            return;
+        }
+
         let dialog = gtk4::AlertDialog::builder()
             .modal(true)
             .message("Delete Keybinding?")
@@ -146,35 +249,11 @@ pub fn wire_up_handlers(
             .default_button(0)
             .build();
 
-        let window_for_inner = window_clone.clone();
-
         dialog.choose(
             Some(&window_clone),
             None::<&gio::Cancellable>,
             move |response| match response {
-                Ok(1) => match controller_clone.delete_keybinding(&binding_clone) {
-                    Ok(()) => {
-                        let updated = controller_clone.get_current_view();
-                        keybind_list_clone.update_with_bindings(updated);
-                        details_panel_clone.update_binding(None);
-                        conflict_panel_clone.refresh();
-                        if let Some(app) = window_for_inner.application() {
-                            crate::ui::actions::sync_history_actions(&app, &controller_clone);
-                        }
-                        eprintln!("✅ Keybinding deleted successfully");
-                    }
-                    Err(e) => {
-                        eprintln!("❌ Failed to delete: {}", e);
-
-                        let error_dialog = gtk4::AlertDialog::builder()
-                            .modal(true)
-                            .message("Delete Failed")
-                            .detail(format!("Failed to delete keybinding:\n{}", e))
-                            .buttons(vec!["OK"])
-                            .build();
-                        error_dialog.show(Some(&window_for_inner));
-                    }
-                },
+                Ok(1) => delete(),
                 Ok(0) => {
                     eprintln!("🚫 Delete cancelled");
                 }
@@ -195,7 +274,6 @@ pub fn wire_up_handlers(
     let controller_for_edit = controller.clone();
     let keybind_list_for_edit = keybind_list.clone();
     let details_panel_for_edit = details_panel.clone();
-    let conflict_panel_for_edit = conflict_panel.clone();
 
     details_panel.connect_edit(move |binding| {
         eprintln!("✏️  Edit button clicked for: {}", binding.key_combo);
@@ -203,7 +281,6 @@ pub fn wire_up_handlers(
         let controller_clone = controller_for_edit.clone();
         let keybind_list_clone = keybind_list_for_edit.clone();
         let details_panel_clone = details_panel_for_edit.clone();
-        let conflict_panel_clone = conflict_panel_for_edit.clone();
         let binding_clone = binding.clone();
         let window_clone = window_for_edit.clone();
         let edit_dialog = EditDialog::new(
@@ -214,12 +291,17 @@ pub fn wire_up_handlers(
         );
 
         if let Some(new_binding) = edit_dialog.show_and_wait() {
+            if !review_change(&window_clone, &controller_clone, || {
+                controller_clone.preview_update(&binding_clone, &new_binding)
+            }) {
+                eprintln!("🚫 Edit cancelled in review");
+                return;
+            }
             match controller_clone.update_keybinding(&binding_clone, new_binding) {
                 Ok(()) => {
                     details_panel_clone.update_binding(None);
                     let updated_bindings = controller_clone.get_current_view();
                     keybind_list_clone.update_with_bindings(updated_bindings);
-                    conflict_panel_clone.refresh();
                     if let Some(app) = window_clone.application() {
                         crate::ui::actions::sync_history_actions(&app, &controller_clone);
                     }
@@ -242,6 +324,44 @@ pub fn wire_up_handlers(
         }
     });
 
+    // ============================================================================
+    // Rewrite button handler (hyprctl dispatch -> dispatcher)
+    // ============================================================================
+    let window_for_rewrite = window.clone();
+    let controller_for_rewrite = controller.clone();
+    let keybind_list_for_rewrite = keybind_list.clone();
+    let details_panel_for_rewrite = details_panel.clone();
+
+    details_panel.connect_rewrite(move |binding, rewrite| {
+        eprintln!(
+            "💡 Rewriting {} to use {}",
+            binding.key_combo, rewrite.dispatcher
+        );
+
+        match controller_for_rewrite.update_keybinding(binding, rewrite) {
+            Ok(()) => {
+                details_panel_for_rewrite.update_binding(None);
+                let updated_bindings = controller_for_rewrite.get_current_view();
+                keybind_list_for_rewrite.update_with_bindings(updated_bindings);
+                if let Some(app) = window_for_rewrite.application() {
+                    crate::ui::actions::sync_history_actions(&app, &controller_for_rewrite);
+                }
+                eprintln!("✅ Keybinding rewritten successfully");
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to rewrite: {}", e);
+
+                let error_dialog = gtk4::AlertDialog::builder()
+                    .modal(true)
+                    .message("Rewrite Failed")
+                    .detail(format!("Failed to rewrite keybinding:\n\n{}", e))
+                    .buttons(vec!["OK"])
+                    .build();
+                error_dialog.show(Some(&window_for_rewrite));
+            }
+        }
+    });
+
     // ============================================================================
     // Add button handler
     // ============================================================================
@@ -249,7 +369,6 @@ pub fn wire_up_handlers(
     let controller_for_add = controller.clone();
     let keybind_list_for_add = keybind_list.clone();
     let details_panel_for_add = details_panel.clone();
-    let conflict_panel_for_add = conflict_panel.clone();
 
     add_button.connect_clicked(move |_| {
         eprintln!("➕ Add button clicked");
@@ -257,14 +376,11 @@ pub fn wire_up_handlers(
         let controller_clone = controller_for_add.clone();
         let keybind_list_clone = keybind_list_for_add.clone();
         let details_panel_clone = details_panel_for_add.clone();
-        let conflict_panel_clone = conflict_panel_for_add.clone();
         let window_clone = window_for_add.clone();
 
         let empty_binding = Keybinding {
-            bind_type: BindType::Bind,
-            key_combo: KeyCombo::new(vec![], ""),
-            dispatcher: String::new(),
-            args: None,
+            bind_type: controller_clone.settings().default_bind_type,
+            ..Keybinding::new(KeyCombo::new(vec![], ""), "", None)
         };
 
         let edit_dialog = EditDialog::new(
@@ -275,12 +391,17 @@ pub fn wire_up_handlers(
         );
 
         if let Some(new_binding) = edit_dialog.show_and_wait() {
+            if !review_change(&window_clone, &controller_clone, || {
+                controller_clone.preview_add(&new_binding)
+            }) {
+                eprintln!("🚫 Add cancelled in review");
+                return;
+            }
             match controller_clone.add_keybinding(new_binding) {
                 Ok(()) => {
                     details_panel_clone.update_binding(None);
                     let updated_bindings = controller_clone.get_current_view();
                     keybind_list_clone.update_with_bindings(updated_bindings);
-                    conflict_panel_clone.refresh();
                     if let Some(app) = window_clone.application() {
                         crate::ui::actions::sync_history_actions(&app, &controller_clone);
                     }
@@ -309,7 +430,6 @@ pub fn wire_up_handlers(
     let controller_for_backup = controller.clone();
     let keybind_list_for_backup = keybind_list.clone();
     let details_panel_for_backup = details_panel.clone();
-    let conflict_panel_for_backup = conflict_panel.clone();
 
     backup_button.connect_clicked(move |_| {
         eprintln!("📦 Backup manager opened");
@@ -325,11 +445,18 @@ pub fn wire_up_handlers(
         let controller_clone = controller_for_backup.clone();
         let keybind_list_clone = keybind_list_for_backup.clone();
         let details_panel_clone = details_panel_for_backup.clone();
-        let conflict_panel_clone = conflict_panel_for_backup.clone();
         let window_for_history_sync = window_for_backup.clone();
 
         let controller_for_delete = controller_for_backup.clone();
 
+        let controller_for_compare = controller_for_backup.clone();
+        let window_for_compare = window_for_backup.clone();
+        let keybind_list_for_compare = keybind_list_for_backup.clone();
+        let details_panel_for_compare = details_panel_for_backup.clone();
+
+        let controller_for_preview = controller_for_backup.clone();
+        let window_for_preview = window_for_backup.clone();
+
         let dialog = BackupDialog::new(
             window_for_backup.upcast_ref::<gtk4::Window>(),
             backups,
@@ -339,7 +466,6 @@ pub fn wire_up_handlers(
 
                     keybind_list_clone.update_with_bindings(updated_bindings);
                     details_panel_clone.update_binding(None);
-                    conflict_panel_clone.refresh();
                     if let Some(app) = window_for_history_sync.application() {
                         crate::ui::actions::sync_history_actions(&app, &controller_clone);
                     }
@@ -348,7 +474,101 @@ pub fn wire_up_handlers(
                 Err(e) => Err(e),
             },
             move |backup_path| controller_for_delete.delete_backup(backup_path),
+            move |backup_path| {
+                let rows = match controller_for_compare.compare_with(backup_path) {
+                    Ok(rows) => rows,
+                    Err(e) => {
+                        eprintln!("❌ Failed to compare with backup: {}", e);
+                        return;
+                    }
+                };
+
+                let controller_clone = controller_for_compare.clone();
+                let keybind_list_clone = keybind_list_for_compare.clone();
+                let details_panel_clone = details_panel_for_compare.clone();
+                let window_for_history_sync = window_for_compare.clone();
+
+                let compare_dialog = CompareDialog::new(
+                    window_for_compare.upcast_ref::<gtk4::Window>(),
+                    &BackupDialog::format_backup_display(backup_path),
+                    rows,
+                    move |rows, choices| {
+                        controller_clone.apply_merge(rows, choices)?;
+
+                        keybind_list_clone
+                            .update_with_bindings(controller_clone.get_current_view());
+                        details_panel_clone.update_binding(None);
+                        if let Some(app) = window_for_history_sync.application() {
+                            crate::ui::actions::sync_history_actions(&app, &controller_clone);
+                        }
+                        Ok(())
+                    },
+                );
+                compare_dialog.show();
+            },
+            move |backup_path| {
+                let diff = match controller_for_preview.preview_restore(backup_path) {
+                    Ok(diff) => diff,
+                    Err(e) => {
+                        eprintln!("❌ Failed to preview restore: {}", e);
+                        return;
+                    }
+                };
+
+                let backup_name = BackupDialog::format_backup_display(backup_path);
+                let file_name = controller_for_preview
+                    .config_path()
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let summary = if diff.is_empty() {
+                    format!("{} matches the current {}.", backup_name, file_name)
+                } else {
+                    format!(
+                        "Restoring {} changes {}: {} line{} removed, {} added.",
+                        backup_name,
+                        file_name,
+                        diff.removed(),
+                        if diff.removed() == 1 { "" } else { "s" },
+                        diff.added()
+                    )
+                };
+                DiffDialog::new(&window_for_preview, "Restore Preview", &summary, &diff).show();
+            },
         );
         dialog.show();
     });
 }
+
+/// Shows the diff of a change and asks whether to save it
+///
+/// Only asks while the "Review changes" preference is on. A preview that
+/// fails does not block the save, which reports the same problem itself.
+///
+/// # Returns
+///
+/// Whether to go ahead with the save
+fn review_change(
+    window: &ApplicationWindow,
+    controller: &Controller,
+    preview: impl FnOnce() -> Result<ConfigDiff, String>,
+) -> bool {
+    if !controller.reviews_changes() {
+        return true;
+    }
+
+    match preview() {
+        Ok(diff) => {
+            let config_path = controller.config_path();
+            let file_name = config_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| config_path.display().to_string());
+            ReviewDialog::new(window, &file_name, &diff).show_and_wait()
+        }
+        Err(e) => {
+            eprintln!("⚠️  {}", e);
+            true
+        }
+    }
+}
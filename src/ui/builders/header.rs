@@ -22,7 +22,13 @@ use gtk4::{gio::Menu, prelude::WidgetExt, Button, HeaderBar, MenuButton};
 ///
 /// Creates a HeaderBar containing a menu button with:
 /// - Export... (app.export action)
+/// - Export Cheat Sheet... (app.cheat-sheet action)
 /// - Import... (app.import action)
+/// - Paste Bindings (app.paste-bindings action)
+/// - Suggest Groups... (app.suggest-groups action)
+/// - Merge Duplicates... (app.merge-duplicates action)
+/// - Preferences... (app.preferences action)
+/// - Help (app.help action)
 /// - Quit (app.quit action)
 ///
 /// # Returns
@@ -34,7 +40,14 @@ pub fn build_header_bar() -> (HeaderBar, Button, Button) {
     // Menu options
     let menu = Menu::new();
     menu.append(Some("Export..."), Some("app.export"));
+    menu.append(Some("Export Cheat Sheet..."), Some("app.cheat-sheet"));
     menu.append(Some("Import..."), Some("app.import"));
+    menu.append(Some("Paste Bindings"), Some("app.paste-bindings"));
+    menu.append(Some("Suggest Groups..."), Some("app.suggest-groups"));
+    menu.append(Some("Merge Duplicates..."), Some("app.merge-duplicates"));
+    menu.append(Some("Statistics..."), Some("app.statistics"));
+    menu.append(Some("Preferences..."), Some("app.preferences"));
+    menu.append(Some("Help"), Some("app.help"));
     menu.append(Some("Quit..."), Some("app.quit"));
 
     // Menu button
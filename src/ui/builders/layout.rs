@@ -17,10 +17,13 @@
 //! Creates the main application layout structure.
 
 use crate::ui::{
-    components::{ConflictPanel, DetailsPanel, KeybindList, SearchBar},
+    components::{
+        CategoryFilter, ConflictPanel, DetailsPanel, KeybindList, ProblemsPanel, ScopeSwitcher,
+        SearchBar,
+    },
     Controller,
 };
-use gtk4::{prelude::*, Box as GtkBox, Button, Orientation, Paned};
+use gtk4::{prelude::*, Box as GtkBox, Button, CheckButton, Orientation, Paned};
 use std::rc::Rc;
 
 pub const DEFAULT_WINDOW_WIDTH: i32 = 1000;
@@ -32,7 +35,8 @@ pub const MIN_LEFT_PANEL_WIDTH: i32 = 520;
 /// Creates a vertical box containing:
 /// - Conflict panel at top
 /// - Paned layout with:
-///   - Left: Search bar, buttons, keybinding list
+///   - Left: Search bar, duplicate action filter, runtime comparison
+///     toggle, scope switcher, buttons, keybinding list
 ///   - Right: Details panel (fixed 280px width)
 ///
 /// # Returns
@@ -56,6 +60,30 @@ pub fn build_main_layout(
     let conflict_panel = Rc::new(ConflictPanel::new(controller.clone()));
     main_vbox.append(conflict_panel.widget());
 
+    // The banner follows the controller rather than being refreshed by hand
+    let conflict_panel_for_changes = conflict_panel.clone();
+    controller.on_conflicts_changed(move |_| conflict_panel_for_changes.refresh());
+
+    // Lines the parser skipped or misread, lint findings and, while the
+    // runtime toggle is on, differences from Hyprland, shown after every
+    // load and edit
+    let problems_panel = Rc::new(ProblemsPanel::new());
+    main_vbox.append(problems_panel.widget());
+    let refresh_problems = {
+        let problems_panel = problems_panel.clone();
+        let controller = controller.clone();
+        Rc::new(move || {
+            problems_panel.refresh(
+                &controller.get_parse_diagnostics(),
+                &controller.get_lint_findings(),
+                controller.get_runtime_drift().as_ref(),
+            )
+        })
+    };
+    refresh_problems();
+    let refresh_problems_for_changes = refresh_problems.clone();
+    controller.on_bindings_changed(move |_| refresh_problems_for_changes());
+
     let paned = Paned::new(Orientation::Horizontal);
     paned.set_wide_handle(true);
 
@@ -73,6 +101,26 @@ pub fn build_main_layout(
     let search_bar = SearchBar::new();
     left_vbox.append(search_bar.widget());
 
+    let duplicates_toggle = CheckButton::with_label("Only actions bound to several keys");
+    duplicates_toggle.set_tooltip_text(Some(
+        "Show only bindings whose dispatcher and arguments another key combo also runs",
+    ));
+    duplicates_toggle.set_can_focus(true);
+    left_vbox.append(&duplicates_toggle);
+
+    let runtime_toggle = CheckButton::with_label("Compare with running Hyprland");
+    runtime_toggle.set_tooltip_text(Some(
+        "List bindings Hyprland has not loaded, and live bindings the config does not declare",
+    ));
+    runtime_toggle.set_can_focus(true);
+    left_vbox.append(&runtime_toggle);
+
+    let scope_switcher = Rc::new(ScopeSwitcher::new(controller.clone()));
+    left_vbox.append(scope_switcher.widget());
+
+    let category_filter = Rc::new(CategoryFilter::new(controller.clone()));
+    left_vbox.append(category_filter.widget());
+
     let add_keybinding_button = Button::builder().label("➕ Add Keybinding").build();
     add_keybinding_button.add_css_class("suggested-action");
     add_keybinding_button.set_tooltip_text(Some("Create a new keybinding"));
@@ -103,6 +151,52 @@ pub fn build_main_layout(
         keybind_list_for_search.update_with_bindings(filtered);
     });
 
+    let keybind_list_for_duplicates = keybind_list.clone();
+    let controller_for_duplicates = controller.clone();
+
+    duplicates_toggle.connect_toggled(move |toggle| {
+        eprintln!("🔁 Duplicate action filter: {}", toggle.is_active());
+        controller_for_duplicates.set_duplicate_actions_only(toggle.is_active());
+        let filtered = controller_for_duplicates.get_current_view();
+        keybind_list_for_duplicates.update_with_bindings(filtered);
+    });
+
+    let controller_for_runtime = controller.clone();
+
+    runtime_toggle.connect_toggled(move |toggle| {
+        eprintln!("🔌 Runtime comparison: {}", toggle.is_active());
+        controller_for_runtime.set_runtime_check(toggle.is_active());
+        refresh_problems();
+    });
+
+    let keybind_list_for_scope = keybind_list.clone();
+    let controller_for_scope = controller.clone();
+
+    scope_switcher.connect_scope_changed(move |scope| {
+        eprintln!("🗂 Scope preview: {}", scope.as_deref().unwrap_or("all"));
+        controller_for_scope.set_scope(scope);
+        let filtered = controller_for_scope.get_current_view();
+        keybind_list_for_scope.update_with_bindings(filtered);
+    });
+
+    let keybind_list_for_category = keybind_list.clone();
+    let controller_for_category = controller.clone();
+
+    category_filter.connect_category_changed(move |category| {
+        eprintln!(
+            "🏷 Category filter: {}",
+            category.as_deref().unwrap_or("all")
+        );
+        controller_for_category.set_category_filter(category);
+        let filtered = controller_for_category.get_current_view();
+        keybind_list_for_category.update_with_bindings(filtered);
+    });
+
+    // Scope sections and categories may come and go whenever the config
+    // changes
+    controller.on_bindings_changed(move |_| scope_switcher.refresh());
+    controller.on_bindings_changed(move |_| category_filter.refresh());
+
     let details_panel = Rc::new(DetailsPanel::new(controller.clone()));
 
     paned.set_start_child(Some(&left_vbox));
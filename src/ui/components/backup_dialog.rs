@@ -26,6 +26,9 @@ use std::{
 ///
 /// Displays a list of timestamped backups sorted newest to oldest, with options to:
 /// - **Restore:** Replace current config with selected backup
+/// - **Preview:** Show what restoring the backup would change in the config,
+///   as a line diff
+/// - **Compare:** Show the backup side by side with the current bindings
 /// - **Delete:** Remove old backups (not yet implemented)
 /// - **Close:** Dismiss dialog
 ///
@@ -43,7 +46,8 @@ impl BackupDialog {
     /// Converts timestamps from `hyprland.conf.2025-10-15_143025` format
     /// to human-readable `2025-10-15 14:30:25`.
     ///
-    /// If the filename doesn't match the expected pattern, returns the
+    /// The pristine backup (`hyprland.conf.pristine`) gets a descriptive
+    /// label. If the filename doesn't match the expected pattern, returns the
     /// filename as-is for safe fallback.
     ///
     /// # Arguments
@@ -60,10 +64,20 @@ impl BackupDialog {
             .and_then(|n| n.to_str())
             .unwrap_or("Unknown backup");
 
+        // Compressed backups end in .gz or .zst after the timestamp
+        let stem = [".gz", ".zst"]
+            .into_iter()
+            .find_map(|extension| filename.strip_suffix(extension))
+            .unwrap_or(filename);
+
         // Timestamp parsing and reformatting/-styling
-        let parts: Vec<&str> = filename.split('.').collect(); // parts = ["hyprland", "conf", "2025-10-15_143025"}
+        let parts: Vec<&str> = stem.split('.').collect(); // parts = ["hyprland", "conf", "2025-10-15_143025"}
         let timestamp = parts.last().unwrap_or(&""); // timestamp = "2025-10-15_143025"
 
+        if *timestamp == "pristine" {
+            return "Original config (before first edit)".to_string();
+        }
+
         // Start with the filename as fallback
         let mut display_text = filename.to_string();
 
@@ -89,14 +103,26 @@ impl BackupDialog {
     /// * `parent` - Parent window for modal behaviour
     /// * `backups` - List of backup file paths (typically from `ConfigManager::list_backups()`)
     /// * `on_restore` - Callback invoked when user clicks Restore (receives backup path)
+    /// * `on_delete` - Callback invoked when user clicks Delete (receives backup path)
+    /// * `on_compare` - Callback invoked when user clicks Compare (receives backup path)
+    /// * `on_preview` - Callback invoked when user clicks Preview (receives backup path)
     ///
     /// # Returns
     ///
     /// A new `BackupDialog` instance ready to be shown with `.show()`
-    pub fn new<F, G>(parent: &Window, backups: Vec<PathBuf>, on_restore: F, on_delete: G) -> Self
+    pub fn new<F, G, H, P>(
+        parent: &Window,
+        backups: Vec<PathBuf>,
+        on_restore: F,
+        on_delete: G,
+        on_compare: H,
+        on_preview: P,
+    ) -> Self
     where
         F: Fn(&Path) -> Result<(), String> + 'static,
         G: Fn(&Path) -> Result<(), String> + 'static,
+        H: Fn(&Path) + 'static,
+        P: Fn(&Path) + 'static,
     {
         let bd_window = Window::builder()
             .title("Backups")
@@ -167,12 +193,18 @@ impl BackupDialog {
             .build();
         restore_button.add_css_class("suggested-action"); // Blue/primary colour
 
+        let preview_button = Button::builder().label("Preview").sensitive(false).build();
+
+        let compare_button = Button::builder().label("Compare").sensitive(false).build();
+
         let delete_button = Button::builder().label("Delete").sensitive(false).build();
         delete_button.add_css_class("destructive-action"); // Red colour
 
         let close_button = Button::builder().label("Close").build();
 
         button_box.append(&restore_button);
+        button_box.append(&preview_button);
+        button_box.append(&compare_button);
         button_box.append(&delete_button);
         button_box.append(&close_button);
 
@@ -187,6 +219,8 @@ impl BackupDialog {
         let selected_backup_clone = selected_backup.clone();
         let restore_clone = restore_button.clone();
         let delete_clone = delete_button.clone();
+        let compare_clone = compare_button.clone();
+        let preview_clone = preview_button.clone();
         let read_clone = dialog_ready.clone();
 
         list_box.connect_row_selected(move |_list, row| {
@@ -204,11 +238,15 @@ impl BackupDialog {
                     selected_backup_clone.set(Some(row_index));
                     restore_clone.set_sensitive(true);
                     delete_clone.set_sensitive(true);
+                    compare_clone.set_sensitive(true);
+                    preview_clone.set_sensitive(true);
                 }
                 None => {
                     selected_backup_clone.set(None);
                     restore_clone.set_sensitive(false);
                     delete_clone.set_sensitive(false);
+                    compare_clone.set_sensitive(false);
+                    preview_clone.set_sensitive(false);
                 }
             }
         });
@@ -259,6 +297,34 @@ impl BackupDialog {
             }
         });
 
+        // ===== COMPARE BUTTON CALLBACK =====
+        let backups_for_compare = backups.clone();
+        let selected_for_compare = selected_backup.clone();
+
+        compare_button.connect_clicked(move |_| {
+            eprintln!("🔍 Compare button clicked");
+
+            if let Some(backup_path) = selected_for_compare
+                .get()
+                .and_then(|index| backups_for_compare.get(index))
+            {
+                on_compare(backup_path);
+            }
+        });
+
+        // ===== PREVIEW BUTTON CALLBACK =====
+        let backups_for_preview = backups.clone();
+        let selected_for_preview = selected_backup.clone();
+
+        preview_button.connect_clicked(move |_| {
+            if let Some(backup_path) = selected_for_preview
+                .get()
+                .and_then(|index| backups_for_preview.get(index))
+            {
+                on_preview(backup_path);
+            }
+        });
+
         let window_for_close = bd_window.clone();
 
         // ===== DELETE BUTTON CALLBACK =====
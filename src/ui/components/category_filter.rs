@@ -0,0 +1,103 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Category filter component
+//!
+//! Drop-down that limits the list to the bindings of one category (set by
+//! `# @category:` annotations). Hidden while no binding has a category.
+
+use gtk4::{prelude::*, Box as GtkBox, DropDown, Label, Orientation, StringList};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::ui::Controller;
+
+/// First entry of the drop-down: no category selected
+const ALL_CATEGORIES: &str = "All categories";
+
+/// Drop-down for showing one category's bindings
+pub struct CategoryFilter {
+    /// Root widget (label and drop-down)
+    widget: GtkBox,
+    /// The drop-down itself
+    drop_down: DropDown,
+    /// Controller reference for the category list
+    controller: Rc<Controller>,
+    /// Categories currently offered, in drop-down order after "All categories"
+    categories: Rc<RefCell<Vec<String>>>,
+}
+
+impl CategoryFilter {
+    /// Creates the filter, offering the categories the controller knows
+    ///
+    /// Returns just the widget - parent is responsible for wiring up
+    /// [`connect_category_changed`](Self::connect_category_changed).
+    pub fn new(controller: Rc<Controller>) -> Self {
+        let widget = GtkBox::new(Orientation::Horizontal, 8);
+        let label = Label::new(Some("Category:"));
+        let drop_down = DropDown::from_strings(&[ALL_CATEGORIES]);
+        drop_down.set_tooltip_text(Some("Show only the bindings tagged with one category"));
+        drop_down.set_can_focus(true);
+        widget.append(&label);
+        widget.append(&drop_down);
+
+        let filter = Self {
+            widget,
+            drop_down,
+            controller,
+            categories: Rc::new(RefCell::new(Vec::new())),
+        };
+        filter.refresh();
+        filter
+    }
+
+    /// Returns the root widget for adding to parent container
+    pub fn widget(&self) -> &GtkBox {
+        &self.widget
+    }
+
+    /// Calls `callback` with the newly selected category (`None`: all)
+    pub fn connect_category_changed<F: Fn(Option<String>) + 'static>(&self, callback: F) {
+        let categories = self.categories.clone();
+        self.drop_down.connect_selected_notify(move |drop_down| {
+            let selected = drop_down.selected() as usize;
+            let category = selected
+                .checked_sub(1)
+                .and_then(|index| categories.borrow().get(index).cloned());
+            callback(category);
+        });
+    }
+
+    /// Offers the categories of the bindings as loaded now
+    ///
+    /// The selected category stays selected if a binding still has it,
+    /// otherwise the filter goes back to all categories.
+    pub fn refresh(&self) {
+        let categories = self.controller.get_categories();
+        self.widget.set_visible(!categories.is_empty());
+        if *self.categories.borrow() == categories {
+            return;
+        }
+
+        let selected = self.controller.get_category_filter();
+        *self.categories.borrow_mut() = categories.clone();
+        let mut names = vec![ALL_CATEGORIES];
+        names.extend(categories.iter().map(String::as_str));
+        self.drop_down.set_model(Some(&StringList::new(&names)));
+
+        let position = selected
+            .and_then(|category| categories.iter().position(|name| *name == category))
+            .map_or(0, |index| index + 1);
+        self.drop_down.set_selected(position as u32);
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gtk4::{
+    gdk, prelude::*, Align, Box as GtkBox, Button, CheckButton, EventControllerKey, Grid, Label,
+    Orientation, ScrolledWindow, ToggleButton, Window,
+};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::core::{
+    compare::{ComparisonRow, RowStatus, Side},
+    Keybinding,
+};
+
+/// Dialog comparing the current bindings with another config side by side.
+///
+/// Rows are aligned by key combo and marked by how the right side differs:
+/// - **+** the right side adds a binding
+/// - **−** the right side lacks the binding
+/// - **~** both sides bind the keys differently
+///
+/// Each differing row has "take left" / "take right" toggles. Nothing is
+/// written until **Apply Merge**, which writes all choices in one
+/// transaction (one backup, one undo step).
+pub struct CompareDialog {
+    window: Window,
+}
+
+impl CompareDialog {
+    /// Creates a new comparison dialog.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Parent window for modal behaviour
+    /// * `right_title` - Heading for the right side (e.g. the backup's date)
+    /// * `rows` - Rows from `Controller::compare_with()`
+    /// * `on_apply` - Callback invoked with the chosen side per row
+    pub fn new<F>(parent: &Window, right_title: &str, rows: Vec<ComparisonRow>, on_apply: F) -> Self
+    where
+        F: Fn(&[ComparisonRow], &[Side]) -> Result<(), String> + 'static,
+    {
+        let window = Window::builder()
+            .title("Compare")
+            .modal(true)
+            .transient_for(parent)
+            .default_width(900)
+            .default_height(500)
+            .build();
+
+        // Escape key handler
+        let key_controller = EventControllerKey::new();
+        let window_for_escape = window.clone();
+        key_controller.connect_key_pressed(move |_, key, _, _| {
+            if key == gdk::Key::Escape {
+                window_for_escape.close();
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+        window.add_controller(key_controller);
+
+        let choices = Rc::new(RefCell::new(vec![Side::Left; rows.len()]));
+
+        let main_vbox = GtkBox::new(Orientation::Vertical, 12);
+        main_vbox.set_margin_start(12);
+        main_vbox.set_margin_end(12);
+        main_vbox.set_margin_top(12);
+        main_vbox.set_margin_bottom(12);
+
+        let differing = rows
+            .iter()
+            .filter(|row| row.status() != RowStatus::Unchanged)
+            .count();
+        let summary = Label::new(Some(&match differing {
+            0 => "Both sides have the same bindings.".to_string(),
+            1 => "1 key combo differs.".to_string(),
+            n => format!("{} key combos differ.", n),
+        }));
+        summary.set_halign(Align::Start);
+        main_vbox.append(&summary);
+
+        let show_unchanged = CheckButton::with_label("Show unchanged bindings");
+        main_vbox.append(&show_unchanged);
+
+        // Columns: marker | key combo | left | take left | take right | right
+        let grid = Grid::builder().column_spacing(12).row_spacing(6).build();
+        for (column, title) in [(2, "Current"), (5, right_title)] {
+            let heading = Label::new(Some(title));
+            heading.add_css_class("heading");
+            heading.set_halign(Align::Start);
+            grid.attach(&heading, column, 0, 1, 1);
+        }
+
+        let mut unchanged_widgets: Vec<gtk4::Widget> = Vec::new();
+        let mut toggles: Vec<(ToggleButton, ToggleButton)> = Vec::new();
+
+        for (index, row) in rows.iter().enumerate() {
+            let grid_row = index as i32 + 1;
+            let status = row.status();
+
+            let marker = Label::new(Some(status.marker()));
+            marker.add_css_class("compare-marker");
+            if let Some(class) = Self::status_class(status) {
+                marker.add_css_class(class);
+            }
+
+            let key_text = match &row.submap {
+                Some(submap) => format!("{} (submap: {})", row.key_combo, submap),
+                None => row.key_combo.to_string(),
+            };
+            let key_label = Label::new(Some(&key_text));
+            key_label.set_halign(Align::Start);
+
+            let left_label = Self::side_label(&row.left);
+            let right_label = Self::side_label(&row.right);
+
+            grid.attach(&marker, 0, grid_row, 1, 1);
+            grid.attach(&key_label, 1, grid_row, 1, 1);
+            grid.attach(&left_label, 2, grid_row, 1, 1);
+            grid.attach(&right_label, 5, grid_row, 1, 1);
+
+            if status == RowStatus::Unchanged {
+                for widget in [
+                    marker.upcast::<gtk4::Widget>(),
+                    key_label.upcast(),
+                    left_label.upcast(),
+                    right_label.upcast(),
+                ] {
+                    widget.set_visible(false);
+                    unchanged_widgets.push(widget);
+                }
+                continue;
+            }
+
+            let take_left = ToggleButton::builder()
+                .label("◀")
+                .tooltip_text("Keep the current binding")
+                .active(true)
+                .build();
+            let take_right = ToggleButton::builder()
+                .label("▶")
+                .tooltip_text(format!("Take the binding from {}", right_title))
+                .build();
+            take_right.set_group(Some(&take_left));
+
+            let choices_for_toggle = choices.clone();
+            take_right.connect_toggled(move |button| {
+                choices_for_toggle.borrow_mut()[index] = if button.is_active() {
+                    Side::Right
+                } else {
+                    Side::Left
+                };
+            });
+
+            grid.attach(&take_left, 3, grid_row, 1, 1);
+            grid.attach(&take_right, 4, grid_row, 1, 1);
+            toggles.push((take_left, take_right));
+        }
+
+        show_unchanged.connect_toggled(move |check| {
+            for widget in &unchanged_widgets {
+                widget.set_visible(check.is_active());
+            }
+        });
+
+        let scrolled_window = ScrolledWindow::builder().vexpand(true).build();
+        scrolled_window.set_child(Some(&grid));
+        main_vbox.append(&scrolled_window);
+
+        // Button row
+        let button_box = GtkBox::new(Orientation::Horizontal, 12);
+        button_box.set_halign(Align::End);
+
+        let all_left_button = Button::builder().label("Keep All Current").build();
+        let all_right_button = Button::builder().label("Take All").build();
+        let apply_button = Button::builder()
+            .label("Apply Merge")
+            .sensitive(differing > 0)
+            .build();
+        apply_button.add_css_class("suggested-action");
+        let close_button = Button::builder().label("Close").build();
+
+        button_box.append(&all_left_button);
+        button_box.append(&all_right_button);
+        button_box.append(&apply_button);
+        button_box.append(&close_button);
+        main_vbox.append(&button_box);
+
+        let toggles = Rc::new(toggles);
+        let toggles_for_left = toggles.clone();
+        all_left_button.connect_clicked(move |_| {
+            for (take_left, _) in toggles_for_left.iter() {
+                take_left.set_active(true);
+            }
+        });
+        all_right_button.connect_clicked(move |_| {
+            for (_, take_right) in toggles.iter() {
+                take_right.set_active(true);
+            }
+        });
+
+        let window_for_apply = window.clone();
+        apply_button.connect_clicked(move |_| {
+            eprintln!("🔀 Applying comparison merge");
+
+            match on_apply(&rows, &choices.borrow()) {
+                Ok(()) => {
+                    eprintln!("✅ Merge applied");
+                    window_for_apply.close();
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to apply merge: {}", e);
+
+                    let error_dialog = gtk4::AlertDialog::builder()
+                        .modal(true)
+                        .message("Merge Failed")
+                        .detail(format!("Failed to apply merge:\n\n{}", e))
+                        .buttons(vec!["OK"])
+                        .build();
+
+                    error_dialog.show(Some(&window_for_apply));
+                }
+            }
+        });
+
+        let window_for_close = window.clone();
+        close_button.connect_clicked(move |_| window_for_close.close());
+
+        window.set_child(Some(&main_vbox));
+
+        Self { window }
+    }
+
+    /// Displays the comparison dialog.
+    pub fn show(&self) {
+        self.window.present();
+    }
+
+    /// CSS class colouring a row marker
+    fn status_class(status: RowStatus) -> Option<&'static str> {
+        match status {
+            RowStatus::Unchanged => None,
+            RowStatus::Added => Some("compare-added"),
+            RowStatus::Removed => Some("compare-removed"),
+            RowStatus::Changed => Some("compare-changed"),
+        }
+    }
+
+    /// One line per binding on a side, or a dash if the side has none
+    fn side_label(bindings: &[Keybinding]) -> Label {
+        let text = if bindings.is_empty() {
+            "—".to_string()
+        } else {
+            bindings
+                .iter()
+                .map(|binding| match &binding.args {
+                    Some(args) => format!("{} {}", binding.dispatcher, args),
+                    None => binding.dispatcher.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let label = Label::new(Some(&text));
+        label.set_halign(Align::Start);
+        label.set_hexpand(true);
+        label.set_wrap(true);
+        label.set_selectable(true);
+        label
+    }
+}
@@ -21,15 +21,16 @@
 //!
 //! - Yellow warning banner using GTK4's GtkBox widget
 //! - Displays count of detected conflicts
+//! - Colour-coded badges counting the conflicts of each severity
 //! - Automatically shows/hides based on conflict state
 //! - Smooth reveal/hide animations
 //!
 //! # Layout
 //!
 //! ```text
-//! ┌─────────────────────────────────────────────────────┐
-//! │ ⚠️  Warning: 2 keybinding conflicts detected        │
-//! └─────────────────────────────────────────────────────┘
+//! ┌─────────────────────────────────────────────────────────────────┐
+//! │ ⚠️  Warning: 3 keybinding conflicts detected  [2 high] [1 low]  │
+//! └─────────────────────────────────────────────────────────────────┘
 //! ```
 //!
 //! # Example
@@ -41,16 +42,20 @@
 //!     Controller::new(PathBuf::from("~/.config/hypr/hyprland.conf"))
 //!         .expect("Failed to create controller")
 //! );
-//! let panel = ConflictPanel::new(controller.clone());
+//! let panel = Rc::new(ConflictPanel::new(controller.clone()));
 //! // Initially hidden (no conflicts loaded yet)
-//! // After loading keybindings:
-//! panel.refresh();  // Shows banner if conflicts exist
+//! // Follow the controller from now on:
+//! let panel_for_changes = panel.clone();
+//! controller.on_conflicts_changed(move |_| panel_for_changes.refresh());
 //! ```
 
 use gtk4::{prelude::*, Box as GtkBox, Button, Label, Orientation, Revealer};
 use std::rc::Rc;
 
-use crate::ui::{components::KeybindList, Controller};
+use crate::{
+    core::ConflictSeverity,
+    ui::{components::KeybindList, Controller},
+};
 
 /// Warning panel that displays when keybinding conflicts are detected
 ///
@@ -62,6 +67,8 @@ pub struct ConflictPanel {
     widget: Revealer,
     /// Label displaying the conflict message and count
     message_label: Label,
+    /// Per-severity count badges, rebuilt on every refresh
+    severity_box: GtkBox,
     /// Button for accessing conflict resolution dialog
     resolve_button: Button,
     /// Controller for accessing conflict data
@@ -122,8 +129,11 @@ impl ConflictPanel {
 
         let resolve_button = Button::builder().label("Resolve Conflict(s)").build();
 
-        // Add label, spacer, and button to warning box
+        let severity_box = GtkBox::new(Orientation::Horizontal, 6);
+
+        // Add label, badges, spacer, and button to warning box
         warning_box.append(&message_label);
+        warning_box.append(&severity_box);
 
         let spacer = GtkBox::new(Orientation::Horizontal, 0);
         spacer.set_hexpand(true);
@@ -135,6 +145,7 @@ impl ConflictPanel {
         Self {
             widget: revealer,
             message_label,
+            severity_box,
             resolve_button,
             controller,
         }
@@ -147,6 +158,8 @@ impl ConflictPanel {
     /// - Hides the panel if no conflicts exist
     ///
     /// The panel smoothly animates in/out using GTK4's reveal animation.
+    /// The main window runs this whenever the controller reports changed
+    /// conflicts, so it only needs calling directly for the initial state.
     ///
     /// # Example
     ///
@@ -158,9 +171,6 @@ impl ConflictPanel {
     ///
     /// // After loading keybindings
     /// panel.refresh();  // Updates based on current conflicts
-    ///
-    /// // After resolving conflicts
-    /// panel.refresh();  // Panel will hide automatically
     /// ```
     pub fn refresh(&self) {
         let conflicts = self.controller.get_conflicts();
@@ -184,6 +194,30 @@ impl ConflictPanel {
             self.message_label.set_label(&message);
             self.resolve_button.set_visible(true);
         }
+
+        // One colour-coded badge per severity present, most severe first
+        while let Some(child) = self.severity_box.first_child() {
+            self.severity_box.remove(&child);
+        }
+        for severity in [
+            ConflictSeverity::High,
+            ConflictSeverity::Medium,
+            ConflictSeverity::Low,
+        ] {
+            let count = conflicts.iter().filter(|c| c.severity == severity).count();
+            if count == 0 {
+                continue;
+            }
+            let badge = Label::new(Some(format!("{} {}", count, severity.label()).as_str()));
+            badge.add_css_class("row-badge");
+            badge.add_css_class(&format!("badge-severity-{}", severity.label()));
+            badge.set_tooltip_text(Some(match severity {
+                ConflictSeverity::High => "Different actions compete for one key",
+                ConflictSeverity::Medium => "Possibly intended, but worth a look",
+                ConflictSeverity::Low => "Exact duplicates, safe to clean up",
+            }));
+            self.severity_box.append(&badge);
+        }
     }
 
     /// Returns the root widget for adding to a container
@@ -222,12 +256,7 @@ impl ConflictPanel {
     ///
     /// # Arguments
     /// * `parent` - Parent window for the modal dialog
-    pub fn connect_resolve_button(
-        &self,
-        parent: &gtk4::Window,
-        conflict_panel: Rc<ConflictPanel>,
-        keybind_list: Rc<KeybindList>,
-    ) {
+    pub fn connect_resolve_button(&self, parent: &gtk4::Window, keybind_list: Rc<KeybindList>) {
         let parent_clone = parent.clone();
         let controller_clone = self.controller.clone();
 
@@ -236,8 +265,8 @@ impl ConflictPanel {
                 crate::ui::components::conflict_resolution_dialog::ConflictResolutionDialog::new(
                     &parent_clone,
                     controller_clone.clone(),
-                    conflict_panel.clone(),
                     keybind_list.clone(),
+                    None,
                 );
             dialog.show();
         });
@@ -18,16 +18,30 @@
 //! Displays all conflicts grouped by key combination, with delete buttons
 //! for each conflicting binding. Automatically refreshes the UI after
 //! deletions and closes when all conflicts in view are resolved.
+//!
+//! The dialog can be limited to a single key combo, which is how the
+//! conflict badge on a list row opens it.
+//!
+//! Conflicts that moving a binding resolves list free key combos, and every
+//! binding but the first gets a button moving it to one of them.
+//!
+//! Each conflict also offers "Keep First", "Keep Last" and "Delete All", and
+//! "Keep First in All" settles every conflict in view at once. Each button
+//! is one write through [`Controller::resolve_conflicts`], so one backup and
+//! one undo step.
+//!
+//! Each conflict has a "Learn more" expander with the embedded explanation
+//! from `core::explanations` and a link to the Hyprland wiki.
 
 use gtk4::{
-    gdk, prelude::*, Align, Box as GtkBox, Button, EventControllerKey, Label, Orientation,
-    ScrolledWindow, Window,
+    gdk, prelude::*, Align, Box as GtkBox, Button, EventControllerKey, Expander, Label, LinkButton,
+    Orientation, ScrolledWindow, Window,
 };
 use std::rc::Rc;
 
-use crate::ui::{
-    components::{ConflictPanel, KeybindList},
-    Controller,
+use crate::{
+    core::{Conflict, KeyCombo},
+    ui::{components::KeybindList, controller::ConflictResolution, Controller},
 };
 
 pub struct ConflictResolutionDialog {
@@ -35,14 +49,27 @@ pub struct ConflictResolutionDialog {
 }
 
 impl ConflictResolutionDialog {
+    /// Creates the dialog
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Parent window for modal behaviour
+    /// * `controller` - Shared controller for conflict data and deletions
+    /// * `keybind_list` - Refreshed after a deletion
+    /// * `only_key_combo` - Show only the conflict for this combo (`None` = all)
     pub fn new(
         parent: &Window,
         controller: Rc<Controller>,
-        conflict_panel: Rc<ConflictPanel>,
         keybind_list: Rc<KeybindList>,
+        only_key_combo: Option<&KeyCombo>,
     ) -> Self {
+        let title = match only_key_combo {
+            Some(key_combo) => format!("Resolve Conflict: {}", key_combo),
+            None => "Resolve Conflicts".to_string(),
+        };
+
         let window = Window::builder()
-            .title("Resolve Conflicts")
+            .title(title)
             .modal(true)
             .transient_for(parent)
             .default_width(500)
@@ -81,7 +108,11 @@ impl ConflictResolutionDialog {
         conflict_box.set_margin_end(6);
 
         // Get conflicts from controller
-        let conflicts = controller.get_conflicts();
+        let conflicts: Vec<_> = controller
+            .get_conflicts()
+            .into_iter()
+            .filter(|conflict| only_key_combo.is_none_or(|combo| conflict.key_combo == *combo))
+            .collect();
 
         for conflict in conflicts.iter() {
             // Group container for this conflict
@@ -89,13 +120,55 @@ impl ConflictResolutionDialog {
             group_box.set_margin_start(20);
 
             // Header showing conflicted key combo
-            let header = Label::new(Some(&format!("⚠️ Conflict: {}", conflict.key_combo)));
+            let header = Label::new(Some(&match &conflict.submap {
+                Some(submap) => format!("⚠️ Conflict: {} (submap {})", conflict.key_combo, submap),
+                None => format!("⚠️ Conflict: {}", conflict.key_combo),
+            }));
             header.set_halign(Align::Start);
             header.add_css_class("conflict-header");
             group_box.append(&header);
 
+            // Whole-conflict resolutions
+            let resolve_row = GtkBox::new(Orientation::Horizontal, 8);
+            resolve_row.set_margin_start(20);
+            for (label, resolution) in [
+                ("Keep First", ConflictResolution::KeepFirst),
+                ("Keep Last", ConflictResolution::KeepLast),
+                ("Delete All", ConflictResolution::DeleteAll),
+            ] {
+                let button = Self::create_resolve_button(
+                    label,
+                    vec![(conflict.clone(), resolution)],
+                    &window,
+                    &controller,
+                    &keybind_list,
+                );
+                if label == "Delete All" {
+                    button.add_css_class("destructive-action");
+                }
+                resolve_row.append(&button);
+            }
+            group_box.append(&resolve_row);
+
+            // Free combos, one per binding that could move
+            let free_combos =
+                controller.suggest_for_conflict(conflict, conflict.conflicting_bindings.len() - 1);
+            if !free_combos.is_empty() {
+                let free_text = free_combos
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let free_label = Label::new(Some(format!("💡 Free: {}", free_text).as_str()));
+                free_label.set_halign(Align::Start);
+                free_label.set_margin_start(20);
+                free_label.set_wrap(true);
+                free_label.add_css_class("dim-label");
+                group_box.append(&free_label);
+            }
+
             // List each conflicting binding
-            for binding in conflict.conflicting_bindings.iter() {
+            for (index, binding) in conflict.conflicting_bindings.iter().enumerate() {
                 let binding_row = GtkBox::new(Orientation::Horizontal, 8);
                 binding_row.set_margin_start(20);
 
@@ -111,6 +184,27 @@ impl ConflictResolutionDialog {
                 label.set_hexpand(true);
                 binding_row.append(&label);
 
+                // Move button: the first binding keeps the combo
+                if let Some(free_combo) = index
+                    .checked_sub(1)
+                    .and_then(|free_index| free_combos.get(free_index))
+                {
+                    let move_button = Self::create_resolve_button(
+                        &format!("Move to {}", free_combo),
+                        vec![(
+                            conflict.clone(),
+                            ConflictResolution::Rebind {
+                                index,
+                                to: free_combo.clone(),
+                            },
+                        )],
+                        &window,
+                        &controller,
+                        &keybind_list,
+                    );
+                    binding_row.append(&move_button);
+                }
+
                 // Delete button
                 let delete_button = Button::with_label("Delete");
                 delete_button.add_css_class("destructive-action");
@@ -120,7 +214,6 @@ impl ConflictResolutionDialog {
                 let binding_clone = binding.clone();
                 let controller_clone = controller.clone();
                 let window_clone = window.clone();
-                let conflict_panel_clone = conflict_panel.clone();
                 let keybind_list_clone = keybind_list.clone();
                 delete_button.connect_clicked(move |_| {
                     eprintln!("🗑️ Deleting keybinding: {}", binding_clone);
@@ -131,7 +224,6 @@ impl ConflictResolutionDialog {
                         // Refresh UI
                         let all_bindings = controller_clone.get_keybindings();
                         keybind_list_clone.update_with_bindings(all_bindings);
-                        conflict_panel_clone.refresh();
                         window_clone.close();
                     }
                 });
@@ -139,12 +231,32 @@ impl ConflictResolutionDialog {
                 group_box.append(&binding_row);
             }
 
+            group_box.append(&Self::create_learn_more(conflict));
+
             conflict_box.append(&group_box);
         }
 
         scrolled.set_child(Some(&conflict_box));
         main_box.append(&scrolled);
 
+        let button_row = GtkBox::new(Orientation::Horizontal, 8);
+        button_row.set_halign(Align::End);
+
+        // Batch resolution, only worth offering for several conflicts
+        if conflicts.len() > 1 {
+            let keep_all_button = Self::create_resolve_button(
+                "Keep First in All",
+                conflicts
+                    .iter()
+                    .map(|conflict| (conflict.clone(), ConflictResolution::KeepFirst))
+                    .collect(),
+                &window,
+                &controller,
+                &keybind_list,
+            );
+            button_row.append(&keep_all_button);
+        }
+
         // Close button
         let close_button = Button::with_label("Close");
         let window_clone = window.clone();
@@ -152,7 +264,8 @@ impl ConflictResolutionDialog {
             window_clone.close();
         });
 
-        main_box.append(&close_button);
+        button_row.append(&close_button);
+        main_box.append(&button_row);
 
         window.set_child(Some(&main_box));
 
@@ -162,4 +275,67 @@ impl ConflictResolutionDialog {
     pub fn show(&self) {
         self.window.present();
     }
+
+    /// Builds a button applying resolutions in one write
+    ///
+    /// On success the list is refreshed and the dialog closes, as the
+    /// conflicts it lists are stale.
+    fn create_resolve_button(
+        label: &str,
+        resolutions: Vec<(Conflict, ConflictResolution)>,
+        window: &Window,
+        controller: &Rc<Controller>,
+        keybind_list: &Rc<KeybindList>,
+    ) -> Button {
+        let button = Button::with_label(label);
+
+        let window = window.clone();
+        let controller = controller.clone();
+        let keybind_list = keybind_list.clone();
+        button.connect_clicked(move |_| match controller.resolve_conflicts(&resolutions) {
+            Ok(()) => {
+                eprintln!("✅ Resolved {} conflict(s)", resolutions.len());
+                keybind_list.update_with_bindings(controller.get_keybindings());
+                window.close();
+            }
+            Err(e) => eprintln!("❌ Error resolving conflict: {}", e),
+        });
+
+        button
+    }
+
+    /// Builds the collapsed "Learn more" section explaining the conflict kind
+    fn create_learn_more(conflict: &Conflict) -> Expander {
+        let explanation = conflict.kind.explanation();
+
+        let content = GtkBox::new(Orientation::Vertical, 6);
+        content.set_margin_start(20);
+        content.set_margin_top(6);
+
+        let summary = Label::new(Some(&format!(
+            "{}: {}",
+            explanation.title, explanation.summary
+        )));
+        summary.set_halign(Align::Start);
+        summary.set_wrap(true);
+        summary.add_css_class("explanation-summary");
+        content.append(&summary);
+
+        for paragraph in explanation.details.split("\n\n") {
+            let label = Label::new(Some(paragraph));
+            label.set_halign(Align::Start);
+            label.set_xalign(0.0);
+            label.set_wrap(true);
+            content.append(&label);
+        }
+
+        let docs_link = LinkButton::with_label(explanation.docs_url, "Hyprland wiki: Binds");
+        docs_link.set_halign(Align::Start);
+        content.append(&docs_link);
+
+        let expander = Expander::new(Some("Learn more"));
+        expander.set_margin_start(20);
+        expander.set_child(Some(&content));
+        expander
+    }
 }
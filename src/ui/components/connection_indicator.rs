@@ -0,0 +1,226 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hyprland connection indicator
+//!
+//! Shows in the header bar whether Hyprland answers, and which version.
+//! Its popover lists every running instance, so a nested Hyprland can be
+//! chosen as the target of reloads, live edits and queries, followed by
+//! what the chosen one reports: focused workspace and keyboard.
+
+use gtk4::{prelude::*, Box as GtkBox, CheckButton, Label, MenuButton, Orientation, Popover};
+use std::rc::Rc;
+
+use crate::ui::Controller;
+
+/// Header bar button showing the Hyprland connection status
+pub struct ConnectionIndicator {
+    /// Button opening the instance list
+    button: MenuButton,
+    /// Label with the status
+    label: Label,
+    /// Controller for probing and selecting instances
+    controller: Rc<Controller>,
+}
+
+impl ConnectionIndicator {
+    /// Creates the indicator (call `refresh()` to probe Hyprland)
+    ///
+    /// # Arguments
+    /// * `controller` - Shared controller for probing and selecting instances
+    pub fn new(controller: Rc<Controller>) -> Self {
+        let label = Label::builder().label("Hyprland –").build();
+        label.add_css_class("connection-indicator");
+
+        let list = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .margin_top(6)
+            .margin_bottom(6)
+            .margin_start(6)
+            .margin_end(6)
+            .build();
+        let popover = Popover::builder().child(&list).build();
+
+        // Instances come and go, so list them each time the popover opens
+        let controller_for_list = controller.clone();
+        let label_for_list = label.clone();
+        popover.connect_show(move |_| {
+            fill_instance_list(&list, &label_for_list, &controller_for_list);
+            append_environment(&list, &controller_for_list);
+        });
+
+        let button = MenuButton::builder()
+            .child(&label)
+            .has_frame(false)
+            .popover(&popover)
+            .build();
+
+        Self {
+            button,
+            label,
+            controller,
+        }
+    }
+
+    /// Probes Hyprland and updates the label
+    pub fn refresh(&self) {
+        update_status(&self.label, &self.controller);
+    }
+
+    /// Returns the root widget for adding to a container
+    pub fn widget(&self) -> &MenuButton {
+        &self.button
+    }
+}
+
+/// Shows whether the selected instance answers
+fn update_status(label: &Label, controller: &Controller) {
+    let info = controller.get_instance_info();
+
+    label.remove_css_class("connection-online");
+    label.remove_css_class("connection-offline");
+
+    let Some(info) = info else {
+        label.set_label("Hyprland offline");
+        label.add_css_class("connection-offline");
+        label.set_tooltip_text(Some(
+            "No running Hyprland found. Changes are saved and take effect \
+             when Hyprland starts.",
+        ));
+        return;
+    };
+
+    label.set_label(&match info.version {
+        Some(version) => format!("Hyprland {}", version),
+        None => "Hyprland".to_string(),
+    });
+    label.add_css_class("connection-online");
+
+    let mut tooltip = format!(
+        "Connected to {}\n{}",
+        info,
+        info.instance.directory.display()
+    );
+    let running = controller.get_instances().len();
+    if running > 1 {
+        tooltip.push_str(&format!(
+            "\n\n{} instances running - click to choose",
+            running
+        ));
+    }
+    label.set_tooltip_text(Some(&tooltip));
+}
+
+/// Lists the running instances as radio buttons, the selected one active
+fn fill_instance_list(list: &GtkBox, label: &Label, controller: &Rc<Controller>) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let heading = Label::builder()
+        .label("Hyprland instances")
+        .xalign(0.0)
+        .build();
+    heading.add_css_class("heading");
+    list.append(&heading);
+
+    let instances = controller.get_instances();
+    if instances.is_empty() {
+        let empty = Label::builder()
+            .label("No running instance found")
+            .xalign(0.0)
+            .build();
+        empty.add_css_class("dim-label");
+        list.append(&empty);
+        return;
+    }
+
+    let selected = controller.get_instance_info().map(|info| info.instance);
+    let mut group: Option<CheckButton> = None;
+    for instance in instances {
+        let check = CheckButton::builder()
+            .label(&instance.signature)
+            .active(selected.as_ref() == Some(&instance))
+            .build();
+        check.set_tooltip_text(Some(&instance.directory.display().to_string()));
+        match &group {
+            Some(first) => check.set_group(Some(first)),
+            None => group = Some(check.clone()),
+        }
+
+        let controller = controller.clone();
+        let label = label.clone();
+        check.connect_toggled(move |check| {
+            if check.is_active() {
+                controller.set_instance(Some(instance.clone()));
+                update_status(&label, &controller);
+            }
+        });
+        list.append(&check);
+    }
+}
+
+/// Adds what the selected instance reports about its environment
+fn append_environment(list: &GtkBox, controller: &Controller) {
+    let mut lines = Vec::new();
+    if let Some(version) = controller.get_hyprland_version() {
+        lines.push(format!("Version: {}", version));
+    }
+    if let Some(workspace) = controller.get_active_workspace() {
+        lines.push(format!(
+            "Workspace: {} ({} window{})",
+            workspace.name,
+            workspace.windows,
+            if workspace.windows == 1 { "" } else { "s" }
+        ));
+    }
+    if let Some(devices) = controller.get_input_devices() {
+        if let Some(keyboard) = devices.main_keyboard() {
+            lines.push(format!(
+                "Keyboard: {} ({})",
+                keyboard.name, keyboard.active_keymap
+            ));
+        }
+        lines.push(format!(
+            "Input devices: {} keyboard{}, {} mouse/touchpad{}",
+            devices.keyboards.len(),
+            if devices.keyboards.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            devices.mice.len(),
+            if devices.mice.len() == 1 { "" } else { "s" }
+        ));
+    }
+    if lines.is_empty() {
+        return;
+    }
+
+    let heading = Label::builder()
+        .label("Environment")
+        .xalign(0.0)
+        .margin_top(6)
+        .build();
+    heading.add_css_class("heading");
+    list.append(&heading);
+
+    let details = Label::builder()
+        .label(lines.join("\n"))
+        .xalign(0.0)
+        .selectable(true)
+        .build();
+    list.append(&details);
+}
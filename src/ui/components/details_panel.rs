@@ -15,16 +15,28 @@
 //! Details panel component for displaying selected keybinding information.
 //!
 //! This component shows comprehensive details about a selected keybinding,
-//! including its key combination, dispatcher, arguments, bind type, and
-//! conflict status.
+//! including its key combination, dispatcher (flagged when the running
+//! Hyprland lacks it), arguments, bind type, conflict status, the danger
+//! assessment of `exec` commands and the `binds` options that change what
+//! the binding does. Bindings that run `hyprctl dispatch` from `exec` get a
+//! button that rewrites them to use the dispatcher directly.
 
 use gtk4::{
-    pango::WrapMode::WordChar, prelude::*, Align, Box as GtkBox, Button, Frame, Grid, Label,
-    Orientation, Separator,
+    pango::WrapMode::WordChar, prelude::*, Align, Box as GtkBox, Button, CheckButton, Frame, Grid,
+    Label, Orientation, Separator,
 };
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{core::types::Keybinding, ui::Controller};
+use crate::{
+    config::danger::DangerLevel,
+    core::{
+        binds_options::{parse_bool, OptionKind},
+        idioms::{dispatch_text, idiomatic_rewrite},
+        submap::SubmapWarning,
+        types::Keybinding,
+    },
+    ui::Controller,
+};
 
 /// A panel that displays detailed information about a selected keybinding.
 ///
@@ -34,6 +46,9 @@ use crate::{core::types::Keybinding, ui::Controller};
 /// - Arguments (e.g., "firefox")
 /// - Bind type (e.g., "bind")
 /// - Conflict status (whether this binding conflicts with others)
+/// - Security assessment (for `exec` bindings)
+/// - `binds` options affecting the binding (toggles for on/off options)
+/// - Rewrite button (shown for `exec, hyprctl dispatch ...` bindings)
 /// - Delete button (disabled when nothing selected)
 ///
 /// The panel width is enforced by the parent Paned widget in app.rs
@@ -50,6 +65,12 @@ pub struct DetailsPanel {
     bind_type_label: Label,
     /// Label displaying conflict status
     status_label: Label,
+    /// Label displaying the danger assessment
+    security_label: Label,
+    /// Box listing the `binds` options that affect the binding
+    options_box: GtkBox,
+    /// Button rewriting `hyprctl dispatch` to the dispatcher itself
+    rewrite_button: Button,
     /// Edit button
     edit_button: Button,
     /// Delete button
@@ -58,6 +79,8 @@ pub struct DetailsPanel {
     controller: Rc<Controller>,
     /// Currently displayed binding (for delete operation)
     current_binding: Rc<RefCell<Option<Keybinding>>>,
+    /// Direct-dispatcher form of the displayed binding, if it has one
+    current_rewrite: Rc<RefCell<Option<Keybinding>>>,
 }
 
 impl DetailsPanel {
@@ -168,9 +191,24 @@ impl DetailsPanel {
         grid.attach(&status_header, 0, 4, 1, 1);
         grid.attach(&status_label, 1, 4, 1, 1);
 
+        // Row 5: Security
+        let (security_header, security_label) = Self::create_label_row("🛡 Security:", "");
+        grid.attach(&security_header, 0, 5, 1, 1);
+        grid.attach(&security_label, 1, 5, 1, 1);
+
+        // Row 6: binds options
+        let (options_header, _) = Self::create_label_row("⚙ Options:", "");
+        let options_box = GtkBox::new(Orientation::Vertical, 4);
+        grid.attach(&options_header, 0, 6, 1, 1);
+        grid.attach(&options_box, 1, 6, 1, 1);
+
         // Add grid to vbox
         vbox.append(&grid);
 
+        // Rewrite button, only shown for bindings that can use a dispatcher
+        let rewrite_button = Button::builder().visible(false).build();
+        vbox.append(&rewrite_button);
+
         // Add separator
         let separator = Separator::new(Orientation::Horizontal);
         separator.set_margin_top(10);
@@ -204,10 +242,14 @@ impl DetailsPanel {
             args_label,
             bind_type_label,
             status_label,
+            security_label,
+            options_box,
+            rewrite_button,
             edit_button,
             delete_button,
             controller,
             current_binding: Rc::new(RefCell::new(None)),
+            current_rewrite: Rc::new(RefCell::new(None)),
         }
     }
 
@@ -235,10 +277,20 @@ impl DetailsPanel {
                 self.key_label.set_has_tooltip(true);
                 self.key_label.set_tooltip_text(Some(&key_combo_text));
 
-                self.dispatcher_label.set_label(&b.dispatcher);
+                // Flag dispatchers the running Hyprland does not have
+                match self.controller.get_dispatcher_warning(&b.dispatcher) {
+                    Some(warning) => {
+                        self.dispatcher_label
+                            .set_label(&format!("{}\n⚠️ {}", b.dispatcher, warning));
+                        self.dispatcher_label.set_tooltip_text(Some(&warning));
+                    }
+                    None => {
+                        self.dispatcher_label.set_label(&b.dispatcher);
+                        self.dispatcher_label.set_tooltip_text(Some(&b.dispatcher));
+                    }
+                }
                 self.dispatcher_label.set_can_target(true);
                 self.dispatcher_label.set_has_tooltip(true);
-                self.dispatcher_label.set_tooltip_text(Some(&b.dispatcher));
 
                 let args_text = b.args.as_deref().unwrap_or("(none)");
                 self.args_label.set_label(args_text);
@@ -246,16 +298,71 @@ impl DetailsPanel {
                 self.args_label.set_has_tooltip(true);
                 self.args_label.set_tooltip_text(Some(args_text));
 
-                // Format BindType for display
-                let bind_type_str = match b.bind_type {
-                    crate::core::types::BindType::Bind => "bind",
-                    crate::core::types::BindType::BindE => "binde",
-                    crate::core::types::BindType::BindL => "bindl",
-                    crate::core::types::BindType::BindM => "bindm",
-                    crate::core::types::BindType::BindR => "bindr",
-                    crate::core::types::BindType::BindEL => "bindel",
-                };
-                self.bind_type_label.set_label(bind_type_str);
+                // Format BindType for display, spelling out its flags
+                let mut bind_type_text = b.bind_type.to_string();
+                let flags: Vec<&str> = b.bind_type.flags().map(|flag| flag.label()).collect();
+                if !flags.is_empty() {
+                    bind_type_text.push_str(&format!(" ({})", flags.join(", ")));
+                }
+                if let Some(description) = &b.description {
+                    bind_type_text.push_str(&format!(" \"{}\"", description));
+                }
+                if let Some(submap) = &b.submap {
+                    bind_type_text.push_str(&format!(" (submap: {})", submap));
+                }
+                let line = b.location.as_ref().map(|location| location.line);
+                match (&b.source_file, line) {
+                    (Some(file), line) => {
+                        let name = file.file_name().unwrap_or(file.as_os_str());
+                        bind_type_text.push_str(&format!(" (from {}", name.to_string_lossy()));
+                        if let Some(line) = line {
+                            bind_type_text.push_str(&format!(", line {}", line));
+                        }
+                        bind_type_text.push(')');
+                    }
+                    (None, Some(line)) => bind_type_text.push_str(&format!(" (line {})", line)),
+                    (None, None) => {}
+                }
+                let mut bind_type_tooltip = b
+                    .location
+                    .as_ref()
+                    .map(|location| location.to_string())
+                    .or_else(|| {
+                        b.source_file
+                            .as_ref()
+                            .map(|file| file.display().to_string())
+                    });
+                let mut warnings = Vec::new();
+                if let Some(warning) = self.controller.get_submap_warning_for(b) {
+                    bind_type_text.push_str(match warning {
+                        SubmapWarning::NoEscape { .. } => "\n⚠️ No way out of this submap",
+                        SubmapWarning::CatchallOutsideSubmap { .. } => {
+                            "\n⚠️ Catchall outside a submap"
+                        }
+                    });
+                    warnings.push(warning.to_string());
+                }
+                if let Some(shadow) = self.controller.get_reserved_shadow_for(b) {
+                    bind_type_text.push_str(&format!("\n⚠️ Reserved by {}", shadow.reserved.owner));
+                    warnings.push(shadow.to_string());
+                }
+                if let Some(missing) = self.controller.get_missing_program_for(b) {
+                    bind_type_text.push_str("\n⚠️ Program not installed");
+                    warnings.push(missing.to_string());
+                }
+                if let Some(layout) = self.controller.get_layout_warning_for(b) {
+                    bind_type_text.push_str(&format!(
+                        "\n⚠️ Missing from layout {}",
+                        layout.missing_from.join(", ")
+                    ));
+                    warnings.push(layout.to_string());
+                }
+                if !warnings.is_empty() {
+                    bind_type_tooltip = Some(warnings.join("\n"));
+                }
+                self.bind_type_label.set_label(&bind_type_text);
+                self.bind_type_label
+                    .set_tooltip_text(bind_type_tooltip.as_deref());
 
                 // Check for conflicts and show which bindings conflict
                 let conflicts = self.controller.get_conflicts();
@@ -265,16 +372,20 @@ impl DetailsPanel {
 
                 for conflict in conflicts.iter() {
                     // Check if this binding is part of this conflict
-                    let is_involved = conflict
-                        .conflicting_bindings
-                        .iter()
-                        .any(|cb| cb.key_combo == b.key_combo && cb.dispatcher == b.dispatcher);
+                    let is_involved = conflict.conflicting_bindings.iter().any(|cb| {
+                        cb.key_combo == b.key_combo
+                            && cb.dispatcher == b.dispatcher
+                            && cb.submap == b.submap
+                    });
 
                     if is_involved {
                         // Collect all other bindings in this conflict
                         for cb in conflict.conflicting_bindings.iter() {
                             // Skip the current binding itself
-                            if cb.key_combo == b.key_combo && cb.dispatcher == b.dispatcher {
+                            if cb.key_combo == b.key_combo
+                                && cb.dispatcher == b.dispatcher
+                                && cb.submap == b.submap
+                            {
                                 continue;
                             }
                             conflicting_bindings.push(cb.clone());
@@ -333,6 +444,10 @@ impl DetailsPanel {
                     self.status_label.set_label(&conflict_description);
                     self.status_label.set_tooltip_text(Some(&full_conflicts));
                 }
+
+                self.update_security(b);
+                self.update_options(b);
+                self.update_rewrite(Some(b));
             }
             None => {
                 // Show friendly placeholder when nothing is selected
@@ -349,10 +464,118 @@ impl DetailsPanel {
 
                 self.status_label.set_label("");
                 self.status_label.set_tooltip_text(None);
+
+                self.security_label.set_label("");
+                self.security_label.set_tooltip_text(None);
+
+                self.clear_options();
+                self.update_rewrite(None);
+            }
+        }
+    }
+
+    /// Shows the danger assessment for the binding's command
+    fn update_security(&self, binding: &Keybinding) {
+        match self.controller.get_danger_assessment(binding) {
+            None => {
+                self.security_label.set_label("—");
+                self.security_label
+                    .set_tooltip_text(Some("This binding does not run a command"));
+            }
+            Some(assessment) => {
+                let label = match assessment.danger_level {
+                    DangerLevel::Safe => "✅ Safe".to_string(),
+                    DangerLevel::Suspicious => format!("🟡 Suspicious\n{}", assessment.reason),
+                    DangerLevel::Dangerous => format!("🟠 Dangerous\n{}", assessment.reason),
+                    DangerLevel::Critical => format!("🔴 Critical\n{}", assessment.reason),
+                };
+                let tooltip = format!("{}\n\n{}", assessment.reason, assessment.recommendation);
+
+                self.security_label.set_label(&label);
+                self.security_label.set_tooltip_text(Some(&tooltip));
             }
         }
     }
 
+    /// Lists the `binds` options that change what the binding does
+    ///
+    /// On/off options get a check button that writes the option straight
+    /// away; numeric options are shown with their value.
+    fn update_options(&self, binding: &Keybinding) {
+        self.clear_options();
+
+        let options = self.controller.get_relevant_binds_options(binding);
+        if options.is_empty() {
+            let none_label = Label::new(Some("—"));
+            none_label.set_halign(Align::Start);
+            none_label.set_tooltip_text(Some("No binds options affect this binding"));
+            self.options_box.append(&none_label);
+            return;
+        }
+
+        for (option, value) in options {
+            let tooltip = format!("binds:{}\n{}", option.name, option.description);
+
+            match option.kind {
+                OptionKind::Bool => {
+                    let check = CheckButton::with_label(option.name);
+                    check.set_active(parse_bool(&value).unwrap_or(false));
+                    check.set_tooltip_text(Some(&tooltip));
+
+                    // Connected after the initial state, so only user toggles write
+                    let controller = self.controller.clone();
+                    let name = option.name;
+                    check.connect_toggled(move |check| {
+                        let value = if check.is_active() { "true" } else { "false" };
+                        match controller.set_binds_option(name, value) {
+                            Ok(()) => eprintln!("⚙️  Set binds:{} = {}", name, value),
+                            Err(e) => {
+                                eprintln!("❌ Failed to set binds:{}: {}", name, e);
+                                check.set_tooltip_text(Some(&e));
+                            }
+                        }
+                    });
+
+                    self.options_box.append(&check);
+                }
+                OptionKind::Int => {
+                    let label = Label::new(Some(&format!("{} = {}", option.name, value)));
+                    label.set_halign(Align::Start);
+                    label.set_tooltip_text(Some(&tooltip));
+                    self.options_box.append(&label);
+                }
+            }
+        }
+    }
+
+    /// Removes the option rows of the previous binding
+    fn clear_options(&self) {
+        while let Some(child) = self.options_box.first_child() {
+            self.options_box.remove(&child);
+        }
+    }
+
+    /// Shows the rewrite button if the binding runs `hyprctl dispatch`
+    ///
+    /// # Arguments
+    /// * `binding` - The displayed binding, or `None` to hide the button
+    fn update_rewrite(&self, binding: Option<&Keybinding>) {
+        let rewrite = binding.and_then(idiomatic_rewrite);
+
+        if let Some(rewrite) = &rewrite {
+            let text = dispatch_text(rewrite);
+            self.rewrite_button
+                .set_label(&format!("💡 Use '{}' directly", text));
+            self.rewrite_button.set_tooltip_text(Some(&format!(
+                "This binding starts hyprctl to run a dispatcher Hyprland has built in.\n\
+                 Rewrite it as '{}' to skip the extra process.",
+                text
+            )));
+        }
+        self.rewrite_button.set_visible(rewrite.is_some());
+        *self.current_rewrite.borrow_mut() = rewrite;
+    }
+
     /// Connects the delete button to a callback
     ///
     /// This should be called from the app after creating the panel,
@@ -401,6 +624,28 @@ impl DetailsPanel {
         });
     }
 
+    /// Connects a callback to the rewrite button
+    ///
+    /// The callback receives the selected keybinding and its rewrite to use
+    /// the dispatcher directly.
+    pub fn connect_rewrite<F>(&self, callback: F)
+    where
+        F: Fn(&Keybinding, Keybinding) + 'static,
+    {
+        let current_binding = self.current_binding.clone();
+        let current_rewrite = self.current_rewrite.clone();
+
+        self.rewrite_button.connect_clicked(move |_button| {
+            // Take both out before the callback refreshes the panel
+            let binding = current_binding.borrow().as_ref().cloned();
+            let rewrite = current_rewrite.borrow().as_ref().cloned();
+
+            if let (Some(binding), Some(rewrite)) = (binding, rewrite) {
+                callback(&binding, rewrite);
+            }
+        });
+    }
+
     /// Get the root widget for adding to a container.
     ///
     /// # Returns
@@ -0,0 +1,88 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gtk4::{
+    gdk, prelude::*, Align, Box as GtkBox, Button, EventControllerKey, Label, Orientation, Window,
+};
+
+use crate::{config::diff::ConfigDiff, ui::components::review_dialog::diff_view};
+
+/// Read-only view of a config diff.
+///
+/// Used by the backup dialog's **Preview** to show what restoring a backup
+/// would change, in the same unified format as the review dialog. Nothing
+/// can be written from here; **Close** (or Escape) dismisses it.
+pub struct DiffDialog {
+    window: Window,
+}
+
+impl DiffDialog {
+    /// Creates a new diff dialog.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Parent window for modal behaviour
+    /// * `title` - Window title
+    /// * `summary` - Sentence shown above the diff
+    /// * `diff` - The change to show
+    pub fn new(parent: &impl IsA<Window>, title: &str, summary: &str, diff: &ConfigDiff) -> Self {
+        let window = Window::builder()
+            .title(title)
+            .modal(true)
+            .transient_for(parent)
+            .default_width(720)
+            .default_height(420)
+            .build();
+
+        // Escape key handler
+        let key_controller = EventControllerKey::new();
+        let window_for_escape = window.clone();
+        key_controller.connect_key_pressed(move |_, key, _, _| {
+            if key == gdk::Key::Escape {
+                window_for_escape.close();
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+        window.add_controller(key_controller);
+
+        let main_vbox = GtkBox::new(Orientation::Vertical, 12);
+        main_vbox.set_margin_start(12);
+        main_vbox.set_margin_end(12);
+        main_vbox.set_margin_top(12);
+        main_vbox.set_margin_bottom(12);
+
+        let summary = Label::new(Some(summary));
+        summary.set_halign(Align::Start);
+        summary.set_wrap(true);
+        main_vbox.append(&summary);
+        main_vbox.append(&diff_view(diff));
+
+        let close_button = Button::builder().label("Close").halign(Align::End).build();
+        let window_for_close = window.clone();
+        close_button.connect_clicked(move |_| window_for_close.close());
+        main_vbox.append(&close_button);
+
+        window.set_child(Some(&main_vbox));
+        window.set_default_widget(Some(&close_button));
+
+        Self { window }
+    }
+
+    /// Shows the dialog.
+    pub fn show(&self) {
+        self.window.present();
+    }
+}
@@ -17,35 +17,86 @@
 //! Provides a GTK4 window-based dialog for creating and updating keybindings.
 //! The dialog includes:
 //! - pre-filled form fields for editing
+//! - bind flags as labelled toggles, combined into the bind keyword
+//! - key capture: press the combo instead of typing it
 //! - inline key-combo availability feedback
+//! - searchable dispatcher dropdown limited to what the running Hyprland
+//!   supports, with an argument helper matching the dispatcher: an app
+//!   chooser for `exec`, a number for workspaces, a choice for directions
 //! - clickable replacement suggestions for busy combos
+//! - live workspace preview for `workspace`/`movetoworkspace` bindings
+//! - danger assessment of exec commands as they are typed; Save is
+//!   disabled for commands the danger policy blocks unless overridden
+//! - opt-in sandboxed test run of exec commands with captured output
+//! - "Try it": the binding is bound in Hyprland for a few seconds, unsaved
 //! - modal save/cancel flow with validation
 
 use crate::{
+    config::{danger::DangerLevel, ConfigError},
     core::{
-        sandbox,
-        types::{BindType, KeyCombo, Keybinding, Modifier},
+        dispatchers::{desktop_exec_command, dispatcher_spec, ArgumentShape},
+        keysyms::{captured_combo, keysym_modifier},
+        sandbox::{self, CapturedRun, TEST_RUN_TIMEOUT},
+        types::{BindFlag, BindType, KeyCombo, Keybinding, Modifier},
+        validator::workspace_warnings,
+        workspace::{
+            is_workspace_dispatcher, parse_workspace_target, workspace_status, WorkspaceInfo,
+            WorkspaceStatus,
+        },
     },
-    ui::controller::KeyComboAvailability,
+    ipc::temporary::TemporaryBind,
     ui::Controller,
 };
 use gtk4::{
-    gdk, prelude::*, ApplicationWindow, Box as GtkBox, Button, Entry, EventControllerKey, Grid,
-    Label, Orientation, Switch, Window,
+    gdk, gio, prelude::*, ApplicationWindow, Box as GtkBox, Button, CheckButton, DropDown, Entry,
+    EntryIconPosition, EventControllerKey, Expander, FlowBox, Grid, Image, Label, ListItem,
+    MenuButton, Orientation, Popover, PropertyExpression, ScrolledWindow, SearchEntry,
+    SignalListItemFactory, SpinButton, Stack, StringList, StringObject, Switch, TextView, Window,
 };
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::{Cell, OnceCell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+/// How long a binding on trial stays bound
+const TRIAL_DURATION: Duration = Duration::from_secs(15);
+
+/// Label of the key capture button, idle and while capturing
+const CAPTURE_LABEL: &str = "⌨ Press keys…";
+const CAPTURE_PROMPT: &str = "Press a combo (Esc cancels)";
+
+/// Highest workspace the argument helper's number goes up to
+const WORKSPACE_SPIN_MAX: f64 = 100.0;
 
 /// Dialog for editing an existing keybinding
 pub struct EditDialog {
     dialog_window: Window,
     key_entry: Entry,
-    dispatcher_entry: Entry,
+    capture_button: Button,
+    dispatcher_dropdown: DropDown,
+    /// Shown when the running Hyprland lacks the selected dispatcher
+    dispatcher_warning: Image,
     args_entry: Entry,
-    bind_type_entry: Entry,
+    args_helper: ArgsHelper,
+    bind_flags: BindFlagToggles,
+    description_entry: Entry,
+    category_entry: Entry,
     sandbox_switch: Switch,
     sandbox_label: Label,
+    test_button: Button,
+    test_output: TextView,
+    test_output_scroller: ScrolledWindow,
+    try_status: Label,
+    /// Binding on trial, with the timeout that ends the trial
+    trial: Rc<RefCell<Option<(TemporaryBind, glib::SourceId)>>>,
     availability_label: Label,
     suggestion_box: GtkBox,
+    workspace_label: Label,
+    danger_banner: DangerBanner,
+    save_button: Button,
+    /// Workspaces of the running Hyprland, queried on first use
+    workspaces: Rc<OnceCell<Option<Vec<WorkspaceInfo>>>>,
     response: Rc<Cell<Option<DialogResponse>>>,
     controller: Rc<Controller>,
     original_binding: Option<Keybinding>,
@@ -54,6 +105,7 @@ pub struct EditDialog {
 #[derive(Clone, Debug, Copy, PartialEq)]
 enum DialogResponse {
     Save,
+    Try,
     Cancel,
 }
 
@@ -110,9 +162,24 @@ impl EditDialog {
             .placeholder_text("e.g., SUPER+SHIFT+M")
             .hexpand(true)
             .build();
-        key_entry.set_tooltip_text(Some("Enter modifiers and key using MOD+KEY format"));
+        key_entry.set_tooltip_text(Some(
+            "Enter modifiers and key using MOD+KEY format\n\
+             Mouse buttons: SUPER+mouse:272 (or SUPER+Mouse Left)\n\
+             Scrolling: SUPER+mouse_down (or SUPER+Scroll Down)",
+        ));
+        let capture_button = Button::builder().label(CAPTURE_LABEL).build();
+        capture_button.set_tooltip_text(Some(
+            "Record the combo by pressing it\n\
+             Combos Hyprland already binds are handled by Hyprland and never reach this dialog",
+        ));
+        let key_row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        key_row.append(&key_entry);
+        key_row.append(&capture_button);
         grid.attach(&key_label, 0, 0, 1, 1);
-        grid.attach(&key_entry, 1, 0, 1, 1);
+        grid.attach(&key_row, 1, 0, 1, 1);
 
         let availability_label = Label::builder()
             .label("Enter a key combination to check availability.")
@@ -137,14 +204,19 @@ impl EditDialog {
             .label("⚡ Dispatcher:")
             .halign(gtk4::Align::End)
             .build();
-        let dispatcher_entry = Entry::builder()
-            .text(&binding.dispatcher)
-            .placeholder_text("e.g., exec, workspace, killactive")
-            .hexpand(true)
+        let dispatcher_dropdown = build_dispatcher_dropdown(&controller, &binding.dispatcher);
+        let dispatcher_warning = Image::builder()
+            .icon_name("dialog-warning-symbolic")
+            .visible(false)
             .build();
-        dispatcher_entry.set_tooltip_text(Some("Enter the Hyprland dispatcher to run"));
+        let dispatcher_row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        dispatcher_row.append(&dispatcher_dropdown);
+        dispatcher_row.append(&dispatcher_warning);
         grid.attach(&dispatcher_label, 0, 3, 1, 1);
-        grid.attach(&dispatcher_entry, 1, 3, 1, 1);
+        grid.attach(&dispatcher_row, 1, 3, 1, 1);
 
         let args_label = Label::builder()
             .label("📝 Arguments:")
@@ -156,21 +228,67 @@ impl EditDialog {
             .hexpand(true)
             .build();
         args_entry.set_tooltip_text(Some("Optional dispatcher arguments"));
+        let args_helper = ArgsHelper::new(&args_entry);
+        let args_row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(6)
+            .build();
+        args_row.append(&args_entry);
+        args_row.append(args_helper.widget());
         grid.attach(&args_label, 0, 4, 1, 1);
-        grid.attach(&args_entry, 1, 4, 1, 1);
+        grid.attach(&args_row, 1, 4, 1, 1);
+
+        let workspace_label = Label::builder()
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .visible(false)
+            .build();
+        let danger_banner = DangerBanner::new();
+        let args_feedback = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        args_feedback.append(&workspace_label);
+        args_feedback.append(danger_banner.widget());
+        grid.attach(&args_feedback, 1, 5, 1, 1);
 
         let bind_type_label = Label::builder()
             .label("🔗 Bind Type:")
             .halign(gtk4::Align::End)
             .build();
-        let bind_type_entry = Entry::builder()
-            .text(binding.bind_type.to_string())
-            .placeholder_text("bind, binde, bindm, etc.")
+        let bind_flags = BindFlagToggles::new(binding.bind_type);
+        let description_entry = Entry::builder()
+            .text(binding.description.as_deref().unwrap_or_default())
+            .placeholder_text("Description (d flag)")
             .hexpand(true)
             .build();
-        bind_type_entry.set_tooltip_text(Some("Choose the Hyprland bind variant"));
-        grid.attach(&bind_type_label, 0, 5, 1, 1);
-        grid.attach(&bind_type_entry, 1, 5, 1, 1);
+        description_entry
+            .set_tooltip_text(Some("Description written by bindd and other d variants"));
+        let bind_type_row = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(6)
+            .build();
+        bind_type_row.append(bind_flags.widget());
+        bind_type_row.append(&description_entry);
+        bind_type_label.set_valign(gtk4::Align::Start);
+        grid.attach(&bind_type_label, 0, 6, 1, 1);
+        grid.attach(&bind_type_row, 1, 6, 1, 1);
+
+        let category_label = Label::builder()
+            .label("🏷 Category:")
+            .halign(gtk4::Align::End)
+            .build();
+        let category_entry = Entry::builder()
+            .text(binding.category.as_deref().unwrap_or_default())
+            .placeholder_text("Optional, e.g. media, launchers")
+            .hexpand(true)
+            .build();
+        category_entry.set_tooltip_text(Some(
+            "Written as a '# @category:' comment above the bind line",
+        ));
+        grid.attach(&category_label, 0, 7, 1, 1);
+        grid.attach(&category_entry, 1, 7, 1, 1);
 
         let sandbox_label = Label::builder()
             .label("🛡️ Bubblewrap Sandbox:")
@@ -183,8 +301,61 @@ impl EditDialog {
         let sandbox_active = binding.args.as_deref().is_some_and(sandbox::is_wrapped)
             && binding.dispatcher == "exec";
         sandbox_switch.set_active(sandbox_active);
-        grid.attach(&sandbox_label, 0, 6, 1, 1);
-        grid.attach(&sandbox_switch, 1, 6, 1, 1);
+        grid.attach(&sandbox_label, 0, 8, 1, 1);
+        grid.attach(&sandbox_switch, 1, 8, 1, 1);
+
+        let test_label = Label::builder()
+            .label("🧪 Test Command:")
+            .halign(gtk4::Align::End)
+            .build();
+        let test_button = Button::builder()
+            .label("▶ Run in sandbox…")
+            .halign(gtk4::Align::Start)
+            .tooltip_text("Run the command once inside the sandbox and show its output")
+            .build();
+        grid.attach(&test_label, 0, 9, 1, 1);
+        grid.attach(&test_button, 1, 9, 1, 1);
+
+        let test_output = TextView::builder()
+            .editable(false)
+            .cursor_visible(false)
+            .monospace(true)
+            .wrap_mode(gtk4::WrapMode::WordChar)
+            .build();
+        let test_output_scroller = ScrolledWindow::builder()
+            .child(&test_output)
+            .min_content_height(120)
+            .hexpand(true)
+            .visible(false)
+            .build();
+        grid.attach(&test_output_scroller, 1, 10, 1, 1);
+
+        let try_label = Label::builder()
+            .label("🎯 Try It:")
+            .halign(gtk4::Align::End)
+            .build();
+        let try_button = Button::builder()
+            .label("⌨ Bind temporarily")
+            .tooltip_text(format!(
+                "Bind the key in Hyprland for {} seconds, without saving, to try it",
+                TRIAL_DURATION.as_secs()
+            ))
+            .build();
+        let try_status = Label::builder()
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .hexpand(true)
+            .build();
+        try_status.add_css_class("dim-label");
+        let try_row = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(12)
+            .build();
+        try_row.append(&try_button);
+        try_row.append(&try_status);
+        grid.attach(&try_label, 0, 11, 1, 1);
+        grid.attach(&try_row, 1, 11, 1, 1);
 
         let button_box = GtkBox::builder()
             .orientation(Orientation::Horizontal)
@@ -218,15 +389,11 @@ impl EditDialog {
             let response = response.clone();
             let window = dialog_window.clone();
             let key_entry = key_entry.clone();
-            let dispatcher_entry = dispatcher_entry.clone();
             let args_entry = args_entry.clone();
-            let bind_type_entry = bind_type_entry.clone();
 
             cancel_button.connect_clicked(move |_| {
                 key_entry.select_region(0, 0);
-                dispatcher_entry.select_region(0, 0);
                 args_entry.select_region(0, 0);
-                bind_type_entry.select_region(0, 0);
 
                 response.set(Some(DialogResponse::Cancel));
                 window.close();
@@ -236,20 +403,23 @@ impl EditDialog {
         {
             let response = response.clone();
             let key_entry = key_entry.clone();
-            let dispatcher_entry = dispatcher_entry.clone();
             let args_entry = args_entry.clone();
-            let bind_type_entry = bind_type_entry.clone();
 
             save_button.connect_clicked(move |_| {
                 key_entry.select_region(0, 0);
-                dispatcher_entry.select_region(0, 0);
                 args_entry.select_region(0, 0);
-                bind_type_entry.select_region(0, 0);
 
                 response.set(Some(DialogResponse::Save));
             });
         }
 
+        {
+            let response = response.clone();
+            try_button.connect_clicked(move |_| {
+                response.set(Some(DialogResponse::Try));
+            });
+        }
+
         {
             let response = response.clone();
             dialog_window.connect_close_request(move |_| {
@@ -271,85 +441,404 @@ impl EditDialog {
         let dialog = Self {
             dialog_window,
             key_entry,
-            dispatcher_entry,
+            capture_button,
+            dispatcher_dropdown,
+            dispatcher_warning,
             args_entry,
-            bind_type_entry,
+            args_helper,
+            bind_flags,
+            description_entry,
+            category_entry,
             sandbox_switch,
             sandbox_label,
+            test_button,
+            test_output,
+            test_output_scroller,
+            try_status,
+            trial: Rc::new(RefCell::new(None)),
             availability_label,
             suggestion_box,
+            workspace_label,
+            danger_banner,
+            save_button,
+            workspaces: Rc::new(OnceCell::new()),
             response,
             controller,
             original_binding,
         };
 
         dialog.connect_key_feedback();
+        dialog.connect_key_capture();
+        dialog.connect_dispatcher_feedback();
+        dialog.connect_args_helper();
         dialog.connect_sandbox_feedback();
+        dialog.connect_workspace_feedback();
+        dialog.connect_danger_feedback();
+        dialog.connect_bind_type_feedback();
+        dialog.connect_test_command();
         dialog.refresh_sandbox_controls();
         dialog.refresh_key_combo_feedback();
+        dialog.refresh_danger_feedback();
+        refresh_dispatcher_warning(
+            &dialog.controller,
+            &dialog.dispatcher(),
+            &dialog.dispatcher_warning,
+        );
+        refresh_argument_feedback(&dialog.controller, &dialog.dispatcher(), &dialog.args_entry);
+        dialog
+            .args_helper
+            .refresh(&dialog.dispatcher(), &dialog.args_entry.text());
+        dialog.refresh_workspace_preview();
         dialog
     }
 
+    /// Refreshes the conflict preview whenever the binding being edited changes
     fn connect_key_feedback(&self) {
+        let form = self.preview_form();
         let controller = self.controller.clone();
-        let original_binding = self.original_binding.clone();
-        let key_entry = self.key_entry.clone();
         let availability_label = self.availability_label.clone();
         let suggestion_box = self.suggestion_box.clone();
-
-        self.key_entry.connect_changed(move |_| {
+        let refresh = Rc::new(move || {
             refresh_key_combo_feedback_widgets(
                 &controller,
-                original_binding.as_ref(),
-                &key_entry,
+                &form,
                 &availability_label,
                 &suggestion_box,
             );
         });
+
+        let on_key = refresh.clone();
+        self.key_entry.connect_changed(move |_| on_key());
+        let on_action = refresh.clone();
+        self.connect_dispatcher_or_args_changed(move || on_action());
+        self.bind_flags.connect_changed(move |_| refresh());
     }
 
     fn refresh_key_combo_feedback(&self) {
         refresh_key_combo_feedback_widgets(
             &self.controller,
-            self.original_binding.as_ref(),
-            &self.key_entry,
+            &self.preview_form(),
             &self.availability_label,
             &self.suggestion_box,
         );
     }
 
+    fn preview_form(&self) -> PreviewForm {
+        PreviewForm {
+            key_entry: self.key_entry.clone(),
+            dispatcher_dropdown: self.dispatcher_dropdown.clone(),
+            args_entry: self.args_entry.clone(),
+            bind_flags: self.bind_flags.clone(),
+            original_binding: self.original_binding.clone(),
+        }
+    }
+
+    /// Wires the "Press keys…" button
+    ///
+    /// While capturing, key presses go to the capture instead of the
+    /// dialog. The first key that is not a modifier fills in the key field
+    /// with the modifiers held; a modifier pressed and released on its own
+    /// is captured as the key. Escape on its own cancels.
+    fn connect_key_capture(&self) {
+        let capturing = Rc::new(Cell::new(false));
+        // Modifier pressed with nothing else yet, captured if released first
+        let lone_modifier: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+
+        {
+            let capturing = capturing.clone();
+            let lone_modifier = lone_modifier.clone();
+            self.capture_button.connect_clicked(move |button| {
+                capturing.set(true);
+                lone_modifier.replace(None);
+                button.set_label(CAPTURE_PROMPT);
+            });
+        }
+
+        // Runs before the dialog's own handlers, so Escape does not close it
+        let key_controller = EventControllerKey::new();
+        key_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+        {
+            let capturing = capturing.clone();
+            let lone_modifier = lone_modifier.clone();
+            let key_entry = self.key_entry.clone();
+            let capture_button = self.capture_button.clone();
+            key_controller.connect_key_pressed(move |controller, key, keycode, state| {
+                if !capturing.get() {
+                    return glib::Propagation::Proceed;
+                }
+                let Some(keysym) = unmodified_keysym(controller, key, keycode) else {
+                    return glib::Propagation::Stop;
+                };
+                if keysym_modifier(&keysym).is_some() {
+                    lone_modifier.replace(Some(keysym));
+                    return glib::Propagation::Stop;
+                }
+
+                lone_modifier.replace(None);
+                let held = held_modifiers(state);
+                let combo = if key == gdk::Key::Escape && held.is_empty() {
+                    None
+                } else {
+                    captured_combo(&keysym, &held)
+                };
+                finish_key_capture(&capturing, &capture_button, &key_entry, combo);
+                glib::Propagation::Stop
+            });
+        }
+        {
+            let key_entry = self.key_entry.clone();
+            let capture_button = self.capture_button.clone();
+            key_controller.connect_key_released(move |controller, key, keycode, state| {
+                if !capturing.get() {
+                    return;
+                }
+                let keysym = unmodified_keysym(controller, key, keycode);
+                if keysym.is_some() && lone_modifier.borrow().as_ref() == keysym.as_ref() {
+                    lone_modifier.replace(None);
+                    let combo =
+                        keysym.and_then(|keysym| captured_combo(&keysym, &held_modifiers(state)));
+                    finish_key_capture(&capturing, &capture_button, &key_entry, combo);
+                }
+            });
+        }
+        self.dialog_window.add_controller(key_controller);
+    }
+
+    /// The selected dispatcher (empty when none is selected)
+    fn dispatcher(&self) -> String {
+        selected_dispatcher(&self.dispatcher_dropdown)
+    }
+
+    /// Calls `refresh` whenever the dispatcher or the arguments change
+    fn connect_dispatcher_or_args_changed(&self, refresh: impl Fn() + 'static) {
+        let refresh = Rc::new(refresh);
+        {
+            let refresh = refresh.clone();
+            self.dispatcher_dropdown
+                .connect_selected_notify(move |_| refresh());
+        }
+        self.args_entry.connect_changed(move |_| refresh());
+    }
+
+    fn connect_dispatcher_feedback(&self) {
+        let controller = self.controller.clone();
+        let dispatcher_warning = self.dispatcher_warning.clone();
+
+        self.dispatcher_dropdown
+            .connect_selected_notify(move |dropdown| {
+                refresh_dispatcher_warning(
+                    &controller,
+                    &selected_dispatcher(dropdown),
+                    &dispatcher_warning,
+                );
+            });
+
+        let controller = self.controller.clone();
+        let dispatcher_dropdown = self.dispatcher_dropdown.clone();
+        let args_entry = self.args_entry.clone();
+        self.connect_dispatcher_or_args_changed(move || {
+            refresh_argument_feedback(
+                &controller,
+                &selected_dispatcher(&dispatcher_dropdown),
+                &args_entry,
+            );
+        });
+    }
+
+    /// Keeps the argument helper in step with the dispatcher and arguments
+    fn connect_args_helper(&self) {
+        let dispatcher_dropdown = self.dispatcher_dropdown.clone();
+        let args_entry = self.args_entry.clone();
+        let args_helper = self.args_helper.clone();
+        self.connect_dispatcher_or_args_changed(move || {
+            args_helper.refresh(
+                &selected_dispatcher(&dispatcher_dropdown),
+                &args_entry.text(),
+            );
+        });
+    }
+
     fn connect_sandbox_feedback(&self) {
-        let dispatcher_entry = self.dispatcher_entry.clone();
         let sandbox_switch = self.sandbox_switch.clone();
         let sandbox_label = self.sandbox_label.clone();
-
-        self.dispatcher_entry.connect_changed(move |_| {
-            refresh_sandbox_controls_widgets(&dispatcher_entry, &sandbox_switch, &sandbox_label);
-        });
+        let test_button = self.test_button.clone();
+
+        self.dispatcher_dropdown
+            .connect_selected_notify(move |dropdown| {
+                refresh_sandbox_controls_widgets(
+                    &selected_dispatcher(dropdown),
+                    &sandbox_switch,
+                    &sandbox_label,
+                    &test_button,
+                );
+            });
     }
 
     fn refresh_sandbox_controls(&self) {
         refresh_sandbox_controls_widgets(
-            &self.dispatcher_entry,
+            &self.dispatcher(),
             &self.sandbox_switch,
             &self.sandbox_label,
+            &self.test_button,
         );
     }
 
+    /// Wires the "Test command" button
+    ///
+    /// The command must be assessed as Safe, and the user confirms before it
+    /// runs once in the Bubblewrap sandbox on a worker thread.
+    fn connect_test_command(&self) {
+        let controller = self.controller.clone();
+        let dialog_window = self.dialog_window.clone();
+        let args_entry = self.args_entry.clone();
+        let test_output = self.test_output.clone();
+        let test_output_scroller = self.test_output_scroller.clone();
+
+        self.test_button.connect_clicked(move |button| {
+            let command = args_entry.text().trim().to_string();
+            if let Err(reason) = controller.check_command_testable(&command) {
+                show_test_output(
+                    &test_output,
+                    &test_output_scroller,
+                    &format!("Cannot test this command: {}", reason),
+                );
+                return;
+            }
+
+            let confirm_dialog = gtk4::AlertDialog::builder()
+                .modal(true)
+                .message("Run Command?")
+                .detail(format!(
+                    "Run this command once to preview its output?\n\n{}\n\n\
+                     It runs inside the Bubblewrap sandbox without a shell, network \
+                     or input, and is stopped after {} seconds.",
+                    command,
+                    TEST_RUN_TIMEOUT.as_secs()
+                ))
+                .buttons(vec!["Cancel", "Run"])
+                .cancel_button(0)
+                .default_button(0)
+                .build();
+
+            let button = button.clone();
+            let test_output = test_output.clone();
+            let test_output_scroller = test_output_scroller.clone();
+            confirm_dialog.choose(
+                Some(&dialog_window),
+                None::<&gio::Cancellable>,
+                move |response| {
+                    if !matches!(response, Ok(1)) {
+                        return;
+                    }
+
+                    button.set_sensitive(false);
+                    show_test_output(&test_output, &test_output_scroller, "Running…");
+
+                    glib::spawn_future_local(async move {
+                        let result = gio::spawn_blocking(move || {
+                            sandbox::test_run(&command, TEST_RUN_TIMEOUT)
+                        })
+                        .await;
+
+                        let text = match result {
+                            Ok(Ok(run)) => describe_captured_run(&run),
+                            Ok(Err(e)) => format!("Could not run the command: {}", e),
+                            Err(_) => "The test run stopped unexpectedly.".to_string(),
+                        };
+                        show_test_output(&test_output, &test_output_scroller, &text);
+                        button.set_sensitive(true);
+                    });
+                },
+            );
+        });
+    }
+
+    fn connect_workspace_feedback(&self) {
+        let controller = self.controller.clone();
+        let workspaces = self.workspaces.clone();
+        let dispatcher_dropdown = self.dispatcher_dropdown.clone();
+        let args_entry = self.args_entry.clone();
+        let workspace_label = self.workspace_label.clone();
+
+        self.connect_dispatcher_or_args_changed(move || {
+            refresh_workspace_preview_widgets(
+                &controller,
+                &workspaces,
+                &selected_dispatcher(&dispatcher_dropdown),
+                &args_entry,
+                &workspace_label,
+            );
+        });
+    }
+
+    fn refresh_workspace_preview(&self) {
+        refresh_workspace_preview_widgets(
+            &self.controller,
+            &self.workspaces,
+            &self.dispatcher(),
+            &self.args_entry,
+            &self.workspace_label,
+        );
+    }
+
+    /// Assesses the command as it is typed, disabling Save while the
+    /// danger policy blocks it and the override is not ticked
+    fn connect_danger_feedback(&self) {
+        let controller = self.controller.clone();
+        let dispatcher_dropdown = self.dispatcher_dropdown.clone();
+        let args_entry = self.args_entry.clone();
+        let danger_banner = self.danger_banner.clone();
+        let save_button = self.save_button.clone();
+
+        self.connect_dispatcher_or_args_changed(move || {
+            danger_banner.refresh(
+                &controller,
+                &selected_dispatcher(&dispatcher_dropdown),
+                &args_entry.text(),
+            );
+            save_button.set_sensitive(danger_banner.allows_save());
+        });
+
+        let danger_banner = self.danger_banner.clone();
+        let save_button = self.save_button.clone();
+        self.danger_banner.override_check.connect_toggled(move |_| {
+            save_button.set_sensitive(danger_banner.allows_save());
+        });
+    }
+
+    fn refresh_danger_feedback(&self) {
+        self.danger_banner.refresh(
+            &self.controller,
+            &self.dispatcher(),
+            &self.args_entry.text(),
+        );
+        self.save_button
+            .set_sensitive(self.danger_banner.allows_save());
+    }
+
     /// Clears text selections in all entry fields.
     fn clear_selections(&self) {
         self.key_entry.select_region(0, 0);
-        self.dispatcher_entry.select_region(0, 0);
         self.args_entry.select_region(0, 0);
-        self.bind_type_entry.select_region(0, 0);
+        self.description_entry.select_region(0, 0);
+        self.category_entry.select_region(0, 0);
+    }
+
+    /// Enables the description field only for bind types with the `d` flag.
+    fn connect_bind_type_feedback(&self) {
+        let description_entry = self.description_entry.clone();
+        let refresh = move |bind_type: BindType| {
+            description_entry.set_sensitive(bind_type.contains(BindFlag::Description));
+        };
+        refresh(self.bind_flags.bind_type());
+        self.bind_flags.connect_changed(refresh);
     }
 
     /// Parses the form fields and returns a new Keybinding if valid.
     fn parse_binding(&self) -> Result<Keybinding, String> {
         let key_text = self.key_entry.text().to_string();
-        let dispatcher = self.dispatcher_entry.text().to_string();
+        let dispatcher = self.dispatcher();
         let args_text = self.args_entry.text().to_string();
-        let bind_type_text = self.bind_type_entry.text().to_string();
 
         let key_combo = parse_key_combo_text(&key_text)?
             .ok_or_else(|| "Key combination cannot be empty".to_string())?;
@@ -357,19 +846,13 @@ impl EditDialog {
         if dispatcher.trim().is_empty() {
             return Err("Dispatcher cannot be empty".to_string());
         }
-        if bind_type_text.trim().is_empty() {
-            return Err("Bind type cannot be empty".to_string());
-        }
 
-        let bind_type = match bind_type_text.to_lowercase().as_str() {
-            "bind" => BindType::Bind,
-            "binde" => BindType::BindE,
-            "bindm" => BindType::BindM,
-            "bindr" => BindType::BindR,
-            "bindl" => BindType::BindL,
-            "bindel" => BindType::BindEL,
-            _ => return Err(format!("Invalid bind type: {}", bind_type_text)),
-        };
+        let bind_type = self.bind_flags.bind_type();
+        let description = bind_type
+            .contains(BindFlag::Description)
+            .then(|| self.description_entry.text().trim().to_string());
+        let category = Some(self.category_entry.text().trim().to_string())
+            .filter(|category| !category.is_empty());
 
         let args = if args_text.trim().is_empty() {
             None
@@ -387,11 +870,39 @@ impl EditDialog {
             key_combo,
             dispatcher: dispatcher.trim().to_string(),
             args,
+            // Edits stay in the submap, file, device and line the binding came from
+            submap: self
+                .original_binding
+                .as_ref()
+                .and_then(|binding| binding.submap.clone()),
+            source_file: self
+                .original_binding
+                .as_ref()
+                .and_then(|binding| binding.source_file.clone()),
+            description,
+            category,
+            device: self
+                .original_binding
+                .as_ref()
+                .and_then(|binding| binding.device.clone()),
+            location: self
+                .original_binding
+                .as_ref()
+                .and_then(|binding| binding.location.clone()),
         })
     }
 
     /// Shows the dialog and waits for user response.
+    ///
+    /// A binding still on trial is unbound before this returns.
     pub fn show_and_wait(self) -> Option<Keybinding> {
+        let binding = self.wait_for_binding();
+        self.end_trial();
+        binding
+    }
+
+    /// Runs the dialog until it is saved or dismissed
+    fn wait_for_binding(&self) -> Option<Keybinding> {
         self.response.set(None);
         self.dialog_window.present();
 
@@ -405,7 +916,14 @@ impl EditDialog {
 
             match self.response.get() {
                 Some(DialogResponse::Save) => match self.parse_binding() {
+                    Ok(_) if !self.danger_banner.allows_save() => {
+                        self.show_error("The danger policy blocks this command");
+                        self.response.set(None);
+                    }
                     Ok(binding) => {
+                        if self.danger_banner.is_overridden() {
+                            self.controller.override_danger_policy(&binding);
+                        }
                         self.dialog_window.close();
                         return Some(binding);
                     }
@@ -414,6 +932,10 @@ impl EditDialog {
                         self.response.set(None);
                     }
                 },
+                Some(DialogResponse::Try) => {
+                    self.start_trial();
+                    self.response.set(None);
+                }
                 Some(DialogResponse::Cancel) => {
                     self.dialog_window.close();
                     return None;
@@ -426,6 +948,51 @@ impl EditDialog {
         }
     }
 
+    /// Binds the form's binding in Hyprland until the trial times out
+    fn start_trial(&self) {
+        self.end_trial();
+
+        let binding = match self.parse_binding() {
+            Ok(binding) => binding,
+            Err(e) => {
+                self.show_error(&e);
+                return;
+            }
+        };
+        let trial = match self.controller.try_keybinding(&binding) {
+            Ok(trial) => trial,
+            Err(e) => {
+                self.try_status.set_label(&e);
+                return;
+            }
+        };
+        self.try_status.set_label(&format!(
+            "Press {} now. It is unbound after {} seconds or when this dialog closes.",
+            binding.key_combo,
+            TRIAL_DURATION.as_secs()
+        ));
+
+        let slot = self.trial.clone();
+        let try_status = self.try_status.clone();
+        let timeout = glib::timeout_add_local_once(TRIAL_DURATION, move || {
+            let expired = slot.borrow_mut().take();
+            if let Some((trial, _)) = expired {
+                try_status.set_label(&describe_trial_end(trial.finish()));
+            }
+        });
+        *self.trial.borrow_mut() = Some((trial, timeout));
+    }
+
+    /// Ends the running trial early, if any
+    fn end_trial(&self) {
+        let running = self.trial.borrow_mut().take();
+        if let Some((trial, timeout)) = running {
+            timeout.remove();
+            self.try_status
+                .set_label(&describe_trial_end(trial.finish()));
+        }
+    }
+
     /// Shows an error message in a modal dialog.
     fn show_error(&self, message: &str) {
         let error_window = Window::builder()
@@ -517,73 +1084,776 @@ fn parse_key_combo_text(input: &str) -> Result<Option<KeyCombo>, String> {
     Ok(Some(KeyCombo::new(modifiers, key)))
 }
 
+/// Name of the pressed key as the layout has it without modifiers
+///
+/// Shift+1 is then `1` rather than `exclam`, as Hyprland matches it.
+fn unmodified_keysym(
+    controller: &EventControllerKey,
+    key: gdk::Key,
+    keycode: u32,
+) -> Option<String> {
+    let unmodified = controller
+        .widget()
+        .and_then(|widget| {
+            widget.display().translate_key(
+                keycode,
+                gdk::ModifierType::empty(),
+                controller.group() as i32,
+            )
+        })
+        .map_or(key, |(keyval, ..)| keyval);
+    unmodified.name().map(|name| name.to_string())
+}
+
+/// Modifiers held according to a key event's state
+fn held_modifiers(state: gdk::ModifierType) -> Vec<Modifier> {
+    [
+        (gdk::ModifierType::SUPER_MASK, Modifier::Super),
+        (gdk::ModifierType::HYPER_MASK, Modifier::Super),
+        (gdk::ModifierType::CONTROL_MASK, Modifier::Ctrl),
+        (gdk::ModifierType::SHIFT_MASK, Modifier::Shift),
+        (gdk::ModifierType::ALT_MASK, Modifier::Alt),
+        (gdk::ModifierType::META_MASK, Modifier::Alt),
+    ]
+    .into_iter()
+    .filter(|(mask, _)| state.contains(*mask))
+    .map(|(_, modifier)| modifier)
+    .collect()
+}
+
+/// Stops capturing, filling in the key field with the captured combo
+fn finish_key_capture(
+    capturing: &Cell<bool>,
+    capture_button: &Button,
+    key_entry: &Entry,
+    combo: Option<KeyCombo>,
+) {
+    capturing.set(false);
+    capture_button.set_label(CAPTURE_LABEL);
+    if let Some(combo) = combo {
+        key_entry.set_text(&combo.to_string());
+    }
+}
+
+/// The form fields the conflict preview is built from
+struct PreviewForm {
+    key_entry: Entry,
+    dispatcher_dropdown: DropDown,
+    args_entry: Entry,
+    bind_flags: BindFlagToggles,
+    original_binding: Option<Keybinding>,
+}
+
+impl PreviewForm {
+    /// The binding as it would be saved, given a parsed key combo
+    ///
+    /// Unlike saving, an empty dispatcher is accepted: the combo can be
+    /// checked before the action is chosen.
+    fn candidate(&self, key_combo: KeyCombo) -> Keybinding {
+        let original = self.original_binding.as_ref();
+        Keybinding {
+            key_combo,
+            bind_type: self.bind_flags.bind_type(),
+            dispatcher: selected_dispatcher(&self.dispatcher_dropdown)
+                .trim()
+                .to_string(),
+            args: Some(self.args_entry.text().trim().to_string()).filter(|args| !args.is_empty()),
+            submap: original.and_then(|binding| binding.submap.clone()),
+            source_file: original.and_then(|binding| binding.source_file.clone()),
+            description: None,
+            category: None,
+            device: original.and_then(|binding| binding.device.clone()),
+            location: None,
+        }
+    }
+}
+
+/// Shows whether the combo is free, and if not what saving would conflict with
 fn refresh_key_combo_feedback_widgets(
     controller: &Rc<Controller>,
-    original_binding: Option<&Keybinding>,
-    key_entry: &Entry,
+    form: &PreviewForm,
     availability_label: &Label,
     suggestion_box: &GtkBox,
 ) {
     clear_suggestion_box(suggestion_box);
 
-    let key_text = key_entry.text();
-    match parse_key_combo_text(&key_text) {
-        Ok(None) => set_feedback_state(
+    let key_combo = match parse_key_combo_text(&form.key_entry.text()) {
+        Ok(Some(key_combo)) => key_combo,
+        Ok(None) => {
+            set_feedback_state(
+                availability_label,
+                "Enter a key combination to check availability.",
+                "availability-hint",
+            );
+            return;
+        }
+        Err(message) => {
+            set_feedback_state(availability_label, &message, "availability-warning");
+            return;
+        }
+    };
+
+    let candidate = form.candidate(key_combo);
+    let preview = controller.get_conflict_preview(&candidate, form.original_binding.as_ref());
+
+    let mut lines = Vec::new();
+    if let Some(kind) = preview.kind {
+        let in_use = preview
+            .bindings
+            .iter()
+            .take(2)
+            .map(describe_binding)
+            .collect::<Vec<_>>()
+            .join(" | ");
+        let suffix = if preview.bindings.len() > 2 {
+            " | ..."
+        } else {
+            ""
+        };
+        lines.push(format!("Already in use by {}{}", in_use, suffix));
+
+        let explanation = kind.explanation();
+        lines.push(format!(
+            "{} ({} severity): {}",
+            explanation.title,
+            kind.severity().label(),
+            explanation.summary
+        ));
+    }
+    if let Some(reserved) = &preview.reserved {
+        lines.push(reserved.to_string());
+    }
+
+    if lines.is_empty() {
+        set_feedback_state(
             availability_label,
-            "Enter a key combination to check availability.",
-            "availability-hint",
-        ),
-        Err(message) => set_feedback_state(availability_label, &message, "availability-warning"),
-        Ok(Some(key_combo)) => {
-            let assistance =
-                controller.get_key_combo_assistance(Some(&key_combo), original_binding);
-            match assistance.availability {
-                KeyComboAvailability::Incomplete => {
-                    set_feedback_state(
-                        availability_label,
-                        "Enter a key combination to check availability.",
-                        "availability-hint",
-                    );
+            "This key combination is currently free.",
+            "availability-available",
+        );
+        return;
+    }
+    set_feedback_state(
+        availability_label,
+        &lines.join("\n"),
+        "availability-warning",
+    );
+
+    if preview.bindings.is_empty() {
+        return;
+    }
+    let assistance = controller
+        .get_key_combo_assistance(Some(&candidate.key_combo), form.original_binding.as_ref());
+    if !assistance.suggestions.is_empty() {
+        for suggestion in assistance.suggestions {
+            let suggestion_text = suggestion.to_string();
+            let button = Button::builder().label(&suggestion_text).build();
+            button.add_css_class("suggestion-button");
+
+            let key_entry = form.key_entry.clone();
+            button.connect_clicked(move |_| {
+                key_entry.set_text(&suggestion_text);
+            });
+
+            suggestion_box.append(&button);
+        }
+        suggestion_box.set_visible(true);
+    }
+}
+
+/// Bind flags offered up front; the others are under "More flags"
+const MAIN_BIND_FLAGS: [BindFlag; 7] = [
+    BindFlag::Repeat,
+    BindFlag::Locked,
+    BindFlag::Mouse,
+    BindFlag::Release,
+    BindFlag::NonConsuming,
+    BindFlag::Transparent,
+    BindFlag::Description,
+];
+
+/// One toggle per bind flag, showing the keyword they make up
+///
+/// Every flag Hyprland knows has a toggle, so editing never drops one.
+#[derive(Clone)]
+struct BindFlagToggles {
+    widget: GtkBox,
+    checks: Vec<(BindFlag, CheckButton)>,
+    /// Shows the resulting keyword, e.g. `bindle`
+    keyword_label: Label,
+}
+
+impl BindFlagToggles {
+    /// Creates the toggles, set to the flags of `bind_type`
+    fn new(bind_type: BindType) -> Self {
+        let main = flag_flow_box();
+        let more = flag_flow_box();
+        let mut checks = Vec::new();
+        for flag in BindFlag::ALL {
+            let check = CheckButton::builder()
+                .label(flag.label())
+                .tooltip_text(format!("{} ({})", flag.description(), flag.letter()))
+                .active(bind_type.contains(flag))
+                .build();
+            if MAIN_BIND_FLAGS.contains(&flag) {
+                main.append(&check);
+            } else {
+                more.append(&check);
+            }
+            checks.push((flag, check));
+        }
+
+        // Opened when the binding already uses one of the rarer flags
+        let expander = Expander::builder()
+            .label("More flags")
+            .child(&more)
+            .expanded(
+                bind_type
+                    .flags()
+                    .any(|flag| !MAIN_BIND_FLAGS.contains(&flag)),
+            )
+            .build();
+        let keyword_label = Label::builder().xalign(0.0).build();
+        keyword_label.add_css_class("dim-label");
+
+        let widget = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .build();
+        widget.append(&main);
+        widget.append(&expander);
+        widget.append(&keyword_label);
+
+        let toggles = Self {
+            widget,
+            checks,
+            keyword_label,
+        };
+        toggles.refresh_keyword();
+        {
+            let toggles_for_keyword = toggles.clone();
+            toggles.connect_changed(move |_| toggles_for_keyword.refresh_keyword());
+        }
+        toggles
+    }
+
+    /// Returns the root widget for adding to a container
+    fn widget(&self) -> &GtkBox {
+        &self.widget
+    }
+
+    /// The bind type the toggled flags make up
+    fn bind_type(&self) -> BindType {
+        self.checks
+            .iter()
+            .filter(|(_, check)| check.is_active())
+            .fold(BindType::Bind, |bind_type, (flag, _)| bind_type.with(*flag))
+    }
+
+    /// Calls `callback` with the new bind type whenever a flag is toggled
+    fn connect_changed(&self, callback: impl Fn(BindType) + 'static) {
+        let callback = Rc::new(callback);
+        for (_, check) in &self.checks {
+            let toggles = self.clone();
+            let callback = callback.clone();
+            check.connect_toggled(move |_| callback(toggles.bind_type()));
+        }
+    }
+
+    fn refresh_keyword(&self) {
+        self.keyword_label
+            .set_label(&format!("Keyword: {}", self.bind_type()));
+    }
+}
+
+/// Lays out flag toggles in rows of up to four
+fn flag_flow_box() -> FlowBox {
+    FlowBox::builder()
+        .max_children_per_line(4)
+        .selection_mode(gtk4::SelectionMode::None)
+        .column_spacing(6)
+        .homogeneous(true)
+        .build()
+}
+
+/// Danger assessment of the command, shown below the arguments field
+///
+/// Hidden unless an `exec` command is entered. When the danger policy
+/// blocks the command, Save stays disabled until the override is ticked.
+#[derive(Clone)]
+struct DangerBanner {
+    container: GtkBox,
+    label: Label,
+    override_check: CheckButton,
+    /// Whether the danger policy blocks the command as typed
+    blocked: Rc<Cell<bool>>,
+}
+
+impl DangerBanner {
+    fn new() -> Self {
+        let label = Label::builder()
+            .halign(gtk4::Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .build();
+        let override_check = CheckButton::builder()
+            .label("Save anyway, overriding the danger policy")
+            .visible(false)
+            .build();
+        let container = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .visible(false)
+            .build();
+        container.add_css_class("danger-banner");
+        container.append(&label);
+        container.append(&override_check);
+
+        Self {
+            container,
+            label,
+            override_check,
+            blocked: Rc::new(Cell::new(false)),
+        }
+    }
+
+    fn widget(&self) -> &GtkBox {
+        &self.container
+    }
+
+    /// Shows the assessment of `args`, or hides the banner if they are not
+    /// a command
+    fn refresh(&self, controller: &Controller, dispatcher: &str, args: &str) {
+        for level in ["safe", "suspicious", "dangerous", "critical"] {
+            self.container
+                .remove_css_class(&format!("danger-banner-{}", level));
+        }
+
+        let Some(assessment) = controller.assess_exec_args(dispatcher, args) else {
+            self.blocked.set(false);
+            self.container.set_visible(false);
+            return;
+        };
+
+        let (heading, level) = match assessment.danger_level {
+            DangerLevel::Safe => ("✅ Safe", "safe"),
+            DangerLevel::Suspicious => ("🟡 Suspicious", "suspicious"),
+            DangerLevel::Dangerous => ("🟠 Dangerous", "dangerous"),
+            DangerLevel::Critical => ("🔴 Critical", "critical"),
+        };
+        let text = match assessment.danger_level {
+            DangerLevel::Safe => heading.to_string(),
+            _ => format!(
+                "{}: {}\n{}",
+                heading, assessment.reason, assessment.recommendation
+            ),
+        };
+        self.label.set_label(&text);
+        self.container
+            .add_css_class(&format!("danger-banner-{}", level));
+        self.container.set_visible(true);
+
+        // An override is for the command it was ticked for
+        let blocked = controller.is_danger_blocked(assessment.danger_level);
+        self.override_check.set_active(false);
+        self.override_check.set_visible(blocked);
+        self.blocked.set(blocked);
+    }
+
+    /// Whether Save may go ahead
+    fn allows_save(&self) -> bool {
+        !self.blocked.get() || self.override_check.is_active()
+    }
+
+    /// Whether the command is blocked and saved anyway
+    fn is_overridden(&self) -> bool {
+        self.blocked.get() && self.override_check.is_active()
+    }
+}
+
+/// Argument helper next to the arguments field
+///
+/// Commands get an app chooser, workspaces a number, directions and fixed
+/// words a choice. The helper writes into the arguments field, which stays
+/// editable for arguments the helper cannot express (`e+1`, `mon:DP-1`).
+#[derive(Clone)]
+struct ArgsHelper {
+    /// One page per helper: none, command, workspace and choice
+    stack: Stack,
+    workspace_spin: SpinButton,
+    choice_dropdown: DropDown,
+    /// Set while the helper follows the arguments field, so it does not
+    /// write back what it was just shown
+    syncing: Rc<Cell<bool>>,
+}
+
+impl ArgsHelper {
+    /// Creates the helper, writing the choices made in it to `args_entry`
+    fn new(args_entry: &Entry) -> Self {
+        let workspace_spin = SpinButton::with_range(1.0, WORKSPACE_SPIN_MAX, 1.0);
+        workspace_spin.set_tooltip_text(Some("Workspace number"));
+        let choice_dropdown = DropDown::from_strings(&[]);
+        choice_dropdown.set_tooltip_text(Some("Pick the argument"));
+
+        let stack = Stack::builder().hhomogeneous(false).build();
+        stack.add_named(&GtkBox::new(Orientation::Horizontal, 0), Some("none"));
+        stack.add_named(&build_app_chooser(args_entry), Some("command"));
+        stack.add_named(&workspace_spin, Some("workspace"));
+        stack.add_named(&choice_dropdown, Some("choice"));
+
+        let syncing = Rc::new(Cell::new(false));
+        {
+            let syncing = syncing.clone();
+            let args_entry = args_entry.clone();
+            workspace_spin.connect_value_changed(move |spin| {
+                if syncing.get() {
+                    return;
                 }
-                KeyComboAvailability::Available => {
-                    set_feedback_state(
-                        availability_label,
-                        "This key combination is currently free.",
-                        "availability-available",
-                    );
+                // Keeps anything after the workspace, e.g. `,window`
+                let text = args_entry.text();
+                let workspace = spin.value_as_int().to_string();
+                let args = match text.split_once(',') {
+                    Some((_, rest)) => format!("{},{}", workspace, rest),
+                    None => workspace,
+                };
+                args_entry.set_text(&args);
+            });
+        }
+        {
+            let syncing = syncing.clone();
+            let args_entry = args_entry.clone();
+            choice_dropdown.connect_selected_notify(move |dropdown| {
+                if syncing.get() {
+                    return;
                 }
-                KeyComboAvailability::InUse(bindings) => {
-                    let preview = bindings
-                        .iter()
-                        .take(2)
-                        .map(describe_binding)
-                        .collect::<Vec<_>>()
-                        .join(" | ");
-                    let suffix = if bindings.len() > 2 { " | ..." } else { "" };
-                    let message = format!("Already in use by {}{}", preview, suffix);
-                    set_feedback_state(availability_label, &message, "availability-warning");
-
-                    if !assistance.suggestions.is_empty() {
-                        for suggestion in assistance.suggestions {
-                            let suggestion_text = suggestion.to_string();
-                            let button = Button::builder().label(&suggestion_text).build();
-                            button.add_css_class("suggestion-button");
-
-                            let key_entry = key_entry.clone();
-                            button.connect_clicked(move |_| {
-                                key_entry.set_text(&suggestion_text);
-                            });
-
-                            suggestion_box.append(&button);
-                        }
-                        suggestion_box.set_visible(true);
+                let Some(choice) = dropdown
+                    .selected_item()
+                    .and_downcast::<StringObject>()
+                    .map(|item| item.string())
+                else {
+                    return;
+                };
+                // Keeps the words after the first, e.g. a window selector
+                let text = args_entry.text();
+                let args = match text.trim().split_once(' ') {
+                    Some((_, rest)) => format!("{} {}", choice, rest),
+                    None => choice.to_string(),
+                };
+                args_entry.set_text(&args);
+            });
+        }
+
+        Self {
+            stack,
+            workspace_spin,
+            choice_dropdown,
+            syncing,
+        }
+    }
+
+    /// Returns the root widget for adding to a container
+    fn widget(&self) -> &Stack {
+        &self.stack
+    }
+
+    /// Shows the helper for the dispatcher, set to the arguments as far
+    /// as it can express them
+    fn refresh(&self, dispatcher: &str, args: &str) {
+        self.syncing.set(true);
+        let args = args.trim();
+        let page = match dispatcher_spec(dispatcher).map(|spec| spec.arguments) {
+            Some(ArgumentShape::Command) => "command",
+            Some(ArgumentShape::Workspace) => {
+                let workspace = args.split(',').next().unwrap_or_default().trim();
+                if let Ok(number) = workspace.parse::<u16>() {
+                    if (1.0..=WORKSPACE_SPIN_MAX).contains(&f64::from(number)) {
+                        self.workspace_spin.set_value(f64::from(number));
                     }
                 }
+                "workspace"
+            }
+            Some(shape) if !shape.choices().is_empty() => {
+                self.show_choices(shape.choices(), args);
+                "choice"
             }
+            _ => "none",
+        };
+        self.stack.set_visible_child_name(page);
+        self.syncing.set(false);
+    }
+
+    /// Offers `choices`, selecting the one the arguments start with
+    fn show_choices(&self, choices: &[&str], args: &str) {
+        let offered = self
+            .choice_dropdown
+            .model()
+            .and_downcast::<StringList>()
+            .is_some_and(|model| {
+                model.n_items() as usize == choices.len()
+                    && choices.iter().enumerate().all(|(index, choice)| {
+                        model
+                            .string(index as u32)
+                            .is_some_and(|offered| offered.as_str() == *choice)
+                    })
+            });
+        if !offered {
+            self.choice_dropdown
+                .set_model(Some(&StringList::new(choices)));
         }
+
+        let first = args.split([' ', ',']).next().unwrap_or_default();
+        let selected = choices
+            .iter()
+            .position(|choice| choice.eq_ignore_ascii_case(first))
+            .map_or(gtk4::INVALID_LIST_POSITION, |index| index as u32);
+        self.choice_dropdown.set_selected(selected);
     }
 }
 
+/// Builds the searchable dispatcher dropdown
+///
+/// Offers the dispatchers the running Hyprland supports, each listed with
+/// its summary. The binding's own dispatcher is offered even when it is
+/// not, so opening the dialog never changes it.
+fn build_dispatcher_dropdown(controller: &Controller, current: &str) -> DropDown {
+    let current = current.trim();
+    let mut names: Vec<&str> = controller
+        .get_available_dispatchers()
+        .iter()
+        .map(|spec| spec.name)
+        .collect();
+    if !current.is_empty() && !names.contains(&current) {
+        names.insert(0, current);
+    }
+
+    let dropdown = DropDown::builder()
+        .model(&StringList::new(&names))
+        .enable_search(true)
+        .search_match_mode(gtk4::StringFilterMatchMode::Substring)
+        .hexpand(true)
+        .build();
+    dropdown.set_expression(Some(PropertyExpression::new(
+        StringObject::static_type(),
+        None::<&gtk4::Expression>,
+        "string",
+    )));
+    dropdown.set_list_factory(Some(&dispatcher_list_factory()));
+    dropdown.set_selected(
+        names
+            .iter()
+            .position(|name| *name == current)
+            .map_or(gtk4::INVALID_LIST_POSITION, |index| index as u32),
+    );
+
+    dropdown.set_tooltip_text(Some(&match controller.get_hyprland_version() {
+        Some(version) => format!(
+            "The Hyprland dispatcher to run (those supported by Hyprland {})",
+            version
+        ),
+        None => "The Hyprland dispatcher to run".to_string(),
+    }));
+    dropdown
+}
+
+/// Lists each dispatcher with its summary, the example as a tooltip
+fn dispatcher_list_factory() -> SignalListItemFactory {
+    let factory = SignalListItemFactory::new();
+    factory.connect_setup(|_, item| {
+        if let Some(item) = item.downcast_ref::<ListItem>() {
+            item.set_child(Some(&Label::builder().xalign(0.0).build()));
+        }
+    });
+    factory.connect_bind(|_, item| {
+        let Some(item) = item.downcast_ref::<ListItem>() else {
+            return;
+        };
+        let (Some(label), Some(name)) = (
+            item.child().and_downcast::<Label>(),
+            item.item().and_downcast::<StringObject>(),
+        ) else {
+            return;
+        };
+
+        let name = name.string();
+        match dispatcher_spec(&name) {
+            Some(spec) => {
+                label.set_label(&format!("{} — {}", spec.name, spec.summary));
+                label.set_tooltip_text(
+                    (!spec.example.is_empty())
+                        .then(|| format!("e.g. {}, {}", spec.name, spec.example))
+                        .as_deref(),
+                );
+            }
+            None => {
+                label.set_label(&name);
+                label.set_tooltip_text(None);
+            }
+        }
+    });
+    factory
+}
+
+/// The dispatcher selected in the dropdown (empty when none is)
+fn selected_dispatcher(dropdown: &DropDown) -> String {
+    dropdown
+        .selected_item()
+        .and_downcast::<StringObject>()
+        .map(|item| item.string().to_string())
+        .unwrap_or_default()
+}
+
+/// Builds the app chooser offered for `exec` bindings
+///
+/// Lists the installed applications; picking one fills in the arguments
+/// field with its command line. The list is read when first opened.
+fn build_app_chooser(args_entry: &Entry) -> MenuButton {
+    let search = SearchEntry::builder()
+        .placeholder_text("Search applications")
+        .build();
+    let list = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(2)
+        .build();
+    let scroller = ScrolledWindow::builder()
+        .child(&list)
+        .min_content_height(240)
+        .propagate_natural_width(true)
+        .build();
+    let content = GtkBox::builder()
+        .orientation(Orientation::Vertical)
+        .spacing(6)
+        .build();
+    content.append(&search);
+    content.append(&scroller);
+    let popover = Popover::builder().child(&content).build();
+
+    // Display name and command of every application shown in menus
+    let apps: Rc<OnceCell<Vec<(String, String)>>> = Rc::new(OnceCell::new());
+    let fill = {
+        let args_entry = args_entry.clone();
+        let popover = popover.clone();
+        let search = search.clone();
+        move || {
+            let apps = apps.get_or_init(installed_apps);
+            fill_app_list(&list, apps, &search.text(), &args_entry, &popover);
+        }
+    };
+    let fill = Rc::new(fill);
+    {
+        let fill = fill.clone();
+        popover.connect_show(move |_| fill());
+    }
+    search.connect_search_changed(move |_| fill());
+
+    MenuButton::builder()
+        .icon_name("system-run-symbolic")
+        .tooltip_text("Choose an installed application")
+        .popover(&popover)
+        .build()
+}
+
+/// Display name and command line of the applications shown in menus
+fn installed_apps() -> Vec<(String, String)> {
+    let mut apps: Vec<(String, String)> = gio::AppInfo::all()
+        .into_iter()
+        .filter(|app| app.should_show())
+        .filter_map(|app| {
+            let command = desktop_exec_command(&app.commandline()?.to_string_lossy());
+            (!command.is_empty()).then(|| (app.display_name().to_string(), command))
+        })
+        .collect();
+    apps.sort_by_key(|(name, _)| name.to_lowercase());
+    apps.dedup();
+    apps
+}
+
+/// Lists the applications whose name or command contains `query`
+fn fill_app_list(
+    list: &GtkBox,
+    apps: &[(String, String)],
+    query: &str,
+    args_entry: &Entry,
+    popover: &Popover,
+) {
+    while let Some(child) = list.first_child() {
+        list.remove(&child);
+    }
+
+    let query = query.trim().to_lowercase();
+    for (name, command) in apps {
+        if !name.to_lowercase().contains(&query) && !command.to_lowercase().contains(&query) {
+            continue;
+        }
+        let button = Button::builder()
+            .label(name)
+            .tooltip_text(command)
+            .has_frame(false)
+            .build();
+        if let Some(label) = button.child().and_downcast::<Label>() {
+            label.set_xalign(0.0);
+        }
+
+        let command = command.clone();
+        let args_entry = args_entry.clone();
+        let popover = popover.clone();
+        button.connect_clicked(move |_| {
+            args_entry.set_text(&command);
+            popover.popdown();
+        });
+        list.append(&button);
+    }
+
+    if list.first_child().is_none() {
+        let empty = Label::builder()
+            .label("No matching application")
+            .xalign(0.0)
+            .build();
+        empty.add_css_class("dim-label");
+        list.append(&empty);
+    }
+}
+
+/// Shows a warning icon when the running Hyprland lacks the dispatcher
+fn refresh_dispatcher_warning(controller: &Controller, dispatcher: &str, warning_icon: &Image) {
+    let warning = controller.get_dispatcher_warning(dispatcher);
+
+    warning_icon.set_visible(warning.is_some());
+    warning_icon.set_tooltip_text(warning.as_deref());
+}
+
+/// Shows what the dispatcher expects in the arguments field
+///
+/// The placeholder gives the catalogue's example, and a warning icon
+/// explains arguments that do not fit (see
+/// [`crate::core::dispatchers::validate_arguments`]).
+fn refresh_argument_feedback(controller: &Controller, dispatcher: &str, args_entry: &Entry) {
+    let placeholder = match dispatcher_spec(dispatcher) {
+        Some(spec) if spec.example.is_empty() => "No arguments".to_string(),
+        Some(spec) if spec.optional => format!("Optional, e.g. {}", spec.example),
+        Some(spec) => format!("e.g. {}", spec.example),
+        None => "Optional arguments".to_string(),
+    };
+    args_entry.set_placeholder_text(Some(&placeholder));
+
+    // Nothing typed yet is not worth a warning; saving still checks it
+    let args = args_entry.text();
+    let problem = (!args.trim().is_empty())
+        .then(|| {
+            controller
+                .get_argument_problem(dispatcher, &args)
+                .or_else(|| {
+                    workspace_warnings(dispatcher, Some(&args))
+                        .first()
+                        .map(ToString::to_string)
+                })
+        })
+        .flatten();
+    args_entry.set_icon_from_icon_name(
+        EntryIconPosition::Secondary,
+        problem.as_ref().map(|_| "dialog-warning-symbolic"),
+    );
+    args_entry.set_icon_tooltip_text(EntryIconPosition::Secondary, problem.as_deref());
+}
+
 fn clear_suggestion_box(suggestion_box: &GtkBox) {
     while let Some(child) = suggestion_box.first_child() {
         suggestion_box.remove(&child);
@@ -611,14 +1881,119 @@ fn describe_binding(binding: &Keybinding) -> String {
     }
 }
 
+/// Shows the workspace target status and the open workspaces
+///
+/// Only visible for workspace dispatchers. Hyprland is queried once per
+/// dialog, the first time a workspace dispatcher is entered.
+fn refresh_workspace_preview_widgets(
+    controller: &Controller,
+    workspaces: &OnceCell<Option<Vec<WorkspaceInfo>>>,
+    dispatcher: &str,
+    args_entry: &Entry,
+    workspace_label: &Label,
+) {
+    if !is_workspace_dispatcher(dispatcher) {
+        workspace_label.set_visible(false);
+        return;
+    }
+
+    let running = workspaces.get_or_init(|| controller.get_workspaces());
+
+    let (status, css_class) = match parse_workspace_target(&args_entry.text()) {
+        Err(reason) => (reason, "availability-warning"),
+        Ok(target) => match running {
+            None => (
+                "Hyprland is not running, so existing workspaces cannot be checked.".to_string(),
+                "availability-hint",
+            ),
+            Some(existing) => match workspace_status(&target, existing) {
+                WorkspaceStatus::Exists { windows } => (
+                    format!("Workspace exists ({}).", describe_window_count(windows)),
+                    "availability-available",
+                ),
+                WorkspaceStatus::WillBeCreated => (
+                    "Workspace does not exist yet; Hyprland creates it on first use.".to_string(),
+                    "availability-hint",
+                ),
+                WorkspaceStatus::Dynamic => (
+                    "Relative target, resolved when the binding is used.".to_string(),
+                    "availability-hint",
+                ),
+            },
+        },
+    };
+
+    let text = match running {
+        Some(existing) if !existing.is_empty() => {
+            let mut sorted: Vec<&WorkspaceInfo> = existing.iter().collect();
+            sorted.sort_by_key(|ws| ws.id);
+            let list = sorted
+                .iter()
+                .map(|ws| format!("{} ({})", ws.name, describe_window_count(ws.windows)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{}\nOpen workspaces: {}", status, list)
+        }
+        _ => status,
+    };
+
+    set_feedback_state(workspace_label, &text, css_class);
+    workspace_label.set_visible(true);
+}
+
+/// Says how a trial ended
+fn describe_trial_end(result: Result<(), ConfigError>) -> String {
+    match result {
+        Ok(()) => "Trial over: the key does what it did before.".to_string(),
+        Err(e) => format!("Trial over, but restoring the key failed: {}", e),
+    }
+}
+
+fn describe_window_count(windows: u16) -> String {
+    match windows {
+        1 => "1 window".to_string(),
+        n => format!("{} windows", n),
+    }
+}
+
+fn show_test_output(test_output: &TextView, test_output_scroller: &ScrolledWindow, text: &str) {
+    test_output.buffer().set_text(text);
+    test_output_scroller.set_visible(true);
+}
+
+fn describe_captured_run(run: &CapturedRun) -> String {
+    let mut text = match (run.timed_out, run.exit_code) {
+        (true, _) => format!(
+            "⏱ Stopped after {} seconds (timeout)",
+            TEST_RUN_TIMEOUT.as_secs()
+        ),
+        (false, Some(0)) => "✅ Exited successfully".to_string(),
+        (false, Some(code)) => format!("❌ Exited with code {}", code),
+        (false, None) => "❌ Terminated by a signal".to_string(),
+    };
+
+    for (name, output) in [("stdout", &run.stdout), ("stderr", &run.stderr)] {
+        if !output.trim().is_empty() {
+            text.push_str(&format!("\n\n── {} ──\n{}", name, output.trim_end()));
+        }
+    }
+    if run.stdout.trim().is_empty() && run.stderr.trim().is_empty() {
+        text.push_str("\n\n(no output)");
+    }
+
+    text
+}
+
 fn refresh_sandbox_controls_widgets(
-    dispatcher_entry: &Entry,
+    dispatcher: &str,
     sandbox_switch: &Switch,
     sandbox_label: &Label,
+    test_button: &Button,
 ) {
-    let enabled = dispatcher_entry.text().trim().eq_ignore_ascii_case("exec");
+    let enabled = dispatcher.trim().eq_ignore_ascii_case("exec");
     sandbox_switch.set_sensitive(enabled);
     sandbox_label.set_sensitive(enabled);
+    test_button.set_sensitive(enabled);
 
     if enabled {
         sandbox_switch.set_tooltip_text(Some(
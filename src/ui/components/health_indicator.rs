@@ -0,0 +1,88 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Config health indicator
+//!
+//! Shows the config health score in the header bar, coloured by band, with
+//! the per-category breakdown and trend in its tooltip. Refreshed whenever
+//! the conflict panel is, i.e. after every change to the bindings.
+
+use gtk4::{prelude::*, Label};
+use std::rc::Rc;
+
+use crate::ui::Controller;
+
+/// Header bar label showing the config health score
+pub struct HealthIndicator {
+    /// Label with the score
+    label: Label,
+    /// Controller for computing the score
+    controller: Rc<Controller>,
+}
+
+impl HealthIndicator {
+    /// Creates the indicator (call `refresh()` once bindings are loaded)
+    ///
+    /// # Arguments
+    /// * `controller` - Shared controller for computing the score
+    pub fn new(controller: Rc<Controller>) -> Self {
+        let label = Label::builder().label("Health –").build();
+        label.add_css_class("health-indicator");
+
+        Self { label, controller }
+    }
+
+    /// Recomputes the score, records it in the audit log and updates the label
+    pub fn refresh(&self) {
+        let report = self.controller.get_health_report();
+        let trend = self.controller.track_health(report.score);
+
+        self.label.set_label(&format!("Health {}", report.score));
+        for class in ["health-good", "health-fair", "health-poor"] {
+            self.label.remove_css_class(class);
+        }
+        self.label.add_css_class(match report.score {
+            90.. => "health-good",
+            70..=89 => "health-fair",
+            _ => "health-poor",
+        });
+
+        let mut tooltip = format!("Config health: {}/100\n", report.score);
+        for component in report.components() {
+            tooltip.push_str(&format!(
+                "\n−{}  {}: {}",
+                component.penalty, component.label, component.detail
+            ));
+        }
+        if !report.missing_executables.is_empty() {
+            tooltip.push_str(&format!(
+                "\n\nNot found: {}",
+                report.missing_executables.join(", ")
+            ));
+        }
+        if let Some(trend) = trend {
+            tooltip.push_str(&format!(
+                "\n\n{:+} since {}",
+                trend.change,
+                trend.since.format("%Y-%m-%d %H:%M")
+            ));
+        }
+        self.label.set_tooltip_text(Some(&tooltip));
+    }
+
+    /// Returns the root widget for adding to a container
+    pub fn widget(&self) -> &Label {
+        &self.label
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Help window
+//!
+//! Searchable documentation embedded in the binary: this tool's features,
+//! bind flags, dispatchers, conflicts and danger rules. The topics come from
+//! [`HelpIndex`], which builds them from the code's own tables, so the help
+//! works offline and matches the version running.
+
+use gtk4::{
+    gdk, pango::WrapMode, prelude::*, Align, Box as GtkBox, EventControllerKey, Label, ListBox,
+    ListBoxRow, Orientation, Paned, ScrolledWindow, SearchEntry, SelectionMode, Window,
+};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::core::help::{HelpIndex, HelpTopic};
+
+/// Window listing help topics next to the selected one
+pub struct HelpWindow {
+    window: Window,
+}
+
+impl HelpWindow {
+    /// Creates the help window.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Window the help belongs to (not modal, so both can be used)
+    /// * `index` - The topics to browse
+    pub fn new(parent: &Window, index: HelpIndex) -> Self {
+        let window = Window::builder()
+            .title("Help")
+            .transient_for(parent)
+            .default_width(900)
+            .default_height(600)
+            .build();
+
+        // Escape key handler
+        let key_controller = EventControllerKey::new();
+        let window_for_escape = window.clone();
+        key_controller.connect_key_pressed(move |_, key, _, _| {
+            if key == gdk::Key::Escape {
+                window_for_escape.close();
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+        window.add_controller(key_controller);
+
+        let search_entry = SearchEntry::builder()
+            .placeholder_text("Search help...")
+            .margin_start(12)
+            .margin_end(12)
+            .margin_top(12)
+            .margin_bottom(6)
+            .build();
+        search_entry.update_property(&[gtk4::accessible::Property::Label("Search help")]);
+
+        let list_box = ListBox::builder()
+            .selection_mode(SelectionMode::Browse)
+            .build();
+        let list_scroller = ScrolledWindow::builder()
+            .vexpand(true)
+            .child(&list_box)
+            .build();
+
+        let left_vbox = GtkBox::new(Orientation::Vertical, 0);
+        left_vbox.append(&search_entry);
+        left_vbox.append(&list_scroller);
+
+        let title_label = Label::builder()
+            .halign(Align::Start)
+            .wrap(true)
+            .selectable(true)
+            .build();
+        title_label.add_css_class("title-2");
+        let section_label = Label::builder().halign(Align::Start).build();
+        section_label.add_css_class("dim-label");
+        let body_label = Label::builder()
+            .halign(Align::Start)
+            .valign(Align::Start)
+            .xalign(0.0)
+            .wrap(true)
+            .wrap_mode(WrapMode::WordChar)
+            .selectable(true)
+            .build();
+
+        let topic_vbox = GtkBox::new(Orientation::Vertical, 6);
+        topic_vbox.set_margin_start(18);
+        topic_vbox.set_margin_end(18);
+        topic_vbox.set_margin_top(18);
+        topic_vbox.set_margin_bottom(18);
+        topic_vbox.append(&title_label);
+        topic_vbox.append(&section_label);
+        topic_vbox.append(&body_label);
+        let topic_scroller = ScrolledWindow::builder()
+            .hexpand(true)
+            .child(&topic_vbox)
+            .build();
+
+        let paned = Paned::builder()
+            .orientation(Orientation::Horizontal)
+            .start_child(&left_vbox)
+            .end_child(&topic_scroller)
+            .position(280)
+            .shrink_start_child(false)
+            .build();
+        window.set_child(Some(&paned));
+
+        // Topics currently listed, in row order
+        let shown: Rc<RefCell<Vec<HelpTopic>>> = Rc::new(RefCell::new(Vec::new()));
+
+        let show_topic = Rc::new(move |topic: Option<&HelpTopic>| match topic {
+            Some(topic) => {
+                title_label.set_label(&topic.title);
+                section_label.set_label(&topic.section.to_string());
+                body_label.set_label(&topic.body);
+            }
+            None => {
+                title_label.set_label("No matching topics");
+                section_label.set_label("");
+                body_label.set_label("Try fewer or different words.");
+            }
+        });
+
+        let shown_for_selection = shown.clone();
+        let show_selected = show_topic.clone();
+        list_box.connect_row_selected(move |_, row| {
+            if let Some(row) = row {
+                show_selected(shown_for_selection.borrow().get(row.index() as usize));
+            }
+        });
+
+        let index = Rc::new(index);
+        let list_for_search = list_box.clone();
+        let show_matches = move |query: &str| {
+            *shown.borrow_mut() = index.search(query).into_iter().cloned().collect();
+            fill_topic_list(&list_for_search, &shown.borrow());
+            match list_for_search.row_at_index(0) {
+                Some(first) => list_for_search.select_row(Some(&first)),
+                None => show_topic(None),
+            }
+        };
+        show_matches("");
+        search_entry.connect_search_changed(move |entry| show_matches(&entry.text()));
+
+        // Section headings above the first topic of each section
+        list_box.set_header_func(|row, before| {
+            let section = |row: &ListBoxRow| row.widget_name().to_string();
+            if before.map(section) != Some(section(row)) {
+                let header = Label::builder()
+                    .label(section(row))
+                    .halign(Align::Start)
+                    .margin_start(8)
+                    .margin_top(10)
+                    .margin_bottom(4)
+                    .build();
+                header.add_css_class("field-header");
+                row.set_header(Some(&header));
+            } else {
+                row.set_header(None::<&gtk4::Widget>);
+            }
+        });
+
+        Self { window }
+    }
+
+    /// Shows the window.
+    pub fn show(&self) {
+        self.window.present();
+    }
+}
+
+/// Replaces the rows of the topic list
+///
+/// Each row's widget name holds its section, for the section headings.
+fn fill_topic_list(list_box: &ListBox, topics: &[HelpTopic]) {
+    list_box.remove_all();
+    for topic in topics {
+        let label = Label::builder()
+            .label(&topic.title)
+            .halign(Align::Start)
+            .margin_start(16)
+            .margin_end(8)
+            .margin_top(4)
+            .margin_bottom(4)
+            .build();
+        let row = ListBoxRow::builder().child(&label).build();
+        row.set_widget_name(&topic.section.to_string());
+        list_box.append(&row);
+    }
+}
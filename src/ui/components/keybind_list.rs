@@ -15,18 +15,45 @@
 //! Keybinding list component
 //!
 //! Displays all keybindings in a scrollable list view.
-//! Each row shows the key combination, dispatcher, and arguments.
+//! Each row shows the key combination, dispatcher, and arguments, followed
+//! by small badges for conflicts (⚠ count) and risky commands (🛡 level).
+//! Configs with scope sections get a scope column before the badges.
+//! Rows can be dragged onto another row to reorder the bindings.
 
 use gtk4::{
-    pango::EllipsizeMode, prelude::*, Box as GtkBox, Grid, Label, ListBox, Orientation,
-    ScrolledWindow,
+    gdk, glib, pango::EllipsizeMode, prelude::*, Align, Box as GtkBox, Button, DragSource,
+    DropTarget, Grid, Label, ListBox, Orientation, ScrolledWindow, WidgetPaintable,
 };
 use std::{cell::RefCell, rc::Rc};
 
-use crate::{core::types::Keybinding, ui::Controller};
+use crate::{
+    config::danger::DangerLevel,
+    core::{
+        scope::GLOBAL_SCOPE,
+        types::{Key, Keybinding},
+    },
+    ui::{controller::BindingBadges, Controller},
+};
 
 const KEY_COLUMN_WIDTH: i32 = 190;
 const DISPATCHER_COLUMN_WIDTH: i32 = 140;
+const SCOPE_COLUMN_WIDTH: i32 = 90;
+const DEVICE_COLUMN_WIDTH: i32 = 110;
+
+/// Device column text of bindings outside any `device` section
+const ALL_DEVICES: &str = "all devices";
+
+/// Which badge on a row was clicked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowBadge {
+    /// The ⚠ conflict count badge
+    Conflict,
+    /// The 🛡 danger level badge
+    Danger,
+}
+
+type BadgeHandler = Rc<dyn Fn(RowBadge, &Keybinding)>;
+type MoveHandler = Rc<dyn Fn(&Keybinding, &Keybinding)>;
 
 /// Displays a scrollable list of keybindings
 pub struct KeybindList {
@@ -37,7 +64,11 @@ pub struct KeybindList {
     /// Controller reference for data access
     controller: Rc<Controller>,
     /// Cache of currently displayed bindings
-    current_bindings: RefCell<Vec<Keybinding>>,
+    current_bindings: Rc<RefCell<Vec<Keybinding>>>,
+    /// Callback for badge clicks (rows are rebuilt, so it is stored here)
+    badge_handler: Rc<RefCell<Option<BadgeHandler>>>,
+    /// Callback for rows dropped onto another row
+    move_handler: Rc<RefCell<Option<MoveHandler>>>,
 }
 
 impl KeybindList {
@@ -78,7 +109,9 @@ impl KeybindList {
             widget: scrolled_window,
             list_box,
             controller,
-            current_bindings: RefCell::new(Vec::new()),
+            current_bindings: Rc::new(RefCell::new(Vec::new())),
+            badge_handler: Rc::new(RefCell::new(None)),
+            move_handler: Rc::new(RefCell::new(None)),
         }
     }
 
@@ -101,15 +134,26 @@ impl KeybindList {
         // Cache the bindings
         *self.current_bindings.borrow_mut() = bindings.clone();
 
+        // The scope column only appears once the config declares scopes
+        let show_scope = !self.controller.get_scopes().is_empty();
+        // ...and the device column once a binding is in a device section
+        let show_device = !self.controller.binding_device_names().is_empty();
+
         // Add new rows with alternating colours
         for (index, binding) in bindings.iter().enumerate() {
-            let row = self.create_row(binding, index);
+            let row = self.create_row(binding, index, show_scope, show_device);
             self.list_box.append(&row);
         }
     }
 
     /// Create a single row widget for a keybinding
-    fn create_row(&self, binding: &Keybinding, index: usize) -> GtkBox {
+    fn create_row(
+        &self,
+        binding: &Keybinding,
+        index: usize,
+        show_scope: bool,
+        show_device: bool,
+    ) -> GtkBox {
         let row = GtkBox::builder()
             .orientation(Orientation::Vertical)
             .margin_start(8)
@@ -140,6 +184,29 @@ impl KeybindList {
             .build();
         key_label.add_css_class("list-key-column");
 
+        // A catchall is not a key: show what it catches instead
+        if binding.key_combo.is_catchall() {
+            let modifiers = binding
+                .key_combo
+                .canonical_modifiers()
+                .iter()
+                .map(|modifier| format!("{}+", modifier))
+                .collect::<String>();
+            key_label.set_label(&format!("{}✱ any other key", modifiers));
+            key_label.add_css_class("list-catchall");
+            key_label.set_tooltip_text(Some(
+                "catchall: fires for every key the submap does not bind otherwise",
+            ));
+        }
+
+        // Mouse buttons, scrolling and keycodes are shown by name
+        if !matches!(binding.key_combo.key, Key::Named(_)) {
+            key_label.set_tooltip_text(Some(&format!(
+                "Written as '{}' in the config",
+                binding.key_combo.config_key()
+            )));
+        }
+
         let dispatcher_label = Label::builder()
             .label(&binding.dispatcher)
             .xalign(0.0)
@@ -167,11 +234,234 @@ impl KeybindList {
         grid.attach(&key_label, 0, 0, 1, 1);
         grid.attach(&dispatcher_label, 1, 0, 1, 1);
         grid.attach(&args_label, 2, 0, 1, 1);
+
+        let mut badge_column = 3;
+        if show_scope {
+            let scope = self.controller.get_scope_for(binding);
+            let scope_label = Label::builder()
+                .label(scope.as_deref().unwrap_or(GLOBAL_SCOPE))
+                .xalign(0.0)
+                .width_request(SCOPE_COLUMN_WIDTH)
+                .ellipsize(EllipsizeMode::End)
+                .build();
+            scope_label.add_css_class("list-scope-column");
+            if scope.is_none() {
+                scope_label.add_css_class("list-scope-global");
+            }
+            grid.attach(&scope_label, badge_column, 0, 1, 1);
+            badge_column += 1;
+        }
+
+        if show_device {
+            let device_label = Label::builder()
+                .label(binding.device.as_deref().unwrap_or(ALL_DEVICES))
+                .xalign(0.0)
+                .width_request(DEVICE_COLUMN_WIDTH)
+                .ellipsize(EllipsizeMode::End)
+                .build();
+            device_label.add_css_class("list-device-column");
+            match &binding.device {
+                Some(device) => device_label
+                    .set_tooltip_text(Some(&format!("Only fires on input device '{}'", device))),
+                None => device_label.add_css_class("list-device-all"),
+            }
+            grid.attach(&device_label, badge_column, 0, 1, 1);
+            badge_column += 1;
+        }
+
+        let badges = self.controller.get_binding_badges(binding);
+        if let Some(badge_box) = self.create_badges(binding, badges) {
+            grid.attach(&badge_box, badge_column, 0, 1, 1);
+        }
+
         row.append(&grid);
 
+        // `bindd` descriptions go on a second line under the binding
+        if let Some(description) = binding.description.as_deref().filter(|d| !d.is_empty()) {
+            let description_label = Label::builder()
+                .label(description)
+                .xalign(0.0)
+                .margin_start(10)
+                .margin_end(10)
+                .margin_bottom(6)
+                .ellipsize(EllipsizeMode::End)
+                .build();
+            description_label.add_css_class("list-description");
+            row.append(&description_label);
+        }
+
+        self.make_draggable(&row, index);
+
         row
     }
 
+    /// Lets a row be dragged onto another to move its binding there
+    ///
+    /// The drag carries the row's index in the current view, and the drop
+    /// looks both bindings up in that view.
+    fn make_draggable(&self, row: &GtkBox, index: usize) {
+        let drag_source = DragSource::builder().actions(gdk::DragAction::MOVE).build();
+        drag_source.connect_prepare(move |_, _, _| {
+            Some(gdk::ContentProvider::for_value(&(index as u32).to_value()))
+        });
+        let row_for_icon = row.clone();
+        drag_source.connect_drag_begin(move |source, _| {
+            source.set_icon(Some(&WidgetPaintable::new(Some(&row_for_icon))), 0, 0);
+        });
+        row.add_controller(drag_source);
+
+        let drop_target = DropTarget::new(glib::Type::U32, gdk::DragAction::MOVE);
+        let bindings = self.current_bindings.clone();
+        let handler = self.move_handler.clone();
+        drop_target.connect_drop(move |_, value, _, _| {
+            let Ok(from) = value.get::<u32>() else {
+                return false;
+            };
+            let (moved, target) = {
+                let bindings = bindings.borrow();
+                match (bindings.get(from as usize), bindings.get(index)) {
+                    (Some(moved), Some(target)) if moved != target => {
+                        (moved.clone(), target.clone())
+                    }
+                    _ => return false,
+                }
+            };
+
+            let Some(handler) = handler.borrow().clone() else {
+                return false;
+            };
+            // The handler rebuilds the rows, so it runs after the drop is done
+            glib::idle_add_local_once(move || handler(&moved, &target));
+            true
+        });
+        row.add_controller(drop_target);
+    }
+
+    /// Creates the badge container for a row, or `None` if there is nothing to flag
+    fn create_badges(&self, binding: &Keybinding, badges: BindingBadges) -> Option<GtkBox> {
+        if badges.conflict_count == 0 && badges.danger_level.is_none() {
+            return None;
+        }
+
+        let badge_box = GtkBox::builder()
+            .orientation(Orientation::Horizontal)
+            .spacing(4)
+            .halign(Align::End)
+            .valign(Align::Center)
+            .build();
+
+        if badges.conflict_count > 0 {
+            let tooltip = if badges.conflict_count == 1 {
+                "Conflicts with 1 other binding - click to resolve".to_string()
+            } else {
+                format!(
+                    "Conflicts with {} other bindings - click to resolve",
+                    badges.conflict_count
+                )
+            };
+            let badge = self.create_badge(
+                &format!("⚠ {}", badges.conflict_count),
+                &tooltip,
+                "badge-conflict",
+                RowBadge::Conflict,
+                binding,
+            );
+            badge_box.append(&badge);
+        }
+
+        if let Some(level) = badges.danger_level {
+            let (text, css_class) = match level {
+                DangerLevel::Critical => ("🛡 Critical", "badge-danger-critical"),
+                DangerLevel::Dangerous => ("🛡 Dangerous", "badge-danger-dangerous"),
+                _ => ("🛡 Suspicious", "badge-danger-suspicious"),
+            };
+            let badge = self.create_badge(
+                text,
+                "Command may be unsafe - click for details",
+                css_class,
+                RowBadge::Danger,
+                binding,
+            );
+            badge_box.append(&badge);
+        }
+
+        Some(badge_box)
+    }
+
+    /// Creates a single clickable badge
+    fn create_badge(
+        &self,
+        text: &str,
+        tooltip: &str,
+        css_class: &str,
+        kind: RowBadge,
+        binding: &Keybinding,
+    ) -> Button {
+        let badge = Button::builder()
+            .label(text)
+            .tooltip_text(tooltip)
+            .has_frame(false)
+            .focus_on_click(false)
+            .build();
+        badge.add_css_class("row-badge");
+        badge.add_css_class(css_class);
+
+        let handler = self.badge_handler.clone();
+        let binding = binding.clone();
+        badge.connect_clicked(move |_| {
+            let handler = handler.borrow().clone();
+            if let Some(handler) = handler {
+                handler(kind, &binding);
+            }
+        });
+
+        badge
+    }
+
+    /// Connects a callback for clicks on row badges
+    ///
+    /// # Arguments
+    /// * `callback` - Receives which badge was clicked and the row's binding
+    pub fn connect_badge_clicked<F>(&self, callback: F)
+    where
+        F: Fn(RowBadge, &Keybinding) + 'static,
+    {
+        *self.badge_handler.borrow_mut() = Some(Rc::new(callback));
+    }
+
+    /// Connects a callback for a row dropped onto another row
+    ///
+    /// # Arguments
+    /// * `callback` - Receives the dragged binding and the binding it was
+    ///   dropped on
+    pub fn connect_binding_moved<F>(&self, callback: F)
+    where
+        F: Fn(&Keybinding, &Keybinding) + 'static,
+    {
+        *self.move_handler.borrow_mut() = Some(Rc::new(callback));
+    }
+
+    /// Selects the row displaying the given binding, if it is visible
+    ///
+    /// # Returns
+    ///
+    /// `true` if the binding was found and its row selected
+    pub fn select_binding(&self, binding: &Keybinding) -> bool {
+        let index = self
+            .current_bindings
+            .borrow()
+            .iter()
+            .position(|b| b == binding);
+
+        match index.and_then(|i| self.list_box.row_at_index(i as i32)) {
+            Some(row) => {
+                self.list_box.select_row(Some(&row));
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Returns the root widget for adding to parent container
     pub fn widget(&self) -> &ScrolledWindow {
         &self.widget
@@ -20,22 +20,59 @@
 //!
 //! - `keybind_list.rs` - Scrollable list of keybindings
 //! - `search_bar.rs` - Real-time search/filter
+//! - `scope_switcher.rs` - Scope preview drop-down
+//! - `category_filter.rs` - Category filter drop-down
 //! - `conflict_panel.rs` - Conflict warning banner
+//! - `problems_panel.rs` - Banner for config lines the parser skipped
+//! - `health_indicator.rs` - Config health score in the header bar
+//! - `connection_indicator.rs` - Hyprland connection status and instance choice
 //! - `details_panel.rs` - Selected binding details
 //! - `edit_dialog.rs` - Add/edit keybinding dialog
 //! - `backup_dialog.rs` - Backup management dialog
+//! - `compare_dialog.rs` - Side-by-side comparison with a backup
+//! - `review_dialog.rs` - Diff of a change before it is saved
+//! - `diff_dialog.rs` - Read-only diff, e.g. of what restoring a backup changes
+//! - `preferences_dialog.rs` - High-contrast and reduced-motion settings
+//! - `help_window.rs` - Searchable embedded documentation
+//! - `statistics_window.rs` - Binding statistics (modifiers, dispatchers, free keys)
 
+mod category_filter;
 mod conflict_panel;
+mod connection_indicator;
 mod details_panel;
 mod edit_dialog;
+mod health_indicator;
 mod keybind_list;
+mod problems_panel;
+mod scope_switcher;
 mod search_bar;
 
 pub(crate) mod backup_dialog;
+mod compare_dialog;
+mod diff_dialog;
+mod help_window;
+mod preferences_dialog;
+mod review_dialog;
+mod statistics_window;
 
 pub mod conflict_resolution_dialog;
 
 pub use {
-    backup_dialog::BackupDialog, conflict_panel::ConflictPanel, details_panel::DetailsPanel,
-    edit_dialog::EditDialog, keybind_list::KeybindList, search_bar::SearchBar,
+    backup_dialog::BackupDialog,
+    category_filter::CategoryFilter,
+    compare_dialog::CompareDialog,
+    conflict_panel::ConflictPanel,
+    connection_indicator::ConnectionIndicator,
+    details_panel::DetailsPanel,
+    diff_dialog::DiffDialog,
+    edit_dialog::EditDialog,
+    health_indicator::HealthIndicator,
+    help_window::HelpWindow,
+    keybind_list::{KeybindList, RowBadge},
+    preferences_dialog::PreferencesDialog,
+    problems_panel::ProblemsPanel,
+    review_dialog::ReviewDialog,
+    scope_switcher::ScopeSwitcher,
+    search_bar::SearchBar,
+    statistics_window::StatisticsWindow,
 };
@@ -0,0 +1,441 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gtk4::{
+    gdk, prelude::*, Align, Box as GtkBox, DropDown, EventControllerKey, Grid, Label, Orientation,
+    SpinButton, Switch, Window,
+};
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use crate::{
+    config::{
+        backup::{BackupCompression, BackupMode, RetentionPolicy, BYTES_PER_MB},
+        settings::{ColorScheme, DangerPolicy, Settings},
+    },
+    core::types::BindType,
+    ui::{theme::Theme, Controller},
+};
+
+/// Colour schemes offered, in menu order
+static COLOR_SCHEMES: [(ColorScheme, &str); 3] = [
+    (ColorScheme::System, "System"),
+    (ColorScheme::Light, "Light"),
+    (ColorScheme::Dark, "Dark"),
+];
+
+/// Danger policies offered, in menu order
+static DANGER_POLICIES: [(DangerPolicy, &str); 3] = [
+    (DangerPolicy::Critical, "Critical only"),
+    (DangerPolicy::Dangerous, "Dangerous and up"),
+    (DangerPolicy::Suspicious, "Anything not safe"),
+];
+
+/// Bind types offered for new bindings, in menu order
+static BIND_TYPES: [(BindType, &str); 5] = [
+    (BindType::Bind, "bind"),
+    (BindType::BindE, "binde (repeat)"),
+    (BindType::BindL, "bindl (locked)"),
+    (BindType::BindEL, "bindel (repeat, locked)"),
+    (BindType::BindR, "bindr (release)"),
+];
+
+/// Backup compressions offered, in menu order
+static COMPRESSIONS: [(BackupCompression, &str); 3] = [
+    (BackupCompression::None, "None"),
+    (BackupCompression::Gzip, "gzip"),
+    (BackupCompression::Zstd, "zstd"),
+];
+
+/// A switch row: title, subtitle, whether it is on, and how it sets the
+/// preference
+type SwitchRow = (&'static str, &'static str, bool, fn(&mut Settings, bool));
+
+/// A backup limit row: title, subtitle, current limit (0: none), and how
+/// it sets the retention policy
+type LimitRow = (
+    &'static str,
+    &'static str,
+    u64,
+    fn(&mut RetentionPolicy, u64),
+);
+
+/// Dialog for the accessibility, checking, backup and notification
+/// preferences.
+///
+/// Each switch takes effect immediately through the [`Theme`] and the
+/// [`Controller`] and is saved to the preferences file, so there is nothing
+/// to confirm:
+/// - **High contrast:** stronger colours, borders and focus outlines
+/// - **Reduce motion:** no animated transitions
+/// - **Reserved combos:** flag bindings on combos Hyprland or common tools
+///   rely on
+/// - **Review changes:** show each edit as a diff before it is saved
+/// - **Confirm deletions:** ask before deleting a binding
+/// - **Apply edits live:** send each added, edited or deleted binding to
+///   the running Hyprland instead of waiting for a reload
+/// - **Git backups:** commit each write to a git repository in the config
+///   directory instead of keeping timestamped copies
+/// - **Backup retention:** how many timestamped backups are kept, for how
+///   long and in how much space (0: no limit); pruned after every write
+/// - **Appearance:** light or dark, or the desktop's choice
+/// - **Block commands:** the danger level from which new and edited
+///   bindings are refused (Critical is always refused)
+/// - **New binding type:** bind type of bindings added in the GUI
+/// - **Backup compression:** gzip or zstd for new timestamped backups
+/// - **Notifications:** which events `watch` shows desktop notifications
+///   for; read by `watch` on every event, so they apply without a restart
+pub struct PreferencesDialog {
+    window: Window,
+}
+
+impl PreferencesDialog {
+    /// Creates a new preferences dialog.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Parent window for modal behaviour
+    /// * `theme` - Theming layer the preferences are applied through
+    /// * `controller` - Applies the checking preferences
+    /// * `settings` - Current preferences, updated as switches change
+    /// * `path` - Where to save the preferences (`None`: not persisted)
+    pub fn new(
+        parent: &Window,
+        theme: Rc<Theme>,
+        controller: Rc<Controller>,
+        settings: Rc<Cell<Settings>>,
+        path: Option<PathBuf>,
+    ) -> Self {
+        let window = Window::builder()
+            .title("Preferences")
+            .modal(true)
+            .transient_for(parent)
+            .default_width(420)
+            .resizable(false)
+            .build();
+
+        // Escape key handler
+        let key_controller = EventControllerKey::new();
+        let window_for_escape = window.clone();
+        key_controller.connect_key_pressed(move |_, key, _, _| {
+            if key == gdk::Key::Escape {
+                window_for_escape.close();
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+        window.add_controller(key_controller);
+
+        let grid = Grid::builder()
+            .column_spacing(24)
+            .row_spacing(12)
+            .margin_start(18)
+            .margin_end(18)
+            .margin_top(18)
+            .margin_bottom(18)
+            .build();
+
+        // Every row applies and saves the whole set of preferences
+        let apply: Rc<dyn Fn(Settings)> = {
+            let settings = settings.clone();
+            Rc::new(move |updated: Settings| {
+                settings.set(updated);
+                theme.apply(updated);
+                controller.set_settings(updated);
+                Self::save(&updated, path.as_deref());
+            })
+        };
+
+        let current = settings.get();
+        let rows: [SwitchRow; 11] = [
+            (
+                "High contrast",
+                "Stronger colours, borders and focus outlines",
+                current.high_contrast,
+                |p, value| p.high_contrast = value,
+            ),
+            (
+                "Reduce motion",
+                "Show and hide panels without animations",
+                current.reduce_motion,
+                |p, value| p.reduce_motion = value,
+            ),
+            (
+                "Reserved combos",
+                "Warn about bindings on combos Hyprland or common tools rely on",
+                current.reserved_combos,
+                |p, value| p.reserved_combos = value,
+            ),
+            (
+                "Review changes",
+                "Show what an edit changes in the config before saving it",
+                current.review_changes,
+                |p, value| p.review_changes = value,
+            ),
+            (
+                "Confirm deletions",
+                "Ask before deleting a keybinding",
+                current.confirm_delete,
+                |p, value| p.confirm_delete = value,
+            ),
+            (
+                "Apply edits live",
+                "Send each added, edited or deleted keybinding to the running Hyprland",
+                current.live_apply,
+                |p, value| p.live_apply = value,
+            ),
+            (
+                "Strict mode",
+                "Refuse changes while the config has lines the parser does not fully understand",
+                current.strict,
+                |p, value| p.strict = value,
+            ),
+            (
+                "Git backups",
+                "Commit each change to a git repository in the config folder",
+                current.backup_mode == BackupMode::Git,
+                |p, value| {
+                    p.backup_mode = if value {
+                        BackupMode::Git
+                    } else {
+                        BackupMode::Timestamped
+                    }
+                },
+            ),
+            (
+                "Notify on external changes",
+                "While watching, when another program changes the config",
+                current.notify_external_changes,
+                |p, value| p.notify_external_changes = value,
+            ),
+            (
+                "Notify on validation failures",
+                "While watching, when a changed config has problems",
+                current.notify_validation_failures,
+                |p, value| p.notify_validation_failures = value,
+            ),
+            (
+                "Notify on drift",
+                "While watching, when Hyprland's bindings differ from the config",
+                current.notify_drift,
+                |p, value| p.notify_drift = value,
+            ),
+        ];
+
+        let switch_rows = rows.len();
+        for (row, (title, subtitle, active, update)) in rows.into_iter().enumerate() {
+            let switch = Switch::builder()
+                .active(active)
+                .valign(Align::Center)
+                .build();
+            switch.update_property(&[gtk4::accessible::Property::Label(title)]);
+
+            let settings = settings.clone();
+            let apply = apply.clone();
+            switch.connect_active_notify(move |switch| {
+                let mut updated = settings.get();
+                update(&mut updated, switch.is_active());
+                apply(updated);
+            });
+
+            grid.attach(&Self::labels(title, subtitle), 0, row as i32, 1, 1);
+            grid.attach(&switch, 1, row as i32, 1, 1);
+        }
+
+        let retention = current.backup_retention;
+        let limits: [LimitRow; 3] = [
+            (
+                "Backups kept",
+                "Most timestamped backups kept; 0 keeps them all",
+                retention.max_count.map_or(0, |count| count as u64),
+                |policy, value| policy.max_count = (value > 0).then_some(value as usize),
+            ),
+            (
+                "Backup age limit",
+                "Days a backup is kept; 0 keeps them however old",
+                retention.max_age_days.map_or(0, u64::from),
+                |policy, value| policy.max_age_days = (value > 0).then_some(value as u32),
+            ),
+            (
+                "Backup size limit",
+                "Megabytes all backups may take; 0 for no limit",
+                retention
+                    .max_total_bytes
+                    .map_or(0, |bytes| bytes.div_ceil(BYTES_PER_MB)),
+                |policy, value| {
+                    policy.max_total_bytes = (value > 0).then_some(value * BYTES_PER_MB)
+                },
+            ),
+        ];
+
+        let limit_rows = limits.len();
+        for (offset, (title, subtitle, value, update)) in limits.into_iter().enumerate() {
+            let row = (switch_rows + offset) as i32;
+            let spin = SpinButton::with_range(0.0, 100_000.0, 1.0);
+            spin.set_value(value as f64);
+            spin.set_valign(Align::Center);
+            spin.update_property(&[gtk4::accessible::Property::Label(title)]);
+
+            let settings = settings.clone();
+            let apply = apply.clone();
+            spin.connect_value_changed(move |spin| {
+                let mut updated = settings.get();
+                update(
+                    &mut updated.backup_retention,
+                    spin.value_as_int().max(0) as u64,
+                );
+                apply(updated);
+            });
+
+            grid.attach(&Self::labels(title, subtitle), 0, row, 1, 1);
+            grid.attach(&spin, 1, row, 1, 1);
+        }
+
+        let choices = [
+            (
+                "Appearance",
+                "Light or dark, or whatever the desktop prefers",
+                Self::choice(
+                    "Appearance",
+                    &COLOR_SCHEMES,
+                    &settings,
+                    |p| p.color_scheme,
+                    |p, scheme| p.color_scheme = scheme,
+                    &apply,
+                ),
+            ),
+            (
+                "Block commands",
+                "Commands new and edited bindings may not run",
+                Self::choice(
+                    "Block commands",
+                    &DANGER_POLICIES,
+                    &settings,
+                    |p| p.danger_policy,
+                    |p, policy| p.danger_policy = policy,
+                    &apply,
+                ),
+            ),
+            (
+                "New binding type",
+                "Bind type of keybindings added with \"Add Keybinding\"",
+                Self::choice(
+                    "New binding type",
+                    &BIND_TYPES,
+                    &settings,
+                    |p| p.default_bind_type,
+                    |p, bind_type| p.default_bind_type = bind_type,
+                    &apply,
+                ),
+            ),
+            (
+                "Backup compression",
+                "Compress new timestamped backups; older ones stay as they are",
+                Self::choice(
+                    "Backup compression",
+                    &COMPRESSIONS,
+                    &settings,
+                    |p| p.backup_compression,
+                    |p, compression| p.backup_compression = compression,
+                    &apply,
+                ),
+            ),
+        ];
+
+        for (offset, (title, subtitle, drop_down)) in choices.into_iter().enumerate() {
+            let row = (switch_rows + limit_rows + offset) as i32;
+            grid.attach(&Self::labels(title, subtitle), 0, row, 1, 1);
+            grid.attach(&drop_down, 1, row, 1, 1);
+        }
+
+        window.set_child(Some(&grid));
+
+        Self { window }
+    }
+
+    /// Shows the dialog.
+    pub fn show(&self) {
+        self.window.present();
+    }
+
+    /// Builds the title and subtitle of a preference row
+    fn labels(title: &str, subtitle: &str) -> GtkBox {
+        let labels = GtkBox::new(Orientation::Vertical, 2);
+        labels.set_hexpand(true);
+        let title_label = Label::builder().label(title).halign(Align::Start).build();
+        title_label.add_css_class("field-header");
+        let subtitle_label = Label::builder()
+            .label(subtitle)
+            .halign(Align::Start)
+            .wrap(true)
+            .build();
+        subtitle_label.add_css_class("dim-label");
+        labels.append(&title_label);
+        labels.append(&subtitle_label);
+        labels
+    }
+
+    /// Builds a drop-down choosing one of `options` for a preference
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - Accessible label
+    /// * `options` - Values and their labels, in display order
+    /// * `get` - Reads the preference
+    /// * `set` - Changes the preference; the result is passed to `apply`
+    fn choice<T: Copy + PartialEq + 'static>(
+        title: &str,
+        options: &'static [(T, &'static str)],
+        settings: &Rc<Cell<Settings>>,
+        get: fn(&Settings) -> T,
+        set: fn(&mut Settings, T),
+        apply: &Rc<dyn Fn(Settings)>,
+    ) -> DropDown {
+        let labels: Vec<&str> = options.iter().map(|(_, label)| *label).collect();
+        let drop_down = DropDown::from_strings(&labels);
+        let current = get(&settings.get());
+        drop_down.set_selected(
+            options
+                .iter()
+                .position(|(option, _)| *option == current)
+                .unwrap_or(0) as u32,
+        );
+        drop_down.set_valign(Align::Center);
+        drop_down.update_property(&[gtk4::accessible::Property::Label(title)]);
+
+        let settings = settings.clone();
+        let apply = apply.clone();
+        drop_down.connect_selected_notify(move |drop_down| {
+            if let Some((option, _)) = options.get(drop_down.selected() as usize) {
+                let mut updated = settings.get();
+                set(&mut updated, *option);
+                apply(updated);
+            }
+        });
+        drop_down
+    }
+
+    /// Saves the preferences, if they are persisted
+    fn save(settings: &Settings, path: Option<&Path>) {
+        if let Some(path) = path {
+            if let Err(e) = settings.save(path) {
+                eprintln!("⚠️  Failed to save preferences: {}", e);
+            }
+        }
+    }
+}
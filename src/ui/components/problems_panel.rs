@@ -0,0 +1,195 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Config problems panel component
+//!
+//! Displays a banner below the conflict panel when the parser skipped lines
+//! of the config or only partially understood them, so a typo never makes
+//! a binding silently disappear from the list. Findings of the lint rules
+//! the lint policy does not allow are listed after them, followed by the
+//! differences from Hyprland's live bindings while those are compared.
+//!
+//! # Layout
+//!
+//! ```text
+//! ┌─────────────────────────────────────────────────────────────────┐
+//! │ ⚠️  2 config lines have problems (1 skipped), 1 lint  [Details] │
+//! │   hyprland.conf:12: Unknown bind flag 'x' in 'bindx'; ...       │
+//! │   hyprland.conf:30: Unknown modifier 'HYPER' was ignored        │
+//! │   SUPER+B: 'btop' runs in a terminal, ... [tui-without-terminal] │
+//! │   not in config: SUPER+F12 → exec notify-send                   │
+//! └─────────────────────────────────────────────────────────────────┘
+//! ```
+//!
+//! The list of problems is folded away until "Details" is pressed.
+
+use gtk4::{prelude::*, Box as GtkBox, Label, Orientation, Revealer, ToggleButton};
+
+use crate::core::{drift::Drift, lint::LintFinding, parser::ParseDiagnostic};
+
+/// Banner listing the parse diagnostics, lint findings and runtime drift of
+/// the loaded config
+pub struct ProblemsPanel {
+    /// Root widget (Revealer for smooth show/hide animation)
+    widget: Revealer,
+    /// Label with the number of problems
+    message_label: Label,
+    /// One line per diagnostic and finding, shown while "Details" is pressed
+    details_label: Label,
+}
+
+impl ProblemsPanel {
+    /// Creates a new, initially hidden problems panel
+    ///
+    /// Call `refresh()` with the controller's diagnostics, lint findings and
+    /// drift whenever the bindings change.
+    pub fn new() -> Self {
+        let revealer = Revealer::builder()
+            .transition_type(gtk4::RevealerTransitionType::SlideDown)
+            .transition_duration(300)
+            .reveal_child(false)
+            .build();
+
+        let banner = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(4)
+            .margin_start(10)
+            .margin_end(10)
+            .margin_top(5)
+            .margin_bottom(5)
+            .hexpand(true)
+            .build();
+        banner.add_css_class("warning-banner");
+
+        let header = GtkBox::new(Orientation::Horizontal, 10);
+
+        let message_label = Label::builder()
+            .xalign(0.0)
+            .margin_start(10)
+            .margin_top(5)
+            .margin_bottom(5)
+            .build();
+
+        let spacer = GtkBox::new(Orientation::Horizontal, 0);
+        spacer.set_hexpand(true);
+
+        let details_button = ToggleButton::with_label("Details");
+
+        header.append(&message_label);
+        header.append(&spacer);
+        header.append(&details_button);
+
+        let details_label = Label::builder()
+            .xalign(0.0)
+            .selectable(true)
+            .wrap(true)
+            .margin_start(20)
+            .margin_end(10)
+            .margin_bottom(5)
+            .build();
+
+        let details_revealer = Revealer::builder()
+            .transition_type(gtk4::RevealerTransitionType::SlideDown)
+            .reveal_child(false)
+            .child(&details_label)
+            .build();
+
+        details_button
+            .bind_property("active", &details_revealer, "reveal-child")
+            .sync_create()
+            .build();
+
+        banner.append(&header);
+        banner.append(&details_revealer);
+        revealer.set_child(Some(&banner));
+
+        Self {
+            widget: revealer,
+            message_label,
+            details_label,
+        }
+    }
+
+    /// Shows the diagnostics, findings and drift, or hides the panel when
+    /// there are none
+    ///
+    /// # Arguments
+    /// * `diagnostics` - Diagnostics of the last load
+    /// * `lints` - Findings of the lint rules, see [`crate::core::lint`]
+    /// * `drift` - Differences from Hyprland's live bindings, if compared
+    pub fn refresh(
+        &self,
+        diagnostics: &[ParseDiagnostic],
+        lints: &[LintFinding],
+        drift: Option<&Drift>,
+    ) {
+        let drift = drift.filter(|drift| !drift.is_empty());
+        if diagnostics.is_empty() && lints.is_empty() && drift.is_none() {
+            self.widget.set_reveal_child(false);
+            self.details_label.set_label("");
+            return;
+        }
+
+        let mut parts = Vec::new();
+        if !diagnostics.is_empty() {
+            let skipped = diagnostics.iter().filter(|d| d.is_skipped_line()).count();
+            let mut part = if diagnostics.len() == 1 {
+                "1 config line has a problem".to_string()
+            } else {
+                format!("{} config lines have problems", diagnostics.len())
+            };
+            if skipped > 0 {
+                part.push_str(&format!(" ({} skipped)", skipped));
+            }
+            parts.push(part);
+        }
+        if !lints.is_empty() {
+            parts.push(format!(
+                "{} lint{}",
+                lints.len(),
+                if lints.len() == 1 { "" } else { "s" }
+            ));
+        }
+        if let Some(drift) = drift {
+            let differences = drift.missing.len() + drift.unexpected.len();
+            parts.push(format!(
+                "{} difference{} from Hyprland",
+                differences,
+                if differences == 1 { "" } else { "s" }
+            ));
+        }
+        self.message_label
+            .set_label(&format!("⚠️  {}", parts.join(", ")));
+
+        let details: Vec<String> = diagnostics
+            .iter()
+            .map(ToString::to_string)
+            .chain(lints.iter().map(ToString::to_string))
+            .chain(drift.map(Drift::describe).unwrap_or_default())
+            .collect();
+        self.details_label.set_label(&details.join("\n"));
+        self.widget.set_reveal_child(true);
+    }
+
+    /// Returns the root widget for adding to a container
+    pub fn widget(&self) -> &Revealer {
+        &self.widget
+    }
+}
+
+impl Default for ProblemsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use gtk4::{
+    gdk, prelude::*, Align, Box as GtkBox, Button, EventControllerKey, Label, Orientation,
+    ScrolledWindow, Window,
+};
+use std::{cell::Cell, rc::Rc};
+
+use crate::config::diff::{ConfigDiff, LineChange};
+
+/// Dialog showing what a save will change in the config, before writing.
+///
+/// The change is shown as a unified diff: removed lines in red, added
+/// lines in green, with a few unchanged lines around each change. Nothing
+/// is written until **Save**; **Cancel** (or Escape) drops the change.
+/// Shown after the edit dialog while the "Review changes" preference is on.
+pub struct ReviewDialog {
+    window: Window,
+    /// `Some(true)` once saved, `Some(false)` once cancelled
+    response: Rc<Cell<Option<bool>>>,
+}
+
+impl ReviewDialog {
+    /// Creates a new review dialog.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Parent window for modal behaviour
+    /// * `file_name` - Config file the change is written to
+    /// * `diff` - The change, from the controller's preview
+    pub fn new(parent: &impl IsA<Window>, file_name: &str, diff: &ConfigDiff) -> Self {
+        let window = Window::builder()
+            .title("Review Changes")
+            .modal(true)
+            .transient_for(parent)
+            .default_width(720)
+            .default_height(420)
+            .build();
+
+        let response = Rc::new(Cell::new(None));
+
+        // Escape key handler
+        let key_controller = EventControllerKey::new();
+        let window_for_escape = window.clone();
+        let response_for_escape = response.clone();
+        key_controller.connect_key_pressed(move |_, key, _, _| {
+            if key == gdk::Key::Escape {
+                response_for_escape.set(Some(false));
+                window_for_escape.close();
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+        window.add_controller(key_controller);
+
+        let main_vbox = GtkBox::new(Orientation::Vertical, 12);
+        main_vbox.set_margin_start(12);
+        main_vbox.set_margin_end(12);
+        main_vbox.set_margin_top(12);
+        main_vbox.set_margin_bottom(12);
+
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        let summary = Label::new(Some(&if diff.is_empty() {
+            format!("Saving leaves {} unchanged.", file_name)
+        } else {
+            format!(
+                "Saving changes {}: {} line{} removed, {} added.",
+                file_name,
+                diff.removed(),
+                plural(diff.removed()),
+                diff.added()
+            )
+        }));
+        summary.set_halign(Align::Start);
+        summary.set_wrap(true);
+        main_vbox.append(&summary);
+
+        main_vbox.append(&diff_view(diff));
+
+        // Button row
+        let button_box = GtkBox::new(Orientation::Horizontal, 12);
+        button_box.set_halign(Align::End);
+
+        let cancel_button = Button::builder().label("Cancel").build();
+        let save_button = Button::builder().label("Save").build();
+        save_button.add_css_class("suggested-action");
+        button_box.append(&cancel_button);
+        button_box.append(&save_button);
+        main_vbox.append(&button_box);
+
+        let window_for_cancel = window.clone();
+        let response_for_cancel = response.clone();
+        cancel_button.connect_clicked(move |_| {
+            response_for_cancel.set(Some(false));
+            window_for_cancel.close();
+        });
+
+        let window_for_save = window.clone();
+        let response_for_save = response.clone();
+        save_button.connect_clicked(move |_| {
+            response_for_save.set(Some(true));
+            window_for_save.close();
+        });
+
+        window.set_child(Some(&main_vbox));
+        window.set_default_widget(Some(&save_button));
+
+        Self { window, response }
+    }
+
+    /// Shows the dialog and blocks until it is answered.
+    ///
+    /// # Returns
+    ///
+    /// Whether the change should be saved (closing the window cancels)
+    pub fn show_and_wait(self) -> bool {
+        self.window.present();
+
+        let main_context = glib::MainContext::default();
+        while self.response.get().is_none() && self.window.is_visible() {
+            main_context.iteration(true);
+        }
+
+        self.response.get().unwrap_or(false)
+    }
+}
+
+/// Builds the scrollable unified view of a diff
+///
+/// Each hunk starts with its `@@` header; removed lines are red, added
+/// lines green. Shared with the backup dialog's restore preview.
+///
+/// # Arguments
+///
+/// * `diff` - The change to show
+pub(crate) fn diff_view(diff: &ConfigDiff) -> ScrolledWindow {
+    let lines_box = GtkBox::new(Orientation::Vertical, 0);
+    for hunk in diff.hunks() {
+        let header = Label::new(Some(&hunk.header()));
+        header.set_halign(Align::Start);
+        header.add_css_class("diff-line");
+        header.add_css_class("diff-hunk");
+        header.set_margin_top(6);
+        lines_box.append(&header);
+
+        for line in &hunk.lines {
+            let (prefix, class) = match line.change {
+                LineChange::Unchanged => (' ', None),
+                LineChange::Removed => ('-', Some("compare-removed")),
+                LineChange::Added => ('+', Some("compare-added")),
+            };
+            let label = Label::new(Some(&format!("{}{}", prefix, line.text)));
+            label.set_halign(Align::Fill);
+            label.set_xalign(0.0);
+            label.set_selectable(true);
+            label.add_css_class("diff-line");
+            if let Some(class) = class {
+                label.add_css_class(class);
+            }
+            lines_box.append(&label);
+        }
+    }
+
+    let scrolled_window = ScrolledWindow::builder().vexpand(true).build();
+    scrolled_window.set_child(Some(&lines_box));
+    scrolled_window
+}
@@ -0,0 +1,105 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scope switcher component
+//!
+//! Drop-down that previews the bindings active in one scope (see
+//! [`crate::core::scope`]). Hidden while the config declares no scopes.
+
+use gtk4::{prelude::*, Box as GtkBox, DropDown, Label, Orientation, StringList};
+use std::{cell::RefCell, rc::Rc};
+
+use crate::ui::Controller;
+
+/// First entry of the drop-down: no scope selected
+const ALL_SCOPES: &str = "All scopes";
+
+/// Drop-down for previewing one scope's bindings
+pub struct ScopeSwitcher {
+    /// Root widget (label and drop-down)
+    widget: GtkBox,
+    /// The drop-down itself
+    drop_down: DropDown,
+    /// Controller reference for the scope list
+    controller: Rc<Controller>,
+    /// Scopes currently offered, in drop-down order after "All scopes"
+    scopes: Rc<RefCell<Vec<String>>>,
+}
+
+impl ScopeSwitcher {
+    /// Creates the switcher, offering the scopes the controller knows
+    ///
+    /// Returns just the widget - parent is responsible for wiring up
+    /// [`connect_scope_changed`](Self::connect_scope_changed).
+    pub fn new(controller: Rc<Controller>) -> Self {
+        let widget = GtkBox::new(Orientation::Horizontal, 8);
+        let label = Label::new(Some("Preview scope:"));
+        let drop_down = DropDown::from_strings(&[ALL_SCOPES]);
+        drop_down.set_tooltip_text(Some(
+            "Show only global bindings and those of one scope; exports follow the preview",
+        ));
+        drop_down.set_can_focus(true);
+        widget.append(&label);
+        widget.append(&drop_down);
+
+        let switcher = Self {
+            widget,
+            drop_down,
+            controller,
+            scopes: Rc::new(RefCell::new(Vec::new())),
+        };
+        switcher.refresh();
+        switcher
+    }
+
+    /// Returns the root widget for adding to parent container
+    pub fn widget(&self) -> &GtkBox {
+        &self.widget
+    }
+
+    /// Calls `callback` with the newly selected scope (`None`: all scopes)
+    pub fn connect_scope_changed<F: Fn(Option<String>) + 'static>(&self, callback: F) {
+        let scopes = self.scopes.clone();
+        self.drop_down.connect_selected_notify(move |drop_down| {
+            let selected = drop_down.selected() as usize;
+            let scope = selected
+                .checked_sub(1)
+                .and_then(|index| scopes.borrow().get(index).cloned());
+            callback(scope);
+        });
+    }
+
+    /// Offers the scopes of the config as loaded now
+    ///
+    /// The selected scope stays selected if the config still declares it,
+    /// otherwise the switcher goes back to all scopes.
+    pub fn refresh(&self) {
+        let scopes = self.controller.get_scopes();
+        self.widget.set_visible(!scopes.is_empty());
+        if *self.scopes.borrow() == scopes {
+            return;
+        }
+
+        let selected = self.controller.get_scope();
+        *self.scopes.borrow_mut() = scopes.clone();
+        let mut names = vec![ALL_SCOPES];
+        names.extend(scopes.iter().map(String::as_str));
+        self.drop_down.set_model(Some(&StringList::new(&names)));
+
+        let position = selected
+            .and_then(|scope| scopes.iter().position(|name| *name == scope))
+            .map_or(0, |index| index + 1);
+        self.drop_down.set_selected(position as u32);
+    }
+}
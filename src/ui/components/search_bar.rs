@@ -55,7 +55,7 @@ impl SearchBar {
             .placeholder_text("Search keybindings...")
             .build();
         widget.set_tooltip_text(Some(
-            "Filter keybindings by combo, dispatcher, or arguments",
+            "Fuzzy search by combo, dispatcher, arguments or description",
         ));
         widget.set_can_focus(true);
 
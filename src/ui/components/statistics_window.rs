@@ -0,0 +1,189 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Statistics window
+//!
+//! The GUI side of `hypr-keybind-manager stats`: how the bindings spread
+//! over modifiers, dispatchers and submaps, how full each common modifier
+//! layer is, and which bindings have the longest arguments. See
+//! [`crate::core::stats`] for what is counted.
+
+use gtk4::{
+    gdk, prelude::*, Align, Box as GtkBox, Button, EventControllerKey, Grid, Label, LevelBar,
+    Orientation, ScrolledWindow, Window,
+};
+
+use crate::core::{
+    key_suggestions::CANDIDATE_KEYS,
+    stats::{BindingStats, Tally},
+};
+
+/// Window showing statistics about the loaded bindings
+pub struct StatisticsWindow {
+    window: Window,
+}
+
+impl StatisticsWindow {
+    /// Creates the statistics window.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Window the statistics belong to (not modal)
+    /// * `stats` - The statistics to show
+    pub fn new(parent: &Window, stats: &BindingStats) -> Self {
+        let window = Window::builder()
+            .title("Statistics")
+            .transient_for(parent)
+            .default_width(560)
+            .default_height(640)
+            .build();
+
+        // Escape key handler
+        let key_controller = EventControllerKey::new();
+        let window_for_escape = window.clone();
+        key_controller.connect_key_pressed(move |_, key, _, _| {
+            if key == gdk::Key::Escape {
+                window_for_escape.close();
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        });
+        window.add_controller(key_controller);
+
+        let content = GtkBox::new(Orientation::Vertical, 6);
+        content.set_margin_start(18);
+        content.set_margin_end(18);
+        content.set_margin_top(18);
+        content.set_margin_bottom(18);
+
+        let summary = Label::new(Some(&format!(
+            "{} bindings, {} with a description",
+            stats.total, stats.described
+        )));
+        summary.set_halign(Align::Start);
+        summary.add_css_class("title-3");
+        content.append(&summary);
+
+        for (title, tallies) in [
+            ("By modifiers", &stats.by_modifiers),
+            ("By dispatcher", &stats.by_dispatcher),
+            ("By submap", &stats.by_submap),
+        ] {
+            content.append(&heading(title));
+            content.append(&tally_grid(tallies));
+        }
+
+        content.append(&heading("Free keys"));
+        let note = Label::new(Some("Letters, digits and F1-F12, outside submaps"));
+        note.set_halign(Align::Start);
+        note.add_css_class("dim-label");
+        content.append(&note);
+        let space_grid = grid();
+        for (row, space) in stats.modifier_space.iter().enumerate() {
+            let level = LevelBar::for_interval(0.0, CANDIDATE_KEYS.len() as f64);
+            level.set_value(space.used as f64);
+            level.set_hexpand(true);
+            level.set_valign(Align::Center);
+            level.set_tooltip_text(Some(&format!("{} used", space.used)));
+            space_grid.attach(&cell(&space.modifiers), 0, row as i32, 1, 1);
+            space_grid.attach(&level, 1, row as i32, 1, 1);
+            space_grid.attach(&cell(&format!("{} free", space.free)), 2, row as i32, 1, 1);
+        }
+        content.append(&space_grid);
+
+        if !stats.longest_args.is_empty() {
+            content.append(&heading("Longest arguments"));
+            let args_grid = grid();
+            for (row, binding) in stats.longest_args.iter().enumerate() {
+                let args = binding.args.as_deref().unwrap_or_default();
+                let command = cell(&format!("{} {}", binding.dispatcher, args));
+                command.set_wrap(true);
+                command.set_selectable(true);
+                command.set_hexpand(true);
+                args_grid.attach(&cell(&binding.key_combo.to_string()), 0, row as i32, 1, 1);
+                args_grid.attach(&command, 1, row as i32, 1, 1);
+                args_grid.attach(
+                    &cell(&args.chars().count().to_string()),
+                    2,
+                    row as i32,
+                    1,
+                    1,
+                );
+            }
+            content.append(&args_grid);
+        }
+
+        let scroller = ScrolledWindow::builder()
+            .vexpand(true)
+            .child(&content)
+            .build();
+
+        let close_button = Button::builder()
+            .label("Close")
+            .halign(Align::End)
+            .margin_end(12)
+            .margin_bottom(12)
+            .build();
+        let window_for_close = window.clone();
+        close_button.connect_clicked(move |_| window_for_close.close());
+
+        let main_vbox = GtkBox::new(Orientation::Vertical, 6);
+        main_vbox.append(&scroller);
+        main_vbox.append(&close_button);
+        window.set_child(Some(&main_vbox));
+        window.set_default_widget(Some(&close_button));
+
+        Self { window }
+    }
+
+    /// Shows the window.
+    pub fn show(&self) {
+        self.window.present();
+    }
+}
+
+/// Section heading
+fn heading(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.set_halign(Align::Start);
+    label.set_margin_top(12);
+    label.add_css_class("heading");
+    label
+}
+
+/// Grid for one section's rows
+fn grid() -> Grid {
+    Grid::builder().column_spacing(18).row_spacing(4).build()
+}
+
+/// Left-aligned grid cell
+fn cell(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.set_halign(Align::Start);
+    label.set_xalign(0.0);
+    label
+}
+
+/// Rows of names and counts
+fn tally_grid(tallies: &[Tally]) -> Grid {
+    let grid = grid();
+    for (row, tally) in tallies.iter().enumerate() {
+        grid.attach(&cell(&tally.name), 0, row as i32, 1, 1);
+        let count = cell(&tally.count.to_string());
+        count.set_halign(Align::End);
+        grid.attach(&count, 1, row as i32, 1, 1);
+    }
+    grid
+}
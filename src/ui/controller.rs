@@ -26,28 +26,85 @@
 //!
 //! The Controller holds references to Model components but doesn't know
 //! about GTK4 widgets. This keeps business logic separate from presentation.
+//!
+//! # Observers
+//!
+//! Views (and programs embedding the controller) register callbacks with
+//! [`Controller::on_bindings_changed`], [`Controller::on_conflicts_changed`]
+//! and [`Controller::on_diagnostics`] instead of refreshing themselves after
+//! every call that might have changed something.
 
 use std::{
-    cell::RefCell,
-    fs,
-    fs::read_to_string,
+    cell::{Cell, OnceCell, RefCell},
+    collections::BTreeSet,
+    fs::{self, read_to_string},
     path::{Path, PathBuf},
-    process::Command,
     rc::Rc,
+    time::{Duration, Instant},
 };
 
-use crate::config::{validator::ConfigValidator, ConfigError, ConfigManager};
+use chrono::Local;
+
+use crate::config::{
+    audit::{AuditLog, HealthTrend},
+    backup::{self, BackupCompression, BackupMode, RetentionPolicy},
+    danger::{patterns, DangerAssessment, DangerDetector, DangerLevel},
+    diff::ConfigDiff,
+    export::ExportFormat,
+    health::{assess_health, executable_on_path, HealthReport},
+    import_review::ImportReview,
+    interop::ForeignConfig,
+    overrides::READ_ONLY_ANNOTATION,
+    profiles::ProfileStore,
+    resolve::{self, CommandResolver, MissingProgram},
+    settings::Settings,
+    validator::ConfigValidator,
+    ConfigError, ConfigManager,
+};
+use crate::core::binds_options::{BindsOption, BindsOptions};
+use crate::core::cheatsheet::CheatSheetFormat;
+use crate::core::compare::{self, ComparisonRow, Side};
+use crate::core::compat::{self, HyprlandVersion};
+use crate::core::dispatchers::{self, DispatcherSpec, DISPATCHER_CATALOGUE};
+use crate::core::drift::{find_drift, Drift};
+use crate::core::environment::{Devices, WindowInfo};
+use crate::core::equivalence;
+use crate::core::grouping::{self, GroupSuggestion};
+use crate::core::help::HelpIndex;
+use crate::core::key_suggestions::KeySuggestionEngine;
+use crate::core::layouts::{configured_layouts, layout_warnings, LayoutWarning};
+use crate::core::lint::{lint_bindings, LintFinding, LintPolicy};
+use crate::core::palette::search_bindings;
+use crate::core::reserved::{self, ReservedShadow};
+use crate::core::scope::ScopeIndex;
+use crate::core::stats::BindingStats;
+use crate::core::submap::{submap_warnings, SubmapWarning};
+use crate::core::workspace::WorkspaceInfo;
 use crate::core::{
-    parser::parse_config_file, validator as injection_validator, Conflict, ConflictDetector,
+    parser::{parse_config_file, parse_config_report, ParseDiagnostic},
+    validator as injection_validator, Conflict, ConflictDetector, ConflictKind, DuplicateAction,
     KeyCombo, Keybinding, Modifier,
 };
+use crate::ipc::{
+    debounce::ReloadDebouncer,
+    events::HyprlandEvent,
+    instance::{HyprlandInstance, InstanceInfo, InstanceLocator},
+    temporary::TemporaryBind,
+    ClientMode, HyprlandClient,
+};
+use crate::ui::undo::UndoManager;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum ImportMode {
-    /// Replace all existing bindings with imported ones
-    Replace,
-    /// Merge imported bindings with existing (skip duplicates)
-    Merge,
+pub use crate::config::import_review::ImportMode;
+
+/// Result of polling a debounced apply
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApplyPoll {
+    /// No apply has been requested
+    Idle,
+    /// An apply is pending; poll again after this long
+    Waiting(Duration),
+    /// The burst went quiet and Hyprland was reloaded once
+    Applied(Result<(), String>),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -63,6 +120,58 @@ pub struct KeyComboAssistance {
     pub suggestions: Vec<KeyCombo>,
 }
 
+/// What saving a binding would conflict with, previewed while editing
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConflictPreview {
+    /// Bindings already on the combo (empty when it is free)
+    pub bindings: Vec<Keybinding>,
+    /// How the conflict would be classified, `None` when the combo is free
+    pub kind: Option<ConflictKind>,
+    /// What the combo is reserved for, when the binding does something else
+    pub reserved: Option<ReservedShadow>,
+}
+
+/// Issue summary shown as badges on a keybinding list row
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BindingBadges {
+    /// Number of other bindings sharing this key combo
+    pub conflict_count: usize,
+    /// Danger level of the command, only set when above `Safe`
+    pub danger_level: Option<DangerLevel>,
+}
+
+/// How [`Controller::resolve_conflict`] settles a conflict
+///
+/// "First" and "last" are in config order, which is the order of
+/// [`Conflict::conflicting_bindings`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConflictResolution {
+    /// Keep the binding written first and delete the others
+    KeepFirst,
+    /// Keep the binding written last and delete the others
+    KeepLast,
+    /// Move one binding of the conflict to another key combo
+    Rebind {
+        /// Index of the binding to move in `conflicting_bindings`
+        index: usize,
+        /// Its new key combo, which must be free in its submap
+        to: KeyCombo,
+    },
+    /// Delete every binding of the conflict (both, for the usual pair)
+    DeleteAll,
+}
+
+/// A callback told about a changed list of `T`
+type Observer<T> = Box<dyn Fn(&[T])>;
+
+/// Callbacks registered through the `on_*` observer methods
+#[derive(Default)]
+struct Observers {
+    bindings_changed: Vec<Observer<Keybinding>>,
+    conflicts_changed: Vec<Observer<Conflict>>,
+    diagnostics: Vec<Observer<ParseDiagnostic>>,
+}
+
 /// MVC Controller coordinating Model and View
 ///
 /// Holds shared references to Model components and provides
@@ -76,10 +185,55 @@ pub struct Controller {
     conflict_detector: RefCell<ConflictDetector>,
     /// Current search query (for preserving filters state)
     current_search_query: RefCell<String>,
-    /// Undo history of complete binding snapshots
-    undo_stack: RefCell<Vec<Vec<Keybinding>>>,
-    /// Redo history of complete binding snapshots
-    redo_stack: RefCell<Vec<Vec<Keybinding>>>,
+    /// When set, the view only shows bindings whose action another combo
+    /// also performs
+    duplicate_actions_only: Cell<bool>,
+    /// Scope sections of the loaded config files
+    scope_index: RefCell<ScopeIndex>,
+    /// Scope being previewed (`None`: all bindings, whatever their scope)
+    selected_scope: RefCell<Option<String>>,
+    /// Category the view is limited to (`None`: every category)
+    selected_category: RefCell<Option<String>>,
+    /// Undo and redo history of complete binding snapshots
+    history: RefCell<UndoManager>,
+    /// Non-fatal parse diagnostics from the last load
+    parse_diagnostics: RefCell<Vec<ParseDiagnostic>>,
+    /// When set, writes are refused while parse diagnostics exist
+    strict_mode: Cell<bool>,
+    /// Whether bindings on reserved combos are flagged
+    reserved_check: Cell<bool>,
+    /// Whether the GUI shows a diff before saving an edit
+    review_changes: Cell<bool>,
+    /// Level of each lint rule
+    lint_policy: RefCell<LintPolicy>,
+    /// Whether the bindings are compared with Hyprland's live bindings
+    runtime_check: Cell<bool>,
+    /// Mode of the IPC client for requests that change Hyprland
+    client_mode: Cell<ClientMode>,
+    /// Hyprland instance to talk to; `None` follows the environment
+    instance: RefCell<Option<HyprlandInstance>>,
+    /// Settings last applied with `set_settings`
+    settings: Cell<Settings>,
+    /// Danger detector (patterns compiled once, reused for every row)
+    danger_detector: DangerDetector,
+    /// Binding the user chose to save despite the danger policy; taken by
+    /// the next danger check
+    danger_override: RefCell<Option<Keybinding>>,
+    /// Coalesces bursts of apply requests into a single reload
+    reload_debouncer: RefCell<ReloadDebouncer>,
+    /// Version of the running Hyprland, queried on first use and
+    /// forgotten when another instance is selected
+    hyprland_version: Cell<Option<Option<HyprlandVersion>>>,
+    /// Where `exec` targets are looked up, read from the environment on first use
+    command_resolver: OnceCell<CommandResolver>,
+    /// `binds` options set by the main config, as of the last load
+    binds_options: RefCell<BindsOptions>,
+    /// Keyboard layouts of `kb_layout`, as of the last load
+    keyboard_layouts: RefCell<Vec<String>>,
+    /// Callbacks told about model changes
+    observers: RefCell<Observers>,
+    /// Conflicts as last reported to `conflicts_changed` observers
+    reported_conflicts: RefCell<Vec<Conflict>>,
 }
 
 const HISTORY_LIMIT: usize = 20;
@@ -118,8 +272,29 @@ impl Controller {
             keybindings: RefCell::new(Vec::new()),
             conflict_detector: RefCell::new(ConflictDetector::new()),
             current_search_query: RefCell::new(String::new()),
-            undo_stack: RefCell::new(Vec::new()),
-            redo_stack: RefCell::new(Vec::new()),
+            duplicate_actions_only: Cell::new(false),
+            scope_index: RefCell::new(ScopeIndex::default()),
+            selected_scope: RefCell::new(None),
+            selected_category: RefCell::new(None),
+            history: RefCell::new(UndoManager::new(HISTORY_LIMIT)),
+            parse_diagnostics: RefCell::new(Vec::new()),
+            strict_mode: Cell::new(false),
+            reserved_check: Cell::new(true),
+            review_changes: Cell::new(true),
+            lint_policy: RefCell::new(LintPolicy::default()),
+            runtime_check: Cell::new(false),
+            client_mode: Cell::new(ClientMode::DryRun),
+            instance: RefCell::new(None),
+            settings: Cell::new(Settings::default()),
+            danger_detector: DangerDetector::with_installed_rules(),
+            danger_override: RefCell::new(None),
+            reload_debouncer: RefCell::new(ReloadDebouncer::default()),
+            hyprland_version: Cell::new(None),
+            command_resolver: OnceCell::new(),
+            binds_options: RefCell::new(BindsOptions::default()),
+            keyboard_layouts: RefCell::new(Vec::new()),
+            observers: RefCell::new(Observers::default()),
+            reported_conflicts: RefCell::new(Vec::new()),
         })
     }
 
@@ -132,11 +307,16 @@ impl Controller {
     ///
     /// This reads the config file, parses all keybindings, and rebuilds
     /// the conflict detector. Call this on startup and after config changes.
+    /// Observers are then told about the diagnostics and the bindings.
+    ///
+    /// Lines that do not parse are skipped and recorded as diagnostics
+    /// rather than failing the load; strict mode then refuses writes until
+    /// they are fixed.
     ///
     /// # Returns
     ///
     /// * `Ok(usize)` - Number of keybindings loaded
-    /// * `Err(ConfigError)` - Failed to read or parse config
+    /// * `Err(ConfigError)` - Failed to read config
     ///
     /// # Example
     ///
@@ -153,33 +333,380 @@ impl Controller {
         let config_manager = self.config_manager.borrow();
         let content = config_manager.read_config()?;
 
-        // Parse keybindings using existing parser
-        let bindings = parse_config_file(&content, Path::new(""))
-            .map_err(|e| ConfigError::ValidationFailed(e.to_string()))?;
+        // Parse keybindings, following `source = path` includes
+        let report = parse_config_report(&content, config_manager.config_path());
+        let bindings = report.bindings;
 
         let count = bindings.len();
+        drop(config_manager);
+
+        // Record anything the parser skipped or only partially understood
+        *self.parse_diagnostics.borrow_mut() = report.diagnostics;
+        *self.binds_options.borrow_mut() = BindsOptions::from_config(&content);
+        *self.keyboard_layouts.borrow_mut() = configured_layouts(&content);
+        *self.scope_index.borrow_mut() = ScopeIndex::from_bindings(&bindings);
 
         // Store keybindings
         *self.keybindings.borrow_mut() = bindings.clone();
 
         // Rebuild conflict detector
         let mut detector = ConflictDetector::new();
+        detector.set_check_reserved(self.reserved_check.get());
         for binding in bindings {
             detector.add_binding(binding);
         }
         *self.conflict_detector.borrow_mut() = detector;
 
+        let diagnostics = self.get_parse_diagnostics();
+        for callback in self.observers.borrow().diagnostics.iter() {
+            callback(&diagnostics);
+        }
+        self.notify_bindings_changed();
+
         Ok(count)
     }
 
-    fn record_undo_snapshot(&self) {
-        let snapshot = self.keybindings.borrow().clone();
-        let mut undo_stack = self.undo_stack.borrow_mut();
-        undo_stack.push(snapshot);
-        if undo_stack.len() > HISTORY_LIMIT {
-            undo_stack.remove(0);
+    /// Enables or disables strict mode
+    ///
+    /// In strict mode every write operation (add, update, delete, import,
+    /// undo and redo) is refused while the loaded config has parse
+    /// diagnostics. The rebuild logic rewrites every bind line, so a line the
+    /// parser only partially understood would otherwise be written back in
+    /// its misread form.
+    ///
+    /// Restoring a backup is still allowed, as it copies the file verbatim
+    /// and is often how users get back to a clean state.
+    ///
+    /// The `strict` preference turns strict mode on too, whatever is set
+    /// here.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict` - Whether writes should be blocked by diagnostics
+    pub fn set_strict_mode(&self, strict: bool) {
+        self.strict_mode.set(strict);
+    }
+
+    /// Returns whether strict mode is enabled, by `--strict` or the
+    /// preferences
+    pub fn is_strict_mode(&self) -> bool {
+        self.strict_mode.get() || self.settings.get().strict
+    }
+
+    /// Enables or disables flagging bindings on reserved combos
+    ///
+    /// On by default; the GUI follows the "Reserved combos" preference.
+    pub fn set_reserved_check(&self, enabled: bool) {
+        self.reserved_check.set(enabled);
+        self.conflict_detector
+            .borrow_mut()
+            .set_check_reserved(enabled);
+        self.notify_conflicts_changed();
+    }
+
+    /// Sets the level of each lint rule
+    ///
+    /// The defaults until set; the GUI reads the lint policy file on start.
+    pub fn set_lint_policy(&self, policy: LintPolicy) {
+        *self.lint_policy.borrow_mut() = policy;
+        self.notify_bindings_changed();
+    }
+
+    /// Runs the lint rules over the loaded bindings
+    ///
+    /// See [`crate::core::lint`]. Rules the policy allows are skipped.
+    pub fn get_lint_findings(&self) -> Vec<LintFinding> {
+        let content = self
+            .config_manager
+            .borrow()
+            .read_config()
+            .unwrap_or_default();
+        lint_bindings(
+            &content,
+            &self.keybindings.borrow(),
+            &self.lint_policy.borrow(),
+        )
+    }
+
+    /// Enables or disables comparing the bindings with Hyprland's
+    ///
+    /// Off by default; the GUI follows the "Compare with running Hyprland"
+    /// toggle.
+    pub fn set_runtime_check(&self, enabled: bool) {
+        self.runtime_check.set(enabled);
+    }
+
+    /// Compares the loaded bindings with the ones Hyprland has loaded
+    ///
+    /// Queries `hyprctl binds` over read-only IPC, see
+    /// [`crate::core::drift`].
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Drift)` - The differences, empty when both agree
+    /// * `None` - The comparison is off, or Hyprland did not answer
+    pub fn get_runtime_drift(&self) -> Option<Drift> {
+        if !self.runtime_check.get() {
+            return None;
+        }
+        let live = self.hyprland_client(ClientMode::ReadOnly).binds().ok()?;
+        Some(find_drift(&self.keybindings.borrow(), &live))
+    }
+
+    /// Enables or disables reviewing changes before they are saved
+    ///
+    /// On by default; the GUI follows the "Review changes" preference.
+    pub fn set_review_changes(&self, enabled: bool) {
+        self.review_changes.set(enabled);
+    }
+
+    /// Returns whether edits are shown as a diff before they are saved
+    pub fn reviews_changes(&self) -> bool {
+        self.review_changes.get()
+    }
+
+    /// Records the configs written in an application state file
+    ///
+    /// The GUI uses the XDG state file; without one, the pristine backup is
+    /// only guarded by its own presence.
+    ///
+    /// # Arguments
+    ///
+    /// * `state_path` - Location of the state file
+    pub fn set_state_path(&self, state_path: PathBuf) {
+        self.config_manager.borrow_mut().set_state_path(state_path);
+    }
+
+    /// Chooses how the config is backed up before each write
+    ///
+    /// The GUI follows the "Git backups" preference.
+    pub fn set_backup_mode(&self, mode: BackupMode) {
+        self.config_manager.borrow_mut().set_backup_mode(mode);
+    }
+
+    /// Chooses how new timestamped backups are compressed
+    ///
+    /// The GUI follows the backup compression preference.
+    pub fn set_backup_compression(&self, compression: BackupCompression) {
+        self.config_manager
+            .borrow_mut()
+            .set_backup_compression(compression);
+    }
+
+    /// Chooses which backups are kept after each write
+    ///
+    /// The GUI follows the backup retention preferences.
+    pub fn set_backup_retention(&self, retention: RetentionPolicy) {
+        self.config_manager.borrow_mut().set_retention(retention);
+    }
+
+    /// Applies the user's preferences
+    ///
+    /// Sets the reserved combo check, change reviews and backups as the
+    /// preferences say, and keeps them for [`Self::settings`]. The
+    /// defaults until set; the GUI applies the saved preferences on start
+    /// and again whenever the preferences dialog changes one.
+    pub fn set_settings(&self, settings: Settings) {
+        self.settings.set(settings);
+        self.set_reserved_check(settings.reserved_combos);
+        self.set_review_changes(settings.review_changes);
+        self.set_backup_mode(settings.backup_mode);
+        self.set_backup_compression(settings.backup_compression);
+        self.set_backup_retention(settings.backup_retention);
+    }
+
+    /// Returns the preferences last applied with [`Self::set_settings`]
+    ///
+    /// Views read the editing preferences here, such as whether to confirm
+    /// deletions and the bind type of new bindings.
+    pub fn settings(&self) -> Settings {
+        self.settings.get()
+    }
+
+    /// Returns whether bindings on reserved combos are flagged
+    pub fn is_reserved_check_enabled(&self) -> bool {
+        self.reserved_check.get()
+    }
+
+    /// Returns the parse diagnostics recorded by the last load
+    pub fn get_parse_diagnostics(&self) -> Vec<ParseDiagnostic> {
+        self.parse_diagnostics.borrow().clone()
+    }
+
+    /// Runs `callback` with the full binding list whenever it changes
+    ///
+    /// Called after every load, write, undo and redo, including writes that
+    /// failed and put the previous bindings back. Callbacks run in the order
+    /// they were registered, and must not register further callbacks. They
+    /// live as long as the controller, and so does anything they capture.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hypr_keybind_manager::ui::Controller;
+    /// # use std::path::PathBuf;
+    /// let controller = Controller::new(PathBuf::from("hyprland.conf"))?;
+    /// controller.on_bindings_changed(|bindings| {
+    ///     println!("{} keybindings", bindings.len());
+    /// });
+    /// controller.load_keybindings()?; // prints the count
+    /// # Ok::<(), hypr_keybind_manager::config::ConfigError>(())
+    /// ```
+    pub fn on_bindings_changed<F: Fn(&[Keybinding]) + 'static>(&self, callback: F) {
+        self.observers
+            .borrow_mut()
+            .bindings_changed
+            .push(Box::new(callback));
+    }
+
+    /// Runs `callback` with the conflict list whenever it differs from the
+    /// last one reported
+    ///
+    /// Changes to the bindings that leave the conflicts as they were (most
+    /// edits) do not call it; turning the reserved combo check on or off
+    /// can.
+    pub fn on_conflicts_changed<F: Fn(&[Conflict]) + 'static>(&self, callback: F) {
+        self.observers
+            .borrow_mut()
+            .conflicts_changed
+            .push(Box::new(callback));
+    }
+
+    /// Runs `callback` with the parse diagnostics after every load
+    ///
+    /// Called even when there are none, so a view can clear what it showed.
+    pub fn on_diagnostics<F: Fn(&[ParseDiagnostic]) + 'static>(&self, callback: F) {
+        self.observers
+            .borrow_mut()
+            .diagnostics
+            .push(Box::new(callback));
+    }
+
+    /// Reacts to an event from Hyprland's event socket
+    ///
+    /// When Hyprland reloads its config, its live bindings may have changed
+    /// without the config file changing, so `bindings_changed` observers
+    /// are told to re-read what depends on them, such as the runtime
+    /// drift. Other events are ignored.
+    pub fn handle_hyprland_event(&self, event: &HyprlandEvent) {
+        if *event == HyprlandEvent::ConfigReloaded {
+            self.notify_bindings_changed();
+        }
+    }
+
+    /// Tells observers the bindings changed, and the conflicts if they did
+    fn notify_bindings_changed(&self) {
+        let bindings = self.get_keybindings();
+        for callback in self.observers.borrow().bindings_changed.iter() {
+            callback(&bindings);
+        }
+        self.notify_conflicts_changed();
+    }
+
+    /// Tells observers about the conflicts if they differ from the last report
+    fn notify_conflicts_changed(&self) {
+        let conflicts = self.get_conflicts();
+        if *self.reported_conflicts.borrow() == conflicts {
+            return;
+        }
+
+        *self.reported_conflicts.borrow_mut() = conflicts.clone();
+        for callback in self.observers.borrow().conflicts_changed.iter() {
+            callback(&conflicts);
+        }
+    }
+
+    /// Refuses a binding whose command the danger policy preference blocks
+    ///
+    /// A binding passed to [`Self::override_danger_policy`] is let through
+    /// once.
+    fn ensure_danger_allowed(&self, binding: &Keybinding) -> Result<(), String> {
+        let overridden = self
+            .danger_override
+            .take()
+            .is_some_and(|allowed| &allowed == binding);
+        let Some(assessment) = self.get_danger_assessment(binding) else {
+            return Ok(());
+        };
+        if overridden || !self.is_danger_blocked(assessment.danger_level) {
+            return Ok(());
+        }
+
+        Err(format!(
+            "The danger policy in Preferences blocks {:?} commands: {}",
+            assessment.danger_level, assessment.reason
+        ))
+    }
+
+    /// Refuses the write when strict mode is on and diagnostics exist
+    fn ensure_writable(&self) -> Result<(), String> {
+        let diagnostics = self.parse_diagnostics.borrow();
+        if !self.is_strict_mode() || diagnostics.is_empty() {
+            return Ok(());
+        }
+
+        let details: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        Err(format!(
+            "Strict mode: resolve {} parse diagnostic{} before making changes:\n{}",
+            diagnostics.len(),
+            if diagnostics.len() == 1 { "" } else { "s" },
+            details.join("\n")
+        ))
+    }
+
+    /// Refuses to change a binding that lives in a `source`d file
+    ///
+    /// Writes only touch the main config, so such an edit would be lost.
+    fn ensure_in_main_config(binding: &Keybinding) -> Result<(), String> {
+        match &binding.source_file {
+            Some(file) => Err(format!(
+                "'{}' is defined in {}; edit that file to change it, or mark it \
+                 '# {}' in the main config to override it there",
+                binding,
+                file.display(),
+                READ_ONLY_ANNOTATION
+            )),
+            None => Ok(()),
         }
-        self.redo_stack.borrow_mut().clear();
+    }
+
+    /// Returns whether a binding comes from a file the main config marks
+    /// read-only (see [`overrides`](crate::config::overrides))
+    fn in_read_only_source(&self, binding: &Keybinding) -> bool {
+        binding
+            .source_file
+            .as_deref()
+            .is_some_and(|file| self.config_manager.borrow().is_read_only_source(file))
+    }
+
+    /// Overrides a binding of a read-only file in the main config
+    ///
+    /// The snapshot history cannot take the appended `unbind` back, so
+    /// like a restore this reloads the bindings and clears the history.
+    fn override_source_binding(
+        &self,
+        old: &Keybinding,
+        new: Option<&Keybinding>,
+    ) -> Result<(), String> {
+        self.config_manager
+            .borrow_mut()
+            .write_source_override(old, new, &self.get_keybindings())
+            .map_err(|e| format!("Failed to write changes to config: {}", e))?;
+        self.load_keybindings()
+            .map_err(|e| format!("Failed to reload keybindings: {}", e))?;
+        self.clear_history();
+        self.apply_live(Some(old), new);
+
+        Ok(())
+    }
+
+    fn record_undo_snapshot(&self) {
+        self.history.borrow_mut().record(&self.keybindings.borrow());
+    }
+
+    /// Drops the snapshot recorded for a change that could not be written
+    fn pop_undo(&self) -> Option<Vec<Keybinding>> {
+        self.history.borrow_mut().discard_last()
     }
 
     fn rebuild_conflict_detector_from_bindings(bindings: &[Keybinding]) -> ConflictDetector {
@@ -198,14 +725,15 @@ impl Controller {
     }
 
     fn replace_bindings(&self, new_bindings: Vec<Keybinding>) {
-        let detector = Self::rebuild_conflict_detector_from_bindings(&new_bindings);
+        let mut detector = Self::rebuild_conflict_detector_from_bindings(&new_bindings);
+        detector.set_check_reserved(self.reserved_check.get());
         *self.keybindings.borrow_mut() = new_bindings;
         *self.conflict_detector.borrow_mut() = detector;
+        self.notify_bindings_changed();
     }
 
     pub fn clear_history(&self) {
-        self.undo_stack.borrow_mut().clear();
-        self.redo_stack.borrow_mut().clear();
+        self.history.borrow_mut().clear();
     }
 
     /// Returns all loaded keybindings
@@ -219,10 +747,13 @@ impl Controller {
 
     /// Filters keybindings by search query
     ///
-    /// Searches in:
+    /// The query is matched fuzzily, as `find` does: its characters must
+    /// appear in order (`ffx` finds `firefox`) in one of
     /// - Key combination (e.g., "SUPER+K")
     /// - Dispatcher name (e.g., "exec")
     /// - Arguments (e.g., "firefox")
+    /// - `bindd` descriptions (e.g., "Close window")
+    /// - `# @category:` annotation and input device
     ///
     /// Search is case-insensitive.
     ///
@@ -232,7 +763,8 @@ impl Controller {
     ///
     /// # Returns
     ///
-    /// Filtered list of keybindings matching query
+    /// Matching keybindings, best match first (all of them, in config
+    /// order, for an empty query)
     ///
     /// # Example
     ///
@@ -251,33 +783,9 @@ impl Controller {
             return self.get_keybindings();
         }
 
-        let query_lower = query.to_lowercase();
-
-        self.keybindings
-            .borrow()
-            .iter()
-            .filter(|binding| {
-                // Search in the key combo
-                let key_combo_str = format!("{}", binding.key_combo).to_lowercase();
-                if key_combo_str.contains(&query_lower) {
-                    return true;
-                }
-
-                // Search in dispatcher
-                if binding.dispatcher.to_lowercase().contains(&query_lower) {
-                    return true;
-                }
-
-                // Search in args
-                if let Some(args) = &binding.args {
-                    if args.to_lowercase().contains(&query_lower) {
-                        return true;
-                    }
-                }
-
-                false
-            })
-            .cloned()
+        search_bindings(&self.keybindings.borrow(), query)
+            .into_iter()
+            .map(|found| found.binding.clone())
             .collect()
     }
 
@@ -302,40 +810,158 @@ impl Controller {
         self.current_search_query.borrow().clone()
     }
 
-    /// Returns the current view of keybindings (respecting active search filter)
+    /// Shows only bindings whose action another key combo also performs
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the duplicate action filter is active
+    pub fn set_duplicate_actions_only(&self, enabled: bool) {
+        self.duplicate_actions_only.set(enabled);
+    }
+
+    /// Returns whether the duplicate action filter is active
+    pub fn is_duplicate_actions_only(&self) -> bool {
+        self.duplicate_actions_only.get()
+    }
+
+    /// Returns the actions reachable from more than one key combo
+    pub fn get_duplicate_actions(&self) -> Vec<DuplicateAction> {
+        self.conflict_detector.borrow().find_duplicate_actions()
+    }
+
+    /// Returns the scopes the config declares, sorted by name
+    ///
+    /// Empty when the config has no scope sections (see
+    /// [`crate::core::scope`]).
+    pub fn get_scopes(&self) -> Vec<String> {
+        self.scope_index.borrow().scopes()
+    }
+
+    /// Returns the scope a binding is declared in (`None`: global)
+    pub fn get_scope_for(&self, binding: &Keybinding) -> Option<String> {
+        self.scope_index
+            .borrow()
+            .scope_of(binding)
+            .map(str::to_string)
+    }
+
+    /// Previews the bindings active in a scope
+    ///
+    /// While a scope is selected, the current view and exports only hold
+    /// global bindings and those of that scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - Scope to preview (`None`: all bindings)
+    pub fn set_scope(&self, scope: Option<String>) {
+        *self.selected_scope.borrow_mut() = scope;
+    }
+
+    /// Returns the scope being previewed (`None`: all bindings)
+    pub fn get_scope(&self) -> Option<String> {
+        self.selected_scope.borrow().clone()
+    }
+
+    /// Returns the categories the bindings are tagged with, sorted by name
+    ///
+    /// Empty when no binding has a `# @category:` annotation.
+    pub fn get_categories(&self) -> Vec<String> {
+        let categories: BTreeSet<String> = self
+            .keybindings
+            .borrow()
+            .iter()
+            .filter_map(|binding| binding.category.clone())
+            .collect();
+        categories.into_iter().collect()
+    }
+
+    /// Returns the input devices with bindings of their own, sorted by name
+    ///
+    /// Empty when the config has no bindings in `device` sections.
+    pub fn binding_device_names(&self) -> Vec<String> {
+        let devices: BTreeSet<String> = self
+            .keybindings
+            .borrow()
+            .iter()
+            .filter_map(|binding| binding.device.clone())
+            .collect();
+        devices.into_iter().collect()
+    }
+
+    /// Limits the current view to the bindings of one category
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - Category to show (`None`: every binding)
+    pub fn set_category_filter(&self, category: Option<String>) {
+        *self.selected_category.borrow_mut() = category;
+    }
+
+    /// Returns the category the view is limited to (`None`: none)
+    pub fn get_category_filter(&self) -> Option<String> {
+        self.selected_category.borrow().clone()
+    }
+
+    /// Returns the bindings active in the selected scope
+    fn scoped_keybindings(&self, bindings: Vec<Keybinding>) -> Vec<Keybinding> {
+        match self.selected_scope.borrow().as_deref() {
+            Some(scope) => self.scope_index.borrow().active_in(&bindings, scope),
+            None => bindings,
+        }
+    }
+
+    /// Returns the current view of keybindings (respecting active filters)
     ///
     /// If a search query is active, returns filtered results.
-    /// If no search query, returns all keybindings.
+    /// If no search query, returns all keybindings. Either way, only
+    /// bindings with a duplicate action remain while that filter is on,
+    /// only those active in the selected scope while one is selected, and
+    /// only those of the selected category while one is selected.
     ///
     /// # Returns
     ///
     /// The keybindings that should currently be displayed in the UI
     pub fn get_current_view(&self) -> Vec<Keybinding> {
         let query = self.current_search_query.borrow().clone();
-        self.filter_keybindings(&query)
+        let mut view = self.scoped_keybindings(self.filter_keybindings(&query));
+        if self.duplicate_actions_only.get() {
+            let detector = self.conflict_detector.borrow();
+            view.retain(|binding| detector.has_duplicate_action(binding));
+        }
+        if let Some(category) = self.selected_category.borrow().as_deref() {
+            view.retain(|binding| binding.category.as_deref() == Some(category));
+        }
+        view
     }
 
     pub fn can_undo(&self) -> bool {
-        !self.undo_stack.borrow().is_empty()
+        self.history.borrow().can_undo()
     }
 
     pub fn can_redo(&self) -> bool {
-        !self.redo_stack.borrow().is_empty()
+        self.history.borrow().can_redo()
     }
 
     /// Returns bindings currently using the provided key combo.
     ///
     /// When `exclude` is set, that exact binding is ignored. This is used by the
-    /// edit dialog so a binding does not report itself as a conflict.
+    /// edit dialog so a binding does not report itself as a conflict. Only
+    /// bindings in the same submap as `exclude` (global when `None`) count,
+    /// as bindings in other submaps are never active at the same time, and
+    /// only bindings that can fire on the same input device as `exclude`.
     pub fn get_bindings_for_key_combo(
         &self,
         key_combo: &KeyCombo,
         exclude: Option<&Keybinding>,
     ) -> Vec<Keybinding> {
+        let submap = exclude.and_then(|binding| binding.submap.as_ref());
+
         self.keybindings
             .borrow()
             .iter()
             .filter(|binding| binding.key_combo == *key_combo)
+            .filter(|binding| binding.submap.as_ref() == submap)
+            .filter(|binding| exclude.is_none_or(|exclude| binding.shares_device_with(exclude)))
             .filter(|binding| exclude != Some(*binding))
             .cloned()
             .collect()
@@ -373,11 +999,60 @@ impl Controller {
         } else {
             KeyComboAssistance {
                 availability: KeyComboAvailability::InUse(in_use),
-                suggestions: self.suggest_key_combos(&key_combo.modifiers, exclude, 5, key_combo),
+                suggestions: self.suggestion_engine(exclude).suggest(
+                    key_combo,
+                    exclude.and_then(|binding| binding.submap.as_deref()),
+                    5,
+                ),
             }
         }
     }
 
+    /// Previews the conflict saving `candidate` would cause
+    ///
+    /// Bindings count as for [`Self::get_bindings_for_key_combo`], but in
+    /// the candidate's own submap and device, so an exact duplicate of a
+    /// saved binding is found too. The conflict is classified as `check`
+    /// classifies it once saved, and a reserved combo is flagged if the
+    /// reserved combo check is enabled.
+    ///
+    /// # Arguments
+    /// * `candidate` - The binding as the edit dialog has it now
+    /// * `original` - The binding being edited, which is not a conflict
+    pub fn get_conflict_preview(
+        &self,
+        candidate: &Keybinding,
+        original: Option<&Keybinding>,
+    ) -> ConflictPreview {
+        let bindings: Vec<Keybinding> = self
+            .keybindings
+            .borrow()
+            .iter()
+            .filter(|binding| binding.key_combo == candidate.key_combo)
+            .filter(|binding| binding.submap == candidate.submap)
+            .filter(|binding| binding.shares_device_with(candidate))
+            .filter(|binding| original != Some(*binding))
+            .cloned()
+            .collect();
+
+        let kind = (!bindings.is_empty()).then(|| {
+            let mut sharing = bindings.clone();
+            sharing.push(candidate.clone());
+            ConflictKind::classify(&sharing)
+        });
+        let reserved = self
+            .reserved_check
+            .get()
+            .then(|| reserved::reserved_shadow(candidate))
+            .flatten();
+
+        ConflictPreview {
+            bindings,
+            kind,
+            reserved,
+        }
+    }
+
     /// Suggests nearby free combos using the same modifier set.
     pub fn suggest_key_combos(
         &self,
@@ -386,17 +1061,30 @@ impl Controller {
         limit: usize,
         original: &KeyCombo,
     ) -> Vec<KeyCombo> {
-        let modifiers = modifiers.to_vec();
+        let submap = exclude.and_then(|binding| binding.submap.as_deref());
 
-        candidate_keys()
-            .into_iter()
-            .map(|key| KeyCombo::new(modifiers.clone(), key))
+        self.suggestion_engine(exclude)
+            .free_keys(modifiers, submap)
             .filter(|candidate| candidate != original)
-            .filter(|candidate| self.is_key_combo_available(candidate, exclude))
             .take(limit)
             .collect()
     }
 
+    /// Suggests free combos to move one of a conflict's bindings to
+    ///
+    /// Empty for conflicts that are not resolved by moving a binding; see
+    /// [`KeySuggestionEngine::suggest_for_conflict`].
+    pub fn suggest_for_conflict(&self, conflict: &Conflict, limit: usize) -> Vec<KeyCombo> {
+        self.suggestion_engine(None)
+            .suggest_for_conflict(conflict, limit)
+    }
+
+    /// Builds a suggestion engine over the loaded bindings, without `exclude`
+    fn suggestion_engine(&self, exclude: Option<&Keybinding>) -> KeySuggestionEngine {
+        let bindings = self.keybindings.borrow();
+        KeySuggestionEngine::new(bindings.iter().filter(|binding| exclude != Some(*binding)))
+    }
+
     /// Returns all detected conflicts
     ///
     /// A conflict occurs when multiple keybindings use the same
@@ -421,6 +1109,94 @@ impl Controller {
         self.conflict_detector.borrow().find_conflicts()
     }
 
+    /// Assesses the command of an `exec` binding for danger
+    ///
+    /// # Returns
+    ///
+    /// * `Some(DangerAssessment)` - Binding runs a command
+    /// * `None` - Binding does not execute anything
+    pub fn get_danger_assessment(&self, binding: &Keybinding) -> Option<DangerAssessment> {
+        self.assess_exec_args(&binding.dispatcher, binding.args.as_deref()?)
+    }
+
+    /// Assesses dispatcher arguments as typed in the edit dialog
+    ///
+    /// Like [`Self::get_danger_assessment`], only `exec` commands are
+    /// assessed; empty arguments are not a command yet.
+    pub fn assess_exec_args(&self, dispatcher: &str, args: &str) -> Option<DangerAssessment> {
+        if dispatcher != "exec" || args.trim().is_empty() {
+            return None;
+        }
+
+        Some(self.danger_detector.assess_command(args))
+    }
+
+    /// Returns whether the danger policy preference refuses this level
+    pub fn is_danger_blocked(&self, level: DangerLevel) -> bool {
+        self.settings.get().danger_policy.blocks(level)
+    }
+
+    /// Lets the next add or edit save this binding despite the danger policy
+    ///
+    /// For the edit dialog's explicit override. Only the very next danger
+    /// check can use it, and only for an identical binding.
+    pub fn override_danger_policy(&self, binding: &Keybinding) {
+        self.danger_override.replace(Some(binding.clone()));
+    }
+
+    /// Checks whether an exec command may be test-run from the edit dialog
+    ///
+    /// Test runs are only offered for commands the danger detector assesses
+    /// as Safe and that contain no shell metacharacters (they would not be
+    /// interpreted anyway, since no shell is involved).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The command may be run after user confirmation
+    /// * `Err(String)` - Why the command cannot be tested
+    pub fn check_command_testable(&self, command: &str) -> Result<(), String> {
+        if command.trim().is_empty() {
+            return Err("There is no command to test".to_string());
+        }
+
+        injection_validator::check_shell_metacharacters(command).map_err(|e| e.to_string())?;
+
+        let assessment = self.danger_detector.assess_command(command);
+        if assessment.danger_level != DangerLevel::Safe {
+            return Err(format!(
+                "Only commands assessed as Safe can be tested ({:?}: {})",
+                assessment.danger_level, assessment.reason
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the conflict and danger badges for a list row
+    ///
+    /// Conflict counts come straight from the conflict detector's index,
+    /// so this is cheap enough to call for every row on refresh.
+    pub fn get_binding_badges(&self, binding: &Keybinding) -> BindingBadges {
+        let conflict_count = self
+            .conflict_detector
+            .borrow()
+            .bindings_for(&binding.key_combo, binding.submap.as_deref())
+            .iter()
+            .filter(|other| other.shares_device_with(binding))
+            .count()
+            .saturating_sub(1);
+
+        let danger_level = self
+            .get_danger_assessment(binding)
+            .map(|assessment| assessment.danger_level)
+            .filter(|level| *level > DangerLevel::Safe);
+
+        BindingBadges {
+            conflict_count,
+            danger_level,
+        }
+    }
+
     /// Validates a keybinding using all security layers
     ///
     /// Runs:
@@ -457,7 +1233,46 @@ impl Controller {
         Ok(())
     }
 
-    /// Returns total count of loaded keybindings
+    /// Computes the health score of the loaded config
+    ///
+    /// See [`crate::config::health`] for how the score is made up.
+    pub fn get_health_report(&self) -> HealthReport {
+        let content = self
+            .config_manager
+            .borrow()
+            .read_config()
+            .unwrap_or_default();
+
+        assess_health(
+            &content,
+            &self.keybindings.borrow(),
+            &self.get_conflicts(),
+            &self.danger_detector,
+            executable_on_path,
+        )
+    }
+
+    /// Records a health score in the audit log and returns its trend
+    ///
+    /// Only changes are written, so calling this after every refresh is fine.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(HealthTrend)` - How the score changed since it was last different
+    /// * `None` - No earlier score, or the audit log is unavailable
+    pub fn track_health(&self, score: u8) -> Option<HealthTrend> {
+        let log = AuditLog::new(AuditLog::default_path()?);
+        let config_path = self.config_path();
+
+        let history = log.health_history(&config_path).ok()?;
+        if let Err(e) = log.record_health(&config_path, score, Local::now()) {
+            eprintln!("Failed to record health score: {}", e);
+        }
+
+        HealthTrend::from_history(&history, score)
+    }
+
+    /// Returns total count of loaded keybindings
     pub fn keybinding_count(&self) -> usize {
         self.keybindings.borrow().len()
     }
@@ -471,6 +1286,8 @@ impl Controller {
     ///
     /// This removes the binding from the in-memory list and immediately
     /// writes the updated list back to the config file, creating a backup.
+    /// A binding from a read-only sourced file is unbound in the main
+    /// config instead (see [`overrides`](crate::config::overrides)).
     ///
     /// # Arguments
     /// * `binding` - The keybinding to delete
@@ -482,7 +1299,7 @@ impl Controller {
     /// # Example
     /// ```no_run
     /// # use hypr_keybind_manager::{
-    ///       core::{Keybinding, KeyCombo, Modifier, BindType},
+    ///       core::{Keybinding, KeyCombo, Modifier},
     ///       ui::Controller
     ///   };
     ///
@@ -492,18 +1309,22 @@ impl Controller {
     /// let controller = Controller::new(PathBuf::from("test.conf"))
     ///     .map_err(|e| e.to_string())?;
     ///
-    /// let binding = Keybinding {
-    ///     key_combo: KeyCombo::new(vec![Modifier::Super], "K"),
-    ///     bind_type: BindType::Bind,
-    ///     dispatcher: "exec".to_string(),
-    ///     args: Some("firefox".to_string()),
-    /// };
+    /// let binding = Keybinding::new(
+    ///     KeyCombo::new(vec![Modifier::Super], "K"),
+    ///     "exec",
+    ///     Some("firefox"),
+    /// );
     ///
     /// controller.delete_keybinding(&binding)?;
     /// # Ok(())
     /// # }
     /// ```
     pub fn delete_keybinding(&self, binding: &Keybinding) -> Result<(), String> {
+        self.ensure_writable()?;
+        if self.in_read_only_source(binding) {
+            return self.override_source_binding(binding, None);
+        }
+        Self::ensure_in_main_config(binding)?;
         self.record_undo_snapshot();
         let mut bindings = self.keybindings.borrow_mut();
         bindings.retain(|b| b != binding);
@@ -511,7 +1332,7 @@ impl Controller {
         drop(bindings);
 
         if let Err(e) = self.write_snapshot(&updated_bindings) {
-            let previous = self.undo_stack.borrow_mut().pop();
+            let previous = self.pop_undo();
             if let Some(previous) = previous {
                 self.replace_bindings(previous);
             }
@@ -519,6 +1340,7 @@ impl Controller {
         }
 
         self.replace_bindings(updated_bindings);
+        self.apply_live(Some(binding), None);
 
         Ok(())
     }
@@ -545,6 +1367,8 @@ impl Controller {
     /// }
     /// ```
     pub fn add_keybinding(&self, binding: Keybinding) -> Result<(), String> {
+        self.ensure_writable()?;
+        self.ensure_danger_allowed(&binding)?;
         self.record_undo_snapshot();
         let mut bindings = self.keybindings.borrow_mut();
         bindings.push(binding.clone());
@@ -552,7 +1376,7 @@ impl Controller {
         drop(bindings);
 
         if let Err(e) = self.write_snapshot(&updated_bindings) {
-            let previous = self.undo_stack.borrow_mut().pop();
+            let previous = self.pop_undo();
             if let Some(previous) = previous {
                 self.replace_bindings(previous);
             }
@@ -560,16 +1384,27 @@ impl Controller {
         }
 
         self.replace_bindings(updated_bindings);
+        self.apply_live(None, Some(&binding));
 
         Ok(())
     }
 
     /// Lists all available backup files, sorted newest first
+    ///
+    /// The pristine backup (the config as it was before this tool first
+    /// wrote to it) is listed last when it exists.
     pub fn list_backups(&self) -> Result<Vec<PathBuf>, String> {
-        self.config_manager
-            .borrow()
+        let config_manager = self.config_manager.borrow();
+        let mut backups = config_manager
             .list_backups()
-            .map_err(|e| format!("Failed to list backups: {}", e))
+            .map_err(|e| format!("Failed to list backups: {}", e))?;
+
+        let pristine = config_manager.pristine_backup_path();
+        if pristine.is_file() {
+            backups.push(pristine);
+        }
+
+        Ok(backups)
     }
 
     /// Restores the configuration from a backup file.
@@ -610,84 +1445,370 @@ impl Controller {
     /// * `Ok(())` - Successfully deleted
     /// * `Err(String)` - Delete failed (file not found, permission error, etc.)
     pub fn delete_backup(&self, backup_path: &Path) -> Result<(), String> {
-        // Delete the backup file
-        fs::remove_file(backup_path).map_err(|e| format!("Failed to delete backup: {}", e))?;
+        // Delete the backup file and its checksum
+        backup::remove_backup(backup_path)
+            .map_err(|e| format!("Failed to delete backup: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Compares the main config's bindings with another config file
+    ///
+    /// The current bindings are the left side, the file (usually a backup)
+    /// the right side. Bindings from `source`d files are left out, as
+    /// backups only cover the main config. Compressed backups are
+    /// decompressed and checked against their checksum.
+    ///
+    /// # Arguments
+    ///
+    /// * `other_path` - Config file to compare against
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(rows)` - Rows aligned by key combo, see [`compare::compare_bindings`]
+    /// * `Err(String)` - The file could not be read or parsed
+    pub fn compare_with(&self, other_path: &Path) -> Result<Vec<ComparisonRow>, String> {
+        let content =
+            backup::read_backup(other_path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let other = parse_config_file(&content, other_path)
+            .map_err(|e| format!("Failed to parse file: {}", e))?;
+
+        let current: Vec<Keybinding> = self
+            .keybindings
+            .borrow()
+            .iter()
+            .filter(|binding| binding.source_file.is_none())
+            .cloned()
+            .collect();
+
+        Ok(compare::compare_bindings(&current, &other))
+    }
+
+    /// Shows what [`restore_backup`](Self::restore_backup) would change in
+    /// the config, without writing
+    ///
+    /// # Arguments
+    ///
+    /// * `backup_path` - Backup that would be restored
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ConfigDiff)` - Line diff from the current config to the backup
+    /// * `Err(String)` - The config or the backup cannot be read
+    pub fn preview_restore(&self, backup_path: &Path) -> Result<ConfigDiff, String> {
+        let backup = backup::read_backup(backup_path)
+            .map_err(|e| format!("Failed to read backup: {}", e))?;
+        let current = self
+            .config_manager
+            .borrow()
+            .read_config()
+            .map_err(|e| format!("Failed to read config: {}", e))?;
+        Ok(ConfigDiff::between(&current, &backup))
+    }
+
+    /// Writes the result of merging a comparison in one transaction
+    ///
+    /// Like other edits, this creates a backup and can be undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - Rows returned by [`compare_with`](Self::compare_with)
+    /// * `choices` - Side to take per row (left: keep the current binding)
+    pub fn apply_merge(&self, rows: &[ComparisonRow], choices: &[Side]) -> Result<(), String> {
+        self.ensure_writable()?;
+
+        let mut merged = compare::merge_rows(rows, choices);
+        merged.extend(
+            self.keybindings
+                .borrow()
+                .iter()
+                .filter(|binding| binding.source_file.is_some())
+                .cloned(),
+        );
+
+        self.record_undo_snapshot();
+        if let Err(e) = self.write_snapshot(&merged) {
+            self.pop_undo();
+            return Err(e);
+        }
+
+        self.replace_bindings(merged);
 
         Ok(())
     }
 
+    /// Saves the main config's bindings as a named profile
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - Where profiles live (usually [`ProfileStore::default_dir`])
+    /// * `name` - Profile name; an existing profile of that name is replaced
+    pub fn save_profile(&self, store: &ProfileStore, name: &str) -> Result<(), String> {
+        store
+            .save(name, &self.keybindings.borrow())
+            .map_err(|e| format!("Failed to save profile: {}", e))
+    }
+
+    /// Returns the names of the saved profiles, sorted
+    pub fn list_profiles(&self, store: &ProfileStore) -> Result<Vec<String>, String> {
+        store
+            .list()
+            .map_err(|e| format!("Failed to list profiles: {}", e))
+    }
+
+    /// Switches to a profile, replacing the main config's bindings with it
+    ///
+    /// The config is rewritten in one transaction, so a failed write leaves
+    /// it untouched. Bindings from `source`d files are kept. A profile with
+    /// validation errors or critical dangers is refused. Like other edits,
+    /// this creates a backup and can be undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - Where profiles live
+    /// * `name` - Profile to switch to
+    pub fn apply_profile(&self, store: &ProfileStore, name: &str) -> Result<(), String> {
+        self.ensure_writable()?;
+
+        let mut bindings = store
+            .load(name)
+            .map_err(|e| format!("Failed to load profile: {}", e))?;
+
+        // Profiles are plain files that may have been edited by hand
+        let report = ConfigValidator::new().validate_bindings(&bindings);
+        if report.has_errors() {
+            return Err("Validation errors detected in profile".to_string());
+        }
+        if report.has_critical_dangers() {
+            return Err("Critical dangers detected in profile".to_string());
+        }
+
+        bindings.extend(
+            self.keybindings
+                .borrow()
+                .iter()
+                .filter(|binding| binding.source_file.is_some())
+                .cloned(),
+        );
+
+        self.record_undo_snapshot();
+        if let Err(e) = self.write_snapshot(&bindings) {
+            self.pop_undo();
+            return Err(e);
+        }
+
+        self.replace_bindings(bindings);
+
+        Ok(())
+    }
+
+    /// Compares two profiles
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - Where profiles live
+    /// * `left` - Profile on the left side
+    /// * `right` - Profile on the right side; `None` compares `left` with
+    ///   the main config's current bindings
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(rows)` - Rows aligned by key combo, see [`compare::compare_bindings`]
+    /// * `Err(String)` - A profile is missing or does not parse
+    pub fn diff_profiles(
+        &self,
+        store: &ProfileStore,
+        left: &str,
+        right: Option<&str>,
+    ) -> Result<Vec<ComparisonRow>, String> {
+        let load = |name: &str| {
+            store
+                .load(name)
+                .map_err(|e| format!("Failed to load profile: {}", e))
+        };
+        let right = match right {
+            Some(name) => load(name)?,
+            None => self
+                .keybindings
+                .borrow()
+                .iter()
+                .filter(|binding| binding.source_file.is_none())
+                .cloned()
+                .collect(),
+        };
+
+        Ok(compare::compare_bindings(&load(left)?, &right))
+    }
+
     /// Exports a keybinding configuration file to a specific file path
     ///
+    /// While a scope is selected (see [`set_scope`](Self::set_scope)), only
+    /// the bindings active in it are exported.
+    ///
     /// # Arguments
     ///
     /// * `export_path` - Path to export file that's created
+    /// * `format` - Config lines, JSON, YAML or TOML
     ///
     /// # Returns
     ///
     /// * `OK(())` - Successfully exported
     /// * `Err(String)` - Export failed (...)
-    pub fn export_to(&self, export_path: &Path) -> Result<(), String> {
+    pub fn export_to(&self, export_path: &Path, format: ExportFormat) -> Result<(), String> {
         // Get bindings from controller's storage
-        let bindings = self.keybindings.borrow();
+        let bindings = self.scoped_keybindings(self.get_keybindings());
 
         self.config_manager
             .borrow_mut()
-            .export_to(export_path, &bindings)
+            .export_to(export_path, &bindings, format)
             .map_err(|e| format!("Failed to export config: {}", e))?;
 
         Ok(())
     }
 
-    pub fn import_from(&self, import_path: &Path, mode: ImportMode) -> Result<(), String> {
-        self.record_undo_snapshot();
+    /// Writes a Markdown or HTML cheat sheet of the bindings
+    ///
+    /// Like [`export_to`](Self::export_to), only the bindings active in
+    /// the selected scope are listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - File to create or overwrite
+    /// * `format` - Markdown or HTML
+    ///
+    /// # Returns
+    ///
+    /// * `Err(String)` - The file cannot be written
+    pub fn export_cheat_sheet(&self, path: &Path, format: CheatSheetFormat) -> Result<(), String> {
+        let bindings = self.scoped_keybindings(self.get_keybindings());
+
+        fs::write(path, format.render(&bindings))
+            .map_err(|e| format!("Failed to write cheat sheet: {}", e))
+    }
 
-        // Read the import file
+    /// Reads an import file and reviews its bindings
+    ///
+    /// Nothing is changed yet: the review says which bindings are blocked
+    /// and which need acknowledging before
+    /// [`import_reviewed`](Self::import_reviewed) adds them. Entries of a
+    /// JSON, YAML or TOML file that are not valid bindings are listed in
+    /// [`ImportReview::unreadable`] rather than failing the import.
+    ///
+    /// # Arguments
+    ///
+    /// * `import_path` - File to import from
+    /// * `format` - Format of the file (`None`: detected from its extension)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImportReview)` - Verdicts for every binding of the file
+    /// * `Err(String)` - The file cannot be read, or is not in the format
+    pub fn review_import(
+        &self,
+        import_path: &Path,
+        format: Option<ExportFormat>,
+    ) -> Result<ImportReview, String> {
         let content = read_to_string(import_path)
             .map_err(|e| format!("Failed to read import file: {}", e))?;
-
-        // Parse bindings from import file
-        let imported_bindings = parse_config_file(&content, import_path)
+        let format = format.unwrap_or_else(|| ExportFormat::from_path(import_path));
+        let imported = format
+            .read(&content, import_path)
             .map_err(|e| format!("Failed to parse import file: {}", e))?;
 
-        // Handle import mode
-        match mode {
-            ImportMode::Replace => {
-                // Replace: Clear all and add imported
-                self.keybindings.borrow_mut().clear();
-                self.keybindings
-                    .borrow_mut()
-                    .extend(imported_bindings.clone());
-            }
-            ImportMode::Merge => {
-                // Merge: Add imported, skip duplicates
-                let mut existing = self.keybindings.borrow_mut();
-                for binding in imported_bindings.clone() {
-                    // Check if binding already exists (same key combo)
-                    let exists = existing.iter().any(|b| b.key_combo == binding.key_combo);
-                    if !exists {
-                        existing.push(binding);
-                    }
-                }
-            }
+        let mut review = ImportReview::new(imported.bindings, &ConfigValidator::new());
+        review.unreadable = imported.errors;
+        Ok(review)
+    }
+
+    /// Reads a sway or i3 config and reviews its translated bindings
+    ///
+    /// Like [`review_import`](Self::review_import), but the bind lines are
+    /// translated first (see [`crate::config::interop`]). Lines that have
+    /// no Hyprland equivalent are listed in [`ImportReview::unreadable`].
+    ///
+    /// # Arguments
+    ///
+    /// * `import_path` - The sway or i3 config
+    /// * `from` - Window manager the config belongs to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImportReview)` - Verdicts for every translated binding
+    /// * `Err(String)` - The file cannot be read, or has no bind lines
+    pub fn review_foreign_import(
+        &self,
+        import_path: &Path,
+        from: ForeignConfig,
+    ) -> Result<ImportReview, String> {
+        let content = read_to_string(import_path)
+            .map_err(|e| format!("Failed to read import file: {}", e))?;
+        let imported = from.translate(&content);
+        if imported.bindings.is_empty() && imported.errors.is_empty() {
+            return Err(format!("No {} bind lines found in the file", from));
         }
 
-        let bindings: Vec<_> = self.keybindings.borrow().clone();
+        let mut review = ImportReview::new(imported.bindings, &ConfigValidator::new());
+        review.unreadable = imported.errors;
+        Ok(review)
+    }
+
+    /// Reviews bind lines pasted from the clipboard
+    ///
+    /// Lines other than bind lines (comments, settings) are ignored.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ImportReview)` - Verdicts for every pasted binding
+    /// * `Err(String)` - Nothing to import, or a bind line does not parse
+    pub fn review_paste(&self, text: &str) -> Result<ImportReview, String> {
+        let pasted_bindings = parse_config_file(text, Path::new(""))
+            .map_err(|e| format!("Failed to parse pasted bindings: {}", e))?;
+        if pasted_bindings.is_empty() {
+            return Err("The clipboard holds no bind lines".to_string());
+        }
+
+        Ok(ImportReview::new(pasted_bindings, &ConfigValidator::new()))
+    }
+
+    /// Adds the bindings a review lets through
+    ///
+    /// Blocked bindings are never added, and Dangerous ones only when
+    /// `acknowledged`.
+    ///
+    /// # Arguments
+    ///
+    /// * `review` - Review from [`review_import`](Self::review_import) or
+    ///   [`review_paste`](Self::review_paste)
+    /// * `mode` - Replace the config's bindings or merge into them
+    /// * `acknowledged` - The user accepted the Dangerous commands
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of bindings added
+    /// * `Err(String)` - Writing the config failed (nothing changed)
+    pub fn import_reviewed(
+        &self,
+        review: &ImportReview,
+        mode: ImportMode,
+        acknowledged: bool,
+    ) -> Result<usize, String> {
+        self.ensure_writable()?;
+        let (bindings, added) = review.apply_to(self.get_keybindings(), mode, acknowledged);
+
+        self.record_undo_snapshot();
         if let Err(e) = self
             .config_manager
             .borrow_mut()
             .write_bindings(&bindings)
             .map_err(|e| format!("Failed to write imported bindings: {}", e))
         {
-            let previous = self.undo_stack.borrow_mut().pop();
-            if let Some(previous) = previous {
-                self.replace_bindings(previous);
-            }
+            self.pop_undo();
             return Err(e);
         }
 
         self.replace_bindings(bindings);
 
-        Ok(())
+        Ok(added)
     }
 
     /// Updates an existing keybinding with new values
@@ -695,9 +1816,14 @@ impl Controller {
     /// This method:
     /// 1. Finds the old binding in the list
     /// 2. Replaces it with the new binding
-    /// 3. Writes changes to disk (creates automatic backup)
+    /// 3. Writes changes to disk (creates automatic backup), replacing
+    ///    only the binding's own line when its location is still accurate
     /// 4. Rebuilds the conflict detector
     ///
+    /// A binding from a read-only sourced file is overridden in the main
+    /// config instead (see [`overrides`](crate::config::overrides)), which
+    /// reloads the bindings and clears the undo history.
+    ///
     /// # Arguments
     /// * `old` - The binding to replace
     /// * `new` - The new binding values
@@ -714,6 +1840,13 @@ impl Controller {
     /// }
     /// ```
     pub fn update_keybinding(&self, old: &Keybinding, new: Keybinding) -> Result<(), String> {
+        self.ensure_writable()?;
+        if self.in_read_only_source(old) {
+            self.ensure_danger_allowed(&new)?;
+            return self.override_source_binding(old, Some(&new));
+        }
+        Self::ensure_in_main_config(old)?;
+        self.ensure_danger_allowed(&new)?;
         self.record_undo_snapshot();
         let mut bindings = self.keybindings.borrow_mut();
         let position = bindings.iter().position(|b| b == old);
@@ -729,8 +1862,13 @@ impl Controller {
         let updated_bindings = bindings.clone();
         drop(bindings);
 
-        if let Err(e) = self.write_snapshot(&updated_bindings) {
-            let previous = self.undo_stack.borrow_mut().pop();
+        if let Err(e) = self
+            .config_manager
+            .borrow_mut()
+            .write_binding_update(old, &new, &updated_bindings)
+            .map_err(|e| format!("Failed to write changes to config: {}", e))
+        {
+            let previous = self.pop_undo();
             if let Some(previous) = previous {
                 self.replace_bindings(previous);
             }
@@ -738,23 +1876,324 @@ impl Controller {
         }
 
         self.replace_bindings(updated_bindings);
+        self.apply_live(Some(old), Some(&new));
+
+        Ok(())
+    }
+
+    /// Moves a binding to the place of another, as dropped in the list
+    ///
+    /// Moved down, the binding ends up below `target`; moved up, above it.
+    /// Order only matters within a submap or device section, so both have
+    /// to be in the same one. The write is position-aware (see
+    /// [`writer`](crate::config::writer)): only the moved binding's line
+    /// moves, comments and the other lines stay where they are.
+    ///
+    /// # Arguments
+    /// * `moved` - The binding being dragged
+    /// * `target` - The binding it was dropped on
+    ///
+    /// # Returns
+    /// * `Ok(())` if successful
+    /// * `Err(String)` for bindings from sourced files or different
+    ///   sections, bindings not loaded, or a failed write
+    pub fn move_keybinding(&self, moved: &Keybinding, target: &Keybinding) -> Result<(), String> {
+        self.ensure_writable()?;
+        Self::ensure_in_main_config(moved)?;
+        Self::ensure_in_main_config(target)?;
+        if moved.submap != target.submap || moved.device != target.device {
+            return Err(
+                "Bindings can only be reordered within their own submap or device section"
+                    .to_string(),
+            );
+        }
+
+        let mut bindings = self.get_keybindings();
+        let position = |binding: &Keybinding| {
+            bindings
+                .iter()
+                .position(|b| b == binding)
+                .ok_or_else(|| "Binding not found in the keybinding list".to_string())
+        };
+        let (from, to) = (position(moved)?, position(target)?);
+        if from == to {
+            return Ok(());
+        }
+        let binding = bindings.remove(from);
+        bindings.insert(to, binding);
+
+        self.record_undo_snapshot();
+        if let Err(e) = self.write_snapshot(&bindings) {
+            let previous = self.pop_undo();
+            if let Some(previous) = previous {
+                self.replace_bindings(previous);
+            }
+            return Err(e);
+        }
+
+        self.replace_bindings(bindings);
+
+        Ok(())
+    }
+
+    /// Shows what [`add_keybinding`](Self::add_keybinding) would change in
+    /// the config, without writing
+    ///
+    /// # Returns
+    /// * `Ok(ConfigDiff)` - Line diff of the main config
+    /// * `Err(String)` - The config cannot be read
+    pub fn preview_add(&self, binding: &Keybinding) -> Result<ConfigDiff, String> {
+        let mut bindings = self.get_keybindings();
+        bindings.push(binding.clone());
+        self.config_manager
+            .borrow()
+            .preview_bindings(&bindings)
+            .map_err(|e| format!("Failed to preview changes: {}", e))
+    }
+
+    /// Shows what [`update_keybinding`](Self::update_keybinding) would
+    /// change in the config, without writing
+    ///
+    /// # Returns
+    /// * `Ok(ConfigDiff)` - Line diff of the main config
+    /// * `Err(String)` - The binding is not loaded, lives in a sourced file,
+    ///   or the config cannot be read
+    pub fn preview_update(&self, old: &Keybinding, new: &Keybinding) -> Result<ConfigDiff, String> {
+        if self.in_read_only_source(old) {
+            return self
+                .config_manager
+                .borrow()
+                .preview_source_override(old, Some(new), &self.get_keybindings())
+                .map_err(|e| format!("Failed to preview changes: {}", e));
+        }
+        Self::ensure_in_main_config(old)?;
+        let mut bindings = self.get_keybindings();
+        let position = bindings
+            .iter()
+            .position(|b| b == old)
+            .ok_or_else(|| "Binding not found in the keybinding list".to_string())?;
+        bindings[position] = new.clone();
+
+        self.config_manager
+            .borrow()
+            .preview_binding_update(old, new, &bindings)
+            .map_err(|e| format!("Failed to preview changes: {}", e))
+    }
+
+    /// Suggests comment groups for a config that has none
+    ///
+    /// # Returns
+    ///
+    /// * `Some(groups)` - Suggested groups, in the order they would be written
+    /// * `None` - The config already uses comment groups, has no bindings,
+    ///   or could not be read
+    pub fn suggest_groups(&self) -> Option<Vec<GroupSuggestion>> {
+        let content = self.config_manager.borrow().read_config().ok()?;
+        if grouping::has_comment_groups(&content) {
+            return None;
+        }
+
+        let groups = grouping::suggest_groups(&self.keybindings.borrow());
+        (!groups.is_empty()).then_some(groups)
+    }
+
+    /// Accepts suggested groups and writes them back as comment headers
+    ///
+    /// Bindings are reordered so that each group is contiguous. Like other
+    /// edits, this creates a backup and can be undone.
+    ///
+    /// # Arguments
+    /// * `groups` - Groups returned by [`suggest_groups`](Self::suggest_groups)
+    pub fn apply_group_suggestions(&self, groups: &[GroupSuggestion]) -> Result<(), String> {
+        self.ensure_writable()?;
+        self.record_undo_snapshot();
+
+        if let Err(e) = self
+            .config_manager
+            .borrow_mut()
+            .write_grouped_bindings(groups)
+            .map_err(|e| format!("Failed to write grouped bindings: {}", e))
+        {
+            self.pop_undo();
+            return Err(e);
+        }
+
+        let reordered = groups
+            .iter()
+            .flat_map(|group| group.bindings.iter().cloned())
+            .collect();
+        self.replace_bindings(reordered);
+
+        Ok(())
+    }
+
+    /// Returns the bindings that repeat another binding in different formatting
+    ///
+    /// These are the copies [`merge_duplicates`](Self::merge_duplicates)
+    /// would remove; see [`equivalence::redundant_copies`].
+    pub fn find_redundant_copies(&self) -> Vec<Keybinding> {
+        let bindings = self.keybindings.borrow();
+        equivalence::redundant_copies(&bindings)
+            .into_iter()
+            .map(|i| bindings[i].clone())
+            .collect()
+    }
+
+    /// Removes every redundant copy of a binding in one write
+    ///
+    /// All copies go in a single transaction, so there is one backup and
+    /// one undo step however many were removed.
+    ///
+    /// # Returns
+    /// * `Ok(count)` - Number of copies removed (0: nothing written)
+    /// * `Err(String)` - Failed to write changes
+    pub fn merge_duplicates(&self) -> Result<usize, String> {
+        self.ensure_writable()?;
+        let bindings = self.get_keybindings();
+        let merged = equivalence::merge_duplicates(&bindings);
+        let removed = bindings.len() - merged.len();
+        if removed == 0 {
+            return Ok(0);
+        }
+
+        self.record_undo_snapshot();
+        if let Err(e) = self.write_snapshot(&merged) {
+            self.pop_undo();
+            return Err(e);
+        }
+        self.replace_bindings(merged);
+
+        Ok(removed)
+    }
+
+    /// Resolves one conflict in a single write
+    ///
+    /// See [`resolve_conflicts`](Self::resolve_conflicts).
+    pub fn resolve_conflict(
+        &self,
+        conflict: &Conflict,
+        resolution: ConflictResolution,
+    ) -> Result<(), String> {
+        self.resolve_conflicts(&[(conflict.clone(), resolution)])
+    }
+
+    /// Resolves several conflicts as one transaction
+    ///
+    /// Every resolution is applied to the binding list first and the result
+    /// is written once, so there is one backup and one undo step however
+    /// many bindings were deleted or moved. If any resolution cannot be
+    /// applied, nothing is written.
+    ///
+    /// # Arguments
+    /// * `resolutions` - Conflicts from [`get_conflicts`](Self::get_conflicts)
+    ///   with the resolution chosen for each
+    ///
+    /// # Errors
+    /// Returns an error without writing when a binding of a conflict is no
+    /// longer loaded, would have to change but lives in a sourced file, or
+    /// would be moved to a key combo that is already bound.
+    pub fn resolve_conflicts(
+        &self,
+        resolutions: &[(Conflict, ConflictResolution)],
+    ) -> Result<(), String> {
+        self.ensure_writable()?;
+
+        // Deleted bindings become `None` so positions stay valid
+        let mut bindings: Vec<Option<Keybinding>> =
+            self.get_keybindings().into_iter().map(Some).collect();
+
+        for (conflict, resolution) in resolutions {
+            let positions = Self::conflict_positions(&bindings, conflict)?;
+            let last = positions.len() - 1;
+            let deleted = match resolution {
+                ConflictResolution::KeepFirst => &positions[1..],
+                ConflictResolution::KeepLast => &positions[..last],
+                ConflictResolution::DeleteAll => &positions[..],
+                ConflictResolution::Rebind { index, to } => {
+                    let Some(binding) = conflict.conflicting_bindings.get(*index) else {
+                        return Err(format!(
+                            "The conflict on {} has no binding {}",
+                            conflict.key_combo, index
+                        ));
+                    };
+                    Self::ensure_in_main_config(binding)?;
+
+                    let taken = bindings
+                        .iter()
+                        .flatten()
+                        .any(|other| other.key_combo == *to && other.submap == binding.submap);
+                    if taken {
+                        return Err(format!("{} is already bound", to));
+                    }
+
+                    if let Some(moved) = bindings[positions[*index]].as_mut() {
+                        moved.key_combo = to.clone();
+                    }
+                    continue;
+                }
+            };
+
+            for &position in deleted {
+                if let Some(binding) = bindings[position].take() {
+                    Self::ensure_in_main_config(&binding)?;
+                }
+            }
+        }
+
+        let updated: Vec<Keybinding> = bindings.into_iter().flatten().collect();
+        self.record_undo_snapshot();
+        if let Err(e) = self.write_snapshot(&updated) {
+            self.pop_undo();
+            return Err(e);
+        }
+        self.replace_bindings(updated);
 
         Ok(())
     }
 
+    /// Finds the list positions of a conflict's bindings
+    ///
+    /// Positions are in the order of `conflicting_bindings`, which is config
+    /// order. Each binding claims the first unclaimed equal entry, so exact
+    /// copies of a binding map to distinct positions.
+    fn conflict_positions(
+        bindings: &[Option<Keybinding>],
+        conflict: &Conflict,
+    ) -> Result<Vec<usize>, String> {
+        let mut positions: Vec<usize> = Vec::new();
+        for binding in &conflict.conflicting_bindings {
+            let position = bindings
+                .iter()
+                .enumerate()
+                .position(|(index, candidate)| {
+                    candidate.as_ref() == Some(binding) && !positions.contains(&index)
+                })
+                .ok_or_else(|| format!("'{}' is no longer in the config", binding))?;
+            positions.push(position);
+        }
+
+        if positions.is_empty() {
+            return Err(format!(
+                "The conflict on {} has no bindings",
+                conflict.key_combo
+            ));
+        }
+        Ok(positions)
+    }
+
+    /// Reverts the last change, writing the bindings as they were before it
+    ///
+    /// If the write fails, the history is left as it was, so the same step
+    /// can be undone again.
     pub fn undo(&self) -> Result<(), String> {
-        let Some(previous) = self.undo_stack.borrow_mut().pop() else {
+        self.ensure_writable()?;
+        let previous = self.history.borrow_mut().undo(&self.keybindings.borrow());
+        let Some(previous) = previous else {
             return Err("Nothing to undo".to_string());
         };
 
-        let current = self.keybindings.borrow().clone();
-        self.redo_stack.borrow_mut().push(current);
-
         if let Err(e) = self.write_snapshot(&previous) {
-            let redo = self.redo_stack.borrow_mut().pop();
-            if let Some(redo) = redo {
-                self.undo_stack.borrow_mut().push(redo);
-            }
+            self.history.borrow_mut().redo(&previous);
             return Err(e);
         }
 
@@ -762,19 +2201,18 @@ impl Controller {
         Ok(())
     }
 
+    /// Re-applies the last undone change
+    ///
+    /// If the write fails, the history is left as it was.
     pub fn redo(&self) -> Result<(), String> {
-        let Some(next) = self.redo_stack.borrow_mut().pop() else {
+        self.ensure_writable()?;
+        let next = self.history.borrow_mut().redo(&self.keybindings.borrow());
+        let Some(next) = next else {
             return Err("Nothing to redo".to_string());
         };
 
-        let current = self.keybindings.borrow().clone();
-        self.undo_stack.borrow_mut().push(current);
-
         if let Err(e) = self.write_snapshot(&next) {
-            let undo = self.undo_stack.borrow_mut().pop();
-            if let Some(undo) = undo {
-                self.redo_stack.borrow_mut().push(undo);
-            }
+            self.history.borrow_mut().undo(&next);
             return Err(e);
         }
 
@@ -782,15 +2220,69 @@ impl Controller {
         Ok(())
     }
 
+    /// Chooses whether requests that change Hyprland are sent
+    ///
+    /// `DryRun` by default, so a controller never touches the running
+    /// Hyprland unless asked to: reloads, live edits and `binds` options
+    /// are validated but not sent. The GUI opts in to `Live`.
+    pub fn set_client_mode(&self, mode: ClientMode) {
+        self.client_mode.set(mode);
+    }
+
+    /// Selects the Hyprland instance to talk to
+    ///
+    /// Only matters when several instances run, e.g. a nested Hyprland;
+    /// `None` goes back to the one `HYPRLAND_INSTANCE_SIGNATURE` names.
+    /// Every later query, live edit and reload goes to the selected one.
+    pub fn set_instance(&self, instance: Option<HyprlandInstance>) {
+        *self.instance.borrow_mut() = instance;
+        self.hyprland_version.set(None);
+    }
+
+    /// Lists the Hyprland instances that accept connections
+    pub fn get_instances(&self) -> Vec<HyprlandInstance> {
+        InstanceLocator::from_env()
+            .instances()
+            .into_iter()
+            .filter(HyprlandInstance::is_running)
+            .collect()
+    }
+
+    /// Describes the Hyprland instance the controller talks to
+    ///
+    /// Probed on every call, so a status display notices Hyprland
+    /// stopping or starting.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(InstanceInfo)` - The instance accepts connections
+    /// * `None` - No instance found, or it does not answer
+    pub fn get_instance_info(&self) -> Option<InstanceInfo> {
+        self.hyprland_client(ClientMode::ReadOnly)
+            .instance_info()
+            .ok()
+    }
+
+    /// Creates an IPC client for the selected instance
+    fn hyprland_client(&self, mode: ClientMode) -> HyprlandClient {
+        match self.instance.borrow().clone() {
+            Some(instance) => HyprlandClient::new(mode).with_instance(instance),
+            None => HyprlandClient::new(mode),
+        }
+    }
+
     /// Applies changes to running Hyprland instance
     ///
-    /// Triggers Hyprland to reload its configuration file, making all
-    /// pending changes take effect immediately without restart.
+    /// Triggers Hyprland to reload its configuration file over IPC, making
+    /// all pending changes take effect immediately without restart. Only
+    /// sent in `Live` mode, see [`Self::set_client_mode`].
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Hyprland reloaded successfully
-    /// * `Err(String)` - Hyprland not running or reload failed
+    /// * `Ok(())` - Hyprland reloaded successfully (or, in `DryRun` mode,
+    ///   would have been)
+    /// * `Err(String)` - Hyprland not running, reload refused, or the
+    ///   client is read-only
     ///
     /// # Example
     ///
@@ -803,26 +2295,348 @@ impl Controller {
     /// # Ok::<(), String>(())
     /// ```
     pub fn apply_to_hyprland(&self) -> Result<(), String> {
-        Command::new("hyprctl")
-            .arg("reload")
-            .output()
-            .map_err(|e| format!("Failed to run hyprctl: {}", e))?;
+        self.hyprland_client(self.client_mode.get())
+            .reload()
+            .map_err(|e| match e {
+                ConfigError::HyprlandNotRunning(socket) => format!(
+                    "Hyprland is not running ({}). The changes are saved and take \
+                     effect when Hyprland starts.",
+                    socket
+                ),
+                e => format!("Failed to reload Hyprland: {}", e),
+            })
+    }
+
+    /// Lists the workspaces of the running Hyprland instance
+    ///
+    /// Uses a read-only IPC client, so the query can never change
+    /// Hyprland's state.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<WorkspaceInfo>)` - Current workspaces with window counts
+    /// * `None` - Hyprland is not running or did not answer
+    pub fn get_workspaces(&self) -> Option<Vec<WorkspaceInfo>> {
+        self.hyprland_client(ClientMode::ReadOnly).workspaces().ok()
+    }
+
+    /// Returns the focused workspace of the running Hyprland instance
+    ///
+    /// # Returns
+    ///
+    /// * `Some(WorkspaceInfo)` - The workspace on the focused monitor
+    /// * `None` - Hyprland is not running or did not answer
+    pub fn get_active_workspace(&self) -> Option<WorkspaceInfo> {
+        self.hyprland_client(ClientMode::ReadOnly)
+            .get_active_workspace()
+            .ok()
+    }
+
+    /// Returns the focused window of the running Hyprland instance
+    ///
+    /// # Returns
+    ///
+    /// * `Some(WindowInfo)` - The focused window
+    /// * `None` - No window has focus, or Hyprland did not answer
+    pub fn get_active_window(&self) -> Option<WindowInfo> {
+        self.hyprland_client(ClientMode::ReadOnly)
+            .active_window()
+            .ok()
+            .flatten()
+    }
+
+    /// Returns the keyboards and mice of the running Hyprland instance
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Devices)` - Keyboards with their layouts, and mice
+    /// * `None` - Hyprland is not running or did not answer
+    pub fn get_input_devices(&self) -> Option<Devices> {
+        self.hyprland_client(ClientMode::ReadOnly)
+            .get_devices()
+            .ok()
+    }
+
+    /// Returns the version of the running Hyprland instance
+    ///
+    /// Queried once over read-only IPC and cached for the session.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(HyprlandVersion)` - Version reported by Hyprland
+    /// * `None` - Hyprland is not running or did not answer
+    pub fn get_hyprland_version(&self) -> Option<HyprlandVersion> {
+        if let Some(version) = self.hyprland_version.get() {
+            return version;
+        }
+        let version = self
+            .hyprland_client(ClientMode::ReadOnly)
+            .get_version()
+            .ok();
+        self.hyprland_version.set(Some(version));
+        version
+    }
+
+    /// Explains why a dispatcher does not work with the running Hyprland
+    ///
+    /// # Returns
+    ///
+    /// * `Some(String)` - The dispatcher is too new or was removed
+    /// * `None` - The dispatcher is fine, or the version is unknown
+    pub fn get_dispatcher_warning(&self, dispatcher: &str) -> Option<String> {
+        compat::dispatcher_warning(dispatcher, self.get_hyprland_version()?)
+    }
+
+    /// Returns the catalogued dispatchers to offer in the edit dialog
+    ///
+    /// Limited to those the running Hyprland supports. Without a version,
+    /// every dispatcher that has not been removed is offered.
+    pub fn get_available_dispatchers(&self) -> Vec<&'static DispatcherSpec> {
+        let version = self.get_hyprland_version();
+        DISPATCHER_CATALOGUE
+            .iter()
+            .filter(|spec| match version {
+                Some(version) => compat::is_dispatcher_available(spec.name, version),
+                None => compat::dispatcher_availability(spec.name)
+                    .is_none_or(|entry| entry.removed.is_none()),
+            })
+            .collect()
+    }
+
+    /// Checks dispatcher arguments against the dispatcher catalogue
+    ///
+    /// For feedback while editing; writes check the same through
+    /// [`Self::validate_keybinding`].
+    ///
+    /// # Returns
+    ///
+    /// * `Some(String)` - Why the arguments do not fit the dispatcher
+    /// * `None` - They fit, or the dispatcher is not catalogued
+    pub fn get_argument_problem(&self, dispatcher: &str, args: &str) -> Option<String> {
+        dispatchers::validate_arguments(dispatcher, Some(args)).err()
+    }
+
+    /// Returns statistics about the loaded bindings
+    ///
+    /// See [`crate::core::stats`].
+    pub fn get_statistics(&self) -> BindingStats {
+        BindingStats::collect(&self.keybindings.borrow())
+    }
+
+    /// Returns the topics of the Help window
+    ///
+    /// The built-in topics of [`HelpIndex`] plus the danger rules.
+    pub fn get_help_index(&self) -> HelpIndex {
+        HelpIndex::new(patterns::danger_rule_topics())
+    }
+
+    /// Returns submaps that cannot be left and misplaced catchall bindings
+    ///
+    /// See [`crate::core::submap`].
+    pub fn get_submap_warnings(&self) -> Vec<SubmapWarning> {
+        submap_warnings(&self.keybindings.borrow())
+    }
+
+    /// Returns the submap warning that concerns a binding, if any
+    ///
+    /// A binding is concerned when its submap cannot be left, or when it is
+    /// itself a catchall outside any submap.
+    pub fn get_submap_warning_for(&self, binding: &Keybinding) -> Option<SubmapWarning> {
+        self.get_submap_warnings()
+            .into_iter()
+            .find(|warning| match warning {
+                SubmapWarning::NoEscape { submap } => binding.submap.as_ref() == Some(submap),
+                SubmapWarning::CatchallOutsideSubmap { key_combo } => {
+                    binding.submap.is_none() && binding.key_combo == *key_combo
+                }
+            })
+    }
+
+    /// Returns bindings whose key some configured keyboard layouts lack
+    ///
+    /// See [`crate::core::layouts`].
+    pub fn get_layout_warnings(&self) -> Vec<LayoutWarning> {
+        layout_warnings(&self.keybindings.borrow(), &self.keyboard_layouts.borrow())
+    }
+
+    /// Returns the layout warning that concerns a binding, if any
+    pub fn get_layout_warning_for(&self, binding: &Keybinding) -> Option<LayoutWarning> {
+        self.get_layout_warnings()
+            .into_iter()
+            .find(|warning| warning.binding == *binding)
+    }
+
+    /// Returns the bindings on combos reserved by Hyprland or common tools
+    ///
+    /// Empty while the check is disabled (see
+    /// [`set_reserved_check`](Self::set_reserved_check)).
+    pub fn get_reserved_shadows(&self) -> Vec<ReservedShadow> {
+        self.conflict_detector.borrow().find_reserved_shadows()
+    }
+
+    /// Returns why a binding's combo is reserved, if it is and the check is on
+    pub fn get_reserved_shadow_for(&self, binding: &Keybinding) -> Option<ReservedShadow> {
+        if !self.reserved_check.get() {
+            return None;
+        }
+        reserved::reserved_shadow(binding)
+    }
+
+    /// Returns what an `exec` binding starts, if it is not installed
+    ///
+    /// See [`crate::config::resolve`].
+    pub fn get_missing_program_for(&self, binding: &Keybinding) -> Option<MissingProgram> {
+        let resolver = self.command_resolver.get_or_init(CommandResolver::from_env);
+        resolve::missing_program(binding, resolver)
+    }
+
+    /// Returns the `binds` options that change what a binding does
+    ///
+    /// # Returns
+    ///
+    /// Each affecting option with the value in effect (the config's, else
+    /// Hyprland's default)
+    pub fn get_relevant_binds_options(
+        &self,
+        binding: &Keybinding,
+    ) -> Vec<(&'static BindsOption, String)> {
+        self.binds_options.borrow().relevant_to(binding)
+    }
+
+    /// Sets a `binds` option
+    ///
+    /// Writes the option to the config, then sets it in the running
+    /// Hyprland over IPC so it takes effect right away. The IPC step is
+    /// best effort: without a running Hyprland the config change is enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Option name inside the `binds` section
+    /// * `value` - New value
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The config was updated
+    /// * `Err(String)` - Unknown option, bad value or write failure
+    pub fn set_binds_option(&self, name: &str, value: &str) -> Result<(), String> {
+        self.ensure_writable()?;
+
+        self.config_manager
+            .borrow_mut()
+            .write_binds_option(name, value)
+            .map_err(|e| format!("Failed to write binds:{}: {}", name, e))?;
+
+        let content = self
+            .config_manager
+            .borrow()
+            .read_config()
+            .map_err(|e| e.to_string())?;
+        *self.binds_options.borrow_mut() = BindsOptions::from_config(&content);
+
+        if self.get_hyprland_version().is_some() {
+            if let Err(e) = self
+                .hyprland_client(self.client_mode.get())
+                .set_binds_option(name, value)
+            {
+                eprintln!("⚠️  Could not set binds:{} live: {}", name, e);
+            }
+        }
 
         Ok(())
     }
-}
 
-fn candidate_keys() -> Vec<&'static str> {
-    let mut keys = Vec::with_capacity(48);
-    keys.extend([
-        "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R",
-        "S", "T", "U", "V", "W", "X", "Y", "Z",
-    ]);
-    keys.extend(["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]);
-    const FUNCTION_KEYS: [&str; 12] = [
-        "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12",
-    ];
-    keys.extend(FUNCTION_KEYS);
-
-    keys
+    /// Binds a key in the running Hyprland so it can be tried before saving
+    ///
+    /// Nothing is written to the config. The binding stays until the
+    /// returned guard is finished or dropped, which also puts back whatever
+    /// Hyprland had on the combo. Refused like an addition when the danger
+    /// policy blocks the command, since pressing the key runs it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TemporaryBind)` - Bound; keep the guard for as long as the trial
+    ///   lasts
+    /// * `Err(String)` - Blocked command, submap binding, or Hyprland not
+    ///   running
+    pub fn try_keybinding(&self, binding: &Keybinding) -> Result<TemporaryBind, String> {
+        self.ensure_danger_allowed(binding)?;
+
+        self.hyprland_client(self.client_mode.get())
+            .add_temporary_bind(binding)
+            .map_err(|e| match e {
+                ConfigError::HyprlandNotRunning(_) => {
+                    "Hyprland is not running, so the binding cannot be tried".to_string()
+                }
+                e => format!("Could not try the binding: {}", e),
+            })
+    }
+
+    /// Sends one edit to the running Hyprland when live apply is on
+    ///
+    /// Follows the "Apply edits live" preference: only the changed binding
+    /// is bound or unbound over IPC, instead of reloading the whole config.
+    /// The config has already been written, so a failure (Hyprland gone,
+    /// a binding inside a submap) only leaves Hyprland behind until the next
+    /// apply, and is logged rather than returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - The binding before the edit, `None` for an addition
+    /// * `new` - The binding after the edit, `None` for a deletion
+    fn apply_live(&self, old: Option<&Keybinding>, new: Option<&Keybinding>) {
+        if !self.settings.get().live_apply || self.get_hyprland_version().is_none() {
+            return;
+        }
+
+        let client = self.hyprland_client(self.client_mode.get());
+        let result = match (old, new) {
+            (Some(old), Some(new)) => client.replace_bind(old, new),
+            (Some(old), None) => client.remove_bind(old),
+            (None, Some(new)) => client.add_bind(new),
+            (None, None) => Ok(()),
+        };
+        if let Err(e) = result {
+            eprintln!("⚠️  Could not apply the change live: {}", e);
+        }
+    }
+
+    /// Requests a debounced apply to the running Hyprland instance
+    ///
+    /// Requests arriving within the coalescing window of each other (bulk
+    /// edits, an import, repeated presses of the apply shortcut) are merged
+    /// so that Hyprland is reloaded only once, after the burst goes quiet.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Duration)` - A new burst started; call
+    ///   [`poll_pending_apply`](Self::poll_pending_apply) after this delay
+    /// * `None` - Joined a burst that is already waiting
+    pub fn request_apply(&self) -> Option<Duration> {
+        let mut debouncer = self.reload_debouncer.borrow_mut();
+        debouncer
+            .request(Instant::now())
+            .then(|| debouncer.window())
+    }
+
+    /// Sends the pending apply if its coalescing window has elapsed
+    ///
+    /// # Returns
+    ///
+    /// * `ApplyPoll::Idle` - Nothing was requested
+    /// * `ApplyPoll::Waiting(delay)` - More requests arrived; poll again after `delay`
+    /// * `ApplyPoll::Applied(result)` - Hyprland was reloaded (or the reload failed)
+    pub fn poll_pending_apply(&self) -> ApplyPoll {
+        let now = Instant::now();
+        let mut debouncer = self.reload_debouncer.borrow_mut();
+
+        if debouncer.poll(now) {
+            drop(debouncer);
+            return ApplyPoll::Applied(self.apply_to_hyprland());
+        }
+
+        match debouncer.time_until_due(now) {
+            Some(remaining) => ApplyPoll::Waiting(remaining),
+            None => ApplyPoll::Idle,
+        }
+    }
 }
@@ -27,7 +27,9 @@
 //! ├── mod.rs          // This file - exports and initialisation
 //! ├── app.rs          // GTK4 Application setup
 //! ├── controller.rs   // MVC Controller
-//! ├── actions.rs      // GTK action setup (quit, export, import)
+//! ├── undo.rs         // Undo/redo history of binding snapshots
+//! ├── actions.rs      // GTK action setup (quit, export, import, preferences)
+//! ├── theme.rs        // Stylesheets and accessibility preferences
 //! ├── builders/       // UI building functions
 //! └── components/     // Reusable UI widgets
 //! ```
@@ -38,6 +40,8 @@ mod builders;
 pub mod components;
 pub mod controller;
 pub mod file_watcher;
+pub mod theme;
+pub mod undo;
 
 pub use {app::App, controller::Controller};
 
@@ -18,6 +18,7 @@
 //! - Timestamp formatting and parsing
 //! - Fallback behaviour for malformed filenames
 //! - Edge cases (missing underscore, invalid lengths, etc.)
+//! - Compressed backup names
 
 use std::path::PathBuf;
 
@@ -80,3 +81,22 @@ fn test_format_backup_display_unknown_backup() {
     let result = BackupDialog::format_backup_display(&path);
     assert_eq!(result, "Unknown backup");
 }
+
+#[test]
+fn test_format_backup_display_pristine() {
+    let path = PathBuf::from("/backups/hyprland.conf.pristine");
+    let result = BackupDialog::format_backup_display(&path);
+    assert_eq!(result, "Original config (before first edit)");
+}
+
+#[test]
+fn test_format_backup_display_compressed() {
+    for name in [
+        "hyprland.conf.2025-10-15_143025.gz",
+        "hyprland.conf.2025-10-15_143025.zst",
+    ] {
+        let path = PathBuf::from("/backups").join(name);
+        let result = BackupDialog::format_backup_display(&path);
+        assert_eq!(result, "2025-10-15 14:30:25");
+    }
+}
@@ -20,8 +20,25 @@ use std::{fs, path::PathBuf};
 use tempfile::TempDir;
 
 use crate::{
-    core::{BindType, KeyCombo, Keybinding, Modifier},
-    ui::controller::{KeyComboAssistance, KeyComboAvailability},
+    config::{
+        danger::DangerLevel,
+        diff::LineChange,
+        export::ExportFormat,
+        interop::ForeignConfig,
+        settings::{DangerPolicy, Settings},
+    },
+    core::{
+        cheatsheet::CheatSheetFormat,
+        compare::{RowStatus, Side},
+        lint::{LintPolicy, LintRule},
+        submap::SubmapWarning,
+        BindFlag, BindType, ConflictKind, KeyCombo, Keybinding, Modifier,
+    },
+    ipc::ClientMode,
+    ui::controller::{
+        ApplyPoll, BindingBadges, ConflictResolution, ImportMode, KeyComboAssistance,
+        KeyComboAvailability,
+    },
     ui::Controller,
 };
 
@@ -114,6 +131,102 @@ fn test_filter_keybindings_by_key() {
     assert_eq!(filtered.len(), 2, "Should find 2 bindings with 'SUPER+K'");
 }
 
+#[test]
+fn test_bindd_description_is_searchable_and_editable() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bindd = SUPER, Q, Close window, killactive\nbind = SUPER, K, exec, kitty\n",
+    )
+    .unwrap();
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let found = controller.filter_keybindings("close");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].description.as_deref(), Some("Close window"));
+
+    let mut edited = found[0].clone();
+    edited.description = Some("Close the focused window".to_string());
+    controller.update_keybinding(&found[0], edited).unwrap();
+
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(content.starts_with("bindd = SUPER, Q, Close the focused window, killactive\n"));
+}
+
+#[test]
+fn test_move_keybinding_reorders_within_its_section() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "# Launchers\n\
+         bind = SUPER, K, exec, firefox\n\
+         bind = SUPER, M, exec, kitty\n\
+         \n\
+         # Windows\n\
+         bind = SUPER, Q, exec, wofi\n\
+         \n\
+         submap = resize\n\
+         bind = , L, resizeactive, 10 0\n\
+         submap = reset\n",
+    )
+    .unwrap();
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+    let bindings = controller.get_keybindings();
+    let (firefox, kitty, wofi, resize) = (&bindings[0], &bindings[1], &bindings[2], &bindings[3]);
+    let bind_lines = || {
+        fs::read_to_string(&config_path)
+            .unwrap()
+            .lines()
+            .filter(|line| line.starts_with("bind"))
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    // Moved up, the binding goes above the one it was dropped on
+    controller.move_keybinding(wofi, firefox).unwrap();
+    assert_eq!(
+        bind_lines(),
+        [
+            "bind = SUPER, Q, exec, wofi",
+            "bind = SUPER, K, exec, firefox",
+            "bind = SUPER, M, exec, kitty",
+            "bind = , L, resizeactive, 10 0",
+        ]
+    );
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(content.contains("# Launchers\n"));
+    assert!(content.contains("# Windows\n"));
+
+    // Moved down, below it
+    controller.move_keybinding(wofi, kitty).unwrap();
+    assert_eq!(
+        controller.get_keybindings()[2].args.as_deref(),
+        Some("wofi")
+    );
+    assert_eq!(bind_lines()[2], "bind = SUPER, Q, exec, wofi");
+
+    assert!(controller.move_keybinding(resize, firefox).is_err());
+}
+
+#[test]
+fn test_filter_keybindings_is_fuzzy_and_ranked() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let filtered = controller.filter_keybindings("ffx");
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].args.as_deref(), Some("firefox"));
+
+    // "kitty" itself outranks the scattered k-i-t of "killactive"
+    let filtered = controller.filter_keybindings("kit");
+    assert_eq!(filtered[0].args.as_deref(), Some("kitty"));
+}
+
 #[test]
 fn test_filter_keybindings_empty_query() {
     let (_temp_dir, config_path) = create_test_config();
@@ -248,12 +361,11 @@ fn test_search_persists_after_add() {
     controller.set_search_query("firefox".to_string());
 
     // Add a new binding (doesn't match filter)
-    let new_binding = Keybinding {
-        bind_type: BindType::Bind,
-        key_combo: KeyCombo::new(vec![Modifier::Super], "X"),
-        dispatcher: "exec".to_string(),
-        args: Some("code".to_string()),
-    };
+    let new_binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "X"),
+        "exec",
+        Some("code"),
+    );
 
     controller.add_keybinding(new_binding).unwrap();
 
@@ -415,12 +527,11 @@ fn test_undo_reverts_added_binding() {
     let controller = Controller::new(config_path).unwrap();
     controller.load_keybindings().unwrap();
 
-    let new_binding = Keybinding {
-        bind_type: BindType::Bind,
-        key_combo: KeyCombo::new(vec![Modifier::Super], "X"),
-        dispatcher: "exec".to_string(),
-        args: Some("code".to_string()),
-    };
+    let new_binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "X"),
+        "exec",
+        Some("code"),
+    );
 
     controller.add_keybinding(new_binding).unwrap();
     assert_eq!(controller.keybinding_count(), 6);
@@ -442,12 +553,11 @@ fn test_redo_reapplies_undone_change() {
     let controller = Controller::new(config_path).unwrap();
     controller.load_keybindings().unwrap();
 
-    let new_binding = Keybinding {
-        bind_type: BindType::Bind,
-        key_combo: KeyCombo::new(vec![Modifier::Super], "X"),
-        dispatcher: "exec".to_string(),
-        args: Some("code".to_string()),
-    };
+    let new_binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "X"),
+        "exec",
+        Some("code"),
+    );
 
     controller.add_keybinding(new_binding).unwrap();
     controller.undo().unwrap();
@@ -475,3 +585,1255 @@ fn test_undo_reverts_updated_binding() {
     assert_eq!(controller.filter_keybindings("brave").len(), 0);
     assert_eq!(controller.filter_keybindings("firefox").len(), 1);
 }
+
+#[test]
+fn test_strict_mode_blocks_writes_with_diagnostics() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, "bind = SUPER_HYPER, K, exec, kitty\n").unwrap();
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+    controller.set_strict_mode(true);
+
+    assert_eq!(controller.get_parse_diagnostics().len(), 1);
+
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "M"),
+        "exec",
+        Some("firefox"),
+    );
+    let result = controller.add_keybinding(binding);
+
+    assert!(result.is_err(), "Strict mode should refuse the write");
+    assert!(
+        !controller.can_undo(),
+        "Refused write should not touch history"
+    );
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "bind = SUPER_HYPER, K, exec, kitty\n"
+    );
+}
+
+#[test]
+fn test_strict_mode_allows_writes_for_clean_config() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+    controller.set_strict_mode(true);
+
+    assert!(controller.get_parse_diagnostics().is_empty());
+
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "N"),
+        "exec",
+        Some("nautilus"),
+    );
+    assert!(controller.add_keybinding(binding).is_ok());
+}
+
+#[test]
+fn test_strict_preference_turns_strict_mode_on() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, "bind = SUPER_HYPER, K, exec, kitty\n").unwrap();
+
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+    assert!(!controller.is_strict_mode());
+    controller.set_settings(Settings {
+        strict: true,
+        ..Settings::default()
+    });
+
+    assert!(controller.is_strict_mode());
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "M"),
+        "exec",
+        Some("firefox"),
+    );
+    assert!(controller.add_keybinding(binding).is_err());
+}
+
+#[test]
+fn test_binding_badges_report_conflicts_and_danger() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER, K, exec, firefox\n\
+         bind = SUPER, K, exec, chrome\n\
+         bind = SUPER, D, exec, chmod 777 ~/.ssh\n\
+         bind = SUPER, F, togglefloating\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+    let bindings = controller.get_keybindings();
+
+    let conflicted = controller.get_binding_badges(&bindings[0]);
+    assert_eq!(conflicted.conflict_count, 1);
+    assert_eq!(conflicted.danger_level, None);
+
+    let dangerous = controller.get_binding_badges(&bindings[2]);
+    assert_eq!(dangerous.conflict_count, 0);
+    assert_eq!(dangerous.danger_level, Some(DangerLevel::Dangerous));
+
+    let plain = controller.get_binding_badges(&bindings[3]);
+    assert_eq!(plain, BindingBadges::default());
+    assert!(controller.get_danger_assessment(&bindings[3]).is_none());
+}
+
+#[test]
+fn test_apply_requests_are_coalesced() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+
+    assert_eq!(controller.poll_pending_apply(), ApplyPoll::Idle);
+
+    // Only the first request of a burst arms the timer
+    assert!(controller.request_apply().is_some());
+    assert!(controller.request_apply().is_none());
+    assert!(controller.request_apply().is_none());
+
+    // Inside the window nothing is sent yet
+    assert!(matches!(
+        controller.poll_pending_apply(),
+        ApplyPoll::Waiting(_)
+    ));
+}
+
+#[test]
+fn test_apply_is_only_sent_after_opting_in() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+
+    // DryRun by default: validated, never sent
+    assert!(controller.apply_to_hyprland().is_ok());
+
+    controller.set_client_mode(ClientMode::ReadOnly);
+    assert!(controller.apply_to_hyprland().is_err());
+}
+
+#[test]
+fn test_only_safe_commands_are_testable() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+
+    assert!(controller
+        .check_command_testable("firefox --version")
+        .is_ok());
+    assert!(controller.check_command_testable("").is_err());
+    assert!(controller
+        .check_command_testable("chmod 777 ~/.ssh")
+        .is_err());
+    assert!(controller
+        .check_command_testable("echo hi; reboot")
+        .is_err());
+}
+
+#[test]
+fn test_accepted_group_suggestions_are_written_as_headers() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "general {\n    gaps_in = 5\n}\n\
+         bind = SUPER, 1, workspace, 1\n\
+         bind = SUPER, B, exec, firefox\n\
+         bind = SUPER, Q, killactive\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let groups = controller.suggest_groups().unwrap();
+    assert_eq!(groups.len(), 3);
+    controller.apply_group_suggestions(&groups).unwrap();
+
+    let written = fs::read_to_string(&config_path).unwrap();
+    assert!(written.contains("gaps_in = 5"));
+    assert!(written.contains(
+        "# Window Management\nbind = SUPER, Q, killactive\n\n\
+         # Workspaces\nbind = SUPER, 1, workspace, 1\n\n\
+         # Launchers\nbind = SUPER, B, exec, firefox\n"
+    ));
+    assert_eq!(controller.get_keybindings()[0].dispatcher, "killactive");
+    assert!(controller.can_undo());
+
+    // Once grouped, nothing more is suggested
+    assert!(controller.suggest_groups().is_none());
+}
+
+#[test]
+fn test_grouped_config_gets_no_suggestions() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    assert!(controller.suggest_groups().is_none());
+}
+
+#[test]
+fn test_health_report_counts_conflicts() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let report = controller.get_health_report();
+    assert_eq!(report.conflicts, controller.conflict_count());
+    assert!(report.score < 100);
+}
+
+#[test]
+fn test_merge_with_backup_is_one_undoable_write() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    let backup_path = temp_dir.path().join("hyprland.conf.backup");
+    fs::write(
+        &config_path,
+        "bind = SUPER, K, exec, kitty\nbind = SUPER, Q, killactive\n",
+    )
+    .unwrap();
+    fs::write(
+        &backup_path,
+        "bind = SUPER, K, exec, foot\nbind = SUPER, N, exec, notes\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let rows = controller.compare_with(&backup_path).unwrap();
+    let statuses: Vec<RowStatus> = rows.iter().map(|row| row.status()).collect();
+    assert_eq!(
+        statuses,
+        vec![RowStatus::Changed, RowStatus::Removed, RowStatus::Added]
+    );
+
+    controller
+        .apply_merge(&rows, &[Side::Right, Side::Left, Side::Right])
+        .unwrap();
+
+    let written = fs::read_to_string(&config_path).unwrap();
+    assert!(written.contains("bind = SUPER, K, exec, foot"));
+    assert!(written.contains("bind = SUPER, Q, killactive"));
+    assert!(written.contains("bind = SUPER, N, exec, notes"));
+    assert_eq!(controller.keybinding_count(), 3);
+
+    controller.undo().unwrap();
+    assert!(fs::read_to_string(&config_path)
+        .unwrap()
+        .contains("bind = SUPER, K, exec, kitty"));
+}
+
+#[test]
+fn test_set_binds_option_updates_config_and_details() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER, L, movefocus, r\n\nbinds {\n    movefocus_cycles_fullscreen = false\n}\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+    let binding = controller.get_keybindings()[0].clone();
+
+    let value_of = |name: &str| {
+        controller
+            .get_relevant_binds_options(&binding)
+            .into_iter()
+            .find(|(option, _)| option.name == name)
+            .map(|(_, value)| value)
+    };
+    assert_eq!(
+        value_of("movefocus_cycles_fullscreen").as_deref(),
+        Some("false")
+    );
+
+    controller
+        .set_binds_option("movefocus_cycles_fullscreen", "true")
+        .unwrap();
+    assert_eq!(
+        value_of("movefocus_cycles_fullscreen").as_deref(),
+        Some("true")
+    );
+    assert!(fs::read_to_string(&config_path)
+        .unwrap()
+        .contains("    movefocus_cycles_fullscreen = true\n"));
+
+    assert!(controller
+        .set_binds_option("movefocus_cycles_fullscreen", "maybe")
+        .is_err());
+}
+
+#[test]
+fn test_submap_warning_for_bindings_of_a_trapping_submap() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER, R, submap, trap\nsubmap = trap\nbind = , T, exec, kitty\nsubmap = reset\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+    let bindings = controller.get_keybindings();
+
+    assert_eq!(controller.get_submap_warnings().len(), 1);
+    assert_eq!(controller.get_submap_warning_for(&bindings[0]), None);
+    assert_eq!(
+        controller.get_submap_warning_for(&bindings[1]),
+        Some(SubmapWarning::NoEscape {
+            submap: "trap".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_merge_duplicates_removes_formatting_copies_in_one_write() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER SHIFT, Return, exec, kitty --single-instance\n\
+         bind = SUPER, Q, killactive\n\
+         bind = SHIFT SUPER, enter, exec, \"kitty\"   --single-instance\n\
+         bind = SUPER SHIFT, RETURN, exec, kitty '--single-instance'\n",
+    )
+    .unwrap();
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+
+    assert_eq!(controller.find_redundant_copies().len(), 2);
+    assert_eq!(controller.merge_duplicates(), Ok(2));
+
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "bind = SUPER SHIFT, Return, exec, kitty --single-instance\n\
+         bind = SUPER, Q, killactive\n"
+    );
+    assert_eq!(controller.keybinding_count(), 2);
+    assert!(controller.find_redundant_copies().is_empty());
+    assert_eq!(controller.merge_duplicates(), Ok(0));
+
+    // A single undo step brings every copy back
+    controller.undo().unwrap();
+    assert_eq!(controller.keybinding_count(), 4);
+}
+
+#[test]
+fn test_conflict_suggestions_move_a_clashing_binding() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let conflict = controller
+        .get_conflicts()
+        .into_iter()
+        .find(|conflict| conflict.key_combo == KeyCombo::new(vec![Modifier::Super], "K"))
+        .unwrap();
+    let suggestions = controller.suggest_for_conflict(&conflict, 1);
+    assert_eq!(
+        suggestions,
+        vec![KeyCombo::new(vec![Modifier::Super, Modifier::Shift], "K")]
+    );
+
+    let mut moved = conflict.conflicting_bindings[1].clone();
+    moved.key_combo = suggestions[0].clone();
+    controller
+        .update_keybinding(&conflict.conflicting_bindings[1], moved)
+        .unwrap();
+    assert_eq!(controller.conflict_count(), 0);
+}
+
+#[test]
+fn test_reserved_check_can_be_toggled_and_survives_edits() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(&config_path, "bind = CTRL ALT, F2, exec, kitty\n").unwrap();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let binding = controller.get_keybindings()[0].clone();
+    assert!(controller.is_reserved_check_enabled());
+    assert_eq!(controller.get_reserved_shadows().len(), 1);
+    assert!(controller.get_reserved_shadow_for(&binding).is_some());
+
+    controller.set_reserved_check(false);
+    assert!(controller.get_reserved_shadows().is_empty());
+    assert!(controller.get_reserved_shadow_for(&binding).is_none());
+
+    // Rebuilding the conflict detector keeps the setting
+    let mut edited = binding.clone();
+    edited.args = Some("firefox".to_string());
+    controller.update_keybinding(&binding, edited).unwrap();
+    assert!(controller.get_reserved_shadows().is_empty());
+}
+
+#[test]
+fn test_conflict_preview_classifies_the_edited_binding() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let kitty = controller
+        .get_keybindings()
+        .into_iter()
+        .find(|binding| binding.args.as_deref() == Some("kitty"))
+        .unwrap();
+
+    // Adding the same binding again runs kitty twice
+    let preview = controller.get_conflict_preview(&kitty, None);
+    assert_eq!(preview.bindings, std::slice::from_ref(&kitty));
+    assert_eq!(preview.kind, Some(ConflictKind::RedundantDuplicate));
+
+    // The binding being edited does not conflict with itself
+    let preview = controller.get_conflict_preview(&kitty, Some(&kitty));
+    assert!(preview.bindings.is_empty());
+    assert_eq!(preview.kind, None);
+
+    let mut on_release = kitty.clone();
+    on_release.bind_type = BindType::Bind.with(BindFlag::Release);
+    on_release.args = Some("wofi".to_string());
+    assert_eq!(
+        controller.get_conflict_preview(&on_release, None).kind,
+        Some(ConflictKind::PressAndRelease)
+    );
+
+    let mut firefox = kitty.clone();
+    firefox.key_combo = KeyCombo::new(vec![Modifier::Super], "K");
+    firefox.args = Some("thunderbird".to_string());
+    let preview = controller.get_conflict_preview(&firefox, None);
+    assert_eq!(preview.bindings.len(), 2);
+    assert_eq!(preview.kind, Some(ConflictKind::MultipleActions));
+    assert!(preview.reserved.is_none());
+
+    let mut reserved = kitty.clone();
+    reserved.key_combo = KeyCombo::new(vec![Modifier::Ctrl, Modifier::Alt], "F2");
+    let preview = controller.get_conflict_preview(&reserved, None);
+    assert!(preview.bindings.is_empty());
+    assert!(preview.reserved.is_some());
+}
+
+#[test]
+fn test_duplicate_action_filter_narrows_the_view() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER, Return, exec, kitty\n\
+         bind = SUPER, T, exec, kitty\n\
+         bind = SUPER, F, exec, firefox\n",
+    )
+    .unwrap();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    assert_eq!(controller.get_duplicate_actions().len(), 1);
+    assert!(!controller.is_duplicate_actions_only());
+    assert_eq!(controller.get_current_view().len(), 3);
+
+    controller.set_duplicate_actions_only(true);
+    assert_eq!(controller.get_current_view().len(), 2);
+
+    // Combines with the search query
+    controller.set_search_query("SUPER+T".to_string());
+    let view = controller.get_current_view();
+    assert_eq!(view.len(), 1);
+    assert_eq!(view[0].key_combo.to_string(), "SUPER+T");
+
+    controller.set_search_query(String::new());
+    controller.set_duplicate_actions_only(false);
+    assert_eq!(controller.get_current_view().len(), 3);
+}
+
+#[test]
+fn test_scope_preview_filters_view_and_export() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER, Return, exec, kitty\n\
+         # scope: laptop\n\
+         bind = , XF86MonBrightnessUp, exec, brightnessctl s +5%\n\
+         # scope: external-kb\n\
+         bind = SUPER, F1, exec, pavucontrol\n",
+    )
+    .unwrap();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    assert_eq!(controller.get_scopes(), ["external-kb", "laptop"]);
+    let bindings = controller.get_keybindings();
+    assert_eq!(controller.get_scope_for(&bindings[0]), None);
+    assert_eq!(
+        controller.get_scope_for(&bindings[2]).as_deref(),
+        Some("external-kb")
+    );
+    assert_eq!(controller.get_current_view().len(), 3);
+
+    controller.set_scope(Some("laptop".to_string()));
+    let view = controller.get_current_view();
+    assert_eq!(view.len(), 2);
+    assert!(view
+        .iter()
+        .all(|binding| binding.args.as_deref() != Some("pavucontrol")));
+
+    let export_path = temp_dir.path().join("laptop.conf");
+    controller
+        .export_to(&export_path, ExportFormat::Hyprland)
+        .unwrap();
+    let exported = fs::read_to_string(&export_path).unwrap();
+    assert!(exported.contains("brightnessctl"));
+    assert!(!exported.contains("pavucontrol"));
+
+    let sheet_path = temp_dir.path().join("laptop.html");
+    controller
+        .export_cheat_sheet(&sheet_path, CheatSheetFormat::Html)
+        .unwrap();
+    let sheet = fs::read_to_string(&sheet_path).unwrap();
+    assert!(sheet.contains("brightnessctl"));
+    assert!(!sheet.contains("pavucontrol"));
+
+    controller.set_scope(None);
+    assert_eq!(controller.get_current_view().len(), 3);
+}
+
+#[test]
+fn test_imports_are_reviewed_before_they_are_written() {
+    let (temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+    let before = controller.get_keybindings().len();
+
+    let import_path = temp_dir.path().join("import.conf");
+    fs::write(
+        &import_path,
+        "bind = SUPER, F9, exec, pavucontrol\n\
+         bind = SUPER, F10, exec, chmod 777 file.txt\n\
+         bind = SUPER, F11, exec, rm -rf /\n",
+    )
+    .unwrap();
+
+    // Reviewing changes nothing
+    let review = controller.review_import(&import_path, None).unwrap();
+    assert!(review.needs_acknowledgement());
+    assert_eq!(review.blocked().count(), 1);
+    assert!(!fs::read_to_string(&config_path)
+        .unwrap()
+        .contains("pavucontrol"));
+    assert!(!controller.can_undo());
+
+    // Without acknowledgement only the safe binding is added
+    let added = controller
+        .import_reviewed(&review, ImportMode::Merge, false)
+        .unwrap();
+    assert_eq!(added, 1);
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(content.contains("pavucontrol"));
+    assert!(!content.contains("chmod"));
+    assert!(!content.contains("rm -rf"));
+
+    // Acknowledging adds the dangerous one, never the blocked one
+    controller.undo().unwrap();
+    let added = controller
+        .import_reviewed(&review, ImportMode::Merge, true)
+        .unwrap();
+    assert_eq!(added, 2);
+    assert_eq!(controller.get_keybindings().len(), before + 2);
+    assert!(!fs::read_to_string(&config_path).unwrap().contains("rm -rf"));
+}
+
+#[test]
+fn test_structured_imports_report_broken_entries_and_review_the_rest() {
+    let (temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+
+    // Detected from the extension: one good entry, one that is not a
+    // binding, one that would inject a line through its submap
+    let import_path = temp_dir.path().join("import.yml");
+    fs::write(
+        &import_path,
+        "bindings:\n\
+         - key_combo: {modifiers: [Super], key: F9}\n\
+         \x20 bind_type: bind\n\
+         \x20 dispatcher: exec\n\
+         \x20 args: pavucontrol\n\
+         - key_combo: {modifiers: [Super], key: F10}\n\
+         \x20 bind_type: bind\n\
+         - key_combo: {modifiers: [Super], key: F11}\n\
+         \x20 bind_type: bind\n\
+         \x20 dispatcher: killactive\n\
+         \x20 submap: \"x\\nexec-once = evil\"\n",
+    )
+    .unwrap();
+
+    let review = controller.review_import(&import_path, None).unwrap();
+    assert_eq!(review.unreadable.len(), 1);
+    assert!(review.unreadable[0].starts_with("Binding 2:"));
+    assert_eq!(review.blocked().count(), 1);
+
+    let added = controller
+        .import_reviewed(&review, ImportMode::Merge, false)
+        .unwrap();
+    assert_eq!(added, 1);
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(content.contains("bind = SUPER, F9, exec, pavucontrol"));
+    assert!(!content.contains("evil"));
+
+    // An explicit format overrides the extension
+    assert!(controller
+        .review_import(&import_path, Some(ExportFormat::Json))
+        .is_err());
+}
+
+#[test]
+fn test_sway_configs_are_translated_and_reviewed() {
+    let (temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let sway_path = temp_dir.path().join("config");
+    fs::write(
+        &sway_path,
+        "set $mod Mod4\n\
+         bindsym $mod+F9 exec pavucontrol\n\
+         bindsym $mod+F10 exec rm -rf /\n\
+         bindsym $mod+F11 focus parent\n",
+    )
+    .unwrap();
+
+    let review = controller
+        .review_foreign_import(&sway_path, ForeignConfig::Sway)
+        .unwrap();
+    assert_eq!(review.unreadable.len(), 1);
+    assert_eq!(review.blocked().count(), 1);
+
+    let added = controller
+        .import_reviewed(&review, ImportMode::Merge, false)
+        .unwrap();
+    assert_eq!(added, 1);
+    assert!(fs::read_to_string(&config_path)
+        .unwrap()
+        .contains("bind = SUPER, F9, exec, pavucontrol"));
+
+    fs::write(&sway_path, "# nothing bound\n").unwrap();
+    assert!(controller
+        .review_foreign_import(&sway_path, ForeignConfig::Sway)
+        .is_err());
+}
+
+#[test]
+fn test_pasted_text_without_bind_lines_is_refused() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    assert!(controller.review_paste("just some text\n").is_err());
+
+    let review = controller
+        .review_paste("# from a forum post\nbind = SUPER, F9, exec, pavucontrol\n")
+        .unwrap();
+    assert_eq!(review.pending(false).len(), 1);
+}
+
+/// Helper: Loads a controller over a config with two conflicts
+fn create_two_conflict_controller() -> (TempDir, PathBuf, Controller) {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER, K, exec, firefox\n\
+         bind = SUPER, M, exec, kitty\n\
+         bind = SUPER, K, exec, chrome\n\
+         bind = SUPER, M, exec, alacritty\n\
+         bind = SUPER, K, exec, brave\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+    (temp_dir, config_path, controller)
+}
+
+#[test]
+fn test_resolve_conflict_keeps_first_or_last() {
+    let (_temp_dir, config_path, controller) = create_two_conflict_controller();
+    let conflict = controller
+        .get_conflicts()
+        .into_iter()
+        .find(|conflict| conflict.key_combo.to_string() == "SUPER+K")
+        .unwrap();
+
+    controller
+        .resolve_conflict(&conflict, ConflictResolution::KeepLast)
+        .unwrap();
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(content.contains("brave"));
+    assert!(!content.contains("firefox"));
+    assert!(!content.contains("chrome"));
+    assert_eq!(controller.conflict_count(), 1);
+
+    controller.undo().unwrap();
+    controller
+        .resolve_conflict(&conflict, ConflictResolution::KeepFirst)
+        .unwrap();
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(content.contains("firefox"));
+    assert!(!content.contains("chrome"));
+    assert!(!content.contains("brave"));
+}
+
+#[test]
+fn test_resolve_conflict_rebinds_or_deletes_all() {
+    let (_temp_dir, config_path, controller) = create_two_conflict_controller();
+    let conflict = controller
+        .get_conflicts()
+        .into_iter()
+        .find(|conflict| conflict.key_combo.to_string() == "SUPER+M")
+        .unwrap();
+    assert_eq!(
+        conflict.conflicting_bindings[1].args.as_deref(),
+        Some("alacritty")
+    );
+
+    // Moving onto a bound combo is refused without writing
+    let taken = controller.resolve_conflict(
+        &conflict,
+        ConflictResolution::Rebind {
+            index: 1,
+            to: KeyCombo::new(vec![Modifier::Super], "K"),
+        },
+    );
+    assert!(taken.is_err());
+    assert!(!controller.can_undo());
+
+    controller
+        .resolve_conflict(
+            &conflict,
+            ConflictResolution::Rebind {
+                index: 1,
+                to: KeyCombo::new(vec![Modifier::Super], "A"),
+            },
+        )
+        .unwrap();
+    assert!(fs::read_to_string(&config_path)
+        .unwrap()
+        .contains("bind = SUPER, A, exec, alacritty"));
+    assert_eq!(controller.conflict_count(), 1);
+
+    let conflict = controller.get_conflicts().remove(0);
+    controller
+        .resolve_conflict(&conflict, ConflictResolution::DeleteAll)
+        .unwrap();
+    assert_eq!(controller.conflict_count(), 0);
+    assert_eq!(controller.keybinding_count(), 2);
+}
+
+#[test]
+fn test_resolve_conflicts_is_one_write_and_one_undo_step() {
+    let (_temp_dir, config_path, controller) = create_two_conflict_controller();
+    let original = fs::read_to_string(&config_path).unwrap();
+    // The first write also takes the pristine backup
+    let timestamped = |controller: &Controller| {
+        controller
+            .list_backups()
+            .unwrap()
+            .iter()
+            .filter(|path| path.extension().is_none_or(|ext| ext != "pristine"))
+            .count()
+    };
+    let backups_before = timestamped(&controller);
+
+    let resolutions: Vec<_> = controller
+        .get_conflicts()
+        .into_iter()
+        .map(|conflict| (conflict, ConflictResolution::KeepFirst))
+        .collect();
+    assert_eq!(resolutions.len(), 2);
+    controller.resolve_conflicts(&resolutions).unwrap();
+
+    assert_eq!(controller.conflict_count(), 0);
+    assert_eq!(controller.keybinding_count(), 2);
+    assert_eq!(timestamped(&controller), backups_before + 1);
+
+    controller.undo().unwrap();
+    assert!(!controller.can_undo());
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+
+    // Stale conflicts are refused rather than half applied
+    controller.resolve_conflicts(&resolutions).unwrap();
+    assert!(controller.resolve_conflicts(&resolutions).is_err());
+}
+
+#[test]
+fn test_observers_hear_about_loads_and_writes() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+
+    let binding_counts = Rc::new(RefCell::new(Vec::new()));
+    let conflict_counts = Rc::new(RefCell::new(Vec::new()));
+    let diagnostic_counts = Rc::new(RefCell::new(Vec::new()));
+    let seen = binding_counts.clone();
+    controller.on_bindings_changed(move |bindings| seen.borrow_mut().push(bindings.len()));
+    let seen = conflict_counts.clone();
+    controller.on_conflicts_changed(move |conflicts| seen.borrow_mut().push(conflicts.len()));
+    let seen = diagnostic_counts.clone();
+    controller.on_diagnostics(move |diagnostics| seen.borrow_mut().push(diagnostics.len()));
+
+    controller.load_keybindings().unwrap();
+    assert_eq!(*binding_counts.borrow(), [5]);
+    assert_eq!(*conflict_counts.borrow(), [1]);
+    assert_eq!(*diagnostic_counts.borrow(), [0]);
+
+    // A free combo changes the bindings but not the conflicts
+    let mut binding = controller.get_keybindings()[1].clone();
+    binding.key_combo = KeyCombo::new(vec![Modifier::Super], "N");
+    controller.add_keybinding(binding.clone()).unwrap();
+    assert_eq!(*binding_counts.borrow(), [5, 6]);
+    assert_eq!(*conflict_counts.borrow(), [1]);
+
+    // Undoing is a change too, and resolving the conflict clears it
+    controller.undo().unwrap();
+    assert_eq!(*binding_counts.borrow(), [5, 6, 5]);
+    let conflict = controller.get_conflicts().remove(0);
+    controller
+        .resolve_conflict(&conflict, ConflictResolution::KeepFirst)
+        .unwrap();
+    assert_eq!(*conflict_counts.borrow(), [1, 0]);
+
+    // Only the load reports diagnostics
+    assert_eq!(*diagnostic_counts.borrow(), [0]);
+}
+
+#[test]
+fn test_dispatcher_picker_and_argument_feedback_use_the_catalogue() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+
+    let offered: Vec<&str> = controller
+        .get_available_dispatchers()
+        .iter()
+        .map(|spec| spec.name)
+        .collect();
+    assert!(offered.contains(&"movefocus"));
+    assert!(offered.contains(&"resizewindow"));
+    if controller.get_hyprland_version().is_none() {
+        assert!(!offered.contains(&"fakefullscreen"));
+    }
+
+    assert_eq!(controller.get_argument_problem("movefocus", "l"), None);
+    assert_eq!(
+        controller.get_argument_problem("resizeactive", "wide"),
+        Some("resizeactive needs two values such as '10 -10' or 'exact 50% 50%'".to_string())
+    );
+}
+
+#[test]
+fn test_layout_warning_for_letter_bindings_with_a_cyrillic_layout() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "input {\n    kb_layout = us,ru\n}\nbind = SUPER, Q, killactive\nbind = SUPER, F1, exec, kitty\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+    let bindings = controller.get_keybindings();
+
+    assert_eq!(controller.get_layout_warnings().len(), 1);
+    assert_eq!(
+        controller
+            .get_layout_warning_for(&bindings[0])
+            .map(|warning| warning.missing_from),
+        Some(vec!["ru".to_string()])
+    );
+    assert_eq!(controller.get_layout_warning_for(&bindings[1]), None);
+}
+
+#[test]
+fn test_missing_program_for_exec_bindings() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER, X, exec, /nonexistent/bin/launcher --fast\n\
+         bind = SUPER, Q, killactive\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+    let bindings = controller.get_keybindings();
+
+    assert_eq!(
+        controller
+            .get_missing_program_for(&bindings[0])
+            .map(|missing| missing.target.to_string()),
+        Some("'/nonexistent/bin/launcher' does not exist or is not executable".to_string())
+    );
+    assert_eq!(controller.get_missing_program_for(&bindings[1]), None);
+}
+
+#[test]
+fn test_load_skips_unparseable_lines_and_reports_them() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "bind = SUPER, K, exec, kitty\nbindx = SUPER, Q, killactive\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    let reported = Rc::new(RefCell::new(Vec::new()));
+    let reported_clone = reported.clone();
+    controller.on_diagnostics(move |diagnostics| {
+        *reported_clone.borrow_mut() = diagnostics.to_vec();
+    });
+
+    assert_eq!(controller.load_keybindings().unwrap(), 1);
+
+    let diagnostics = reported.borrow();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].file, Some(config_path));
+    assert_eq!(diagnostics[0].line, 2);
+}
+
+#[test]
+fn test_categories_filter_the_view_and_survive_edits() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        &config_path,
+        "# @category: media\n\
+         bind = SUPER, P, exec, playerctl play-pause\n\
+         # @category: launchers\n\
+         bind = SUPER, Return, exec, kitty\n\
+         bind = SUPER, Q, killactive\n",
+    )
+    .unwrap();
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+
+    assert_eq!(controller.get_categories(), ["launchers", "media"]);
+    controller.set_category_filter(Some("media".to_string()));
+    let view = controller.get_current_view();
+    assert_eq!(view.len(), 1);
+    assert_eq!(view[0].args.as_deref(), Some("playerctl play-pause"));
+    controller.set_category_filter(None);
+    assert_eq!(controller.get_current_view().len(), 3);
+
+    // Editing the action keeps the annotation; tagging adds one
+    let bindings = controller.get_keybindings();
+    let mut edited = bindings[1].clone();
+    edited.args = Some("foot".to_string());
+    controller.update_keybinding(&bindings[1], edited).unwrap();
+    let mut tagged = bindings[2].clone();
+    tagged.category = Some("windows".to_string());
+    controller.update_keybinding(&bindings[2], tagged).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "# @category: media\n\
+         bind = SUPER, P, exec, playerctl play-pause\n\
+         # @category: launchers\n\
+         bind = SUPER, RETURN, exec, foot\n\
+         # @category: windows\n\
+         bind = SUPER, Q, killactive\n"
+    );
+}
+
+#[test]
+fn test_profiles_switch_the_config_and_can_be_undone() {
+    use crate::config::profiles::ProfileStore;
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    fs::write(
+        temp_dir.path().join("extra.conf"),
+        "bind = SUPER, E, exec, thunar\n",
+    )
+    .unwrap();
+    let work_config = "source = ./extra.conf\n\
+                       bind = SUPER, T, exec, kitty\n\
+                       bind = SUPER, M, exec, slack\n";
+    fs::write(&config_path, work_config).unwrap();
+    let store = ProfileStore::new(temp_dir.path().join("profiles"));
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+    controller.save_profile(&store, "work").unwrap();
+
+    let bindings = controller.get_keybindings();
+    let mut gaming = bindings[1].clone();
+    gaming.args = Some("steam".to_string());
+    controller.update_keybinding(&bindings[1], gaming).unwrap();
+    controller.delete_keybinding(&bindings[2]).unwrap();
+    controller.save_profile(&store, "gaming").unwrap();
+    assert_eq!(
+        controller.list_profiles(&store).unwrap(),
+        ["gaming", "work"]
+    );
+
+    let diff = controller
+        .diff_profiles(&store, "gaming", Some("work"))
+        .unwrap();
+    let statuses: Vec<_> = diff.iter().map(|row| row.status()).collect();
+    assert_eq!(statuses, [RowStatus::Changed, RowStatus::Added]);
+    assert!(controller
+        .diff_profiles(&store, "gaming", None)
+        .unwrap()
+        .iter()
+        .all(|row| row.status() == RowStatus::Unchanged));
+
+    controller.apply_profile(&store, "work").unwrap();
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(content.contains("source = ./extra.conf"));
+    assert!(content.contains("bind = SUPER, T, exec, kitty"));
+    assert!(content.contains("bind = SUPER, M, exec, slack"));
+    assert!(!content.contains("steam"));
+    assert_eq!(
+        controller.keybinding_count(),
+        3,
+        "sourced bindings are kept"
+    );
+
+    controller.undo().unwrap();
+    assert!(fs::read_to_string(&config_path).unwrap().contains("steam"));
+    assert!(controller.apply_profile(&store, "laptop").is_err());
+}
+
+#[test]
+fn test_edits_of_read_only_sourced_bindings_are_overridden_in_the_main_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    let defaults = "bind = SUPER, Q, killactive\nbind = SUPER, E, exec, thunar\n";
+    fs::write(temp_dir.path().join("defaults.conf"), defaults).unwrap();
+    fs::write(
+        temp_dir.path().join("extra.conf"),
+        "bind = SUPER, X, exec, xeyes\n",
+    )
+    .unwrap();
+    fs::write(
+        &config_path,
+        "# @read-only\nsource = ./defaults.conf\nsource = ./extra.conf\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+    let bindings = controller.get_keybindings();
+
+    let mut edited = bindings[0].clone();
+    edited.dispatcher = "exec".to_string();
+    edited.args = Some("kitty".to_string());
+    controller.update_keybinding(&bindings[0], edited).unwrap();
+    controller.delete_keybinding(&bindings[1]).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("defaults.conf")).unwrap(),
+        defaults,
+        "the read-only file is untouched"
+    );
+    assert!(fs::read_to_string(&config_path)
+        .unwrap()
+        .ends_with("unbind = SUPER, Q\nbind = SUPER, Q, exec, kitty\n\nunbind = SUPER, E\n"));
+    let loaded = controller.get_keybindings();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].args.as_deref(), Some("xeyes"));
+    assert_eq!(loaded[1].args.as_deref(), Some("kitty"));
+    assert_eq!(loaded[1].source_file, None);
+
+    let error = controller
+        .delete_keybinding(&controller.get_keybindings()[0])
+        .unwrap_err();
+    assert!(
+        error.contains("@read-only"),
+        "other sourced files are still refused: {error}"
+    );
+}
+
+#[test]
+fn test_previews_show_the_edit_without_writing_it() {
+    let (_temp_dir, config_path) = create_test_config();
+    let original = fs::read_to_string(&config_path).unwrap();
+    let controller = Controller::new(config_path.clone()).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let old = controller.get_keybindings()[1].clone();
+    let mut new = old.clone();
+    new.args = Some("alacritty".to_string());
+    let diff = controller.preview_update(&old, &new).unwrap();
+    let changed: Vec<_> = diff
+        .hunks()
+        .iter()
+        .flat_map(|hunk| &hunk.lines)
+        .filter(|line| line.change != LineChange::Unchanged)
+        .map(|line| line.text.as_str())
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            "bind = SUPER, M, exec, kitty",
+            "bind = SUPER, M, exec, alacritty"
+        ]
+    );
+
+    let mut added = old.clone();
+    added.key_combo = KeyCombo::new(vec![Modifier::Super], "B");
+    added.location = None;
+    let diff = controller.preview_add(&added).unwrap();
+    assert_eq!((diff.removed(), diff.added()), (0, 1));
+
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+    assert!(!controller.can_undo());
+    assert!(controller.reviews_changes());
+}
+
+#[test]
+fn test_preview_restore_shows_what_the_backup_would_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("hyprland.conf");
+    let backup_path = temp_dir.path().join("hyprland.conf.backup");
+    fs::write(
+        &config_path,
+        "bind = SUPER, K, exec, kitty\nbind = SUPER, Q, killactive\n",
+    )
+    .unwrap();
+    fs::write(
+        &backup_path,
+        "bind = SUPER, K, exec, foot\nbind = SUPER, Q, killactive\n",
+    )
+    .unwrap();
+
+    let controller = Controller::new(config_path.clone()).unwrap();
+    let diff = controller.preview_restore(&backup_path).unwrap();
+
+    let changed: Vec<(LineChange, &str)> = diff.hunks()[0]
+        .lines
+        .iter()
+        .map(|line| (line.change, line.text.as_str()))
+        .filter(|(change, _)| *change != LineChange::Unchanged)
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            (LineChange::Removed, "bind = SUPER, K, exec, kitty"),
+            (LineChange::Added, "bind = SUPER, K, exec, foot"),
+        ]
+    );
+    assert!(
+        fs::read_to_string(&config_path).unwrap().contains("kitty"),
+        "previewing writes nothing"
+    );
+    assert!(controller
+        .preview_restore(&temp_dir.path().join("missing"))
+        .is_err());
+}
+
+#[test]
+fn test_statistics_follow_the_loaded_bindings() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let stats = controller.get_statistics();
+    assert_eq!(stats.total, 5);
+    assert_eq!(stats.by_dispatcher[0].name, "exec");
+    assert_eq!(stats.by_dispatcher[0].count, 4);
+    // SUPER+K is bound twice but is one key of the SUPER layer
+    assert_eq!(stats.modifier_space[0].used, 4);
+}
+
+#[test]
+fn test_lint_findings_follow_the_policy() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    // Descriptions are not linted by default
+    assert!(controller.get_lint_findings().is_empty());
+
+    controller.set_lint_policy(LintPolicy::parse("missing-description = warn\n"));
+    let findings = controller.get_lint_findings();
+    // Both SUPER+K bindings have a comment above them
+    assert_eq!(findings.len(), 3);
+    assert!(findings
+        .iter()
+        .all(|finding| finding.rule == LintRule::MissingDescription));
+}
+
+#[test]
+fn test_danger_policy_preference_blocks_new_bindings() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "I"),
+        "exec",
+        Some("iptables -F"),
+    );
+
+    controller.set_settings(Settings {
+        danger_policy: DangerPolicy::Dangerous,
+        ..Settings::default()
+    });
+    assert!(controller.add_keybinding(binding.clone()).is_err());
+    assert_eq!(controller.keybinding_count(), 5);
+
+    controller.set_settings(Settings::default());
+    assert!(controller.add_keybinding(binding).is_ok());
+    assert_eq!(controller.keybinding_count(), 6);
+}
+
+#[test]
+fn test_danger_policy_override_allows_one_save() {
+    let (_temp_dir, config_path) = create_test_config();
+    let controller = Controller::new(config_path).unwrap();
+    controller.load_keybindings().unwrap();
+
+    let binding = Keybinding::new(
+        KeyCombo::new(vec![Modifier::Super], "W"),
+        "exec",
+        Some("rm -rf /"),
+    );
+    let assessment = controller
+        .assess_exec_args(&binding.dispatcher, "rm -rf /")
+        .unwrap();
+    assert_eq!(assessment.danger_level, DangerLevel::Critical);
+    assert!(controller.is_danger_blocked(assessment.danger_level));
+    assert!(controller.assess_exec_args("exec", "  ").is_none());
+    assert!(controller
+        .assess_exec_args("workspace", "rm -rf /")
+        .is_none());
+
+    // An override covers only the binding it was given
+    let mut other = binding.clone();
+    other.key_combo = KeyCombo::new(vec![Modifier::Super], "E");
+    controller.override_danger_policy(&other);
+    assert!(controller.add_keybinding(binding.clone()).is_err());
+
+    controller.override_danger_policy(&binding);
+    assert!(controller.add_keybinding(binding.clone()).is_ok());
+    assert_eq!(controller.keybinding_count(), 6);
+
+    // ... and only once
+    assert!(controller.add_keybinding(binding).is_err());
+    assert_eq!(controller.keybinding_count(), 6);
+}
@@ -16,6 +16,7 @@
 //!
 //! Contains test suites for GTK4 user interface components:
 //! - Controller tests (MVC controller behaviour, state management)
+//! - Undo tests (undo/redo history bookkeeping)
 
 #[cfg(test)]
 mod controller_tests;
@@ -25,3 +26,6 @@ mod backup_dialog_tests;
 
 #[cfg(test)]
 mod layout_tests;
+
+#[cfg(test)]
+mod undo_tests;
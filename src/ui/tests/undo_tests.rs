@@ -0,0 +1,91 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::core::{parser::parse_config_file, Keybinding};
+use crate::ui::undo::UndoManager;
+
+fn bindings(config: &str) -> Vec<Keybinding> {
+    parse_config_file(config, Path::new("")).unwrap()
+}
+
+#[test]
+fn test_undo_and_redo_walk_through_the_history() {
+    let first = bindings("bind = SUPER, Q, killactive\n");
+    let second = bindings("bind = SUPER, Q, killactive\nbind = SUPER, E, exec, nautilus\n");
+    let third = bindings("bind = SUPER, E, exec, nautilus\n");
+
+    let mut history = UndoManager::new(20);
+    history.record(&first);
+    history.record(&second);
+
+    assert_eq!(history.undo(&third), Some(second.clone()));
+    assert_eq!(history.undo(&second), Some(first.clone()));
+    assert_eq!(history.undo(&first), None);
+    assert!(!history.can_undo());
+
+    assert_eq!(history.redo(&first), Some(second.clone()));
+    assert_eq!(history.redo(&second), Some(third));
+    assert_eq!(history.redo(&[]), None);
+    assert!(history.can_undo());
+}
+
+#[test]
+fn test_recording_a_change_clears_the_redo_history() {
+    let before = bindings("bind = SUPER, Q, killactive\n");
+    let after = bindings("bind = SUPER, W, killactive\n");
+
+    let mut history = UndoManager::new(20);
+    history.record(&before);
+    history.undo(&after).unwrap();
+    assert!(history.can_redo());
+
+    history.record(&before);
+    assert!(!history.can_redo());
+}
+
+#[test]
+fn test_redo_reverses_an_undo_whose_write_failed() {
+    let before = bindings("bind = SUPER, Q, killactive\n");
+    let after = bindings("bind = SUPER, W, killactive\n");
+
+    let mut history = UndoManager::new(20);
+    history.record(&before);
+
+    // The caller could not write `previous`: put the step back
+    let previous = history.undo(&after).unwrap();
+    history.redo(&previous).unwrap();
+
+    assert!(!history.can_redo());
+    assert_eq!(history.undo(&after), Some(before));
+}
+
+#[test]
+fn test_history_keeps_the_newest_steps_up_to_its_limit() {
+    let mut history = UndoManager::new(2);
+    for key in ["A", "B", "C"] {
+        history.record(&bindings(&format!("bind = SUPER, {}, killactive\n", key)));
+    }
+
+    assert_eq!(
+        history.discard_last(),
+        Some(bindings("bind = SUPER, C, killactive\n"))
+    );
+    assert_eq!(
+        history.discard_last(),
+        Some(bindings("bind = SUPER, B, killactive\n"))
+    );
+    assert_eq!(history.discard_last(), None);
+}
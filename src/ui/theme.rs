@@ -0,0 +1,110 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Theming layer
+//!
+//! Loads the application stylesheet and applies the accessibility
+//! preferences on top of it:
+//! - **High contrast**: `high_contrast.css` is layered over `style.css`
+//!   at a higher priority, and removed again when switched off
+//! - **Reduced motion**: GTK's `gtk-enable-animations` setting is turned
+//!   off, which stops revealer slides and CSS transitions everywhere.
+//!   Switching it back on restores the system setting rather than forcing
+//!   animations on
+//! - **Colour scheme**: GTK's `gtk-application-prefer-dark-theme` setting
+//!   is forced on or off, or restored to the system setting
+
+use std::cell::Cell;
+
+use gtk4::{gdk, CssProvider};
+
+use crate::config::{
+    paths::RuntimePaths,
+    settings::{ColorScheme, Settings},
+};
+
+/// Application styling with switchable accessibility overrides
+pub struct Theme {
+    display: gdk::Display,
+    high_contrast: CssProvider,
+    high_contrast_loaded: Cell<bool>,
+    /// `gtk-enable-animations` as the system set it at startup
+    system_animations: bool,
+    /// `gtk-application-prefer-dark-theme` as the system set it at startup
+    system_prefers_dark: bool,
+}
+
+impl Theme {
+    /// Loads the stylesheet for the default display and applies preferences
+    ///
+    /// # Returns
+    ///
+    /// `None` if there is no display to connect to
+    pub fn install(settings: Settings) -> Option<Self> {
+        let display = gdk::Display::default()?;
+
+        if let Some(icons) = RuntimePaths::from_env().icon_dir() {
+            gtk4::IconTheme::for_display(&display).add_search_path(icons);
+        }
+
+        let base = CssProvider::new();
+        base.load_from_string(include_str!("style.css"));
+        gtk4::style_context_add_provider_for_display(
+            &display,
+            &base,
+            gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
+        );
+
+        let high_contrast = CssProvider::new();
+        high_contrast.load_from_string(include_str!("high_contrast.css"));
+
+        let gtk_settings = gtk4::Settings::for_display(&display);
+        let system_animations = gtk_settings.is_gtk_enable_animations();
+        let system_prefers_dark = gtk_settings.is_gtk_application_prefer_dark_theme();
+
+        let theme = Self {
+            display,
+            high_contrast,
+            high_contrast_loaded: Cell::new(false),
+            system_animations,
+            system_prefers_dark,
+        };
+        theme.apply(settings);
+        Some(theme)
+    }
+
+    /// Applies preferences to every window on the display
+    pub fn apply(&self, settings: Settings) {
+        if settings.high_contrast != self.high_contrast_loaded.get() {
+            if settings.high_contrast {
+                gtk4::style_context_add_provider_for_display(
+                    &self.display,
+                    &self.high_contrast,
+                    gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION + 1,
+                );
+            } else {
+                gtk4::style_context_remove_provider_for_display(&self.display, &self.high_contrast);
+            }
+            self.high_contrast_loaded.set(settings.high_contrast);
+        }
+
+        let gtk_settings = gtk4::Settings::for_display(&self.display);
+        gtk_settings.set_gtk_enable_animations(self.system_animations && !settings.reduce_motion);
+        gtk_settings.set_gtk_application_prefer_dark_theme(match settings.color_scheme {
+            ColorScheme::System => self.system_prefers_dark,
+            ColorScheme::Light => false,
+            ColorScheme::Dark => true,
+        });
+    }
+}
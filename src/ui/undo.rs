@@ -0,0 +1,136 @@
+// Copyright 2025 Eric Jingryd (tidynest@proton.me)
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Undo and redo history of binding changes
+//!
+//! [`UndoManager`] keeps complete snapshots of the bindings rather than
+//! inverse operations: undoing any edit (an add, a merge, a profile switch)
+//! is then the same single write of the previous list. Snapshots are kept
+//! as [`CompactBindings`] sharing one [`Interner`], so unchanged bindings are
+//! stored once across the whole history.
+//!
+//! The manager only keeps the history. Writing a snapshot to the config is
+//! up to the caller, which undoes the bookkeeping when that write fails:
+//!
+//! ```
+//! use hypr_keybind_manager::ui::undo::UndoManager;
+//!
+//! let mut history = UndoManager::new(20);
+//! history.record(&[]);
+//! assert!(history.can_undo());
+//!
+//! // Undo: the recorded bindings come back, the current ones become redoable
+//! let previous = history.undo(&[]).unwrap();
+//! assert!(history.can_redo());
+//!
+//! // Had writing `previous` failed, redoing puts the history back as it was
+//! history.redo(&previous);
+//! assert!(history.can_undo() && !history.can_redo());
+//! ```
+
+use crate::core::{
+    compact::{CompactBindings, Interner},
+    Keybinding,
+};
+
+/// Bounded undo and redo stacks of binding snapshots
+#[derive(Debug)]
+pub struct UndoManager {
+    /// Snapshots to go back to, oldest first
+    undo_stack: Vec<CompactBindings>,
+    /// Snapshots undone, most recently undone last
+    redo_stack: Vec<CompactBindings>,
+    /// Strings and bindings shared by all snapshots
+    interner: Interner,
+    /// Undo steps kept; older ones are dropped
+    limit: usize,
+}
+
+impl UndoManager {
+    /// Creates an empty history keeping at most `limit` undo steps
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            interner: Interner::default(),
+            limit,
+        }
+    }
+
+    /// Records the bindings as they are before a change
+    ///
+    /// A new change makes the redo history meaningless, so it is cleared.
+    pub fn record(&mut self, current: &[Keybinding]) {
+        let snapshot = CompactBindings::new(current, &mut self.interner);
+        self.undo_stack.push(snapshot);
+        if self.undo_stack.len() > self.limit {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+        self.interner.purge();
+    }
+
+    /// Drops the latest recorded snapshot, returning its bindings
+    ///
+    /// Used when the change it was recorded for could not be written.
+    pub fn discard_last(&mut self) -> Option<Vec<Keybinding>> {
+        let snapshot = self.undo_stack.pop()?;
+        Some(self.expand(snapshot))
+    }
+
+    /// Steps back: returns the bindings to restore
+    ///
+    /// `current` becomes the latest redo step. Returns `None`, changing
+    /// nothing, when there is nothing to undo.
+    pub fn undo(&mut self, current: &[Keybinding]) -> Option<Vec<Keybinding>> {
+        let previous = self.undo_stack.pop()?;
+        let current = CompactBindings::new(current, &mut self.interner);
+        self.redo_stack.push(current);
+        Some(self.expand(previous))
+    }
+
+    /// Steps forward again: returns the bindings to restore
+    ///
+    /// `current` becomes the latest undo step. Returns `None`, changing
+    /// nothing, when there is nothing to redo.
+    pub fn redo(&mut self, current: &[Keybinding]) -> Option<Vec<Keybinding>> {
+        let next = self.redo_stack.pop()?;
+        let current = CompactBindings::new(current, &mut self.interner);
+        self.undo_stack.push(current);
+        Some(self.expand(next))
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Forgets every undo and redo step
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.interner.purge();
+    }
+
+    /// Expands a snapshot taken off a stack, freeing what only it used
+    fn expand(&mut self, snapshot: CompactBindings) -> Vec<Keybinding> {
+        let bindings = snapshot.to_bindings();
+        drop(snapshot);
+        self.interner.purge();
+        bindings
+    }
+}